@@ -59,6 +59,26 @@ export async function transcribeAsset(sessionId: string, assetId: string): Promi
   });
 }
 
+// ─── Imported Subtitles ────────────────────────────────────────────────────
+export interface SubtitleCue { index: number; start: number; end: number; text: string }
+export interface CueFix { cue: number; fix: string }
+
+export async function listCues(sessionId: string, assetId: string): Promise<{ cues: SubtitleCue[]; fixes: CueFix[] }> {
+  return request(`/sessions/${sessionId}/assets/${assetId}/cues`);
+}
+
+export async function editCue(
+  sessionId: string,
+  assetId: string,
+  index: number,
+  edit: Partial<Pick<SubtitleCue, 'text' | 'start' | 'end'>>,
+): Promise<SubtitleCue> {
+  return request(`/sessions/${sessionId}/assets/${assetId}/cues/${index}`, {
+    method: 'PATCH',
+    body: JSON.stringify(edit),
+  });
+}
+
 // ─── Project ───────────────────────────────────────────────────────────────
 export async function saveProject(sessionId: string, data: unknown): Promise<void> {
   await request(`/sessions/${sessionId}/project/save`, {
@@ -67,15 +87,22 @@ export async function saveProject(sessionId: string, data: unknown): Promise<voi
   });
 }
 
-export async function loadProject(sessionId: string): Promise<unknown> {
-  return request(`/sessions/${sessionId}/project/load`);
+export async function listProjectHistory(
+  sessionId: string,
+): Promise<{ snapshots: { id: string; savedAt: number; revision: number; size: number }[]; keep: number }> {
+  return request(`/sessions/${sessionId}/project/history`);
+}
+
+export async function loadProject(sessionId: string, snapshot?: string): Promise<unknown> {
+  const query = snapshot ? `?snapshot=${encodeURIComponent(snapshot)}` : '';
+  return request(`/sessions/${sessionId}/project/load${query}`);
 }
 
 // ─── AI ────────────────────────────────────────────────────────────────────
-export async function aiChat(sessionId: string, message: string): Promise<{ response: string; actions?: { type: string; label: string; params: Record<string, unknown> }[] }> {
+export async function aiChat(sessionId: string, message: string, model?: string): Promise<{ response: string; actions?: { type: string; label: string; params: Record<string, unknown> }[] }> {
   return request(`/sessions/${sessionId}/ai/chat`, {
     method: 'POST',
-    body: JSON.stringify({ message }),
+    body: JSON.stringify({ message, model }),
   });
 }
 
@@ -97,3 +124,8 @@ export async function startRender(sessionId: string, projectData: unknown): Prom
 export async function getRenderStatus(sessionId: string): Promise<RenderStatus> {
   return request(`/sessions/${sessionId}/render/status`);
 }
+
+/** The last finished render; `version` busts the player's cache between renders. */
+export function renderOutputUrl(sessionId: string, version: number): string {
+  return `${BASE}/sessions/${sessionId}/render/output?v=${version}`;
+}
@@ -18,6 +18,8 @@ export function Editor() {
     const [showRender, setShowRender] = useState(false);
     const [uploading, setUploading] = useState(false);
     const [editRunning, setEditRunning] = useState(false);
+    const [draftRendering, setDraftRendering] = useState(false);
+    const [draftSrc, setDraftSrc] = useState<string | undefined>(undefined);
 
     // Derive the preview video URL from the main Video clip
     const previewVideoSrc = useMemo(() => {
@@ -28,6 +30,11 @@ export function Editor() {
         return asset?.streamUrl;
     }, [p.clips, p.assets]);
 
+    // A draft preview only shows the arrangement it was rendered from
+    useEffect(() => {
+        setDraftSrc(undefined);
+    }, [p.clips]);
+
     // Handle file upload
     const handleUpload = useCallback(async (file: File) => {
         setUploading(true);
@@ -93,6 +100,34 @@ export function Editor() {
         setShowRender(true);
     }, [p]);
 
+    // Preview render: fast 480p draft of the same cuts, loaded into the player
+    const handlePreviewRender = useCallback(async () => {
+        if (!p.sessionId) return;
+        const sessionId = p.sessionId;
+        const mainAsset = p.assets.find(a => a.type === 'video');
+        setDraftRendering(true);
+        try {
+            await api.startRender(sessionId, {
+                assetId: mainAsset?.id,
+                clips: p.clips,
+                captionData: p.captionData,
+                draft: true,
+            });
+            const poll = setInterval(async () => {
+                const s = await api.getRenderStatus(sessionId);
+                if (s.status === 'done' || s.status === 'error') {
+                    clearInterval(poll);
+                    setDraftRendering(false);
+                    if (s.status === 'done') {
+                        setDraftSrc(api.renderOutputUrl(sessionId, Date.now()));
+                    }
+                }
+            }, 1500);
+        } catch {
+            setDraftRendering(false);
+        }
+    }, [p]);
+
     // Transcribe shortcut
     const handleTranscribeFromProps = useCallback(async (assetId: string) => {
         await p.transcribeAsset(assetId);
@@ -135,6 +170,16 @@ export function Editor() {
 
                 <div className="toolbar-spacer" />
 
+                {/* Draft preview */}
+                <button
+                    className="toolbar-btn"
+                    onClick={handlePreviewRender}
+                    disabled={p.assets.length === 0 || draftRendering}
+                    title="Fast low-res render of the current edit, played in the preview"
+                >
+                    {draftRendering ? '⏳ Rendering preview…' : '👁 Preview render'}
+                </button>
+
                 {/* Export */}
                 <button
                     className="toolbar-export"
@@ -159,7 +204,7 @@ export function Editor() {
 
             {/* ── Preview ── */}
             <PreviewPanel
-                videoSrc={previewVideoSrc}
+                videoSrc={draftSrc ?? previewVideoSrc}
                 isPlaying={p.isPlaying}
                 playheadPosition={p.playheadPosition}
                 duration={p.duration}
@@ -51,6 +51,15 @@ export const DEFAULT_TRANSFORM: ClipTransform = {
   cropTop: 0, cropBottom: 0, cropLeft: 0, cropRight: 0,
 };
 
+export type TransitionType = 'crossfade' | 'dip-to-black';
+
+export interface ClipTransition {
+  type: TransitionType;
+  duration: number;         // seconds, into this clip from the one before it
+}
+
+export type AudioRole = 'music' | 'voice' | 'sfx';
+
 export interface TimelineClip {
   id: string;
   assetId: string;          // '' for caption clips
@@ -62,6 +71,8 @@ export interface TimelineClip {
   transform: ClipTransform;
   speed: number;
   volume: number;
+  transition?: ClipTransition;
+  role?: AudioRole;         // the track's audio role; music ducks under voice
 }
 
 // ─── Captions ──────────────────────────────────────────────────────────────
@@ -166,6 +177,8 @@ export interface RenderStatus {
   status: 'idle' | 'rendering' | 'done' | 'error';
   outputPath?: string;
   error?: string;
+  /** Markdown report of why each region was cut or kept (smart edits). */
+  explanation?: string;
 }
 
 // ─── Right Panel Tabs ──────────────────────────────────────────────────────
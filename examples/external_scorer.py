@@ -0,0 +1,54 @@
+#!/usr/bin/env python3
+"""Example external scorer for the SYNOID smart editor.
+
+SYNOID sends the scenes, transcript and intent as JSON on stdin and expects
+{"scores": [...]} on stdout: one score in 0..1 per scene, in scene order.
+Replace `score_scene` with your own model.
+
+    synoid-core process --input in.mp4 --output out.mp4 \
+        --intent "keep the talking parts" \
+        --external-scorer "python3 examples/external_scorer.py"
+
+The same request can be POSTed to an HTTP endpoint instead; answer with the
+same JSON body.
+"""
+
+import json
+import sys
+
+# Words per second at which speech counts as dense
+DENSE_SPEECH = 2.5
+
+
+def spoken_words(scene, transcript):
+    """Words said during the scene, counted from overlapping segments."""
+    count = 0.0
+    for segment in transcript:
+        overlap = min(scene["end"], segment["end"]) - max(scene["start"], segment["start"])
+        length = segment["end"] - segment["start"]
+        if overlap > 0 and length > 0:
+            count += len(segment["text"].split()) * overlap / length
+    return count
+
+
+def score_scene(scene, transcript, intent):
+    """Nudge the built-in score towards scenes with dense speech."""
+    score = scene["score"]
+    duration = max(scene["duration"], 1e-6)
+    density = spoken_words(scene, transcript) / duration
+    score += 0.3 * min(density / DENSE_SPEECH, 1.0) - 0.1
+    if intent.get("ruthless") and duration > 20:
+        score -= 0.1
+    return min(max(score, 0.0), 1.0)
+
+
+def main():
+    request = json.load(sys.stdin)
+    transcript = request.get("transcript") or []
+    intent = request.get("intent") or {}
+    scores = [score_scene(s, transcript, intent) for s in request["scenes"]]
+    json.dump({"scores": scores}, sys.stdout)
+
+
+if __name__ == "__main__":
+    main()
@@ -6,6 +6,7 @@
 //
 // CLI: synoid-core gemma4 --task "improve smart_editor scene detection"
 
+use crate::agent::engines::proc;
 use reqwest::Client;
 use serde_json::json;
 use std::path::{Path, PathBuf};
@@ -78,9 +79,11 @@ pub struct Gemma4Harness {
 
 impl Gemma4Harness {
     pub fn new(work_dir: &Path, dry_run: bool) -> Self {
-        let ollama_url = std::env::var("SYNOID_API_URL")
-            .unwrap_or_else(|_| "http://localhost:11434".to_string());
-        let model = std::env::var("SYNOID_MODEL").unwrap_or_else(|_| "gemma4:26b".to_string());
+        let crate::config::SynoidConfig {
+            api_url: ollama_url,
+            model,
+            ..
+        } = crate::config::current();
 
         Self {
             client: Client::builder()
@@ -365,8 +368,8 @@ impl Gemma4Harness {
     }
 
     async fn run_cargo(&self, args: &[&str]) -> String {
-        use tokio::process::Command;
-        match Command::new("cargo")
+        // Unmanaged: a cargo build is not media work
+match proc::unmanaged("cargo")
             .args(args)
             .current_dir(&self.work_dir)
             .output()
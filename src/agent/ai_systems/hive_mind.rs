@@ -207,6 +207,17 @@ impl HiveMind {
         "Ollama VLM (Llava/Moondream)".to_string()
     }
 
+    /// The installed model a request for `requested` refers to. A bare name
+    /// means its `:latest` tag, as it does for `ollama run`.
+    pub fn resolve_model(&self, requested: &str) -> Option<String> {
+        let requested = requested.trim();
+        if self.models.contains_key(requested) {
+            return Some(requested.to_string());
+        }
+        let latest = format!("{}:latest", requested);
+        (!requested.contains(':') && self.models.contains_key(&latest)).then_some(latest)
+    }
+
     /// Get the active backend for reasoning tasks.
     pub fn get_reasoner_backend(&self) -> &ModelBackend {
         &self.reasoner_backend
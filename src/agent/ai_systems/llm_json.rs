@@ -0,0 +1,320 @@
+// SYNOID LLM JSON — extraction, validation and repair of structured replies
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Every prompt that asks the model for JSON goes through `parse_with_repair`:
+// the first JSON block is cut out of the reply (models love "Here is the
+// JSON:" and ``` fences), deserialized into the target type and checked by a
+// caller-supplied validator. On failure the model gets a targeted repair
+// prompt quoting the exact error and the offending snippet, up to
+// `MAX_REPAIRS` times, before the caller falls back to its heuristics.
+//
+// Per-prompt outcomes are counted in `stats()` so flaky prompts show up.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+/// Repair round-trips allowed after the first reply.
+pub const MAX_REPAIRS: u32 = 2;
+
+/// Characters of context quoted either side of a syntax error.
+const SNIPPET_RADIUS: usize = 60;
+
+/// Longest previous reply echoed back in a repair prompt.
+const MAX_ECHO_CHARS: usize = 4000;
+
+/// Outcome counts for one prompt label.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct RepairStats {
+    /// Replies parsed and validated on the first try.
+    pub clean: u64,
+    /// Replies that needed at least one repair round-trip but then passed.
+    pub repaired: u64,
+    /// Replies that never passed; the caller fell back.
+    pub failed: u64,
+    /// Total repair prompts sent.
+    pub repair_attempts: u64,
+}
+
+impl RepairStats {
+    /// Share of replies that did not pass on the first try.
+    pub fn repair_rate(&self) -> f64 {
+        let total = self.clean + self.repaired + self.failed;
+        if total == 0 {
+            0.0
+        } else {
+            (self.repaired + self.failed) as f64 / total as f64
+        }
+    }
+}
+
+static STATS: Mutex<BTreeMap<String, RepairStats>> = Mutex::new(BTreeMap::new());
+
+/// Snapshot of the repair counters, keyed by prompt label.
+pub fn stats() -> BTreeMap<String, RepairStats> {
+    STATS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn record(label: &str, f: impl FnOnce(&mut RepairStats)) {
+    let mut stats = STATS.lock().unwrap_or_else(|e| e.into_inner());
+    f(stats.entry(label.to_string()).or_default());
+}
+
+/// The first balanced `{...}` or `[...]` in `response`, ignoring brackets
+/// inside strings. An unterminated block is returned to the end of the text
+/// so serde can report where it broke off.
+pub fn extract_json_block(response: &str) -> Option<&str> {
+    let start = response.find(['{', '['])?;
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in response[start..].char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' | '[' => depth += 1,
+            '}' | ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&response[start..start + i + 1]);
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(response[start..].trim_end().trim_end_matches("```").trim_end())
+}
+
+/// Text around `line:column` of `json`, for pointing the model at an error.
+fn snippet_at(json: &str, line: usize, column: usize) -> String {
+    let offset: usize = json
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum::<usize>()
+        + column.saturating_sub(1);
+    let offset = offset.min(json.len());
+    let mut from = offset.saturating_sub(SNIPPET_RADIUS);
+    while !json.is_char_boundary(from) {
+        from -= 1;
+    }
+    let mut to = (offset + SNIPPET_RADIUS).min(json.len());
+    while !json.is_char_boundary(to) {
+        to += 1;
+    }
+    json[from..to].to_string()
+}
+
+fn truncate(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+/// Parse and validate one reply; on failure, the error and the snippet to
+/// quote back to the model.
+fn try_parse<T, V>(response: &str, validate: &V) -> Result<T, (String, String)>
+where
+    T: DeserializeOwned,
+    V: Fn(&T) -> Result<(), String>,
+{
+    let Some(json) = extract_json_block(response) else {
+        return Err((
+            "the reply contains no JSON object or array".to_string(),
+            truncate(response.trim(), SNIPPET_RADIUS * 2).to_string(),
+        ));
+    };
+    let value: T = serde_json::from_str(json).map_err(|e| {
+        (
+            e.to_string(),
+            snippet_at(json, e.line(), e.column()),
+        )
+    })?;
+    validate(&value).map_err(|e| (e, truncate(json, SNIPPET_RADIUS * 4).to_string()))?;
+    Ok(value)
+}
+
+fn repair_prompt(prompt: &str, previous: &str, error: &str, snippet: &str) -> String {
+    format!(
+        "{}\n\n### YOUR PREVIOUS REPLY\n{}\n\n### WHY IT WAS REJECTED\n{}\nNear: `{}`\n\nReply again with ONLY the corrected JSON. No prose, no code fences.",
+        prompt,
+        truncate(previous.trim(), MAX_ECHO_CHARS),
+        error,
+        snippet
+    )
+}
+
+/// Deserialize `response` into `T`, asking the model to fix its reply (via
+/// `ask`, which re-prompts the same model) when parsing or `validate` fails.
+/// `label` names the prompt in `stats()` and the logs; `prompt` is the
+/// original request, repeated so the repair has full context.
+pub async fn parse_with_repair<T, V, F, Fut>(
+    label: &str,
+    prompt: &str,
+    response: &str,
+    validate: V,
+    mut ask: F,
+) -> Result<T, String>
+where
+    T: DeserializeOwned,
+    V: Fn(&T) -> Result<(), String>,
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<String, String>>,
+{
+    let mut reply = response.to_string();
+    let mut attempt = 0;
+    loop {
+        let (error, snippet) = match try_parse(&reply, &validate) {
+            Ok(value) => {
+                if attempt == 0 {
+                    record(label, |s| s.clean += 1);
+                } else {
+                    info!("[LLM-JSON] '{}' reply repaired after {} attempt(s)", label, attempt);
+                    record(label, |s| s.repaired += 1);
+                }
+                return Ok(value);
+            }
+            Err(e) => e,
+        };
+
+        if attempt >= MAX_REPAIRS {
+            warn!("[LLM-JSON] '{}' reply still invalid after {} repair(s): {}", label, attempt, error);
+            record(label, |s| s.failed += 1);
+            return Err(error);
+        }
+        attempt += 1;
+        warn!("[LLM-JSON] '{}' reply invalid ({}), asking for repair {}/{}", label, error, attempt, MAX_REPAIRS);
+        record(label, |s| s.repair_attempts += 1);
+
+        match ask(repair_prompt(prompt, &reply, &error, &snippet)).await {
+            Ok(next) => reply = next,
+            Err(e) => {
+                record(label, |s| s.failed += 1);
+                return Err(format!("{} (repair request failed: {})", error, e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::cell::RefCell;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Plan {
+        title: String,
+        cuts: Vec<f64>,
+    }
+
+    fn no_checks(_: &Plan) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Runs `parse_with_repair` against canned replies and returns the result
+    /// plus the repair prompts that were sent.
+    fn run(
+        label: &str,
+        first: &str,
+        repairs: &[&str],
+        validate: impl Fn(&Plan) -> Result<(), String>,
+    ) -> (Result<Plan, String>, Vec<String>) {
+        let sent = RefCell::new(Vec::new());
+        let canned = RefCell::new(repairs.iter().map(|s| s.to_string()).collect::<Vec<_>>());
+        let result = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(parse_with_repair(label, "PROMPT", first, validate, |p| {
+                sent.borrow_mut().push(p);
+                let next = canned.borrow_mut().remove(0);
+                async move { Ok::<_, String>(next) }
+            }));
+        (result, sent.into_inner())
+    }
+
+    #[test]
+    fn extracts_json_from_prose_and_fences() {
+        let reply = "Here is the JSON:\n```json\n{\"title\": \"a } in a string\", \"cuts\": [1]}\n```\nEnjoy!";
+        assert_eq!(
+            extract_json_block(reply),
+            Some("{\"title\": \"a } in a string\", \"cuts\": [1]}")
+        );
+        assert_eq!(extract_json_block("[1, 2] and {3}"), Some("[1, 2]"));
+        assert_eq!(extract_json_block("{\"cut\": [1, 2\n```"), Some("{\"cut\": [1, 2"));
+        assert_eq!(extract_json_block("no json here"), None);
+    }
+
+    #[test]
+    fn clean_reply_needs_no_repair() {
+        let (result, sent) = run("test_clean", r#"{"title": "x", "cuts": [1.5]}"#, &[], no_checks);
+        assert_eq!(result.unwrap().cuts, vec![1.5]);
+        assert!(sent.is_empty());
+        assert_eq!(stats()["test_clean"].clean, 1);
+    }
+
+    #[test]
+    fn syntax_error_is_quoted_back_and_repaired() {
+        let broken = r#"Sure! {"title": "x", "cuts": [1.5, 2.0,], }"#;
+        let (result, sent) = run(
+            "test_syntax",
+            broken,
+            &[r#"{"title": "x", "cuts": [1.5, 2.0]}"#],
+            no_checks,
+        );
+        assert_eq!(result.unwrap().cuts, vec![1.5, 2.0]);
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].starts_with("PROMPT"));
+        assert!(sent[0].contains("trailing comma"), "{}", sent[0]);
+        assert!(sent[0].contains("2.0,]"), "{}", sent[0]);
+
+        let s = &stats()["test_syntax"];
+        assert_eq!((s.repaired, s.repair_attempts, s.failed), (1, 1, 0));
+        assert_eq!(s.repair_rate(), 1.0);
+    }
+
+    #[test]
+    fn wrong_shape_and_failed_validation_trigger_repairs() {
+        let not_empty = |p: &Plan| {
+            if p.cuts.is_empty() {
+                Err("cuts must not be empty".to_string())
+            } else {
+                Ok(())
+            }
+        };
+        let (result, sent) = run(
+            "test_validate",
+            r#"{"title": 7, "cuts": []}"#,
+            &[r#"{"title": "x", "cuts": []}"#, r#"{"title": "x", "cuts": [3.0]}"#],
+            not_empty,
+        );
+        assert_eq!(result.unwrap().cuts, vec![3.0]);
+        assert!(sent[0].contains("invalid type: integer `7`"), "{}", sent[0]);
+        assert!(sent[1].contains("cuts must not be empty"), "{}", sent[1]);
+    }
+
+    #[test]
+    fn gives_up_after_max_repairs() {
+        let (result, sent) = run("test_give_up", "no json", &["still none", "nope"], no_checks);
+        assert!(result.unwrap_err().contains("no JSON"));
+        assert_eq!(sent.len() as u32, MAX_REPAIRS);
+        let s = &stats()["test_give_up"];
+        assert_eq!((s.failed, s.repair_attempts), (1, MAX_REPAIRS as u64));
+    }
+}
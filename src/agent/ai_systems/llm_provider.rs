@@ -4,6 +4,7 @@
 // All reasoning, fast-request, and vision tasks route through the local
 // Ollama server.  No cloud providers (Groq / Google) are used.
 
+use crate::agent::ai_systems::ollama_pull;
 use crate::agent::ai_systems::token_optimizer::TokenOptimizer;
 use crate::net;
 use serde_json::json;
@@ -36,13 +37,11 @@ pub struct ProviderConfig {
 
 impl Default for ProviderConfig {
     fn default() -> Self {
+        let config = crate::config::current();
         Self {
-            ollama_url: std::env::var("SYNOID_API_URL")
-                .unwrap_or_else(|_| "http://localhost:11434".to_string()),
-            ollama_model: std::env::var("SYNOID_MODEL")
-                .unwrap_or_else(|_| "gemma4:26b".to_string()),
-            ollama_vision_model: std::env::var("SYNOID_VISION_MODEL")
-                .unwrap_or_else(|_| "llava:latest".to_string()),
+            ollama_url: config.api_url,
+            ollama_model: config.model,
+            ollama_vision_model: config.vision_model,
         }
     }
 }
@@ -132,24 +131,42 @@ impl MultiProviderLlm {
         });
 
         let url = format!("{}/api/generate", base);
-        // Retry up to 2 extra times on timeout / connect errors before going offline.
-        let resp = net::retry(2, net::is_transient_reqwest, || {
-            self.client.post(&url).json(&payload).send()
-        })
-        .await;
-
-        match resp {
-            Ok(r) if r.status().is_success() => {
-                let json: serde_json::Value = r.json().await.map_err(|e| e.to_string())?;
-                Ok(json["response"]
-                    .as_str()
-                    .unwrap_or("Error: Empty response")
-                    .to_string())
-            }
-            Ok(r) => Err(format!("Ollama API error: {}", r.status())),
-            Err(e) => {
-                warn!("[LLM] Ollama unreachable ({}), entering offline mode", e);
-                Ok(format!("(Offline Mode) Mock response for: {}", request))
+        let mut pulled = false;
+        loop {
+            // Retry up to 2 extra times on timeout / connect errors before going offline.
+            let resp = net::retry(2, net::is_transient_reqwest, || {
+                self.client.post(&url).json(&payload).send()
+            })
+            .await;
+
+            match resp {
+                Ok(r) if r.status().is_success() => {
+                    let json: serde_json::Value = r.json().await.map_err(|e| e.to_string())?;
+                    return Ok(json["response"]
+                        .as_str()
+                        .unwrap_or("Error: Empty response")
+                        .to_string());
+                }
+                Ok(r) => {
+                    let status = r.status();
+                    let body = r.text().await.unwrap_or_default();
+                    if !pulled && ollama_pull::is_model_not_found(status, &body) {
+                        // Pull (or explain how to), then retry the original request once
+                        ollama_pull::recover_missing_model(
+                            base,
+                            &self.config.ollama_model,
+                            "SYNOID_MODEL",
+                        )
+                        .await?;
+                        pulled = true;
+                        continue;
+                    }
+                    return Err(format!("Ollama API error: {} {}", status, body.trim()));
+                }
+                Err(e) => {
+                    warn!("[LLM] Ollama unreachable ({}), entering offline mode", e);
+                    return Ok(format!("(Offline Mode) Mock response for: {}", request));
+                }
             }
         }
     }
@@ -171,19 +188,35 @@ impl MultiProviderLlm {
         // Vision inference is slow — use a dedicated 90 s client to avoid overriding the
         // 60 s reasoning timeout on the shared client.
         let vision_client = net::build_local_client(Duration::from_secs(90));
-        match vision_client
-            .post(format!("{}/api/generate", base))
-            .json(&body)
-            .send()
-            .await
-        {
-            Ok(resp) => {
-                let json: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
-                Ok(json["response"].as_str().unwrap_or("").to_string())
-            }
-            Err(e) => {
-                warn!("[LLM] Ollama VLM unavailable: {}", e);
-                Ok(String::new())
+        let mut pulled = false;
+        loop {
+            match vision_client
+                .post(format!("{}/api/generate", base))
+                .json(&body)
+                .send()
+                .await
+            {
+                Ok(resp) => {
+                    let status = resp.status();
+                    let text = resp.text().await.map_err(|e| e.to_string())?;
+                    if !pulled && ollama_pull::is_model_not_found(status, &text) {
+                        ollama_pull::recover_missing_model(
+                            base,
+                            &self.config.ollama_vision_model,
+                            "SYNOID_VISION_MODEL",
+                        )
+                        .await?;
+                        pulled = true;
+                        continue;
+                    }
+                    let json: serde_json::Value =
+                        serde_json::from_str(&text).map_err(|e| e.to_string())?;
+                    return Ok(json["response"].as_str().unwrap_or("").to_string());
+                }
+                Err(e) => {
+                    warn!("[LLM] Ollama VLM unavailable: {}", e);
+                    return Ok(String::new());
+                }
             }
         }
     }
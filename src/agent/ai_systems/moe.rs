@@ -12,9 +12,9 @@
 // for LLM-routed text tasks with no gradient descent — pure prompt-based gating.
 
 use crate::agent::ai_systems::gpt_oss_bridge::SynoidAgent;
+use crate::agent::engines::proc;
 use crate::agent::engines::process_utils::CommandExt;
 use serde::{Deserialize, Serialize};
-use std::process::Command;
 use tracing::info;
 
 // ──────────────────────────────────────────────────────────────────────────────
@@ -381,8 +381,9 @@ impl MoeRouter {
         }
 
         let task_owned = task.to_string();
-        let output = tokio::task::spawn_blocking(move || {
-            Command::new("python")
+        // Unmanaged: the Hermes CLI is an LLM agent, not media work
+let output = tokio::task::spawn_blocking(move || {
+            proc::blocking_unmanaged("python")
                 .arg("cli.py")
                 .arg("-q")
                 .arg(&task_owned)
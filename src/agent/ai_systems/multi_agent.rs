@@ -3,8 +3,8 @@
 // Copyright (c) 2026 xingxerx_The_Creator | SYNOID
 
 use crate::agent::ai_systems::reasoning::{ReasoningEffort, ReasoningManager};
+use crate::agent::engines::proc::{self, ProcClass};
 use serde::{Deserialize, Serialize};
-use std::process::Command;
 use crate::agent::engines::process_utils::CommandExt;
 use tracing::info;
 
@@ -30,6 +30,22 @@ pub struct StoryPlan {
 }
 
 impl StoryPlan {
+    /// Checks an LLM-produced plan beyond what deserialization enforces.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.scenes.is_empty() {
+            return Err("scenes must contain at least one scene".to_string());
+        }
+        for (i, s) in self.scenes.iter().enumerate() {
+            if !(s.timestamp_start >= 0.0 && s.timestamp_end > s.timestamp_start) {
+                return Err(format!(
+                    "scenes[{}]: timestamp_end ({}) must be after timestamp_start ({}) and both >= 0",
+                    i, s.timestamp_end, s.timestamp_start
+                ));
+            }
+        }
+        Ok(())
+    }
+
     pub fn expected_duration(&self) -> f64 {
         self.scenes
             .iter()
@@ -98,19 +114,20 @@ impl DirectorAgent {
             .await
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
 
-        // Attempt to parse JSON.
-        // Note: LLMs might wrap JSON in markdown code blocks, so we simple-clean it.
-        let clean_json = response_text
-            .trim()
-            .trim_start_matches("```json")
-            .trim_start_matches("```")
-            .trim_end_matches("```")
-            .trim();
-
-        match serde_json::from_str::<StoryPlan>(clean_json) {
+        let agent = &self.agent;
+        let parsed = crate::agent::llm_json::parse_with_repair(
+            "story_plan",
+            &prompt,
+            &response_text,
+            StoryPlan::validate,
+            |repair| async move { agent.reason(&repair).await },
+        )
+        .await;
+
+        match parsed {
             Ok(plan) => Ok(plan),
             Err(e) => {
-                info!("[DIRECTOR] JSON Parse Failed. Response: {}", response_text);
+                info!("[DIRECTOR] Story plan unusable ({}). Response: {}", e, response_text);
                 // Fallback to a simple plan if LLM fails formatting
                 let fallback = StoryPlan {
                     global_intent: user_prompt.to_string(),
@@ -257,7 +274,7 @@ impl RenderJob {
             return Ok(());
         }
 
-        let status = Command::new("ffmpeg")
+        let status = proc::blocking_ffmpeg(ProcClass::Render)
             .stealth()
             .arg("-y")
             .arg("-i")
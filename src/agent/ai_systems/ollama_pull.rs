@@ -0,0 +1,203 @@
+// SYNOID Ollama Model Pull — recovering from "model not found"
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Ollama answers a request for a model that was never pulled with a 404 and
+// `{"error":"model \"x\" not found, try pulling it first"}`. The LLM bridge
+// uses this module to recognise that reply and either pull the model through
+// `/api/pull` (when `SYNOID_AUTO_PULL=true`) or turn it into an error that
+// names the model and the setting to change.
+
+use crate::net;
+use serde_json::{json, Value};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Pulls are multi-gigabyte downloads; the request timeout only guards
+/// against a server that stops talking altogether.
+const PULL_TIMEOUT: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Human-readable progress of the pull in flight, if any. Mirrored into the
+/// agent status line by `AgentCore::get_status`.
+static PULL_STATUS: Mutex<Option<String>> = Mutex::new(None);
+
+/// Serialises pulls so concurrent callers hitting the same missing model
+/// don't each start a download.
+fn pull_lock() -> &'static tokio::sync::Mutex<()> {
+    static LOCK: OnceLock<tokio::sync::Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| tokio::sync::Mutex::new(()))
+}
+
+/// The `auto_pull` setting (SYNOID_AUTO_PULL) lets SYNOID download missing
+/// models on its own.
+pub fn auto_pull_enabled() -> bool {
+    crate::config::current().auto_pull
+}
+
+/// Current pull progress, e.g. "⬇️ Pulling gemma4:26b — downloading 42%".
+pub fn pull_status() -> Option<String> {
+    PULL_STATUS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn set_pull_status(status: Option<String>) {
+    *PULL_STATUS.lock().unwrap_or_else(|e| e.into_inner()) = status;
+}
+
+/// Whether an Ollama error reply means the requested model isn't installed.
+pub fn is_model_not_found(status: reqwest::StatusCode, body: &str) -> bool {
+    let body = body.to_lowercase();
+    status == reqwest::StatusCode::NOT_FOUND && body.contains("model") && body.contains("not found")
+}
+
+/// Only a server on this machine is pulled onto automatically; a shared
+/// remote server is somebody else's disk.
+pub fn is_local_server(base_url: &str) -> bool {
+    url::Url::parse(base_url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.trim_matches(['[', ']']).to_lowercase()))
+        .is_some_and(|h| matches!(h.as_str(), "localhost" | "127.0.0.1" | "::1" | "0.0.0.0"))
+}
+
+/// Status line for one NDJSON progress object from `/api/pull`.
+fn progress_line(model: &str, update: &Value) -> Option<String> {
+    let phase = update["status"].as_str()?;
+    match (update["completed"].as_u64(), update["total"].as_u64()) {
+        (Some(done), Some(total)) if total > 0 => Some(format!(
+            "⬇️ Pulling {} — {} {:.0}%",
+            model,
+            phase,
+            done as f64 * 100.0 / total as f64
+        )),
+        _ => Some(format!("⬇️ Pulling {} — {}", model, phase)),
+    }
+}
+
+/// Make `model` available after Ollama reported it missing. Pulls it when
+/// auto-pull is enabled and the server is local; otherwise (or when the pull
+/// fails) returns an error naming the model and `config_key`.
+pub async fn recover_missing_model(
+    base_url: &str,
+    model: &str,
+    config_key: &str,
+) -> Result<(), String> {
+    if !auto_pull_enabled() {
+        return Err(format!(
+            "Ollama model '{}' is not installed. Run `ollama pull {}`, set SYNOID_AUTO_PULL=true to pull it automatically, or set {} to a model you have.",
+            model, model, config_key
+        ));
+    }
+    if !is_local_server(base_url) {
+        return Err(format!(
+            "Ollama model '{}' is not installed on the remote server {}; SYNOID only auto-pulls onto a local server. Pull it there or set {} to a model it has.",
+            model, base_url, config_key
+        ));
+    }
+    pull_model(base_url, model).await.map_err(|e| {
+        format!(
+            "Ollama model '{}' is missing and pulling it failed ({}). Free up disk space and retry, or set {} to a model you have.",
+            model, e, config_key
+        )
+    })
+}
+
+/// Pull `model` via `POST /api/pull`, streaming progress into `pull_status`.
+pub async fn pull_model(base_url: &str, model: &str) -> Result<(), String> {
+    let _guard = pull_lock().lock().await;
+    info!("[LLM] ⬇️ Pulling missing Ollama model '{}'", model);
+    set_pull_status(Some(format!("⬇️ Pulling {}...", model)));
+    let result = stream_pull(base_url, model).await;
+    set_pull_status(None);
+
+    match &result {
+        Ok(()) => info!("[LLM] ✅ Pulled Ollama model '{}'", model),
+        Err(e) => warn!("[LLM] Pull of '{}' failed: {}", model, e),
+    }
+    result
+}
+
+async fn stream_pull(base_url: &str, model: &str) -> Result<(), String> {
+    let client = net::build_local_client(PULL_TIMEOUT);
+    let mut resp = client
+        .post(format!("{}/api/pull", base_url))
+        .json(&json!({ "model": model, "stream": true }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        return Err(format!("{} {}", status, body.trim()));
+    }
+
+    let mut buf: Vec<u8> = Vec::new();
+    let mut last_logged = None;
+    let mut succeeded = false;
+    while let Some(chunk) = resp.chunk().await.map_err(|e| e.to_string())? {
+        buf.extend_from_slice(&chunk);
+        while let Some(nl) = buf.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buf.drain(..=nl).collect();
+            let Ok(update) = serde_json::from_slice::<Value>(&line) else {
+                continue;
+            };
+            if let Some(err) = update["error"].as_str() {
+                return Err(err.to_string());
+            }
+            if update["status"] == "success" {
+                succeeded = true;
+            }
+            if let Some(line) = progress_line(model, &update) {
+                // Log each phase once rather than every progress tick
+                let phase = update["status"].as_str().map(str::to_string);
+                if phase != last_logged {
+                    info!("[LLM] {}", line);
+                    last_logged = phase;
+                }
+                set_pull_status(Some(line));
+            }
+        }
+    }
+
+    if succeeded {
+        Ok(())
+    } else {
+        Err("pull stream ended before Ollama reported success".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn recognises_ollama_missing_model_reply() {
+        let body = r#"{"error":"model \"gpt-oss:20b\" not found, try pulling it first"}"#;
+        assert!(is_model_not_found(StatusCode::NOT_FOUND, body));
+        assert!(!is_model_not_found(StatusCode::NOT_FOUND, "404 page not found"));
+        assert!(!is_model_not_found(StatusCode::INTERNAL_SERVER_ERROR, body));
+    }
+
+    #[test]
+    fn only_loopback_servers_count_as_local() {
+        assert!(is_local_server("http://localhost:11434"));
+        assert!(is_local_server("http://127.0.0.1:11434"));
+        assert!(is_local_server("http://[::1]:11434"));
+        assert!(!is_local_server("http://gpu-box.lan:11434"));
+        assert!(!is_local_server("not a url"));
+    }
+
+    #[test]
+    fn progress_lines_show_percent_when_sizes_are_known() {
+        let update = json!({"status": "pulling 8eeb52dfb3bb", "completed": 250, "total": 1000});
+        assert_eq!(
+            progress_line("llama3:latest", &update).unwrap(),
+            "⬇️ Pulling llama3:latest — pulling 8eeb52dfb3bb 25%"
+        );
+        let update = json!({"status": "verifying sha256 digest"});
+        assert_eq!(
+            progress_line("llama3:latest", &update).unwrap(),
+            "⬇️ Pulling llama3:latest — verifying sha256 digest"
+        );
+        assert!(progress_line("llama3:latest", &json!({})).is_none());
+    }
+}
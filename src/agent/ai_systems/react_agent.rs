@@ -21,6 +21,8 @@
 //   • finish(answer)            — terminate loop with final answer
 
 use crate::agent::ai_systems::gpt_oss_bridge::SynoidAgent;
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::engines::process_utils::CommandExt;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
@@ -381,6 +383,44 @@ finish(answer: str)
         }
     }
 
+    /// Split a command line on whitespace, keeping double-quoted words
+    /// (with `\"` escapes) together. Other backslashes are literal, so
+    /// Windows paths survive.
+    fn split_command(command: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        let mut current = String::new();
+        let mut in_quote = false;
+        let mut started = false;
+        let mut chars = command.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' if chars.peek() == Some(&'"') => {
+                    current.push('"');
+                    chars.next();
+                    started = true;
+                }
+                '"' => {
+                    in_quote = !in_quote;
+                    started = true;
+                }
+                c if c.is_whitespace() && !in_quote => {
+                    if started {
+                        words.push(std::mem::take(&mut current));
+                        started = false;
+                    }
+                }
+                c => {
+                    current.push(c);
+                    started = true;
+                }
+            }
+        }
+        if started {
+            words.push(current);
+        }
+        words
+    }
+
     /// Extract quoted string arguments from a raw args string like `"foo", "bar", "baz"`.
     fn extract_quoted_args(s: &str) -> Vec<String> {
         let mut args = Vec::new();
@@ -444,7 +484,7 @@ finish(answer: str)
             "ffprobe -v quiet -print_format json -show_format -show_streams \"{}\"",
             path
         );
-        match Self::run_whitelisted(&cmd) {
+        match Self::run_whitelisted(&cmd).await {
             Ok(out) => {
                 if out.is_empty() {
                     format!("Error: ffprobe returned no output for {}", path)
@@ -463,7 +503,7 @@ finish(answer: str)
             "yt-dlp --no-download --print title --print webpage_url -I 1:5 \"ytsearch5:{}\"",
             safe_query
         );
-        match Self::run_whitelisted(&cmd) {
+        match Self::run_whitelisted(&cmd).await {
             Ok(out) => format!("YouTube search results for '{}':\n{}", query, out),
             Err(e) => format!("YouTube search error: {}", e),
         }
@@ -513,7 +553,7 @@ finish(answer: str)
 
     async fn tool_run_command(&self, command: &str) -> String {
         info!("[REACT:tool] run_command({})", command);
-        match Self::run_whitelisted(command) {
+        match Self::run_whitelisted(command).await {
             Ok(out) => {
                 if out.is_empty() {
                     "(Command completed with no output)".to_string()
@@ -525,24 +565,26 @@ finish(answer: str)
         }
     }
 
-    /// Execute a whitelisted command synchronously.
+    /// Execute a whitelisted command through the process governor.
     ///
     /// Only ffprobe, ffmpeg, and yt-dlp are allowed. This is enforced here
     /// rather than relying on the LLM to be safe.
-    fn run_whitelisted(command: &str) -> Result<String, String> {
-        let normalized = command.trim().to_lowercase();
-
-        let allowed_prefixes = ["ffprobe", "ffmpeg", "yt-dlp", "yt_dlp"];
-        let is_allowed = allowed_prefixes
-            .iter()
-            .any(|p| normalized.starts_with(p));
+    async fn run_whitelisted(command: &str) -> Result<String, String> {
+        let words = Self::split_command(command);
+        let program = words.first().map(|w| w.to_lowercase()).unwrap_or_default();
+        let class = match program.trim_end_matches(".exe") {
+            "ffprobe" => Some(ProcClass::Probe),
+            "ffmpeg" => Some(ProcClass::Render),
+            "yt-dlp" => Some(ProcClass::Download),
+            _ => None,
+        };
 
-        if !is_allowed {
+        let Some(class) = class else {
             return Err(format!(
                 "Command blocked by ReAct security policy. Only ffprobe/ffmpeg/yt-dlp allowed. Got: {}",
                 &command[..command.len().min(80)]
             ));
-        }
+        };
 
         // Additional injection checks
         let injection_patterns = [";", "&&", "||", "|", "`", "$(" , "$(", "rm ", "del "];
@@ -555,9 +597,12 @@ finish(answer: str)
             }
         }
 
-        let output = std::process::Command::new("cmd")
-            .args(["/C", command])
+        let output = proc::command(&words[0], class)
+            .stealth()
+            .args(&words[1..])
+            .stdin(std::process::Stdio::null())
             .output()
+            .await
             .map_err(|e| format!("Failed to execute command: {}", e))?;
 
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
@@ -629,23 +674,36 @@ mod tests {
         assert!(matches!(action, AgentTool::EditVideo { .. }));
     }
 
-    #[test]
-    fn command_whitelist_blocks_rm() {
-        let result = ReActAgent::run_whitelisted("rm -rf /");
+    #[tokio::test]
+    async fn command_whitelist_blocks_rm() {
+        let result = ReActAgent::run_whitelisted("rm -rf /").await;
         assert!(result.is_err(), "rm should be blocked");
+        let result = ReActAgent::run_whitelisted("ffmpeg-evil -version").await;
+        assert!(result.is_err(), "only the exact program names are allowed");
     }
 
-    #[test]
-    fn command_whitelist_blocks_injection() {
-        let result = ReActAgent::run_whitelisted("ffprobe file.mp4 && rm -rf /");
+    #[tokio::test]
+    async fn command_whitelist_blocks_injection() {
+        let result = ReActAgent::run_whitelisted("ffprobe file.mp4 && rm -rf /").await;
         assert!(result.is_err(), "injection should be blocked");
     }
 
     #[test]
-    fn command_whitelist_allows_ffprobe() {
+    fn commands_split_on_whitespace_outside_quotes() {
+        let words = ReActAgent::split_command(
+            r#"yt-dlp --print title "ytsearch5:say \"hi\"" "D:\My Videos\in.mp4" """#,
+        );
+        assert_eq!(
+            words,
+            vec!["yt-dlp", "--print", "title", "ytsearch5:say \"hi\"", "D:\\My Videos\\in.mp4", ""]
+        );
+    }
+
+    #[tokio::test]
+    async fn command_whitelist_allows_ffprobe() {
         // Just check it doesn't error on the whitelist check itself
         // (actual execution may fail if ffprobe not installed in test env)
-        let result = ReActAgent::run_whitelisted("ffprobe -version");
+        let result = ReActAgent::run_whitelisted("ffprobe -version").await;
         // Error is OK (ffprobe may not be installed), but it should NOT be
         // a "blocked by security policy" error
         match &result {
@@ -3,7 +3,10 @@
 
 use crate::agent::core_systems::brain::Brain;
 use crate::agent::specialized::smart_editor;
-use crate::agent::{specialized::academy::code_scanner::CodeScanner, tools::source_tools};
+use crate::agent::specialized::academy::code_scanner::{self, CodeScanner, ScanNote, ScannerConfig};
+use crate::agent::tools::deep_research::ResearchReport;
+use crate::agent::tools::{search_providers, source_tools};
+use crate::events::{EventBus, SynoidEvent};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fs;
@@ -22,6 +25,8 @@ struct LearnerState {
     known_repos: Vec<String>,
     #[serde(default)]
     downloaded_videos: Vec<VideoRecord>,
+    #[serde(default)]
+    usage: DailyUsage,
 }
 
 #[derive(Serialize, Deserialize, Default, Clone, Debug)]
@@ -42,6 +47,329 @@ fn expected_download_path(download_dir: &Path, title: &str) -> PathBuf {
     download_dir.join(filename)
 }
 
+/// Rest between learning cycles.
+const CYCLE_REST: Duration = Duration::from_secs(600);
+/// Longest rest while searches keep getting rate-limited.
+const MAX_RATE_LIMITED_REST: Duration = Duration::from_secs(4 * 3600);
+
+/// Rest after a cycle, doubling for each consecutive rate-limited search.
+fn cycle_rest(rate_limit_streak: u32) -> Duration {
+    CYCLE_REST
+        .saturating_mul(1 << rate_limit_streak.min(16))
+        .min(MAX_RATE_LIMITED_REST)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Daily Budget
+// Caps on how much the learner downloads and processes per (local) day, so a
+// background service stays polite on metered connections and shared machines.
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Per-day limits. `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LearnerBudget {
+    pub max_downloads_per_day: Option<u32>,
+    pub max_process_minutes_per_day: Option<f64>,
+}
+
+impl LearnerBudget {
+    /// The configured `learner_max_downloads` / `learner_max_process_minutes`,
+    /// which reach the background worker process through its environment.
+    pub fn from_config() -> Self {
+        let config = crate::config::current();
+        Self {
+            max_downloads_per_day: config.learner_max_downloads,
+            max_process_minutes_per_day: config.learner_max_process_minutes,
+        }
+    }
+}
+
+/// What the learner has used so far today. Persisted in `LearnerState`.
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+struct DailyUsage {
+    day: Option<chrono::NaiveDate>,
+    downloads: u32,
+    process_secs: f64,
+}
+
+impl DailyUsage {
+    /// Reset the counters when `today` is a new day.
+    fn roll(&mut self, today: chrono::NaiveDate) {
+        if self.day != Some(today) {
+            *self = Self {
+                day: Some(today),
+                ..Self::default()
+            };
+        }
+    }
+
+    fn downloads_exhausted(&mut self, budget: &LearnerBudget, today: chrono::NaiveDate) -> bool {
+        self.roll(today);
+        budget
+            .max_downloads_per_day
+            .is_some_and(|max| self.downloads >= max)
+    }
+
+    fn processing_exhausted(&mut self, budget: &LearnerBudget, today: chrono::NaiveDate) -> bool {
+        self.roll(today);
+        budget
+            .max_process_minutes_per_day
+            .is_some_and(|max| self.process_secs >= max * 60.0)
+    }
+
+    fn record_download(&mut self, today: chrono::NaiveDate) {
+        self.roll(today);
+        self.downloads += 1;
+    }
+
+    fn record_processing(&mut self, today: chrono::NaiveDate, secs: f64) {
+        self.roll(today);
+        self.process_secs += secs;
+    }
+}
+
+fn today() -> chrono::NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+/// Time left until local midnight, when the daily budget resets.
+fn until_next_day() -> Duration {
+    let now = chrono::Local::now().naive_local();
+    let midnight = (now.date() + chrono::Days::new(1))
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or(now);
+    (midnight - now).to_std().unwrap_or(Duration::from_secs(600))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Supervised Mode
+// Each cycle publishes what it intends to download and waits for a human to
+// approve it (flag file or API call) before acquiring anything.
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Rough size of a ≤1080p download per second of runtime (~5 Mbps).
+const EST_MB_PER_SEC: f64 = 0.6;
+
+/// One video a cycle intends to download.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PlannedDownload {
+    pub title: String,
+    pub url: String,
+    pub duration_secs: f64,
+    pub estimated_mb: f64,
+}
+
+/// Everything a cycle will acquire, published for review in supervised mode.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct CyclePlan {
+    pub cycle: usize,
+    pub topic: String,
+    pub downloads: Vec<PlannedDownload>,
+    pub estimated_mb: f64,
+    /// Allow-listed repositories cloned for code analysis.
+    #[serde(default)]
+    pub repos: Vec<String>,
+    /// Wikipedia article studied for theory.
+    #[serde(default)]
+    pub theory: Option<String>,
+    /// Web search query scouted for tips.
+    #[serde(default)]
+    pub web_search: Option<String>,
+}
+
+impl CyclePlan {
+    /// True when the cycle would fetch nothing from the network.
+    pub fn is_empty(&self) -> bool {
+        self.downloads.is_empty()
+            && self.repos.is_empty()
+            && self.theory.is_none()
+            && self.web_search.is_none()
+    }
+
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return format!("Cycle #{} ('{}'): nothing to fetch", self.cycle, self.topic);
+        }
+        let mut parts = Vec::new();
+        if !self.downloads.is_empty() {
+            let titles: Vec<&str> = self.downloads.iter().map(|d| d.title.as_str()).collect();
+            parts.push(format!(
+                "will download {}; estimated {:.0} MB",
+                titles.join(", "),
+                self.estimated_mb
+            ));
+        }
+        if !self.repos.is_empty() {
+            parts.push(format!("will clone {}", self.repos.join(", ")));
+        }
+        if let Some(theory) = &self.theory {
+            parts.push(format!("will read {}", theory));
+        }
+        if let Some(query) = &self.web_search {
+            parts.push(format!("will search the web for '{}'", query));
+        }
+        format!("Cycle #{} ('{}'): {}", self.cycle, self.topic, parts.join("; "))
+    }
+
+    /// Hex SHA-256 of what the plan would acquire. The cycle number is left
+    /// out so an unchanged plan stays approved when it is re-published.
+    pub fn hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        hasher.update(self.topic.as_bytes());
+        let fetches = self
+            .downloads
+            .iter()
+            .map(|d| d.url.as_str())
+            .chain(self.repos.iter().map(String::as_str))
+            .chain(self.theory.as_deref())
+            .chain(self.web_search.as_deref());
+        for fetch in fetches {
+            hasher.update(b"\n");
+            hasher.update(fetch.as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+/// Approval gate for supervised mode. Approval is a one-shot file holding
+/// the approved plan's hash so it works for both the in-process learner and
+/// the background worker, and never carries over to a different plan.
+pub struct ApprovalGate {
+    supervised: bool,
+    dir: PathBuf,
+}
+
+impl ApprovalGate {
+    pub fn new(instance_id: &str, supervised: bool) -> Self {
+        Self::in_dir(PathBuf::from(format!("cortex_cache{}", instance_id)), supervised)
+    }
+
+    pub fn in_dir(dir: PathBuf, supervised: bool) -> Self {
+        Self { supervised, dir }
+    }
+
+    pub fn is_supervised(&self) -> bool {
+        self.supervised
+    }
+
+    pub fn plan_path(&self) -> PathBuf {
+        self.dir.join("learner_plan.json")
+    }
+
+    pub fn approval_path(&self) -> PathBuf {
+        self.dir.join("learner_approved")
+    }
+
+    /// Write the plan for review and log it.
+    pub fn publish(&self, plan: &CyclePlan) {
+        info!("[LEARNER] 📋 Plan: {}", plan.summary());
+        let _ = fs::create_dir_all(&self.dir);
+        if let Ok(data) = serde_json::to_string_pretty(plan) {
+            let _ = fs::write(self.plan_path(), data);
+        }
+    }
+
+    /// Grant approval for the published plan by recording its hash.
+    pub fn approve(&self) -> std::io::Result<()> {
+        let data = fs::read_to_string(self.plan_path())?;
+        let plan: CyclePlan = serde_json::from_str(&data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        fs::write(self.approval_path(), plan.hash())
+    }
+
+    /// Consume a pending approval if it was granted for `plan`. An approval
+    /// for some other plan is discarded. Always true when not supervised.
+    pub fn take_approval(&self, plan: &CyclePlan) -> bool {
+        if !self.supervised {
+            return true;
+        }
+        let Ok(approved) = fs::read_to_string(self.approval_path()) else {
+            return false;
+        };
+        let _ = fs::remove_file(self.approval_path());
+        if approved.trim() == plan.hash() {
+            true
+        } else {
+            warn!("[LEARNER] Discarding approval granted for a different plan.");
+            false
+        }
+    }
+
+    /// Check an already published plan for approval without re-publishing it.
+    pub fn poll(&self, plan: &CyclePlan) -> bool {
+        if !self.take_approval(plan) {
+            return false;
+        }
+        if self.supervised {
+            info!("[LEARNER] ✅ Plan approved — proceeding with cycle #{}.", plan.cycle);
+            let _ = fs::remove_file(self.plan_path());
+        }
+        true
+    }
+
+    /// Publish `plan` and decide whether this cycle may run.
+    pub fn permits(&self, plan: &CyclePlan) -> bool {
+        if !self.supervised || plan.is_empty() {
+            return true;
+        }
+        if self.poll(plan) {
+            true
+        } else {
+            self.publish(plan);
+            info!(
+                "[LEARNER] ⏸️ Awaiting approval (POST /api/learner/approve or write {} to {:?}).",
+                plan.hash(),
+                self.approval_path()
+            );
+            false
+        }
+    }
+}
+
+/// Build a cycle plan from search results, applying the same filters the
+/// download loop uses.
+fn plan_cycle(
+    cycle: usize,
+    topic: &str,
+    results: &[source_tools::SourceInfo],
+    processed: &HashSet<String>,
+) -> CyclePlan {
+    let downloads: Vec<PlannedDownload> = results
+        .iter()
+        .filter(|s| s.duration > 60.0 && s.duration < 900.0)
+        .filter_map(|s| {
+            let url = s.original_url.as_ref()?;
+            if processed.contains(url) || !is_trusted_video_url(url) {
+                return None;
+            }
+            Some(PlannedDownload {
+                title: s.title.clone(),
+                url: url.clone(),
+                duration_secs: s.duration,
+                estimated_mb: s.duration * EST_MB_PER_SEC,
+            })
+        })
+        .collect();
+    let estimated_mb = downloads.iter().map(|d| d.estimated_mb).sum();
+    CyclePlan {
+        cycle,
+        topic: topic.to_string(),
+        downloads,
+        estimated_mb,
+        ..Default::default()
+    }
+}
+
+/// Snapshot returned by the learner report API.
+#[derive(Serialize, Debug)]
+pub struct LearnerReport {
+    pub pending_plan: Option<CyclePlan>,
+    pub downloads_today: u32,
+    pub process_minutes_today: f64,
+    pub code_notes: Vec<ScanNote>,
+}
+
 impl LearnerState {
     fn path(instance_id: &str) -> PathBuf {
         let dir = PathBuf::from(format!("cortex_cache{}", instance_id));
@@ -80,6 +408,9 @@ pub struct AutonomousLearner {
     learning_topics: Vec<String>,
     wiki_targets: Vec<String>,
     instance_id: String,
+    supervised: bool,
+    budget: LearnerBudget,
+    events: Option<EventBus>,
 }
 
 impl AutonomousLearner {
@@ -109,6 +440,9 @@ impl AutonomousLearner {
             brain,
             state: Arc::new(Mutex::new(state)),
             instance_id: inst_id,
+            supervised: crate::config::current().learner_supervised,
+            budget: LearnerBudget::from_config(),
+            events: None,
             learning_topics: vec![
                 "cinematic travel video".to_string(),
                 "gaming montage editing".to_string(),
@@ -124,6 +458,24 @@ impl AutonomousLearner {
         }
     }
 
+    /// Override the daily download/processing limits.
+    pub fn with_budget(mut self, budget: LearnerBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Require approval before each cycle fetches anything (see `ApprovalGate`).
+    pub fn with_supervision(mut self) -> Self {
+        self.supervised = true;
+        self
+    }
+
+    /// Publish a `LearnerCycle` to `bus` after each completed cycle.
+    pub fn with_events(mut self, bus: EventBus) -> Self {
+        self.events = Some(bus);
+        self
+    }
+
     pub fn start(&self) {
         if self.is_running.load(Ordering::SeqCst) {
             info!("[LEARNER] Already running.");
@@ -142,15 +494,28 @@ impl AutonomousLearner {
         let scanner = CodeScanner::new("http://localhost:11434/v1");
 
         let instance_id = self.instance_id.clone();
+        let gate = ApprovalGate::new(&instance_id, self.supervised);
+        let budget = self.budget;
+        let events = self.events.clone();
 
-        info!("[LEARNER] 🚀 Autonomous Learning Loop Started (Sentinel Active)");
+        info!(
+            "[LEARNER] 🚀 Autonomous Learning Loop Started (Sentinel Active{})",
+            if self.supervised { ", Supervised" } else { "" }
+        );
 
         tokio::spawn(async move {
             let mut cycle_count = 0;
+            let mut rate_limit_streak = 0;
+            // A supervised cycle parked until its published plan is approved.
+            // Nothing else runs, and the cycle is not counted, meanwhile.
+            let mut pending: Option<(CyclePlan, Vec<source_tools::SourceInfo>)> = None;
 
             while is_running.load(Ordering::SeqCst) {
-                cycle_count += 1;
-                info!("[LEARNER] 🏁 Starting Learning Cycle #{}", cycle_count);
+                let cycle = cycle_count + 1;
+                let cycle_started = std::time::Instant::now();
+                if pending.is_none() {
+                    info!("[LEARNER] 🏁 Starting Learning Cycle #{}", cycle);
+                }
 
                 // 0. Sentinel Health Check
                 let alerts = sentinel.scan_processes();
@@ -163,228 +528,338 @@ impl AutonomousLearner {
                 // Lock state for this cycle
                 let mut state = state_arc.lock().await;
 
-                let topic = &topics[state.topic_index % topics.len()];
-                info!("[LEARNER] 🔍 Scouting topic: '{}'", topic);
+                // 0b. Daily budget — idle until tomorrow once it is spent
+                if state.usage.downloads_exhausted(&budget, today())
+                    || state.usage.processing_exhausted(&budget, today())
+                {
+                    let wait = until_next_day();
+                    info!(
+                        "[LEARNER] 💤 Daily budget reached ({} downloads, {:.0} min processed). Idling {}m until tomorrow.",
+                        state.usage.downloads,
+                        state.usage.process_secs / 60.0,
+                        wait.as_secs() / 60
+                    );
+                    state.save(&instance_id);
+                    drop(state);
+                    // Wake periodically so stop() is honoured promptly
+                    tokio::time::sleep(wait.min(Duration::from_secs(600))).await;
+                    continue;
+                }
 
-                // 1. Search for candidates
-                let search_result = source_tools::search_youtube(topic, 12)
-                    .await
-                    .map_err(|e| e.to_string());
+                let (plan, results) = match pending.take() {
+                    Some((plan, results)) => {
+                        if !gate.poll(&plan) {
+                            pending = Some((plan, results));
+                            drop(state);
+                            tokio::time::sleep(Duration::from_secs(60)).await;
+                            continue;
+                        }
+                        (plan, results)
+                    }
+                    None => {
+                        let topic = &topics[state.topic_index % topics.len()];
+                        info!("[LEARNER] 🔍 Scouting topic: '{}'", topic);
+
+                        // 1. Search for candidates, unless a fresh deep-research
+                        //    report already holds what this topic's videos teach
+                        let report = ResearchReport::load(topic)
+                            .filter(|r| r.suffices(chrono::Utc::now()));
+                        let search_result = match &report {
+                            Some(report) => {
+                                if state.processed_urls.insert(report.study_key()) {
+                                    study_research_report(&brain, report).await;
+                                    state
+                                        .processed_urls
+                                        .extend(report.items.iter().map(|item| item.url.clone()));
+                                }
+                                info!(
+                                    "[LEARNER] 📜 Research report covers '{}' — skipping downloads",
+                                    topic
+                                );
+                                Ok(Vec::new())
+                            }
+                            None => search_providers::search("youtube", topic, 12)
+                                .await
+                                .map_err(|e| e.to_string()),
+                        };
+                        // Throttling lasts hours; searching again in 10 minutes only
+                        // prolongs it, even when a fallback provider answered
+                        if search_providers::rate_limited_since(cycle_started) {
+                            rate_limit_streak += 1;
+                            warn!(
+                                "[LEARNER] ⏳ Search is rate-limited; resting {}m before the next cycle",
+                                cycle_rest(rate_limit_streak).as_secs() / 60
+                            );
+                        } else if report.is_none() {
+                            rate_limit_streak = 0;
+                        }
+
+                        let results = search_result.unwrap_or_else(|e| {
+                            error!("[LEARNER] Search failed for topic '{}': {}", topic, e);
+                            Vec::new()
+                        });
+                        let mut plan = plan_cycle(cycle, topic, &results, &state.processed_urls);
+                        if cycle % 3 == 0 {
+                            let scan_config = ScannerConfig::load(&instance_id);
+                            let repos = &scan_config.allowed_repos;
+                            plan.repos = (0..scan_config.max_repos_per_cycle.min(repos.len()))
+                                .map(|i| repos[(state.repo_index + i) % repos.len()].clone())
+                                .collect();
+                        }
+                        if cycle % 3 == 1 {
+                            plan.theory = Some(wikis[cycle % wikis.len()].clone());
+                        }
+                        if cycle % 5 == 2 {
+                            plan.web_search = Some(format!("{} editing techniques tips blog", topic));
+                        }
+                        if !gate.permits(&plan) {
+                            pending = Some((plan, results));
+                            drop(state);
+                            tokio::time::sleep(Duration::from_secs(60)).await;
+                            continue;
+                        }
+                        (plan, results)
+                    }
+                };
+                cycle_count = cycle;
+                let topic = plan.topic.clone();
+
+                for source in results {
+                    if !is_running.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if state.usage.downloads_exhausted(&budget, today())
+                        || state.usage.processing_exhausted(&budget, today())
+                    {
+                        info!("[LEARNER] 🪫 Daily budget reached — no more downloads this cycle.");
+                        break;
+                    }
 
-                match search_result {
-                    Ok(results) => {
-                        for source in results {
-                            if !is_running.load(Ordering::SeqCst) {
-                                break;
+                    // Check if already processed
+                    if let Some(url) = &source.original_url {
+                        if state.processed_urls.contains(url) {
+                            continue;
+                        }
+
+                        if !is_trusted_video_url(url) {
+                            warn!(
+                                "[LEARNER] ⏭️ Skipping untrusted acquisition source: {}",
+                                url
+                            );
+                            state.processed_urls.insert(url.clone());
+                            continue;
+                        }
+                    }
+
+                    // Filter criteria (e.g., duration < 10 mins to be quick)
+                    if source.duration > 60.0 && source.duration < 900.0 {
+                        // Increased max duration
+                        // 1b. Safety Check URL
+                        if let Some(url) = &source.original_url {
+                            if let Err(e) =
+                                crate::agent::download_guard::DownloadGuard::validate_url(
+                                    url,
+                                )
+                            {
+                                error!("[LEARNER] 🛡️ Skipped unsafe URL: {}", e);
+                                continue;
                             }
+                        }
 
-                            // Check if already processed
-                            if let Some(url) = &source.original_url {
-                                if state.processed_urls.contains(url) {
-                                    continue;
-                                }
+                        info!("[LEARNER] 📥 Acquiring candidate: {}", source.title);
 
-                                if !is_trusted_video_url(url) {
-                                    warn!(
-                                        "[LEARNER] ⏭️ Skipping untrusted acquisition source: {}",
-                                        url
-                                    );
-                                    state.processed_urls.insert(url.clone());
-                                    continue;
-                                }
+                        let download_dir_buf =
+                            crate::agent::video_style_learner::get_download_dir();
+                        let download_dir = download_dir_buf.as_path();
+                        let _ = std::fs::create_dir_all(download_dir);
+
+                        let existing_path =
+                            expected_download_path(download_dir, &source.title);
+                        if existing_path.exists() {
+                            info!(
+                                "[LEARNER] ⏭️ Skipping already-present reference video: {}",
+                                source.title
+                            );
+                            if let Some(url) = &source.original_url {
+                                state.processed_urls.insert(url.clone());
                             }
+                            continue;
+                        }
 
-                            // Filter criteria (e.g., duration < 10 mins to be quick)
-                            if source.duration > 60.0 && source.duration < 900.0 {
-                                // Increased max duration
-                                // 1b. Safety Check URL
-                                if let Some(url) = &source.original_url {
-                                    if let Err(e) =
-                                        crate::agent::download_guard::DownloadGuard::validate_url(
-                                            url,
-                                        )
-                                    {
-                                        error!("[LEARNER] 🛡️ Skipped unsafe URL: {}", e);
-                                        continue;
-                                    }
+                        // 1b. Proactive Eviction: Ensure we have room for 1 more video
+                        ensure_download_capacity(&mut state, download_dir).await;
+
+                        let browser = source_tools::detect_browser();
+                        let download_result = source_tools::download_youtube(
+                            source.original_url.as_deref().unwrap_or(""),
+                            download_dir,
+                            browser.as_deref(),
+                        )
+                        .await
+                        .map_err(|e| e.to_string());
+
+                        match download_result {
+                            Ok(downloaded) => {
+                                state.usage.record_download(today());
+
+                                // 1c. Safety Check File (ffprobe/ffmpeg under a blocking permit)
+                                let check_path = downloaded.local_path.clone();
+                                let verdict = tokio::task::spawn_blocking(move || {
+                                    crate::agent::download_guard::DownloadGuard::validate_downloaded_file(&check_path)
+                                })
+                                .await
+                                .unwrap_or_else(|e| Err(e.to_string()));
+                                if let Err(e) = verdict {
+                                    error!("[LEARNER] 🛡️ Downloaded file rejected: {}", e);
+                                    let _ = std::fs::remove_file(&downloaded.local_path);
+                                    continue;
                                 }
 
-                                info!("[LEARNER] 📥 Acquiring candidate: {}", source.title);
+                                info!(
+                                    "[LEARNER] 🎓 New video acquired: '{}'",
+                                    downloaded.title
+                                );
 
-                                let download_dir_buf =
-                                    crate::agent::video_style_learner::get_download_dir();
-                                let download_dir = download_dir_buf.as_path();
-                                let _ = std::fs::create_dir_all(download_dir);
+                                // ── Full style-learning pass ──────────────────────────────────────
+                                // Run video_style_learner on the entire Download folder.
+                                // Existing videos use their cached profiles (instant, no XP).
+                                // The newly downloaded file gets real scene detection + XP.
+                                // Eviction happens AFTER the new video is fully memorized.
+                                let mut brain_lock = brain.lock().await;
 
-                                let existing_path =
-                                    expected_download_path(download_dir, &source.title);
-                                if existing_path.exists() {
+                                let learn_started = std::time::Instant::now();
+                                let result = crate::agent::video_style_learner::learn_from_downloads(
+                                    &mut brain_lock,
+                                )
+                                .await;
+                                state.usage.record_processing(
+                                    today(),
+                                    learn_started.elapsed().as_secs_f64(),
+                                );
+
+                                if result.has_new {
+                                    crate::agent::video_style_learner::synthesise_and_save_strategy(
+                                        &result.profiles,
+                                    );
                                     info!(
-                                        "[LEARNER] ⏭️ Skipping already-present reference video: {}",
-                                        source.title
+                                        "[LEARNER] 🎨 EditingStrategy updated from {} profile(s)",
+                                        result.profiles.len()
                                     );
-                                    if let Some(url) = &source.original_url {
-                                        state.processed_urls.insert(url.clone());
-                                    }
-                                    continue;
                                 }
 
-                                // 1b. Proactive Eviction: Ensure we have room for 1 more video
-                                ensure_download_capacity(&mut state, download_dir).await;
+                                let speed = brain_lock.neuroplasticity.current_speed();
+                                let level = brain_lock.neuroplasticity.adaptation_level();
+                                let sleep_duration =
+                                    brain_lock.neuroplasticity.adaptive_delay_secs(30);
 
-                                let browser = source_tools::detect_browser();
-                                let download_result = source_tools::download_youtube(
-                                    source.original_url.as_deref().unwrap_or(""),
-                                    download_dir,
-                                    browser.as_deref(),
-                                )
-                                .await
-                                .map_err(|e| e.to_string());
-
-                                match download_result {
-                                    Ok(downloaded) => {
-                                        // 1c. Safety Check File
-                                        if let Err(e) = crate::agent::download_guard::DownloadGuard::validate_downloaded_file(&downloaded.local_path) {
-                                            error!("[LEARNER] 🛡️ Downloaded file rejected: {}", e);
-                                            let _ = std::fs::remove_file(&downloaded.local_path);
-                                            continue;
-                                        }
-
-                                        info!(
-                                            "[LEARNER] 🎓 New video acquired: '{}'",
-                                            downloaded.title
-                                        );
-
-                                        // ── Full style-learning pass ──────────────────────────────────────
-                                        // Run video_style_learner on the entire Download folder.
-                                        // Existing videos use their cached profiles (instant, no XP).
-                                        // The newly downloaded file gets real scene detection + XP.
-                                        // Eviction happens AFTER the new video is fully memorized.
-                                        let mut brain_lock = brain.lock().await;
-
-                                        let result = crate::agent::video_style_learner::learn_from_downloads(
-                                            &mut brain_lock,
-                                        )
-                                        .await;
-
-                                        if result.has_new {
-                                            crate::agent::video_style_learner::synthesise_and_save_strategy(
-                                                &result.profiles,
-                                            );
-                                            info!(
-                                                "[LEARNER] 🎨 EditingStrategy updated from {} profile(s)",
-                                                result.profiles.len()
-                                            );
-                                        }
-
-                                        let speed = brain_lock.neuroplasticity.current_speed();
-                                        let level = brain_lock.neuroplasticity.adaptation_level();
-                                        let sleep_duration =
-                                            brain_lock.neuroplasticity.adaptive_delay_secs(30);
-
-                                        drop(brain_lock);
-                                        // ── End style-learning pass ───────────────────────────────────────
-
-                                        // Mark URL as processed so we never re-download it
-                                        if let Some(url) = &source.original_url {
-                                            state.processed_urls.insert(url.clone());
-                                        }
-
-                                        // ── Record the new video in state tracking ──────────────────────────
-                                        let new_path_str =
-                                            downloaded.local_path.to_string_lossy().to_string();
-                                        let new_score = result
-                                            .profiles
-                                            .iter()
-                                            .find(|p| p.path == new_path_str)
-                                            .map(|p| p.outcome_xp * 5.0)
-                                            .unwrap_or(4.0);
-                                        state.downloaded_videos.push(VideoRecord {
-                                            path: new_path_str.clone(),
-                                            score: new_score,
-                                        });
-
-                                        state.save(&instance_id);
-
-                                        info!(
-                                            "[LEARNER] ✅ '{}' learned & memorized (Speed: {:.1}× - {})",
-                                            downloaded.title, speed, level
-                                        );
-
-                                        // Adaptive sleep — release locks first
-                                        drop(state);
-
-                                        info!(
-                                            "[LEARNER] 💤 Resting for {}s (Adaptive)",
-                                            sleep_duration
-                                        );
-                                        tokio::time::sleep(Duration::from_secs(sleep_duration))
-                                            .await;
-
-                                        // Re-lock state for loop continuation
-                                        state = state_arc.lock().await;
-                                    }
-                                    Err(e) => {
-                                        error!("[LEARNER] Failed download: {}", e);
-                                    }
+                                drop(brain_lock);
+                                // ── End style-learning pass ───────────────────────────────────────
+
+                                // Mark URL as processed so we never re-download it
+                                if let Some(url) = &source.original_url {
+                                    state.processed_urls.insert(url.clone());
                                 }
+
+                                // ── Record the new video in state tracking ──────────────────────────
+                                let new_path_str =
+                                    downloaded.local_path.to_string_lossy().to_string();
+                                let new_score = result
+                                    .profiles
+                                    .iter()
+                                    .find(|p| p.path == new_path_str)
+                                    .map(|p| p.outcome_xp * 5.0)
+                                    .unwrap_or(4.0);
+                                state.downloaded_videos.push(VideoRecord {
+                                    path: new_path_str.clone(),
+                                    score: new_score,
+                                });
+
+                                state.save(&instance_id);
+
+                                info!(
+                                    "[LEARNER] ✅ '{}' learned & memorized (Speed: {:.1}× - {})",
+                                    downloaded.title, speed, level
+                                );
+
+                                // Adaptive sleep — release locks first
+                                drop(state);
+
+                                info!(
+                                    "[LEARNER] 💤 Resting for {}s (Adaptive)",
+                                    sleep_duration
+                                );
+                                tokio::time::sleep(Duration::from_secs(sleep_duration))
+                                    .await;
+
+                                // Re-lock state for loop continuation
+                                state = state_arc.lock().await;
+                            }
+                            Err(e) => {
+                                error!("[LEARNER] Failed download: {}", e);
                             }
                         }
                     }
-                    Err(e) => error!("[LEARNER] Search failed for topic '{}': {}", topic, e),
                 }
 
-                // 2. Interleaved Code Analysis (Stealthy)
-                // Random chance or round-robin to scan a repo file
-                if cycle_count % 3 == 0 && !state.known_repos.is_empty() {
-                    let repo_url = &state.known_repos[state.repo_index % state.known_repos.len()];
-                    info!(
-                        "[LEARNER] 🕵️ Switching mode: Stealth Analysis on {}",
-                        repo_url
-                    );
-
-                    match scanner.scan_remote_code(repo_url).await {
-                        Ok(concept) => {
-                            info!(
-                                "[LEARNER] 💡 Discovered Logic: '{}' ({})",
-                                concept.logic_summary, concept.file_type
-                            );
-
-                            // Memorize the abstract concept
-                            let mut brain_lock = brain.lock().await;
-                            // We map this to a "Conceptual" pattern
-                            let pattern = crate::agent::learning::EditingPattern {
-                                intent_tag: format!("algo_{}", concept.file_type),
-                                avg_scene_duration: 0.0, // N/A
-                                transition_speed: 1.0,
-                                music_sync_strictness: 0.0,
-                                color_grade_style: "algorithmic".to_string(),
-                                success_rating: 5,
-                                source_video: Some(repo_url.clone()),
-                                kept_ratio: 0.5,
-                                outcome_xp: 0.9,
-                            };
-                            brain_lock
-                                .learning_kernel
-                                .lock()
-                                .await
-                                .memorize(&format!("algo_{}", concept.file_type), pattern);
-                            brain_lock.neuroplasticity.record_success();
-                            info!("[LEARNER] 🧠 Integrated concept into neuroplasticity network.");
-
-                            // Advance repo index
-                            state.repo_index += 1;
-                            state.save(&instance_id);
+                // 2. Interleaved Code Analysis (sandboxed, allow-listed repos only)
+                if !plan.repos.is_empty() {
+                    let scan_config = ScannerConfig::load(&instance_id);
+                    for repo_url in &plan.repos {
+                        if !is_running.load(Ordering::SeqCst) {
+                            break;
                         }
-                        Err(e) => {
-                            warn!("[LEARNER] Analysis skipped (Stealth Mode/Limit): {}", e);
+                        state.repo_index += 1;
+                        info!("[LEARNER] 🕵️ Switching mode: Code Analysis on {}", repo_url);
+
+                        match scanner.scan_repo(repo_url, &scan_config).await {
+                            Ok(notes) => {
+                                if notes.is_empty() {
+                                    continue;
+                                }
+                                let mut brain_lock = brain.lock().await;
+                                for note in &notes {
+                                    info!(
+                                        "[LEARNER] 💡 Discovered Logic in {}: '{}'",
+                                        note.file, note.technique
+                                    );
+                                    let file_type = Path::new(&note.file)
+                                        .extension()
+                                        .and_then(|e| e.to_str())
+                                        .unwrap_or("unknown")
+                                        .to_string();
+                                    // We map this to a "Conceptual" pattern
+                                    let pattern = crate::agent::learning::EditingPattern {
+                                        intent_tag: format!("algo_{}", file_type),
+                                        avg_scene_duration: 0.0, // N/A
+                                        transition_speed: 1.0,
+                                        music_sync_strictness: 0.0,
+                                        color_grade_style: "algorithmic".to_string(),
+                                        success_rating: 5,
+                                        source_video: Some(repo_url.clone()),
+                                        kept_ratio: 0.5,
+                                        outcome_xp: 0.9,
+                                    };
+                                    brain_lock
+                                        .learning_kernel
+                                        .lock()
+                                        .await
+                                        .memorize(&format!("algo_{}", file_type), pattern);
+                                }
+                                brain_lock.neuroplasticity.record_success();
+                                drop(brain_lock);
+                                code_scanner::append_notes(&instance_id, &notes);
+                                info!("[LEARNER] 🧠 Integrated {} concept(s) into neuroplasticity network.", notes.len());
+                            }
+                            Err(e) => {
+                                warn!("[LEARNER] Analysis skipped: {}", e);
+                            }
                         }
                     }
+                    state.save(&instance_id);
                 }
 
                 // 3. Interleaved Theory Learning (Wikipedia)
-                if cycle_count % 3 == 1 {
-                    let wiki_url = &wikis[cycle_count % wikis.len()];
+                if let Some(wiki_url) = &plan.theory {
                     info!("[LEARNER] 📖 Studying Theory: {}", wiki_url);
 
                     // Use Wikipedia REST API for plain text extract (Send-safe, no scraper)
@@ -431,14 +906,13 @@ impl AutonomousLearner {
                 }
 
                 // 4. Free Web Scouting (DuckDuckGo Lite)
-                if cycle_count % 5 == 2 {
-                    let search_topic = format!("{} editing techniques tips blog", topic);
+                if let Some(search_topic) = &plan.web_search {
                     info!(
                         "[LEARNER] 🕵️ Scouting the web for keywords: '{}'",
                         search_topic
                     );
 
-                    match source_tools::web_search(&search_topic).await {
+                    match source_tools::web_search(search_topic).await {
                         Ok(results) => {
                             for (res_title, snippet) in results {
                                 info!("[LEARNER] 📖 Scouted: {} - {}", res_title, snippet);
@@ -468,15 +942,24 @@ impl AutonomousLearner {
                 state.topic_index += 1;
                 state.save(&instance_id);
 
+                let rest = cycle_rest(rate_limit_streak);
                 info!(
-                    "[LEARNER] ✅ Cycle #{} Summary: Topic '{}' processed. Next cycle in 10m.",
-                    cycle_count, topic
+                    "[LEARNER] ✅ Cycle #{} Summary: Topic '{}' processed. Next cycle in {}m.",
+                    cycle_count,
+                    topic,
+                    rest.as_secs() / 60
                 );
+                if let Some(bus) = &events {
+                    bus.publish(SynoidEvent::LearnerCycle {
+                        cycle: cycle_count,
+                        topic: topic.clone(),
+                    });
+                }
 
                 // Release state lock before long sleep
                 drop(state);
 
-                tokio::time::sleep(Duration::from_secs(600)).await;
+                tokio::time::sleep(rest).await;
             }
 
             info!("[LEARNER] 🛑 Loop Stopped");
@@ -491,6 +974,35 @@ impl AutonomousLearner {
         self.is_running.load(Ordering::SeqCst)
     }
 
+    /// Approve the pending supervised-mode plan for `instance_id`.
+    pub fn approve_pending_plan(instance_id: &str) -> std::io::Result<()> {
+        ApprovalGate::new(instance_id, true).approve()
+    }
+
+    /// What the learner has been up to: pending plan, today's budget use and
+    /// the structured notes from code scanning.
+    pub fn report(instance_id: &str) -> LearnerReport {
+        // Read directly rather than via `load`, which logs on every call
+        let usage = fs::read_to_string(LearnerState::path(instance_id))
+            .ok()
+            .and_then(|d| serde_json::from_str::<LearnerState>(&d).ok())
+            .map(|s| s.usage)
+            .unwrap_or_default();
+        let today_usage = usage.day == Some(today());
+        LearnerReport {
+            pending_plan: Self::pending_plan(instance_id),
+            downloads_today: if today_usage { usage.downloads } else { 0 },
+            process_minutes_today: if today_usage { usage.process_secs / 60.0 } else { 0.0 },
+            code_notes: code_scanner::load_notes(instance_id),
+        }
+    }
+
+    /// The plan currently awaiting approval, if any.
+    pub fn pending_plan(instance_id: &str) -> Option<CyclePlan> {
+        let path = ApprovalGate::new(instance_id, true).plan_path();
+        serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+    }
+
     /// NEW: Learn from a recently completed manual or queued edit job
     pub async fn learn_from_edit(
         &self,
@@ -567,6 +1079,39 @@ impl AutonomousLearner {
 }
 
 /// Proactively ensures we have space for a new download by evicting the oldest learned videos.
+/// Memorize each technique in a research report, the way web-scouted tips
+/// are memorized.
+async fn study_research_report(brain: &Arc<Mutex<Brain>>, report: &ResearchReport) {
+    let brain_lock = brain.lock().await;
+    let mut kernel = brain_lock.learning_kernel.lock().await;
+    let techniques = report.techniques();
+    for (i, technique) in techniques.iter().enumerate() {
+        let tag = format!("research_{}_{}", report.topic.replace(' ', "_").to_lowercase(), i);
+        let source = report
+            .items
+            .iter()
+            .find(|item| item.techniques.contains(technique))
+            .map(|item| item.url.clone());
+        let pattern = crate::agent::learning::EditingPattern {
+            intent_tag: technique.clone(),
+            avg_scene_duration: 0.0,
+            transition_speed: 1.0,
+            music_sync_strictness: 0.0,
+            color_grade_style: "learned_from_research".to_string(),
+            success_rating: 4,
+            source_video: source,
+            kept_ratio: 0.5,
+            outcome_xp: 0.75,
+        };
+        kernel.memorize(&tag, pattern);
+    }
+    info!(
+        "[LEARNER] 🎓 Studied {} technique(s) from the '{}' research report",
+        techniques.len(),
+        report.topic
+    );
+}
+
 async fn ensure_download_capacity(state: &mut LearnerState, download_dir: &Path) {
     let max_videos = crate::agent::video_style_learner::MAX_VIDEOS;
 
@@ -654,4 +1199,114 @@ mod tests {
             PathBuf::from(r"D:\SYNOID\Download\So You Want To See The World_ _Travel Film_.mp4")
         );
     }
+
+    #[test]
+    fn rate_limited_searches_stretch_the_rest() {
+        assert_eq!(cycle_rest(0), Duration::from_secs(600));
+        assert_eq!(cycle_rest(1), Duration::from_secs(1200));
+        assert_eq!(cycle_rest(3), Duration::from_secs(4800));
+        assert_eq!(cycle_rest(5), MAX_RATE_LIMITED_REST);
+        assert_eq!(cycle_rest(40), MAX_RATE_LIMITED_REST);
+    }
+
+    fn source(title: &str, url: &str, duration: f64) -> source_tools::SourceInfo {
+        source_tools::SourceInfo {
+            title: title.to_string(),
+            duration,
+            width: 1920,
+            height: 1080,
+            local_path: PathBuf::new(),
+            original_url: Some(url.to_string()),
+            format: "mp4".to_string(),
+            provider: "yt-dlp".to_string(),
+        }
+    }
+
+    #[test]
+    fn supervised_mode_skips_downloads_until_approved() {
+        let dir = std::env::temp_dir().join(format!("synoid_gate_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let results = vec![
+            source("Cinematic Pacing", "https://youtu.be/a", 300.0),
+            source("Too Short", "https://youtu.be/b", 30.0),
+        ];
+        let plan = plan_cycle(1, "editing", &results, &HashSet::new());
+        assert_eq!(plan.downloads.len(), 1);
+        assert!(plan.summary().contains("Cinematic Pacing"));
+
+        let gate = ApprovalGate::in_dir(dir.clone(), true);
+        assert!(!gate.permits(&plan));
+        assert!(gate.plan_path().exists());
+
+        gate.approve().unwrap();
+        assert!(gate.permits(&plan));
+        // Approval is one-shot: the next cycle needs a fresh one.
+        assert!(!gate.permits(&plan));
+
+        // An approval only covers the plan it was granted for...
+        gate.approve().unwrap();
+        let other = plan_cycle(2, "color grading", &results, &HashSet::new());
+        assert!(!gate.permits(&other));
+        // ...but survives a re-plan that would fetch the same videos.
+        assert!(!gate.permits(&plan));
+        gate.approve().unwrap();
+        let replanned = plan_cycle(3, "editing", &results, &HashSet::new());
+        assert!(gate.permits(&replanned));
+
+        assert!(ApprovalGate::in_dir(dir.clone(), false).permits(&plan));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn supervised_mode_gates_clones_and_fetches_too() {
+        let dir = std::env::temp_dir().join(format!("synoid_gate_fetch_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let gate = ApprovalGate::in_dir(dir.clone(), true);
+
+        let idle = plan_cycle(1, "editing", &[], &HashSet::new());
+        assert!(idle.is_empty());
+        assert!(gate.permits(&idle));
+
+        let mut plan = idle.clone();
+        plan.theory = Some("https://en.wikipedia.org/wiki/Film_editing".to_string());
+        assert!(!gate.permits(&plan));
+        assert!(gate.plan_path().exists());
+
+        // Polling waits on the published plan without touching it.
+        assert!(!gate.poll(&plan));
+        assert!(gate.plan_path().exists());
+        gate.approve().unwrap();
+
+        // An approval does not stretch to a plan that also clones.
+        let mut cloning = plan.clone();
+        cloning.repos = vec!["https://github.com/xingxerx/SYNOID".to_string()];
+        assert_ne!(cloning.hash(), plan.hash());
+        assert!(cloning.summary().contains("will clone"));
+
+        assert!(gate.poll(&plan));
+        assert!(!gate.plan_path().exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn download_budget_pauses_until_next_day() {
+        let budget = LearnerBudget {
+            max_downloads_per_day: Some(2),
+            max_process_minutes_per_day: None,
+        };
+        let day1 = chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let day2 = day1.succ_opt().unwrap();
+        let mut usage = DailyUsage::default();
+
+        assert!(!usage.downloads_exhausted(&budget, day1));
+        usage.record_download(day1);
+        usage.record_download(day1);
+        assert!(usage.downloads_exhausted(&budget, day1));
+        // Still paused for the rest of the day.
+        assert!(usage.downloads_exhausted(&budget, day1));
+
+        assert!(!usage.downloads_exhausted(&budget, day2));
+        assert_eq!(usage.downloads, 0);
+        assert!(!usage.processing_exhausted(&budget, day2));
+    }
 }
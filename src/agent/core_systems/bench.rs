@@ -0,0 +1,744 @@
+// SYNOID Bench — the regression-tracking benchmark suite behind `bench`
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Generates synthetic fixtures (a 1-minute and a 10-minute clip: a test
+// pattern that inverts every 5 seconds, over a tone that plays for 7 seconds
+// and rests for 3), runs a fixed set of operations on each and records wall
+// time, the peak resident memory of SYNOID and every process it spawned, and
+// the size of what the stage wrote. Reports are JSON so runs can be diffed;
+// `compare` lines one up against a stored baseline as percentage deltas.
+//
+// Every stage works on a fresh scratch copy of its fixture so no cache from
+// an earlier stage or run makes it look faster. Stages that need a GPU are
+// skipped, with the reason recorded, on machines without one.
+
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::smart_editor::{self, EditIntent, RenderMode};
+use crate::agent::tools::production_tools;
+use crate::agent::tools::transcription::{self, TranscriptSegment, TranscriptionEngine};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Bumped whenever the fixtures change, so cached ones are regenerated and
+/// nobody compares timings across different inputs.
+const FIXTURE_VERSION: u32 = 1;
+/// How often the process tree's memory is sampled.
+const RSS_SAMPLE_EVERY: Duration = Duration::from_millis(200);
+/// Wall-time deltas above this are flagged in the comparison.
+pub const DEFAULT_REGRESSION_PCT: f64 = 10.0;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Fixtures and stages
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Fixture {
+    #[serde(rename = "1min")]
+    OneMinute,
+    #[serde(rename = "10min")]
+    TenMinutes,
+}
+
+impl Fixture {
+    pub const ALL: [Fixture; 2] = [Self::OneMinute, Self::TenMinutes];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::OneMinute => "1min",
+            Self::TenMinutes => "10min",
+        }
+    }
+
+    pub fn secs(self) -> u32 {
+        match self {
+            Self::OneMinute => 60,
+            Self::TenMinutes => 600,
+        }
+    }
+
+    /// Where the tone plays: 7 seconds of every 10. Stands in for a
+    /// transcript so silence removal has speech to keep without depending on
+    /// what Whisper makes of a sine wave.
+    pub fn speech(self) -> Vec<TranscriptSegment> {
+        (0..self.secs() / 10)
+            .map(|i| TranscriptSegment {
+                start: i as f64 * 10.0,
+                end: i as f64 * 10.0 + 7.0,
+                text: "benchmark tone".to_string(),
+                words: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Stage {
+    SceneDetection,
+    /// Whisper `tiny`, local, on the CPU.
+    Transcription,
+    /// Whisper `tiny`, local, on the GPU.
+    TranscriptionGpu,
+    /// `smart_edit` with a "remove silence" intent.
+    SilenceRemoval,
+    Compress,
+    ThumbnailStrip,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 6] = [
+        Self::SceneDetection,
+        Self::Transcription,
+        Self::TranscriptionGpu,
+        Self::SilenceRemoval,
+        Self::Compress,
+        Self::ThumbnailStrip,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::SceneDetection => "scene_detection",
+            Self::Transcription => "transcription",
+            Self::TranscriptionGpu => "transcription_gpu",
+            Self::SilenceRemoval => "silence_removal",
+            Self::Compress => "compress",
+            Self::ThumbnailStrip => "thumbnail_strip",
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Report
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StageStatus {
+    Ok,
+    Skipped,
+    Failed,
+}
+
+/// One stage on one fixture.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageResult {
+    pub stage: Stage,
+    pub fixture: Fixture,
+    pub status: StageStatus,
+    /// Why it was skipped or failed, or a detail such as the scene count.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub wall_secs: Option<f64>,
+    /// Largest combined RSS of SYNOID and its child processes.
+    #[serde(default)]
+    pub peak_rss_mb: Option<f64>,
+    #[serde(default)]
+    pub output_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostInfo {
+    pub os: String,
+    pub arch: String,
+    pub cpus: usize,
+    pub gpu: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub synoid_version: String,
+    pub created_at: String,
+    pub host: HostInfo,
+    pub results: Vec<StageResult>,
+}
+
+impl BenchReport {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        serde_json::from_str(&text)
+            .map_err(|e| format!("{} is not a bench report: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+    }
+
+    /// One line per stage, for the terminal.
+    pub fn summary(&self) -> String {
+        let mut out = format!(
+            "{:<18} {:<6} {:<8} {:>9} {:>10} {:>10}\n",
+            "stage", "input", "status", "wall", "peak RSS", "output"
+        );
+        for r in &self.results {
+            let status = match r.status {
+                StageStatus::Ok => "ok",
+                StageStatus::Skipped => "skipped",
+                StageStatus::Failed => "FAILED",
+            };
+            out.push_str(&format!(
+                "{:<18} {:<6} {:<8} {:>9} {:>10} {:>10}",
+                r.stage.name(),
+                r.fixture.name(),
+                status,
+                r.wall_secs
+                    .map_or("-".to_string(), |s| format!("{:.2}s", s)),
+                r.peak_rss_mb
+                    .map_or("-".to_string(), |m| format!("{:.0} MB", m)),
+                r.output_bytes.map_or("-".to_string(), format_bytes),
+            ));
+            if let Some(note) = &r.note {
+                out.push_str(&format!("  {}", note));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 20 => format!("{:.1} MB", b as f64 / (1u64 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KB", b as f64 / 1024.0),
+        b => format!("{} B", b),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Baseline comparison
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// How a stage moved against the baseline, in percent (positive = more).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageDelta {
+    pub stage: Stage,
+    pub fixture: Fixture,
+    pub wall_pct: Option<f64>,
+    pub rss_pct: Option<f64>,
+    pub size_pct: Option<f64>,
+}
+
+fn pct(current: Option<f64>, baseline: Option<f64>) -> Option<f64> {
+    match (current, baseline) {
+        (Some(c), Some(b)) if b > 0.0 => Some((c - b) / b * 100.0),
+        _ => None,
+    }
+}
+
+/// Deltas for every stage that ran in both reports. Skipped and failed
+/// stages have nothing to compare.
+pub fn compare(current: &BenchReport, baseline: &BenchReport) -> Vec<StageDelta> {
+    current
+        .results
+        .iter()
+        .filter(|r| r.status == StageStatus::Ok)
+        .filter_map(|r| {
+            let base = baseline.results.iter().find(|b| {
+                b.stage == r.stage && b.fixture == r.fixture && b.status == StageStatus::Ok
+            })?;
+            Some(StageDelta {
+                stage: r.stage,
+                fixture: r.fixture,
+                wall_pct: pct(r.wall_secs, base.wall_secs),
+                rss_pct: pct(r.peak_rss_mb, base.peak_rss_mb),
+                size_pct: pct(
+                    r.output_bytes.map(|b| b as f64),
+                    base.output_bytes.map(|b| b as f64),
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Stages whose wall time grew by more than `max_pct`.
+pub fn regressions(deltas: &[StageDelta], max_pct: f64) -> Vec<&StageDelta> {
+    deltas
+        .iter()
+        .filter(|d| d.wall_pct.is_some_and(|p| p > max_pct))
+        .collect()
+}
+
+/// The comparison table, flagging wall-time regressions above `flag_pct`.
+pub fn render_comparison(
+    current: &BenchReport,
+    baseline: &BenchReport,
+    deltas: &[StageDelta],
+    flag_pct: f64,
+) -> String {
+    let mut out = format!(
+        "vs baseline from {} (SYNOID {})\n",
+        baseline.created_at, baseline.synoid_version
+    );
+    if current.host != baseline.host {
+        out.push_str(
+            "⚠️  The baseline was recorded on a different machine; deltas are not like for like\n",
+        );
+    }
+    let show = |p: Option<f64>| p.map_or("-".to_string(), |p| format!("{:+.1}%", p));
+    for d in deltas {
+        let flag = if d.wall_pct.is_some_and(|p| p > flag_pct) {
+            "  ⚠️ slower"
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "{:<18} {:<6} wall {:>8}   rss {:>8}   output {:>8}{}\n",
+            d.stage.name(),
+            d.fixture.name(),
+            show(d.wall_pct),
+            show(d.rss_pct),
+            show(d.size_pct),
+            flag
+        ));
+    }
+    if deltas.is_empty() {
+        out.push_str("No stage ran in both reports\n");
+    }
+    out
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Running
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub struct BenchOptions {
+    /// Fixtures, scratch space and the default report paths.
+    pub dir: PathBuf,
+    pub fixtures: Vec<Fixture>,
+}
+
+impl BenchOptions {
+    /// `<cache_dir>/bench`, both fixtures.
+    pub fn configured() -> Self {
+        Self {
+            dir: PathBuf::from(crate::config::current().cache_dir).join("bench"),
+            fixtures: Fixture::ALL.to_vec(),
+        }
+    }
+}
+
+/// What a finished stage produced.
+struct Produced {
+    bytes: Option<u64>,
+    note: Option<String>,
+}
+
+/// Run every stage on every fixture in `options`.
+pub async fn run(options: &BenchOptions) -> Result<BenchReport, String> {
+    std::fs::create_dir_all(&options.dir)
+        .map_err(|e| format!("Cannot create {}: {}", options.dir.display(), e))?;
+    let gpu = crate::gpu_backend::get_gpu_context().await.has_gpu();
+    let whisper = prepare_whisper().await;
+
+    let mut results = Vec::new();
+    for &fixture in &options.fixtures {
+        let source = ensure_fixture(&options.dir, fixture).await?;
+        for stage in Stage::ALL {
+            let skip = match stage {
+                Stage::TranscriptionGpu if !gpu => Some("no NVIDIA GPU".to_string()),
+                Stage::TranscriptionGpu if !cfg!(feature = "cuda") => {
+                    Some("built without the cuda feature".to_string())
+                }
+                Stage::Transcription | Stage::TranscriptionGpu => whisper.clone().err(),
+                _ => None,
+            };
+            let result = match skip {
+                Some(reason) => {
+                    info!(
+                        "[BENCH] {} on {}: skipped ({})",
+                        stage.name(),
+                        fixture.name(),
+                        reason
+                    );
+                    StageResult {
+                        stage,
+                        fixture,
+                        status: StageStatus::Skipped,
+                        note: Some(reason),
+                        wall_secs: None,
+                        peak_rss_mb: None,
+                        output_bytes: None,
+                    }
+                }
+                None => measure(&options.dir, stage, fixture, &source).await,
+            };
+            results.push(result);
+        }
+    }
+
+    Ok(BenchReport {
+        synoid_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        host: HostInfo {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            cpus: num_cpus::get(),
+            gpu,
+        },
+        results,
+    })
+}
+
+/// Make sure the `tiny` Whisper model is cached before anything is timed.
+async fn prepare_whisper() -> Result<(), String> {
+    if transcription::model_path("tiny").exists() {
+        return Ok(());
+    }
+    info!("[BENCH] Downloading the tiny Whisper model...");
+    let models_dir = crate::config::current().models_dir();
+    transcription::download_model(&models_dir, "tiny", |_, _| {})
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("tiny Whisper model unavailable: {}", e))
+}
+
+/// The cached fixture, generated on first use.
+async fn ensure_fixture(dir: &Path, fixture: Fixture) -> Result<PathBuf, String> {
+    let path = dir.join(format!(
+        "fixture_v{}_{}.mp4",
+        FIXTURE_VERSION,
+        fixture.name()
+    ));
+    if path.exists() {
+        return Ok(path);
+    }
+    info!("[BENCH] Generating the {} fixture...", fixture.name());
+    let secs = fixture.secs().to_string();
+    let partial = path.with_extension("part.mp4");
+    let status = proc::ffmpeg(ProcClass::Render)
+        .stealth()
+        .args(["-y", "-nostdin", "-v", "error", "-f", "lavfi", "-i"])
+        .arg(format!("testsrc2=size=1280x720:rate=30:duration={}", secs))
+        .args(["-f", "lavfi", "-i"])
+        .arg(format!(
+            "aevalsrc='if(lt(mod(t,10),7),0.25*sin(2*PI*440*t),0)':s=48000:d={}",
+            secs
+        ))
+        // Inverting every 5 seconds gives scene detection real cuts
+        .args(["-vf", "negate=enable='lt(mod(t,10),5)'"])
+        .args([
+            "-c:v", "libx264", "-preset", "veryfast", "-crf", "23", "-pix_fmt", "yuv420p",
+        ])
+        .args(["-c:a", "aac", "-b:a", "128k", "-shortest"])
+        .arg(&partial)
+        .status()
+        .await
+        .map_err(|e| format!("Cannot run ffmpeg: {}", e))?;
+    if !status.success() {
+        let _ = std::fs::remove_file(&partial);
+        return Err(format!(
+            "ffmpeg could not generate the {} fixture",
+            fixture.name()
+        ));
+    }
+    std::fs::rename(&partial, &path).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+/// Run one stage on a scratch copy of `source`, timing it and sampling
+/// memory throughout.
+async fn measure(dir: &Path, stage: Stage, fixture: Fixture, source: &Path) -> StageResult {
+    let scratch = dir
+        .join("run")
+        .join(format!("{}_{}", fixture.name(), stage.name()));
+    let _ = std::fs::remove_dir_all(&scratch);
+    let input = scratch.join("input.mp4");
+    let staged = std::fs::create_dir_all(&scratch).and_then(|_| {
+        std::fs::hard_link(source, &input).or_else(|_| std::fs::copy(source, &input).map(|_| ()))
+    });
+
+    info!("[BENCH] {} on {}...", stage.name(), fixture.name());
+    let sampler = RssSampler::start();
+    let started = Instant::now();
+    let outcome = match staged {
+        Ok(()) => run_stage(stage, fixture, &input, &scratch).await,
+        Err(e) => Err(format!("Cannot stage the fixture: {}", e)),
+    };
+    let wall = started.elapsed().as_secs_f64();
+    let peak = sampler.stop();
+    let _ = std::fs::remove_dir_all(&scratch);
+
+    match outcome {
+        Ok(produced) => StageResult {
+            stage,
+            fixture,
+            status: StageStatus::Ok,
+            note: produced.note,
+            wall_secs: Some(wall),
+            peak_rss_mb: (peak > 0).then(|| peak as f64 / (1u64 << 20) as f64),
+            output_bytes: produced.bytes,
+        },
+        Err(e) => {
+            info!(
+                "[BENCH] {} on {} failed: {}",
+                stage.name(),
+                fixture.name(),
+                e
+            );
+            StageResult {
+                stage,
+                fixture,
+                status: StageStatus::Failed,
+                note: Some(e),
+                wall_secs: None,
+                peak_rss_mb: None,
+                output_bytes: None,
+            }
+        }
+    }
+}
+
+async fn run_stage(
+    stage: Stage,
+    fixture: Fixture,
+    input: &Path,
+    scratch: &Path,
+) -> Result<Produced, String> {
+    let size = |p: &Path| std::fs::metadata(p).map(|m| m.len()).ok();
+    match stage {
+        Stage::SceneDetection => {
+            let scenes = smart_editor::detect_scenes(input, 0.3)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(Produced {
+                bytes: None,
+                note: Some(format!("{} scenes", scenes.len())),
+            })
+        }
+        Stage::Transcription | Stage::TranscriptionGpu => {
+            let wav = scratch.join("audio.wav");
+            production_tools::extract_audio_wav(input, &wav)
+                .await
+                .map_err(|e| e.to_string())?;
+            let engine = TranscriptionEngine::new(Some("tiny".to_string()))
+                .await
+                .map_err(|e| e.to_string())?;
+            let segments = engine
+                .transcribe_local(&wav, stage == Stage::TranscriptionGpu)
+                .await
+                .map_err(|e| e.to_string())?;
+            let transcript = scratch.join("transcript.json");
+            let json = serde_json::to_string(&segments).map_err(|e| e.to_string())?;
+            std::fs::write(&transcript, json).map_err(|e| e.to_string())?;
+            Ok(Produced {
+                bytes: size(&transcript),
+                note: Some(format!("{} segments", segments.len())),
+            })
+        }
+        Stage::SilenceRemoval => {
+            let output = scratch.join("edited.mp4");
+            let mut intent = EditIntent::from_text("remove silence");
+            intent.enable_subtitles = false;
+            intent.censor_profanity = false;
+            smart_editor::smart_edit_with_intent(
+                input,
+                intent,
+                "bench: remove silence",
+                &output,
+                None,
+                None,
+                Some(fixture.speech()),
+                None,
+                RenderMode::Final,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            Ok(Produced {
+                bytes: size(&output),
+                note: None,
+            })
+        }
+        Stage::Compress => {
+            let output = scratch.join("compressed.mp4");
+            let target_mb = (size(input).unwrap_or(0) as f64 / (1u64 << 20) as f64 / 4.0).max(1.0);
+            production_tools::compress_video(input, target_mb, &output)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(Produced {
+                bytes: size(&output),
+                note: Some(format!("target {:.1} MB", target_mb)),
+            })
+        }
+        Stage::ThumbnailStrip => {
+            let output = scratch.join("strip.jpg");
+            production_tools::thumbnail_strip(input, &output, 10, 192)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(Produced {
+                bytes: size(&output),
+                note: None,
+            })
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Peak memory
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Samples the resident memory of this process plus all its descendants
+/// (ffmpeg and friends) on a background thread, keeping the peak.
+struct RssSampler {
+    stop: Arc<AtomicBool>,
+    peak: Arc<AtomicU64>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl RssSampler {
+    fn start() -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let peak = Arc::new(AtomicU64::new(0));
+        let thread = sysinfo::get_current_pid().ok().map(|root| {
+            let (stop, peak) = (stop.clone(), peak.clone());
+            std::thread::spawn(move || {
+                let mut sys = sysinfo::System::new();
+                loop {
+                    sys.refresh_processes();
+                    peak.fetch_max(tree_rss(&sys, root), Ordering::Relaxed);
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    std::thread::sleep(RSS_SAMPLE_EVERY);
+                }
+            })
+        });
+        Self { stop, peak, thread }
+    }
+
+    /// Stop sampling and return the peak in bytes (0 if it never sampled).
+    fn stop(mut self) -> u64 {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        self.peak.load(Ordering::Relaxed)
+    }
+}
+
+/// RSS of `root` and every process below it. Threads are listed as
+/// processes on Linux and share their parent's memory, so they are skipped.
+fn tree_rss(sys: &sysinfo::System, root: sysinfo::Pid) -> u64 {
+    let processes = sys.processes();
+    let mut tree = vec![root];
+    let mut total = 0;
+    let mut i = 0;
+    while let Some(&pid) = tree.get(i) {
+        if let Some(p) = processes.get(&pid) {
+            total += p.memory();
+        }
+        tree.extend(
+            processes
+                .iter()
+                .filter(|(_, p)| p.parent() == Some(pid) && p.thread_kind().is_none())
+                .map(|(&child, _)| child),
+        );
+        i += 1;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(stage: Stage, fixture: Fixture, wall: f64, rss: f64, bytes: u64) -> StageResult {
+        StageResult {
+            stage,
+            fixture,
+            status: StageStatus::Ok,
+            note: None,
+            wall_secs: Some(wall),
+            peak_rss_mb: Some(rss),
+            output_bytes: Some(bytes),
+        }
+    }
+
+    fn report(results: Vec<StageResult>) -> BenchReport {
+        BenchReport {
+            synoid_version: "2.0.0".to_string(),
+            created_at: "2026-10-17T00:00:00+00:00".to_string(),
+            host: HostInfo {
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                cpus: 8,
+                gpu: false,
+            },
+            results,
+        }
+    }
+
+    #[test]
+    fn deltas_compare_stages_that_ran_in_both_reports() {
+        let baseline = report(vec![
+            result(Stage::SceneDetection, Fixture::OneMinute, 4.0, 100.0, 0),
+            result(Stage::Compress, Fixture::OneMinute, 10.0, 200.0, 1000),
+            result(Stage::ThumbnailStrip, Fixture::OneMinute, 1.0, 50.0, 2000),
+        ]);
+        let mut skipped = result(Stage::ThumbnailStrip, Fixture::OneMinute, 0.0, 0.0, 0);
+        skipped.status = StageStatus::Skipped;
+        let current = report(vec![
+            result(Stage::SceneDetection, Fixture::OneMinute, 3.0, 100.0, 0),
+            result(Stage::Compress, Fixture::OneMinute, 12.5, 180.0, 1100),
+            result(Stage::Compress, Fixture::TenMinutes, 90.0, 220.0, 9000),
+            skipped,
+        ]);
+
+        let deltas = compare(&current, &baseline);
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[0].wall_pct, Some(-25.0));
+        // No output to compare against
+        assert_eq!(deltas[0].size_pct, None);
+        assert_eq!(deltas[1].wall_pct, Some(25.0));
+        assert_eq!(deltas[1].rss_pct, Some(-10.0));
+        assert_eq!(deltas[1].size_pct, Some(10.0));
+
+        let slow = regressions(&deltas, DEFAULT_REGRESSION_PCT);
+        assert_eq!(slow.len(), 1);
+        assert_eq!(slow[0].stage, Stage::Compress);
+        assert!(regressions(&deltas, 30.0).is_empty());
+
+        let table = render_comparison(&current, &baseline, &deltas, DEFAULT_REGRESSION_PCT);
+        assert!(
+            table.contains("compress           1min   wall   +25.0%"),
+            "{}",
+            table
+        );
+        assert!(table.contains("⚠️ slower"), "{}", table);
+        assert!(!table.contains("different machine"), "{}", table);
+    }
+
+    #[test]
+    fn reports_round_trip_through_json() {
+        let mut failed = result(Stage::Transcription, Fixture::TenMinutes, 0.0, 0.0, 0);
+        failed.status = StageStatus::Failed;
+        failed.note = Some("tiny Whisper model unavailable".to_string());
+        let original = report(vec![
+            result(Stage::SilenceRemoval, Fixture::OneMinute, 12.0, 300.0, 4096),
+            failed,
+        ]);
+        let json = serde_json::to_string(&original).unwrap();
+        assert!(json.contains(r#""stage":"silence_removal","fixture":"1min","status":"ok""#));
+        let back: BenchReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, original);
+        assert!(back.summary().contains("FAILED"));
+    }
+
+    #[test]
+    fn the_fixture_transcript_covers_the_tone() {
+        let speech = Fixture::OneMinute.speech();
+        assert_eq!(speech.len(), 6);
+        assert_eq!((speech[1].start, speech[1].end), (10.0, 17.0));
+        assert_eq!(Fixture::TenMinutes.speech().len(), 60);
+    }
+}
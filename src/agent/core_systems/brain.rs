@@ -25,6 +25,8 @@ pub enum Intent {
     CreateEdit(String, String),
     Research(String),
     DiscoverFile(String),
+    /// A registered processing tool: (tool, input path, params)
+    RunTool(String, String, crate::agent::tool_registry::ToolParams),
 
     /// Complex creative request requiring MoE orchestration
     Orchestrate(String, Option<String>),
@@ -88,6 +90,27 @@ impl Brain {
         }
     }
 
+    /// A brain that reasons with `model` instead, for a single request. It
+    /// shares this brain's learned styles, motor cortex and GPU.
+    pub fn with_model(&self, model: &str) -> Self {
+        let mut brain = Self::new(&self._api_url, model, self.animator.clone());
+        brain.motor_cortex = self.motor_cortex.clone();
+        brain.learning_kernel = self.learning_kernel.clone();
+        brain.gpu = self.gpu;
+        brain
+    }
+
+    /// The brain's own model, for prompts that should skip intent routing.
+    pub fn agent(&self) -> SynoidAgent {
+        self._agent.clone()
+    }
+
+    /// Split a compound edit request into ordered steps; see
+    /// `smart_editor::steps`.
+    pub async fn decompose_edit_request(&self, text: &str) -> Vec<crate::agent::smart_editor::EditStep> {
+        crate::agent::smart_editor::steps::decompose(&self._agent, text).await
+    }
+
     pub async fn initialize_hive_mind(&mut self) -> Result<(), String> {
         self.hive_mind
             .refresh_models()
@@ -126,6 +149,16 @@ impl Brain {
     pub fn fast_classify(&self, request: &str) -> Intent {
         let req_lower = request.to_lowercase();
 
+        // 0. Registered tools, called explicitly: "<tool> <path> key=value ..."
+        if let Some(first) = req_lower.split_whitespace().next() {
+            if let Some(tool) = crate::agent::tool_registry::get_tool(first) {
+                let params = crate::agent::tool_registry::parse_params(request);
+                if let (Some(path), false) = (Self::extract_path(request), params.is_empty()) {
+                    return Intent::RunTool(tool.name().to_string(), path, params);
+                }
+            }
+        }
+
         // 1. YouTube Download Heuristics
         if (req_lower.contains("download") || req_lower.contains("get"))
             && (req_lower.contains("youtube") || req_lower.contains("http"))
@@ -347,9 +380,9 @@ impl Brain {
             Intent::ScanVideo(path) => {
                 info!("[BRAIN] ⚡ Fast-path activated: Visual Scan");
                 // Activate Vision Tools ONLY
-                use crate::agent::vision_tools;
+                use crate::agent::scan_cache;
                 let path = std::path::Path::new(&path);
-                match vision_tools::scan_visual(path).await {
+                match scan_cache::visual(path).await {
                     Ok(scenes) => {
                         self.neuroplasticity.record_success();
                         Ok(format!("Scanned {} scenes.", scenes.len()))
@@ -359,11 +392,11 @@ impl Brain {
             }
             Intent::LearnStyle(input, name) => {
                 info!("[BRAIN] 🧠 Learning style '{}' from video...", name);
-                use crate::agent::vision_tools;
+                use crate::agent::scan_cache;
                 let path = std::path::Path::new(&input);
 
                 // 1. Analyze the video to extract style metrics
-                match vision_tools::scan_visual(path).await {
+                match scan_cache::visual(path).await {
                     Ok(scenes) => {
                         if scenes.len() < 2 {
                             return Err(
@@ -443,6 +476,17 @@ impl Brain {
                     Err(e) => Err(format!("Edit creation failed: {}", e)),
                 }
             }
+            Intent::RunTool(tool, path, params) => {
+                info!("[BRAIN] ⚡ Fast-path activated: tool '{}'", tool);
+                let input = std::path::Path::new(&path);
+                match crate::agent::tool_registry::run_tool(&tool, input, &params).await {
+                    Ok(output) => {
+                        self.neuroplasticity.record_success();
+                        Ok(format!("{} wrote {}", tool, output.display()))
+                    }
+                    Err(e) => Err(format!("{} failed: {}", tool, e)),
+                }
+            }
             Intent::DiscoverFile(query) => {
                 info!("[BRAIN] Discovery mode for: {}", query);
                 // Signal to AgentCore to run the global file scanner
@@ -495,4 +539,21 @@ mod tests {
             _ => panic!("Failed to classify video scan"),
         }
     }
+
+    #[test]
+    fn test_fast_classify_registered_tool() {
+        let brain = Brain::new("http://localhost", "mock-model", None);
+        match brain.fast_classify("trim clip.mp4 start=5 duration=10") {
+            Intent::RunTool(tool, path, params) => {
+                assert_eq!((tool.as_str(), path.as_str()), ("trim", "clip.mp4"));
+                assert_eq!(params.get("duration").map(String::as_str), Some("10"));
+            }
+            other => panic!("Failed to classify tool call: {:?}", other),
+        }
+        // Without explicit params "trim" stays a creative request
+        assert!(matches!(
+            brain.fast_classify("trim the boring parts from clip.mp4"),
+            Intent::Orchestrate(..)
+        ));
+    }
 }
@@ -5,8 +5,9 @@
 // It maintains state, manages long-running processes, and routes intent.
 
 use std::fs::{self, OpenOptions};
+use crate::agent::engines::proc::{self, ProcClass};
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::Stdio;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use tokio::sync::Mutex as AsyncMutex;
@@ -20,12 +21,16 @@ use crate::agent::core_systems::brain::Brain;
 use crate::agent::security::defense::{IntegrityGuard, Sentinel};
 use crate::agent::specialized::global_discovery::GlobalDiscovery;
 use crate::agent::engines::motor_cortex::MotorCortex;
+use crate::agent::tools::output_naming;
 use crate::agent::tools::production_tools;
 use crate::agent::tools::source_tools;
 use crate::agent::engines::unified_pipeline::{PipelineConfig, PipelineStage, UnifiedPipeline};
 
 use crate::agent::core_systems::autonomous_learner::AutonomousLearner;
+use crate::agent::core_systems::operation_history::{OperationHistory, OperationKind, OperationRecord};
+use crate::agent::core_systems::project::{self, Project};
 use crate::agent::engines::editor_queue::{EditJob, JobStatus, VideoEditorQueue};
+use crate::events::{EventBus, SynoidEvent};
 use crate::gpu_backend;
 
 const AUTONOMOUS_PID_FILE: &str = "autonomous_worker.pid";
@@ -81,7 +86,8 @@ fn is_pid_running(pid: u32) -> bool {
     #[cfg(windows)]
     {
         let filter = format!("PID eq {}", pid);
-        return Command::new("tasklist")
+        // Unmanaged: PID bookkeeping for the daemon, not media work
+        return proc::blocking_unmanaged("tasklist")
             .stealth()
             .args(["/FI", &filter, "/FO", "CSV", "/NH"])
             .output()
@@ -94,7 +100,7 @@ fn is_pid_running(pid: u32) -> bool {
 
     #[cfg(not(windows))]
     {
-        Command::new("kill")
+        proc::blocking_unmanaged("kill")
             .args(["-0", &pid.to_string()])
             .status()
             .map(|status| status.success())
@@ -125,7 +131,8 @@ fn spawn_autonomous_worker(api_url: &str, instance_id: &str) -> Result<(u32, Pat
     let current_dir = std::env::current_dir().map_err(|e| e.to_string())?;
     let exe_path = std::env::current_exe().map_err(|e| e.to_string())?;
 
-    let mut command = Command::new(exe_path);
+    // Unmanaged: the daemon is this binary, which governs its own children
+    let mut command = proc::blocking_unmanaged(exe_path);
     command.stealth();
     command
         .arg("autonomous")
@@ -156,15 +163,16 @@ fn stop_autonomous_worker(instance_id: &str) -> Result<Option<u32>, String> {
         return Ok(None);
     }
 
+    // Unmanaged: stopping the daemon must never wait for a slot
     #[cfg(windows)]
-    let status = Command::new("taskkill")
+    let status = proc::blocking_unmanaged("taskkill")
         .stealth()
         .args(["/PID", &pid.to_string(), "/T", "/F"])
         .status()
         .map_err(|e| e.to_string())?;
 
     #[cfg(not(windows))]
-    let status = Command::new("kill")
+    let status = proc::blocking_unmanaged("kill")
         .args(["-TERM", &pid.to_string()])
         .status()
         .map_err(|e| e.to_string())?;
@@ -243,6 +251,40 @@ impl HciTracker {
     }
 }
 
+/// Wakes the GUI when state it shows changes, so the window can sleep while
+/// idle instead of repainting every frame. Does nothing until a window
+/// attaches its context.
+#[derive(Clone, Default)]
+pub struct GuiWaker {
+    dirty: Arc<AtomicBool>,
+    ctx: Arc<std::sync::OnceLock<eframe::egui::Context>>,
+}
+
+impl GuiWaker {
+    /// Mark logs/status as changed and schedule a repaint.
+    pub fn wake(&self) {
+        self.dirty.store(true, Ordering::Release);
+        if let Some(ctx) = self.ctx.get() {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Whether anything changed since the last call.
+    pub fn take_dirty(&self) -> bool {
+        self.dirty.swap(false, Ordering::AcqRel)
+    }
+}
+
+/// Whether the LLM backend answered the last connection attempt. Everything
+/// that only drives ffmpeg works either way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrainLink {
+    Connecting,
+    Online,
+    /// Why the last attempt failed.
+    Offline(String),
+}
+
 /// The shared state of the agent
 #[derive(Clone)]
 pub struct AgentCore {
@@ -284,6 +326,18 @@ pub struct AgentCore {
     // Gemma 4 harness state
     pub gemma4_running: Arc<AtomicBool>,
     pub gemma4_shutdown: Arc<Mutex<Option<tokio::sync::watch::Sender<bool>>>>,
+
+    // Live collaboration channels for editor sessions (shared by API + GUI)
+    pub collab: Arc<crate::collab::CollabHub>,
+
+    // Repaint hook for the GUI (log/status changes)
+    pub gui: GuiWaker,
+
+    // Process-wide event bus (jobs, health, learner, integrity)
+    pub events: EventBus,
+
+    // LLM backend reachability, for the offline banner
+    pub brain_link: Arc<Mutex<BrainLink>>,
 }
 
 impl AgentCore {
@@ -300,6 +354,9 @@ impl AgentCore {
             "[SYSTEM] SYNOID Core initialized.".to_string()
         ]));
         let logs_for_queue = logs.clone();
+        let events = EventBus::new();
+        let gui = GuiWaker::default();
+        let gui_for_queue = gui.clone();
         let log_fn: Arc<dyn Fn(&str) + Send + Sync> = Arc::new(move |msg: &str| {
             if let Ok(mut l) = logs_for_queue.lock() {
                 l.push(msg.to_string());
@@ -307,12 +364,14 @@ impl AgentCore {
                     l.remove(0);
                 }
             }
+            gui_for_queue.wake();
         });
         let editor_queue = Arc::new(VideoEditorQueue::new_with_log(
             brain.clone(),
             instance_id,
             animator.clone(),
             log_fn,
+            events.clone(),
         ));
 
         let mut cortex_inst = MotorCortex::new(api_url);
@@ -338,9 +397,18 @@ impl AgentCore {
             improve_shutdown: Arc::new(Mutex::new(None)),
             gemma4_running: Arc::new(AtomicBool::new(false)),
             gemma4_shutdown: Arc::new(Mutex::new(None)),
+            collab: Arc::new(crate::collab::CollabHub::with_events(events.clone())),
+            gui,
+            events,
+            brain_link: Arc::new(Mutex::new(BrainLink::Connecting)),
         }
     }
 
+    /// Let log lines and status changes repaint the window at `ctx`.
+    pub fn attach_gui(&self, ctx: eframe::egui::Context) {
+        let _ = self.gui.ctx.set(ctx);
+    }
+
     pub fn ensure_video_editing_agent(&self) {
         let mut vea = self.video_editing_agent.lock().unwrap();
         if vea.is_none() {
@@ -413,7 +481,8 @@ impl AgentCore {
 
             info!("[SYSTEM] Jobs completed. Spawning new instance via cargo run...");
 
-            let mut cmd = Command::new("cargo");
+            // Unmanaged: the rebuilt instance governs its own children
+            let mut cmd = proc::blocking_unmanaged("cargo");
             cmd.args([
                 "run",
                 "--release",
@@ -458,8 +527,45 @@ impl AgentCore {
     }
 
     pub async fn initialize_hive_mind(&self) -> Result<(), String> {
-        let mut brain = self.brain.lock().await;
-        brain.initialize_hive_mind().await
+        self.set_brain_link(BrainLink::Connecting);
+        let result = self.brain.lock().await.initialize_hive_mind().await;
+        self.set_brain_link(match &result {
+            Ok(()) => BrainLink::Online,
+            Err(e) => BrainLink::Offline(e.clone()),
+        });
+        result
+    }
+
+    /// Connect to the LLM backend in the background. Nothing waits on it:
+    /// until it answers, only the panels that need an LLM are unavailable.
+    pub fn connect_brain(self: &Arc<Self>) {
+        let core = self.clone();
+        tokio::spawn(async move {
+            match core.initialize_hive_mind().await {
+                Ok(_) => info!("🐝 Hive Mind Active: Connected to Ollama Neural Network"),
+                Err(e) => {
+                    tracing::debug!("⚠️ Hive Mind Offline: {}", e);
+                    tracing::debug!("⚠️ Continuing in degraded mode (Brain defaults only)");
+                }
+            }
+        });
+    }
+
+    pub fn brain_link(&self) -> BrainLink {
+        self.brain_link.lock().unwrap().clone()
+    }
+
+    /// What to tell the user while the LLM backend is unreachable.
+    pub fn brain_offline_notice(&self) -> Option<String> {
+        match self.brain_link() {
+            BrainLink::Offline(_) => Some(format!("Brain offline — start Ollama at {}", self.api_url)),
+            _ => None,
+        }
+    }
+
+    fn set_brain_link(&self, link: BrainLink) {
+        *self.brain_link.lock().unwrap() = link;
+        self.gui.wake();
     }
 
     pub async fn get_hive_status(&self) -> String {
@@ -506,10 +612,32 @@ impl AgentCore {
         if let Ok(mut status) = self.status.lock() {
             *status = msg.to_string();
         }
+        self.gui.wake();
+    }
+
+    /// Progress callback for downloads: mirrors the percentage into the
+    /// status line (GUI and `/api/status`) and logs every 10%.
+    pub fn download_progress_reporter(&self) -> source_tools::DownloadProgress {
+        let status = self.status.clone();
+        let gui = self.gui.clone();
+        let last_logged = Arc::new(AtomicU64::new(u64::MAX));
+        Arc::new(move |pct: f32| {
+            if let Ok(mut status) = status.lock() {
+                *status = format!("📥 Downloading... {:.0}%", pct);
+            }
+            gui.wake();
+            let bucket = (pct / 10.0).floor() as u64;
+            if last_logged.swap(bucket, Ordering::Relaxed) != bucket {
+                info!("[CORE] 📥 Download progress: {:.0}%", pct);
+            }
+        })
     }
 
+    /// Record a line for the GUI log panel. It is emitted as a tracing event
+    /// inside this instance's span, so the console and log files carry the
+    /// same line.
     pub fn log(&self, msg: &str) {
-        info!("{}", msg); // Also log to stdout/tracing
+        tracing::info_span!("core", instance = %self.instance_id).in_scope(|| info!("{}", msg));
         if let Ok(mut logs) = self.logs.lock() {
             logs.push(msg.to_string());
             // Cap at 500 logs to prevent memory exhaustion
@@ -517,9 +645,14 @@ impl AgentCore {
                 logs.remove(0);
             }
         }
+        self.gui.wake();
     }
 
     pub fn get_status(&self) -> String {
+        // A model pull blocks every LLM call, so it takes over the status line
+        if let Some(pull) = crate::agent::ollama_pull::pull_status() {
+            return pull;
+        }
         self.status
             .lock()
             .unwrap_or_else(|e| e.into_inner())
@@ -560,17 +693,21 @@ impl AgentCore {
         result
     }
 
+    /// Fetch (or pick up) a video and edit it per `intent`. Returns the
+    /// output path; with an intent the edit is queued on `editor_queue` and
+    /// may still be running.
     pub async fn process_youtube_intent(
         &self,
         url: &str,
         intent: &str,
         output: Option<PathBuf>,
         login: Option<&str>,
+        download_options: &source_tools::DownloadOptions,
         funny_mode: bool,
         chunk_minutes: u32,
         enable_subtitles: bool,
         enable_censoring: bool,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
         if chunk_minutes > 0 && chunk_minutes < 600 {
             // Just logging for now as chunking logic is complex and requires ffmpeg splitting
             self.log(&format!("[CORE] ℹ️ Note: Long video chunking ({} mins) requested but experimental. Proceeding with full video.", chunk_minutes));
@@ -660,7 +797,16 @@ impl AgentCore {
             }
 
             // Extract needed fields immediately so the non-Send Result is dropped before next await
-            match source_tools::download_youtube(&sanitized_url, output_dir, login).await {
+            let progress = self.download_progress_reporter();
+            match source_tools::download_youtube_with_progress(
+                &sanitized_url,
+                output_dir,
+                login,
+                download_options,
+                Some(progress),
+            )
+            .await
+            {
                 Ok(info) => (info.title, info.local_path),
                 Err(e) => {
                     let msg = format!("[CORE] ❌ Download failed: {}", e);
@@ -688,10 +834,12 @@ impl AgentCore {
                 .file_name()
                 .and_then(|s| s.to_str())
                 .unwrap_or("output");
-            local_path
-                .parent()
-                .unwrap_or_else(|| Path::new("."))
-                .join(format!("{}_edited.mp4", stem))
+            output_naming::default_output(
+                &local_path.with_file_name(format!("{}.mp4", stem)),
+                "edited",
+                Some(intent),
+                "mp4",
+            )
         });
         // Ensure the output directory exists
         if let Some(parent) = out_path.parent() {
@@ -786,38 +934,46 @@ impl AgentCore {
             self.set_status("📥 Edit Queued");
         } else {
             if let Err(e) = std::fs::copy(&local_path, &out_path) {
-                self.log(&format!("[CORE] ❌ Copy failed: {}", e));
-            } else {
-                self.log(&format!("[CORE] ✅ Saved to {:?}", out_path));
+                let msg = format!("[CORE] ❌ Copy failed: {}", e);
+                self.log(&msg);
+                self.set_status("⚡ Ready");
+                return Err(msg.into());
             }
+            self.log(&format!("[CORE] ✅ Saved to {:?}", out_path));
         }
 
         self.set_status("⚡ Ready");
-        Ok(())
+        Ok(out_path)
     }
 
     pub async fn process_research(
         &self,
         topic: &str,
         limit: usize,
+        provider: &str,
+        deep: Option<crate::agent::deep_research::DeepOptions>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         self.set_status(&format!("🕵️ Researching: {}", topic));
-        self.log(&format!("[CORE] Researching topic: {}", topic));
+        self.log(&format!("[CORE] Researching topic: {} (via {})", topic, provider));
 
-        match source_tools::search_youtube(topic, limit).await {
+        match crate::agent::search_providers::search(provider, topic, limit).await {
             Ok(results) => {
+                if let Some(options) = deep {
+                    self.deep_research(topic, &results, options).await?;
+                }
                 self.log(&format!("[CORE] === 📚 Results: '{}' ===", topic));
                 for (i, source) in results.iter().enumerate() {
                     self.log(&format!(
-                        "{}. {} (Duration: {:.1} min)",
+                        "{}. {} (Duration: {:.1} min, via {})",
                         i + 1,
                         source.title,
-                        source.duration / 60.0
-                    ));
-                    self.log(&format!(
-                        "   URL: {}",
-                        source.original_url.as_deref().unwrap_or("Unknown")
+                        source.duration / 60.0,
+                        source.provider
                     ));
+                    match &source.original_url {
+                        Some(url) => self.log(&format!("   URL: {}", url)),
+                        None => self.log(&format!("   Path: {}", source.local_path.display())),
+                    }
                 }
             }
             Err(e) => {
@@ -830,6 +986,37 @@ impl AgentCore {
         Ok(())
     }
 
+    /// Read the transcripts (and comments) behind `results`, summarize them
+    /// through the Brain and save the topic's research report.
+    async fn deep_research(
+        &self,
+        topic: &str,
+        results: &[crate::agent::source_tools::SourceInfo],
+        options: crate::agent::deep_research::DeepOptions,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        use crate::agent::deep_research;
+
+        self.set_status(&format!("📜 Deep research: {}", topic));
+        let agent = self.brain.lock().await.agent();
+        let report = deep_research::deep_research(topic, results, options, &agent).await;
+        let (json_path, md_path) = report.save()?;
+
+        let techniques = report.techniques();
+        self.log(&format!(
+            "[CORE] === 🧠 Key techniques from {} video(s) ===",
+            report.items.len()
+        ));
+        for t in &techniques {
+            self.log(&format!("   • {}", t));
+        }
+        self.log(&format!(
+            "[CORE] 📝 Research report: {} ({})",
+            md_path.display(),
+            json_path.display()
+        ));
+        Ok(())
+    }
+
     /// Run the full AutoResearch pipeline (inspired by AutoResearchClaw).
     /// Queries arXiv, Semantic Scholar, and OpenAlex, then synthesises gaps and hypotheses.
     pub async fn process_auto_research(
@@ -878,12 +1065,20 @@ impl AgentCore {
         output: Option<PathBuf>,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         self.set_status("✂️ Clipping...");
-        let out_path = output.unwrap_or_else(|| {
-            let stem = input.file_stem().unwrap_or_default().to_string_lossy();
-            input.with_file_name(format!("{}_clip.mp4", stem))
-        });
-
-        match production_tools::trim_video(input, start, duration, &out_path).await {
+        let out_path =
+            output.unwrap_or_else(|| output_naming::default_output(input, "clip", None, "mp4"));
+        let started_at = chrono::Utc::now();
+
+        let result = production_tools::trim_video(input, start, duration, &out_path).await;
+        self.record_operation(
+            OperationKind::Clip,
+            input,
+            &out_path,
+            &[("start", start.to_string()), ("duration", duration.to_string())],
+            started_at,
+            result.as_ref().map(|r| format!("{:.2} MB", r.size_mb)),
+        );
+        match result {
             Ok(res) => {
                 self.log(&format!(
                     "[CORE] ✂️ Clip saved: {:?} ({:.2} MB)",
@@ -899,19 +1094,126 @@ impl AgentCore {
         Ok(())
     }
 
+    /// Append a finished operation to the persisted history.
+    fn record_operation<E: std::fmt::Display>(
+        &self,
+        kind: OperationKind,
+        input: &Path,
+        output: &Path,
+        params: &[(&str, String)],
+        started_at: chrono::DateTime<chrono::Utc>,
+        result: Result<String, E>,
+    ) {
+        let params = params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect();
+        OperationHistory::for_instance(&self.instance_id).record(
+            kind,
+            input,
+            Some(output),
+            params,
+            started_at,
+            &result.map_err(|e| e.to_string()),
+        );
+    }
+
+    /// Re-run a recorded operation (after any overrides were applied).
+    pub async fn rerun_operation(
+        &self,
+        record: &OperationRecord,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.log(&format!("[CORE] 🔁 Re-running {}", record.summary()));
+        match record.kind {
+            OperationKind::Clip => {
+                let start = record.param_f64("start").ok_or("clip record has no valid 'start'")?;
+                let duration = record
+                    .param_f64("duration")
+                    .ok_or("clip record has no valid 'duration'")?;
+                self.clip_video(&record.input, start, duration, record.output.clone())
+                    .await
+            }
+            OperationKind::Compress => {
+                let size = record
+                    .param_f64("size_mb")
+                    .ok_or("compress record has no valid 'size_mb'")?;
+                let measure = record.param_bool("measure_quality").unwrap_or(false);
+                let defaults = production_tools::ResolutionLadder::default();
+                let ladder = production_tools::ResolutionLadder {
+                    min_height: record
+                        .param_f64("min_height")
+                        .map(|h| h as u32)
+                        .unwrap_or(defaults.min_height),
+                    enabled: record.param_bool("downscale").unwrap_or(defaults.enabled),
+                };
+                self.compress_video(&record.input, size, record.output.clone(), measure, ladder)
+                    .await
+            }
+            OperationKind::Edit => {
+                let intent = record.param("intent").unwrap_or_default().to_string();
+                let output = record.output.clone().unwrap_or_default();
+                self.embody_intent(
+                    &record.input,
+                    &intent,
+                    &output,
+                    false,
+                    record.param_bool("subtitles").unwrap_or(true),
+                    record.param_bool("censoring").unwrap_or(true),
+                )
+                .await
+                .map(|_| ())
+            }
+        }
+    }
+
     pub async fn compress_video(
         &self,
         input: &Path,
         size_mb: f64,
         output: Option<PathBuf>,
+        measure_quality: bool,
+        ladder: production_tools::ResolutionLadder,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         self.set_status("📦 Compressing...");
-        let out_path = output.unwrap_or_else(|| {
-            let stem = input.file_stem().unwrap_or_default().to_string_lossy();
-            input.with_file_name(format!("{}_compressed.mp4", stem))
-        });
-
-        match production_tools::compress_video(input, size_mb, &out_path).await {
+        let out_path =
+            output.unwrap_or_else(|| output_naming::default_output(input, "compressed", None, "mp4"));
+        let started_at = chrono::Utc::now();
+
+        let result =
+            production_tools::compress_video_with(input, size_mb, &out_path, ladder).await;
+        // The quality summary goes into the history record with the size
+        let quality = match &result {
+            Ok(res) if measure_quality => {
+                match production_tools::compare_quality(input, &res.output_path).await {
+                    Ok(report) => {
+                        self.log(&format!("[CORE] 📏 Quality vs source: {}", report.summary()));
+                        Some(report.summary())
+                    }
+                    Err(e) => {
+                        self.log(&format!("[CORE] ⚠️ Quality measurement failed: {}", e));
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+        self.record_operation(
+            OperationKind::Compress,
+            input,
+            &out_path,
+            &[
+                ("size_mb", size_mb.to_string()),
+                ("measure_quality", measure_quality.to_string()),
+                ("min_height", ladder.min_height.to_string()),
+                ("downscale", ladder.enabled.to_string()),
+            ],
+            started_at,
+            result.as_ref().map(|r| match &quality {
+                Some(summary) => format!("{:.2} MB, {}", r.size_mb, summary),
+                None => format!("{:.2} MB", r.size_mb),
+            }),
+        );
+        match result {
             Ok(res) => {
                 self.log(&format!(
                     "[CORE] 📦 Compressed saved: {:?} ({:.2} MB)",
@@ -993,6 +1295,7 @@ impl AgentCore {
         result
     }
 
+    /// Queue one smart edit of `input` towards `intent` and return its job id.
     pub async fn embody_intent(
         &self,
         input: &Path,
@@ -1001,11 +1304,12 @@ impl AgentCore {
         _dry_run: bool,
         enable_subtitles: bool,
         enable_censoring: bool,
-    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<uuid::Uuid, Box<dyn std::error::Error + Send + Sync>> {
         // Human chose the intent; AI will handle execution — record both sides.
         self.record_director_decision();
         self.set_status("🤖 Embodying...");
         self.log(&format!("[CORE] Embodied Agent Activating for: {}", intent));
+        Project::with_active(&self.instance_id, |p| p.record_intent("embody", input, intent));
 
         // Note: We used to scan visual and audio context here, but that blocks the GUI thread
         // for several minutes on large files. The Smart Editor handles its own scanning inside
@@ -1033,24 +1337,7 @@ impl AgentCore {
             }
         }
 
-        // Guard: never write to the input file itself; derive _edited.mp4 if needed.
-        let safe_output = if output.as_os_str().is_empty() || output == input {
-            let video_exts = ["mp4", "mov", "mkv", "avi", "webm", "flv", "wmv", "m4v"];
-            let mut stem_path = input.to_path_buf();
-            loop {
-                match stem_path.extension().and_then(|e| e.to_str()) {
-                    Some(ext) if video_exts.contains(&ext.to_lowercase().as_str()) => {
-                        stem_path = stem_path.with_extension("");
-                    }
-                    _ => break,
-                }
-            }
-            let stem = stem_path.file_name().and_then(|s| s.to_str()).unwrap_or("output");
-            input.parent().unwrap_or_else(|| Path::new("."))
-                .join(format!("{}_edited.mp4", stem))
-        } else {
-            output.to_path_buf()
-        };
+        let safe_output = Self::embody_output(input, output);
 
         let job = EditJob {
             id: Uuid::new_v4(),
@@ -1084,7 +1371,121 @@ impl AgentCore {
         }
 
         self.set_status("⚡ Ready");
-        Ok(())
+        Ok(job_id)
+    }
+
+    /// Guard: never write to the input file itself; derive _edited.mp4 if needed.
+    fn embody_output(input: &Path, output: &Path) -> PathBuf {
+        if !(output.as_os_str().is_empty() || output == input) {
+            return output.to_path_buf();
+        }
+        let video_exts = ["mp4", "mov", "mkv", "avi", "webm", "flv", "wmv", "m4v"];
+        let mut stem_path = input.to_path_buf();
+        loop {
+            match stem_path.extension().and_then(|e| e.to_str()) {
+                Some(ext) if video_exts.contains(&ext.to_lowercase().as_str()) => {
+                    stem_path = stem_path.with_extension("");
+                }
+                _ => break,
+            }
+        }
+        let stem = stem_path.file_name().and_then(|s| s.to_str()).unwrap_or("output");
+        input.parent().unwrap_or_else(|| Path::new("."))
+            .join(format!("{}_edited.mp4", stem))
+    }
+
+    /// Plan each step of `intent` the way `embody_steps` would run it, without
+    /// scanning, transcribing or rendering anything. Each step's intent goes
+    /// through the render's parser; scenes come from the scan cache, so they
+    /// stay unknown until an earlier edit has scanned the source.
+    pub async fn plan_embody(
+        &self,
+        input: &Path,
+        intent: &str,
+        output: &Path,
+    ) -> Vec<crate::agent::engines::motor_cortex::RenderPlan> {
+        use crate::agent::smart_editor::{self, steps};
+        use crate::agent::tools::scan_cache;
+
+        let agent = self.brain.lock().await.agent();
+        let plan = steps::decompose(&agent, intent).await;
+        let output = Self::embody_output(input, output);
+        let threshold = smart_editor::EditingStrategy::load().scene_threshold;
+
+        let mut plans = Vec::with_capacity(plan.len());
+        for (i, step) in plan.iter().enumerate() {
+            // A step fed by an earlier one edits a video that does not exist yet.
+            let (step_input, scenes) = match step.input_from {
+                Some(from) => (steps::step_output(&output, &plan, from), Vec::new()),
+                None => (
+                    input.to_path_buf(),
+                    scan_cache::cached_scenes(input, threshold).unwrap_or_default(),
+                ),
+            };
+            let step_output = steps::step_output(&output, &plan, i);
+            let edit_intent = smart_editor::parse_intent(&step.intent).await;
+            plans.push(self.cortex.lock().await.plan(
+                &step.intent,
+                edit_intent,
+                &step_input,
+                &step_output,
+                scenes,
+                &[],
+            ));
+        }
+
+        for (i, render) in plans.iter().enumerate() {
+            self.log(&format!("[CORE] 🧪 Plan for step {}:\n{}", i + 1, render.describe()));
+        }
+        plans
+    }
+
+    /// Embody a request that may hold several edits ("remove silence, then
+    /// make a highlights version"): split it into steps, queue each one and
+    /// wait for it before the next, feeding earlier outputs forward where a
+    /// step asks for it. Outputs of finished steps are kept when a later step
+    /// fails; every step's result is returned.
+    pub async fn embody_steps(
+        &self,
+        input: &Path,
+        intent: &str,
+        output: &Path,
+        enable_subtitles: bool,
+        enable_censoring: bool,
+    ) -> Vec<crate::agent::smart_editor::StepOutcome> {
+        use crate::agent::smart_editor::steps;
+
+        let agent = self.brain.lock().await.agent();
+        let plan = steps::decompose(&agent, intent).await;
+        if plan.len() > 1 {
+            self.log(&format!("[CORE] 🧩 Request split into {} steps:", plan.len()));
+            for (i, step) in plan.iter().enumerate() {
+                let from = match step.input_from {
+                    Some(from) => format!("output of step {}", from + 1),
+                    None => "source".to_string(),
+                };
+                self.log(&format!("[CORE]   {}. {} — \"{}\" (from {})", i + 1, step.name, step.intent, from));
+            }
+        }
+
+        let output = Self::embody_output(input, output);
+        let outcomes = steps::run_steps(input, &plan, &output, |_, step_input, step, step_output| async move {
+            let job_id = self
+                .embody_intent(&step_input, &step.intent, &step_output, false, enable_subtitles, enable_censoring)
+                .await
+                .map_err(|e| e.to_string())?;
+            match self.editor_queue.wait_for_job(job_id).await {
+                Some(JobStatus::Completed { .. }) => Ok(()),
+                Some(JobStatus::Failed(e)) => Err(e),
+                _ => Err(format!("job {} left the queue unfinished", job_id)),
+            }
+        })
+        .await;
+
+        for outcome in &outcomes {
+            self.log(&format!("[CORE] {}", outcome.describe()));
+        }
+        outcomes
     }
 
     pub async fn learn_style(
@@ -1172,7 +1573,7 @@ impl AgentCore {
         let safe_input = production_tools::safe_arg_path(path);
 
         // Extract 1 frame at the given timestamp as a JPG
-        let output = tokio::process::Command::new("ffmpeg")
+        let output = proc::ffmpeg(ProcClass::Thumbnail)
             .stealth()
             .arg("-ss")
             .arg(time_secs.to_string())
@@ -1299,6 +1700,12 @@ impl AgentCore {
             }
         }
 
+        if let Some(ref intent_str) = intent {
+            Project::with_active(&self.instance_id, |p| {
+                p.record_intent("process", input, intent_str)
+            });
+        }
+
         let parsed_stages = PipelineStage::parse_list(stages_str);
         if parsed_stages.is_empty() {
             let msg = "No valid stages specified.";
@@ -1318,18 +1725,43 @@ impl AgentCore {
 
         // Config
         let self_clone = self.clone();
+        let started_at = chrono::Utc::now();
+        let intent_text = intent.clone().unwrap_or_default();
         let config = PipelineConfig {
             stages: parsed_stages,
             intent,
             scale_factor: scale,
             target_size_mb: 0.0,
+            matting: Default::default(),
             progress_callback: Some(Arc::new(move |msg: &str| {
                 self_clone.log(msg);
             })),
             learned_pattern: pattern,
             animator: Some(self.animator.clone()),
         };
-        match pipeline.process(input, output, config).await {
+        let result = pipeline.process(input, output, config).await;
+        project::record_render_in_active(
+            &self.instance_id,
+            project::RenderInput {
+                command: "process".to_string(),
+                source: input.to_path_buf(),
+                intent: intent_text,
+                output: result.as_ref().map(|p| p.clone()).unwrap_or_else(|_| output.to_path_buf()),
+                params: [
+                    ("stages".to_string(), stages_str.to_string()),
+                    ("scale".to_string(), scale.to_string()),
+                ]
+                .into_iter()
+                .collect(),
+                started_at,
+                result: result
+                    .as_ref()
+                    .map(|p| format!("Pipeline output {:?}", p))
+                    .map_err(|e| e.to_string()),
+            },
+        )
+        .await;
+        match result {
             Ok(out_path) => self.log(&format!("[CORE] ✅ Pipeline complete: {:?}", out_path)),
             Err(e) => {
                 self.log(&format!("[CORE] ❌ Pipeline failed: {}", e));
@@ -1428,9 +1860,8 @@ impl AgentCore {
         self.log(&format!("[GEMMA4] Starting: {}", task));
 
         // Clear previous log
-        if let Ok(mut s) = ui_state.lock() {
-            s.gemma4_log = format!("Starting Gemma 4 harness...\nTask: {}\n\n", task);
-        }
+        crate::window::lock_ui(&ui_state).gemma4_log =
+            format!("Starting Gemma 4 harness...\nTask: {}\n\n", task);
 
         let running_flag = self.gemma4_running.clone();
         let work_dir = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
@@ -1442,18 +1873,14 @@ impl AgentCore {
 
             // Override run_task to stream each step into ui_state.gemma4_log
             // We replicate the loop here so we can capture intermediate output.
-            let _ollama_url = std::env::var("SYNOID_API_URL")
-                .unwrap_or_else(|_| "http://localhost:11434".to_string());
-            let model = std::env::var("SYNOID_MODEL")
-                .unwrap_or_else(|_| "gemma4:26b".to_string());
+            let model = crate::config::current().model;
 
             let append_log = {
                 let ui = ui_state.clone();
                 move |text: &str| {
-                    if let Ok(mut s) = ui.lock() {
-                        s.gemma4_log.push_str(text);
-                        s.gemma4_log.push('\n');
-                    }
+                    let mut s = crate::window::lock_ui(&ui);
+                    s.gemma4_log.push_str(text);
+                    s.gemma4_log.push('\n');
                 }
             };
 
@@ -1492,6 +1919,10 @@ impl AgentCore {
     }
 
     pub async fn activate_sentinel(&self, mode: &str, watch: Option<PathBuf>) {
+        if !Sentinel::is_enabled() {
+            self.log(&format!("[CORE] 🛡️ {}", crate::agent::defense::sentinel::DISABLED_MESSAGE));
+            return;
+        }
         self.set_status(&format!("🛡️ Sentinel Active ({})", mode));
         self.log("[CORE] 🛡️ ACTIVATING SENTINEL Cyberdefense System...");
         self.sentinel_active.store(true, Ordering::Relaxed);
@@ -1520,6 +1951,7 @@ impl AgentCore {
                 let violations = integrity.verify_integrity().await;
                 for v in violations {
                     self.log(&format!("[INTEGRITY] ❌ {}", v));
+                    self.events.publish(SynoidEvent::IntegrityViolation { detail: v });
                 }
             }
 
@@ -1565,7 +1997,8 @@ impl AgentCore {
 
                 let mut learner_guard = self.autonomous_learner.lock().unwrap();
                 if learner_guard.is_none() {
-                    let learner = AutonomousLearner::new(self.brain.clone(), &self.instance_id);
+                    let learner = AutonomousLearner::new(self.brain.clone(), &self.instance_id)
+                        .with_events(self.events.clone());
                     *learner_guard = Some(learner);
                 }
 
@@ -1617,6 +2050,71 @@ impl AgentCore {
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_unreachable_brain_is_reported_offline() {
+        // Nothing listens on port 9 (discard), so the connection is refused
+        let core = AgentCore::new("http://127.0.0.1:9", "test_brain_offline");
+        assert_eq!(core.brain_link(), BrainLink::Connecting);
+        assert_eq!(core.brain_offline_notice(), None);
+
+        assert!(core.initialize_hive_mind().await.is_err());
+        assert!(matches!(core.brain_link(), BrainLink::Offline(_)));
+        assert_eq!(
+            core.brain_offline_notice().as_deref(),
+            Some("Brain offline — start Ollama at http://127.0.0.1:9")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_disabled_sentinel_returns_without_monitoring() {
+        std::env::remove_var("SYNOID_ENABLE_SENTINEL");
+        assert!(!Sentinel::is_enabled());
+
+        let core = AgentCore::new("http://localhost:11434", "test_instance");
+        // An enabled Sentinel loops until stopped; a disabled one must not start
+        tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            core.activate_sentinel("all", None),
+        )
+        .await
+        .expect("a disabled Sentinel entered the monitor loop");
+        assert!(!core.sentinel_active.load(Ordering::Relaxed));
+    }
+
+    /// A headless egui context that counts repaint requests.
+    fn counting_ctx() -> (eframe::egui::Context, Arc<AtomicU64>) {
+        let ctx = eframe::egui::Context::default();
+        let repaints = Arc::new(AtomicU64::new(0));
+        let counter = repaints.clone();
+        ctx.set_request_repaint_callback(move |_| {
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+        (ctx, repaints)
+    }
+
+    #[tokio::test]
+    async fn test_log_and_status_wake_an_attached_gui() {
+        let core = AgentCore::new("http://localhost:11434", "test_instance");
+        // Before a window attaches, changes only mark the state dirty
+        core.log("[TEST] before attach");
+        assert!(core.gui.take_dirty());
+        assert!(!core.gui.take_dirty());
+
+        let (ctx, repaints) = counting_ctx();
+        core.attach_gui(ctx);
+        assert_eq!(repaints.load(Ordering::Relaxed), 0);
+        core.log("[TEST] after attach");
+        assert_eq!(repaints.load(Ordering::Relaxed), 1);
+        assert!(core.gui.take_dirty());
+
+        let core = AgentCore::new("http://localhost:11434", "test_instance");
+        let (ctx, repaints) = counting_ctx();
+        core.attach_gui(ctx);
+        core.set_status("🧪 Testing");
+        assert_eq!(repaints.load(Ordering::Relaxed), 1);
+        assert!(core.gui.take_dirty());
+    }
+
     #[test]
     fn test_sanitize_input() {
         // Test trimming
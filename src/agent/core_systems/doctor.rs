@@ -0,0 +1,685 @@
+// SYNOID Doctor — environment checks shared by `doctor` and `setup`
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Each check looks at one thing SYNOID needs (a tool on PATH, the Ollama
+// server and model, the configured directories, the Whisper model) and says
+// whether it is usable and how to fix it if not. `doctor` prints them all;
+// `setup` runs the checks for one area, offers the fix, then checks again,
+// so the two can never disagree about what is missing.
+
+use crate::agent::engines::proc;
+use crate::agent::engines::process_utils::CommandExt;
+use crate::config::SynoidConfig;
+use serde::Serialize;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+/// The areas `setup` walks through, in order; also the `--only` values.
+/// `Versions` is the `--version-detail` report and is not a setup step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckArea {
+    Tools,
+    Ollama,
+    Dirs,
+    Whisper,
+    Versions,
+}
+
+impl CheckArea {
+    pub const ALL: [CheckArea; 4] = [Self::Tools, Self::Ollama, Self::Dirs, Self::Whisper];
+    /// How `report` orders its sections.
+    const REPORT_ORDER: [CheckArea; 5] = [
+        Self::Versions,
+        Self::Tools,
+        Self::Ollama,
+        Self::Dirs,
+        Self::Whisper,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Tools => "tools",
+            Self::Ollama => "ollama",
+            Self::Dirs => "dirs",
+            Self::Whisper => "whisper",
+            Self::Versions => "versions",
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Tools => "External tools",
+            Self::Ollama => "Ollama",
+            Self::Dirs => "Directories",
+            Self::Whisper => "Whisper model",
+            Self::Versions => "Versions",
+        }
+    }
+}
+
+impl std::str::FromStr for CheckArea {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|a| a.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "Unknown setup step '{}' (expected tools, ollama, dirs or whisper)",
+                    s
+                )
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    /// Usable, but a feature is degraded or a download will happen later.
+    Warn,
+    /// Something SYNOID needs is missing.
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub area: CheckArea,
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to do about a Warn or Fail.
+    pub fix: Option<String>,
+}
+
+impl Check {
+    fn new(area: CheckArea, name: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            area,
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+
+    /// One report line, plus an indented fix when there is one.
+    pub fn line(&self) -> String {
+        let icon = match self.status {
+            CheckStatus::Ok => "✅",
+            CheckStatus::Warn => "⚠️",
+            CheckStatus::Fail => "❌",
+        };
+        let mut line = format!("{} {}: {}", icon, self.name, self.detail);
+        if let Some(fix) = &self.fix {
+            line.push_str(&format!("\n     → {}", fix));
+        }
+        line
+    }
+}
+
+/// The tools SYNOID shells out to; yt-dlp only matters for downloads.
+const TOOLS: [(&str, &str, CheckStatus); 3] = [
+    ("ffmpeg", "-version", CheckStatus::Fail),
+    ("ffprobe", "-version", CheckStatus::Fail),
+    ("yt-dlp", "--version", CheckStatus::Warn),
+];
+
+/// First line of `<tool> <flag>`, or `None` when it does not run.
+pub fn tool_version(tool: &str, flag: &str) -> Option<String> {
+    // Unmanaged: a version check must answer even while renders hold
+    // every ffmpeg slot
+    let output = proc::blocking_unmanaged(tool)
+        .stealth()
+        .arg(flag)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    )
+}
+
+/// How to install `tool` on this OS.
+pub fn install_hint(tool: &str) -> String {
+    let (windows, macos, linux) = match tool {
+        "yt-dlp" => (
+            "winget install yt-dlp",
+            "brew install yt-dlp",
+            "pipx install yt-dlp",
+        ),
+        // ffprobe ships with ffmpeg everywhere
+        _ => (
+            "winget install Gyan.FFmpeg",
+            "brew install ffmpeg",
+            "sudo apt install ffmpeg",
+        ),
+    };
+    let cmd = if cfg!(target_os = "windows") {
+        windows
+    } else if cfg!(target_os = "macos") {
+        macos
+    } else {
+        linux
+    };
+    format!("Install with `{}` and make sure {} is on PATH", cmd, tool)
+}
+
+pub fn check_tools() -> Vec<Check> {
+    check_tools_with(tool_version)
+}
+
+/// `check_tools` with the probe swapped out, for tests.
+fn check_tools_with(probe: impl Fn(&str, &str) -> Option<String>) -> Vec<Check> {
+    TOOLS
+        .iter()
+        .map(|&(tool, flag, when_missing)| match probe(tool, flag) {
+            Some(version) => Check::new(CheckArea::Tools, tool, CheckStatus::Ok, version),
+            None => Check::new(CheckArea::Tools, tool, when_missing, "not found")
+                .fix(install_hint(tool)),
+        })
+        .collect()
+}
+
+/// The native Ollama API root for `api_url`, which may be the
+/// OpenAI-compatible `/v1` endpoint.
+pub fn ollama_base(api_url: &str) -> &str {
+    api_url.trim_end_matches('/').trim_end_matches("/v1")
+}
+
+/// Whether `model` is among `installed`; `llama3` and `llama3:latest` name
+/// the same model.
+pub fn has_model(installed: &[String], model: &str) -> bool {
+    installed
+        .iter()
+        .any(|m| m == model || m.strip_suffix(":latest") == Some(model))
+}
+
+/// Models installed on the Ollama server at `api_url` (`/api/tags`).
+pub async fn installed_models(api_url: &str) -> Result<Vec<String>, String> {
+    let base = ollama_base(api_url);
+    let resp = crate::net::build_local_client(Duration::from_secs(5))
+        .get(format!("{}/api/tags", base))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("{} answered {}", base, resp.status()));
+    }
+    let json: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
+    let mut models: Vec<String> = json["models"]
+        .as_array()
+        .map(|list| {
+            list.iter()
+                .filter_map(|m| m["name"].as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    models.sort();
+    Ok(models)
+}
+
+/// The server is reachable and has the configured reasoning model.
+pub async fn check_ollama(config: &SynoidConfig) -> Check {
+    ollama_check(
+        &config.api_url,
+        &config.model,
+        installed_models(&config.api_url).await,
+    )
+}
+
+fn ollama_check(api_url: &str, model: &str, installed: Result<Vec<String>, String>) -> Check {
+    let models = match installed {
+        Ok(models) => models,
+        Err(e) => {
+            return Check::new(
+                CheckArea::Ollama,
+                "Ollama",
+                CheckStatus::Fail,
+                format!("no server at {} ({})", api_url, e),
+            )
+            .fix("Install Ollama from https://ollama.com and start it with `ollama serve`, or set SYNOID_API_URL")
+        }
+    };
+    if has_model(&models, model) {
+        Check::new(
+            CheckArea::Ollama,
+            "Ollama",
+            CheckStatus::Ok,
+            format!(
+                "{} installed ({} model(s) at {})",
+                model,
+                models.len(),
+                api_url
+            ),
+        )
+    } else {
+        Check::new(
+            CheckArea::Ollama,
+            "Ollama",
+            CheckStatus::Warn,
+            format!(
+                "model {} is not installed ({} model(s) at {})",
+                model,
+                models.len(),
+                api_url
+            ),
+        )
+        .fix(format!(
+            "Run `synoid-core setup --only ollama` or `ollama pull {}`",
+            model
+        ))
+    }
+}
+
+pub fn check_dirs(config: &SynoidConfig) -> Vec<Check> {
+    let cache = Path::new(&config.cache_dir);
+    let cache_check = if cache.is_dir() {
+        Check::new(
+            CheckArea::Dirs,
+            "Cache directory",
+            CheckStatus::Ok,
+            cache.display().to_string(),
+        )
+    } else {
+        Check::new(
+            CheckArea::Dirs,
+            "Cache directory",
+            CheckStatus::Warn,
+            format!("{} does not exist yet", cache.display()),
+        )
+        .fix("It is created on first use; `synoid-core setup --only dirs` creates it now")
+    };
+
+    let video_check = match &config.video_dir {
+        Some(dir) if Path::new(dir).is_dir() => Check::new(
+            CheckArea::Dirs,
+            "Video directory",
+            CheckStatus::Ok,
+            dir.clone(),
+        ),
+        Some(dir) => Check::new(
+            CheckArea::Dirs,
+            "Video directory",
+            CheckStatus::Fail,
+            format!("{} does not exist", dir),
+        )
+        .fix("Run `synoid-core setup --only dirs` or fix SYNOID_VIDEO_DIR"),
+        None => Check::new(
+            CheckArea::Dirs,
+            "Video directory",
+            CheckStatus::Warn,
+            "not set",
+        )
+        .fix("Run `synoid-core setup --only dirs` to choose where your videos live"),
+    };
+    vec![video_check, cache_check]
+}
+
+pub fn check_whisper(config: &SynoidConfig) -> Check {
+    use crate::agent::transcription;
+    let path = transcription::model_path_in(&config.models_dir(), &config.whisper_model);
+    if transcription::is_custom_model(&config.whisper_model) {
+        // Nothing will fetch a custom model, so a bad one fails outright
+        return match transcription::check_model_file(&path) {
+            Ok(()) => Check::new(
+                CheckArea::Whisper,
+                "Whisper model",
+                CheckStatus::Ok,
+                format!("custom ({})", path.display()),
+            ),
+            Err(e) => Check::new(CheckArea::Whisper, "Whisper model", CheckStatus::Fail, e.to_string())
+                .fix("Point SYNOID_WHISPER_MODEL at a ggml or GGUF Whisper model file"),
+        };
+    }
+    if path.is_file() {
+        Check::new(
+            CheckArea::Whisper,
+            "Whisper model",
+            CheckStatus::Ok,
+            format!("{} ({})", config.whisper_model, path.display()),
+        )
+    } else {
+        Check::new(
+            CheckArea::Whisper,
+            "Whisper model",
+            CheckStatus::Warn,
+            format!(
+                "{} not downloaded; the first transcription will fetch it",
+                config.whisper_model
+            ),
+        )
+        .fix("Run `synoid-core setup --only whisper` to download it now")
+    }
+}
+
+/// Every check in `area`.
+pub async fn run_area(area: CheckArea, config: &SynoidConfig) -> Vec<Check> {
+    match area {
+        CheckArea::Tools => check_tools(),
+        CheckArea::Ollama => vec![check_ollama(config).await],
+        CheckArea::Dirs => check_dirs(config),
+        CheckArea::Whisper => vec![check_whisper(config)],
+        CheckArea::Versions => version_checks(
+            &probe_versions(config).await,
+            chrono::Local::now().date_naive(),
+        ),
+    }
+}
+
+/// Every check, in setup order.
+pub async fn run_all(config: &SynoidConfig) -> Vec<Check> {
+    let mut checks = Vec::new();
+    for area in CheckArea::ALL {
+        checks.extend(run_area(area, config).await);
+    }
+    checks
+}
+
+/// The `doctor` report, grouped by area, ending with a one-line verdict.
+pub fn report(checks: &[Check]) -> String {
+    let mut out = String::new();
+    for area in CheckArea::REPORT_ORDER {
+        let in_area: Vec<&Check> = checks.iter().filter(|c| c.area == area).collect();
+        if in_area.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("{}\n", area.title()));
+        for check in in_area {
+            out.push_str(&format!("  {}\n", check.line()));
+        }
+    }
+    let failed = checks
+        .iter()
+        .filter(|c| c.status == CheckStatus::Fail)
+        .count();
+    let warned = checks
+        .iter()
+        .filter(|c| c.status == CheckStatus::Warn)
+        .count();
+    out.push_str(&match (failed, warned) {
+        (0, 0) => "All checks passed.".to_string(),
+        (0, w) => format!("Ready, with {} warning(s).", w),
+        (f, _) => format!("{} problem(s) need fixing before SYNOID can edit.", f),
+    });
+    out
+}
+
+/// What the `--version-detail` probes found; `None` means not found.
+#[derive(Debug, Clone, Default)]
+pub struct VersionProbes {
+    /// First line of `ffmpeg -version`.
+    pub ffmpeg: Option<String>,
+    /// `yt-dlp --version`, a release date like `2025.06.30`.
+    pub yt_dlp: Option<String>,
+    pub whisper_model: String,
+    pub whisper_downloaded: bool,
+    /// `/api/version` of the configured Ollama server.
+    pub ollama: Option<String>,
+    /// `(GPU name, driver version)` from nvidia-smi.
+    pub gpu_driver: Option<(String, String)>,
+}
+
+/// Oldest FFmpeg major release SYNOID's filter graphs are tested with.
+const MIN_FFMPEG_MAJOR: u32 = 5;
+/// yt-dlp releases older than this stop working as sites change.
+const MAX_YT_DLP_AGE_DAYS: i64 = 90;
+/// Oldest Ollama that runs the default models.
+const MIN_OLLAMA: (u32, u32) = (0, 6);
+
+/// Ask each component for its version.
+pub async fn probe_versions(config: &SynoidConfig) -> VersionProbes {
+    let gpu = crate::gpu_backend::get_gpu_context().await;
+    let gpu_driver = match &gpu.backend {
+        crate::gpu_backend::GpuBackend::NvencGpu {
+            name,
+            driver_version,
+        } => Some((name.clone(), driver_version.clone())),
+        crate::gpu_backend::GpuBackend::Cpu { .. } => None,
+    };
+    VersionProbes {
+        ffmpeg: tool_version("ffmpeg", "-version"),
+        yt_dlp: tool_version("yt-dlp", "--version"),
+        whisper_model: config.whisper_model.clone(),
+        whisper_downloaded: crate::agent::transcription::model_path_in(
+            &config.models_dir(),
+            &config.whisper_model,
+        )
+        .is_file(),
+        ollama: ollama_version(&config.api_url).await,
+        gpu_driver,
+    }
+}
+
+async fn ollama_version(api_url: &str) -> Option<String> {
+    let resp = crate::net::build_local_client(Duration::from_secs(5))
+        .get(format!("{}/api/version", ollama_base(api_url)))
+        .send()
+        .await
+        .ok()?;
+    let json: serde_json::Value = resp.json().await.ok()?;
+    json["version"].as_str().map(str::to_string)
+}
+
+/// Leading `major.minor` of a version string, after any `v`/`n` prefix.
+fn major_minor(version: &str) -> Option<(u32, u32)> {
+    let digits = version.trim_start_matches(|c: char| !c.is_ascii_digit());
+    let mut parts = digits.split(|c: char| !c.is_ascii_digit());
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().and_then(|m| m.parse().ok()).unwrap_or(0);
+    Some((major, minor))
+}
+
+/// The `--version-detail` checks for `probes`; `today` dates yt-dlp.
+pub fn version_checks(probes: &VersionProbes, today: chrono::NaiveDate) -> Vec<Check> {
+    let area = CheckArea::Versions;
+    let mut checks = vec![Check::new(
+        area,
+        "SYNOID",
+        CheckStatus::Ok,
+        env!("CARGO_PKG_VERSION"),
+    )];
+
+    checks.push(match &probes.ffmpeg {
+        None => {
+            Check::new(area, "ffmpeg", CheckStatus::Fail, "not found").fix(install_hint("ffmpeg"))
+        }
+        Some(line) => {
+            let version = line.strip_prefix("ffmpeg version ").unwrap_or(line);
+            let version = version.split_whitespace().next().unwrap_or(version);
+            match major_minor(version) {
+                // Git builds ("N-113000-g…") carry no release number
+                Some((major, _)) if major < MIN_FFMPEG_MAJOR && !version.starts_with('N') => {
+                    Check::new(
+                        area,
+                        "ffmpeg",
+                        CheckStatus::Warn,
+                        format!("{} (outdated)", version),
+                    )
+                    .fix(format!(
+                        "SYNOID is tested with ffmpeg {} or newer",
+                        MIN_FFMPEG_MAJOR
+                    ))
+                }
+                _ => Check::new(area, "ffmpeg", CheckStatus::Ok, version),
+            }
+        }
+    });
+
+    checks.push(match &probes.yt_dlp {
+        None => {
+            Check::new(area, "yt-dlp", CheckStatus::Warn, "not found").fix(install_hint("yt-dlp"))
+        }
+        Some(version) => {
+            let released = chrono::NaiveDate::parse_from_str(version.trim(), "%Y.%m.%d").ok();
+            match released.map(|d| (today - d).num_days()) {
+                Some(age) if age > MAX_YT_DLP_AGE_DAYS => Check::new(
+                    area,
+                    "yt-dlp",
+                    CheckStatus::Warn,
+                    format!("{} (outdated, {} days old)", version, age),
+                )
+                .fix("Update with `yt-dlp -U`; old releases break as sites change"),
+                _ => Check::new(area, "yt-dlp", CheckStatus::Ok, version.clone()),
+            }
+        }
+    });
+
+    checks.push(if probes.whisper_downloaded {
+        Check::new(
+            area,
+            "Whisper model",
+            CheckStatus::Ok,
+            probes.whisper_model.clone(),
+        )
+    } else {
+        Check::new(
+            area,
+            "Whisper model",
+            CheckStatus::Warn,
+            format!("{} (not downloaded)", probes.whisper_model),
+        )
+        .fix("Run `synoid-core setup --only whisper`")
+    });
+
+    checks.push(match &probes.ollama {
+        None => Check::new(area, "Ollama", CheckStatus::Fail, "not reachable")
+            .fix("Start it with `ollama serve`, or set SYNOID_API_URL"),
+        Some(version) => match major_minor(version) {
+            Some(v) if v < MIN_OLLAMA => Check::new(
+                area,
+                "Ollama",
+                CheckStatus::Warn,
+                format!("{} (outdated)", version),
+            )
+            .fix(format!(
+                "Ollama {}.{} or newer is needed for the default models",
+                MIN_OLLAMA.0, MIN_OLLAMA.1
+            )),
+            _ => Check::new(area, "Ollama", CheckStatus::Ok, version.clone()),
+        },
+    });
+
+    checks.push(match &probes.gpu_driver {
+        Some((name, driver)) => Check::new(
+            area,
+            "GPU driver",
+            CheckStatus::Ok,
+            format!("{} (driver {})", name, driver),
+        ),
+        None => Check::new(
+            area,
+            "GPU driver",
+            CheckStatus::Warn,
+            "no NVIDIA GPU found; encoding runs on the CPU",
+        ),
+    });
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_tools_fail_or_warn_by_importance() {
+        let checks = check_tools_with(|tool, _| {
+            (tool == "ffmpeg").then(|| "ffmpeg version 7.0".to_string())
+        });
+        let status: Vec<(&str, CheckStatus)> =
+            checks.iter().map(|c| (c.name.as_str(), c.status)).collect();
+        assert_eq!(
+            status,
+            [
+                ("ffmpeg", CheckStatus::Ok),
+                ("ffprobe", CheckStatus::Fail),
+                ("yt-dlp", CheckStatus::Warn),
+            ]
+        );
+        assert!(checks[1].fix.as_deref().unwrap().contains("ffprobe"));
+
+        let text = report(&checks);
+        assert!(text.starts_with("External tools\n"));
+        assert!(text.ends_with("1 problem(s) need fixing before SYNOID can edit."));
+    }
+
+    #[test]
+    fn ollama_check_tells_missing_server_from_missing_model() {
+        let url = "http://localhost:11434";
+        let down = ollama_check(url, "gemma4:26b", Err("connection refused".into()));
+        assert_eq!(down.status, CheckStatus::Fail);
+
+        let installed = Ok(vec!["llama3:latest".to_string()]);
+        assert_eq!(
+            ollama_check(url, "llama3", installed.clone()).status,
+            CheckStatus::Ok
+        );
+        let missing = ollama_check(url, "gemma4:26b", installed);
+        assert_eq!(missing.status, CheckStatus::Warn);
+        assert!(missing.fix.unwrap().contains("ollama pull gemma4:26b"));
+    }
+
+    #[test]
+    fn version_report_aggregates_every_probe() {
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let probes = VersionProbes {
+            ffmpeg: Some("ffmpeg version 4.4.2-0ubuntu0.22.04.1 Copyright (c) 2000-2021".into()),
+            yt_dlp: Some("2026.09.20".into()),
+            whisper_model: "base".into(),
+            whisper_downloaded: true,
+            ollama: None,
+            gpu_driver: Some(("NVIDIA GeForce RTX 4070".into(), "560.35".into())),
+        };
+        let checks = version_checks(&probes, today);
+        let status: Vec<(&str, CheckStatus)> =
+            checks.iter().map(|c| (c.name.as_str(), c.status)).collect();
+        assert_eq!(
+            status,
+            [
+                ("SYNOID", CheckStatus::Ok),
+                ("ffmpeg", CheckStatus::Warn),
+                ("yt-dlp", CheckStatus::Ok),
+                ("Whisper model", CheckStatus::Ok),
+                ("Ollama", CheckStatus::Fail),
+                ("GPU driver", CheckStatus::Ok),
+            ]
+        );
+        assert_eq!(checks[1].detail, "4.4.2-0ubuntu0.22.04.1 (outdated)");
+        assert_eq!(checks[5].detail, "NVIDIA GeForce RTX 4070 (driver 560.35)");
+
+        let stale = VersionProbes {
+            ffmpeg: Some("ffmpeg version N-113000-g1234 Copyright".into()),
+            yt_dlp: Some("2025.01.15".into()),
+            ollama: Some("0.5.7".into()),
+            ..probes
+        };
+        let checks = version_checks(&stale, today);
+        assert_eq!(checks[1].status, CheckStatus::Ok);
+        assert!(checks[2].detail.contains("outdated"));
+        assert_eq!(checks[4].status, CheckStatus::Warn);
+
+        let text = report(&checks);
+        assert!(text.starts_with("Versions\n  ✅ SYNOID: "));
+    }
+
+    #[test]
+    fn setup_steps_parse_by_name() {
+        assert_eq!("Whisper".parse::<CheckArea>(), Ok(CheckArea::Whisper));
+        assert!("models".parse::<CheckArea>().is_err());
+    }
+}
@@ -4,7 +4,9 @@
 // Provides continuous self-monitoring, crash recovery, and uptime guarantees.
 // The HealthMonitor runs as a background task and periodically checks system health.
 
+use crate::agent::engines::proc;
 use crate::agent::engines::process_utils::CommandExt;
+use crate::events::{EventBus, SynoidEvent};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -24,6 +26,7 @@ pub struct HealthMonitor {
     is_running: Arc<AtomicBool>,
     heartbeat_count: Arc<AtomicU64>,
     check_interval: Duration,
+    events: Option<EventBus>,
 }
 
 impl HealthMonitor {
@@ -34,9 +37,16 @@ impl HealthMonitor {
             is_running: Arc::new(AtomicBool::new(false)),
             heartbeat_count: Arc::new(AtomicU64::new(0)),
             check_interval: Duration::from_secs(check_interval_secs),
+            events: None,
         }
     }
 
+    /// Publish every heartbeat to `bus` as a `HealthTick`.
+    pub fn with_events(mut self, bus: EventBus) -> Self {
+        self.events = Some(bus);
+        self
+    }
+
     /// Get system uptime in seconds
     pub fn uptime_secs(&self) -> u64 {
         self.start_time.elapsed().as_secs()
@@ -58,6 +68,8 @@ impl HealthMonitor {
         let is_running = self.is_running.clone();
         let heartbeat_count = self.heartbeat_count.clone();
         let interval = self.check_interval;
+        let start_time = self.start_time;
+        let events = self.events.clone();
 
         is_running.store(true, Ordering::Relaxed);
         let shutdown = is_running.clone();
@@ -78,6 +90,14 @@ impl HealthMonitor {
                 let mem_ok = check_memory_health();
                 // Check disk space
                 let disk_ok = check_disk_health();
+                if let Some(bus) = &events {
+                    bus.publish(SynoidEvent::HealthTick {
+                        heartbeat: count,
+                        uptime_secs: start_time.elapsed().as_secs(),
+                        memory_ok: mem_ok,
+                        disk_ok,
+                    });
+                }
 
                 // Only log on state transitions to prevent log spam
                 if !mem_ok && prev_mem_ok {
@@ -192,8 +212,10 @@ pub fn check_dependencies() -> Vec<String> {
     // Required dependencies — the app cannot function well without these
     let required = vec![("ffmpeg", "-version"), ("python", "--version")];
 
+    // Unmanaged: presence checks must answer at startup even if renders
+    // already hold every slot
     for (dep, flag) in required {
-        let mut found = std::process::Command::new(dep)
+        let mut found = proc::blocking_unmanaged(dep)
             .stealth()
             .arg(flag)
             .stdout(std::process::Stdio::null())
@@ -204,7 +226,7 @@ pub fn check_dependencies() -> Vec<String> {
 
         // Special fallbacks for common developer environments
         if !found && dep == "python" {
-            found = std::process::Command::new("python3")
+            found = proc::blocking_unmanaged("python3")
                 .stealth()
                 .arg(flag)
                 .stdout(std::process::Stdio::null())
@@ -223,7 +245,7 @@ pub fn check_dependencies() -> Vec<String> {
     let optional = vec![("yt-dlp", "--version"), ("ollama", "--version")];
 
     for (dep, flag) in optional {
-        let mut found = std::process::Command::new(dep)
+        let mut found = proc::blocking_unmanaged(dep)
             .stealth()
             .arg(flag)
             .stdout(std::process::Stdio::null())
@@ -235,7 +257,7 @@ pub fn check_dependencies() -> Vec<String> {
         if !found && dep == "yt-dlp" {
             // Check if available as a python module
             for py_cmd in ["python3", "python", "py"] {
-                if std::process::Command::new(py_cmd)
+                if proc::blocking_unmanaged(py_cmd)
                     .stealth()
                     .args(["-m", "yt_dlp", "--version"])
                     .stdout(std::process::Stdio::null())
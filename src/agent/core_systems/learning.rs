@@ -64,7 +64,7 @@ pub struct LearningKernel {
 
 impl LearningKernel {
     pub fn new() -> Self {
-        let suffix = std::env::var("SYNOID_INSTANCE_ID").unwrap_or_default();
+        let suffix = crate::config::current().instance_id.unwrap_or_default();
         let cache_dir = format!("cortex_cache{}", suffix);
         let _ = fs::create_dir_all(&cache_dir);
         let path = PathBuf::from(&cache_dir).join("brain_memory.json");
@@ -172,7 +172,7 @@ impl LearningKernel {
     }
 
     fn log_learned_style_to_markdown(&self, key: &str, pattern: &EditingPattern) {
-        let suffix = std::env::var("SYNOID_INSTANCE_ID").unwrap_or_default();
+        let suffix = crate::config::current().instance_id.unwrap_or_default();
         let cache_dir = format!("cortex_cache{}", suffix);
         let md_path = PathBuf::from(&cache_dir).join("learned_styles.md");
         let _ = fs::create_dir_all(&cache_dir);
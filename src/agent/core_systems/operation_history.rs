@@ -0,0 +1,289 @@
+// SYNOID Operation History
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Every completed clip/compress/edit is appended to
+// `cortex_cache{instance}/operation_history.json` so it can be listed,
+// filtered and re-run with tweaked parameters from the GUI or CLI.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::warn;
+
+const HISTORY_FILE: &str = "operation_history.json";
+/// Oldest records are dropped past this many.
+const MAX_RECORDS: usize = 1000;
+
+/// Serialises `record`'s load-append-rewrite, which the editor queue, the GUI
+/// and the CLI can run concurrently; otherwise one would drop another's entry.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    Clip,
+    Compress,
+    Edit,
+}
+
+impl OperationKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Clip => "clip",
+            Self::Compress => "compress",
+            Self::Edit => "edit",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationRecord {
+    pub id: u64,
+    pub kind: OperationKind,
+    pub input: PathBuf,
+    pub output: Option<PathBuf>,
+    /// Command-specific parameters, e.g. `start`/`duration` for a clip.
+    pub params: BTreeMap<String, String>,
+    pub started_at: String,
+    pub duration_secs: f64,
+    pub success: bool,
+    /// Summary on success, error message on failure.
+    pub result: String,
+}
+
+impl OperationRecord {
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.params.get(key).map(String::as_str)
+    }
+
+    pub fn param_f64(&self, key: &str) -> Option<f64> {
+        self.param(key)?.parse().ok()
+    }
+
+    pub fn param_bool(&self, key: &str) -> Option<bool> {
+        self.param(key)?.parse().ok()
+    }
+
+    /// One-line description for listings.
+    pub fn summary(&self) -> String {
+        let params: Vec<String> = self
+            .params
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect();
+        format!(
+            "#{} {} {} {:?} → {} [{}] ({:.1}s)",
+            self.id,
+            if self.success { "✅" } else { "❌" },
+            self.kind.label(),
+            self.input,
+            self.output
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            params.join(", "),
+            self.duration_secs
+        )
+    }
+
+    /// Case-insensitive match against kind, paths and parameter values.
+    pub fn matches(&self, filter: &str) -> bool {
+        let needle = filter.trim().to_lowercase();
+        if needle.is_empty() {
+            return true;
+        }
+        let haystack = format!(
+            "{} {} {} {}",
+            self.kind.label(),
+            self.input.display(),
+            self.output
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+            self.params
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+        .to_lowercase();
+        haystack.contains(&needle)
+    }
+
+    /// Copy of this record with `key=value` overrides applied. `input` and
+    /// `output` replace the paths; any other key must be an existing parameter.
+    pub fn with_overrides(&self, overrides: &[(String, String)]) -> Result<Self, String> {
+        let mut next = self.clone();
+        for (key, value) in overrides {
+            match key.as_str() {
+                "input" => next.input = PathBuf::from(value),
+                "output" => next.output = Some(PathBuf::from(value)),
+                k if next.params.contains_key(k) => {
+                    next.params.insert(k.to_string(), value.clone());
+                }
+                k => {
+                    let known: Vec<&str> = self.params.keys().map(String::as_str).collect();
+                    return Err(format!(
+                        "Unknown parameter '{}' for {} (expected input, output or one of: {})",
+                        k,
+                        self.kind.label(),
+                        known.join(", ")
+                    ));
+                }
+            }
+        }
+        Ok(next)
+    }
+}
+
+/// Parse a `--set key=value` argument.
+pub fn parse_override(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((k, v)) if !k.trim().is_empty() => Ok((k.trim().to_string(), v.to_string())),
+        _ => Err(format!("Expected key=value, got '{}'", arg)),
+    }
+}
+
+/// Persisted list of completed operations.
+pub struct OperationHistory {
+    path: PathBuf,
+}
+
+impl OperationHistory {
+    pub fn for_instance(instance_id: &str) -> Self {
+        Self::at(PathBuf::from(format!("cortex_cache{}", instance_id)).join(HISTORY_FILE))
+    }
+
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All records, oldest first.
+    pub fn load(&self) -> Vec<OperationRecord> {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|d| serde_json::from_str(&d).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, id: u64) -> Option<OperationRecord> {
+        self.load().into_iter().find(|r| r.id == id)
+    }
+
+    /// Append a finished operation and return its id.
+    pub fn record(
+        &self,
+        kind: OperationKind,
+        input: &Path,
+        output: Option<&Path>,
+        params: BTreeMap<String, String>,
+        started_at: chrono::DateTime<chrono::Utc>,
+        result: &Result<String, String>,
+    ) -> u64 {
+        let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut records = self.load();
+        let id = records.iter().map(|r| r.id).max().unwrap_or(0) + 1;
+        records.push(OperationRecord {
+            id,
+            kind,
+            input: input.to_path_buf(),
+            output: output.map(Path::to_path_buf),
+            params,
+            started_at: started_at.to_rfc3339(),
+            duration_secs: (chrono::Utc::now() - started_at).num_milliseconds() as f64 / 1000.0,
+            success: result.is_ok(),
+            result: match result {
+                Ok(s) | Err(s) => s.clone(),
+            },
+        });
+        if records.len() > MAX_RECORDS {
+            records.drain(..records.len() - MAX_RECORDS);
+        }
+        if let Some(dir) = self.path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        match serde_json::to_string_pretty(&records) {
+            Ok(json) => {
+                // Renamed into place so `load` never sees a half-written file
+                let tmp = self.path.with_extension("json.tmp");
+                if let Err(e) = fs::write(&tmp, json).and_then(|_| fs::rename(&tmp, &self.path)) {
+                    warn!("[HISTORY] Failed to write {:?}: {}", self.path, e);
+                }
+            }
+            Err(e) => warn!("[HISTORY] Failed to serialise history: {}", e),
+        }
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_round_trip_and_rerun_overrides() {
+        let path = std::env::temp_dir().join(format!("synoid_history_{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let history = OperationHistory::at(path.clone());
+
+        let params: BTreeMap<String, String> =
+            [("start".to_string(), "5".to_string()), ("duration".to_string(), "10".to_string())]
+                .into_iter()
+                .collect();
+        let id = history.record(
+            OperationKind::Clip,
+            Path::new("in.mp4"),
+            Some(Path::new("out.mp4")),
+            params,
+            chrono::Utc::now(),
+            &Ok("saved".to_string()),
+        );
+        assert_eq!(id, 1);
+
+        let rec = history.get(1).unwrap();
+        assert!(rec.matches("CLIP"));
+        assert!(!rec.matches("compress"));
+
+        let sets = vec![parse_override("duration=4").unwrap()];
+        let rerun = rec.with_overrides(&sets).unwrap();
+        assert_eq!(rerun.param_f64("duration"), Some(4.0));
+        assert!(rec.with_overrides(&[("bogus".into(), "1".into())]).is_err());
+        assert!(parse_override("novalue").is_err());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn concurrent_records_are_all_kept() {
+        let path =
+            std::env::temp_dir().join(format!("synoid_history_race_{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    let history = OperationHistory::at(path.clone());
+                    for _ in 0..10 {
+                        history.record(
+                            OperationKind::Compress,
+                            Path::new("in.mp4"),
+                            None,
+                            BTreeMap::new(),
+                            chrono::Utc::now(),
+                            &Ok("done".to_string()),
+                        );
+                    }
+                });
+            }
+        });
+
+        let ids: Vec<u64> = OperationHistory::at(path.clone()).load().iter().map(|r| r.id).collect();
+        assert_eq!(ids, (1..=80).collect::<Vec<u64>>());
+        let _ = fs::remove_file(&path);
+    }
+}
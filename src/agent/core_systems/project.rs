@@ -0,0 +1,795 @@
+// SYNOID Project Files
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// A `.synoid` project is a directory tying together what goes into and comes
+// out of an edit: source references (with hashes), every intent tried,
+// pointers to cached analysis, plans, and each render with its QA check and
+// provenance report. `manifest.json` at the root holds the index; plans and
+// reports are copied under `plans/` and `reports/` so the project stays
+// meaningful after the sidecars next to the outputs are gone.
+//
+// `project archive` packs all of it, outputs and optionally the sources into
+// one zip; `project import` unpacks it elsewhere and repoints the manifest.
+//
+// The open project is remembered in `cortex_cache{instance}/active_project`;
+// Embody and Process record into it automatically while one is active.
+
+use crate::agent::security::download_guard::DownloadGuard;
+use crate::agent::specialized::smart_editor::EditPlan;
+use crate::agent::tools::archive::{self, ZipWriter};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+pub const PROJECT_EXTENSION: &str = "synoid";
+const MANIFEST_FILE: &str = "manifest.json";
+const ACTIVE_PROJECT_FILE: &str = "active_project";
+const FORMAT_VERSION: u32 = 1;
+const ARCHIVE_INDEX: &str = "archive.json";
+const ARCHIVE_FORMAT: &str = "synoid-project";
+const ARCHIVE_VERSION: u32 = 1;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Manifest
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SourceRef {
+    pub path: PathBuf,
+    pub sha256: String,
+    pub size_bytes: u64,
+    /// Modification time (unix secs) the hash was taken at; an unchanged
+    /// size and mtime skip re-hashing.
+    #[serde(default)]
+    pub modified: Option<u64>,
+    pub added_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentEntry {
+    pub at: String,
+    /// Command that used it ("embody", "process").
+    pub command: String,
+    pub source: PathBuf,
+    pub intent: String,
+}
+
+/// Cached analysis produced for a source (transcript, scene cache, ...).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AnalysisRef {
+    pub source: PathBuf,
+    pub kind: String,
+    pub path: PathBuf,
+}
+
+/// Post-render check that the output actually decodes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderQa {
+    pub decodable: bool,
+    pub duration_secs: Option<f64>,
+    pub size_bytes: u64,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl RenderQa {
+    /// Decode-check `output`. Blocking.
+    pub fn check(output: &Path) -> Self {
+        let size_bytes = fs::metadata(output).map(|m| m.len()).unwrap_or(0);
+        match DownloadGuard::check_decodable(output, None) {
+            Ok(duration) => RenderQa {
+                decodable: true,
+                duration_secs: duration,
+                size_bytes,
+                error: None,
+            },
+            Err(e) => RenderQa {
+                decodable: false,
+                duration_secs: None,
+                size_bytes,
+                error: Some(e),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderEntry {
+    pub id: u64,
+    pub at: String,
+    pub command: String,
+    pub source: PathBuf,
+    #[serde(default)]
+    pub source_sha256: Option<String>,
+    pub intent: String,
+    pub output: PathBuf,
+    pub success: bool,
+    /// Summary on success, error message on failure.
+    pub result: String,
+    /// Copy of the edit plan, relative to the project root.
+    #[serde(default)]
+    pub plan: Option<PathBuf>,
+    #[serde(default)]
+    pub qa: Option<RenderQa>,
+    /// Provenance report, relative to the project root.
+    #[serde(default)]
+    pub provenance: Option<PathBuf>,
+}
+
+/// Written to `reports/render-<id>.provenance.json` for every render.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Provenance {
+    pub synoid_version: String,
+    pub render_id: u64,
+    pub command: String,
+    pub source: PathBuf,
+    pub source_sha256: Option<String>,
+    pub intent: String,
+    pub params: BTreeMap<String, String>,
+    pub started_at: String,
+    pub finished_at: String,
+    pub output: PathBuf,
+    pub os: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectManifest {
+    pub format_version: u32,
+    pub name: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub sources: Vec<SourceRef>,
+    #[serde(default)]
+    pub intents: Vec<IntentEntry>,
+    #[serde(default)]
+    pub analysis: Vec<AnalysisRef>,
+    #[serde(default)]
+    pub renders: Vec<RenderEntry>,
+}
+
+/// `archive.json` in a project archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ArchiveIndex {
+    format: String,
+    version: u32,
+    includes_sources: bool,
+    /// Entry name → the path the manifest records for that file.
+    files: BTreeMap<String, PathBuf>,
+}
+
+/// What `Project::archive` packed.
+#[derive(Debug, Clone, Copy)]
+pub struct ArchiveSummary {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// What a finished Embody/Process run hands to `Project::record_render`.
+#[derive(Debug, Clone)]
+pub struct RenderInput {
+    pub command: String,
+    pub source: PathBuf,
+    pub intent: String,
+    pub output: PathBuf,
+    pub params: BTreeMap<String, String>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub result: Result<String, String>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Project
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub struct Project {
+    root: PathBuf,
+    pub manifest: ProjectManifest,
+}
+
+impl Project {
+    /// Create a new project directory. `.synoid` is appended to `path` when
+    /// missing; the name defaults to the directory stem.
+    pub fn create(path: &Path, name: Option<&str>) -> io::Result<Self> {
+        let root = with_project_extension(path);
+        if root.join(MANIFEST_FILE).exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{:?} is already a project", root),
+            ));
+        }
+        fs::create_dir_all(root.join("plans"))?;
+        fs::create_dir_all(root.join("reports"))?;
+        let name = name
+            .map(str::to_string)
+            .or_else(|| root.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .unwrap_or_else(|| "Untitled".to_string());
+        let project = Self {
+            root,
+            manifest: ProjectManifest {
+                format_version: FORMAT_VERSION,
+                name,
+                created_at: now(),
+                sources: Vec::new(),
+                intents: Vec::new(),
+                analysis: Vec::new(),
+                renders: Vec::new(),
+            },
+        };
+        project.save()?;
+        Ok(project)
+    }
+
+    /// Open a project by its directory or its `manifest.json`.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let root = if path.file_name().is_some_and(|n| n == MANIFEST_FILE) {
+            path.parent().unwrap_or(Path::new(".")).to_path_buf()
+        } else if path.join(MANIFEST_FILE).exists() {
+            path.to_path_buf()
+        } else {
+            with_project_extension(path)
+        };
+        let data = fs::read_to_string(root.join(MANIFEST_FILE))?;
+        let manifest: ProjectManifest = serde_json::from_str(&data)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if manifest.format_version > FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "project format v{} is newer than this build supports (v{})",
+                    manifest.format_version, FORMAT_VERSION
+                ),
+            ));
+        }
+        Ok(Self { root, manifest })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.manifest)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        // Write-then-rename so a crash never leaves a truncated manifest
+        let tmp = self.root.join(format!("{}.tmp", MANIFEST_FILE));
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, self.root.join(MANIFEST_FILE))
+    }
+
+    /// Add or refresh a source reference, hashing the file unless its size
+    /// and mtime match the recorded ones.
+    pub fn add_source(&mut self, path: &Path) -> io::Result<SourceRef> {
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let meta = fs::metadata(&path)?;
+        let modified = meta
+            .modified()
+            .ok()
+            .and_then(|m| m.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs());
+
+        if let Some(existing) = self.manifest.sources.iter().find(|s| s.path == path) {
+            if existing.size_bytes == meta.len() && existing.modified == modified && modified.is_some() {
+                return Ok(existing.clone());
+            }
+        }
+
+        let source = SourceRef {
+            sha256: sha256_file(&path)?,
+            size_bytes: meta.len(),
+            modified,
+            added_at: now(),
+            path: path.clone(),
+        };
+        match self.manifest.sources.iter_mut().find(|s| s.path == path) {
+            Some(existing) => *existing = source.clone(),
+            None => self.manifest.sources.push(source.clone()),
+        }
+        Ok(source)
+    }
+
+    pub fn record_intent(&mut self, command: &str, source: &Path, intent: &str) {
+        self.manifest.intents.push(IntentEntry {
+            at: now(),
+            command: command.to_string(),
+            source: source.to_path_buf(),
+            intent: intent.to_string(),
+        });
+    }
+
+    pub fn add_analysis(&mut self, source: &Path, kind: &str, path: &Path) {
+        let entry = AnalysisRef {
+            source: source.to_path_buf(),
+            kind: kind.to_string(),
+            path: path.to_path_buf(),
+        };
+        if !self.manifest.analysis.contains(&entry) {
+            self.manifest.analysis.push(entry);
+        }
+    }
+
+    /// Record a finished render: hash its source, copy its edit plan in, run
+    /// a decode check on the output and write a provenance report. Blocking.
+    pub fn record_render(&mut self, render: RenderInput) -> u64 {
+        let id = self.manifest.renders.iter().map(|r| r.id).max().unwrap_or(0) + 1;
+
+        let source_sha256 = match self.add_source(&render.source) {
+            Ok(s) => Some(s.sha256),
+            Err(e) => {
+                warn!("[PROJECT] Could not hash source {:?}: {}", render.source, e);
+                None
+            }
+        };
+
+        // The smart editor caches the source transcript next to the input
+        let transcript = render.source.with_extension("srt");
+        if transcript.exists() {
+            self.add_analysis(&render.source, "transcript", &transcript);
+        }
+
+        let plan = EditPlan::path_for(&render.output);
+        let plan = if render.result.is_ok() && plan.exists() {
+            let rel = PathBuf::from("plans").join(format!("render-{}.plan.json", id));
+            match fs::copy(&plan, self.root.join(&rel)) {
+                Ok(_) => Some(rel),
+                Err(e) => {
+                    warn!("[PROJECT] Could not copy plan {:?}: {}", plan, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let qa = render.result.is_ok().then(|| RenderQa::check(&render.output));
+
+        let provenance = Provenance {
+            synoid_version: env!("CARGO_PKG_VERSION").to_string(),
+            render_id: id,
+            command: render.command.clone(),
+            source: render.source.clone(),
+            source_sha256: source_sha256.clone(),
+            intent: render.intent.clone(),
+            params: render.params.clone(),
+            started_at: render.started_at.to_rfc3339(),
+            finished_at: now(),
+            output: render.output.clone(),
+            os: std::env::consts::OS.to_string(),
+        };
+        let rel = PathBuf::from("reports").join(format!("render-{}.provenance.json", id));
+        let provenance = match serde_json::to_string_pretty(&provenance)
+            .map_err(io::Error::other)
+            .and_then(|json| fs::write(self.root.join(&rel), json))
+        {
+            Ok(()) => Some(rel),
+            Err(e) => {
+                warn!("[PROJECT] Could not write provenance report: {}", e);
+                None
+            }
+        };
+
+        let (success, result) = match render.result {
+            Ok(s) => (true, s),
+            Err(e) => (false, e),
+        };
+        self.manifest.renders.push(RenderEntry {
+            id,
+            at: now(),
+            command: render.command,
+            source: render.source,
+            source_sha256,
+            intent: render.intent,
+            output: render.output,
+            success,
+            result,
+            plan,
+            qa,
+            provenance,
+        });
+        id
+    }
+
+    /// Multi-line overview for `project status`.
+    pub fn status(&self) -> String {
+        let m = &self.manifest;
+        let mut out = format!(
+            "📁 {} ({})\n   created {}\n",
+            m.name,
+            self.root.display(),
+            m.created_at
+        );
+        out.push_str(&format!("   sources: {}\n", m.sources.len()));
+        for s in &m.sources {
+            out.push_str(&format!(
+                "     • {} [{}…, {:.1} MB]\n",
+                s.path.display(),
+                &s.sha256[..s.sha256.len().min(12)],
+                s.size_bytes as f64 / 1_048_576.0
+            ));
+        }
+        out.push_str(&format!("   intents: {}\n", m.intents.len()));
+        if let Some(last) = m.intents.last() {
+            out.push_str(&format!("     last: \"{}\" ({})\n", last.intent, last.command));
+        }
+        out.push_str(&format!("   analysis: {}\n", m.analysis.len()));
+        out.push_str(&format!("   renders: {}\n", m.renders.len()));
+        for r in &m.renders {
+            let qa = match &r.qa {
+                Some(q) if q.decodable => format!(
+                    "QA ok, {:.1}s",
+                    q.duration_secs.unwrap_or(0.0)
+                ),
+                Some(q) => format!("QA failed: {}", q.error.as_deref().unwrap_or("?")),
+                None => "no QA".to_string(),
+            };
+            out.push_str(&format!(
+                "     #{} {} {} → {} ({})\n",
+                r.id,
+                if r.success { "✅" } else { "❌" },
+                r.command,
+                r.output.display(),
+                qa
+            ));
+        }
+        out
+    }
+
+    // ── Archives ─────────────────────────────────────────────────────────────
+
+    /// Pack the project into a zip at `out` for handoff: the manifest, plans,
+    /// reports and cached analysis, every render output still on disk with its
+    /// subtitle sidecar and, with `include_sources`, the source files.
+    /// Blocking.
+    pub fn archive(&self, out: &Path, include_sources: bool) -> io::Result<ArchiveSummary> {
+        let mut index = ArchiveIndex {
+            format: ARCHIVE_FORMAT.to_string(),
+            version: ARCHIVE_VERSION,
+            includes_sources: include_sources,
+            files: BTreeMap::new(),
+        };
+        let mut packed: Vec<(String, PathBuf)> = Vec::new();
+        for dir in ["plans", "reports"] {
+            let Ok(entries) = fs::read_dir(self.root.join(dir)) else {
+                continue;
+            };
+            let mut files: Vec<PathBuf> = entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_file())
+                .collect();
+            files.sort();
+            for path in files {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                packed.push((format!("{}/{}", dir, archive::entry_name(&name)), path));
+            }
+        }
+
+        // Later renders to the same path overwrote earlier ones; pack it once
+        let mut external = |dir: String, path: &Path, packed: &mut Vec<(String, PathBuf)>| {
+            if !path.is_file() || index.files.values().any(|p| p == path) {
+                return;
+            }
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let entry = format!("{}/{}", dir, archive::entry_name(&name));
+            index.files.insert(entry.clone(), path.to_path_buf());
+            packed.push((entry, path.to_path_buf()));
+        };
+        for render in &self.manifest.renders {
+            let dir = format!("outputs/render-{}", render.id);
+            external(dir.clone(), &render.output, &mut packed);
+            external(dir, &render.output.with_extension("srt"), &mut packed);
+        }
+        for (i, analysis) in self.manifest.analysis.iter().enumerate() {
+            external(format!("analysis/{}", i + 1), &analysis.path, &mut packed);
+        }
+        if include_sources {
+            for (i, source) in self.manifest.sources.iter().enumerate() {
+                external(format!("sources/{}", i + 1), &source.path, &mut packed);
+            }
+        }
+
+        let written = (|| {
+            let mut zip = ZipWriter::new(io::BufWriter::new(fs::File::create(out)?));
+            let index = serde_json::to_vec_pretty(&index).map_err(io::Error::other)?;
+            let manifest = serde_json::to_vec_pretty(&self.manifest).map_err(io::Error::other)?;
+            archive::add_bytes(&mut zip, ARCHIVE_INDEX, &index)?;
+            archive::add_bytes(&mut zip, MANIFEST_FILE, &manifest)?;
+            for (entry, path) in &packed {
+                archive::add_file(&mut zip, entry, path)?;
+            }
+            zip.finish()?.flush()
+        })();
+        if let Err(e) = written {
+            let _ = fs::remove_file(out);
+            return Err(e);
+        }
+        Ok(ArchiveSummary {
+            files: packed.len() + 2,
+            bytes: fs::metadata(out)?.len(),
+        })
+    }
+
+    /// Unpack an archive made by `archive` into a new project at `into`, with
+    /// the manifest pointing at the unpacked outputs and sources. Sources left
+    /// out of the archive keep their original paths. Blocking.
+    pub fn import_archive(archive: &Path, into: &Path) -> io::Result<Self> {
+        let mut zip = archive::open(archive)?;
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+        let index: ArchiveIndex = match archive::read_entry(&mut zip, ARCHIVE_INDEX)? {
+            Some(data) => serde_json::from_slice(&data).map_err(|e| invalid(e.to_string()))?,
+            None => return Err(invalid(format!("{:?} is not a SYNOID project archive", archive))),
+        };
+        if index.format != ARCHIVE_FORMAT || index.version > ARCHIVE_VERSION {
+            return Err(invalid(format!(
+                "unsupported archive ({} v{})",
+                index.format, index.version
+            )));
+        }
+        let data = archive::read_entry(&mut zip, MANIFEST_FILE)?
+            .ok_or_else(|| invalid("archive has no manifest".to_string()))?;
+        let mut manifest: ProjectManifest =
+            serde_json::from_slice(&data).map_err(|e| invalid(e.to_string()))?;
+        if manifest.format_version > FORMAT_VERSION {
+            return Err(invalid(format!(
+                "project format v{} is newer than this build supports (v{})",
+                manifest.format_version, FORMAT_VERSION
+            )));
+        }
+
+        let root = with_project_extension(into);
+        if root.join(MANIFEST_FILE).exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{:?} is already a project", root),
+            ));
+        }
+        fs::create_dir_all(root.join("plans"))?;
+        fs::create_dir_all(root.join("reports"))?;
+
+        let mut moved: BTreeMap<PathBuf, PathBuf> = BTreeMap::new();
+        for name in archive::entry_names(&zip) {
+            if name.ends_with('/') || name == ARCHIVE_INDEX || name == MANIFEST_FILE {
+                continue;
+            }
+            let dest = archive::entry_path(&root, &name)
+                .ok_or_else(|| invalid(format!("unsafe entry name '{}'", name)))?;
+            archive::extract(&mut zip, &name, &dest)?;
+            if let Some(original) = index.files.get(&name) {
+                moved.insert(original.clone(), fs::canonicalize(&dest).unwrap_or(dest));
+            }
+        }
+
+        let relocate = |path: &mut PathBuf| {
+            if let Some(new) = moved.get(path) {
+                *path = new.clone();
+            }
+        };
+        for source in &mut manifest.sources {
+            if moved.contains_key(&source.path) {
+                relocate(&mut source.path);
+                // Unpacking changed the mtime; the next add_source re-hashes
+                source.modified = None;
+            }
+        }
+        for intent in &mut manifest.intents {
+            relocate(&mut intent.source);
+        }
+        for analysis in &mut manifest.analysis {
+            relocate(&mut analysis.source);
+            relocate(&mut analysis.path);
+        }
+        for render in &mut manifest.renders {
+            relocate(&mut render.source);
+            relocate(&mut render.output);
+        }
+
+        let project = Self { root, manifest };
+        project.save()?;
+        Ok(project)
+    }
+
+    // ── Active project ───────────────────────────────────────────────────────
+
+    fn active_marker(instance_id: &str) -> PathBuf {
+        PathBuf::from(format!("cortex_cache{}", instance_id)).join(ACTIVE_PROJECT_FILE)
+    }
+
+    /// The project currently open for this instance, if any.
+    pub fn active(instance_id: &str) -> Option<Self> {
+        let root = fs::read_to_string(Self::active_marker(instance_id)).ok()?;
+        let root = root.trim();
+        if root.is_empty() {
+            return None;
+        }
+        match Self::open(Path::new(root)) {
+            Ok(p) => Some(p),
+            Err(e) => {
+                warn!("[PROJECT] Active project {:?} unreadable: {}", root, e);
+                None
+            }
+        }
+    }
+
+    /// Make `root` the open project for this instance, or close it with `None`.
+    pub fn set_active(instance_id: &str, root: Option<&Path>) -> io::Result<()> {
+        let marker = Self::active_marker(instance_id);
+        match root {
+            Some(root) => {
+                if let Some(dir) = marker.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                let root = fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+                fs::write(marker, root.to_string_lossy().as_bytes())
+            }
+            None => match fs::remove_file(marker) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            },
+        }
+    }
+
+    /// Apply `f` to the active project and save it. Does nothing when no
+    /// project is open; failures are logged, never returned, so recording
+    /// can't break the edit it describes. Blocking.
+    pub fn with_active(instance_id: &str, f: impl FnOnce(&mut Project)) {
+        let Some(mut project) = Self::active(instance_id) else {
+            return;
+        };
+        f(&mut project);
+        if let Err(e) = project.save() {
+            warn!("[PROJECT] Failed to save {:?}: {}", project.root, e);
+        }
+    }
+}
+
+/// Async wrapper for recording a render into the active project; hashing and
+/// the decode check run on the blocking pool.
+pub async fn record_render_in_active(instance_id: &str, render: RenderInput) {
+    let instance_id = instance_id.to_string();
+    let _ = tokio::task::spawn_blocking(move || {
+        Project::with_active(&instance_id, |p| {
+            p.record_render(render);
+        })
+    })
+    .await;
+}
+
+fn with_project_extension(path: &Path) -> PathBuf {
+    if path.extension().is_some_and(|e| e == PROJECT_EXTENSION) {
+        path.to_path_buf()
+    } else {
+        let mut s = path.as_os_str().to_os_string();
+        s.push(".");
+        s.push(PROJECT_EXTENSION);
+        PathBuf::from(s)
+    }
+}
+
+/// Hex SHA-256 of a file's contents. Blocking.
+pub fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0; 65536];
+    loop {
+        let count = file.read(&mut buffer)?;
+        if count == 0 {
+            break;
+        }
+        hasher.update(&buffer[..count]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn now() -> String {
+    chrono::Utc::now().to_rfc3339()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_round_trips_sources_intents_and_renders() {
+        let dir = std::env::temp_dir().join(format!("synoid_project_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let source = dir.join("source.mp4");
+        fs::write(&source, b"not really a video").unwrap();
+        let output = dir.join("out.mp4");
+
+        let mut project = Project::create(&dir.join("demo"), None).unwrap();
+        assert_eq!(project.root(), dir.join("demo.synoid"));
+        assert_eq!(project.manifest.name, "demo");
+        assert!(Project::create(&dir.join("demo.synoid"), None).is_err());
+
+        let first = project.add_source(&source).unwrap();
+        assert_eq!(first.size_bytes, 18);
+        assert_eq!(first.sha256.len(), 64);
+        assert_eq!(project.add_source(&source).unwrap(), first);
+
+        project.record_intent("embody", &source, "remove boring parts");
+        let id = project.record_render(RenderInput {
+            command: "embody".to_string(),
+            source: source.clone(),
+            intent: "remove boring parts".to_string(),
+            output: output.clone(),
+            params: BTreeMap::new(),
+            started_at: chrono::Utc::now(),
+            result: Err("render failed".to_string()),
+        });
+        assert_eq!(id, 1);
+        project.save().unwrap();
+
+        let reopened = Project::open(&dir.join("demo")).unwrap();
+        let m = &reopened.manifest;
+        assert_eq!(m.sources.len(), 1);
+        assert_eq!(m.intents[0].intent, "remove boring parts");
+        let render = &m.renders[0];
+        assert!(!render.success);
+        assert_eq!(render.source_sha256.as_deref(), Some(first.sha256.as_str()));
+        assert!(render.qa.is_none() && render.plan.is_none());
+        let report = reopened.root().join(render.provenance.as_ref().unwrap());
+        let provenance: Provenance =
+            serde_json::from_str(&fs::read_to_string(report).unwrap()).unwrap();
+        assert_eq!(provenance.render_id, 1);
+        assert!(reopened.status().contains("#1 ❌ embody"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn archives_unpack_into_a_project_on_another_machine() {
+        let dir = std::env::temp_dir().join(format!("synoid_archive_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let source = dir.join("source clip.mp4");
+        fs::write(&source, b"source bytes").unwrap();
+        let output = dir.join("out.mp4");
+        fs::write(&output, b"output bytes").unwrap();
+        fs::write(output.with_extension("srt"), "1\n00:00:00,000 --> 00:00:01,000\nHi\n").unwrap();
+        fs::write(EditPlan::path_for(&output), "{}").unwrap();
+
+        let mut project = Project::create(&dir.join("demo"), None).unwrap();
+        project.record_render(RenderInput {
+            command: "process".to_string(),
+            source: source.clone(),
+            intent: "keep the talking".to_string(),
+            output: output.clone(),
+            params: BTreeMap::new(),
+            started_at: chrono::Utc::now(),
+            result: Ok("done".to_string()),
+        });
+        project.save().unwrap();
+        let recorded_source = project.manifest.sources[0].path.clone();
+
+        let zip = dir.join("handoff.zip");
+        let summary = project.archive(&zip, true).unwrap();
+        // index, manifest, plan, provenance, output, srt, source
+        assert_eq!(summary.files, 7);
+
+        let copy = Project::import_archive(&zip, &dir.join("elsewhere")).unwrap();
+        assert_eq!(copy.root(), dir.join("elsewhere.synoid"));
+        let m = &copy.manifest;
+        assert!(m.sources[0].path.starts_with(copy.root()), "{:?}", m.sources[0].path);
+        assert_eq!(fs::read(&m.sources[0].path).unwrap(), b"source bytes");
+        let render = &m.renders[0];
+        assert_eq!(render.source, m.sources[0].path);
+        assert_eq!(fs::read(&render.output).unwrap(), b"output bytes");
+        assert!(render.output.with_extension("srt").exists());
+        assert!(copy.root().join(render.plan.as_ref().unwrap()).exists());
+        assert!(copy.root().join(render.provenance.as_ref().unwrap()).exists());
+        assert!(Project::import_archive(&zip, &dir.join("elsewhere")).is_err());
+
+        // Without sources the manifest keeps pointing at the originals
+        project.archive(&zip, false).unwrap();
+        let copy = Project::import_archive(&zip, &dir.join("slim")).unwrap();
+        assert_eq!(copy.manifest.sources[0].path, recorded_source);
+        assert!(copy.manifest.renders[0].output.starts_with(copy.root()));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,340 @@
+// SYNOID Setup — first-run wizard
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// `synoid-core setup` walks the same areas `doctor` checks: external tools,
+// Ollama and its model, the video and cache directories, and the Whisper
+// model. Each step shows the doctor checks, offers the fix (pull, create,
+// download), checks again, and the answers are merged into the config file.
+// `--only <step>` re-runs a single step; the GUI's Setup panel drives the
+// same helpers.
+
+use super::doctor::{self, Check, CheckArea, CheckStatus};
+use crate::agent::ollama_pull;
+use crate::agent::transcription;
+use crate::config::{self, ConfigLayer, SynoidConfig};
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Whisper models offered by the wizard, smallest first.
+pub use crate::agent::transcription::WHISPER_MODELS;
+
+/// Where the wizard suggests keeping videos.
+pub fn default_video_dir() -> String {
+    dirs::video_dir()
+        .or_else(|| dirs::home_dir().map(|h| h.join("Videos")))
+        .unwrap_or_else(|| PathBuf::from("Videos"))
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// `config` as it will be once `answers` are saved, so checks after a step
+/// see the new values without a restart.
+pub fn apply(config: &mut SynoidConfig, answers: &ConfigLayer) {
+    if let Some(model) = &answers.model {
+        config.model = model.clone();
+    }
+    if let Some(dir) = &answers.video_dir {
+        config.video_dir = Some(dir.clone());
+    }
+    if let Some(dir) = &answers.cache_dir {
+        config.cache_dir = dir.clone();
+    }
+    if let Some(model) = &answers.whisper_model {
+        config.whisper_model = model.clone();
+    }
+    if let Some(actions) = &answers.after_render {
+        config.after_render = actions.clone();
+    }
+}
+
+/// Create the video and cache directories.
+pub fn create_dirs(config: &SynoidConfig) -> Result<(), String> {
+    let dirs = config
+        .video_dir
+        .iter()
+        .chain(std::iter::once(&config.cache_dir));
+    for dir in dirs {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {}", dir, e))?;
+    }
+    Ok(())
+}
+
+/// Merge `answers` into the config file (`config::file_path()`), keeping
+/// whatever else it already holds.
+pub fn save(answers: &ConfigLayer) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+    let path = config::file_path();
+    save_to(&path, answers)?;
+    Ok(path)
+}
+
+fn save_to(
+    path: &Path,
+    answers: &ConfigLayer,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let existing = if path.exists() {
+        ConfigLayer::load(path)?
+    } else {
+        ConfigLayer::default()
+    };
+    existing.merge(answers.clone()).save(path)
+}
+
+/// Terminal prompts; with `assume_yes` every question takes its default.
+struct Prompt {
+    assume_yes: bool,
+}
+
+impl Prompt {
+    fn ask(&self, question: &str, default: &str) -> String {
+        if self.assume_yes {
+            println!("{} [{}]: {}", question, default, default);
+            return default.to_string();
+        }
+        print!("{} [{}]: ", question, default);
+        let _ = std::io::stdout().flush();
+        let mut line = String::new();
+        // A closed stdin answers every question with its default
+        let _ = std::io::stdin().lock().read_line(&mut line);
+        match line.trim() {
+            "" => default.to_string(),
+            answer => answer.to_string(),
+        }
+    }
+
+    fn confirm(&self, question: &str) -> bool {
+        let answer = self.ask(&format!("{} (y/n)", question), "y");
+        matches!(answer.to_lowercase().as_str(), "y" | "yes")
+    }
+}
+
+fn print_checks(checks: &[Check]) {
+    for check in checks {
+        println!("  {}", check.line());
+    }
+}
+
+/// Run the wizard, or only its `only` step, then save what changed.
+pub async fn run(
+    only: Option<CheckArea>,
+    assume_yes: bool,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let prompt = Prompt { assume_yes };
+    let mut config = config::current();
+    let mut answers = ConfigLayer::default();
+    let steps: Vec<CheckArea> = match only {
+        Some(area) => vec![area],
+        None => CheckArea::ALL.to_vec(),
+    };
+
+    println!("🛠️  SYNOID setup");
+    for (i, &area) in steps.iter().enumerate() {
+        println!("\n[{}/{}] {}", i + 1, steps.len(), area.title());
+        let checks = doctor::run_area(area, &config).await;
+        print_checks(&checks);
+
+        match area {
+            CheckArea::Tools => tools_step(&checks),
+            CheckArea::Ollama => ollama_step(&prompt, &config, &mut answers).await,
+            CheckArea::Dirs => dirs_step(&prompt, &config, &mut answers),
+            CheckArea::Whisper => whisper_step(&prompt, &config, &mut answers).await,
+            CheckArea::Versions => {}
+        }
+        apply(&mut config, &answers);
+
+        if area != CheckArea::Tools {
+            println!("  Checking again:");
+            print_checks(&doctor::run_area(area, &config).await);
+        }
+    }
+
+    if answers != ConfigLayer::default() {
+        let path = save(&answers)?;
+        println!("\n💾 Saved {}", path.display());
+    }
+    if only.is_none() {
+        println!("\n{}", doctor::report(&doctor::run_all(&config).await));
+    }
+    Ok(())
+}
+
+fn tools_step(checks: &[Check]) {
+    if checks.iter().any(|c| c.status != CheckStatus::Ok) {
+        println!("  Install the missing tools, then run `synoid-core setup --only tools` to check again.");
+    }
+}
+
+async fn ollama_step(prompt: &Prompt, config: &SynoidConfig, answers: &mut ConfigLayer) {
+    let models = match doctor::installed_models(&config.api_url).await {
+        Ok(models) => models,
+        // The check above already said how to start the server
+        Err(_) => return,
+    };
+    if !models.is_empty() {
+        println!("  Installed: {}", models.join(", "));
+    }
+    let model = prompt.ask("  Reasoning model", &config.model);
+    if model != config.model {
+        answers.model = Some(model.clone());
+    }
+    if doctor::has_model(&models, &model) || !prompt.confirm(&format!("  Pull {} now?", model)) {
+        return;
+    }
+
+    let base = doctor::ollama_base(&config.api_url).to_string();
+    let pull = tokio::spawn({
+        let model = model.clone();
+        async move { ollama_pull::pull_model(&base, &model).await }
+    });
+    while !pull.is_finished() {
+        if let Some(status) = ollama_pull::pull_status() {
+            print!("\r  {:<70}", status);
+            let _ = std::io::stdout().flush();
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    println!();
+    match pull.await {
+        Ok(Ok(())) => println!("  ✅ Pulled {}", model),
+        Ok(Err(e)) => println!("  ❌ Pull failed: {}", e),
+        Err(e) => println!("  ❌ Pull failed: {}", e),
+    }
+}
+
+fn dirs_step(prompt: &Prompt, config: &SynoidConfig, answers: &mut ConfigLayer) {
+    let video_default = config.video_dir.clone().unwrap_or_else(default_video_dir);
+    let video_dir = prompt.ask("  Video directory", &video_default);
+    let cache_dir = prompt.ask("  Cache directory", &config.cache_dir);
+
+    let mut chosen = config.clone();
+    chosen.video_dir = Some(video_dir.clone());
+    chosen.cache_dir = cache_dir.clone();
+    if let Err(e) = create_dirs(&chosen) {
+        println!("  ❌ {}", e);
+        return;
+    }
+    if config.video_dir.as_deref() != Some(video_dir.as_str()) {
+        answers.video_dir = Some(video_dir);
+    }
+    if cache_dir != config.cache_dir {
+        answers.cache_dir = Some(cache_dir);
+    }
+}
+
+async fn whisper_step(prompt: &Prompt, config: &SynoidConfig, answers: &mut ConfigLayer) {
+    println!(
+        "  Models: {} (larger is slower and more accurate)",
+        WHISPER_MODELS
+            .iter()
+            .map(|m| m.name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let model = prompt.ask("  Whisper model", &config.whisper_model);
+    if model != config.whisper_model {
+        answers.whisper_model = Some(model.clone());
+    }
+    if transcription::model_path_in(&config.models_dir(), &model).is_file()
+        || !prompt.confirm(&format!("  Download {} now?", model))
+    {
+        return;
+    }
+    download_whisper(&config.models_dir(), &model).await;
+}
+
+/// Download Whisper `model` with a progress line on stderr.
+async fn download_whisper(models_dir: &Path, model: &str) {
+    let mut last_shown = None;
+    let result = transcription::download_model(models_dir, model, |done, total| {
+        let shown = match total {
+            Some(total) if total > 0 => format!("{:.0}%", done as f64 * 100.0 / total as f64),
+            _ => format!("{} MB", done / (1024 * 1024)),
+        };
+        if last_shown.as_ref() != Some(&shown) {
+            eprint!("\r  ⬇️ Downloading ggml-{}.bin — {}   ", model, shown);
+            let _ = std::io::stderr().flush();
+            last_shown = Some(shown);
+        }
+    })
+    .await;
+    eprintln!();
+    match result {
+        Ok(path) => eprintln!("  ✅ Saved {}", path.display()),
+        Err(e) => eprintln!("  ❌ Download failed: {}", e),
+    }
+}
+
+/// After an edit ran without a transcript: say so in a yellow banner on
+/// stderr and, at a terminal, offer to download the small fallback model so
+/// the next edit can transcribe.
+pub async fn offer_fallback_model(reason: &str) {
+    let (yellow, reset) = if std::io::stderr().is_terminal() {
+        ("\x1b[33m", "\x1b[0m")
+    } else {
+        ("", "")
+    };
+    eprintln!("{}⚠️  Semantic editing was off for this edit: {}", yellow, reason);
+    eprintln!(
+        "   With no transcript, scenes were chosen from audio energy alone, so cuts may be rougher.{}",
+        reset
+    );
+
+    let config = config::current();
+    let model = transcription::FALLBACK_MODEL;
+    if transcription::model_path_in(&config.models_dir(), model).is_file() {
+        return;
+    }
+    if !std::io::stdin().is_terminal() {
+        eprintln!("   Run `synoid-core setup --only whisper` to download a Whisper model.");
+        return;
+    }
+    eprint!("   Download the {} Whisper model (~75 MB) now? (y/n) [y]: ", model);
+    let _ = std::io::stderr().flush();
+    let mut line = String::new();
+    let _ = std::io::stdin().lock().read_line(&mut line);
+    if matches!(line.trim().to_lowercase().as_str(), "" | "y" | "yes") {
+        download_whisper(&config.models_dir(), model).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saving_answers_keeps_the_rest_of_the_file() {
+        let dir = std::env::temp_dir().join(format!("synoid_setup_{}", std::process::id()));
+        let path = dir.join("synoid.json");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let first = ConfigLayer {
+            api_url: Some("http://gpu-box:11434".into()),
+            whisper_model: Some("base".into()),
+            ..Default::default()
+        };
+        save_to(&path, &first).unwrap();
+        let rerun = ConfigLayer {
+            whisper_model: Some("small".into()),
+            ..Default::default()
+        };
+        save_to(&path, &rerun).unwrap();
+
+        let saved = ConfigLayer::load(&path).unwrap();
+        assert_eq!(saved.api_url.as_deref(), Some("http://gpu-box:11434"));
+        assert_eq!(saved.whisper_model.as_deref(), Some("small"));
+
+        let mut config =
+            SynoidConfig::resolve(&saved, ConfigLayer::default(), &ConfigLayer::default());
+        apply(
+            &mut config,
+            &ConfigLayer {
+                cache_dir: Some("/tmp/c".into()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(config.cache_dir, "/tmp/c");
+        assert_eq!(config.whisper_model, "small");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
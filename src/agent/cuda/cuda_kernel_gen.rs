@@ -2,12 +2,12 @@
 // Inspired by CUDA-Agent: AI-powered kernel generation for video processing
 // Copyright (c) 2026 xingxerx_The_Creator | SYNOID
 
+use crate::agent::engines::proc;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Stdio;
-use tokio::process::Command;
 use tracing::{info, warn};
 
 /// CUDA kernel generation request
@@ -233,7 +233,8 @@ __global__ void basic_process(
 
         // Compile with nvcc
         info!("[CUDA-GEN] Compiling kernel: {}", kernel.name);
-        let output = Command::new("nvcc")
+        // Unmanaged: kernel compilation is not media work
+        let output = proc::unmanaged("nvcc")
             .args([
                 "--ptx",
                 "-O3",
@@ -3,6 +3,7 @@
 // Copyright (c) 2026 xingxerx_The_Creator | SYNOID
 
 use super::cuda_kernel_gen::{KernelRequest, OptimizationTarget};
+use crate::agent::engines::proc;
 use crate::agent::specialized::synoid_link::SynoidLink;
 use anyhow::Result;
 use std::collections::HashMap;
@@ -187,9 +188,8 @@ impl CudaPipeline {
 
 /// Get video dimensions using ffprobe
 async fn get_video_dimensions(video_path: &Path) -> Result<(u32, u32)> {
-    use tokio::process::Command;
 
-    let output = Command::new("ffprobe")
+    let output = proc::ffprobe()
         .args([
             "-v",
             "error",
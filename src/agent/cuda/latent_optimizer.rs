@@ -5,8 +5,8 @@
 // Reduces computational load by working in compressed latent representations
 
 use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
 use std::path::{Path, PathBuf};
-use tokio::process::Command;
 use tracing::{info, warn};
 
 /// Latent representation configuration
@@ -75,7 +75,7 @@ impl LatentOptimizer {
 
         info!("[LATENT] Extracting frames with quality: {}", quality);
 
-        let mut cmd = Command::new("ffmpeg");
+        let mut cmd = proc::ffmpeg(ProcClass::Render);
         cmd.stealth()
             .args(["-i"])
             .arg(input_video)
@@ -134,7 +134,7 @@ impl LatentOptimizer {
         let target_fps = fps.unwrap_or(30.0);
 
         // Reconstruct video from frames
-        let mut cmd = Command::new("ffmpeg");
+        let mut cmd = proc::ffmpeg(ProcClass::Render);
         cmd.stealth()
             .args(["-framerate", &target_fps.to_string()])
             .args(["-pattern_type", "glob"])
@@ -243,7 +243,7 @@ impl LatentOptimizer {
                 blend_weight, blend_weight
             );
 
-            let status = Command::new("ffmpeg")
+            let status = proc::ffmpeg(ProcClass::Render)
                 .stealth()
                 .args(["-i"])
                 .arg(prev_frame)
@@ -9,7 +9,7 @@ use tokio::sync::{mpsc, Mutex};
 use tracing::{error, info};
 use uuid::Uuid;
 
-fn parse_progress_from_msg(msg: &str) -> Option<f32> {
+pub(crate) fn parse_progress_from_msg(msg: &str) -> Option<f32> {
     if let Some(pos) = msg.find("Vision progress: ") {
         let rest = &msg[pos + 17..];
         if let Some(slash) = rest.find('/') {
@@ -31,9 +31,12 @@ fn parse_progress_from_msg(msg: &str) -> Option<f32> {
 }
 
 use crate::agent::core_systems::brain::Brain;
+use crate::agent::core_systems::operation_history::{OperationHistory, OperationKind};
+use crate::agent::core_systems::project;
 use crate::agent::specialized::smart_editor;
 use crate::agent::specialized::smart_editor::Scene;
 use crate::agent::tools::transcription::TranscriptSegment;
+use crate::events::{EventBus, JobOrigin, SynoidEvent};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum JobStatus {
@@ -72,18 +75,21 @@ pub struct VideoEditorQueue {
 }
 
 impl VideoEditorQueue {
-    /// Convenience constructor — no GUI log wiring.
+    /// Convenience constructor — no GUI log wiring, and job events go to a
+    /// bus nobody listens on.
     pub fn new(brain: Arc<Mutex<Brain>>, instance_id: &str, animator: Arc<crate::agent::animator::Animator>) -> Self {
-        Self::new_with_log(brain, instance_id, animator, Arc::new(|_| {}))
+        Self::new_with_log(brain, instance_id, animator, Arc::new(|_| {}), EventBus::new())
     }
 
     /// Full constructor that accepts a log callback so the queue worker can
-    /// push progress messages into the GUI's visible log pane.
+    /// push progress messages into the GUI's visible log pane, and the bus
+    /// each job's start, progress and result are published to.
     pub fn new_with_log(
         brain: Arc<Mutex<Brain>>,
         instance_id: &str,
         animator: Arc<crate::agent::animator::Animator>,
         log_fn: Arc<dyn Fn(&str) + Send + Sync>,
+        events: EventBus,
     ) -> Self {
         let jobs = Arc::new(Mutex::new(Vec::<EditJob>::new()));
         let (tx, mut rx) = mpsc::unbounded_channel::<Uuid>();
@@ -110,8 +116,14 @@ impl VideoEditorQueue {
 
                 if let Some(mut job) = job_opt {
                     info!("[QUEUE] Processing Job {}: {:?}", job_id, job.input);
+                    events.publish(SynoidEvent::JobStarted {
+                        origin: JobOrigin::Queue,
+                        job: job_id.to_string(),
+                        label: job.intent.clone(),
+                    });
 
                     let log_fn_job = log_fn.clone();
+                    let events_job = events.clone();
                     let progress_arc = job.progress_shared.clone();
                     let progress_cb: Option<Box<dyn Fn(&str) + Send + Sync>> =
                         Some(Box::new(move |msg: &str| {
@@ -121,9 +133,16 @@ impl VideoEditorQueue {
                                 if let Ok(mut p) = progress_arc.lock() {
                                     *p = pct;
                                 }
+                                events_job.publish(SynoidEvent::JobProgress {
+                                    origin: JobOrigin::Queue,
+                                    job: job_id.to_string(),
+                                    progress: pct,
+                                    status: msg.to_string(),
+                                });
                             }
                         }));
 
+                    let started_at = chrono::Utc::now();
                     let result: Result<String, Box<dyn std::error::Error + Send + Sync>> =
                         smart_editor::smart_edit(
                             &job.input,
@@ -140,6 +159,51 @@ impl VideoEditorQueue {
                         )
                         .await;
 
+                    OperationHistory::for_instance(&instance_id_worker).record(
+                        OperationKind::Edit,
+                        &job.input,
+                        Some(&job.output),
+                        [
+                            ("intent".to_string(), job.intent.clone()),
+                            ("subtitles".to_string(), job.enable_subtitles.to_string()),
+                            ("censoring".to_string(), job.enable_censoring.to_string()),
+                        ]
+                        .into_iter()
+                        .collect(),
+                        started_at,
+                        &result.as_ref().map(|s| s.clone()).map_err(|e| e.to_string()),
+                    );
+
+                    project::record_render_in_active(
+                        &instance_id_worker,
+                        project::RenderInput {
+                            command: "embody".to_string(),
+                            source: job.input.clone(),
+                            intent: job.intent.clone(),
+                            output: job.output.clone(),
+                            params: [
+                                ("subtitles".to_string(), job.enable_subtitles.to_string()),
+                                ("censoring".to_string(), job.enable_censoring.to_string()),
+                            ]
+                            .into_iter()
+                            .collect(),
+                            started_at,
+                            result: result.as_ref().map(|s| s.clone()).map_err(|e| e.to_string()),
+                        },
+                    )
+                    .await;
+
+                    // Published once the job list shows the final status
+                    let done = SynoidEvent::JobDone {
+                        origin: JobOrigin::Queue,
+                        job: job_id.to_string(),
+                        ok: result.is_ok(),
+                        detail: match &result {
+                            Ok(summary) => summary.clone(),
+                            Err(e) => e.to_string(),
+                        },
+                    };
+
                     let mut jobs = jobs_worker.lock().await;
                     if let Some(final_job) = jobs.iter_mut().find(|j| j.id == job_id) {
                         match result {
@@ -180,6 +244,8 @@ impl VideoEditorQueue {
                             }
                         }
                     }
+                    drop(jobs);
+                    events.publish(done);
                 }
             }
         });
@@ -218,6 +284,18 @@ impl VideoEditorQueue {
         jobs.retain(|j| !matches!(j.status, JobStatus::Completed { .. } | JobStatus::Failed(_)));
     }
 
+    /// Wait for job `id` to finish and return its final status, or `None` if
+    /// the queue does not know it.
+    pub async fn wait_for_job(&self, id: Uuid) -> Option<JobStatus> {
+        loop {
+            match self.get_job_status(id).await? {
+                JobStatus::Queued | JobStatus::Processing => {}
+                done => return Some(done),
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        }
+    }
+
     /// Blocks until all queued and processing jobs are completed.
     /// Useful for graceful shutdown.
     pub async fn wait_for_completion(&self) {
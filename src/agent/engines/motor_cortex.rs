@@ -1,7 +1,10 @@
 use crate::agent::specialized::academy::StyleLibrary;
+use crate::agent::tools::audio_classify::{self, AudioClass, AudioMix};
 use crate::agent::tools::audio_tools::AudioAnalysis;
+use crate::agent::engines::proc::{self, ProcClass};
 use crate::agent::engines::process_utils::CommandExt;
 use crate::agent::tools::production_tools;
+use crate::agent::tools::scan_cache;
 use crate::agent::specialized::smart_editor;
 use crate::agent::tools::transcription::TranscriptSegment;
 use crate::agent::tools::vision_tools::VisualScene;
@@ -37,6 +40,108 @@ impl EditPlan {
     }
 }
 
+/// One scene of a [`RenderPlan`] and whether the planner expects to keep it.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PlannedScene {
+    pub start: f64,
+    pub end: f64,
+    pub score: f64,
+    pub keep: bool,
+    /// What the scene's audio is, when an earlier edit classified the source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_mix: Option<AudioMix>,
+}
+
+/// What a smart render would do, worked out without touching the input: the
+/// parsed intent, the strategy it is scored against, the scenes predicted to
+/// survive and the one-shot command used if the Smart Editor fails.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RenderPlan {
+    pub intent: String,
+    pub edit_intent: smart_editor::EditIntent,
+    pub funny_mode: bool,
+    pub strategy: smart_editor::EditingStrategy,
+    pub style_profile: String,
+    pub video_filters: Vec<String>,
+    pub audio_filters: Vec<String>,
+    /// Source spans the intent cuts outright.
+    pub excluded: Vec<(f64, f64)>,
+    /// Empty when the source was not scanned and its length is unknown.
+    pub scenes: Vec<PlannedScene>,
+    pub fallback_command: Vec<String>,
+}
+
+impl RenderPlan {
+    /// Seconds of source the plan expects to keep.
+    pub fn kept_secs(&self) -> f64 {
+        self.scenes
+            .iter()
+            .filter(|s| s.keep)
+            .map(|s| s.end - s.start)
+            .sum()
+    }
+
+    /// A few lines for logs and the CLI.
+    pub fn describe(&self) -> String {
+        let i = &self.edit_intent;
+        let mut lines = vec![
+            format!("Intent: \"{}\"", self.intent),
+            format!(
+                "  density {:?}, ruthless {}, remove silence {}, remove boring {}, keep speech {}, keep action {}, funny {}",
+                i.density, i.ruthless, i.remove_silence, i.remove_boring, i.keep_speech, i.keep_action, self.funny_mode
+            ),
+        ];
+        if let Some((min, max)) = i.target_duration {
+            lines.push(format!("  target length {:.0}–{:.0}s", min, max));
+        }
+        let classes = |list: &[AudioClass]| {
+            list.iter().map(|c| c.name()).collect::<Vec<_>>().join(", ")
+        };
+        if !i.keep_audio.is_empty() {
+            lines.push(format!("  keep {} audio", classes(&i.keep_audio)));
+        }
+        if !i.cut_audio.is_empty() {
+            lines.push(format!("  cut {} audio", classes(&i.cut_audio)));
+        }
+        lines.push(format!(
+            "Strategy: min scene score {:.2}, scene threshold {:.2}, speech boost {:.2}, silence penalty {:.2}",
+            self.strategy.min_scene_score,
+            self.strategy.scene_threshold,
+            self.strategy.speech_boost,
+            self.strategy.silence_penalty
+        ));
+        for (start, end) in &self.excluded {
+            lines.push(format!("Cut outright: {:.1}s–{:.1}s", start, end));
+        }
+        if self.scenes.is_empty() {
+            lines.push("Scenes: unknown until the source is scanned".to_string());
+        } else {
+            lines.push(format!(
+                "Scenes: keeping {} of {} ({:.1}s)",
+                self.scenes.iter().filter(|s| s.keep).count(),
+                self.scenes.len(),
+                self.kept_secs()
+            ));
+            for s in &self.scenes {
+                let mut line = format!(
+                    "  {} {:.1}s–{:.1}s (score {:.2})",
+                    if s.keep { "keep" } else { "drop" },
+                    s.start,
+                    s.end,
+                    s.score
+                );
+                if let Some(mix) = &s.audio_mix {
+                    line.push_str(&format!(" {}", mix.describe()));
+                }
+                lines.push(line);
+            }
+        }
+        lines.push(format!("Style: {}", self.style_profile));
+        lines.push(format!("Fallback command: {}", self.fallback_command.join(" ")));
+        lines.join("\n")
+    }
+}
+
 // MotorCortex defined below with impl block
 
 #[derive(Debug, Clone)]
@@ -142,6 +247,83 @@ impl MotorCortex {
         }
     }
 
+    /// Work out what a smart render of `input` would do without decoding or
+    /// writing anything, given `intent` already parsed into `edit_intent`.
+    /// With no `scenes` the plan leaves them unknown. Audio classes cached by
+    /// an earlier edit of `input` label the scenes.
+    pub fn plan(
+        &self,
+        intent: &str,
+        edit_intent: smart_editor::EditIntent,
+        input: &Path,
+        output: &Path,
+        scenes: Vec<smart_editor::Scene>,
+        transcript: &[TranscriptSegment],
+    ) -> RenderPlan {
+        let strategy = smart_editor::EditingStrategy::load();
+        let total = scenes.last().map(|s| s.end_time).unwrap_or(0.0);
+        let transcript_opt = if transcript.is_empty() {
+            None
+        } else {
+            Some(transcript)
+        };
+
+        let (exclusions, _) = smart_editor::exclusions::resolve_exclusions(
+            &edit_intent.exclude_ranges,
+            &edit_intent.exclude_phrases,
+            transcript_opt,
+            total,
+        );
+        let excluded: Vec<(f64, f64)> = exclusions.iter().map(|e| (e.start, e.end)).collect();
+        let mut remaining = smart_editor::remove_spans(scenes, &excluded);
+        if let Some(windows) = scan_cache::cached_audio_classes(input) {
+            audio_classify::label_scenes(&mut remaining, &windows);
+        }
+        smart_editor::score_scenes(&mut remaining, &edit_intent, transcript_opt, &strategy, total);
+        let scenes = remaining
+            .iter()
+            .map(|s| PlannedScene {
+                start: s.start_time,
+                end: s.end_time,
+                score: s.score,
+                keep: s.score >= strategy.min_scene_score,
+                audio_mix: s.audio_mix,
+            })
+            .collect();
+
+        let one_shot = one_shot_command(intent, input, output);
+        RenderPlan {
+            intent: intent.to_string(),
+            funny_mode: is_funny(intent),
+            edit_intent,
+            strategy,
+            style_profile: one_shot.profile,
+            video_filters: one_shot.video_filters,
+            audio_filters: one_shot.audio_filters,
+            excluded,
+            scenes,
+            fallback_command: one_shot.args,
+        }
+    }
+
+    /// What a dry run of `execute_smart_render` reports, for an intent that
+    /// is already parsed.
+    pub fn describe_dry_run(
+        &self,
+        intent: &str,
+        edit_intent: smart_editor::EditIntent,
+        input: &Path,
+        output: &Path,
+        visual_data: &[VisualScene],
+        transcript: &[TranscriptSegment],
+    ) -> String {
+        let scenes = scenes_from_visual(visual_data, 0.0);
+        self.plan(intent, edit_intent, input, output, scenes, transcript)
+            .describe()
+    }
+
+    /// Plan, then hand the pre-scanned scenes to the Smart Editor. A dry run
+    /// stops after planning and returns the plan instead of rendering.
     pub async fn execute_smart_render(
         &mut self,
         intent: &str,
@@ -154,31 +336,22 @@ impl MotorCortex {
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         info!("[CORTEX] 🧠 Planning Smart Render based on Visual Analysis & Sovereign Ear...");
 
-        // 1. Convert VisualScene to SmartEditor::Scene
-        // We need to estimate end times for each visual scene based on the next timestamp
-        let mut editor_scenes = Vec::new();
-
-        for i in 0..visual_data.len() {
-            let start = visual_data[i].timestamp;
-            let end = if i + 1 < visual_data.len() {
-                visual_data[i + 1].timestamp
-            } else {
-                // Estimate last scene: default to 10s if we can't probe total duration easily here.
-                // In a full implementation, we would probe the file duration.
-                start + 10.0
-            };
-
-            if end > start {
-                editor_scenes.push(smart_editor::Scene {
-                    start_time: start,
-                    end_time: end,
-                    duration: end - start,
-                    score: 0.5,
-                    vision_tags: Vec::new(),
-                });
-            }
+        if dry_run {
+            info!("[CORTEX] 🧪 Dry Run: planning only, nothing is rendered.");
+            let edit_intent = smart_editor::parse_intent(intent).await;
+            return Ok(self.describe_dry_run(
+                intent,
+                edit_intent,
+                input,
+                output,
+                visual_data,
+                transcript,
+            ));
         }
 
+        // 1. Convert VisualScene to SmartEditor::Scene
+        let editor_scenes = scenes_from_visual(visual_data, 0.0);
+
         info!(
             "[CORTEX] 🛠️ Integrating {} visual scenes into Smart Editor pipeline.",
             editor_scenes.len()
@@ -190,9 +363,7 @@ impl MotorCortex {
             info!("{}", msg);
         });
 
-        // We assume 'funny_mode' is false unless the intent carries specific markers
-        let funny_mode =
-            intent.to_lowercase().contains("funny") || intent.to_lowercase().contains("comedy");
+        let funny_mode = is_funny(intent);
 
         let transcript_opt = if transcript.is_empty() {
             None
@@ -200,15 +371,6 @@ impl MotorCortex {
             Some(transcript.to_vec())
         };
 
-        if dry_run {
-            info!("[CORTEX] 🧪 Dry Run: Skipping smart_edit execution.");
-            // Fallback to one-shot render in dry-run mode to generate command
-            return self
-                .execute_one_shot_render(intent, input, output, visual_data, _audio_data, true)
-                .await
-                .map(|args| format!("(Dry Run) Command: {}", args.join(" ")));
-        }
-
         match smart_editor::smart_edit(
             input,
             intent,
@@ -250,107 +412,21 @@ impl MotorCortex {
         _audio_data: &AudioAnalysis,
         dry_run: bool,
     ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
-        let library = StyleLibrary::new();
-        let profile = library.get_profile(intent);
-
-        info!("[CORTEX] Applying Style Profile: {}", profile.name);
-
-        // 1. Rhythmic Assembly
-        // Divide video into segments based on avg_shot_length and snap to nearest audio beat
-        let mut filters = Vec::new();
-
-        if profile.anamorphic {
-            filters.push("crop=in_w:in_w/2.39".to_string()); // 2.39:1 Cinematic Mask
-        }
-
-        if let Some(lut) = &profile.color_lut {
-            filters.push(format!("lut3d={}", lut));
-        }
-
-        // 2. Build FFmpeg Filtergraph (Video)
-        if filters.is_empty() {
-            // No video filters
-        }
-
-        // 3. Build Audio Filtergraph (Enhanced Voice & Smart Cut)
-        let mut audio_filters = Vec::new();
-        let intent_lower = intent.to_lowercase();
-
-        // Feature: Smart Cut (Silence Removal) - "Ruthless" editing
-        if intent_lower.contains("ruthless")
-            || intent_lower.contains("cut")
-            || intent_lower.contains("short")
-        {
-            info!("[CORTEX] ✂️ Applying Ruthless Silence Removal");
-            // fail: 1s silence, threshold: -40dB
-            audio_filters.push(
-                "silenceremove=stop_periods=-1:stop_duration=1:stop_threshold=-40dB".to_string(),
-            );
-        }
-
-        // Feature: Neural Audio Enhancement
-        if intent_lower.contains("enhance")
-            || intent_lower.contains("fix")
-            || intent_lower.contains("voice")
-            || intent_lower.contains("audio")
-            || intent_lower.contains("louder")
-        // User asked for louder voice
-        {
-            info!("[CORTEX] 🎙️ Enhancing Voice Clarity & Volume");
-            // 1. Highpass to remove rumble
-            audio_filters.push("highpass=f=100".to_string());
-            // 2. Compressor to level out voice (makes it "louder" and consistent)
-            audio_filters
-                .push("acompressor=threshold=-12dB:ratio=4:attack=5:release=50".to_string());
-            // 3. EQ Presense boost
-            audio_filters.push("equalizer=f=3000:t=q:w=1:g=5".to_string());
-            // 4. Loudness Normalization to standard -16 LUFS
-            audio_filters.push("loudnorm=I=-16:TP=-1.5:LRA=11".to_string());
+        let OneShot {
+            profile,
+            video_filters,
+            audio_filters,
+            args,
+        } = one_shot_command(intent, input, output);
+
+        info!("[CORTEX] Applying Style Profile: {}", profile);
+        if !video_filters.is_empty() {
+            info!("[CORTEX] 🎨 Video filters: {}", video_filters.join(","));
         }
-
-        // Construct Final Command using Vec<String> to avoid shell injection and space issues
-        let mut args = Vec::new();
-        args.push("ffmpeg".to_string());
-        args.push("-y".to_string());
-        args.push("-i".to_string());
-        args.push(
-            production_tools::safe_arg_path(input)
-                .to_string_lossy()
-                .to_string(),
-        );
-
-        if !filters.is_empty() {
-            args.push("-vf".to_string());
-            args.push(filters.join(","));
-        }
-
         if !audio_filters.is_empty() {
-            args.push("-af".to_string());
-            args.push(audio_filters.join(","));
-            args.push("-c:a".to_string());
-            args.push("aac".to_string());
-            args.push("-b:a".to_string());
-            args.push("192k".to_string());
-        } else {
-            args.push("-c:a".to_string());
-            args.push("copy".to_string());
+            info!("[CORTEX] 🎙️ Audio filters: {}", audio_filters.join(","));
         }
 
-        args.push("-c:v".to_string());
-        args.push("libx264".to_string());
-        args.push("-preset".to_string());
-        args.push("medium".to_string()); // Kept 'medium' from HEAD
-        args.push("-crf".to_string());
-        args.push("23".to_string()); // Kept '23' from HEAD
-        args.push("-pix_fmt".to_string());
-        args.push("yuv420p".to_string());
-
-        args.push(
-            production_tools::safe_arg_path(output)
-                .to_string_lossy()
-                .to_string(),
-        );
-
         // EXECUTE THE COMMAND
         if dry_run {
             info!("[CORTEX] 🧪 Dry Run: Command would be:");
@@ -361,10 +437,12 @@ impl MotorCortex {
         info!("[CORTEX] 🎬 Executing One-Shot Render...");
         info!("Command: {:?}", args.join(" "));
 
-        let status = std::process::Command::new(&args[0])
+        // args[0] is "ffmpeg"
+        let status = proc::ffmpeg(ProcClass::Render)
             .stealth()
             .args(&args[1..])
             .status()
+            .await
             .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
 
         if !status.success() {
@@ -374,3 +452,221 @@ impl MotorCortex {
         Ok(args)
     }
 }
+
+/// The Smart Editor's scenes for `visual_data`, each running to the next
+/// cut. The last one ends at `source_duration` when that is known and
+/// otherwise runs 10 s.
+fn scenes_from_visual(visual_data: &[VisualScene], source_duration: f64) -> Vec<smart_editor::Scene> {
+    let bounds: Vec<(f64, f64)> = if visual_data.is_empty() {
+        if source_duration > 0.0 {
+            vec![(0.0, source_duration)]
+        } else {
+            Vec::new()
+        }
+    } else {
+        (0..visual_data.len())
+            .map(|i| {
+                let start = visual_data[i].timestamp;
+                let end = match visual_data.get(i + 1) {
+                    Some(next) => next.timestamp,
+                    None if source_duration > start => source_duration,
+                    None => start + 10.0,
+                };
+                (start, end)
+            })
+            .collect()
+    };
+
+    bounds
+        .into_iter()
+        .filter(|(start, end)| end > start)
+        .map(|(start, end)| smart_editor::Scene {
+            start_time: start,
+            end_time: end,
+            duration: end - start,
+            score: 0.5,
+            vision_tags: Vec::new(),
+            breakdown: None,
+            audio_mix: None,
+        })
+        .collect()
+}
+
+fn is_funny(intent: &str) -> bool {
+    let lower = intent.to_lowercase();
+    lower.contains("funny") || lower.contains("comedy")
+}
+
+/// The one-shot fallback: a style profile, filter chains and the full
+/// ffmpeg command line.
+struct OneShot {
+    profile: String,
+    video_filters: Vec<String>,
+    audio_filters: Vec<String>,
+    args: Vec<String>,
+}
+
+fn one_shot_command(intent: &str, input: &Path, output: &Path) -> OneShot {
+    let library = StyleLibrary::new();
+    let profile = library.get_profile(intent);
+
+    // 1. Rhythmic Assembly
+    // Divide video into segments based on avg_shot_length and snap to nearest audio beat
+    let mut filters = Vec::new();
+
+    if profile.anamorphic {
+        filters.push("crop=in_w:in_w/2.39".to_string()); // 2.39:1 Cinematic Mask
+    }
+
+    if let Some(lut) = &profile.color_lut {
+        filters.push(format!("lut3d={}", lut));
+    }
+
+    // 2. Build Audio Filtergraph (Enhanced Voice & Smart Cut)
+    let mut audio_filters = Vec::new();
+    let intent_lower = intent.to_lowercase();
+
+    // Feature: Smart Cut (Silence Removal) - "Ruthless" editing
+    if intent_lower.contains("ruthless")
+        || intent_lower.contains("cut")
+        || intent_lower.contains("short")
+    {
+        // fail: 1s silence, threshold: -40dB
+        audio_filters.push(
+            "silenceremove=stop_periods=-1:stop_duration=1:stop_threshold=-40dB".to_string(),
+        );
+    }
+
+    // Feature: Neural Audio Enhancement
+    if intent_lower.contains("enhance")
+        || intent_lower.contains("fix")
+        || intent_lower.contains("voice")
+        || intent_lower.contains("audio")
+        || intent_lower.contains("louder")
+    // User asked for louder voice
+    {
+        // 1. Highpass to remove rumble
+        audio_filters.push("highpass=f=100".to_string());
+        // 2. Compressor to level out voice (makes it "louder" and consistent)
+        audio_filters.push("acompressor=threshold=-12dB:ratio=4:attack=5:release=50".to_string());
+        // 3. EQ Presense boost
+        audio_filters.push("equalizer=f=3000:t=q:w=1:g=5".to_string());
+        // 4. Loudness Normalization to standard -16 LUFS
+        audio_filters.push("loudnorm=I=-16:TP=-1.5:LRA=11".to_string());
+    }
+
+    // Construct Final Command using Vec<String> to avoid shell injection and space issues
+    let mut args = vec![
+        "ffmpeg".to_string(),
+        "-y".to_string(),
+        "-i".to_string(),
+        production_tools::safe_arg_path(input)
+            .to_string_lossy()
+            .to_string(),
+    ];
+
+    if !filters.is_empty() {
+        args.push("-vf".to_string());
+        args.push(filters.join(","));
+    }
+
+    if !audio_filters.is_empty() {
+        args.push("-af".to_string());
+        args.push(audio_filters.join(","));
+        args.push("-c:a".to_string());
+        args.push("aac".to_string());
+        args.push("-b:a".to_string());
+        args.push("192k".to_string());
+    } else {
+        args.push("-c:a".to_string());
+        args.push("copy".to_string());
+    }
+
+    args.push("-c:v".to_string());
+    args.push("libx264".to_string());
+    args.push("-preset".to_string());
+    args.push("medium".to_string()); // Kept 'medium' from HEAD
+    args.push("-crf".to_string());
+    args.push("23".to_string()); // Kept '23' from HEAD
+    args.push("-pix_fmt".to_string());
+    args.push("yuv420p".to_string());
+
+    args.push(
+        production_tools::safe_arg_path(output)
+            .to_string_lossy()
+            .to_string(),
+    );
+
+    OneShot {
+        profile: profile.name.clone(),
+        video_filters: filters,
+        audio_filters,
+        args,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene(timestamp: f64) -> VisualScene {
+        VisualScene {
+            timestamp,
+            motion_score: 0.0,
+            scene_score: 0.0,
+        }
+    }
+
+    #[test]
+    fn dry_run_returns_the_plan_without_rendering() {
+        let dir = std::env::temp_dir().join(format!("synoid_cortex_plan_{}", std::process::id()));
+        let input = dir.join("missing.mp4");
+        let output = dir.join("never.mp4");
+        let cortex = MotorCortex::new("http://localhost:11434");
+
+        // The intent is parsed heuristically here; the LLM is not consulted
+        let intent = "ruthless cut, remove silence";
+        let described = cortex.describe_dry_run(
+            intent,
+            smart_editor::EditIntent::from_text(intent),
+            &input,
+            &output,
+            &[scene(0.0), scene(12.0)],
+            &[],
+        );
+
+        assert!(described.contains("ruthless true"), "{}", described);
+        assert!(described.contains("Scenes: keeping"), "{}", described);
+        assert!(described.contains("silenceremove"), "{}", described);
+        assert!(!output.exists() && !dir.exists(), "a dry run wrote files");
+    }
+
+    #[test]
+    fn the_plan_cuts_excluded_ranges_and_closes_the_last_scene() {
+        let cortex = MotorCortex::new("http://localhost:11434");
+        let plan = cortex.plan(
+            "cut 0:10 to 0:20",
+            smart_editor::EditIntent::from_text("cut 0:10 to 0:20"),
+            Path::new("in.mp4"),
+            Path::new("out.mp4"),
+            scenes_from_visual(&[scene(0.0), scene(15.0)], 30.0),
+            &[],
+        );
+
+        assert_eq!(plan.excluded, vec![(10.0, 20.0)]);
+        let spans: Vec<(f64, f64)> = plan.scenes.iter().map(|s| (s.start, s.end)).collect();
+        assert_eq!(spans, vec![(0.0, 10.0), (20.0, 30.0)]);
+        assert_eq!(plan.fallback_command.first().map(String::as_str), Some("ffmpeg"));
+
+        let unscanned = cortex.plan(
+            "tighten it up",
+            smart_editor::EditIntent::from_text("tighten it up"),
+            Path::new("in.mp4"),
+            Path::new("out.mp4"),
+            Vec::new(),
+            &[],
+        );
+        assert!(unscanned.scenes.is_empty());
+        assert!(unscanned.describe().contains("unknown until the source is scanned"));
+    }
+}
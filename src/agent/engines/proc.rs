@@ -0,0 +1,758 @@
+// SYNOID Process Governor - FFmpeg & Tool Concurrency Limits
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Every ffmpeg/ffprobe/yt-dlp child goes through here. Each process class
+// (probe, thumbnail, preview, render, download) has its own concurrency limit
+// and a priority queue, and every running child's PID is registered so the
+// Sentinel can attribute CPU spikes and shutdown can kill stragglers.
+//
+// Spawn with `proc::ffmpeg(class)` / `proc::ffprobe()` / `proc::command(..)`
+// (async) or the `blocking_*` variants. `proc::unmanaged(..)` is the explicit
+// escape hatch for the rare process that must not wait for a permit.
+
+use crate::agent::engines::process_utils::CommandExt;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap};
+use std::ffi::OsStr;
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::process::{ExitStatus, Output, Stdio};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
+use std::time::Instant;
+use tokio::sync::Notify;
+use tracing::{info, warn};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Classes & priorities
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProcClass {
+    /// Short metadata reads (ffprobe, duration checks).
+    Probe,
+    /// Single-frame grabs, waveforms, scene detection passes.
+    Thumbnail,
+    /// Long-lived playback decoders.
+    Preview,
+    /// Encodes: clips, concat, smart-edit renders, upscales.
+    Render,
+    /// yt-dlp and other network fetches.
+    Download,
+    /// User-configured external scene scorers.
+    Scorer,
+}
+
+impl ProcClass {
+    pub const ALL: [ProcClass; 6] = [
+        ProcClass::Probe,
+        ProcClass::Thumbnail,
+        ProcClass::Preview,
+        ProcClass::Render,
+        ProcClass::Download,
+        ProcClass::Scorer,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Probe => "probe",
+            Self::Thumbnail => "thumbnail",
+            Self::Preview => "preview",
+            Self::Render => "render",
+            Self::Download => "download",
+            Self::Scorer => "scorer",
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    fn default_limit(&self) -> usize {
+        let cpus = num_cpus::get().max(1);
+        match self {
+            Self::Probe => cpus.clamp(2, 8),
+            Self::Thumbnail => 2,
+            Self::Preview => 2,
+            Self::Render => (cpus / 4).clamp(1, 4),
+            Self::Download => 2,
+            Self::Scorer => 1,
+        }
+    }
+
+    /// The `proc_limits` setting for this class (e.g.
+    /// `SYNOID_PROC_LIMIT_RENDER=3`), else the default.
+    pub fn configured_limit(&self) -> usize {
+        crate::config::current()
+            .proc_limits
+            .get(self.label())
+            .copied()
+            .unwrap_or_else(|| self.default_limit())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Priority {
+    /// Background work (autonomous learner, batch jobs).
+    Low,
+    #[default]
+    Normal,
+    /// Something a user is actively waiting on.
+    High,
+}
+
+/// A process SYNOID started and is still waiting on.
+#[derive(Debug, Clone)]
+pub struct ChildInfo {
+    pub pid: u32,
+    pub class: ProcClass,
+    pub program: String,
+    pub started: Instant,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Governor
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Queue key: per class, higher priority first, then FIFO.
+type Ticket = (usize, Reverse<Priority>, u64);
+
+struct GovernorState {
+    limits: [usize; ProcClass::ALL.len()],
+    active: [usize; ProcClass::ALL.len()],
+    queue: BTreeSet<Ticket>,
+    next_seq: u64,
+    children: HashMap<u32, ChildInfo>,
+}
+
+pub struct Governor {
+    state: Mutex<GovernorState>,
+    /// Wakes async waiters when a permit frees up or the queue head changes.
+    notify: Notify,
+    /// Same for blocking waiters.
+    cond: Condvar,
+}
+
+impl Governor {
+    pub fn new(limits: impl Fn(ProcClass) -> usize) -> Self {
+        let mut per_class = [1; ProcClass::ALL.len()];
+        for class in ProcClass::ALL {
+            per_class[class.index()] = limits(class).max(1);
+        }
+        Self {
+            state: Mutex::new(GovernorState {
+                limits: per_class,
+                active: [0; ProcClass::ALL.len()],
+                queue: BTreeSet::new(),
+                next_seq: 0,
+                children: HashMap::new(),
+            }),
+            notify: Notify::new(),
+            cond: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, GovernorState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set_limit(&self, class: ProcClass, limit: usize) {
+        self.lock().limits[class.index()] = limit.max(1);
+        self.wake();
+    }
+
+    pub fn limit(&self, class: ProcClass) -> usize {
+        self.lock().limits[class.index()]
+    }
+
+    /// Processes currently holding a permit in `class`.
+    pub fn active(&self, class: ProcClass) -> usize {
+        self.lock().active[class.index()]
+    }
+
+    /// Requests waiting for a permit in `class`.
+    pub fn queued(&self, class: ProcClass) -> usize {
+        let c = class.index();
+        self.lock().queue.iter().filter(|t| t.0 == c).count()
+    }
+
+    /// Registered children, oldest first.
+    pub fn children(&self) -> Vec<ChildInfo> {
+        let mut list: Vec<ChildInfo> = self.lock().children.values().cloned().collect();
+        list.sort_by_key(|c| c.started);
+        list
+    }
+
+    pub fn child(&self, pid: u32) -> Option<ChildInfo> {
+        self.lock().children.get(&pid).cloned()
+    }
+
+    fn wake(&self) {
+        self.notify.notify_waiters();
+        self.cond.notify_all();
+    }
+
+    fn enqueue(&self, class: ProcClass, priority: Priority) -> Ticket {
+        let mut state = self.lock();
+        let ticket = (class.index(), Reverse(priority), state.next_seq);
+        state.next_seq += 1;
+        state.queue.insert(ticket);
+        ticket
+    }
+
+    /// Take a permit if `ticket` heads its class queue and a slot is free.
+    fn try_take(state: &mut GovernorState, ticket: &Ticket) -> bool {
+        let c = ticket.0;
+        let head = state.queue.range((c, Reverse(Priority::High), 0)..).next();
+        if head == Some(ticket) && state.active[c] < state.limits[c] {
+            state.active[c] += 1;
+            state.queue.remove(ticket);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Wait (asynchronously) for a permit in `class`.
+    pub async fn acquire(self: &Arc<Self>, class: ProcClass, priority: Priority) -> ProcPermit {
+        let mut waiter = Waiter {
+            governor: self,
+            ticket: self.enqueue(class, priority),
+            granted: false,
+        };
+        loop {
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if Self::try_take(&mut self.lock(), &waiter.ticket) {
+                waiter.granted = true;
+                return ProcPermit::new(self.clone(), class);
+            }
+            notified.await;
+        }
+    }
+
+    /// Wait (blocking the thread) for a permit in `class`.
+    pub fn acquire_blocking(self: &Arc<Self>, class: ProcClass, priority: Priority) -> ProcPermit {
+        let ticket = self.enqueue(class, priority);
+        let mut state = self.lock();
+        while !Self::try_take(&mut state, &ticket) {
+            state = self.cond.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+        drop(state);
+        ProcPermit::new(self.clone(), class)
+    }
+
+    fn release(&self, class: ProcClass, pid: Option<u32>) {
+        {
+            let mut state = self.lock();
+            let c = class.index();
+            state.active[c] = state.active[c].saturating_sub(1);
+            if let Some(pid) = pid {
+                state.children.remove(&pid);
+            }
+        }
+        self.wake();
+    }
+
+    /// Kill every registered child. Used on shutdown so no ffmpeg outlives us.
+    pub fn kill_all(&self) -> usize {
+        let pids: Vec<u32> = self.lock().children.keys().copied().collect();
+        if pids.is_empty() {
+            return 0;
+        }
+        let mut sys = sysinfo::System::new();
+        sys.refresh_processes();
+        let mut killed = 0;
+        for pid in pids {
+            if let Some(p) = sys.process(sysinfo::Pid::from_u32(pid)) {
+                if p.kill() {
+                    killed += 1;
+                }
+            }
+        }
+        warn!("[PROC] ⛔ Killed {} managed child process(es)", killed);
+        killed
+    }
+}
+
+/// Removes an abandoned queue entry when an `acquire` future is dropped.
+struct Waiter<'a> {
+    governor: &'a Governor,
+    ticket: Ticket,
+    granted: bool,
+}
+
+impl Drop for Waiter<'_> {
+    fn drop(&mut self) {
+        if !self.granted {
+            self.governor.lock().queue.remove(&self.ticket);
+            self.governor.wake();
+        }
+    }
+}
+
+/// A held slot in one process class. Released on drop, along with the child's
+/// PID registration.
+pub struct ProcPermit {
+    governor: Arc<Governor>,
+    class: ProcClass,
+    pid: Option<u32>,
+}
+
+impl ProcPermit {
+    fn new(governor: Arc<Governor>, class: ProcClass) -> Self {
+        Self {
+            governor,
+            class,
+            pid: None,
+        }
+    }
+
+    pub fn class(&self) -> ProcClass {
+        self.class
+    }
+
+    fn register(&mut self, pid: Option<u32>, program: &str) {
+        let Some(pid) = pid else { return };
+        self.pid = Some(pid);
+        self.governor.lock().children.insert(
+            pid,
+            ChildInfo {
+                pid,
+                class: self.class,
+                program: program.to_string(),
+                started: Instant::now(),
+            },
+        );
+    }
+}
+
+impl Drop for ProcPermit {
+    fn drop(&mut self) {
+        self.governor.release(self.class, self.pid);
+    }
+}
+
+/// The process-wide governor, with limits from the `proc_limits` setting.
+pub fn governor() -> &'static Arc<Governor> {
+    static GOVERNOR: OnceLock<Arc<Governor>> = OnceLock::new();
+    GOVERNOR.get_or_init(|| {
+        let gov = Governor::new(|c| c.configured_limit());
+        let limits: Vec<String> = ProcClass::ALL
+            .iter()
+            .map(|c| format!("{}={}", c.label(), gov.limit(*c)))
+            .collect();
+        info!("[PROC] Process governor limits: {}", limits.join(", "));
+        Arc::new(gov)
+    })
+}
+
+/// Kill every child registered with the process-wide governor.
+pub fn kill_all_children() -> usize {
+    governor().kill_all()
+}
+
+/// Short description of a PID if SYNOID owns it, for Sentinel alerts.
+pub fn describe_pid(pid: u32) -> Option<String> {
+    governor().child(pid).map(|c| {
+        format!(
+            "SYNOID {} '{}' running {:.0}s",
+            c.class.label(),
+            c.program,
+            c.started.elapsed().as_secs_f64()
+        )
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Managed commands
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn program_name(program: &OsStr) -> String {
+    Path::new(program)
+        .file_name()
+        .unwrap_or(program)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Async command that waits for a governor permit before spawning. Mirrors the
+/// subset of `tokio::process::Command` the codebase uses.
+pub struct ManagedCommand {
+    inner: tokio::process::Command,
+    program: String,
+    class: ProcClass,
+    priority: Priority,
+    governor: Arc<Governor>,
+    stdin_set: bool,
+    stdout_set: bool,
+    stderr_set: bool,
+}
+
+/// Managed async command for `program` in `class`.
+pub fn command<S: AsRef<OsStr>>(program: S, class: ProcClass) -> ManagedCommand {
+    ManagedCommand::with_governor(program, class, governor().clone())
+}
+
+/// Managed `ffmpeg` invocation.
+pub fn ffmpeg(class: ProcClass) -> ManagedCommand {
+    command("ffmpeg", class)
+}
+
+/// Managed `ffprobe` invocation.
+pub fn ffprobe() -> ManagedCommand {
+    command("ffprobe", ProcClass::Probe)
+}
+
+/// Escape hatch: a plain `tokio::process::Command` that bypasses the governor.
+/// Only for processes that must never queue; say why at the call site.
+pub fn unmanaged<S: AsRef<OsStr>>(program: S) -> tokio::process::Command {
+    tokio::process::Command::new(program)
+}
+
+/// Blocking counterpart of [`unmanaged`], for threads outside the runtime.
+pub fn blocking_unmanaged<S: AsRef<OsStr>>(program: S) -> std::process::Command {
+    std::process::Command::new(program)
+}
+
+impl ManagedCommand {
+    pub fn with_governor<S: AsRef<OsStr>>(program: S, class: ProcClass, governor: Arc<Governor>) -> Self {
+        Self {
+            program: program_name(program.as_ref()),
+            inner: tokio::process::Command::new(program),
+            class,
+            priority: Priority::Normal,
+            governor,
+            stdin_set: false,
+            stdout_set: false,
+            stderr_set: false,
+        }
+    }
+
+    pub fn priority(&mut self, priority: Priority) -> &mut Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Self {
+        self.inner.arg(arg);
+        self
+    }
+
+    pub fn args<I, S>(&mut self, args: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.inner.args(args);
+        self
+    }
+
+    pub fn env<K: AsRef<OsStr>, V: AsRef<OsStr>>(&mut self, key: K, val: V) -> &mut Self {
+        self.inner.env(key, val);
+        self
+    }
+
+    pub fn current_dir<P: AsRef<Path>>(&mut self, dir: P) -> &mut Self {
+        self.inner.current_dir(dir);
+        self
+    }
+
+    pub fn stdin<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Self {
+        self.stdin_set = true;
+        self.inner.stdin(cfg);
+        self
+    }
+
+    pub fn stdout<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Self {
+        self.stdout_set = true;
+        self.inner.stdout(cfg);
+        self
+    }
+
+    pub fn stderr<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Self {
+        self.stderr_set = true;
+        self.inner.stderr(cfg);
+        self
+    }
+
+    pub fn kill_on_drop(&mut self, kill: bool) -> &mut Self {
+        self.inner.kill_on_drop(kill);
+        self
+    }
+
+    /// Start the child in process group `pgroup` (0 for a new one), so
+    /// everything it starts can be killed together.
+    #[cfg(unix)]
+    pub fn process_group(&mut self, pgroup: i32) -> &mut Self {
+        self.inner.process_group(pgroup);
+        self
+    }
+
+    pub fn as_std(&self) -> &std::process::Command {
+        self.inner.as_std()
+    }
+
+    /// Wait for a permit, then spawn. The permit is held until the returned
+    /// child is dropped.
+    pub async fn spawn(&mut self) -> io::Result<ManagedChild> {
+        let mut permit = self.governor.acquire(self.class, self.priority).await;
+        let child = self.inner.spawn()?;
+        permit.register(child.id(), &self.program);
+        Ok(ManagedChild {
+            child,
+            _permit: permit,
+        })
+    }
+
+    pub async fn status(&mut self) -> io::Result<ExitStatus> {
+        let mut child = self.spawn().await?;
+        child.wait().await
+    }
+
+    /// Like `Command::output`: stdout/stderr are captured and stdin is closed
+    /// unless configured otherwise.
+    pub async fn output(&mut self) -> io::Result<Output> {
+        if !self.stdin_set {
+            self.inner.stdin(Stdio::null());
+        }
+        if !self.stdout_set {
+            self.inner.stdout(Stdio::piped());
+        }
+        if !self.stderr_set {
+            self.inner.stderr(Stdio::piped());
+        }
+        let ManagedChild { child, _permit } = self.spawn().await?;
+        child.wait_with_output().await
+    }
+}
+
+impl CommandExt for ManagedCommand {
+    fn stealth(&mut self) -> &mut Self {
+        self.inner.stealth();
+        self
+    }
+}
+
+/// A spawned managed child; dereferences to `tokio::process::Child`.
+pub struct ManagedChild {
+    child: tokio::process::Child,
+    _permit: ProcPermit,
+}
+
+impl Deref for ManagedChild {
+    type Target = tokio::process::Child;
+    fn deref(&self) -> &Self::Target {
+        &self.child
+    }
+}
+
+impl DerefMut for ManagedChild {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.child
+    }
+}
+
+/// Blocking counterpart of `ManagedCommand` for synchronous call sites.
+pub struct BlockingManagedCommand {
+    inner: std::process::Command,
+    program: String,
+    class: ProcClass,
+    priority: Priority,
+    governor: Arc<Governor>,
+    stdin_set: bool,
+    stdout_set: bool,
+    stderr_set: bool,
+}
+
+/// Managed blocking command for `program` in `class`.
+pub fn blocking_command<S: AsRef<OsStr>>(program: S, class: ProcClass) -> BlockingManagedCommand {
+    BlockingManagedCommand {
+        program: program_name(program.as_ref()),
+        inner: std::process::Command::new(program),
+        class,
+        priority: Priority::Normal,
+        governor: governor().clone(),
+        stdin_set: false,
+        stdout_set: false,
+        stderr_set: false,
+    }
+}
+
+/// Managed blocking `ffmpeg` invocation.
+pub fn blocking_ffmpeg(class: ProcClass) -> BlockingManagedCommand {
+    blocking_command("ffmpeg", class)
+}
+
+impl BlockingManagedCommand {
+    pub fn priority(&mut self, priority: Priority) -> &mut Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Self {
+        self.inner.arg(arg);
+        self
+    }
+
+    pub fn args<I, S>(&mut self, args: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.inner.args(args);
+        self
+    }
+
+    pub fn stdin<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Self {
+        self.stdin_set = true;
+        self.inner.stdin(cfg);
+        self
+    }
+
+    pub fn stdout<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Self {
+        self.stdout_set = true;
+        self.inner.stdout(cfg);
+        self
+    }
+
+    pub fn stderr<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Self {
+        self.stderr_set = true;
+        self.inner.stderr(cfg);
+        self
+    }
+
+    pub fn spawn(&mut self) -> io::Result<BlockingManagedChild> {
+        let mut permit = self.governor.acquire_blocking(self.class, self.priority);
+        let child = self.inner.spawn()?;
+        permit.register(Some(child.id()), &self.program);
+        Ok(BlockingManagedChild {
+            child,
+            _permit: permit,
+        })
+    }
+
+    pub fn status(&mut self) -> io::Result<ExitStatus> {
+        let mut child = self.spawn()?;
+        child.wait()
+    }
+
+    pub fn output(&mut self) -> io::Result<Output> {
+        if !self.stdin_set {
+            self.inner.stdin(Stdio::null());
+        }
+        if !self.stdout_set {
+            self.inner.stdout(Stdio::piped());
+        }
+        if !self.stderr_set {
+            self.inner.stderr(Stdio::piped());
+        }
+        let BlockingManagedChild { child, _permit } = self.spawn()?;
+        child.wait_with_output()
+    }
+}
+
+impl CommandExt for BlockingManagedCommand {
+    fn stealth(&mut self) -> &mut Self {
+        self.inner.stealth();
+        self
+    }
+}
+
+/// A spawned blocking managed child; dereferences to `std::process::Child`.
+pub struct BlockingManagedChild {
+    child: std::process::Child,
+    _permit: ProcPermit,
+}
+
+impl Deref for BlockingManagedChild {
+    type Target = std::process::Child;
+    fn deref(&self) -> &Self::Target {
+        &self.child
+    }
+}
+
+impl DerefMut for BlockingManagedChild {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.child
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn permits_respect_limits_and_priority() {
+        let gov = Arc::new(Governor::new(|_| 1));
+        let first = gov.acquire(ProcClass::Render, Priority::Normal).await;
+        assert_eq!(gov.active(ProcClass::Render), 1);
+
+        // Other classes are independent
+        let probe = gov.acquire(ProcClass::Probe, Priority::Normal).await;
+        drop(probe);
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let spawn_waiter = |priority: Priority, tag: &'static str| {
+            let gov = gov.clone();
+            let order = order.clone();
+            tokio::spawn(async move {
+                let _permit = gov.acquire(ProcClass::Render, priority).await;
+                order.lock().unwrap().push(tag);
+            })
+        };
+        let low = spawn_waiter(Priority::Low, "low");
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let high = spawn_waiter(Priority::High, "high");
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(gov.queued(ProcClass::Render), 2);
+
+        drop(first);
+        high.await.unwrap();
+        low.await.unwrap();
+        assert_eq!(*order.lock().unwrap(), vec!["high", "low"]);
+        assert_eq!(gov.active(ProcClass::Render), 0);
+    }
+
+    #[tokio::test]
+    async fn cancelled_waiter_leaves_the_queue() {
+        let gov = Arc::new(Governor::new(|_| 1));
+        let held = gov.acquire(ProcClass::Download, Priority::Normal).await;
+        let waiting = tokio::time::timeout(
+            Duration::from_millis(20),
+            gov.acquire(ProcClass::Download, Priority::High),
+        )
+        .await;
+        assert!(waiting.is_err());
+        assert_eq!(gov.queued(ProcClass::Download), 0);
+        drop(held);
+        let _again = gov.acquire(ProcClass::Download, Priority::Low).await;
+    }
+
+    /// Every spawn must go through this module: governed, or explicitly
+    /// `unmanaged` with a reason at the call site.
+    #[test]
+    fn no_unmanaged_media_processes_in_tree() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut offenders = Vec::new();
+        for entry in walkdir::WalkDir::new(&root).into_iter().flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("rs") || path.ends_with("proc.rs") {
+                continue;
+            }
+            let Ok(text) = std::fs::read_to_string(path) else { continue };
+            for (n, line) in text.lines().enumerate() {
+                if !line.trim_start().starts_with("//") && line.contains("Command::new(") {
+                    offenders.push(format!("{}:{}", path.display(), n + 1));
+                }
+            }
+        }
+        assert!(
+            offenders.is_empty(),
+            "spawn these through agent::proc (or proc::unmanaged): {:?}",
+            offenders
+        );
+    }
+}
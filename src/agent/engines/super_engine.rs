@@ -183,9 +183,9 @@ impl SuperEngine {
                 }
             }
             Intent::ScanVideo(path) => {
-                use crate::agent::vision_tools;
+                use crate::agent::scan_cache;
                 let p = Path::new(&path);
-                match vision_tools::scan_visual(p).await {
+                match scan_cache::visual(p).await {
                     Ok(scenes) => Ok(format!("Scanned {} scenes in {:?}", scenes.len(), p)),
                     Err(e) => Err(e.to_string()),
                 }
@@ -199,6 +199,12 @@ impl SuperEngine {
                 instruction, input
             )),
             Intent::DiscoverFile(query) => Ok(format!("DISCOVERY_MODE:{}", query)),
+            Intent::RunTool(tool, path, params) => {
+                crate::agent::tool_registry::run_tool(&tool, Path::new(&path), &params)
+                    .await
+                    .map(|output| format!("{} wrote {}", tool, output.display()))
+                    .map_err(|e| e.to_string())
+            }
             Intent::Orchestrate(_, _) => unreachable!("Handled in process_command"),
             Intent::Unknown(_) => unreachable!("Handled in process_command"),
         }
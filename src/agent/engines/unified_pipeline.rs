@@ -4,31 +4,59 @@
 // Combines all processing stages into a single, GPU-accelerated pipeline.
 
 use crate::agent::tools::production_tools::safe_arg_path;
+use crate::agent::engines::proc::{self, ProcClass};
 use crate::gpu_backend::{get_gpu_context, GpuBackend, GpuContext};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::process::Command;
 use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::tools::tool_registry::{self, ToolParams};
 use tracing::{info, warn};
 
 /// Pipeline stages that can be executed
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PipelineStage {
     Download,   // Download from YouTube/URL
     Transcribe, // Speech-to-text transcription
     SmartEdit,  // Intent-based smart editing
+    RemoveBg,   // Person segmentation / background replacement
 
     Enhance, // Audio enhancement
     Encode,  // Final video encoding
+    /// A registered `Tool` (`trim:start=5;duration=10`)
+    Tool { name: String, params: ToolParams },
 }
 
 impl PipelineStage {
-    /// Parse stage from string
+    /// Parse stage from string; anything else that names a registered tool
+    /// becomes a `Tool` stage, with params after a `:`.
     pub fn from_str(s: &str) -> Option<Self> {
-        match s.to_lowercase().as_str() {
+        let (name, spec) = s.split_once(':').unwrap_or((s, ""));
+        if let Some(tool) = tool_registry::get_tool(name.trim()) {
+            // Built-in stage names win over a tool of the same name
+            if Self::builtin(name).is_none() {
+                return Some(Self::Tool {
+                    name: tool.name().to_string(),
+                    params: tool_registry::parse_params(spec),
+                });
+            }
+        }
+        Self::builtin(name)
+    }
+
+    /// Short name for logs and work file names.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Tool { name, .. } => name.clone(),
+            other => format!("{:?}", other),
+        }
+    }
+
+    fn builtin(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
             "download" => Some(Self::Download),
             "transcribe" => Some(Self::Transcribe),
             "smart_edit" | "smartedit" | "edit" => Some(Self::SmartEdit),
+            "removebg" | "remove_bg" | "matting" => Some(Self::RemoveBg),
 
             "enhance" | "audio" => Some(Self::Enhance),
             "encode" | "render" => Some(Self::Encode),
@@ -64,6 +92,8 @@ pub struct PipelineConfig {
     pub scale_factor: f64,
     /// Target size in MB for compression (0 = no compression)
     pub target_size_mb: f64,
+    /// Background removal settings for the RemoveBg stage
+    pub matting: crate::agent::matting::MattingConfig,
 
     /// Progress callback
     pub progress_callback: Option<Arc<dyn Fn(&str) + Send + Sync>>,
@@ -80,6 +110,7 @@ impl Default for PipelineConfig {
             intent: None,
             scale_factor: 2.0,
             target_size_mb: 0.0,
+            matting: Default::default(),
 
             progress_callback: None,
             learned_pattern: None,
@@ -122,11 +153,11 @@ impl UnifiedPipeline {
         self.report_progress(&config, &format!("GPU Backend: {}", self.gpu.backend));
 
         for (i, stage) in config.stages.iter().enumerate() {
-            let stage_output = work_dir.join(format!("stage_{:02}_{:?}.mp4", i, stage));
+            let stage_output = work_dir.join(format!("stage_{:02}_{}.mp4", i, stage.label()));
 
             self.report_progress(
                 &config,
-                &format!("Stage {}/{}: {:?}", i + 1, config.stages.len(), stage),
+                &format!("Stage {}/{}: {}", i + 1, config.stages.len(), stage.label()),
             );
 
             match stage {
@@ -143,6 +174,15 @@ impl UnifiedPipeline {
                         warn!("[PIPELINE] SmartEdit skipped: no intent provided");
                     }
                 }
+                PipelineStage::RemoveBg => {
+                    if crate::agent::matting::MattingEngine::is_available() {
+                        current_input = self
+                            .run_remove_bg(&current_input, &stage_output, &config)
+                            .await?;
+                    } else {
+                        warn!("[PIPELINE] RemoveBg skipped: rembg or segmentation model not found");
+                    }
+                }
 
                 PipelineStage::Enhance => {
                     current_input = self
@@ -154,6 +194,11 @@ impl UnifiedPipeline {
                         .run_encode(&current_input, &stage_output, &config)
                         .await?;
                 }
+                PipelineStage::Tool { name, params } => {
+                    let mut params = params.clone();
+                    params.insert("output".to_string(), stage_output.to_string_lossy().into_owned());
+                    current_input = tool_registry::run_tool(name, &current_input, &params).await?;
+                }
                 _ => {
                     info!("[PIPELINE] Stage {:?} not yet implemented", stage);
                 }
@@ -229,6 +274,29 @@ impl UnifiedPipeline {
         Ok(output.to_path_buf())
     }
 
+    async fn run_remove_bg(
+        &self,
+        input: &Path,
+        output: &Path,
+        config: &PipelineConfig,
+    ) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+        use crate::agent::matting::{MatteBackground, MattingEngine};
+
+        self.report_progress(config, "Removing background...");
+
+        // Stage outputs are .mp4, which can't carry alpha — key over green instead.
+        let mut matting = config.matting.clone();
+        if matting.background == MatteBackground::Transparent {
+            matting.background = MatteBackground::Color("green".to_string());
+        }
+
+        MattingEngine::remove_background(input, output, &matting)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(output.to_path_buf())
+    }
+
     async fn run_enhance(
         &self,
         input: &Path,
@@ -245,7 +313,7 @@ impl UnifiedPipeline {
 
         // Remux with enhanced audio using GPU encoder
         let encoder = self.gpu.ffmpeg_encoder();
-        let mut cmd = Command::new("ffmpeg");
+        let mut cmd = proc::ffmpeg(ProcClass::Render);
         cmd.stealth();
         cmd.args(["-y", "-nostdin"]);
 
@@ -287,7 +355,7 @@ impl UnifiedPipeline {
         );
 
         // let encoder = self.gpu.ffmpeg_encoder();
-        let mut cmd = Command::new("ffmpeg");
+        let mut cmd = proc::ffmpeg(ProcClass::Render);
         cmd.stealth();
         cmd.args(["-y", "-nostdin"]);
 
@@ -348,4 +416,26 @@ mod tests {
         let stages = PipelineStage::parse_list("all");
         assert!(stages.len() >= 3);
     }
+
+    #[test]
+    fn test_removebg_stage_parsing() {
+        let stages = PipelineStage::parse_list("removebg, encode");
+        assert_eq!(stages, vec![PipelineStage::RemoveBg, PipelineStage::Encode]);
+        assert_eq!(PipelineStage::from_str("matting"), Some(PipelineStage::RemoveBg));
+    }
+
+    #[test]
+    fn registered_tools_parse_as_stages_with_params() {
+        let stages = PipelineStage::parse_list("trim:start=5;duration=10, grade, bogus, encode");
+        assert_eq!(stages.len(), 3);
+        assert_eq!(
+            stages[0],
+            PipelineStage::Tool {
+                name: "trim".to_string(),
+                params: tool_registry::parse_params("start=5;duration=10"),
+            }
+        );
+        assert_eq!(stages[1].label(), "grade");
+        assert_eq!(stages[2], PipelineStage::Encode);
+    }
 }
@@ -11,6 +11,11 @@ pub mod core_systems {
     pub mod core;
     pub mod body;
     pub mod health;
+    pub mod operation_history;
+    pub mod project;
+    pub mod doctor;
+    pub mod bench;
+    pub mod setup;
     // GEPA: Goal-Experience-Policy-Agent self-improvement loop
     pub mod trajectory;
     pub mod gepa;
@@ -19,6 +24,8 @@ pub mod core_systems {
 // AI Systems - LLM providers, reasoning, multi-agent orchestration
 pub mod ai_systems {
     pub mod llm_provider;
+    pub mod llm_json;
+    pub mod ollama_pull;
     pub mod gpt_oss_bridge;
     pub mod token_optimizer;
     pub mod reasoning;
@@ -38,6 +45,7 @@ pub mod ai_systems {
 pub mod video_processing {
     pub mod video_editing_agent;
     pub mod video_player;
+    pub mod preview_audio;
     pub mod video_stitcher;
     pub mod video_style_learner;
     pub mod video_refresh;
@@ -45,6 +53,11 @@ pub mod video_processing {
     pub mod multicam;
     pub mod animator;
     pub mod upscale_engine;
+    pub mod upscale_checkpoint;
+    pub mod matting;
+    pub mod vector_engine;
+    pub mod effects;
+    pub mod timeline_render;
 }
 
 // Tools - Audio, vision, transcription, research, and production utilities
@@ -55,6 +68,18 @@ pub mod tools {
     pub mod source_tools;
     pub mod research_tools;
     pub mod production_tools;
+    pub mod platform_presets;
+    pub mod search_providers;
+    pub mod deep_research;
+    pub mod subtitle_import;
+    pub mod output_naming;
+    pub mod tool_registry;
+    pub mod archive;
+    pub mod timecode;
+    pub mod scan_cache;
+    pub mod post_render;
+    pub mod media_analysis;
+    pub mod audio_classify;
 }
 
 // Engines - Core processing engines and pipelines
@@ -64,6 +89,7 @@ pub mod engines {
     pub mod motor_cortex;
     pub mod editor_queue;
     pub mod process_utils;
+    pub mod proc;
 }
 
 // CUDA - High-performance GPU computation
@@ -97,11 +123,11 @@ pub mod specialized {
 }
 
 // Re-export commonly used modules at the root level for backwards compatibility
-pub use core_systems::{brain, core, consciousness, autonomous_learner, learning, body, health, neuroplasticity};
-pub use ai_systems::{llm_provider, gpt_oss_bridge, token_optimizer, reasoning, moe, supervisor, multi_agent, hive_mind, turbo_quant, react_agent, gemma4_harness};
-pub use video_processing::{video_editing_agent, video_player, video_stitcher, video_style_learner, multicam, animator, upscale_engine};
-pub use tools::{audio_tools, vision_tools, transcription, source_tools, research_tools, production_tools};
-pub use engines::{super_engine, unified_pipeline, motor_cortex, editor_queue, process_utils};
+pub use core_systems::{brain, core, consciousness, autonomous_learner, learning, body, health, neuroplasticity, operation_history, project, doctor, bench, setup};
+pub use ai_systems::{llm_provider, llm_json, ollama_pull, gpt_oss_bridge, token_optimizer, reasoning, moe, supervisor, multi_agent, hive_mind, turbo_quant, react_agent, gemma4_harness};
+pub use video_processing::{video_editing_agent, video_player, preview_audio, video_stitcher, video_style_learner, multicam, animator, upscale_engine, upscale_checkpoint, matting, vector_engine, effects, timeline_render};
+pub use tools::{audio_tools, vision_tools, transcription, source_tools, research_tools, production_tools, platform_presets, search_providers, deep_research, subtitle_import, output_naming, tool_registry, archive, timecode, scan_cache, post_render, media_analysis, audio_classify};
+pub use engines::{super_engine, unified_pipeline, motor_cortex, editor_queue, process_utils, proc};
 pub use cuda::{cuda_kernel_gen, cuda_pipeline, latent_optimizer};
 pub use security::{io_shield, validation_gate, download_guard, recovery, defense};
 pub use specialized::{reference_editor, synoid_link, global_discovery, smart_editor, academy, auto_research, auto_improve};
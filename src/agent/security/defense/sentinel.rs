@@ -3,6 +3,10 @@ use std::collections::HashMap;
 use sysinfo::System;
 use tracing::{error, info, warn};
 
+/// Logged wherever a disabled Sentinel is asked to run.
+pub const DISABLED_MESSAGE: &str =
+    "Sentinel is DISABLED. Set enable_sentinel in the config file or SYNOID_ENABLE_SENTINEL=true to activate.";
+
 /// The Sentinel monitors system state for anomalies
 pub struct Sentinel {
     system: System,
@@ -14,21 +18,20 @@ pub struct Sentinel {
 }
 
 impl Sentinel {
+    /// The explicit opt-in every entry point (GUI, `guard`, the learner)
+    /// checks before monitoring.
+    pub fn is_enabled() -> bool {
+        crate::config::current().enable_sentinel
+    }
+
     pub fn new() -> Self {
         let mut system = System::new_all();
         system.refresh_all();
 
-        // Default to local Ollama instance for Sentinel
-        let api_url =
-            std::env::var("SYNOID_API_URL").unwrap_or("http://localhost:11434/v1".to_string());
-
-        // Explicit opt-in required for Sentinel
-        let enabled = std::env::var("SYNOID_ENABLE_SENTINEL")
-            .map(|v| v.to_lowercase() == "true" || v == "1")
-            .unwrap_or(false);
-
+        let api_url = crate::config::current().api_url;
+        let enabled = Self::is_enabled();
         if !enabled {
-            warn!("[SENTINEL] ⚠️ Sentinel initialized but DISABLED. Set SYNOID_ENABLE_SENTINEL=true to activate.");
+            warn!("[SENTINEL] ⚠️ {}", DISABLED_MESSAGE);
         }
 
         Self {
@@ -65,9 +68,12 @@ impl Sentinel {
             // 1. High Resource Usage Alert (Normalized CPU > 80%)
             // We ignore synoid-core (ourselves) to prevent the learner from blocking on its own work
             if cpu_usage > 80.0 && !name.contains("synoid-core") {
+                let owner = crate::agent::engines::proc::describe_pid(pid.as_u32())
+                    .map(|d| format!(" [{}]", d))
+                    .unwrap_or_default();
                 let msg = format!(
-                    "High CPU Alert: Process '{}' (PID: {}) is using {:.1}% CPU",
-                    name, pid, cpu_usage
+                    "High CPU Alert: Process '{}' (PID: {}) is using {:.1}% CPU{}",
+                    name, pid, cpu_usage, owner
                 );
                 warn!("[SENTINEL] {}", msg);
                 alerts.push(msg);
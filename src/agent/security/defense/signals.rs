@@ -27,6 +27,7 @@ pub fn install_signal_handler(on_shutdown: EmergencySaveFn) {
             Ok(()) => {
                 warn!("[SIGNAL] ⛔ SIGINT (Ctrl-C) received. Initiating Atomic Stop...");
                 on_shutdown().await;
+                crate::agent::engines::proc::kill_all_children();
                 info!("[SIGNAL] ✅ Emergency save complete. SYNOID hibernated safely.");
                 std::process::exit(0);
             }
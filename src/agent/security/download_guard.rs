@@ -10,6 +10,8 @@
 //   Allowed  — Any HTTPS URL that passes pattern checks
 //   Blocked  — Non-HTTPS, blocked patterns, injection URIs
 
+use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
 use std::fs::{self, File};
 use std::io::Read;
 use std::path::Path;
@@ -69,6 +71,13 @@ const MIN_FILE_SIZE: u64 = 10 * 1024;
 /// Maximum sane file size (10 GB).
 const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024 * 1024;
 
+/// Allowed drift from the expected duration: this many seconds or
+/// `DURATION_TOLERANCE_RATIO` of the expected length, whichever is larger.
+const DURATION_TOLERANCE_SECS: f64 = 2.0;
+const DURATION_TOLERANCE_RATIO: f64 = 0.02;
+/// How far before the end the tail decode starts (ffmpeg's `-sseof`).
+const TAIL_DECODE_SECS: &str = "-5";
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Security tier
 // ─────────────────────────────────────────────────────────────────────────────
@@ -190,6 +199,9 @@ impl DownloadGuard {
         // 5. Container signature check — verify the file is actually a valid media container
         Self::check_container_signature(path, &ext)?;
 
+        // 6. Decodability check — a valid header doesn't mean the rest arrived
+        Self::check_decodable(path, None)?;
+
         info!(
             "[GUARD] ✅ File passed full security check: {:?} ({} bytes)",
             path.file_name().unwrap_or_default(),
@@ -198,6 +210,96 @@ impl DownloadGuard {
         Ok(())
     }
 
+    /// Verify with ffprobe that the file demuxes and reports a duration, and
+    /// that the duration is close to `expected_duration` when one is known,
+    /// then decode the last few seconds with ffmpeg. Catches truncated or
+    /// interrupted downloads. Returns the probed duration, or `None` when
+    /// ffprobe is not installed and the file could not be checked.
+    pub fn check_decodable(path: &Path, expected_duration: Option<f64>) -> Result<Option<f64>, String> {
+        let output = match proc::blocking_command("ffprobe", ProcClass::Probe)
+            .stealth()
+            .args([
+                "-v",
+                "error",
+                "-show_entries",
+                "format=duration",
+                "-of",
+                "default=noprint_wrappers=1:nokey=1",
+            ])
+            .arg(path)
+            .output()
+        {
+            Ok(output) => output,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                warn!("[GUARD] ffprobe not found; cannot verify {:?} decodes", path);
+                return Ok(None);
+            }
+            Err(e) => return Err(format!("Cannot run ffprobe to verify {:?}: {}", path, e)),
+        };
+
+        if !output.status.success() {
+            warn!("[GUARD] 🛡️ Undecodable media: {:?}", path);
+            return Err(format!(
+                "File is not decodable: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let duration: f64 = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .unwrap_or(0.0);
+        if duration <= 0.0 {
+            return Err("File is not decodable: no playable duration".to_string());
+        }
+
+        if let Some(expected) = expected_duration.filter(|d| *d > 0.0) {
+            let tolerance = DURATION_TOLERANCE_SECS.max(expected * DURATION_TOLERANCE_RATIO);
+            if (duration - expected).abs() > tolerance {
+                warn!(
+                    "[GUARD] 🛡️ Duration mismatch for {:?}: {:.1}s vs expected {:.1}s",
+                    path, duration, expected
+                );
+                return Err(format!(
+                    "File duration {:.1}s differs from expected {:.1}s — likely truncated",
+                    duration, expected
+                ));
+            }
+        }
+
+        Self::check_tail_decodes(path)?;
+        Ok(Some(duration))
+    }
+
+    /// Decode the last seconds of `path`. A header can report the full
+    /// duration while the data behind it is cut short or corrupt.
+    fn check_tail_decodes(path: &Path) -> Result<(), String> {
+        let output = match proc::blocking_command("ffmpeg", ProcClass::Probe)
+            .stealth()
+            .args(["-v", "error", "-sseof", TAIL_DECODE_SECS, "-i"])
+            .arg(path)
+            .args(["-f", "null", "-"])
+            .output()
+        {
+            Ok(output) => output,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                warn!("[GUARD] ffmpeg not found; cannot verify the end of {:?} decodes", path);
+                return Ok(());
+            }
+            Err(e) => return Err(format!("Cannot run ffmpeg to verify {:?}: {}", path, e)),
+        };
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() || !stderr.trim().is_empty() {
+            warn!("[GUARD] 🛡️ End of media does not decode: {:?}", path);
+            return Err(format!(
+                "File does not decode to the end: {}",
+                stderr.lines().next().unwrap_or("ffmpeg failed").trim()
+            ));
+        }
+        Ok(())
+    }
+
     /// Inspect the first bytes of a file for executable signatures.
     fn check_magic_bytes(path: &Path) -> Result<(), String> {
         let mut file = File::open(path)
@@ -451,6 +553,65 @@ mod tests {
         let _ = fs::remove_dir_all(&dir);
     }
 
+    fn have_tool(name: &str) -> bool {
+        // Unmanaged: only asks whether the tool exists
+        proc::blocking_unmanaged(name).arg("-version").output().is_ok()
+    }
+
+    #[test]
+    fn test_block_undecodable_media() {
+        if !have_tool("ffprobe") {
+            eprintln!("ffprobe not found; skipping");
+            return;
+        }
+        let dir = std::env::temp_dir().join("synoid_guard_test_decode");
+        let _ = fs::create_dir_all(&dir);
+        let truncated = dir.join("truncated.mp4");
+
+        // Valid MP4 signature, but nothing after it can be demuxed
+        let mut f = File::create(&truncated).unwrap();
+        f.write_all(&[0, 0, 0, 0x18]).unwrap();
+        f.write_all(b"ftypisom").unwrap();
+        f.write_all(&vec![0u8; 20_000]).unwrap();
+        f.flush().unwrap();
+
+        let result = DownloadGuard::validate_downloaded_file(&truncated);
+        assert!(result.is_err());
+        assert!(DownloadGuard::check_decodable(&truncated, Some(60.0)).is_err());
+
+        let _ = fs::remove_file(&truncated);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_block_media_with_corrupt_tail() {
+        if !have_tool("ffprobe") || !have_tool("ffmpeg") {
+            eprintln!("ffmpeg/ffprobe not found; skipping");
+            return;
+        }
+        let dir = std::env::temp_dir().join("synoid_guard_test_tail");
+        let _ = fs::create_dir_all(&dir);
+        let clip = dir.join("clip.mp4");
+        let made = proc::blocking_ffmpeg(ProcClass::Render)
+            .args(["-y", "-v", "error", "-f", "lavfi", "-i", "testsrc=duration=10:size=320x240:rate=25"])
+            .args(["-c:v", "libx264", "-movflags", "+faststart"])
+            .arg(&clip)
+            .status()
+            .is_ok_and(|s| s.success());
+        assert!(made, "ffmpeg could not encode the test clip");
+        assert!(DownloadGuard::check_decodable(&clip, Some(10.0)).unwrap().is_some());
+
+        // Header and index intact, the last third of the media data garbage
+        let mut data = fs::read(&clip).unwrap();
+        let from = data.len() * 2 / 3;
+        data[from..].fill(0xFF);
+        fs::write(&clip, &data).unwrap();
+        let err = DownloadGuard::check_decodable(&clip, Some(10.0)).unwrap_err();
+        assert!(err.contains("decode to the end"), "{}", err);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_sanitize_path_traversal() {
         assert_eq!(
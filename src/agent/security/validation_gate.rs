@@ -6,8 +6,8 @@
 // text on stderr.
 
 use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
 use std::path::Path;
-use std::process::Command;
 use tracing::{error, info};
 
 pub struct ValidationGate;
@@ -25,7 +25,7 @@ impl ValidationGate {
             }
         };
 
-        let output = Command::new("ffmpeg")
+        let output = proc::blocking_ffmpeg(ProcClass::Probe)
             .stealth()
             .args([
                 "-v", "error", // Only report real errors
@@ -2,10 +2,36 @@
 // Copyright (c) 2026 xingxerx_The_Creator | SYNOID
 
 use crate::agent::ai_systems::gpt_oss_bridge::SynoidAgent;
+use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
 use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tracing::{info, warn};
 use url::Url;
 
+const SCANNER_CONFIG_FILE: &str = "code_scanner.json";
+const SCANNER_NOTES_FILE: &str = "scanner_notes.json";
+/// Oldest notes are dropped past this many.
+const MAX_NOTES: usize = 500;
+const MAX_FILE_BYTES: u64 = 100_000;
+const CLONE_TIMEOUT_SECS: u64 = 120;
+
+/// Source files worth distilling; everything else in a clone is ignored.
+const SCANNABLE_EXTENSIONS: &[&str] = &[
+    "rs", "c", "cc", "cpp", "cxx", "h", "hpp", "py", "js", "ts", "glsl", "frag", "cu",
+];
+/// Directories holding vendored or generated code rather than the project's own logic.
+const SKIPPED_DIRS: &[&str] = &[
+    ".git", "vendor", "third_party", "thirdparty", "external", "node_modules", "build", "dist",
+];
+/// Filename hints that a file implements editing logic.
+const TECHNIQUE_HINTS: &[&str] = &[
+    "transition", "filter", "blend", "color", "colour", "interp", "keyframe", "curve", "audio",
+    "mix", "render", "scale", "effect", "fade", "composit",
+];
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AnalyzedConcept {
     pub source_repo: String,
@@ -15,6 +41,223 @@ pub struct AnalyzedConcept {
     pub confidence: f32,
 }
 
+/// Limits on what the learner's repository scanning may touch.
+/// Stored as `cortex_cache{instance}/code_scanner.json`; a default file is
+/// written on first use so the allow-list can be edited.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScannerConfig {
+    /// Only these repositories are ever cloned.
+    pub allowed_repos: Vec<String>,
+    /// `git clone --depth`.
+    pub clone_depth: u32,
+    /// Repositories larger than this (per the host's API, then on disk) are skipped.
+    pub max_repo_mb: u64,
+    pub max_repos_per_cycle: usize,
+    pub max_files_per_repo: usize,
+    /// Clones live here only for the duration of a scan.
+    pub scratch_dir: PathBuf,
+}
+
+impl Default for ScannerConfig {
+    fn default() -> Self {
+        Self {
+            allowed_repos: vec![
+                "https://github.com/mltframework/mlt".to_string(),
+                "https://github.com/KDE/kdenlive".to_string(),
+                "https://github.com/OpenShot/libopenshot".to_string(),
+                "https://github.com/Shotcut/shotcut".to_string(),
+                "https://github.com/obsproject/obs-studio".to_string(),
+            ],
+            clone_depth: 1,
+            max_repo_mb: 200,
+            max_repos_per_cycle: 1,
+            max_files_per_repo: 3,
+            scratch_dir: std::env::temp_dir().join("synoid_scanner"),
+        }
+    }
+}
+
+impl ScannerConfig {
+    pub fn load(instance_id: &str) -> Self {
+        let dir = PathBuf::from(format!("cortex_cache{}", instance_id));
+        let path = dir.join(SCANNER_CONFIG_FILE);
+        if let Ok(data) = fs::read_to_string(&path) {
+            return serde_json::from_str(&data).unwrap_or_else(|e| {
+                warn!("[SCANNER] ⚠️ Invalid {:?} ({}); using defaults.", path, e);
+                Self::default()
+            });
+        }
+        let config = Self::default();
+        let _ = fs::create_dir_all(&dir);
+        if let Ok(json) = serde_json::to_string_pretty(&config) {
+            if fs::write(&path, json).is_ok() {
+                info!("[SCANNER] Created default config at {:?}", path);
+            }
+        }
+        config
+    }
+
+    pub fn is_allowed(&self, repo_url: &str) -> bool {
+        let wanted = normalize_repo_url(repo_url);
+        self.allowed_repos
+            .iter()
+            .any(|r| normalize_repo_url(r) == wanted)
+    }
+}
+
+fn normalize_repo_url(url: &str) -> String {
+    let lower = url.trim().to_ascii_lowercase();
+    let trimmed = lower.trim_end_matches('/');
+    trimmed.strip_suffix(".git").unwrap_or(trimmed).to_string()
+}
+
+/// One technique distilled from a scanned file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanNote {
+    pub repo: String,
+    /// Path relative to the repository root.
+    pub file: String,
+    pub technique: String,
+    pub scanned_at: String,
+}
+
+fn notes_path(instance_id: &str) -> PathBuf {
+    PathBuf::from(format!("cortex_cache{}", instance_id)).join(SCANNER_NOTES_FILE)
+}
+
+/// All stored scan notes, oldest first.
+pub fn load_notes(instance_id: &str) -> Vec<ScanNote> {
+    fs::read_to_string(notes_path(instance_id))
+        .ok()
+        .and_then(|d| serde_json::from_str(&d).ok())
+        .unwrap_or_default()
+}
+
+pub fn append_notes(instance_id: &str, new_notes: &[ScanNote]) {
+    if new_notes.is_empty() {
+        return;
+    }
+    let mut notes = load_notes(instance_id);
+    notes.extend_from_slice(new_notes);
+    if notes.len() > MAX_NOTES {
+        notes.drain(..notes.len() - MAX_NOTES);
+    }
+    let path = notes_path(instance_id);
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(&notes) {
+        let _ = fs::write(path, json);
+    }
+}
+
+/// Scratch clone directory, removed when dropped.
+struct ScratchDir(PathBuf);
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_dir_all(&self.0) {
+            if self.0.exists() {
+                warn!("[SCANNER] ⚠️ Failed to clean scratch dir {:?}: {}", self.0, e);
+            }
+        }
+    }
+}
+
+fn dir_size_bytes(dir: &Path) -> u64 {
+    walkdir::WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Pick up to `limit` source files from a clone, preferring names that hint
+/// at editing techniques. Symlinks are never followed.
+fn pick_candidate_files(root: &Path, limit: usize) -> Vec<PathBuf> {
+    let mut candidates: Vec<(bool, PathBuf)> = walkdir::WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            !(e.file_type().is_dir()
+                && SKIPPED_DIRS.contains(&e.file_name().to_string_lossy().to_lowercase().as_str()))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.metadata().map(|m| m.len() <= MAX_FILE_BYTES).unwrap_or(false))
+        .filter(|e| {
+            e.path()
+                .extension()
+                .and_then(|x| x.to_str())
+                .is_some_and(|x| SCANNABLE_EXTENSIONS.contains(&x.to_lowercase().as_str()))
+        })
+        .map(|e| {
+            let name = e.file_name().to_string_lossy().to_lowercase();
+            let hinted = TECHNIQUE_HINTS.iter().any(|h| name.contains(h));
+            (hinted, e.into_path())
+        })
+        .collect();
+    // Hinted files first, then a stable path order
+    candidates.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    candidates.into_iter().take(limit).map(|(_, p)| p).collect()
+}
+
+/// Wait for a clone into `dir`, killing it once it times out or writes more
+/// than `max_bytes`. Hosts other than GitHub report no size up front, so this
+/// is what actually bounds the transfer.
+async fn wait_for_clone(
+    child: &mut proc::ManagedChild,
+    dir: &Path,
+    max_bytes: u64,
+) -> Result<std::process::ExitStatus, String> {
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(CLONE_TIMEOUT_SECS);
+    let mut tick = tokio::time::interval(Duration::from_millis(500));
+    loop {
+        tokio::select! {
+            status = child.wait() => return status.map_err(|e| e.to_string()),
+            _ = tick.tick() => {
+                if tokio::time::Instant::now() >= deadline {
+                    let _ = child.kill().await;
+                    return Err("timed out".to_string());
+                }
+                let owned = dir.to_path_buf();
+                let size = tokio::task::spawn_blocking(move || dir_size_bytes(&owned))
+                    .await
+                    .unwrap_or(0);
+                if size > max_bytes {
+                    let _ = child.kill().await;
+                    return Err(format!("passed {} MB", max_bytes / 1_048_576));
+                }
+            }
+        }
+    }
+}
+
+/// Repository size in MB as reported by GitHub, when the host is GitHub.
+async fn github_repo_size_mb(repo_url: &str) -> Option<u64> {
+    let url = Url::parse(repo_url).ok()?;
+    if url.host_str()? != "github.com" {
+        return None;
+    }
+    let mut parts = url.path_segments()?;
+    let owner = parts.next()?;
+    let name = parts.next()?.trim_end_matches(".git");
+    let client = crate::net::build_client(Duration::from_secs(10));
+    let resp = client
+        .get(format!("https://api.github.com/repos/{}/{}", owner, name))
+        .header("User-Agent", "synoid-scanner")
+        .send()
+        .await
+        .ok()?;
+    let json: serde_json::Value = resp.json().await.ok()?;
+    // GitHub reports size in KB
+    json.get("size")?.as_u64().map(|kb| kb / 1024)
+}
+
 pub struct CodeScanner {
     agent: SynoidAgent,
 }
@@ -68,23 +311,144 @@ impl CodeScanner {
         }
 
         let code_content = resp.text().await?;
-        let code_len = code_content.len();
 
         // 2. Filter for relevance (Client-side heuristic)
         // If file is too huge or binary, skip
-        if code_len > 100_000 || code_content.contains('\0') {
+        if code_content.len() > MAX_FILE_BYTES as usize || code_content.contains('\0') {
             return Err("File too large or binary".into());
         }
 
+        let logic = self.distill_logic(url, &code_content).await;
+
+        let file_ext = Url::parse(url)?
+            .path_segments()
+            .and_then(|check| check.last())
+            .and_then(|name| name.split('.').last())
+            .unwrap_or("unknown")
+            .to_string();
+
+        Ok(AnalyzedConcept {
+            source_repo: url.to_string(),
+            concept: "Algorithmic Logic".to_string(),
+            file_type: file_ext,
+            logic_summary: logic,
+            confidence: 0.85,
+        })
+    }
+
+    /// Shallow-clone an allow-listed repository into a scratch directory,
+    /// distill a few source files and return structured notes. The clone is
+    /// only ever read — nothing in it is built or executed — and it is
+    /// deleted when the scan finishes, successful or not.
+    pub async fn scan_repo(
+        &self,
+        repo_url: &str,
+        config: &ScannerConfig,
+    ) -> Result<Vec<ScanNote>, Box<dyn std::error::Error + Send + Sync>> {
+        if !config.is_allowed(repo_url) {
+            return Err(format!("{} is not in the scanner allow-list", repo_url).into());
+        }
+
+        if let Some(mb) = github_repo_size_mb(repo_url).await {
+            if mb > config.max_repo_mb {
+                return Err(format!("{} is {} MB (cap {} MB)", repo_url, mb, config.max_repo_mb).into());
+            }
+        }
+
+        let slug: String = normalize_repo_url(repo_url)
+            .rsplit('/')
+            .take(2)
+            .collect::<Vec<_>>()
+            .join("_")
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        fs::create_dir_all(&config.scratch_dir)?;
+        let scratch = ScratchDir(
+            config
+                .scratch_dir
+                .join(format!("{}_{}", slug, std::process::id())),
+        );
+        let _ = fs::remove_dir_all(&scratch.0);
+        // An empty hooks directory: `/dev/null` is not a path on Windows
+        let hooks = ScratchDir(
+            config
+                .scratch_dir
+                .join(format!("{}_{}_hooks", slug, std::process::id())),
+        );
+        fs::create_dir_all(&hooks.0)?;
+
+        info!("[SCANNER] 📦 Shallow-cloning {} (depth {})", repo_url, config.clone_depth);
+        let mut cmd = proc::command("git", ProcClass::Download);
+        cmd.stealth()
+            // No hooks, no symlinks out of the tree, no local-file or LFS fetches
+            .arg("-c")
+            .arg(format!("core.hooksPath={}", hooks.0.display()))
+            .args(["-c", "core.symlinks=false"])
+            .args(["-c", "protocol.file.allow=never"])
+            .env("GIT_LFS_SKIP_SMUDGE", "1")
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .args(["clone", "--quiet", "--single-branch", "--no-tags"])
+            .arg(format!("--depth={}", config.clone_depth.max(1)))
+            .arg(repo_url)
+            .arg(&scratch.0)
+            .kill_on_drop(true);
+        let mut child = cmd.spawn().await?;
+        let status = wait_for_clone(&mut child, &scratch.0, config.max_repo_mb * 1_048_576)
+            .await
+            .map_err(|e| format!("Clone of {} {}", repo_url, e))?;
+        if !status.success() {
+            return Err(format!("git clone failed for {}", repo_url).into());
+        }
+
+        let size_mb = dir_size_bytes(&scratch.0) / 1_048_576;
+        if size_mb > config.max_repo_mb {
+            return Err(format!(
+                "Clone of {} is {} MB (cap {} MB)",
+                repo_url, size_mb, config.max_repo_mb
+            )
+            .into());
+        }
+
+        let files = pick_candidate_files(&scratch.0, config.max_files_per_repo);
+        let mut notes = Vec::with_capacity(files.len());
+        for file in files {
+            // Non-UTF-8 files are skipped
+            let Ok(code) = fs::read_to_string(&file) else {
+                continue;
+            };
+            if code.contains('\0') {
+                continue;
+            }
+            let rel = file
+                .strip_prefix(&scratch.0)
+                .unwrap_or(&file)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let technique = self
+                .distill_logic(&format!("{}/{}", repo_url, rel), &code)
+                .await;
+            notes.push(ScanNote {
+                repo: repo_url.to_string(),
+                file: rel,
+                technique,
+                scanned_at: chrono::Utc::now().to_rfc3339(),
+            });
+        }
+        info!("[SCANNER] 🧹 {} note(s) from {}; scratch cleaned.", notes.len(), repo_url);
+        Ok(notes)
+    }
+
+    /// Extract a one-sentence description of the technique in `code`.
+    async fn distill_logic(&self, source: &str, code: &str) -> String {
         // 3. Extract Conceptual Logic (LLM)
         // We do strictly extraction of *math* or *logic*, no copy-paste.
-        info!("[SCANNER] 🧠 Distilling logic from {} bytes...", code_len);
+        info!("[SCANNER] 🧠 Distilling logic from {} bytes...", code.len());
 
         // Truncate for context window
-        let snippet = if code_len > 3000 {
-            &code_content[..3000]
-        } else {
-            &code_content
+        let snippet = match code.char_indices().nth(3000) {
+            Some((idx, _)) => &code[..idx],
+            None => code,
         };
 
         let prompt = format!(
@@ -92,11 +456,10 @@ impl CodeScanner {
             Extract ONLY the mathematical concept or logic rule (e.g., 'Use Catmull-Rom splines for smooth keyframes').\n\
             DO NOT output any code. Output a single sentence summary.\n\n\
             Code:\n```\n{}\n```",
-            url, snippet
+            source, snippet
         );
 
-        let logic = self
-            .agent
+        self.agent
             .reason(&prompt)
             .await
             .map_err(|e| {
@@ -106,21 +469,38 @@ impl CodeScanner {
                 );
                 e
             })
-            .unwrap_or_else(|_| "Algorithmic logic distilled from source code.".to_string());
+            .unwrap_or_else(|_| "Algorithmic logic distilled from source code.".to_string())
+    }
+}
 
-        let file_ext = Url::parse(url)?
-            .path_segments()
-            .and_then(|check| check.last())
-            .and_then(|name| name.split('.').last())
-            .unwrap_or("unknown")
-            .to_string();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        Ok(AnalyzedConcept {
-            source_repo: url.to_string(),
-            concept: "Algorithmic Logic".to_string(),
-            file_type: file_ext,
-            logic_summary: logic,
-            confidence: 0.85,
-        })
+    #[test]
+    fn allow_list_ignores_trailing_slash_and_git_suffix() {
+        let config = ScannerConfig::default();
+        assert!(config.is_allowed("https://github.com/KDE/kdenlive.git"));
+        assert!(config.is_allowed("https://github.com/kde/kdenlive/"));
+        assert!(!config.is_allowed("https://github.com/evil/kdenlive"));
+    }
+
+    #[test]
+    fn candidate_files_skip_vendored_and_non_source() {
+        let root = std::env::temp_dir().join(format!("synoid_scan_pick_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("vendor")).unwrap();
+        fs::write(root.join("src/main.c"), "int main() {}").unwrap();
+        fs::write(root.join("src/transition_wipe.c"), "void wipe() {}").unwrap();
+        fs::write(root.join("vendor/blend.c"), "void blend() {}").unwrap();
+        fs::write(root.join("build.sh"), "echo hostile").unwrap();
+
+        let names: Vec<String> = pick_candidate_files(&root, 5)
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["transition_wipe.c", "main.c"]);
+        let _ = fs::remove_dir_all(&root);
     }
 }
@@ -59,11 +59,11 @@ impl UrlReader {
         info!("[SENSES] Detected Video URL. Initiating Visual Analysis...");
 
         // 1. Download metadata via yt-dlp (requires local install)
-        use tokio::process::Command;
+        use crate::agent::engines::proc::{self, ProcClass};
         use crate::agent::engines::process_utils::CommandExt;
         let python_cmd = crate::agent::tools::source_tools::get_python_command().await;
         let browser = crate::agent::tools::source_tools::detect_browser();
-        let mut cmd = Command::new(&python_cmd);
+        let mut cmd = proc::command(&python_cmd, ProcClass::Download);
         cmd.stealth();
         if !python_cmd.ends_with("yt-dlp") {
             cmd.args(["-m", "yt_dlp"]);
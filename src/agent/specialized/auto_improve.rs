@@ -22,8 +22,9 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{info, warn};
 
 use crate::agent::specialized::smart_editor::{
-    detect_scenes, score_scenes, EditDensity, EditIntent, EditingStrategy,
+    score_scenes, EditDensity, EditIntent, EditingStrategy,
 };
+use crate::agent::tools::scan_cache;
 use crate::agent::core_systems::neuroplasticity::Neuroplasticity;
 
 // ─── AI Strategy Advisor ──────────────────────────────────────────────────────
@@ -36,8 +37,7 @@ pub struct LlmStrategyAdvisor {
 
 impl LlmStrategyAdvisor {
     pub fn new() -> Self {
-        let api_url = std::env::var("OLLAMA_API_URL")
-            .unwrap_or_else(|_| "http://localhost:11434".to_string());
+        let api_url = crate::config::current().api_url;
         // Use a reasoning model for strategy generation
         Self {
             agent: SynoidAgent::new(&api_url, "llama-3.3-70b-versatile"), // Groq default if available
@@ -119,31 +119,23 @@ Example:
 
         match self.agent.reason(&prompt).await {
             Ok(response) => {
-                let extracted = if let Some(mat) = regex::Regex::new(r"(?s)\[.*\]")
-                    .ok()
-                    .and_then(|re| re.find(response.trim()))
-                {
-                    mat.as_str()
-                } else {
-                    response.trim()
-                };
-
-                let clean_json = extracted
-                    .trim_start_matches("```json")
-                    .trim_start_matches("```")
-                    .trim_end_matches("```")
-                    .trim();
-
-                match serde_json::from_str::<Vec<EditingStrategy>>(clean_json) {
-                    Ok(variants) => {
+                let agent = &self.agent;
+                crate::agent::llm_json::parse_with_repair::<Vec<EditingStrategy>, _, _, _>(
+                    "strategy_variants",
+                    &prompt,
+                    &response,
+                    |variants| {
                         if variants.len() >= 4 {
-                            Ok(variants.into_iter().take(4).collect())
+                            Ok(())
                         } else {
-                            Err("LLM returned fewer than 4 variants".into())
+                            Err(format!("expected 4 EditingStrategy objects, got {}", variants.len()))
                         }
-                    }
-                    Err(e) => Err(format!("Failed to parse LLM variants: {}. Raw: {}", e, clean_json)),
-                }
+                    },
+                    |repair| async move { agent.reason(&repair).await },
+                )
+                .await
+                .map(|variants| variants.into_iter().take(4).collect())
+                .map_err(|e| format!("Failed to parse LLM variants: {}", e))
             }
             Err(e) => Err(format!("LLM reasoning failed: {}", e)),
         }
@@ -389,8 +381,8 @@ async fn run_dry_eval(
     iteration: u64,
     candidate_id: usize,
 ) -> Option<ExperimentResult> {
-    // Detect scenes (ffprobe pass — fast, no render)
-    let mut scenes = match detect_scenes(video, strategy.scene_threshold).await {
+    // Candidates differ in threshold, not input: one cached analysis serves them all
+    let mut scenes = match scan_cache::scenes(video, strategy.scene_threshold).await {
         Ok(s) => s,
         Err(e) => {
             warn!("[IMPROVE] Scene detection failed for candidate {}: {}", candidate_id, e);
@@ -410,7 +402,7 @@ async fn run_dry_eval(
         keep_speech: true,
         ruthless: false,
         density: EditDensity::Balanced,
-        custom_keywords: vec![],
+        keywords: vec![],
         target_duration: None,
         censor_profanity: false,
         profanity_replacement: None,
@@ -418,6 +410,16 @@ async fn run_dry_eval(
         use_remotion: false,
         remotion_template: None,
         enable_subtitles: false,
+        edge_padding_secs: 0.3,
+        audio_reactive: None,
+        duck_music: None,
+        exclude_ranges: Vec::new(),
+        exclude_phrases: Vec::new(),
+        intro: None,
+        outro: None,
+        intro_crossfade: None,
+        keep_audio: Vec::new(),
+        cut_audio: Vec::new(),
     };
 
     let total_duration = scenes.last().map(|s| s.end_time).unwrap_or(0.0);
@@ -458,7 +460,7 @@ pub struct ImproveLog {
 
 impl ImproveLog {
     fn log_path() -> PathBuf {
-        let suffix = std::env::var("SYNOID_INSTANCE_ID").unwrap_or_default();
+        let suffix = crate::config::current().instance_id.unwrap_or_default();
         PathBuf::from(format!("cortex_cache{}", suffix)).join("improve_log.json")
     }
 
@@ -609,7 +611,7 @@ pub struct AutoImprove {
 
 impl AutoImprove {
     pub fn new() -> Self {
-        let suffix = std::env::var("SYNOID_INSTANCE_ID").unwrap_or_default();
+        let suffix = crate::config::current().instance_id.unwrap_or_default();
         Self {
             download_dir: crate::agent::video_style_learner::get_download_dir(),
             candidates_per_iter: 4,
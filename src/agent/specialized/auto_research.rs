@@ -119,17 +119,15 @@ pub struct AutoResearchPipeline {
 
 impl AutoResearchPipeline {
     pub fn new() -> Self {
-        let ollama_url = std::env::var("SYNOID_API_URL")
-            .unwrap_or_else(|_| "http://localhost:11434".to_string());
+        let config = crate::config::current();
         let groq_model = std::env::var("GROQ_REASONING_MODEL")
             .unwrap_or_else(|_| "llama-3.3-70b-versatile".to_string());
 
         Self {
             client: crate::net::build_client(std::time::Duration::from_secs(30)),
             api_key_semantic: std::env::var("SEMANTIC_SCHOLAR_API_KEY").ok(),
-            ollama_url,
-            ollama_model: std::env::var("SYNOID_MODEL")
-                .unwrap_or_else(|_| "llama3.2:latest".to_string()),
+            ollama_url: config.api_url,
+            ollama_model: config.model,
             groq_key: std::env::var("GROQ_API_KEY").ok(),
             groq_model,
         }
@@ -5,9 +5,9 @@
 // Combines instruction-based editing with reference image guidance
 
 use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
-use tokio::process::Command;
 use tracing::{info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -312,7 +312,7 @@ impl ReferenceEditor {
         let style_filter =
             self.build_style_filter(&reference.description, config.temporal_consistency);
 
-        let status = Command::new("ffmpeg")
+        let status = proc::ffmpeg(ProcClass::Render)
             .stealth()
             .args(["-i"])
             .arg(input)
@@ -388,7 +388,7 @@ impl ReferenceEditor {
             }
         );
 
-        let status = Command::new("ffmpeg")
+        let status = proc::ffmpeg(ProcClass::Render)
             .stealth()
             .args(["-i"])
             .arg(input)
@@ -490,7 +490,7 @@ impl ReferenceEditor {
             blend_strength
         );
 
-        let status = Command::new("ffmpeg")
+        let status = proc::ffmpeg(ProcClass::Render)
             .stealth()
             .args(["-i"])
             .arg(video1)
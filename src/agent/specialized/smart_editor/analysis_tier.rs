@@ -0,0 +1,152 @@
+// SYNOID Smart Editor - Analysis Tiers
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Scoring leans on the transcript: it keeps scenes with speech, cuts the
+// ones without, and splits scenes at pauses. On a fresh install with no
+// Whisper model (and no network to fetch one) there is no transcript, and
+// the edit used to carry on scoring blind. Without one, ffmpeg's
+// silencedetect finds where the audio is loud enough to matter, and those
+// spans stand in for speech during scene selection, so "remove silence"
+// still cuts the dead air. Subtitles, censorship and keyword matching get
+// nothing from the stand-in. The tier used is recorded in the edit plan and
+// the job's summary so a weaker edit can be explained.
+
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::production_tools::safe_arg_path;
+use crate::agent::tools::transcription::TranscriptSegment;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Level below which audio counts as silence for the stand-in.
+const SILENCE_NOISE_DB: f64 = -35.0;
+/// Shortest quiet stretch that counts as a pause.
+const SILENCE_MIN_SECS: f64 = 0.5;
+
+/// What the scene selection of an edit had to go on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalysisTier {
+    /// A speech transcript: full semantic editing.
+    Transcript,
+    /// No transcript; loud stretches of audio stood in for speech.
+    AudioEnergy,
+    /// Neither a transcript nor usable audio; scene changes only.
+    ScenesOnly,
+}
+
+impl AnalysisTier {
+    pub fn describe(self) -> &'static str {
+        match self {
+            AnalysisTier::Transcript => "transcript",
+            AnalysisTier::AudioEnergy => "audio energy only (no transcript)",
+            AnalysisTier::ScenesOnly => "scene changes only (no transcript or audio)",
+        }
+    }
+}
+
+/// Stretches of `input`'s audio louder than SILENCE_NOISE_DB, from ffmpeg's
+/// silencedetect.
+pub async fn sound_spans(
+    input: &Path,
+    duration: f64,
+) -> Result<Vec<(f64, f64)>, Box<dyn std::error::Error + Send + Sync>> {
+    let output = proc::ffmpeg(ProcClass::Thumbnail)
+        .stealth()
+        .args(["-hide_banner", "-nostats", "-nostdin", "-i"])
+        .arg(safe_arg_path(input))
+        .args(["-vn", "-af"])
+        .arg(format!(
+            "silencedetect=noise={}dB:d={}",
+            SILENCE_NOISE_DB, SILENCE_MIN_SECS
+        ))
+        .args(["-f", "null", "-"])
+        .output()
+        .await?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() || !stderr.contains("Audio:") {
+        return Err(format!("{:?} has no audio to measure", input).into());
+    }
+    Ok(sound_from_silences(&stderr, duration))
+}
+
+/// The gaps between the silences silencedetect reported in `stderr`, over
+/// `0..duration`. A silence still open at the end runs to `duration`.
+pub fn sound_from_silences(stderr: &str, duration: f64) -> Vec<(f64, f64)> {
+    let value = |line: &str, key: &str| -> Option<f64> {
+        let rest = &line[line.find(key)? + key.len()..];
+        rest.split(|c: char| c.is_whitespace() || c == '|')
+            .next()?
+            .parse()
+            .ok()
+    };
+    let mut silences = Vec::new();
+    let mut open = None;
+    for line in stderr.lines() {
+        if let Some(start) = value(line, "silence_start: ") {
+            open = Some(start.max(0.0));
+        } else if let Some(end) = value(line, "silence_end: ") {
+            silences.push((open.take().unwrap_or(0.0), end));
+        }
+    }
+    if let Some(start) = open {
+        silences.push((start, duration));
+    }
+
+    let mut spans = Vec::new();
+    let mut cursor = 0.0;
+    for (start, end) in silences {
+        if start > cursor {
+            spans.push((cursor, start.min(duration)));
+        }
+        cursor = cursor.max(end);
+    }
+    if duration > cursor {
+        spans.push((cursor, duration));
+    }
+    spans.retain(|(start, end)| end > start);
+    spans
+}
+
+/// Textless segments over `spans`, for scene selection to treat as speech.
+pub fn stand_in_transcript(spans: &[(f64, f64)]) -> Vec<TranscriptSegment> {
+    spans
+        .iter()
+        .map(|&(start, end)| TranscriptSegment {
+            start,
+            end,
+            text: String::new(),
+            words: Vec::new(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sound_is_what_lies_between_the_silences() {
+        let stderr = "\
+  Stream #0:1: Audio: aac, 48000 Hz, stereo
+[silencedetect @ 0x5581] silence_start: 0
+[silencedetect @ 0x5581] silence_end: 2.5 | silence_duration: 2.5
+[silencedetect @ 0x5581] silence_start: 10.25
+[silencedetect @ 0x5581] silence_end: 14 | silence_duration: 3.75
+[silencedetect @ 0x5581] silence_start: 28.1
+";
+        assert_eq!(
+            sound_from_silences(stderr, 30.0),
+            vec![(2.5, 10.25), (14.0, 28.1)]
+        );
+        assert_eq!(sound_from_silences("", 12.0), vec![(0.0, 12.0)]);
+
+        let stand_in = stand_in_transcript(&[(2.5, 10.25)]);
+        assert_eq!((stand_in[0].start, stand_in[0].end), (2.5, 10.25));
+        assert!(stand_in[0].text.is_empty());
+        assert_eq!(
+            serde_json::to_string(&AnalysisTier::AudioEnergy).unwrap(),
+            "\"audio_energy\""
+        );
+    }
+}
@@ -0,0 +1,299 @@
+// SYNOID Smart Editor - Intro/Outro Bookends
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// A branded intro and outro card attached around the finished edit. Both are
+// probed before the edit starts, so a missing or unreadable file fails the
+// run before anything is rendered. Afterwards they go through the timeline
+// renderer with the edit, which conforms them to the edit's resolution and
+// frame rate and plays the three back to back. The intro's sound can fade out
+// as the first scene's fades in.
+
+use super::types::EditIntent;
+use crate::agent::tools::transcription;
+use crate::agent::video_processing::timeline_render::{self, Canvas, ClipSource, TimelineClip};
+use crate::agent::video_processing::vector_engine;
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// How long a still-image intro or outro card stays on screen.
+pub const CARD_SECS: f64 = 4.0;
+
+/// Clips and their sources, as `timeline_render::build_plan` takes them.
+type Timeline = (Vec<TimelineClip>, HashMap<String, ClipSource>);
+
+const IMAGE_EXTENSIONS: [&str; 4] = ["png", "jpg", "jpeg", "webp"];
+
+/// One file to play before or after the edit.
+#[derive(Debug, Clone)]
+pub struct Bookend {
+    pub path: PathBuf,
+    pub duration: f64,
+    pub has_audio: bool,
+    pub still: bool,
+}
+
+impl Bookend {
+    /// Probe `path`; `role` ("intro", "outro") names it in errors.
+    pub async fn probe(role: &str, path: &Path) -> Result<Self, String> {
+        if !path.is_file() {
+            return Err(format!("{} {} does not exist", role, path.display()));
+        }
+        let geometry = vector_engine::probe_geometry(path)
+            .await
+            .map_err(|e| format!("Cannot probe {} {}: {}", role, path.display(), e))?;
+        if geometry.width == 0 || geometry.height == 0 {
+            return Err(format!(
+                "{} {} has no readable picture",
+                role,
+                path.display()
+            ));
+        }
+        let still = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.as_str()));
+        let duration = if still { CARD_SECS } else { geometry.duration };
+        if !(duration.is_finite() && duration > 0.0) {
+            return Err(format!("{} {} has no duration", role, path.display()));
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            duration,
+            has_audio: !still && timeline_render::probe_has_audio(path).await,
+            still,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bookends {
+    pub intro: Option<Bookend>,
+    pub outro: Option<Bookend>,
+    /// Seconds of sound fade between the intro and the first scene.
+    pub crossfade: f64,
+}
+
+impl Bookends {
+    /// The intent's intro and outro, else the configured ones, probed.
+    /// `None` when neither is set.
+    pub async fn resolve(intent: &EditIntent) -> Result<Option<Self>, String> {
+        let config = crate::config::current();
+        let intro = intent.intro.clone().or(config.intro.map(PathBuf::from));
+        let outro = intent.outro.clone().or(config.outro.map(PathBuf::from));
+        let crossfade = intent.intro_crossfade.unwrap_or(config.intro_crossfade);
+        if intro.is_none() && outro.is_none() {
+            return Ok(None);
+        }
+
+        let intro = match intro {
+            Some(path) => Some(Bookend::probe("Intro", &path).await?),
+            None => None,
+        };
+        let outro = match outro {
+            Some(path) => Some(Bookend::probe("Outro", &path).await?),
+            None => None,
+        };
+        let bookends = Self {
+            intro,
+            outro,
+            crossfade,
+        };
+        bookends.check_crossfade()?;
+        Ok(Some(bookends))
+    }
+
+    fn check_crossfade(&self) -> Result<(), String> {
+        if !(self.crossfade.is_finite() && self.crossfade >= 0.0) {
+            return Err(format!(
+                "Intro crossfade {} must be non-negative seconds",
+                self.crossfade
+            ));
+        }
+        match &self.intro {
+            Some(intro) if self.crossfade > intro.duration => Err(format!(
+                "Intro crossfade {:.1}s is longer than the {:.1}s intro",
+                self.crossfade, intro.duration
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Seconds the bookends add to the edit.
+    pub fn duration(&self) -> f64 {
+        self.intro
+            .iter()
+            .chain(&self.outro)
+            .map(|b| b.duration)
+            .sum()
+    }
+
+    /// Where the edit starts in the finished video.
+    pub fn offset(&self) -> f64 {
+        self.intro.as_ref().map_or(0.0, |b| b.duration)
+    }
+
+    /// The edit's share of a duration target, so the finished video with its
+    /// bookends still lands inside `target`.
+    pub fn fit_target(&self, target: Option<(f64, f64)>) -> Result<Option<(f64, f64)>, String> {
+        let Some((min, max)) = target else {
+            return Ok(None);
+        };
+        let added = self.duration();
+        if max - added < 1.0 {
+            return Err(format!(
+                "The {:.1}s intro/outro leaves no room for the edit in a {:.0}s target",
+                added, max
+            ));
+        }
+        Ok(Some(((min - added).max(0.0), max - added)))
+    }
+
+    /// The bookends and the edit (`main`) as a one-track timeline.
+    fn timeline(&self, main: &Bookend) -> Result<Timeline, String> {
+        let parts = [
+            ("intro", self.intro.as_ref()),
+            ("edit", Some(main)),
+            ("outro", self.outro.as_ref()),
+        ];
+        let mut clips = Vec::new();
+        let mut sources = HashMap::new();
+        let mut start = 0.0;
+        for (id, part) in parts {
+            let Some(part) = part else { continue };
+            let fade_out = if id == "intro" { self.crossfade } else { 0.0 };
+            let fade_in = if id == "edit" && self.intro.is_some() {
+                self.crossfade.min(part.duration)
+            } else {
+                0.0
+            };
+            clips.push(json!({
+                "id": id,
+                "assetId": id,
+                "trackId": "V1",
+                "start": start,
+                "outPoint": part.duration,
+                "audioFadeIn": fade_in,
+                "audioFadeOut": fade_out,
+            }));
+            sources.insert(
+                id.to_string(),
+                ClipSource {
+                    path: part.path.clone(),
+                    kind: if part.still { "image" } else { "video" }.to_string(),
+                    has_audio: part.has_audio,
+                },
+            );
+            start += part.duration;
+        }
+        Ok((timeline_render::parse_clips(&json!(clips))?, sources))
+    }
+
+    /// Put the bookends around the rendered edit at `edit`, in place.
+    pub async fn attach(&self, edit: &Path, work_dir: &Path) -> Result<(), String> {
+        let geometry = vector_engine::probe_geometry(edit)
+            .await
+            .map_err(|e| format!("Cannot probe the edit: {}", e))?;
+        let main = Bookend {
+            path: edit.to_path_buf(),
+            duration: geometry.duration,
+            has_audio: timeline_render::probe_has_audio(edit).await,
+            still: false,
+        };
+        let canvas = Canvas {
+            width: geometry.width as u32,
+            height: geometry.height as u32,
+            fps: geometry.fps,
+        };
+        let (clips, sources) = self.timeline(&main)?;
+        let plan = timeline_render::build_plan(&clips, &sources, canvas)?;
+
+        let joined = edit.with_extension("bookends.mp4");
+        let result = async {
+            timeline_render::render(&plan, &joined, work_dir, false)
+                .await
+                .map_err(|e| e.to_string())?;
+            // Copy rather than rename: WSL mounts reject cross-device renames
+            std::fs::copy(&joined, edit).map_err(|e| format!("Cannot replace the edit: {}", e))?;
+            Ok(())
+        }
+        .await;
+        let _ = std::fs::remove_file(&joined);
+        result
+    }
+}
+
+/// `srt` with every cue moved `offset` seconds later, for the sidecar of an
+/// edit that now starts after its intro. Unparseable input is kept as is.
+pub fn shift_srt(srt: &str, offset: f64) -> String {
+    match transcription::parse_srt(srt) {
+        Ok(mut cues) => {
+            for cue in &mut cues {
+                cue.start += offset;
+                cue.end += offset;
+            }
+            transcription::generate_srt(&cues)
+        }
+        Err(_) => srt.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(name: &str, duration: f64) -> Bookend {
+        Bookend {
+            path: PathBuf::from(name),
+            duration,
+            has_audio: !name.ends_with(".png"),
+            still: name.ends_with(".png"),
+        }
+    }
+
+    #[test]
+    fn bookends_shrink_the_target_and_play_around_the_edit() {
+        let bookends = Bookends {
+            intro: Some(part("intro.mp4", 3.0)),
+            outro: Some(part("outro.png", CARD_SECS)),
+            crossfade: 0.5,
+        };
+        // A "60 second short" still totals 60 seconds
+        assert_eq!(
+            bookends.fit_target(Some((55.0, 60.0))).unwrap(),
+            Some((48.0, 53.0))
+        );
+        assert_eq!(bookends.fit_target(None).unwrap(), None);
+        assert!(bookends.fit_target(Some((5.0, 7.0))).is_err());
+        assert_eq!(bookends.offset(), 3.0);
+
+        let (clips, sources) = bookends.timeline(&part("edit.mp4", 50.0)).unwrap();
+        let starts: Vec<f64> = clips.iter().map(|c| c.start).collect();
+        assert_eq!(starts, vec![0.0, 3.0, 53.0]);
+        assert_eq!(sources["outro"].kind, "image");
+
+        let plan = timeline_render::build_plan(&clips, &sources, Canvas::default()).unwrap();
+        let g = &plan.filtergraph;
+        assert!(
+            g.contains("afade=t=out:st=2.500:d=0.500,adelay=0:all=1[a0]"),
+            "{}",
+            g
+        );
+        assert!(
+            g.contains("afade=t=in:st=0:d=0.500,adelay=3000:all=1[a1]"),
+            "{}",
+            g
+        );
+        assert!(g.contains("[v0][v1][v2]concat=n=3"), "{}", g);
+        assert_eq!(plan.duration, 57.0);
+
+        let srt = "1\n00:00:01,000 --> 00:00:02,500\nHello\n\n";
+        assert!(shift_srt(srt, 3.0).contains("00:00:04,000 --> 00:00:05,500"));
+
+        let too_long = Bookends {
+            crossfade: 4.0,
+            ..bookends
+        };
+        assert!(too_long.check_crossfade().is_err());
+    }
+}
@@ -0,0 +1,278 @@
+// SYNOID Smart Editor - Source Chapters
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Chapter markers carried by the source (YouTube downloads usually have
+// them) are lost when the kept scenes are re-encoded. They are read with
+// ffprobe, moved through the edit plan's offset table and written back into
+// the finished output with an FFmetadata pass. A chapter whose whole span
+// was cut is dropped; one that starts inside a cut begins at its first kept
+// moment instead.
+
+use super::edit_plan::EditPlan;
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::engines::process_utils::CommandExt;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// What happens to the source's chapters. `chapters` / SYNOID_CHAPTERS.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChapterMode {
+    /// Remap the source's chapters onto the edit.
+    #[default]
+    Preserve,
+    /// Leave the output without chapters.
+    Off,
+}
+
+impl std::str::FromStr for ChapterMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "preserve" => Ok(Self::Preserve),
+            "off" | "none" => Ok(Self::Off),
+            other => Err(format!(
+                "Unknown chapter mode '{}' (expected preserve or off)",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chapter {
+    pub start: f64,
+    pub end: f64,
+    pub title: String,
+}
+
+#[derive(Deserialize)]
+struct ProbeChapters {
+    #[serde(default)]
+    chapters: Vec<ProbeChapter>,
+}
+
+#[derive(Deserialize)]
+struct ProbeChapter {
+    start_time: String,
+    end_time: String,
+    #[serde(default)]
+    tags: std::collections::HashMap<String, String>,
+}
+
+/// Chapters from ffprobe's `-show_chapters -of json` output, in order.
+pub fn parse_probe(json: &str) -> Result<Vec<Chapter>, String> {
+    let probe: ProbeChapters =
+        serde_json::from_str(json).map_err(|e| format!("Bad ffprobe chapter output: {}", e))?;
+    let mut chapters: Vec<Chapter> = probe
+        .chapters
+        .into_iter()
+        .filter_map(|c| {
+            let start = c.start_time.parse::<f64>().ok()?;
+            let end = c.end_time.parse::<f64>().ok()?;
+            (end > start).then(|| Chapter {
+                start,
+                end,
+                title: c.tags.get("title").cloned().unwrap_or_default(),
+            })
+        })
+        .collect();
+    chapters.sort_by(|a, b| a.start.total_cmp(&b.start));
+    Ok(chapters)
+}
+
+/// The chapters stored in `path`; empty when it has none.
+pub async fn read(path: &Path) -> Result<Vec<Chapter>, String> {
+    let out = proc::ffprobe()
+        .stealth()
+        .args(["-v", "error", "-show_chapters", "-of", "json"])
+        .arg(path)
+        .output()
+        .await
+        .map_err(|e| format!("ffprobe failed: {}", e))?;
+    if !out.status.success() {
+        return Err(format!(
+            "ffprobe could not read chapters: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        ));
+    }
+    parse_probe(&String::from_utf8_lossy(&out.stdout))
+}
+
+/// `chapters` (source times) placed on the edit's timeline. Each starts at
+/// the first moment of its span that survived the cut; chapters with nothing
+/// kept are dropped. A chapter runs until the next one, the last until the
+/// end of the edit.
+pub fn remap(chapters: &[Chapter], plan: &EditPlan) -> Vec<Chapter> {
+    let mut remapped: Vec<Chapter> = Vec::new();
+    for chapter in chapters {
+        let start = plan.segments.iter().find_map(|s| {
+            let from = chapter.start.max(s.source_start);
+            (from < s.source_end.min(chapter.end)).then_some(s.output_start + from - s.source_start)
+        });
+        let Some(start) = start else { continue };
+        // Crossfades can pull a later chapter onto an earlier one
+        if remapped.last().is_some_and(|prev| start <= prev.start) {
+            continue;
+        }
+        remapped.push(Chapter {
+            start,
+            end: start,
+            title: chapter.title.clone(),
+        });
+    }
+
+    let edit_end = plan.output_duration();
+    let starts: Vec<f64> = remapped.iter().skip(1).map(|c| c.start).collect();
+    for (chapter, end) in remapped
+        .iter_mut()
+        .zip(starts.into_iter().chain([edit_end]))
+    {
+        chapter.end = end.max(chapter.start);
+    }
+    remapped.retain(|c| c.end > c.start);
+    remapped
+}
+
+/// FFmetadata's reserved characters, backslash-escaped.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '=' | ';' | '#' | '\\' | '\n') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// `chapters` as an FFmetadata file, in milliseconds.
+pub fn ffmetadata(chapters: &[Chapter]) -> String {
+    let mut meta = String::from(";FFMETADATA1\n");
+    for chapter in chapters {
+        meta.push_str(&format!(
+            "[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+            (chapter.start * 1000.0).round() as i64,
+            (chapter.end * 1000.0).round() as i64,
+            escape(&chapter.title)
+        ));
+    }
+    meta
+}
+
+/// Write `chapters` into the video at `output`, in place. Streams are copied,
+/// not re-encoded.
+pub async fn write(output: &Path, chapters: &[Chapter], work_dir: &Path) -> Result<(), String> {
+    let meta_path = work_dir.join("chapters.ffmeta");
+    std::fs::write(&meta_path, ffmetadata(chapters))
+        .map_err(|e| format!("Cannot write chapter metadata: {}", e))?;
+    let tagged = output.with_extension("chapters.mp4");
+    let result = async {
+        let status = proc::ffmpeg(ProcClass::Render)
+            .stealth()
+            .args(["-y", "-hide_banner", "-loglevel", "error", "-nostdin", "-i"])
+            .arg(output)
+            .arg("-i")
+            .arg(&meta_path)
+            .args([
+                "-map",
+                "0",
+                "-map_metadata",
+                "0",
+                "-map_chapters",
+                "1",
+                "-c",
+                "copy",
+            ])
+            .arg(&tagged)
+            .status()
+            .await
+            .map_err(|e| format!("ffmpeg failed: {}", e))?;
+        if !status.success() {
+            return Err("ffmpeg could not write the chapters".to_string());
+        }
+        // Copy rather than rename: WSL mounts reject cross-device renames
+        std::fs::copy(&tagged, output).map_err(|e| format!("Cannot replace the edit: {}", e))?;
+        Ok(())
+    }
+    .await;
+    let _ = std::fs::remove_file(&tagged);
+    let _ = std::fs::remove_file(&meta_path);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::specialized::smart_editor::types::Scene;
+    use std::path::PathBuf;
+
+    fn scene(start: f64, end: f64) -> Scene {
+        Scene {
+            start_time: start,
+            end_time: end,
+            duration: end - start,
+            score: 0.5,
+            vision_tags: Vec::new(),
+            breakdown: None,
+            audio_mix: None,
+        }
+    }
+
+    fn chapter(start: f64, end: f64, title: &str) -> Chapter {
+        Chapter {
+            start,
+            end,
+            title: title.to_string(),
+        }
+    }
+
+    #[test]
+    fn chapters_follow_their_content_through_the_cut() {
+        let probe = r#"{"chapters": [
+            {"id": 1, "start_time": "30.000000", "end_time": "60.000000", "tags": {"title": "Setup"}},
+            {"id": 0, "start_time": "0.000000", "end_time": "30.000000", "tags": {"title": "Intro"}},
+            {"id": 2, "start_time": "60.000000", "end_time": "70.000000", "tags": {"title": "Sponsor"}},
+            {"id": 3, "start_time": "70.000000", "end_time": "120.000000", "tags": {"title": "Build; part 1"}}
+        ]}"#;
+        let chapters = parse_probe(probe).unwrap();
+        assert_eq!(chapters[0].title, "Intro");
+
+        // Kept: 5-20, 35-58, 80-110; the sponsor chapter is cut entirely and
+        // "Build" starts inside a cut, so it snaps to 80.
+        let kept = [scene(5.0, 20.0), scene(35.0, 58.0), scene(80.0, 110.0)];
+        let plan = EditPlan::from_kept_scenes(
+            &PathBuf::from("in.mp4"),
+            &PathBuf::from("out.mp4"),
+            &kept,
+            &[15.0, 23.0, 30.0],
+            0.25,
+        );
+        let remapped = remap(&chapters, &plan);
+        let titles: Vec<&str> = remapped.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["Intro", "Setup", "Build; part 1"]);
+
+        // Each remapped start plays the content its chapter opened on (or the
+        // first kept moment after it)
+        for (out, source_start) in remapped.iter().zip([5.0, 35.0, 80.0]) {
+            let plays = plan.source_time(out.start).unwrap();
+            assert!(
+                (plays - source_start).abs() <= 0.5,
+                "{} plays {}",
+                out.title,
+                plays
+            );
+        }
+        assert_eq!(remapped[0].start, 0.0);
+        assert_eq!(remapped[0].end, remapped[1].start);
+        assert_eq!(remapped[2].end, plan.output_duration());
+
+        let meta = ffmetadata(&remapped);
+        assert!(meta.starts_with(";FFMETADATA1\n[CHAPTER]\nTIMEBASE=1/1000\nSTART=0\n"));
+        assert!(meta.contains("title=Build\\; part 1\n"), "{}", meta);
+
+        assert_eq!(remap(&[chapter(60.0, 70.0, "Sponsor")], &plan), Vec::new());
+        assert_eq!("off".parse::<ChapterMode>(), Ok(ChapterMode::Off));
+    }
+}
@@ -0,0 +1,282 @@
+// SYNOID Smart Editor - Draft Renders
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// A draft is the same edit rendered small and fast (480p, ultrafast, 96k
+// audio) so the scene selection can be checked in minutes instead of waiting
+// on the full encode. The draft leaves its cut list next to the input; the
+// next final render of the same input and intent picks it up instead of
+// re-analysing, so the final cuts land on exactly the frames the draft showed.
+
+use super::exclusions::Exclusion;
+use super::types::{EditingStrategy, Scene, SceneSelection};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Draft frame height; width follows the source aspect ratio.
+pub const DRAFT_HEIGHT: u32 = 480;
+
+pub const DRAFT_AUDIO_BITRATE: &str = "96k";
+
+/// With `fast_stretches`, kept segments at least this long play at 2×.
+pub const DRAFT_FAST_MIN_SECS: f64 = 8.0;
+
+/// Video encoder arguments for every draft encode.
+pub const DRAFT_VIDEO_ARGS: [&str; 8] = [
+    "-c:v", "libx264", "-preset", "ultrafast", "-crf", "28", "-pix_fmt", "yuv420p",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderMode {
+    #[default]
+    Final,
+    /// Low-res preview. `fast_stretches` plays long uncut segments at 2×;
+    /// cut points still fall on the same source frames, but output times no
+    /// longer match the final render.
+    Draft { fast_stretches: bool },
+    /// Scene analysis only: save the cuts for the next final render and an
+    /// edit plan with the scores, then stop. Used by `review`.
+    Analyze,
+}
+
+impl RenderMode {
+    /// The configured `draft` setting (`--draft` / SYNOID_DRAFT) selects a
+    /// draft render, and `draft_fast` speeds up its long stretches.
+    pub fn from_config() -> Self {
+        let config = crate::config::current();
+        if config.draft {
+            RenderMode::Draft {
+                fast_stretches: config.draft_fast,
+            }
+        } else {
+            RenderMode::Final
+        }
+    }
+
+    pub fn is_draft(self) -> bool {
+        matches!(self, RenderMode::Draft { .. })
+    }
+
+    fn speeds_up(self, segment_secs: f64) -> bool {
+        matches!(self, RenderMode::Draft { fast_stretches: true })
+            && segment_secs >= DRAFT_FAST_MIN_SECS
+    }
+
+    /// `-vf` for one draft segment: downscale, plus 2× for long stretches.
+    pub fn segment_video_filter(self, segment_secs: f64) -> String {
+        let scale = format!("scale=-2:{}", DRAFT_HEIGHT);
+        if self.speeds_up(segment_secs) {
+            format!("{},setpts=0.5*PTS", scale)
+        } else {
+            scale
+        }
+    }
+
+    /// `-af` for one draft segment, when it needs one.
+    pub fn segment_audio_filter(self, segment_secs: f64) -> Option<&'static str> {
+        self.speeds_up(segment_secs).then_some("atempo=2.0")
+    }
+}
+
+/// Everything besides the source that decides which cuts an edit makes.
+/// Cuts are only reused under the same key.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct CutsKey {
+    pub intent: String,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Hash of the `EditingStrategy` (learned pattern applied) that scored
+    /// the scenes.
+    #[serde(default)]
+    pub strategy: String,
+    /// External scorer that rescored the scenes, if any.
+    #[serde(default)]
+    pub scorer: Option<String>,
+}
+
+impl CutsKey {
+    pub fn new(
+        intent: &str,
+        seed: Option<u64>,
+        strategy: &EditingStrategy,
+        scorer: Option<&str>,
+    ) -> Self {
+        let json = serde_json::to_string(strategy).unwrap_or_default();
+        Self {
+            intent: intent.to_string(),
+            seed,
+            strategy: format!("{:x}", Sha256::digest(json.as_bytes())),
+            scorer: scorer.map(str::to_string),
+        }
+    }
+}
+
+/// The segments a draft kept, stored so the final render can skip analysis.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DraftCuts {
+    #[serde(flatten)]
+    pub key: CutsKey,
+    /// Source size and mtime when the cuts were made; any change invalidates them.
+    pub source_len: u64,
+    pub source_modified: u64,
+    pub kept: Vec<(f64, f64)>,
+    pub cut_points: Vec<(f64, f64)>,
+    pub removed: usize,
+    pub scene_count: usize,
+    #[serde(default)]
+    pub exclusions: Vec<Exclusion>,
+}
+
+fn source_stamp(input: &Path) -> Option<(u64, u64)> {
+    let meta = std::fs::metadata(input).ok()?;
+    let modified = meta
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_secs();
+    Some((meta.len(), modified))
+}
+
+impl DraftCuts {
+    pub fn new(input: &Path, key: CutsKey, selection: &SceneSelection) -> Self {
+        let (source_len, source_modified) = source_stamp(input).unwrap_or_default();
+        Self {
+            key,
+            source_len,
+            source_modified,
+            kept: selection.kept.iter().map(|s| (s.start_time, s.end_time)).collect(),
+            cut_points: selection.cut_points.clone(),
+            removed: selection.removed,
+            scene_count: selection.scene_count,
+            exclusions: selection.exclusions.clone(),
+        }
+    }
+
+    /// `<dir>/synoid_<prefix>_draft_cuts.json`, beside the other per-input caches.
+    pub fn path_for(input_parent: &Path, job_prefix: &str) -> PathBuf {
+        input_parent.join(format!("synoid_{}_draft_cuts.json", job_prefix))
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, json)
+    }
+
+    pub fn load(path: &Path) -> Option<Self> {
+        serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+    }
+
+    /// Cuts at `path` made for this exact input and key, if any.
+    pub fn load_matching(path: &Path, input: &Path, key: &CutsKey) -> Option<Self> {
+        Self::load(path).filter(|cuts| cuts.matches(input, key))
+    }
+
+    /// True when these cuts were made under `key` over `input` as it is now.
+    pub fn matches(&self, input: &Path, key: &CutsKey) -> bool {
+        self.key == *key && self.source_unchanged(input)
+    }
+
+    /// True when `input` still has the size and mtime it had when the cuts
+    /// were made.
+    pub fn source_unchanged(&self, input: &Path) -> bool {
+        source_stamp(input) == Some((self.source_len, self.source_modified))
+    }
+
+    /// The stored cuts as a selection ready to render. Scores and vision
+    /// tags are not kept; rendering only needs the time ranges.
+    pub fn into_selection(self) -> SceneSelection {
+        let kept = self
+            .kept
+            .iter()
+            .map(|&(start, end)| Scene {
+                start_time: start,
+                end_time: end,
+                duration: end - start,
+                score: 1.0,
+                vision_tags: Vec::new(),
+                breakdown: None,
+                audio_mix: None,
+            })
+            .collect();
+        SceneSelection {
+            kept,
+            cut_points: self.cut_points,
+            removed: self.removed,
+            scene_count: self.scene_count,
+            exclusions: self.exclusions,
+            scoring: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_long_segments_speed_up_and_only_when_asked() {
+        let plain = RenderMode::Draft { fast_stretches: false };
+        let fast = RenderMode::Draft { fast_stretches: true };
+        assert_eq!(plain.segment_video_filter(30.0), "scale=-2:480");
+        assert_eq!(plain.segment_audio_filter(30.0), None);
+        assert_eq!(fast.segment_video_filter(3.0), "scale=-2:480");
+        assert_eq!(fast.segment_video_filter(30.0), "scale=-2:480,setpts=0.5*PTS");
+        assert_eq!(fast.segment_audio_filter(30.0), Some("atempo=2.0"));
+    }
+
+    #[test]
+    fn cuts_only_match_the_same_source_and_settings() {
+        let dir = std::env::temp_dir().join(format!("synoid_draft_cuts_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("clip.mp4");
+        std::fs::write(&input, b"not really a video").unwrap();
+        let selection = SceneSelection {
+            kept: vec![Scene {
+                start_time: 1.0,
+                end_time: 4.5,
+                duration: 3.5,
+                score: 0.7,
+                vision_tags: vec!["goal".to_string()],
+                breakdown: None,
+                audio_mix: None,
+            }],
+            cut_points: vec![(4.5, 9.0)],
+            removed: 9,
+            scene_count: 12,
+            exclusions: Vec::new(),
+            scoring: None,
+        };
+
+        let strategy = EditingStrategy::default();
+        let key = |intent: &str, seed, scorer| CutsKey::new(intent, seed, &strategy, scorer);
+        let path = DraftCuts::path_for(&dir, "abcd1234");
+        DraftCuts::new(&input, key("keep the goals", None, None), &selection)
+            .save(&path)
+            .unwrap();
+
+        let reused = DraftCuts::load_matching(&path, &input, &key("keep the goals", None, None))
+            .unwrap()
+            .into_selection();
+        assert_eq!(reused.kept[0].duration, 3.5);
+        assert_eq!(reused.cut_points, vec![(4.5, 9.0)]);
+        assert_eq!((reused.removed, reused.scene_count), (9, 12));
+        assert!(DraftCuts::load_matching(&path, &input, &key("keep the saves", None, None)).is_none());
+        assert!(DraftCuts::load_matching(&path, &input, &key("keep the goals", Some(7), None)).is_none());
+        let rescored = key("keep the goals", None, Some("python3 score.py"));
+        assert!(DraftCuts::load_matching(&path, &input, &rescored).is_none());
+        let retuned = EditingStrategy {
+            boring_penalty_threshold: strategy.boring_penalty_threshold + 5.0,
+            ..strategy.clone()
+        };
+        let retuned = CutsKey::new("keep the goals", None, &retuned, None);
+        assert!(DraftCuts::load_matching(&path, &input, &retuned).is_none());
+
+        std::fs::write(&input, b"a different, longer source file").unwrap();
+        assert!(DraftCuts::load_matching(&path, &input, &key("keep the goals", None, None)).is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
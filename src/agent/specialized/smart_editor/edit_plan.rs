@@ -0,0 +1,145 @@
+// SYNOID Smart Editor - Edit Plan Sidecar
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// The offset table of a finished smart edit, written next to the output as
+// `<name>.plan.json` so tools (the GUI's before/after split preview) can map
+// any output timestamp back to where it came from in the source.
+
+use super::analysis_tier::AnalysisTier;
+use super::exclusions::Exclusion;
+use super::explain::ScoringReport;
+use super::seed::EditDecisions;
+use super::types::Scene;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// One kept span: `[source_start, source_end)` plays from `output_start`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlanSegment {
+    pub source_start: f64,
+    pub source_end: f64,
+    pub output_start: f64,
+    /// Intent keywords found in this span.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+}
+
+impl PlanSegment {
+    pub fn duration(&self) -> f64 {
+        (self.source_end - self.source_start).max(0.0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditPlan {
+    pub source: PathBuf,
+    pub output: PathBuf,
+    /// Crossfade overlap between consecutive segments (0 for hard cuts).
+    #[serde(default)]
+    pub crossfade_secs: f64,
+    pub segments: Vec<PlanSegment>,
+    /// Spans cut because the intent named them, and whether each came from
+    /// an explicit range or a transcript match.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclusions: Vec<Exclusion>,
+    /// What a `--seed` run decided, so the same seed replays it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decisions: Option<EditDecisions>,
+    /// Every scene's score breakdown, for tuning (`synoid-core tune`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scoring: Option<ScoringReport>,
+    /// Intro and outro seconds around the edit, so editing this output
+    /// again leaves them out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bookends: Option<(f64, f64)>,
+    /// Whether scenes were chosen with a transcript, audio energy or
+    /// neither; `None` in plans from before it was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analysis_tier: Option<AnalysisTier>,
+}
+
+impl EditPlan {
+    /// Build the offset table for `kept_scenes`, using the same cursor
+    /// arithmetic as subtitle remapping so both agree on output time.
+    pub fn from_kept_scenes(
+        source: &Path,
+        output: &Path,
+        kept_scenes: &[Scene],
+        exact_durations: &[f64],
+        crossfade_secs: f64,
+    ) -> Self {
+        let mut segments = Vec::with_capacity(kept_scenes.len());
+        let mut cursor = 0.0_f64;
+        for (i, scene) in kept_scenes.iter().enumerate() {
+            segments.push(PlanSegment {
+                source_start: scene.start_time,
+                source_end: scene.end_time,
+                output_start: cursor,
+                keywords: Vec::new(),
+            });
+            cursor += exact_durations.get(i).copied().unwrap_or(scene.duration);
+            if i + 1 < kept_scenes.len() {
+                cursor -= crossfade_secs;
+            }
+        }
+        Self {
+            source: source.to_path_buf(),
+            output: output.to_path_buf(),
+            crossfade_secs,
+            segments,
+            exclusions: Vec::new(),
+            decisions: None,
+            scoring: None,
+            bookends: None,
+            analysis_tier: None,
+        }
+    }
+
+    /// Sidecar location for `output` (`clip.mp4` → `clip.plan.json`).
+    pub fn path_for(output: &Path) -> PathBuf {
+        output.with_extension("plan.json")
+    }
+
+    pub fn save(&self) -> std::io::Result<PathBuf> {
+        let path = Self::path_for(&self.output);
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(&path, json)?;
+        Ok(path)
+    }
+
+    /// Load the plan sitting next to `output`, if there is one.
+    pub fn load_for(output: &Path) -> Option<Self> {
+        let data = std::fs::read_to_string(Self::path_for(output)).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
+    /// Length of the rendered output according to the plan.
+    pub fn output_duration(&self) -> f64 {
+        self.segments
+            .last()
+            .map(|s| s.output_start + s.duration())
+            .unwrap_or(0.0)
+    }
+
+    /// Index of the segment playing at `output_time`. During a crossfade the
+    /// incoming segment wins.
+    pub fn segment_at(&self, output_time: f64) -> Option<usize> {
+        if self.segments.is_empty() {
+            return None;
+        }
+        let idx = self
+            .segments
+            .iter()
+            .rposition(|s| s.output_start <= output_time)
+            .unwrap_or(0);
+        Some(idx)
+    }
+
+    /// Map an output timestamp to the source timestamp it was cut from.
+    pub fn source_time(&self, output_time: f64) -> Option<f64> {
+        let seg = &self.segments[self.segment_at(output_time)?];
+        let offset = (output_time - seg.output_start).clamp(0.0, seg.duration());
+        Some(seg.source_start + offset)
+    }
+}
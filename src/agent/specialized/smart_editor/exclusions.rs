@@ -0,0 +1,359 @@
+// SYNOID Smart Editor - Explicit Exclusions
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// "Cut out 12:30 to 14:00 and the part where I say goodbye": timestamp ranges
+// and spoken references pulled out of the intent text. Both become hard cuts
+// applied before scoring, so no score can bring the footage back. Ranges are
+// taken literally; references are looked up in the transcript. Each resolved
+// exclusion remembers which of the two it came from, so the plan shows it.
+
+use super::keywords::{stem, stem_words};
+use crate::agent::tools::timecode;
+use crate::agent::tools::transcription::TranscriptSegment;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// A removal verb must be the closest verb before a range or reference for
+/// it to count; "keep the part where I win" is not an exclusion.
+const REMOVAL_VERBS: &str =
+    r"\b(cut|cuts|remove|drop|skip|delete|exclude|lose|trim|get rid of|take out|leave out)\b";
+const KEEP_VERBS: &str = r"\b(keep|include|show|save|leave in|highlight|focus on)\b";
+
+/// Words ignored when a reference has no exact match in the transcript.
+const FILLER_WORDS: [&str; 14] = [
+    "the", "a", "an", "my", "our", "it", "that", "this", "to", "of", "and", "on", "at", "in",
+];
+
+/// Where a resolved exclusion came from.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "from", rename_all = "snake_case")]
+pub enum ExclusionOrigin {
+    /// An explicit timestamp range in the intent.
+    Range,
+    /// A transcript segment matching a reference in the intent.
+    Transcript { phrase: String, said: String },
+}
+
+/// One span of source time cut regardless of scores.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Exclusion {
+    pub start: f64,
+    pub end: f64,
+    #[serde(flatten)]
+    pub origin: ExclusionOrigin,
+}
+
+impl Exclusion {
+    /// One line for logs and reports.
+    pub fn describe(&self) -> String {
+        format!(
+            "{}-{} ({})",
+            format_timestamp(self.start),
+            format_timestamp(self.end),
+            self.reason()
+        )
+    }
+
+    /// Why the span was cut, without its times.
+    pub fn reason(&self) -> String {
+        match &self.origin {
+            ExclusionOrigin::Range => "explicit range".to_string(),
+            ExclusionOrigin::Transcript { phrase, said } => {
+                format!("transcript match for \"{}\": \"{}\"", phrase, said.trim())
+            }
+        }
+    }
+}
+
+pub(super) fn format_timestamp(secs: f64) -> String {
+    let secs = secs.max(0.0);
+    let whole = secs as u64;
+    let frac = secs - whole as f64;
+    let (h, m, s) = (whole / 3600, whole / 60 % 60, whole % 60);
+    let tenths = if frac >= 0.05 { format!(".{}", (frac * 10.0).round() as u64 % 10) } else { String::new() };
+    if h > 0 {
+        format!("{}:{:02}:{:02}{}", h, m, s, tenths)
+    } else {
+        format!("{}:{:02}{}", m, s, tenths)
+    }
+}
+
+
+fn count_word(word: &str) -> Option<f64> {
+    if let Ok(n) = word.parse::<f64>() {
+        return Some(n);
+    }
+    let n = match word {
+        "a" | "an" | "one" => 1.0,
+        "two" => 2.0,
+        "three" => 3.0,
+        "four" => 4.0,
+        "five" => 5.0,
+        "six" => 6.0,
+        "seven" => 7.0,
+        "eight" => 8.0,
+        "nine" => 9.0,
+        "ten" => 10.0,
+        "fifteen" => 15.0,
+        "twenty" => 20.0,
+        "thirty" => 30.0,
+        "forty" => 40.0,
+        "forty-five" => 45.0,
+        "sixty" => 60.0,
+        "ninety" => 90.0,
+        _ => return None,
+    };
+    Some(n)
+}
+
+/// Whether the nearest verb before `at` in the same sentence removes.
+fn in_removal_clause(text: &str, at: usize) -> bool {
+    let sentence_start = text[..at].rfind(['.', '!', '?', ';', '\n']).map_or(0, |i| i + 1);
+    let clause = &text[sentence_start..at];
+    let last = |pattern: &str| {
+        Regex::new(pattern)
+            .ok()
+            .and_then(|re| re.find_iter(clause).last().map(|m| m.start()))
+    };
+    match (last(REMOVAL_VERBS), last(KEEP_VERBS)) {
+        (Some(cut), Some(keep)) => cut > keep,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
+
+/// Explicit ranges to cut from lowercase intent `text`, plus the text with
+/// them blanked out so "the first 2 minutes" is not also read as a target
+/// length. Ranges counted from the end ("the last 30 seconds") are stored as
+/// negative times: `(-30.0, 0.0)`.
+pub fn parse_exclude_ranges(text: &str) -> (Vec<(f64, f64)>, String) {
+    let ts = r"\d{1,2}(?::\d{1,2}){1,2}(?:\.\d+)?";
+    let absolute = Regex::new(&format!(
+        r"({ts})\s*(?:-|–|to|until|through|thru)\s*({ts})"
+    ))
+    .expect("valid range regex");
+    let relative = Regex::new(
+        r"\b(first|opening|last|final)\s+(?:(\d+(?:\.\d+)?|an?|one|two|three|four|five|six|seven|eight|nine|ten|fifteen|twenty|thirty|forty-five|forty|sixty|ninety)\s*)?(seconds?|secs?|minutes?|mins?)\b",
+    )
+    .expect("valid relative range regex");
+
+    let mut ranges = Vec::new();
+    let mut spans = Vec::new();
+    for caps in absolute.captures_iter(text) {
+        let whole = caps.get(0).expect("match");
+        if !in_removal_clause(text, whole.start()) {
+            continue;
+        }
+        if let (Ok(start), Ok(end)) = (timecode::parse(&caps[1]), timecode::parse(&caps[2])) {
+            if end > start {
+                ranges.push((start, end));
+                spans.push(whole.range());
+            }
+        }
+    }
+    for caps in relative.captures_iter(text) {
+        let whole = caps.get(0).expect("match");
+        if !in_removal_clause(text, whole.start()) {
+            continue;
+        }
+        let count = caps.get(2).map_or(Some(1.0), |m| count_word(m.as_str()));
+        let Some(count) = count.filter(|c| *c > 0.0) else {
+            continue;
+        };
+        let secs = if caps[3].starts_with('m') { count * 60.0 } else { count };
+        if matches!(&caps[1], "first" | "opening") {
+            ranges.push((0.0, secs));
+        } else {
+            ranges.push((-secs, 0.0));
+        }
+        spans.push(whole.range());
+    }
+
+    let mut rest = text.to_string();
+    spans.sort_by_key(|r| std::cmp::Reverse(r.start));
+    for span in spans {
+        rest.replace_range(span, " ");
+    }
+    (ranges, rest)
+}
+
+/// Spoken references to cut ("the part where I say goodbye" → "goodbye",
+/// "where I drop the controller" → "drop the controller", or a quoted
+/// line) from lowercase intent `text`.
+pub fn parse_exclude_phrases(text: &str) -> Vec<String> {
+    let end = r#"\s*(?:[,.;!?"“”]|\band\b|\bthen\b|$)"#;
+    let reference = Regex::new(&format!(r#"\b(?:where|when)\s+([^,.;!?"“”]+?){end}"#))
+        .expect("valid reference regex");
+    let quoted = Regex::new(r#"["“]([^"”]+)["”]"#).expect("valid quote regex");
+    let lead = Regex::new(
+        r"^(?:(?:i|we|he|she|they|you|someone)\s+)?(?:(?:say|says|said|talk about|talks about|talked about|mention|mentions|mentioned)\s+)?",
+    )
+    .expect("valid lead regex");
+
+    let mut phrases: Vec<String> = Vec::new();
+    let mut push = |phrase: &str| {
+        let phrase = phrase.trim().trim_matches(['"', '\'', '“', '”']).trim().to_string();
+        if !phrase.is_empty() && !phrases.contains(&phrase) {
+            phrases.push(phrase);
+        }
+    };
+    for caps in quoted.captures_iter(text) {
+        if in_removal_clause(text, caps.get(0).expect("match").start()) {
+            push(&caps[1]);
+        }
+    }
+    for caps in reference.captures_iter(text) {
+        let whole = caps.get(0).expect("match");
+        // A quoted line after "where I say" was already taken above
+        if whole.as_str().ends_with(['"', '“']) || !in_removal_clause(text, whole.start()) {
+            continue;
+        }
+        push(&lead.replace(caps[1].trim(), ""));
+    }
+    phrases
+}
+
+/// Transcript segments where `phrase` is said: an exact (stemmed) match if
+/// there is one, otherwise segments containing every non-filler word.
+fn find_phrase<'a>(phrase: &str, transcript: &'a [TranscriptSegment]) -> Vec<&'a TranscriptSegment> {
+    let needle = stem_words(phrase);
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let exact: Vec<_> = transcript
+        .iter()
+        .filter(|seg| {
+            stem_words(&seg.text)
+                .windows(needle.len())
+                .any(|w| w == needle.as_slice())
+        })
+        .collect();
+    if !exact.is_empty() {
+        return exact;
+    }
+    let content: Vec<&String> = needle
+        .iter()
+        .filter(|w| !FILLER_WORDS.iter().any(|f| stem(f) == **w))
+        .collect();
+    if content.is_empty() {
+        return Vec::new();
+    }
+    transcript
+        .iter()
+        .filter(|seg| {
+            let words = stem_words(&seg.text);
+            content.iter().all(|w| words.contains(w))
+        })
+        .collect()
+}
+
+/// Turn the intent's ranges and phrases into source-time exclusions, in
+/// source order. Negative ranges count back from `source_duration`. Phrases
+/// with no transcript match are returned separately so they can be reported.
+pub fn resolve_exclusions(
+    ranges: &[(f64, f64)],
+    phrases: &[String],
+    transcript: Option<&[TranscriptSegment]>,
+    source_duration: f64,
+) -> (Vec<Exclusion>, Vec<String>) {
+    let mut exclusions: Vec<Exclusion> = ranges
+        .iter()
+        .map(|&(start, end)| {
+            if start < 0.0 {
+                (source_duration + start, source_duration + end)
+            } else {
+                (start, end)
+            }
+        })
+        .map(|(start, end)| (start.max(0.0), end.min(source_duration.max(start))))
+        .filter(|(start, end)| end > start)
+        .map(|(start, end)| Exclusion {
+            start,
+            end,
+            origin: ExclusionOrigin::Range,
+        })
+        .collect();
+
+    let mut unmatched = Vec::new();
+    for phrase in phrases {
+        let found = transcript.map(|t| find_phrase(phrase, t)).unwrap_or_default();
+        if found.is_empty() {
+            unmatched.push(phrase.clone());
+        }
+        exclusions.extend(found.into_iter().map(|seg| Exclusion {
+            start: seg.start,
+            end: seg.end,
+            origin: ExclusionOrigin::Transcript {
+                phrase: phrase.clone(),
+                said: seg.text.clone(),
+            },
+        }));
+    }
+    exclusions.sort_by(|a, b| a.start.total_cmp(&b.start));
+    (exclusions, unmatched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(start: f64, end: f64, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            start,
+            end,
+            text: text.to_string(),
+            words: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parses_timestamps_and_relative_ranges_in_removal_clauses() {
+        let (ranges, _) = parse_exclude_ranges(
+            "cut out 12:30 to 14:00 and 1:02:03-1:02:10, also remove the first two minutes and the last 30 seconds",
+        );
+        assert_eq!(
+            ranges,
+            vec![(750.0, 840.0), (3723.0, 3730.0), (0.0, 120.0), (-30.0, 0.0)]
+        );
+
+        let (kept, rest) = parse_exclude_ranges("keep 2:00 to 3:00 and make it 5 minutes");
+        assert!(kept.is_empty());
+        assert!(rest.contains("5 minutes"));
+
+        // The relative range is blanked so it is not read as a target length
+        let (_, rest) = parse_exclude_ranges("skip the first 2 minutes");
+        assert!(!rest.contains("minutes"), "{}", rest);
+    }
+
+    #[test]
+    fn parses_spoken_references() {
+        let phrases = parse_exclude_phrases(
+            "cut out 12:30 to 14:00 and the part where i drop the controller, then remove where i say goodbye",
+        );
+        assert_eq!(phrases, vec!["drop the controller", "goodbye"]);
+        assert_eq!(parse_exclude_phrases("remove the bit where he says \"like and subscribe\""), vec!["like and subscribe"]);
+        assert!(parse_exclude_phrases("cut the boring bits and keep the part where i win").is_empty());
+    }
+
+    #[test]
+    fn resolves_against_the_transcript_and_source_length() {
+        let transcript = vec![
+            seg(10.0, 14.0, "welcome back everyone"),
+            seg(300.0, 303.5, "oh no I dropped the controller"),
+            seg(590.0, 596.0, "okay that's it, goodbye!"),
+        ];
+        let (exclusions, unmatched) = resolve_exclusions(
+            &[(-30.0, 0.0), (60.0, 90.0)],
+            &["drop the controller".to_string(), "goodbye".to_string(), "the boss fight".to_string()],
+            Some(&transcript),
+            600.0,
+        );
+        let spans: Vec<(f64, f64)> = exclusions.iter().map(|e| (e.start, e.end)).collect();
+        assert_eq!(spans, vec![(60.0, 90.0), (300.0, 303.5), (570.0, 600.0), (590.0, 596.0)]);
+        assert_eq!(exclusions[0].origin, ExclusionOrigin::Range);
+        assert!(matches!(&exclusions[1].origin, ExclusionOrigin::Transcript { phrase, .. } if phrase == "drop the controller"));
+        assert_eq!(unmatched, vec!["the boss fight"]);
+        assert!(exclusions[1].describe().contains("transcript match"));
+        assert!(exclusions[2].describe().starts_with("9:30-10:00 (explicit range)"));
+    }
+}
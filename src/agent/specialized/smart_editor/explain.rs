@@ -0,0 +1,388 @@
+// SYNOID Smart Editor - Edit Explanations
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// "Why did it cut that?" Scoring records every factor that moved a scene's
+// score; after the edit, each removed region gets a one-line reason built from
+// the factors that pushed it under the keep threshold (or the exclusion or
+// keyword filter that dropped it), and each kept segment lists what held it
+// in. The report is written as Markdown next to the output video.
+
+use super::exclusions::{format_timestamp, Exclusion};
+use super::scene_ops::ScoreBreakdown;
+use super::types::Scene;
+use crate::agent::ai_systems::gpt_oss_bridge::SynoidAgent;
+use crate::agent::audio_classify::AudioMix;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Gaps this short between kept segments are frame slop, not removals.
+const MIN_REMOVED_SECS: f64 = 0.25;
+
+/// Scenes shorter than this are dropped as micro-clips even when they
+/// score above the threshold (see `select_scenes`).
+const MICRO_CLIP_SECS: f64 = 3.5;
+
+/// Factors named per line.
+const MAX_REASONS: usize = 3;
+
+/// One scored scene, as the explanation sees it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneScore {
+    pub start: f64,
+    pub end: f64,
+    #[serde(flatten)]
+    pub breakdown: ScoreBreakdown,
+    /// What the scene's audio is, when it was classified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio_mix: Option<AudioMix>,
+    /// Why the scene was dropped regardless of its score.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dropped: Option<String>,
+}
+
+/// Every scene's score, kept by scene analysis for the explanation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoringReport {
+    pub scenes: Vec<SceneScore>,
+    /// The threshold scenes had to beat, after any duration targeting.
+    pub keep_threshold: f64,
+    pub source_duration: f64,
+}
+
+/// A span of source time and why it was cut or kept.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RegionNote {
+    pub start: f64,
+    pub end: f64,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EditExplanation {
+    pub removed: Vec<RegionNote>,
+    pub kept: Vec<RegionNote>,
+}
+
+/// `<output>.explain.md`, beside the edit plan.
+pub fn explanation_path(output: &Path) -> PathBuf {
+    output.with_extension("explain.md")
+}
+
+fn overlap(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.1.min(b.1) - a.0.max(b.0)).max(0.0)
+}
+
+/// "47s", "2.5s" or "3m 05s".
+fn format_span(secs: f64) -> String {
+    if secs >= 60.0 {
+        let whole = secs.round() as u64;
+        format!("{}m {:02}s", whole / 60, whole % 60)
+    } else if secs >= 10.0 {
+        format!("{:.0}s", secs)
+    } else {
+        format!("{:.1}s", secs)
+    }
+}
+
+/// Explain the cuts between `kept` segments, using the scores when scene
+/// analysis ran (reused draft or seeded cuts carry none).
+pub fn explain(
+    kept: &[Scene],
+    scoring: Option<&ScoringReport>,
+    exclusions: &[Exclusion],
+) -> EditExplanation {
+    let source_end = scoring
+        .map(|s| s.source_duration)
+        .or_else(|| kept.last().map(|s| s.end_time))
+        .unwrap_or(0.0);
+
+    let mut removed = Vec::new();
+    let mut prev_end = 0.0;
+    let gaps = kept
+        .iter()
+        .map(|s| (s.start_time, s.end_time))
+        .chain(std::iter::once((source_end, source_end)));
+    for (start, end) in gaps {
+        if start - prev_end > MIN_REMOVED_SECS {
+            removed.push(RegionNote {
+                start: prev_end,
+                end: start,
+                reason: removed_reason((prev_end, start), scoring, exclusions),
+            });
+        }
+        prev_end = prev_end.max(end);
+    }
+
+    let kept = kept
+        .iter()
+        .map(|s| RegionNote {
+            start: s.start_time,
+            end: s.end_time,
+            reason: kept_reason((s.start_time, s.end_time), scoring),
+        })
+        .collect();
+    EditExplanation { removed, kept }
+}
+
+/// The scored scene covering most of `span`.
+fn dominant_scene(span: (f64, f64), scoring: &ScoringReport) -> Option<&SceneScore> {
+    scoring
+        .scenes
+        .iter()
+        .map(|s| (overlap(span, (s.start, s.end)), s))
+        .filter(|(o, _)| *o > 0.0)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, s)| s)
+}
+
+fn removed_reason(span: (f64, f64), scoring: Option<&ScoringReport>, exclusions: &[Exclusion]) -> String {
+    if let Some(exclusion) = exclusions
+        .iter()
+        .filter(|e| overlap(span, (e.start, e.end)) > 0.0)
+        .max_by(|a, b| overlap(span, (a.start, a.end)).total_cmp(&overlap(span, (b.start, b.end))))
+    {
+        return format!("excluded by {}", exclusion.reason());
+    }
+    let Some(scoring) = scoring else {
+        return "cut by an earlier run of this edit (scores not recorded)".to_string();
+    };
+    let Some(scene) = dominant_scene(span, scoring) else {
+        return "not part of any detected scene".to_string();
+    };
+    if let Some(dropped) = &scene.dropped {
+        return dropped.clone();
+    }
+
+    let score = scene.breakdown.score;
+    if score <= scoring.keep_threshold {
+        let mut negative: Vec<_> = scene.breakdown.factors.iter().filter(|f| f.delta < 0.0).collect();
+        negative.sort_by(|a, b| a.delta.total_cmp(&b.delta));
+        let reasons: Vec<&str> = negative.iter().take(MAX_REASONS).map(|f| f.reason.as_str()).collect();
+        let verdict = format!("score {:.2}, threshold {:.2}", score, scoring.keep_threshold);
+        if reasons.is_empty() {
+            format!("nothing lifted it above the keep threshold ({})", verdict)
+        } else {
+            format!("{} ({})", reasons.join(", "), verdict)
+        }
+    } else if scene.end - scene.start < MICRO_CLIP_SECS {
+        format!(
+            "too short to keep on its own (under {}s, score {:.2})",
+            MICRO_CLIP_SECS, score
+        )
+    } else {
+        format!("trimmed at a scene edge to tighten the cut (score {:.2})", score)
+    }
+}
+
+fn kept_reason(span: (f64, f64), scoring: Option<&ScoringReport>) -> String {
+    let Some(scoring) = scoring else {
+        return "kept by an earlier run of this edit (scores not recorded)".to_string();
+    };
+    let scenes: Vec<&SceneScore> = scoring
+        .scenes
+        .iter()
+        .filter(|s| s.dropped.is_none() && overlap(span, (s.start, s.end)) > 0.0)
+        .collect();
+    let best = scenes.iter().map(|s| s.breakdown.score).fold(0.0, f64::max);
+
+    // Strongest positive factors across the scenes merged into this segment
+    let mut positive: Vec<(&str, f64)> = Vec::new();
+    for factor in scenes.iter().flat_map(|s| &s.breakdown.factors).filter(|f| f.delta > 0.0) {
+        match positive.iter_mut().find(|(reason, _)| *reason == factor.reason) {
+            Some((_, delta)) => *delta = delta.max(factor.delta),
+            None => positive.push((&factor.reason, factor.delta)),
+        }
+    }
+    positive.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let reasons: Vec<&str> = positive.iter().take(MAX_REASONS).map(|(r, _)| *r).collect();
+
+    if best <= scoring.keep_threshold {
+        format!("bridges a long jump or fills in for too few kept scenes (score {:.2})", best)
+    } else if reasons.is_empty() {
+        format!("scored {:.2}, above threshold {:.2}", best, scoring.keep_threshold)
+    } else {
+        format!("{} (score {:.2})", reasons.join(", "), best)
+    }
+}
+
+impl EditExplanation {
+    pub fn to_markdown(&self, output: &Path) -> String {
+        let total = |notes: &[RegionNote]| notes.iter().map(|n| n.end - n.start).sum::<f64>();
+        let name = output
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut md = format!(
+            "# Edit explanation: {}\n\nKept {} segment(s) ({}), removed {} region(s) ({}).\n\n## Removed\n\n",
+            name,
+            self.kept.len(),
+            format_span(total(&self.kept)),
+            self.removed.len(),
+            format_span(total(&self.removed))
+        );
+        if self.removed.is_empty() {
+            md.push_str("_Nothing was removed._\n");
+        }
+        for note in &self.removed {
+            md.push_str(&format!(
+                "- {} removed at {} — {}\n",
+                format_span(note.end - note.start),
+                format_timestamp(note.start),
+                note.reason
+            ));
+        }
+        md.push_str("\n## Kept\n\n");
+        for note in &self.kept {
+            md.push_str(&format!(
+                "- {}–{} ({}) — {}\n",
+                format_timestamp(note.start),
+                format_timestamp(note.end),
+                format_span(note.end - note.start),
+                note.reason
+            ));
+        }
+        md
+    }
+}
+
+/// The `explain_polish` setting has the Brain reword the report. The numbers
+/// and structure must survive; anything else falls back to the plain report.
+pub async fn polish(markdown: &str) -> String {
+    let config = crate::config::current();
+    if !config.explain_polish {
+        return markdown.to_string();
+    }
+    let api_url = config.api_url;
+    let agent = SynoidAgent::new(&api_url, "default");
+    let prompt = format!(
+        "Reword this video edit report so each bullet reads as a natural sentence. \
+         Keep every heading, bullet, timestamp, duration and score exactly as written; \
+         change only the wording after each dash. Reply with ONLY the Markdown.\n\n{}",
+        markdown
+    );
+    match agent.fast_reason(&prompt).await {
+        Ok(polished) if keeps_structure(markdown, &polished) => polished.trim().to_string() + "\n",
+        _ => markdown.to_string(),
+    }
+}
+
+/// True when `polished` has the same headings and bullet count as `original`.
+fn keeps_structure(original: &str, polished: &str) -> bool {
+    let shape = |md: &str| {
+        md.lines()
+            .filter(|l| l.starts_with('#') || l.starts_with("- "))
+            .map(|l| l.starts_with('#'))
+            .collect::<Vec<_>>()
+    };
+    shape(original) == shape(polished)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::exclusions::ExclusionOrigin;
+    use super::super::scene_ops::{ScoreComponent, ScoreFactor};
+
+    fn scored(start: f64, end: f64, score: f64, factors: &[(&str, f64)]) -> SceneScore {
+        SceneScore {
+            start,
+            end,
+            breakdown: ScoreBreakdown {
+                base: 0.35,
+                score,
+                factors: factors
+                    .iter()
+                    .map(|&(reason, delta)| ScoreFactor {
+                        component: ScoreComponent::Speech,
+                        reason: reason.to_string(),
+                        delta,
+                    })
+                    .collect(),
+                ..Default::default()
+            },
+            audio_mix: None,
+            dropped: None,
+        }
+    }
+
+    fn kept(start: f64, end: f64) -> Scene {
+        Scene {
+            start_time: start,
+            end_time: end,
+            duration: end - start,
+            score: 1.0,
+            vision_tags: Vec::new(),
+            breakdown: None,
+            audio_mix: None,
+        }
+    }
+
+    #[test]
+    fn removed_regions_name_the_factors_that_sank_them() {
+        let mut keyword_dropped = scored(800.0, 820.0, 0.9, &[("speech", 0.3)]);
+        keyword_dropped.dropped = Some("mentions excluded keyword \"sponsor\"".to_string());
+        let scoring = ScoringReport {
+            scenes: vec![
+                scored(0.0, 730.0, 0.95, &[("speech", 0.3), ("opening of the video", 0.1)]),
+                scored(
+                    730.0,
+                    777.0,
+                    0.0,
+                    &[
+                        ("late-video penalty", -0.15),
+                        ("no speech", -0.4),
+                        ("opening of the video", 0.1),
+                        ("scene exceeded boring threshold", -0.2),
+                        ("ruthless trim", -0.05),
+                    ],
+                ),
+                scored(777.0, 800.0, 0.8, &[("excited or funny moment", 0.25)]),
+                keyword_dropped,
+                scored(820.0, 900.0, 0.7, &[]),
+            ],
+            keep_threshold: 0.3,
+            source_duration: 930.0,
+        };
+        let exclusions = vec![Exclusion {
+            start: 900.0,
+            end: 930.0,
+            origin: ExclusionOrigin::Range,
+        }];
+        let report = explain(
+            &[kept(0.0, 730.0), kept(777.0, 800.0), kept(820.0, 900.0)],
+            Some(&scoring),
+            &exclusions,
+        );
+
+        let removed: Vec<(f64, f64, &str)> =
+            report.removed.iter().map(|n| (n.start, n.end, n.reason.as_str())).collect();
+        assert_eq!(
+            removed,
+            vec![
+                (
+                    730.0,
+                    777.0,
+                    "no speech, scene exceeded boring threshold, late-video penalty (score 0.00, threshold 0.30)"
+                ),
+                (800.0, 820.0, "mentions excluded keyword \"sponsor\""),
+                (900.0, 930.0, "excluded by explicit range"),
+            ]
+        );
+        assert_eq!(report.kept[0].reason, "speech, opening of the video (score 0.95)");
+        assert_eq!(report.kept[2].reason, "scored 0.70, above threshold 0.30");
+
+        let md = report.to_markdown(Path::new("clip_edited.mp4"));
+        assert!(md.contains("- 47s removed at 12:10 — no speech, scene exceeded boring threshold"));
+        assert!(md.contains("Kept 3 segment(s) (13m 53s), removed 3 region(s) (1m 37s)."));
+    }
+
+    #[test]
+    fn cuts_without_scores_still_get_a_reason() {
+        let report = explain(&[kept(5.0, 20.0), kept(20.1, 30.0)], None, &[]);
+        assert_eq!(report.removed.len(), 1);
+        assert_eq!((report.removed[0].start, report.removed[0].end), (0.0, 5.0));
+        assert!(report.removed[0].reason.contains("scores not recorded"));
+        assert!(keeps_structure("# A\n\n- x\n- y\n", "# A\nintro\n- x, reworded\n- y\n"));
+        assert!(!keeps_structure("# A\n\n- x\n- y\n", "# A\n\n- x and y\n"));
+    }
+}
@@ -0,0 +1,282 @@
+// SYNOID Smart Editor - External Scorer
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// A hook for scoring models written outside Rust. After the built-in scoring,
+// the scenes, transcript and intent go to a user-configured command (on
+// stdin) or HTTP endpoint (as a POST body) as JSON:
+//
+//   {"version": 1, "intent": {...}, "transcript": [...],
+//    "scenes": [{"start": 0.0, "end": 4.2, "score": 0.55, ...}, ...]}
+//
+// It answers with `{"scores": [...]}`, one score in 0..1 per scene in the
+// same order, which replace the built-in ones. Anything else, a failure or
+// a timeout leaves the built-in scores in place. `examples/external_scorer.py`
+// is a starting point.
+
+use super::scene_ops::ScoreComponent;
+use super::types::{EditIntent, Scene};
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::tools::transcription::TranscriptSegment;
+use serde::Deserialize;
+use serde_json::json;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Seconds the scorer gets when none is configured.
+pub const DEFAULT_TIMEOUT_SECS: f64 = 30.0;
+/// Version of the request format, sent as `version`.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Where scores come from: a shell command, or a URL starting with http://
+/// or https://.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExternalScorer {
+    Command(String),
+    Http(String),
+}
+
+#[derive(Deserialize)]
+struct ScorerResponse {
+    scores: Vec<f64>,
+}
+
+impl ExternalScorer {
+    /// `None` for an empty spec.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let spec = spec.trim();
+        if spec.is_empty() {
+            None
+        } else if spec.starts_with("http://") || spec.starts_with("https://") {
+            Some(Self::Http(spec.to_string()))
+        } else {
+            Some(Self::Command(spec.to_string()))
+        }
+    }
+
+    /// The configured scorer and its timeout, if one is set.
+    pub fn from_config() -> Option<(Self, Duration)> {
+        let config = crate::config::current();
+        let scorer = Self::parse(config.external_scorer.as_deref()?)?;
+        let secs = config.external_scorer_timeout;
+        let secs = if secs.is_finite() && secs > 0.0 {
+            secs
+        } else {
+            DEFAULT_TIMEOUT_SECS
+        };
+        Some((scorer, Duration::from_secs_f64(secs)))
+    }
+
+    /// Replace the scores of `scenes` with the scorer's. On any error the
+    /// scenes are left untouched.
+    pub async fn rescore(
+        &self,
+        scenes: &mut [Scene],
+        intent: &EditIntent,
+        transcript: Option<&[TranscriptSegment]>,
+        timeout: Duration,
+    ) -> Result<(), String> {
+        let body = request(scenes, intent, transcript).to_string();
+        let reply = self.exchange(&body, timeout).await?;
+        let scores = parse_response(&reply, scenes.len())?;
+        for (scene, score) in scenes.iter_mut().zip(scores) {
+            scene.score = score;
+            if let Some(breakdown) = scene.breakdown.as_mut() {
+                breakdown.set(ScoreComponent::External, "external scorer", score);
+            }
+        }
+        Ok(())
+    }
+
+    async fn exchange(&self, body: &str, timeout: Duration) -> Result<String, String> {
+        match self {
+            Self::Http(url) => tokio::time::timeout(timeout, post(url, body, timeout))
+                .await
+                .map_err(|_| no_answer(timeout))?,
+            Self::Command(command) => run_command(command, body, timeout).await,
+        }
+    }
+}
+
+fn no_answer(timeout: Duration) -> String {
+    format!("no answer within {:.0}s", timeout.as_secs_f64())
+}
+
+/// POST `body` to `url`; the response body.
+async fn post(url: &str, body: &str, timeout: Duration) -> Result<String, String> {
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("{} failed: {}", url, e))?;
+    response.text().await.map_err(|e| e.to_string())
+}
+
+/// Run `command` through the shell with `body` on stdin; its stdout. Past
+/// `timeout` the shell and everything it started are killed.
+async fn run_command(command: &str, body: &str, timeout: Duration) -> Result<String, String> {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = proc::command("cmd", ProcClass::Scorer);
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = proc::command("sh", ProcClass::Scorer);
+        cmd.args(["-c", command]);
+        cmd
+    };
+    // Its own group, so a timeout reaches the interpreter behind the shell
+    #[cfg(unix)]
+    cmd.process_group(0);
+    let mut child = cmd
+        .stealth()
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .await
+        .map_err(|e| format!("cannot start '{}': {}", command, e))?;
+
+    let pid = child.id();
+
+    let mut stdin = child.stdin.take().ok_or("scorer has no stdin")?;
+    let mut stdout = child.stdout.take().ok_or("scorer has no stdout")?;
+    let mut stderr = child.stderr.take().ok_or("scorer has no stderr")?;
+    let write = async move {
+        // A scorer that ignores its input closes the pipe early; its answer
+        // still decides.
+        let _ = stdin.write_all(body.as_bytes()).await;
+    };
+    let mut out = Vec::new();
+    let mut err = Vec::new();
+    let exchange = async {
+        let (_, read_out, _) = tokio::join!(
+            write,
+            stdout.read_to_end(&mut out),
+            stderr.read_to_end(&mut err)
+        );
+        read_out?;
+        child.wait().await
+    };
+    let status = match tokio::time::timeout(timeout, exchange).await {
+        Ok(status) => status.map_err(|e| e.to_string())?,
+        Err(_) => {
+            if let Some(pid) = pid {
+                kill_tree(pid).await;
+            }
+            return Err(no_answer(timeout));
+        }
+    };
+    if !status.success() {
+        return Err(format!(
+            "'{}' exited with {}: {}",
+            command,
+            status,
+            String::from_utf8_lossy(&err).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&out).into_owned())
+}
+
+/// Kill a timed-out scorer and everything it started: its process group on
+/// Unix, its process tree on Windows.
+async fn kill_tree(pid: u32) {
+    // Unmanaged: killing a runaway scorer must never wait for a slot
+    #[cfg(windows)]
+    let _ = proc::unmanaged("taskkill")
+        .stealth()
+        .args(["/PID", &pid.to_string(), "/T", "/F"])
+        .status()
+        .await;
+
+    #[cfg(not(windows))]
+    let _ = proc::unmanaged("kill")
+        .args(["-KILL", "--", &format!("-{}", pid)])
+        .status()
+        .await;
+}
+
+/// The JSON sent to the scorer.
+pub fn request(
+    scenes: &[Scene],
+    intent: &EditIntent,
+    transcript: Option<&[TranscriptSegment]>,
+) -> serde_json::Value {
+    let scenes: Vec<_> = scenes
+        .iter()
+        .map(|s| {
+            json!({
+                "start": s.start_time,
+                "end": s.end_time,
+                "duration": s.duration,
+                "score": s.score,
+                "vision_tags": s.vision_tags,
+                "breakdown": s.breakdown,
+            })
+        })
+        .collect();
+    json!({
+        "version": PROTOCOL_VERSION,
+        "intent": intent,
+        "transcript": transcript.unwrap_or_default(),
+        "scenes": scenes,
+    })
+}
+
+/// The scores in `body`, checked against the `expected` scene count and the
+/// 0..1 range.
+pub fn parse_response(body: &str, expected: usize) -> Result<Vec<f64>, String> {
+    let response: ScorerResponse = serde_json::from_str(body.trim())
+        .map_err(|e| format!("answer is not {{\"scores\": [...]}}: {}", e))?;
+    if response.scores.len() != expected {
+        return Err(format!(
+            "{} score(s) for {} scene(s)",
+            response.scores.len(),
+            expected
+        ));
+    }
+    if let Some((i, score)) = response
+        .scores
+        .iter()
+        .enumerate()
+        .find(|(_, s)| !(0.0..=1.0).contains(*s))
+    {
+        return Err(format!("score {} for scene {} is outside 0..1", score, i));
+    }
+    Ok(response.scores)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn responses_must_match_the_scenes() {
+        assert_eq!(
+            parse_response(r#"{"scores": [0.2, 1.0]}"#, 2),
+            Ok(vec![0.2, 1.0])
+        );
+        assert!(parse_response(r#"{"scores": [0.2]}"#, 2).is_err());
+        assert!(parse_response(r#"{"scores": [0.2, 1.5]}"#, 2).is_err());
+        assert!(parse_response(r#"{"scores": [0.2, NaN]}"#, 2).is_err());
+        assert!(parse_response("[0.2, 0.3]", 2).is_err());
+
+        assert_eq!(
+            ExternalScorer::parse("https://ml.local/score"),
+            Some(ExternalScorer::Http("https://ml.local/score".into()))
+        );
+        assert_eq!(
+            ExternalScorer::parse(" python3 score.py "),
+            Some(ExternalScorer::Command("python3 score.py".into()))
+        );
+        assert_eq!(ExternalScorer::parse(""), None);
+    }
+}
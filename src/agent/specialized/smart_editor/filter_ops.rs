@@ -1,8 +1,10 @@
 use super::types::{Scene};
+use crate::agent::engines::proc::{self, ProcClass};
+use super::interval_index::IntervalIndex;
+use rayon::prelude::*;
 use tracing::{info, warn};
 use std::path::Path;
 use std::fs;
-use tokio::process::Command;
 use crate::agent::engines::process_utils::CommandExt;
 // SYNOID Smart Editor Refactoring
 
@@ -21,7 +23,7 @@ pub async fn insert_cut_markers(
     );
 
     // Probe the resolution of the output file so our marker frame matches
-    let probe = Command::new("ffprobe")
+    let probe = proc::ffprobe()
         .stealth()
         .args([
             "-v",
@@ -49,7 +51,7 @@ pub async fn insert_cut_markers(
     let drawtext = format!(
         "drawtext=text='[CUT]':fontsize=48:fontcolor=white@0.85:x=(w-text_w)/2:y=(h-text_h)/2:shadowcolor=black:shadowx=2:shadowy=2"
     );
-    let marker_status = Command::new("ffmpeg")
+    let marker_status = proc::ffmpeg(ProcClass::Render)
         .stealth()
         .args([
             "-y",
@@ -143,7 +145,7 @@ pub async fn insert_cut_markers(
 
     let marked_path = work_dir.join("output_marked.mp4");
     let gpu_ctx = crate::gpu_backend::get_gpu_context().await;
-    let mut mark_cmd = Command::new("ffmpeg");
+    let mut mark_cmd = proc::ffmpeg(ProcClass::Render);
     mark_cmd.stealth();
     mark_cmd.args(["-y", "-hide_banner", "-loglevel", "error", "-nostdin"]);
     if let Some(hwaccel) = gpu_ctx.ffmpeg_hwaccel() {
@@ -185,6 +187,35 @@ pub async fn insert_cut_markers(
     Ok(())
 }
 
+/// Map transcript segments onto the output timeline. `offsets` holds
+/// `(src_start, src_end, out_start)` per kept scene; a segment spanning a cut
+/// yields one entry per kept scene it touches, in kept-scene order.
+pub fn remap_transcript_entries(
+    transcript: &[crate::agent::transcription::TranscriptSegment],
+    offsets: &[(f64, f64, f64)],
+) -> Vec<(f64, f64, String)> {
+    let index = IntervalIndex::new(offsets.iter().map(|&(s, e, _)| (s, e)));
+    transcript
+        .par_iter()
+        .flat_map_iter(|seg| {
+            let text = seg.text.trim().to_string();
+            index
+                .overlapping(seg.start, seg.end)
+                .into_iter()
+                .map(move |i| {
+                    let (src_start, src_end, out_start) = offsets[i];
+                    let clip_start = seg.start.max(src_start);
+                    let clip_end = seg.end.min(src_end);
+                    (
+                        out_start + (clip_start - src_start),
+                        out_start + (clip_end - src_start),
+                        text.clone(),
+                    )
+                })
+        })
+        .collect()
+}
+
 /// Detect scenes in a video using FFmpeg scene detection
 pub fn generate_srt_for_kept_scenes(
     transcript: &[crate::agent::transcription::TranscriptSegment],
@@ -211,21 +242,7 @@ pub fn generate_srt_for_kept_scenes(
     }
 
     // --- Pass 1: Collect all candidate entries (start, end, text) ---
-    let mut entries: Vec<(f64, f64, String)> = Vec::new();
-
-    for seg in transcript {
-        for &(src_start, src_end, out_start) in &output_offsets {
-            let clip_start = seg.start.max(src_start);
-            let clip_end = seg.end.min(src_end);
-            if clip_end <= clip_start {
-                continue;
-            }
-            let new_start = out_start + (clip_start - src_start);
-            let new_end = out_start + (clip_end - src_start);
-            entries.push((new_start, new_end, seg.text.trim().to_string()));
-            // removed break to allow subtitle segments to span across cut boundaries
-        }
-    }
+    let entries = remap_transcript_entries(transcript, &output_offsets);
 
     // --- Pass 2: Merge flash entries or identical consecutive entries (caused by cut loops) ---
     let normalise = |s: &str| -> String {
@@ -0,0 +1,201 @@
+// SYNOID Smart Editor - Funny Moments
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Classifies transcript segments as funny moments. Laughter ("haha",
+// Whisper's "[laughter]"), fails ("oops", "noooo"), hype (reaction words,
+// shouting) and deadpan punchlines (a short reply right after a question,
+// confirmed when laughter follows) each get a kind and a confidence, so the
+// scorer can tell a joke from a loud moment.
+
+use crate::agent::tools::transcription::TranscriptSegment;
+use serde::{Deserialize, Serialize};
+
+/// Longest pause, in seconds, between a setup and its punchline.
+const PUNCHLINE_GAP: f64 = 3.0;
+/// Punchlines are short; longer replies are just conversation.
+const PUNCHLINE_MAX_WORDS: usize = 8;
+
+const LAUGH_PREFIXES: [&str; 6] = ["haha", "hehe", "lol", "lmao", "rofl", "laugh"];
+const FAIL_WORDS: [&str; 10] = [
+    "oops", "whoops", "ouch", "fail", "failed", "noo", "dang", "yikes", "rip", "nope",
+];
+const HYPE_WORDS: [&str; 12] = [
+    "wow",
+    "whoa",
+    "woah",
+    "omg",
+    "cool",
+    "crazy",
+    "insane",
+    "funny",
+    "hilarious",
+    "yes",
+    "let's",
+    "clutch",
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FunnyKind {
+    Laughter,
+    Punchline,
+    Fail,
+    Hype,
+}
+
+impl FunnyKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FunnyKind::Laughter => "laughter",
+            FunnyKind::Punchline => "punchline",
+            FunnyKind::Fail => "fail",
+            FunnyKind::Hype => "hype",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FunnyMoment {
+    pub start: f64,
+    pub end: f64,
+    pub kind: FunnyKind,
+    /// 0.0–1.0.
+    pub confidence: f64,
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn is_laugh(word: &str) -> bool {
+    LAUGH_PREFIXES.iter().any(|p| word.starts_with(p))
+}
+
+/// "nooo" and "noooooo" are the same fail.
+fn is_fail(word: &str) -> bool {
+    FAIL_WORDS.contains(&word)
+        || (word.len() > 3
+            && word.starts_with("noo")
+            && word.trim_start_matches('n').chars().all(|c| c == 'o'))
+}
+
+fn laughs(segment: &TranscriptSegment) -> bool {
+    words(&segment.text).iter().any(|w| is_laugh(w))
+}
+
+/// Kind and confidence of `segment` taken on its own.
+fn classify_text(text: &str) -> Option<(FunnyKind, f64)> {
+    let words = words(text);
+    let count = |pred: &dyn Fn(&str) -> bool| words.iter().filter(|w| pred(w)).count();
+
+    let laughter = count(&is_laugh);
+    if laughter > 0 {
+        return Some((FunnyKind::Laughter, (0.6 + 0.1 * laughter as f64).min(0.95)));
+    }
+    let fails = count(&is_fail);
+    if fails > 0 {
+        return Some((FunnyKind::Fail, (0.5 + 0.15 * fails as f64).min(0.9)));
+    }
+
+    let hype = count(&|w| HYPE_WORDS.contains(&w));
+    let exclamations = text.matches('!').count();
+    if hype == 0 && exclamations == 0 {
+        return None;
+    }
+    let confidence = 0.3 + 0.15 * hype as f64 + 0.1 * exclamations.min(3) as f64;
+    Some((FunnyKind::Hype, confidence.min(0.85)))
+}
+
+/// Classify `segments[i]` using its neighbours: a short reply to a question
+/// is a punchline, and laughter in the next segment makes it a sure one.
+pub fn classify(segments: &[TranscriptSegment], i: usize) -> Option<FunnyMoment> {
+    let segment = segments.get(i)?;
+    let moment = |kind, confidence| FunnyMoment {
+        start: segment.start,
+        end: segment.end,
+        kind,
+        confidence,
+    };
+
+    if let Some((kind, confidence)) = classify_text(&segment.text) {
+        return Some(moment(kind, confidence));
+    }
+
+    let setup = i.checked_sub(1).map(|p| &segments[p])?;
+    let reply_words = words(&segment.text).len();
+    let is_punchline = setup.text.trim_end().ends_with('?')
+        && segment.start - setup.end <= PUNCHLINE_GAP
+        && (1..=PUNCHLINE_MAX_WORDS).contains(&reply_words);
+    if !is_punchline {
+        return None;
+    }
+    let followed_by_laughter = segments.get(i + 1).is_some_and(laughs);
+    Some(moment(
+        FunnyKind::Punchline,
+        if followed_by_laughter { 0.8 } else { 0.4 },
+    ))
+}
+
+/// Every funny moment in the transcript, in order.
+pub fn funny_moments(segments: &[TranscriptSegment]) -> Vec<FunnyMoment> {
+    (0..segments.len())
+        .filter_map(|i| classify(segments, i))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(start: f64, end: f64, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            start,
+            end,
+            text: text.to_string(),
+            words: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn segments_are_typed_by_what_is_said() {
+        let transcript = [
+            seg(0.0, 2.0, "So what do you call a fake noodle?"),
+            seg(2.5, 3.5, "An impasta."),
+            seg(3.6, 5.0, "[LAUGHTER] hahaha"),
+            seg(6.0, 8.0, "Okay, jumping over the gap now"),
+            seg(8.0, 9.0, "Nooooo, oops"),
+            seg(10.0, 12.0, "Whoa, that was insane!"),
+            seg(13.0, 15.0, "Loading the next level."),
+        ];
+
+        let kinds: Vec<(f64, FunnyKind)> = funny_moments(&transcript)
+            .iter()
+            .map(|m| (m.start, m.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (2.5, FunnyKind::Punchline),
+                (3.6, FunnyKind::Laughter),
+                (8.0, FunnyKind::Fail),
+                (10.0, FunnyKind::Hype),
+            ]
+        );
+        // Laughter right after confirms the deadpan reply
+        assert_eq!(classify(&transcript, 1).unwrap().confidence, 0.8);
+        // Plain statements and long replies are not jokes
+        assert!(classify(&transcript, 3).is_none());
+        let rambling = [
+            seg(0.0, 1.0, "Why?"),
+            seg(
+                1.0,
+                4.0,
+                "Because the save file got corrupted again last night",
+            ),
+        ];
+        assert!(classify(&rambling, 1).is_none());
+    }
+}
@@ -0,0 +1,193 @@
+// SYNOID Smart Editor - Intent Languages
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// The heuristic intent parser matches English words, so "quita las partes
+// aburridas" used to fall through to the defaults whenever the LLM was
+// unavailable. Rather than a second parser per language, each language has
+// a table mapping its phrasings onto the English words the parser already
+// knows; the intent is glossed through the table and then parsed as usual.
+// The LLM path needs none of this: it is asked to read any language.
+
+use serde::{Deserialize, Serialize};
+
+/// Language the heuristic parser reads an intent in. `intent_language` /
+/// SYNOID_INTENT_LANGUAGE; unset detects it from the intent's words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IntentLanguage {
+    En,
+    Es,
+    De,
+}
+
+impl std::str::FromStr for IntentLanguage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "en" | "english" => Ok(Self::En),
+            "es" | "spanish" | "español" | "espanol" => Ok(Self::Es),
+            "de" | "german" | "deutsch" => Ok(Self::De),
+            other => Err(format!(
+                "Unknown intent language '{}' (expected en, es or de)",
+                other
+            )),
+        }
+    }
+}
+
+/// Common words that give a language away, counted to detect it.
+const STOPWORDS: &[(IntentLanguage, &[&str])] = &[
+    (
+        IntentLanguage::En,
+        &[
+            "the", "and", "of", "to", "it", "with", "all", "make", "keep", "cut", "remove", "parts",
+        ],
+    ),
+    (
+        IntentLanguage::Es,
+        &[
+            "el", "la", "los", "las", "de", "que", "y", "en", "un", "una", "por", "con", "para",
+            "quita", "quitar", "corta", "deja", "partes", "todo", "hazlo",
+        ],
+    ),
+    (
+        IntentLanguage::De,
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "ein", "eine", "mit", "zu", "den", "alle",
+            "teile", "entferne", "schneide", "mach", "behalte", "ohne",
+        ],
+    ),
+];
+
+/// Phrasings (regex, matched as whole words) and the English words the
+/// parser knows them by.
+const SPANISH: &[(&str, &str)] = &[
+    (r"aburrid[oa]s?", "boring"),
+    (r"lent[oa]s?", "slow"),
+    (r"tiempos? muertos?", "dead air"),
+    (r"silencios?[oa]?s?", "silence"),
+    (r"callad[oa]s?", "quiet"),
+    (r"acci[oó]n", "action"),
+    (r"emocionantes?", "exciting"),
+    (r"intens[oa]s?", "intense"),
+    (r"interesantes?", "interesting"),
+    (r"r[aá]pid[oa]s?", "fast"),
+    (r"di[aá]logos?|conversaci[oó]n(?:es)?|voz|habla", "speech"),
+    (r"subt[ií]tulos?", "subtitles"),
+    (r"cort[oa]|breve", "short"),
+    (r"lo mejor|mejores momentos|resumen", "highlights"),
+    (r"complet[oa]|enter[oa]", "complete"),
+    (r"agresiv[oa]", "aggressive"),
+    (r"sin relleno", "no filler"),
+    (r"minutos?", "minutes"),
+    (r"horas?", "hour"),
+    (r"m[uú]sica", "music"),
+];
+
+const GERMAN: &[(&str, &str)] = &[
+    (r"langweilig\w*", "boring"),
+    (r"lahm\w*", "lame"),
+    (r"langsam\w*", "slow"),
+    (r"totzeit\w*|leerlauf\w*", "dead air"),
+    (r"stille\w*|pausen", "silence"),
+    (r"ruhig\w*", "quiet"),
+    (r"actionszenen|action", "action"),
+    (r"spannend\w*|aufregend\w*", "exciting"),
+    (r"intensiv\w*", "intense"),
+    (r"interessant\w*", "interesting"),
+    // "fast" is German for "almost", not a request for pace
+    (r"fast", "nearly"),
+    (r"schnell\w*", "quick"),
+    (r"sprache|gespr[aä]ch\w*|dialog\w*|stimme", "speech"),
+    (r"untertitel\w*", "subtitles"),
+    (r"kurz\w*", "short"),
+    (r"h[oö]hepunkte\w*", "highlights"),
+    (r"vollst[aä]ndig\w*|ganze[nrs]?", "complete"),
+    (r"aggressiv\w*", "aggressive"),
+    (r"ohne f[uü]ll(?:er|material)", "no filler"),
+    (r"minuten", "minutes"),
+    (r"stunden?", "hour"),
+    (r"musik", "music"),
+];
+
+impl IntentLanguage {
+    /// The configured language, else the one `lower` reads as.
+    pub fn resolve(lower: &str) -> Self {
+        crate::config::current()
+            .intent_language
+            .unwrap_or_else(|| Self::detect(lower))
+    }
+
+    /// The language whose common words `lower` uses most; English on a tie.
+    pub fn detect(lower: &str) -> Self {
+        let words: Vec<&str> = lower
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .collect();
+        let mut best = (Self::En, 0);
+        for &(language, stopwords) in STOPWORDS {
+            let mut hits = words.iter().filter(|w| stopwords.contains(w)).count();
+            hits += match language {
+                Self::Es => lower
+                    .matches(['ñ', '¿', '¡', 'á', 'é', 'í', 'ó', 'ú'])
+                    .count(),
+                Self::De => lower.matches(['ß', 'ä', 'ö', 'ü']).count(),
+                Self::En => 0,
+            };
+            if hits > best.1 {
+                best = (language, hits);
+            }
+        }
+        best.0
+    }
+
+    fn table(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::En => &[],
+            Self::Es => SPANISH,
+            Self::De => GERMAN,
+        }
+    }
+
+    /// `lower` with this language's phrasings replaced by their English
+    /// keywords, ready for the heuristic parser.
+    pub fn gloss(self, lower: &str) -> String {
+        let mut text = lower.to_string();
+        for (pattern, english) in self.table() {
+            let re = regex::Regex::new(&format!(r"\b(?:{})\b", pattern)).expect("valid regex");
+            text = re.replace_all(&text, *english).into_owned();
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_the_language_and_glosses_its_keywords() {
+        assert_eq!(
+            IntentLanguage::detect("quita las partes aburridas"),
+            IntentLanguage::Es
+        );
+        assert_eq!(
+            IntentLanguage::detect("entferne die langweiligen teile"),
+            IntentLanguage::De
+        );
+        assert_eq!(
+            IntentLanguage::detect("remove the boring parts"),
+            IntentLanguage::En
+        );
+        assert_eq!(
+            IntentLanguage::Es.gloss("quita las partes aburridas"),
+            "quita las partes boring"
+        );
+        assert_eq!(
+            IntentLanguage::De.gloss("fast alles mit untertiteln"),
+            "nearly alles mit subtitles"
+        );
+        assert_eq!("deutsch".parse(), Ok(IntentLanguage::De));
+    }
+}
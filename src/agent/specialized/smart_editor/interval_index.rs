@@ -0,0 +1,112 @@
+// SYNOID Smart Editor - Interval Index
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Overlap lookups between scenes and transcript segments. A 6-hour VOD has
+// thousands of scenes and tens of thousands of segments, so scanning one list
+// per entry of the other is far too slow; this sorts once and answers each
+// query with two binary searches.
+
+/// Sorted view of `(start, end)` intervals answering "which intervals overlap
+/// `[start, end)`" in O(log n + k).
+pub struct IntervalIndex {
+    /// Original position of each interval, in start-time order.
+    order: Vec<usize>,
+    starts: Vec<f64>,
+    ends: Vec<f64>,
+    /// Running maximum of `ends`, so the first candidate can be binary searched
+    /// even when long intervals contain shorter ones.
+    max_end: Vec<f64>,
+}
+
+impl IntervalIndex {
+    pub fn new(intervals: impl IntoIterator<Item = (f64, f64)>) -> Self {
+        let mut items: Vec<(usize, f64, f64)> = intervals
+            .into_iter()
+            .enumerate()
+            .map(|(i, (s, e))| (i, s, e))
+            .collect();
+        items.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+
+        let mut max_end = Vec::with_capacity(items.len());
+        let mut running = f64::NEG_INFINITY;
+        for &(_, _, end) in &items {
+            running = running.max(end);
+            max_end.push(running);
+        }
+
+        Self {
+            order: items.iter().map(|i| i.0).collect(),
+            starts: items.iter().map(|i| i.1).collect(),
+            ends: items.iter().map(|i| i.2).collect(),
+            max_end,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Original indices of the intervals with a non-empty overlap with
+    /// `[start, end)`, in ascending index order.
+    pub fn overlapping(&self, start: f64, end: f64) -> Vec<usize> {
+        if end <= start {
+            return Vec::new();
+        }
+        let first = self.max_end.partition_point(|&e| e <= start);
+        let last = self.starts.partition_point(|&s| s < end);
+        let mut hits: Vec<usize> = (first..last.max(first))
+            .filter(|&i| self.ends[i] > start && self.ends[i] > self.starts[i])
+            .map(|i| self.order[i])
+            .collect();
+        hits.sort_unstable();
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::specialized::smart_editor::test_support::XorShift;
+
+    fn naive_overlapping(intervals: &[(f64, f64)], start: f64, end: f64) -> Vec<usize> {
+        intervals
+            .iter()
+            .enumerate()
+            .filter(|(_, &(s, e))| e.min(end) > s.max(start))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    #[test]
+    fn overlapping_matches_naive_scan_on_random_intervals() {
+        let mut rng = XorShift(0x5EED_CAFE);
+        for _ in 0..200 {
+            let n = (rng.next_f64() * 60.0) as usize;
+            let intervals: Vec<(f64, f64)> = (0..n)
+                .map(|_| {
+                    let s = rng.next_f64() * 100.0;
+                    // Includes zero-length and inverted intervals
+                    (s, s + rng.next_f64() * 20.0 - 2.0)
+                })
+                .collect();
+            let index = IntervalIndex::new(intervals.iter().copied());
+            for _ in 0..20 {
+                let s = rng.next_f64() * 110.0 - 5.0;
+                let e = s + rng.next_f64() * 15.0;
+                assert_eq!(index.overlapping(s, e), naive_overlapping(&intervals, s, e));
+            }
+        }
+    }
+
+    #[test]
+    fn touching_intervals_do_not_overlap() {
+        let index = IntervalIndex::new([(0.0, 1.0), (1.0, 2.0), (2.0, 3.0)]);
+        assert_eq!(index.overlapping(1.0, 2.0), vec![1]);
+        assert!(index.overlapping(3.0, 4.0).is_empty());
+        assert!(index.overlapping(1.5, 1.5).is_empty());
+    }
+}
@@ -0,0 +1,241 @@
+// SYNOID Smart Editor - Intent Keywords
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Weighted keywords from the edit intent ("keep the boss fights", "cut
+// anything about sponsors") and the matching used to apply them to scenes.
+// Matching is case-insensitive on lightly stemmed words, so "fights" in the
+// request finds "fight" and "fighting" in the transcript.
+
+use super::interval_index::IntervalIndex;
+use super::types::Scene;
+use crate::agent::tools::transcription::TranscriptSegment;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Score added per unit of weight when a `Boost` keyword matches a scene.
+pub const KEYWORD_BOOST: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum KeywordMode {
+    /// Raise the scene score by `weight * KEYWORD_BOOST`.
+    #[default]
+    #[serde(alias = "boost")]
+    Boost,
+    /// Only scenes containing the term may be kept.
+    #[serde(alias = "require")]
+    Require,
+    /// Scenes containing the term are always dropped.
+    #[serde(alias = "exclude")]
+    Exclude,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Keyword {
+    pub term: String,
+    #[serde(default = "default_keyword_weight")]
+    pub weight: f64,
+    #[serde(default)]
+    pub mode: KeywordMode,
+}
+
+fn default_keyword_weight() -> f64 {
+    1.0
+}
+
+impl Keyword {
+    pub fn boost(term: impl Into<String>) -> Self {
+        Self {
+            term: term.into(),
+            weight: default_keyword_weight(),
+            mode: KeywordMode::Boost,
+        }
+    }
+
+    /// True when every word of the term appears, in order and adjacent, in
+    /// the already-stemmed `words`.
+    pub fn matches(&self, words: &[String]) -> bool {
+        let needle = stem_words(&self.term);
+        !needle.is_empty() && words.windows(needle.len()).any(|w| w == needle.as_slice())
+    }
+}
+
+/// Accepts both the current object form and the older flat list of strings
+/// (`"custom_keywords": ["goal"]`), which become weight-1 `Boost` keywords.
+pub fn deserialize_keywords<'de, D>(deserializer: D) -> Result<Vec<Keyword>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Entry {
+        Term(String),
+        Full(Keyword),
+    }
+
+    let entries = Option::<Vec<Entry>>::deserialize(deserializer)?.unwrap_or_default();
+    Ok(entries
+        .into_iter()
+        .map(|e| match e {
+            Entry::Term(term) => Keyword::boost(term),
+            Entry::Full(k) => k,
+        })
+        .filter(|k| !k.term.trim().is_empty())
+        .collect())
+}
+
+/// Reduce a word to a rough stem: plurals, "-ing" and "-ed" are stripped and
+/// a doubled final consonant is undone ("running" → "run").
+pub fn stem(word: &str) -> String {
+    let w = word.to_lowercase();
+    if let Some(s) = w.strip_suffix("ies").filter(|s| s.len() >= 2) {
+        format!("{}y", s)
+    } else if let Some(s) = w
+        .strip_suffix("es")
+        .filter(|s| ["x", "ch", "sh", "ss"].iter().any(|e| s.ends_with(e)))
+    {
+        s.to_string()
+    } else if let Some(s) = w.strip_suffix("ing").filter(|s| s.len() >= 3) {
+        undouble(s)
+    } else if let Some(s) = w.strip_suffix("ed").filter(|s| s.len() >= 3) {
+        undouble(s)
+    } else if let Some(s) = w
+        .strip_suffix('s')
+        .filter(|s| s.len() >= 3 && !s.ends_with('s') && !s.ends_with('u'))
+    {
+        s.to_string()
+    } else {
+        w
+    }
+}
+
+fn undouble(s: &str) -> String {
+    let b = s.as_bytes();
+    let n = b.len();
+    if n >= 2 && b[n - 1] == b[n - 2] && !b"aeioulsz".contains(&b[n - 1]) {
+        s[..n - 1].to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+/// Split `text` into stemmed words, dropping punctuation.
+pub fn stem_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|w| w.trim_matches('\''))
+        .filter(|w| !w.is_empty())
+        .map(stem)
+        .collect()
+}
+
+/// Stemmed words spoken during `scene` plus its vision tags.
+pub fn scene_words(
+    scene: &Scene,
+    transcript: Option<(&[TranscriptSegment], &IntervalIndex)>,
+) -> Vec<String> {
+    let mut words = Vec::new();
+    if let Some((segments, index)) = transcript {
+        for i in index.overlapping(scene.start_time, scene.end_time) {
+            words.extend(stem_words(&segments[i].text));
+        }
+    }
+    for tag in &scene.vision_tags {
+        words.extend(stem_words(tag));
+    }
+    words
+}
+
+/// Indices into `keywords` of the terms found in `words`.
+pub fn matched_keywords(keywords: &[Keyword], words: &[String]) -> Vec<usize> {
+    keywords
+        .iter()
+        .enumerate()
+        .filter(|(_, k)| k.matches(words))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Whether the Require/Exclude keywords allow a scene with these matches.
+pub fn passes_keyword_filters(keywords: &[Keyword], matched: &[usize]) -> bool {
+    keywords.iter().enumerate().all(|(i, k)| match k.mode {
+        KeywordMode::Boost => true,
+        KeywordMode::Require => matched.contains(&i),
+        KeywordMode::Exclude => !matched.contains(&i),
+    })
+}
+
+/// Score added by the matched `Boost` keywords.
+pub fn keyword_boost(keywords: &[Keyword], matched: &[usize]) -> f64 {
+    matched
+        .iter()
+        .filter(|&&i| keywords[i].mode == KeywordMode::Boost)
+        .map(|&i| keywords[i].weight * KEYWORD_BOOST)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyword(term: &str, weight: f64, mode: KeywordMode) -> Keyword {
+        Keyword {
+            term: term.to_string(),
+            weight,
+            mode,
+        }
+    }
+
+    #[test]
+    fn stemming_folds_common_inflections() {
+        for (a, b) in [
+            ("fights", "fight"),
+            ("Fighting", "fight"),
+            ("running", "run"),
+            ("jumped", "jump"),
+            ("bosses", "boss"),
+            ("matches", "match"),
+            ("stories", "story"),
+            ("boss", "boss"),
+        ] {
+            assert_eq!(stem(a), stem(b), "{} vs {}", a, b);
+        }
+        assert_ne!(stem("fight"), stem("flight"));
+    }
+
+    #[test]
+    fn multi_word_terms_match_adjacent_words_only() {
+        let k = Keyword::boost("Boss Fights");
+        assert!(k.matches(&stem_words("and then the boss fight started!")));
+        assert!(!k.matches(&stem_words("the boss wants a fight")));
+    }
+
+    #[test]
+    fn legacy_string_keywords_become_boosts() {
+        let parsed: Vec<Keyword> = deserialize_keywords(serde_json::json!([
+            "goal",
+            {"term": "sponsor", "mode": "exclude"},
+            {"term": "clutch", "weight": 2.5}
+        ]))
+        .unwrap();
+        assert_eq!(parsed[0], Keyword::boost("goal"));
+        assert_eq!(parsed[1], keyword("sponsor", 1.0, KeywordMode::Exclude));
+        assert_eq!(parsed[2], keyword("clutch", 2.5, KeywordMode::Boost));
+        assert!(deserialize_keywords(serde_json::Value::Null).unwrap().is_empty());
+    }
+
+    #[test]
+    fn filters_and_boosts_follow_modes() {
+        let keywords = vec![
+            keyword("fight", 2.0, KeywordMode::Boost),
+            keyword("boss", 1.0, KeywordMode::Require),
+            keyword("sponsor", 1.0, KeywordMode::Exclude),
+        ];
+        let hits = |text: &str| matched_keywords(&keywords, &stem_words(text));
+
+        let both = hits("Boss fights are the best");
+        assert_eq!(both, vec![0, 1]);
+        assert!(passes_keyword_filters(&keywords, &both));
+        assert_eq!(keyword_boost(&keywords, &both), 2.0 * KEYWORD_BOOST);
+
+        assert!(!passes_keyword_filters(&keywords, &hits("just a fight")));
+        assert!(!passes_keyword_filters(&keywords, &hits("this boss is brought to you by our sponsor")));
+    }
+}
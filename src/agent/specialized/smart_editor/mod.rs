@@ -2,10 +2,45 @@ pub mod types;
 pub mod scene_ops;
 pub mod filter_ops;
 pub mod transition_ops;
+pub mod edit_plan;
+pub mod interval_index;
+pub mod keywords;
+pub mod draft;
+pub mod exclusions;
+pub mod seed;
+pub mod explain;
+pub mod tune;
+pub mod review;
+pub mod steps;
+pub mod bookends;
+pub mod funny;
+pub mod chapters;
+pub mod external_scorer;
+pub mod reedit;
+pub mod analysis_tier;
+pub mod intent_language;
+#[cfg(test)]
+mod test_support;
 pub use types::*;
 pub use scene_ops::*;
 pub use filter_ops::*;
 pub use transition_ops::*;
+pub use edit_plan::{EditPlan, PlanSegment};
+pub use interval_index::IntervalIndex;
+pub use keywords::{Keyword, KeywordMode};
+pub use draft::{CutsKey, DraftCuts, RenderMode};
+pub use exclusions::{Exclusion, ExclusionOrigin};
+pub use seed::EditDecisions;
+pub use explain::{explanation_path, EditExplanation, SceneScore, ScoringReport};
+pub use tune::{ComponentStats, TuningStats};
+pub use steps::{EditStep, StepOutcome};
+pub use bookends::Bookends;
+pub use funny::{FunnyKind, FunnyMoment};
+pub use chapters::{Chapter, ChapterMode};
+pub use external_scorer::ExternalScorer;
+pub use reedit::{OutputStamp, Reedit};
+pub use analysis_tier::AnalysisTier;
+pub use intent_language::IntentLanguage;
 // SYNOID Smart Editor Refactoring
 
 // SYNOID Smart Editor - AI-Powered Intent-Based Video Editing
@@ -15,16 +50,18 @@ pub use transition_ops::*;
 // It analyzes scenes, scores them against user intent, and generates trimmed output.
 
 use std::sync::Arc;
+use crate::agent::engines::proc::{self, ProcClass};
 use crate::agent::engines::process_utils::CommandExt;
 use crate::agent::tools::production_tools;
+use crate::agent::tools::scan_cache::{self, ScanCache};
 use crate::agent::tools::source_tools;
 use crate::agent::tools::transcription::{TranscriptSegment, TranscriptionEngine};
 use crate::agent::ai_systems::gpt_oss_bridge::SynoidAgent;
 use crate::agent::video_processing::animator::Animator;
+use crate::agent::video_processing::effects::EffectsRegistry;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use tokio::process::Command;
 use tracing::{error, info, warn};
 
 /// Strip the Windows extended-length path prefix (`\\?\` or `//?/`) from a
@@ -41,19 +78,103 @@ fn strip_unc_prefix(p: PathBuf) -> PathBuf {
     PathBuf::from(stripped)
 }
 
+/// Receives each progress line of a running edit.
+pub type ProgressCallback = Box<dyn Fn(&str) + Send + Sync>;
+
 /// Density of the edit - how much to keep vs how much to prune
+#[allow(clippy::too_many_arguments)]
 pub async fn smart_edit(
+    input: &Path,
+    intent_text: &str,
+    output: &Path,
+    funny_mode: bool,
+    progress_callback: Option<ProgressCallback>,
+    pre_scanned_scenes: Option<Vec<Scene>>,
+    pre_scanned_transcript: Option<Vec<TranscriptSegment>>,
+    learned_pattern: Option<crate::agent::learning::EditingPattern>,
+    animator: Option<Arc<Animator>>,
+    enable_subtitles_override: bool,
+    enable_censoring_override: bool,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    smart_edit_with_mode(
+        input,
+        intent_text,
+        output,
+        funny_mode,
+        progress_callback,
+        pre_scanned_scenes,
+        pre_scanned_transcript,
+        learned_pattern,
+        animator,
+        enable_subtitles_override,
+        enable_censoring_override,
+        RenderMode::from_config(),
+    )
+    .await
+}
+
+/// `intent_text` parsed the way a render parses it: by the LLM, or by the
+/// heuristic parser in a seeded run so the parse is repeatable.
+pub async fn parse_intent(intent_text: &str) -> EditIntent {
+    if crate::config::current().seed.is_some() {
+        info!("[SMART] 🎲 Seeded run: using the heuristic intent parser");
+        EditIntent::from_text(intent_text)
+    } else {
+        EditIntent::from_llm(intent_text).await
+    }
+}
+
+/// `smart_edit` with an explicit draft/final choice instead of the
+/// configured `draft` setting.
+#[allow(clippy::too_many_arguments)]
+pub async fn smart_edit_with_mode(
     input: &Path,
     intent_text: &str,
     output: &Path,
     _funny_mode: bool,
-    progress_callback: Option<Box<dyn Fn(&str) + Send + Sync>>,
+    progress_callback: Option<ProgressCallback>,
     pre_scanned_scenes: Option<Vec<Scene>>,
     pre_scanned_transcript: Option<Vec<TranscriptSegment>>,
     learned_pattern: Option<crate::agent::learning::EditingPattern>,
     _animator: Option<Arc<Animator>>,
     enable_subtitles_override: bool,
     enable_censoring_override: bool,
+    mode: RenderMode,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    // 1. Analyze Intent
+    let mut intent = parse_intent(intent_text).await;
+    // UI checkboxes always win — override whatever the LLM/heuristic parsed
+    intent.enable_subtitles = enable_subtitles_override;
+    intent.censor_profanity = enable_censoring_override;
+
+    smart_edit_with_intent(
+        input,
+        intent,
+        intent_text,
+        output,
+        progress_callback,
+        pre_scanned_scenes,
+        pre_scanned_transcript,
+        learned_pattern,
+        mode,
+    )
+    .await
+}
+
+/// Edit with an already-structured intent, skipping LLM parsing entirely.
+/// `intent_key` names the intent in the draft cut cache: the original text
+/// for parsed intents, the intent's JSON for structured ones.
+#[allow(clippy::too_many_arguments)]
+pub async fn smart_edit_with_intent(
+    input: &Path,
+    intent: EditIntent,
+    intent_key: &str,
+    output: &Path,
+    progress_callback: Option<ProgressCallback>,
+    pre_scanned_scenes: Option<Vec<Scene>>,
+    pre_scanned_transcript: Option<Vec<TranscriptSegment>>,
+    learned_pattern: Option<crate::agent::learning::EditingPattern>,
+    mode: RenderMode,
 ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
     let log = move |msg: &str| {
         info!("{}", msg);
@@ -62,13 +183,11 @@ pub async fn smart_edit(
         }
     };
 
-    log("[SMART] 🧠 Starting AI-powered edit...");
-
-    // 1. Analyze Intent
-    let mut intent = EditIntent::from_llm(intent_text).await;
-    // UI checkboxes always win — override whatever the LLM/heuristic parsed
-    intent.enable_subtitles = enable_subtitles_override;
-    intent.censor_profanity = enable_censoring_override;
+    log(if mode.is_draft() {
+        "[SMART] 🧠 Starting AI-powered edit (draft preview render)..."
+    } else {
+        "[SMART] 🧠 Starting AI-powered edit..."
+    });
 
     // 1.1 Render Remotion elements if requested (commented out due to undefined variables)
     let remotion_segment: Option<PathBuf> = None;
@@ -141,8 +260,49 @@ pub async fn smart_edit(
 
     // ... (Audio enhancement remains same)
 
+    // A seeded run replays the decisions an earlier run with the same seed
+    // recorded in the plan, or makes fresh ones that do not drift with learning.
+    let settings = crate::config::current();
+    let seed = settings.seed;
+    let scorer = settings.external_scorer;
+    let replay = seed.and_then(|seed| {
+        EditDecisions::load_matching(output, seed, input, intent_key, scorer.as_deref())
+    });
+    if let Some(d) = &replay {
+        log(&format!(
+            "[SMART] 🎲 Replaying the decisions recorded for seed {} in the edit plan",
+            d.seed
+        ));
+    }
+    let intent = match &replay {
+        Some(d) => d.intent.clone(),
+        None => intent,
+    };
+
+    // Probe the intro/outro now so a bad file fails the run before any work
+    let bookends = Bookends::resolve(&intent).await?;
+    if let Some(b) = &bookends {
+        log(&format!(
+            "[SMART] 🎬 Intro/outro attached after the edit ({:.1}s in total)",
+            b.duration()
+        ));
+    }
+
+    // An input SYNOID already edited: don't redo what it already has
+    let reedit = reedit::detect(input, production_tools::ENHANCED_LOUDNESS_LUFS).await;
+    if let Some(r) = &reedit {
+        for line in r.warnings() {
+            log(&format!("[SMART] ♻️ {}", line));
+        }
+    }
+
     // Load Strategy
-    let mut config = EditingStrategy::load();
+    let mut config = match (&replay, seed) {
+        (Some(d), _) => d.strategy.clone(),
+        (None, Some(_)) => EditingStrategy::default(),
+        (None, None) => EditingStrategy::load(),
+    };
+    let base_strategy = config.clone();
 
     // APPLY LEARNED PATTERN IF AVAILABLE
     if let Some(pattern) = &learned_pattern {
@@ -194,12 +354,7 @@ pub async fn smart_edit(
     // This creates a clean audio spine for the edit
 
     // Use a deterministic prefix derived from the input path so segment dirs survive across runs.
-    let job_prefix_owned: String = {
-        use std::hash::{Hash, Hasher};
-        let mut h = std::collections::hash_map::DefaultHasher::new();
-        input.hash(&mut h);
-        format!("{:08x}", h.finish() & 0xFFFFFFFF)
-    };
+    let job_prefix_owned = job_prefix_for(input);
     let job_prefix = job_prefix_owned.as_str();
 
     let input_parent = input.parent().ok_or("Input path has no parent")?;
@@ -213,29 +368,91 @@ pub async fn smart_edit(
     // The raw enhanced WAV lives in the temp dir as a build cache (not shown to the user).
     let audio_path = input_parent.join(format!("synoid_{}_audio.wav", job_prefix));
     let audio_meta_path = input_parent.join(format!("synoid_{}_audio.meta", job_prefix));
-    // Internal build cache — used to re-censor without re-enhancing.
-    let enhanced_audio_path = work_dir_buf.join("audio_enhanced.wav");
+
+    // 0.5 Duck the music track under speech before anything listens to the
+    //     audio; the ducked mixdown then stands in for the source's audio.
+    let ducked_audio = match intent.duck_music {
+        Some(duck) => {
+            let speech = pre_scanned_transcript
+                .as_deref()
+                .map(production_tools::speech_regions)
+                .unwrap_or_default();
+            let ducked = work_dir_buf.join("audio_ducked.wav");
+            log("[SMART] 🎚️ Ducking music under speech...");
+            match production_tools::duck_music_under_speech(input, &ducked, &speech, &duck).await {
+                Ok(method) => {
+                    log(&format!(
+                        "[SMART] 🎚️ Music ducked {:.0} dB under speech ({:?})",
+                        duck.depth_db, method
+                    ));
+                    Some(ducked)
+                }
+                Err(e) => {
+                    warn!("[SMART] Music ducking skipped: {}", e);
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+    let audio_input: &Path = ducked_audio.as_deref().unwrap_or(input);
+    // A ducked and an unducked combined audio file must never stand in for each other.
+    let duck_tag = if ducked_audio.is_some() { ",ducked" } else { "" };
+    let cached_tag = fs::read_to_string(&audio_meta_path).unwrap_or_default();
+    if cached_tag.trim().ends_with(",ducked") != ducked_audio.is_some() {
+        let _ = fs::remove_file(&audio_path);
+        let _ = fs::remove_file(&audio_meta_path);
+    }
+
+    // Scans of this exact file from earlier edits are reused; the key is
+    // its content hash, so an edited or re-exported file starts fresh.
+    let scans = ScanCache::default();
+    let scan_key = match scans.key(input).await {
+        Ok(key) => Some(key),
+        Err(e) => {
+            warn!("[SMART] Could not hash input for the scan cache: {}", e);
+            None
+        }
+    };
+
+    // Internal build cache — used to re-censor without re-enhancing. The
+    // plain enhancement depends only on the input, so it lives in the scan
+    // cache and outlives this edit; a ducked mix depends on the intent.
+    let mut enhanced_audio_path = match (&scan_key, &ducked_audio) {
+        (Some(key), None) => scans.artifact(key, "audio_enhanced.wav"),
+        (_, Some(_)) => work_dir_buf.join("audio_enhanced_ducked.wav"),
+        (None, None) => work_dir_buf.join("audio_enhanced.wav"),
+    };
 
     let enhanced_cached = fs::metadata(&enhanced_audio_path)
         .map(|m| m.len() > 0)
         .unwrap_or(false);
-    if enhanced_cached {
+    let skip_normalization = reedit.as_ref().is_some_and(|r| r.skip_normalization);
+    if skip_normalization {
+        log("[SMART] 🔇 Input audio is already normalized; using it as is.");
+    } else if enhanced_cached {
         log("[SMART] ⚡ Reusing cached enhanced audio (build cache).");
     } else {
         log("[SMART] 🎙️ Enhancing audio (High-Pass + Compression + Normalization)...");
-        match production_tools::enhance_audio(input, &enhanced_audio_path).await {
+        match production_tools::enhance_audio(audio_input, &enhanced_audio_path).await {
             Ok(_) => log("[SMART] Audio enhanced successfully."),
             Err(e) => {
                 warn!("[SMART] Audio enhancement failed ({}), using original.", e);
+                // A partial WAV must not pass for a cached enhancement next run.
+                let _ = fs::remove_file(&enhanced_audio_path);
             }
         }
     }
 
-    let mut use_enhanced_audio = if let Ok(metadata) = fs::metadata(&enhanced_audio_path) {
-        metadata.len() > 0
-    } else {
-        false
-    };
+    let mut use_enhanced_audio = !skip_normalization
+        && fs::metadata(&enhanced_audio_path).is_ok_and(|m| m.len() > 0);
+    // Recorded in the output's stamp
+    let normalized = use_enhanced_audio || skip_normalization;
+    // Enhancement failed: the ducked mix still beats the raw source audio.
+    if let (false, Some(ducked)) = (use_enhanced_audio, &ducked_audio) {
+        enhanced_audio_path = ducked.clone();
+        use_enhanced_audio = true;
+    }
 
     // Transcribe — Check for existing SRT files first, then attempt transcription
     // Fall back to extracting audio directly from the raw input if needed.
@@ -246,7 +463,17 @@ pub async fn smart_edit(
             t.len()
         ));
         Some(t)
+    } else if let Some(t) = scan_key
+        .as_deref()
+        .and_then(|key| scans.load::<Vec<TranscriptSegment>>(key, "transcript"))
+    {
+        log(&format!(
+            "[SMART] ⚡ Reusing cached transcript of this file ({} segments)",
+            t.len()
+        ));
+        Some(t)
     } else {
+        let fresh = {
         // Try to find and reuse existing SRT file (saves massive time!)
         // IMPORTANT: Only load SRT files that are time-aligned to the INPUT video.
         // output.srt / synoid_subtitles.srt are remapped to the *edited* output
@@ -344,6 +571,7 @@ pub async fn smart_edit(
         match TranscriptionEngine::new(None).await {
             Err(e) => {
                 warn!("[SMART] Transcription engine init failed: {}", e);
+                crate::agent::tools::transcription::report_unavailable(&e.to_string());
                 None
             }
             Ok(engine) => {
@@ -372,12 +600,46 @@ pub async fn smart_edit(
                     }
                     Err(e) => {
                         warn!("[SMART] Transcription failed: {}", e);
+                        crate::agent::tools::transcription::report_unavailable(&e.to_string());
                         None
                     }
                 }
             }
         }
         }
+        };
+        if let (Some(key), Some(t)) = (&scan_key, &fresh) {
+            scans.store(key, "transcript", t);
+        }
+        fresh
+    };
+
+    // Without a transcript, loud stretches of audio stand in for speech
+    // while choosing scenes, so silence can still be cut.
+    let (analysis_tier, stand_in) = match &transcript {
+        Some(_) => (AnalysisTier::Transcript, None),
+        None => {
+            let duration = source_tools::get_video_duration(input).await.unwrap_or(0.0);
+            match analysis_tier::sound_spans(input, duration).await {
+                Ok(spans) => {
+                    log(&format!(
+                        "[SMART] ⚠️ No transcript: semantic editing is off; {} stretch(es) of sound stand in for speech.",
+                        spans.len()
+                    ));
+                    (
+                        AnalysisTier::AudioEnergy,
+                        Some(analysis_tier::stand_in_transcript(&spans)),
+                    )
+                }
+                Err(e) => {
+                    log(&format!(
+                        "[SMART] ⚠️ No transcript and no audio to measure ({}); cutting on scene changes alone.",
+                        e
+                    ));
+                    (AnalysisTier::ScenesOnly, None)
+                }
+            }
+        }
     };
 
     log(&format!(
@@ -404,7 +666,7 @@ pub async fn smart_edit(
             // profanity list hasn't changed since it was generated.
             let current_list_fingerprint = {
                 let words = get_profanity_word_list();
-                format!("censored,n={}", words.len())
+                format!("censored,n={}{}", words.len(), duck_tag)
             };
             let mut audio_cached = fs::metadata(&audio_path)
                 .map(|m| m.len() > 0)
@@ -501,7 +763,7 @@ pub async fn smart_edit(
                         if let Err(e) = fs::copy(&audio_source, &audio_path) {
                             warn!("[SMART] Could not write combined audio: {}", e);
                         } else {
-                            let _ = fs::write(&audio_meta_path, "enhanced");
+                            let _ = fs::write(&audio_meta_path, format!("enhanced{}", duck_tag));
                             final_enhanced_audio_path = audio_path.clone();
                         }
                     }
@@ -518,7 +780,7 @@ pub async fn smart_edit(
             if let Err(e) = fs::copy(&audio_source, &audio_path) {
                 warn!("[SMART] Could not write combined audio: {}", e);
             } else {
-                let _ = fs::write(&audio_meta_path, "enhanced");
+                let _ = fs::write(&audio_meta_path, format!("enhanced{}", duck_tag));
                 final_enhanced_audio_path = audio_path.clone();
             }
         } else if audio_exists {
@@ -526,583 +788,360 @@ pub async fn smart_edit(
         }
     }
 
-    // 2. Detect scenes
-    log("[SMART] 🔍 Analyzing video scenes...");
-    let mut scenes = if let Some(s) = pre_scanned_scenes {
+    // 2-4. Choose the scenes to keep. A final render that follows a draft of
+    // the same edit reuses the draft's cuts instead of analysing again, so
+    // the two land on exactly the same frames.
+    let draft_cuts_path = DraftCuts::path_for(input_parent, job_prefix);
+    let cuts_key = CutsKey::new(intent_key, seed, &config, scorer.as_deref());
+    let reused_cuts = match (mode, &pre_scanned_scenes) {
+        (RenderMode::Final, None) if replay.is_none() => {
+            DraftCuts::load_matching(&draft_cuts_path, input, &cuts_key)
+        }
+        _ => None,
+    };
+    let reused_draft = reused_cuts.is_some();
+    let replayed_cuts = replay.as_ref().map(|d| d.cuts.clone());
+    let selection = match (replayed_cuts, reused_cuts) {
+        (Some(cuts), _) => {
+            log(&format!(
+                "[SMART] 🎲 Reusing the {} segment(s) recorded for this seed (skipping scene analysis)",
+                cuts.kept.len()
+            ));
+            cuts.into_selection()
+        }
+        (None, Some(cuts)) => {
+            log(&format!(
+                "[SMART] ♻️ Reusing the {} segment(s) chosen for the draft render (skipping scene analysis)",
+                cuts.kept.len()
+            ));
+            cuts.into_selection()
+        }
+        (None, None) => {
+            // The bookends take their share of a duration target
+            let mut selection_intent = intent.clone();
+            if let Some(b) = &bookends {
+                selection_intent.target_duration = b.fit_target(intent.target_duration)?;
+            }
+            // Re-editing a bookended render (a later step of a multi-step
+            // request): its old intro and outro are not footage.
+            if let Some((intro, outro)) = EditPlan::load_for(input).and_then(|p| p.bookends) {
+                if intro > 0.0 {
+                    selection_intent.exclude_ranges.push((0.0, intro));
+                }
+                if outro > 0.0 {
+                    selection_intent.exclude_ranges.push((-outro, 0.0));
+                }
+            }
+            select_scenes(
+                input,
+                &selection_intent,
+                &config,
+                transcript.as_deref().or(stand_in.as_deref()),
+                learned_pattern.as_ref(),
+                pre_scanned_scenes,
+                &log,
+            )
+            .await?
+        }
+    };
+    if mode.is_draft() {
+        match DraftCuts::new(input, cuts_key.clone(), &selection).save(&draft_cuts_path) {
+            Ok(()) => log("[SMART] 📝 Draft cuts saved; the final render will reuse them."),
+            Err(e) => warn!("[SMART] Could not save draft cuts (non-fatal): {}", e),
+        }
+    }
+    if mode == RenderMode::Analyze {
+        DraftCuts::new(input, cuts_key.clone(), &selection).save(&draft_cuts_path)?;
+        let mut plan = EditPlan::from_kept_scenes(input, output, &selection.kept, &[], 0.0);
+        plan.exclusions = selection.exclusions;
+        plan.scoring = selection.scoring;
+        plan.analysis_tier = Some(analysis_tier);
+        plan.save()?;
+        let summary = format!(
+            "Scene analysis complete: {} segment(s) kept from {} scene(s). Analysis: {}.",
+            selection.kept.len(),
+            selection.scene_count,
+            analysis_tier.describe()
+        );
+        log(&format!("[SMART] 🔎 {}", summary));
+        return Ok(summary);
+    }
+    let recorded_cuts = seed.map(|_| DraftCuts::new(input, cuts_key, &selection));
+    let SceneSelection {
+        kept: scenes_to_keep,
+        cut_points,
+        removed,
+        scene_count,
+        exclusions,
+        scoring,
+    } = selection;
+
+    // Determine neuroplasticity-driven transition style
+    let neuro = crate::agent::neuroplasticity::Neuroplasticity::new();
+    let neuro_level = neuro.adaptation_level();
+    // transition_type drives xfade selection
+    // transition_dur = subtle (0.08-0.25 s) — enough to hide the cut, not
+    // enough to look like a slow film wipe
+    let neuro_transition_dur: f64 = (0.08 + config.continuity_boost * 0.20).clamp(0.08, 0.28);
+    let neuro_transition_name: String = match (&replay, seed) {
+        (Some(d), _) => d.transition.clone(),
+        (None, Some(seed)) => seed::seeded_transition(seed).to_string(),
+        (None, None) => match neuro_level {
+            "Baseline" => "fade",
+            "Accelerated" => "fade",
+            "Hyperspeed" => "slideleft",
+            "Neural Overdrive" => "wiperight",
+            "Singularity" => "pixelize",
+            _ => "fade",
+        }
+        .to_string(),
+    };
+    if seed.is_some() {
         log(&format!(
-            "[SMART] Using pre-scanned scenes ({} scenes)",
-            s.len()
+            "[SMART] 🎲 Seeded transition: {} @ {:.2}s",
+            neuro_transition_name, neuro_transition_dur
         ));
-        s
     } else {
-        detect_scenes(input, config.scene_threshold).await?
+        log(&format!(
+            "[SMART] 🧠 Neuroplasticity transition: {} @ {:.2}s ({} level)",
+            neuro_transition_name, neuro_transition_dur, neuro_level
+        ));
+    }
+
+    // work_dir IS already the synoid_temp_{prefix} folder; segments live inside it.
+    // Draft segments get their own folder so they never pass for final ones.
+    let segments_dir = if mode.is_draft() {
+        work_dir.join("draft_segments")
+    } else {
+        work_dir.to_path_buf()
     };
+    let total_segments = scenes_to_keep.len();
 
-    // 2.5 Refine scenes with transcript (Split by silences)
-    if let Some(t) = &transcript {
-        log("[SMART] 🛠️ Refining scene boundaries with transcript gaps...");
-        scenes = refine_scenes_with_transcript(scenes, t);
+    // Fingerprint the current scene selection so we can detect if scenes changed between runs.
+    // Format: "start,end" per line, one line per scene — fast to compare with fs::read_to_string.
+    let scene_fingerprint: String = scenes_to_keep.iter()
+        .map(|s| format!("{:.6},{:.6}", s.start_time, s.end_time))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let fingerprint_path = segments_dir.join("scene_fingerprint.txt");
+    let cached_fingerprint = if segments_dir.exists() {
+        fs::read_to_string(&fingerprint_path).ok()
+    } else {
+        None
+    };
+    let fingerprint_matches = cached_fingerprint.as_deref() == Some(scene_fingerprint.as_str());
+
+    // Check if all segments from a previous run already exist — reuse them to save time.
+    // Fingerprint must match to ensure the cached segments correspond to the current edit.
+    let all_segs_cached = fingerprint_matches && segments_dir.exists() && {
+        (0..total_segments).all(|i| {
+            let p = segments_dir.join(format!("seg_{:04}.mp4", i));
+            p.exists() && fs::metadata(&p).map(|m| m.len() > 1000).unwrap_or(false)
+        })
+    };
+
+    if all_segs_cached {
+        log(&format!(
+            "[SMART] ⚡ Reusing {} cached segments from previous run (skipping re-cut).",
+            total_segments
+        ));
+    } else {
+        if segments_dir.exists() {
+            fs::remove_dir_all(&segments_dir)?;
+        }
+        fs::create_dir_all(&segments_dir)?;
     }
 
-    // 2.8 Semantic Vision Scan (rate-limited, sampled)
-    // Cap at 40 frames to stay within Gemini free-tier (1500 req/day, 15 RPM).
-    // Sample evenly across all eligible scenes so the whole video is represented.
-    const MAX_VISION_FRAMES: usize = 40;
-    log("[SMART] 👁️ Performing sampled vision scan on scenes...");
+    // Only the encode shares the GPU with other renders; drafts encode on
+    // the CPU and skip the line.
+    let gpu_slot = if mode.is_draft() {
+        None
+    } else {
+        if crate::gpu_backend::gpu_slots().await.is_full() {
+            log(crate::gpu_backend::WAITING_FOR_SLOT);
+        }
+        let holder = format!("render {}", output.file_name().unwrap_or_default().to_string_lossy());
+        crate::gpu_backend::acquire_encode_slot(&holder).await
+    };
 
-    let agent = Arc::new(SynoidAgent::new("http://localhost:11434", "llava:latest"));
+    log("[SMART] ✂️ Assembling segments with single-pass render...");
 
-    let all_eligible: Vec<(usize, f64, f64)> = scenes.iter().enumerate()
-        .filter(|(_, s)| s.duration >= 2.0)
-        .map(|(i, s)| (i, s.start_time, s.end_time))
-        .collect();
+    // Commentary Generator removed (funny_engine deprecated)
 
-    // Even stride sampling: pick at most MAX_VISION_FRAMES spread across the whole list
-    let stride = (all_eligible.len() / MAX_VISION_FRAMES).max(1);
-    let scenes_to_scan: Vec<(usize, f64, f64)> = all_eligible
-        .into_iter()
-        .enumerate()
-        .filter(|(idx, _)| idx % stride == 0)
-        .map(|(_, v)| v)
-        .take(MAX_VISION_FRAMES)
-        .collect();
+    // NVENC hardware encoder session limits:
+    // - GeForce consumer GPUs: max 2-3 concurrent sessions (driver enforced)
+    // - Quadro/Tesla: higher limits (8+)
+    // Use conservative limit to prevent "incompatible client key" and OOM errors
+    let gpu_ctx = crate::gpu_backend::get_gpu_context().await;
+    let max_concurrency = if gpu_ctx.has_gpu() {
+        2  // Conservative limit for NVENC consumer GPUs
+    } else {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4)
+            .clamp(2, 6)
+    };
+    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(max_concurrency));
+    let mut tasks = Vec::with_capacity(total_segments);
 
-    let total_to_scan = scenes_to_scan.len();
-    log(&format!(
-        "[SMART] Vision Scan: sampling {}/{} scenes (stride {})",
-        total_to_scan, scenes.len(), stride
-    ));
+    for (i, scene) in scenes_to_keep.iter().enumerate() {
+        let seg_path = segments_dir.join(format!("seg_{:04}.mp4", i));
 
-    // Sequential with a small inter-call delay to stay under 15 RPM
-    for (completed, (i, start_time, end_time)) in scenes_to_scan.into_iter().enumerate() {
-        let mid_time = start_time + (end_time - start_time) / 2.0;
-        let frame_path = format!("temp_frame_{}_{}.jpg", start_time.to_bits(), end_time.to_bits());
+        // Skip re-encoding segments that already exist from cache
+        if all_segs_cached {
+            tasks.push(tokio::spawn(async move {
+                let dur = source_tools::get_video_duration(&seg_path).await.unwrap_or(0.0);
+                Some((seg_path, dur))
+            }));
+            continue;
+        }
+        let scene_duration = scene.duration;
+        let scene_start = scene.start_time;
+
+        // Clone for move into task
         let input_path = input.to_path_buf();
+        let enhanced_path = final_enhanced_audio_path.clone();
+        let permit = semaphore.clone().acquire_owned().await.unwrap();
 
-        let extract_status = tokio::process::Command::new("ffmpeg")
-            .stealth()
-            .args(["-y", "-ss", &mid_time.to_string(), "-i",
-                   input_path.to_str().unwrap_or_default(),
-                   "-frames:v", "1", "-q:v", "2", &frame_path])
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .status()
-            .await;
+        let handle = tokio::spawn(async move {
+            let mut cmd = proc::ffmpeg(ProcClass::Render);
+            cmd.stealth();
+            cmd.arg("-y")
+                .arg("-hide_banner")
+                .arg("-loglevel")
+                .arg("error")
+                .arg("-nostdin");
 
-        if let Ok(st) = extract_status {
-            if st.success() {
-                if let Ok(desc) = crate::agent::vision_tools::describe_frame_multi_provider(
-                    &agent, &PathBuf::from(&frame_path), mid_time,
-                ).await {
-                    if !desc.tags.is_empty() {
-                        scenes[i].vision_tags = desc.tags;
-                    }
-                }
+            let gpu_ctx = crate::gpu_backend::get_gpu_context().await;
+
+            // Enable hardware decode acceleration if available
+            if let Some(hwaccel) = gpu_ctx.ffmpeg_hwaccel() {
+                cmd.arg("-hwaccel").arg(hwaccel);
             }
-        }
-        let _ = tokio::fs::remove_file(&frame_path).await;
 
-        if (completed + 1) % 10 == 0 || completed + 1 == total_to_scan {
-            log(&format!("[SMART] Vision progress: {}/{} frames analyzed", completed + 1, total_to_scan));
-        }
-        // ~4s gap between calls keeps us safely under 15 RPM (= 1 req/4s)
-        if completed + 1 < total_to_scan {
-            tokio::time::sleep(tokio::time::Duration::from_secs(4)).await;
-        }
-    }
-
-    // 3. Score scenes based on intent AND transcript
-    log("[SMART] 📊 Scoring scenes based on semantic data...");
-
-    // Calculate total duration from scenes if possible, or use end time of last scene
-    let total_duration = scenes.last().map(|s| s.end_time).unwrap_or(0.0);
-
-    score_scenes(
-        &mut scenes,
-        &intent,
-        transcript.as_deref(),
-        &config,
-        total_duration,
-    );
-
-    // 3.5 ML Pacing Refinement
-    if let Some(pattern) = &learned_pattern {
-        let target_dur = pattern.avg_scene_duration;
-        let strictness = pattern.music_sync_strictness;
-
-        for scene in scenes.iter_mut() {
-            let dur_ratio = scene.duration / target_dur;
+            // Accurate input-seeking (-ss and -t before -i) prevents frame doubling and lag
+            cmd.arg("-ss").arg(&scene_start.to_string());
+            cmd.arg("-t").arg(&scene_duration.to_string());
+            cmd.arg("-i")
+                .arg(production_tools::safe_arg_path(&input_path));
 
-            // A. Boost scenes that match the learned pacing (within 20% tolerance)
-            // BUT ONLY IF they are already somewhat decent (score > 0.2)
-            if scene.score > 0.2 {
-                let diff = (scene.duration - target_dur).abs();
-                if diff < target_dur * 0.2 {
-                    // Verify context allows it - don't boost long boring scenes just because they match avg
-                    scene.score = (scene.score + 0.1).clamp(0.0, 1.0);
-                }
+            if use_enhanced_audio {
+                cmd.arg("-ss").arg(&scene_start.to_string());
+                cmd.arg("-t").arg(&scene_duration.to_string());
+                cmd.arg("-i")
+                    .arg(production_tools::safe_arg_path(&enhanced_path));
             }
 
-            // B. PENALIZE scenes that deviate too much (too long)
-            // If strictness is high, we hate long scenes unless they are "Action" or "Speech" heavy (high score)
-            if dur_ratio > 2.0 {
-                // It's double the average length.
-                // If it's a really good scene (score > 0.7), let it slide slightly.
-                // If it's mediocre (score < 0.5), HAMMER IT.
-                let penalty = if scene.score < 0.5 {
-                    0.2 * strictness // Heavy penalty for boring long scenes
-                } else {
-                    0.05 * strictness // Light penalty for good long scenes
-                };
-                scene.score = (scene.score - penalty).clamp(0.0, 1.0);
-            }
+            // Mapping
+            cmd.arg("-map").arg("0:v"); // Video from input 0
 
-            // C. PENALIZE scenes that deviate too much (too short)
-            // Only if we aren't in "fast" mode
-            if target_dur > 5.0 && dur_ratio < 0.3 {
-                scene.score = (scene.score - 0.1 * strictness).clamp(0.0, 1.0);
+            if use_enhanced_audio {
+                cmd.arg("-map").arg("1:a:0"); // Audio from input 1 (enhanced)
+            } else {
+                cmd.arg("-map").arg("0:a:0"); // Original audio
             }
-        }
-    }
 
-    // 4. Filter scenes to keep (score > threshold)
-    let mut keep_threshold = config.min_scene_score;
-    let total_before_filtering = scenes.len();
-    let mut scenes_to_keep: Vec<Scene> = Vec::new();
+            if mode.is_draft() {
+                // Draft: 480p ultrafast on the CPU, same cut points as the final.
+                cmd.args(draft::DRAFT_VIDEO_ARGS);
+                cmd.arg("-vf").arg(mode.segment_video_filter(scene_duration));
+                if let Some(af) = mode.segment_audio_filter(scene_duration) {
+                    cmd.arg("-af").arg(af);
+                }
+                cmd.arg("-c:a").arg("aac").arg("-b:a").arg(draft::DRAFT_AUDIO_BITRATE);
+            } else {
+                let neuro = crate::agent::neuroplasticity::Neuroplasticity::new();
+                cmd.arg("-c:v").arg(gpu_ctx.ffmpeg_encoder());
+                cmd.arg("-pix_fmt").arg("yuv420p");
+                for flag in gpu_ctx.neuroplastic_ffmpeg_flags(neuro.current_speed()) {
+                    cmd.arg(flag);
+                }
 
-    // Iterative Refinement for Duration Target
-    if let Some((min_d, max_d)) = intent.target_duration {
-        log(&format!(
-            "[SMART] 🎯 Targeting duration: {:.0}s - {:.0}s",
-            min_d, max_d
-        ));
+                // Ensure frame dimensions are even, which NVENC requires.
+                cmd.arg("-vf").arg("scale=trunc(iw/2)*2:trunc(ih/2)*2");
 
-        // Log score distribution
-        let scores: Vec<f64> = scenes.iter().map(|s| s.score).collect();
-        let min_s = scores.iter().cloned().fold(1.0, f64::min);
-        let max_s = scores.iter().cloned().fold(0.0, f64::max);
-        let avg_s = scores.iter().sum::<f64>() / scores.len() as f64;
-        log(&format!(
-            "[SMART] Score Stats: Min={:.2}, Max={:.2}, Avg={:.2}",
-            min_s, max_s, avg_s
-        ));
+                // High quality fixed quantization for intermediate clips if encoding supports it
+                if gpu_ctx.has_gpu() {
+                    cmd.arg("-rc").arg("vbr"); // Required for NVENC -cq to work properly
+                    cmd.arg("-b:v").arg("0");
+                    cmd.arg("-cq").arg("23"); // NVENC constant quality
+                } else {
+                    cmd.arg("-crf").arg("23"); // CPU
+                }
 
-        // Start strictly if we are way over duration
-        let mut step_size = 0.02;
+                // Always re-encode audio to AAC to ensure format consistency
+                cmd.arg("-c:a").arg("aac").arg("-b:a").arg("192k");
+            }
 
-        for iteration in 1..=50 {
-            scenes_to_keep = scenes
-                .iter()
-                .cloned()
-                .filter(|s| s.score > keep_threshold)
-                .collect();
-            let current_duration: f64 = scenes_to_keep.iter().map(|s| s.duration).sum();
+            cmd.arg("-avoid_negative_ts").arg("make_zero");
+            cmd.arg(production_tools::safe_arg_path(&seg_path));
 
-            log(&format!(
-                "        - Iteration {}: Threshold={:.2}, Duration={:.0}s (Target: {:.0}-{:.0})",
-                iteration, keep_threshold, current_duration, min_d, max_d
-            ));
+            let output_res = cmd.output().await;
+            drop(permit); // Release concurrency slot
 
-            if current_duration < min_d {
-                // Too short, lower threshold to include more
-                if keep_threshold <= 0.0 {
-                    break;
-                }
-                keep_threshold = (keep_threshold - step_size).max(0.0);
-            } else if current_duration > max_d {
-                // Too long, raise threshold to be more selective
-                if keep_threshold >= 1.0 {
-                    break;
+            if let Ok(s) = output_res {
+                if s.status.success() {
+                    return Some((seg_path, scene_duration));
+                } else {
+                    tracing::error!(
+                        "[SMART] Segment extraction failed for {}: {}",
+                        seg_path.display(),
+                        String::from_utf8_lossy(&s.stderr)
+                    );
                 }
-                keep_threshold = (keep_threshold + step_size).min(1.0);
-            } else {
-                log(&format!(
-                    "[SMART] ✅ Target duration reached in {} attempts.",
-                    iteration
-                ));
-                break;
+            } else if let Err(e) = output_res {
+                tracing::error!("[SMART] Failed to spawn ffmpeg: {}", e);
             }
+            None
+        });
 
-            // Dynamic step size to avoid oscillation
-            if iteration > 10 {
-                step_size = 0.01;
-            }
-            if iteration > 30 {
-                step_size = 0.005;
-            }
-        }
-    } else {
-        scenes_to_keep = scenes
-            .iter()
-            .cloned()
-            .filter(|s| s.score > keep_threshold)
-            .collect();
+        tasks.push(handle);
     }
 
-    // 4.1 — Minimum scene duration filter: remove micro-clips that flash by too fast.
-    //       Keep only scenes ≥ 3.5s.  If that would remove everything, skip this filter.
-    {
-        let before_min_dur = scenes_to_keep.len();
-        let filtered: Vec<Scene> = scenes_to_keep
-            .iter()
-            .cloned()
-            .filter(|s| s.duration >= 3.5 || scene_has_speech(s, transcript.as_deref()))
-            .collect();
-        if !filtered.is_empty() {
-            scenes_to_keep = filtered;
-            let removed_micro = before_min_dur - scenes_to_keep.len();
-            if removed_micro > 0 {
-                log(&format!(
-                    "[SMART] 🚫 Removed {} micro-clips (< 3.5s) to prevent choppy cuts",
-                    removed_micro
-                ));
-            }
-        }
+    // Await all segment-extraction tasks and collect successful results
+    let mut segment_files: Vec<std::path::PathBuf> = Vec::new();
+    
+    // ADD PRODUCED REMOTION SEGMENTS IF ANY
+    if let Some(path) = remotion_segment {
+        segment_files.push(path);
     }
 
-    let mut total_kept = scenes_to_keep.len();
-    let removed = total_before_filtering - total_kept;
-
-    if scenes_to_keep.is_empty() {
-        log("[SMART] ⚠️ All scenes were filtered out! Triggering Best-of Fallback...");
-        // Sort all scenes by score descending and take the top 3 (or all if < 3)
-        let mut all_scenes = scenes.clone();
-        all_scenes.sort_by(|a, b| {
-            b.score
-                .partial_cmp(&a.score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+    for handle in tasks {
+        if let Ok(Some((path, _dur))) = handle.await {
+            segment_files.push(path);
+        }
+    }
 
-        scenes_to_keep = all_scenes.into_iter().take(3).collect();
-        // Sort back by time
-        scenes_to_keep.sort_by(|a, b| {
-            a.start_time
-                .partial_cmp(&b.start_time)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+    if segment_files.is_empty() {
+        fs::remove_dir_all(&segments_dir).ok();
+        return Err("Failed to extract any video segments".into());
+    }
 
-        total_kept = scenes_to_keep.len();
-        log(&format!(
-            "[SMART] 🎯 Fallback: Selected top {} highest-scoring segments.",
-            total_kept
-        ));
+    // Persist scene fingerprint so future runs can validate the segment cache.
+    if !all_segs_cached {
+        let _ = fs::write(&fingerprint_path, &scene_fingerprint);
     }
 
     log(&format!(
-        "[SMART] Keeping {}/{} segments after refinement. Final duration: {:.2}s",
-        total_kept,
-        total_before_filtering,
-        scenes_to_keep.iter().map(|s| s.duration).sum::<f64>()
+        "[SMART] 🔗 Stitching {} segments together...",
+        segment_files.len()
     ));
 
-    if scenes_to_keep.is_empty() {
-        return Err("Fatal: Could not produce any segments even with fallback.".into());
-    }
+    // 7. Stitch segments — use crossfade transitions when feasible (≤ 30 segments),
+    //    fall back to simple concat for very long edit lists.
+    let xfade_dur = neuro_transition_dur.clamp(0.12, 0.25);
+    let applied_xfade_dur = if segment_files.len() >= 2 && segment_files.len() <= 30 {
+        xfade_dur
+    } else {
+        0.0
+    };
 
-    // 4.5 — Merge neighboring kept-scenes that share a transcript sentence so
-    //        a single sentence never becomes multiple separate micro-clips.
-    //        Gap tolerance 4.0s (up from 2.0s) — natural speech pauses are 2-4s.
-    if let Some(ref t) = transcript {
-        let before_merge = scenes_to_keep.len();
-        scenes_to_keep = merge_neighboring_scenes(scenes_to_keep, t, 4.0);
-        if scenes_to_keep.len() < before_merge {
-            log(&format!(
-                "[SMART] 🔗 Sentence-merge: {} → {} scenes (grouped {} split sentences)",
-                before_merge,
-                scenes_to_keep.len(),
-                before_merge - scenes_to_keep.len()
-            ));
-        }
-    }
-
-    // 4.6 — Bridge large narrative gaps.
-    // If two consecutive kept scenes are more than max_jump_gap_secs apart we
-    // insert the best available scene from within that gap so the edit doesn't
-    // jump minutes ahead without any transitional context.
-    {
-        let before_bridge = scenes_to_keep.len();
-        scenes_to_keep = bridge_narrative_gaps(scenes_to_keep, &scenes, config.max_jump_gap_secs);
-        if scenes_to_keep.len() > before_bridge {
-            log(&format!(
-                "[SMART] 🌉 Gap-bridge: {} → {} scenes after inserting narrative bridges",
-                before_bridge,
-                scenes_to_keep.len()
-            ));
-        }
-    }
-
-    // Collect the removed gaps for the [CUT] marker step later.
-    // A gap exists wherever two consecutive kept-scenes are NOT touching in
-    // the original video timeline.
-    let mut cut_points: Vec<(f64, f64)> = Vec::new();
-    {
-        let mut prev_end: Option<f64> = None;
-        for sc in &scenes_to_keep {
-            if let Some(pe) = prev_end {
-                let gap = sc.start_time - pe;
-                if gap > 0.25 {
-                    cut_points.push((pe, sc.start_time));
-                }
-            }
-            prev_end = Some(sc.end_time);
-        }
-    }
-    log(&format!(
-        "[SMART] ✂️ {} cut point(s) in original video",
-        cut_points.len()
-    ));
-
-    // Combine strictly contiguous scenes in `scenes_to_keep` so we don't
-    // chop the video up into identical contiguous parts during extraction.
-    {
-        let before_contig = scenes_to_keep.len();
-        let mut merged: Vec<crate::agent::specialized::smart_editor::types::Scene> = Vec::new();
-        for sc in scenes_to_keep {
-            if let Some(last) = merged.last_mut() {
-                // If the start of this scene is basically the end of the last one
-                if sc.start_time - last.end_time <= 0.25 {
-                    last.end_time = sc.end_time;
-                    last.duration = last.end_time - last.start_time;
-                    continue;
-                }
-            }
-            merged.push(sc);
-        }
-        scenes_to_keep = merged;
-        log(&format!(
-            "[SMART] 🔗 Contiguous-merge: {} → {} physical segments for rendering",
-            before_contig,
-            scenes_to_keep.len()
-        ));
-    }
-
-    // Determine neuroplasticity-driven transition style
-    let neuro = crate::agent::neuroplasticity::Neuroplasticity::new();
-    let neuro_level = neuro.adaptation_level();
-    // transition_type drives xfade selection
-    // transition_dur = subtle (0.08-0.25 s) — enough to hide the cut, not
-    // enough to look like a slow film wipe
-    let neuro_transition_dur: f64 = (0.08 + config.continuity_boost * 0.20).clamp(0.08, 0.28);
-    let neuro_transition_name: &str = match neuro_level {
-        "Baseline" => "fade",
-        "Accelerated" => "fade",
-        "Hyperspeed" => "slideleft",
-        "Neural Overdrive" => "wiperight",
-        "Singularity" => "pixelize",
-        _ => "fade",
-    };
-    log(&format!(
-        "[SMART] 🧠 Neuroplasticity transition: {} @ {:.2}s ({} level)",
-        neuro_transition_name, neuro_transition_dur, neuro_level
-    ));
-
-    // work_dir IS already the synoid_temp_{prefix} folder; segments live inside it.
-    let segments_dir = work_dir.to_path_buf();
-    let total_segments = scenes_to_keep.len();
-
-    // Fingerprint the current scene selection so we can detect if scenes changed between runs.
-    // Format: "start,end" per line, one line per scene — fast to compare with fs::read_to_string.
-    let scene_fingerprint: String = scenes_to_keep.iter()
-        .map(|s| format!("{:.6},{:.6}", s.start_time, s.end_time))
-        .collect::<Vec<_>>()
-        .join("\n");
-    let fingerprint_path = segments_dir.join("scene_fingerprint.txt");
-    let cached_fingerprint = if segments_dir.exists() {
-        fs::read_to_string(&fingerprint_path).ok()
-    } else {
-        None
-    };
-    let fingerprint_matches = cached_fingerprint.as_deref() == Some(scene_fingerprint.as_str());
-
-    // Check if all segments from a previous run already exist — reuse them to save time.
-    // Fingerprint must match to ensure the cached segments correspond to the current edit.
-    let all_segs_cached = fingerprint_matches && segments_dir.exists() && {
-        (0..total_segments).all(|i| {
-            let p = segments_dir.join(format!("seg_{:04}.mp4", i));
-            p.exists() && fs::metadata(&p).map(|m| m.len() > 1000).unwrap_or(false)
-        })
-    };
-
-    if all_segs_cached {
-        log(&format!(
-            "[SMART] ⚡ Reusing {} cached segments from previous run (skipping re-cut).",
-            total_segments
-        ));
-    } else {
-        if segments_dir.exists() {
-            fs::remove_dir_all(&segments_dir)?;
-        }
-        fs::create_dir_all(&segments_dir)?;
-    }
-
-    log("[SMART] ✂️ Assembling segments with single-pass render...");
-
-    // Commentary Generator removed (funny_engine deprecated)
-
-    // NVENC hardware encoder session limits:
-    // - GeForce consumer GPUs: max 2-3 concurrent sessions (driver enforced)
-    // - Quadro/Tesla: higher limits (8+)
-    // Use conservative limit to prevent "incompatible client key" and OOM errors
-    let gpu_ctx = crate::gpu_backend::get_gpu_context().await;
-    let max_concurrency = if gpu_ctx.has_gpu() {
-        2  // Conservative limit for NVENC consumer GPUs
-    } else {
-        std::thread::available_parallelism()
-            .map(|n| n.get())
-            .unwrap_or(4)
-            .clamp(2, 6)
-    };
-    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(max_concurrency));
-    let mut tasks = Vec::with_capacity(total_segments);
-
-    for (i, scene) in scenes_to_keep.iter().enumerate() {
-        let seg_path = segments_dir.join(format!("seg_{:04}.mp4", i));
-
-        // Skip re-encoding segments that already exist from cache
-        if all_segs_cached {
-            tasks.push(tokio::spawn(async move {
-                let dur = source_tools::get_video_duration(&seg_path).await.unwrap_or(0.0);
-                Some((seg_path, dur))
-            }));
-            continue;
-        }
-        let scene_duration = scene.duration;
-        let scene_start = scene.start_time;
-
-        // Clone for move into task
-        let input_path = input.to_path_buf();
-        let enhanced_path = final_enhanced_audio_path.clone();
-        let permit = semaphore.clone().acquire_owned().await.unwrap();
-
-        let handle = tokio::spawn(async move {
-            let mut cmd = tokio::process::Command::new("ffmpeg");
-            cmd.stealth();
-            cmd.arg("-y")
-                .arg("-hide_banner")
-                .arg("-loglevel")
-                .arg("error")
-                .arg("-nostdin");
-
-            let gpu_ctx = crate::gpu_backend::get_gpu_context().await;
-
-            // Enable hardware decode acceleration if available
-            if let Some(hwaccel) = gpu_ctx.ffmpeg_hwaccel() {
-                cmd.arg("-hwaccel").arg(hwaccel);
-            }
-
-            // Accurate input-seeking (-ss and -t before -i) prevents frame doubling and lag
-            cmd.arg("-ss").arg(&scene_start.to_string());
-            cmd.arg("-t").arg(&scene_duration.to_string());
-            cmd.arg("-i")
-                .arg(production_tools::safe_arg_path(&input_path));
-
-            if use_enhanced_audio {
-                cmd.arg("-ss").arg(&scene_start.to_string());
-                cmd.arg("-t").arg(&scene_duration.to_string());
-                cmd.arg("-i")
-                    .arg(production_tools::safe_arg_path(&enhanced_path));
-            }
-
-            // Mapping
-            cmd.arg("-map").arg("0:v"); // Video from input 0
-
-            if use_enhanced_audio {
-                cmd.arg("-map").arg("1:a:0"); // Audio from input 1 (enhanced)
-            } else {
-                cmd.arg("-map").arg("0:a:0"); // Original audio
-            }
-
-            let neuro = crate::agent::neuroplasticity::Neuroplasticity::new();
-            cmd.arg("-c:v").arg(gpu_ctx.ffmpeg_encoder());
-            cmd.arg("-pix_fmt").arg("yuv420p");
-            for flag in gpu_ctx.neuroplastic_ffmpeg_flags(neuro.current_speed()) {
-                cmd.arg(flag);
-            }
-
-            // Ensure frame dimensions are even, which NVENC requires.
-            cmd.arg("-vf").arg("scale=trunc(iw/2)*2:trunc(ih/2)*2");
-
-            // High quality fixed quantization for intermediate clips if encoding supports it
-            if gpu_ctx.has_gpu() {
-                cmd.arg("-rc").arg("vbr"); // Required for NVENC -cq to work properly
-                cmd.arg("-b:v").arg("0");
-                cmd.arg("-cq").arg("23"); // NVENC constant quality
-            } else {
-                cmd.arg("-crf").arg("23"); // CPU
-            }
-
-            // Always re-encode audio to AAC to ensure format consistency
-            cmd.arg("-c:a").arg("aac").arg("-b:a").arg("192k");
-
-            cmd.arg("-avoid_negative_ts").arg("make_zero");
-            cmd.arg(production_tools::safe_arg_path(&seg_path));
-
-            let output_res = cmd.output().await;
-            drop(permit); // Release concurrency slot
-
-            if let Ok(s) = output_res {
-                if s.status.success() {
-                    return Some((seg_path, scene_duration));
-                } else {
-                    tracing::error!(
-                        "[SMART] Segment extraction failed for {}: {}",
-                        seg_path.display(),
-                        String::from_utf8_lossy(&s.stderr)
-                    );
-                }
-            } else if let Err(e) = output_res {
-                tracing::error!("[SMART] Failed to spawn ffmpeg: {}", e);
-            }
-            None
-        });
-
-        tasks.push(handle);
-    }
-
-    // Await all segment-extraction tasks and collect successful results
-    let mut segment_files: Vec<std::path::PathBuf> = Vec::new();
-    
-    // ADD PRODUCED REMOTION SEGMENTS IF ANY
-    if let Some(path) = remotion_segment {
-        segment_files.push(path);
-    }
-
-    for handle in tasks {
-        if let Ok(Some((path, _dur))) = handle.await {
-            segment_files.push(path);
-        }
-    }
-
-    if segment_files.is_empty() {
-        fs::remove_dir_all(&segments_dir).ok();
-        return Err("Failed to extract any video segments".into());
-    }
-
-    // Persist scene fingerprint so future runs can validate the segment cache.
-    if !all_segs_cached {
-        let _ = fs::write(&fingerprint_path, &scene_fingerprint);
-    }
-
-    log(&format!(
-        "[SMART] 🔗 Stitching {} segments together...",
-        segment_files.len()
-    ));
-
-    // 7. Stitch segments — use crossfade transitions when feasible (≤ 30 segments),
-    //    fall back to simple concat for very long edit lists.
-    let xfade_dur = neuro_transition_dur.clamp(0.12, 0.25);
-    let applied_xfade_dur = if segment_files.len() >= 2 && segment_files.len() <= 30 {
-        xfade_dur
-    } else {
-        0.0
-    };
-
-    let status = if applied_xfade_dur > 0.0 {
-        // ── Crossfade path ──────────────────────────────────────────────
-        log(&format!(
-            "[SMART] 🎞️ Using crossfade transitions ({:.2}s, {} style)",
-            xfade_dur, neuro_transition_name
-        ));
+    let status = if applied_xfade_dur > 0.0 {
+        // ── Crossfade path ──────────────────────────────────────────────
+        log(&format!(
+            "[SMART] 🎞️ Using crossfade transitions ({:.2}s, {} style)",
+            xfade_dur, neuro_transition_name
+        ));
 
         // Build filter_complex that chains xfade/acrossfade across all segments
         let n = segment_files.len();
@@ -1111,7 +1150,7 @@ pub async fn smart_edit(
         // Probe each segment duration (needed for xfade offset calculation)
         let mut seg_durations: Vec<f64> = Vec::with_capacity(n);
         for seg in &segment_files {
-            let probe = Command::new("ffprobe")
+            let probe = proc::ffprobe()
                 .stealth()
                 .args([
                     "-v",
@@ -1181,7 +1220,7 @@ pub async fn smart_edit(
             filter.truncate(filter.len() - 2);
         }
 
-        let mut cmd = Command::new("ffmpeg");
+        let mut cmd = proc::ffmpeg(ProcClass::Render);
         cmd.stealth();
         cmd.arg("-y")
             .arg("-hide_banner")
@@ -1205,18 +1244,23 @@ pub async fn smart_edit(
         cmd.arg("-map").arg("[outv]");
         cmd.arg("-map").arg("[outa]");
 
-        let neuro = crate::agent::neuroplasticity::Neuroplasticity::new();
-        cmd.arg("-c:v").arg(gpu_ctx.ffmpeg_encoder());
-        cmd.arg("-pix_fmt").arg("yuv420p");
-        for flag in gpu_ctx.neuroplastic_ffmpeg_flags(neuro.current_speed()) {
-            cmd.arg(flag);
-        }
-        if gpu_ctx.has_gpu() {
-            cmd.arg("-cq").arg("23");
+        if mode.is_draft() {
+            cmd.args(draft::DRAFT_VIDEO_ARGS);
+            cmd.arg("-c:a").arg("aac").arg("-b:a").arg(draft::DRAFT_AUDIO_BITRATE);
         } else {
-            cmd.arg("-crf").arg("23");
+            let neuro = crate::agent::neuroplasticity::Neuroplasticity::new();
+            cmd.arg("-c:v").arg(gpu_ctx.ffmpeg_encoder());
+            cmd.arg("-pix_fmt").arg("yuv420p");
+            for flag in gpu_ctx.neuroplastic_ffmpeg_flags(neuro.current_speed()) {
+                cmd.arg(flag);
+            }
+            if gpu_ctx.has_gpu() {
+                cmd.arg("-cq").arg("23");
+            } else {
+                cmd.arg("-crf").arg("23");
+            }
+            cmd.arg("-c:a").arg("aac").arg("-b:a").arg("192k");
         }
-        cmd.arg("-c:a").arg("aac").arg("-b:a").arg("192k");
         cmd.arg("-movflags").arg("+faststart");
         cmd.arg(production_tools::safe_arg_path(output));
 
@@ -1245,7 +1289,7 @@ pub async fn smart_edit(
                 }
             }
 
-            Command::new("ffmpeg")
+            proc::ffmpeg(ProcClass::Render)
                 .stealth()
                 .arg("-y")
                 .arg("-hide_banner")
@@ -1280,7 +1324,7 @@ pub async fn smart_edit(
 
         log("[SMART] 🔗 Using simple concat (single segment or too many for crossfade).");
 
-        Command::new("ffmpeg")
+        proc::ffmpeg(ProcClass::Render)
             .stealth()
             .arg("-y")
             .arg("-hide_banner")
@@ -1305,18 +1349,93 @@ pub async fn smart_edit(
         error!("[SMART] FFmpeg concat failed: {}", stderr);
         return Err("Failed to concatenate segments".into());
     }
+    drop(gpu_slot);
 
     // Get output file size
     let metadata = fs::metadata(output)?;
     let size_mb = metadata.len() as f64 / 1_048_576.0;
 
-    let kept_ratio = scenes_to_keep.len() as f64 / scenes.len().max(1) as f64;
+    let kept_ratio = scenes_to_keep.len() as f64 / scene_count.max(1) as f64;
     let summary = format!(
-        "✅ Smart edit complete! Removed {} boring segments. Output: {:.2} MB (kept_ratio: {:.2})",
-        removed, size_mb, kept_ratio
+        "✅ Smart edit complete! Removed {} boring segments. Output: {:.2} MB (kept_ratio: {:.2}). Analysis: {}.",
+        removed, size_mb, kept_ratio, analysis_tier.describe()
     );
     log(&format!("[SMART] {}", summary));
 
+    // Offset table next to the output so the before/after preview can map
+    // output time back to source time.
+    let mut plan = EditPlan::from_kept_scenes(input, output, &scenes_to_keep, &[], applied_xfade_dur);
+    let keyword_report = scene_keyword_report(&scenes_to_keep, &intent, transcript.as_deref());
+    for (segment, matched) in plan.segments.iter_mut().zip(keyword_report) {
+        if !matched.is_empty() {
+            info!(
+                "[SMART] 🔑 {:.1}s-{:.1}s matched: {}",
+                segment.source_start,
+                segment.source_end,
+                matched.join(", ")
+            );
+        }
+        segment.keywords = matched;
+    }
+    let explanation = explain::explain(&scenes_to_keep, scoring.as_ref(), &exclusions);
+    let report = explain::polish(&explanation.to_markdown(output)).await;
+    match fs::write(explain::explanation_path(output), report) {
+        Ok(()) => log(&format!(
+            "[SMART] 🧾 Edit explanation written ({} removed region(s) explained)",
+            explanation.removed.len()
+        )),
+        Err(e) => warn!("[SMART] Could not write edit explanation (non-fatal): {}", e),
+    }
+    plan.exclusions = exclusions;
+    plan.scoring = scoring;
+    plan.analysis_tier = Some(analysis_tier);
+    if let (Some(seed), Some(cuts)) = (seed, recorded_cuts) {
+        plan.decisions = Some(EditDecisions {
+            seed,
+            intent: intent.clone(),
+            strategy: base_strategy,
+            transition: neuro_transition_name.clone(),
+            cuts,
+        });
+    }
+    // The final render starts after the intro; drafts skip the bookends.
+    if let (Some(b), false) = (&bookends, mode.is_draft()) {
+        for segment in &mut plan.segments {
+            segment.output_start += b.offset();
+        }
+        plan.bookends = Some((b.offset(), b.duration() - b.offset()));
+    }
+    // A sped-up draft's output times no longer line up with the plan.
+    if mode != (RenderMode::Draft { fast_stretches: true }) {
+        if let Err(e) = plan.save() {
+            warn!("[SMART] Could not write edit plan (non-fatal): {}", e);
+        }
+    }
+
+    if mode.is_draft() {
+        log("[SMART] 👀 Draft render: skipping effects, [CUT] markers, subtitle burn-in and intro/outro.");
+        let _ = fs::remove_dir_all(&segments_dir);
+        return Ok(summary);
+    }
+
+    // Render-stage effects (audio-reactive zoom) run before markers and
+    // subtitles so burned-in text does not pulse with the picture.
+    if let Some(pump) = intent.audio_reactive {
+        log("[SMART] 🥁 Applying audio-reactive zoom pulses...");
+        let mut effects = EffectsRegistry::new();
+        effects.register(Box::new(pump));
+        let fx_output = output.with_extension("fx.mp4");
+        match effects.apply(output, &fx_output, work_dir).await {
+            Ok(true) => match fs::copy(&fx_output, output) {
+                Ok(_) => log("[SMART] ✅ Audio-reactive pass applied."),
+                Err(e) => warn!("[SMART] Could not replace output with effects version: {}", e),
+            },
+            Ok(false) => log("[SMART] No onsets detected; skipping audio-reactive pass."),
+            Err(e) => warn!("[SMART] Audio-reactive pass failed (non-fatal): {}", e),
+        }
+        let _ = fs::remove_file(&fx_output);
+    }
+
     // 9. [CUT] Marker pass — burn flash indicators showing where content was removed
     // Skip when density is Full (nothing was cut) or cut_points is empty.
     if intent.show_cut_markers && intent.density != EditDensity::Full && !cut_points.is_empty() {
@@ -1329,6 +1448,8 @@ pub async fn smart_edit(
 
     // 8. Subtitle Generation & Burning
     // Only attempt if we have a transcript to work with and subtitles are enabled
+    let mux_subtitles = reedit.as_ref().is_some_and(|r| r.skip_subtitle_burn);
+    let mut subtitles_burned = false;
     if let Some(ref t) = transcript {
         if !t.is_empty() && intent.enable_subtitles {
             log("[SMART] 📝 Generating remapped subtitles for edited video...");
@@ -1343,7 +1464,7 @@ pub async fn smart_edit(
                     let path = p.clone();
                     let fallback = scenes_to_keep.get(global_i).map(|s| s.duration).unwrap_or(0.0);
                     tasks.push(tokio::spawn(async move {
-                        let probe = Command::new("ffprobe")
+                        let probe = proc::ffprobe()
                             .args(["-v", "error", "-show_entries", "format=duration", "-of", "default=noprint_wrappers=1:nokey=1"])
                             .arg(production_tools::safe_arg_path(&path))
                             .output().await;
@@ -1379,10 +1500,16 @@ pub async fn smart_edit(
                             srt_content.lines().filter(|l| l.contains(" --> ")).count()
                         ));
 
-                        log("[SMART] 🔥 Burning subtitles into video...");
-                        match production_tools::burn_subtitles(&abs_output, &srt_path, &sub_output)
-                            .await
-                        {
+                        // Text burned in by an earlier edit stays; a second layer
+                        // would sit on top of it
+                        let made = if mux_subtitles {
+                            log("[SMART] 📎 Input already has burned-in subtitles; adding a soft subtitle track instead...");
+                            production_tools::mux_subtitles(&abs_output, &srt_path, &sub_output).await
+                        } else {
+                            log("[SMART] 🔥 Burning subtitles into video...");
+                            production_tools::burn_subtitles(&abs_output, &srt_path, &sub_output).await
+                        };
+                        match made {
                             Ok(_) => {
                                 // Validate the subtitled output was successfully created and is not corrupted
                                 match fs::metadata(&sub_output) {
@@ -1395,7 +1522,12 @@ pub async fn smart_edit(
                                             match fs::copy(&sub_output, &abs_output) {
                                                 Ok(_) => {
                                                     let _ = fs::remove_file(&sub_output);
-                                                    log("[SMART] ✅ Subtitles burned into final video.");
+                                                    if mux_subtitles {
+                                                        log("[SMART] ✅ Subtitle track added to final video.");
+                                                    } else {
+                                                        subtitles_burned = true;
+                                                        log("[SMART] ✅ Subtitles burned into final video.");
+                                                    }
                                                 }
                                                 Err(e) => warn!("[SMART] Could not replace output with subtitled version: {}", e),
                                             }
@@ -1410,32 +1542,667 @@ pub async fn smart_edit(
                                     }
                                 }
                             }
-                            Err(e) => warn!("[SMART] Subtitle burning failed (non-fatal): {}", e),
+                            Err(e) => warn!("[SMART] Subtitle pass failed (non-fatal): {}", e),
                         }
 
-                        // Keep the raw SRT alongside the output for reference and clean up the temp
-                        let _ = fs::copy(&srt_path, &output_srt);
-                        let _ = fs::remove_file(&srt_path);
-                    }
-                    Err(e) => warn!("[SMART] Failed to write SRT file: {}", e),
+                        // Keep the raw SRT alongside the output for reference and clean up the temp
+                        match &bookends {
+                            Some(b) => {
+                                let _ = fs::write(&output_srt, bookends::shift_srt(&srt_content, b.offset()));
+                            }
+                            None => {
+                                let _ = fs::copy(&srt_path, &output_srt);
+                            }
+                        }
+                        let _ = fs::remove_file(&srt_path);
+                    }
+                    Err(e) => warn!("[SMART] Failed to write SRT file: {}", e),
+                }
+            } else {
+                log("[SMART] ⚠️ No subtitle entries generated (empty transcript after remapping).");
+            }
+        }
+    }
+
+    // 10. Intro/outro go around the finished edit, after everything that is
+    // timed against it.
+    if let Some(b) = &bookends {
+        log("[SMART] 🎬 Attaching intro/outro...");
+        b.attach(output, work_dir).await?;
+        log("[SMART] ✅ Intro/outro attached.");
+    }
+
+    // 11. The source's chapters, moved onto the edit. Last, because every
+    // pass above re-encodes and drops them.
+    if crate::config::current().chapters == ChapterMode::Preserve {
+        match chapters::read(input).await {
+            Ok(source_chapters) if !source_chapters.is_empty() => {
+                let remapped = chapters::remap(&source_chapters, &plan);
+                log(&format!(
+                    "[SMART] 📑 Keeping {} of {} source chapter(s)...",
+                    remapped.len(),
+                    source_chapters.len()
+                ));
+                if !remapped.is_empty() {
+                    if let Err(e) = chapters::write(output, &remapped, work_dir).await {
+                        warn!("[SMART] Could not write chapters (non-fatal): {}", e);
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("[SMART] Could not read source chapters (non-fatal): {}", e),
+        }
+    }
+
+    // 12. Stamp the output so a later edit of it knows what was done. Last,
+    // so no re-encode drops the tag.
+    let mut stamp = OutputStamp::new(input, intent_key, reedit.as_ref().map(|r| &r.stamp));
+    stamp.subtitles_burned |= subtitles_burned;
+    if normalized {
+        stamp.loudness_target = Some(production_tools::ENHANCED_LOUDNESS_LUFS);
+    }
+    if let Err(e) = reedit::write_stamp(output, &stamp, work_dir).await {
+        warn!("[SMART] Could not stamp the output (non-fatal): {}", e);
+    }
+
+    // If we used a learned pattern to tune this config, persist it
+    // so the next edit starts with these refined parameters.
+    if learned_pattern.is_some() {
+        config.save_to_cortex();
+    }
+
+    // Clean up — remove entire temp dir (segments + WAVs).  Non-fatal so a
+    // missing dir from a previous run doesn't abort an otherwise-complete edit.
+    let _ = fs::remove_dir_all(&work_dir_buf);
+    // The draft's cuts have served their purpose once the final is out.
+    if reused_draft {
+        let _ = fs::remove_file(&draft_cuts_path);
+    }
+
+    Ok(summary)
+}
+
+/// Deterministic per-input prefix for the caches and temp files beside it.
+pub(crate) fn job_prefix_for(input: &Path) -> String {
+    use std::hash::{Hash, Hasher};
+    let mut h = std::collections::hash_map::DefaultHasher::new();
+    input.hash(&mut h);
+    format!("{:08x}", h.finish() & 0xFFFFFFFF)
+}
+
+/// Record a learned-pacing adjustment already applied to `scene.score`.
+fn record_pattern(scene: &mut Scene, reason: &str) {
+    if let Some(breakdown) = scene.breakdown.as_mut() {
+        breakdown.set(ScoreComponent::Pattern, reason, scene.score);
+    }
+}
+
+/// A short input kept whole, minus any explicit exclusions, with its silent
+/// head and tail trimmed when the intent removes silence.
+fn short_input_selection(
+    duration: f64,
+    intent: &EditIntent,
+    transcript: Option<&[TranscriptSegment]>,
+) -> Result<SceneSelection, Box<dyn std::error::Error + Send + Sync>> {
+    let whole = Scene {
+        start_time: 0.0,
+        end_time: duration,
+        duration,
+        score: 1.0,
+        vision_tags: Vec::new(),
+        breakdown: None,
+        audio_mix: None,
+    };
+    let (exclusions, _) = exclusions::resolve_exclusions(
+        &intent.exclude_ranges,
+        &intent.exclude_phrases,
+        transcript,
+        duration,
+    );
+    let excluded_spans: Vec<(f64, f64)> = exclusions.iter().map(|e| (e.start, e.end)).collect();
+    let mut kept = remove_spans(vec![whole], &excluded_spans);
+    if kept.is_empty() {
+        return Err("The excluded ranges cover the whole video".into());
+    }
+    if intent.remove_silence {
+        if let Some(t) = transcript {
+            trim_heads_and_tails(&mut kept, t, intent.edge_padding_secs);
+        }
+    }
+    let cut_points = kept.windows(2).map(|w| (w[0].end_time, w[1].start_time)).collect();
+    Ok(SceneSelection {
+        kept,
+        cut_points,
+        removed: 0,
+        exclusions,
+        scene_count: 1,
+        scoring: None,
+    })
+}
+
+/// Detect, score and filter scenes for `intent`, then tidy the survivors
+/// into the physical segments to render.
+async fn select_scenes(
+    input: &Path,
+    intent: &EditIntent,
+    config: &EditingStrategy,
+    transcript: Option<&[TranscriptSegment]>,
+    learned_pattern: Option<&crate::agent::learning::EditingPattern>,
+    pre_scanned_scenes: Option<Vec<Scene>>,
+    log: &impl Fn(&str),
+) -> Result<SceneSelection, Box<dyn std::error::Error + Send + Sync>> {
+    // Short inputs have at most one scene worth the name, so there is
+    // nothing to choose between: pass them through whole.
+    let source_duration = match &pre_scanned_scenes {
+        Some(s) => s.last().map(|s| s.end_time).unwrap_or(0.0),
+        None => source_tools::get_video_duration(input).await.unwrap_or(0.0),
+    };
+    // The configured `short_input_secs`; 0 turns the cutoff off
+    let short_secs = crate::config::current().short_input_secs;
+    if source_duration > 0.0 && source_duration < short_secs {
+        log(&format!(
+            "[SMART] ⏱️ {:.1}s input is under {:.0}s; passing it through without scene analysis.",
+            source_duration, short_secs
+        ));
+        return short_input_selection(source_duration, intent, transcript);
+    }
+
+    // 2. Detect scenes
+    log("[SMART] 🔍 Analyzing video scenes...");
+    let mut scenes = if let Some(s) = pre_scanned_scenes {
+        log(&format!(
+            "[SMART] Using pre-scanned scenes ({} scenes)",
+            s.len()
+        ));
+        s
+    } else {
+        scan_cache::scenes(input, config.scene_threshold).await?
+    };
+
+    // 2.5 Refine scenes with transcript (Split by silences)
+    if let Some(t) = transcript {
+        log("[SMART] 🛠️ Refining scene boundaries with transcript gaps...");
+        scenes = refine_scenes_with_transcript(scenes, t);
+    }
+
+    // 2.6 Local sponsor detection — cut spans where a configured logo is on screen.
+    //     Templates come from the `sponsor_logos` setting (`--sponsor-logos`).
+    if let Some(logo_dir) = crate::config::current().sponsor_logos {
+        log("[SMART] 🏷️ Scanning for sponsor logos...");
+        match crate::agent::vision_tools::detect_sponsor_spans(input, Path::new(&logo_dir), 1.0)
+            .await
+        {
+            Ok(spans) if !spans.is_empty() => {
+                let removed: f64 = spans.iter().map(|(s, e)| e - s).sum();
+                scenes = remove_spans(scenes, &spans);
+                log(&format!(
+                    "[SMART] 🏷️ Removed {} sponsor span(s) ({:.1}s)",
+                    spans.len(),
+                    removed
+                ));
+            }
+            Ok(_) => log("[SMART] 🏷️ No sponsor logos detected."),
+            Err(e) => warn!("[SMART] Sponsor logo scan failed: {}", e),
+        }
+    }
+
+    if scenes.is_empty() {
+        return Err("No scenes left to edit after scene detection".into());
+    }
+
+    // 2.7 Explicit exclusions — timestamp ranges and transcript references
+    //     from the intent are cut before scoring so no score can keep them.
+    let (exclusions, unmatched) = exclusions::resolve_exclusions(
+        &intent.exclude_ranges,
+        &intent.exclude_phrases,
+        transcript,
+        scenes.last().map(|s| s.end_time).unwrap_or(0.0),
+    );
+    for phrase in &unmatched {
+        log(&format!(
+            "[SMART] ⚠️ Nothing in the transcript matches \"{}\"; that part is not cut.",
+            phrase
+        ));
+    }
+    let excluded_spans: Vec<(f64, f64)> = exclusions.iter().map(|e| (e.start, e.end)).collect();
+    if !exclusions.is_empty() {
+        for exclusion in &exclusions {
+            log(&format!("[SMART] ⛔ Excluding {}", exclusion.describe()));
+        }
+        scenes = remove_spans(scenes, &excluded_spans);
+        if scenes.is_empty() {
+            return Err("The excluded ranges cover the whole video".into());
+        }
+    }
+
+    // 2.8 Semantic Vision Scan (rate-limited, sampled)
+    // Cap at 40 frames to stay within Gemini free-tier (1500 req/day, 15 RPM).
+    // Sample evenly across all eligible scenes so the whole video is represented.
+    const MAX_VISION_FRAMES: usize = 40;
+    log("[SMART] 👁️ Performing sampled vision scan on scenes...");
+
+    let agent = Arc::new(SynoidAgent::new("http://localhost:11434", "llava:latest"));
+
+    let all_eligible: Vec<(usize, f64, f64)> = scenes.iter().enumerate()
+        .filter(|(_, s)| s.duration >= 2.0)
+        .map(|(i, s)| (i, s.start_time, s.end_time))
+        .collect();
+
+    // Even stride sampling: pick at most MAX_VISION_FRAMES spread across the whole list
+    let stride = (all_eligible.len() / MAX_VISION_FRAMES).max(1);
+    let scenes_to_scan: Vec<(usize, f64, f64)> = all_eligible
+        .into_iter()
+        .enumerate()
+        .filter(|(idx, _)| idx % stride == 0)
+        .map(|(_, v)| v)
+        .take(MAX_VISION_FRAMES)
+        .collect();
+
+    let total_to_scan = scenes_to_scan.len();
+    log(&format!(
+        "[SMART] Vision Scan: sampling {}/{} scenes (stride {})",
+        total_to_scan, scenes.len(), stride
+    ));
+
+    // Sequential with a small inter-call delay to stay under 15 RPM
+    for (completed, (i, start_time, end_time)) in scenes_to_scan.into_iter().enumerate() {
+        let mid_time = start_time + (end_time - start_time) / 2.0;
+        let frame_path = format!("temp_frame_{}_{}.jpg", start_time.to_bits(), end_time.to_bits());
+        let input_path = input.to_path_buf();
+
+        let extract_status = proc::ffmpeg(ProcClass::Render)
+            .stealth()
+            .args(["-y", "-ss", &mid_time.to_string(), "-i",
+                   input_path.to_str().unwrap_or_default(),
+                   "-frames:v", "1", "-q:v", "2", &frame_path])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .await;
+
+        if let Ok(st) = extract_status {
+            if st.success() {
+                if let Ok(desc) = crate::agent::vision_tools::describe_frame_multi_provider(
+                    &agent, &PathBuf::from(&frame_path), mid_time,
+                ).await {
+                    if !desc.tags.is_empty() {
+                        scenes[i].vision_tags = desc.tags;
+                    }
+                }
+            }
+        }
+        let _ = tokio::fs::remove_file(&frame_path).await;
+
+        if (completed + 1) % 10 == 0 || completed + 1 == total_to_scan {
+            log(&format!("[SMART] Vision progress: {}/{} frames analyzed", completed + 1, total_to_scan));
+        }
+        // ~4s gap between calls keeps us safely under 15 RPM (= 1 req/4s)
+        if completed + 1 < total_to_scan {
+            tokio::time::sleep(tokio::time::Duration::from_secs(4)).await;
+        }
+    }
+
+    // 2.9 Label each scene's audio as speech, music, sound effects or silence
+    match scan_cache::audio_classes(input).await {
+        Ok(windows) => crate::agent::audio_classify::label_scenes(&mut scenes, &windows),
+        Err(e) => log(&format!("[SMART] ⚠️ Audio classification skipped: {}", e)),
+    }
+
+    // 3. Score scenes based on intent AND transcript
+    log("[SMART] 📊 Scoring scenes based on semantic data...");
+
+    // Calculate total duration from scenes if possible, or use end time of last scene
+    let total_duration = scenes.last().map(|s| s.end_time).unwrap_or(0.0);
+
+    score_scenes(
+        &mut scenes,
+        intent,
+        transcript,
+        config,
+        total_duration,
+    );
+
+    // 3.5 ML Pacing Refinement
+    if let Some(pattern) = learned_pattern {
+        let target_dur = pattern.avg_scene_duration;
+        let strictness = pattern.music_sync_strictness;
+
+        for scene in scenes.iter_mut() {
+            let dur_ratio = scene.duration / target_dur;
+
+            // A. Boost scenes that match the learned pacing (within 20% tolerance)
+            // BUT ONLY IF they are already somewhat decent (score > 0.2)
+            if scene.score > 0.2 {
+                let diff = (scene.duration - target_dur).abs();
+                if diff < target_dur * 0.2 {
+                    // Verify context allows it - don't boost long boring scenes just because they match avg
+                    scene.score = (scene.score + 0.1).clamp(0.0, 1.0);
+                    record_pattern(scene, "matches learned pacing");
+                }
+            }
+
+            // B. PENALIZE scenes that deviate too much (too long)
+            // If strictness is high, we hate long scenes unless they are "Action" or "Speech" heavy (high score)
+            if dur_ratio > 2.0 {
+                // It's double the average length.
+                // If it's a really good scene (score > 0.7), let it slide slightly.
+                // If it's mediocre (score < 0.5), HAMMER IT.
+                let penalty = if scene.score < 0.5 {
+                    0.2 * strictness // Heavy penalty for boring long scenes
+                } else {
+                    0.05 * strictness // Light penalty for good long scenes
+                };
+                scene.score = (scene.score - penalty).clamp(0.0, 1.0);
+                record_pattern(scene, "much longer than learned pacing");
+            }
+
+            // C. PENALIZE scenes that deviate too much (too short)
+            // Only if we aren't in "fast" mode
+            if target_dur > 5.0 && dur_ratio < 0.3 {
+                scene.score = (scene.score - 0.1 * strictness).clamp(0.0, 1.0);
+                record_pattern(scene, "much shorter than learned pacing");
+            }
+        }
+    }
+
+    // 3.55 An external scoring model has the last word on scores.
+    if let Some((scorer, timeout)) = ExternalScorer::from_config() {
+        log("[SMART] 🧪 Asking the external scorer...");
+        match scorer.rescore(&mut scenes, intent, transcript, timeout).await {
+            Ok(()) => log(&format!(
+                "[SMART] 🧪 External scorer rescored {} scene(s).",
+                scenes.len()
+            )),
+            Err(e) => warn!(
+                "[SMART] External scorer failed; keeping built-in scores: {}",
+                e
+            ),
+        }
+    }
+
+    let mut scored: Vec<SceneScore> = scenes
+        .iter()
+        .map(|scene| SceneScore {
+            start: scene.start_time,
+            end: scene.end_time,
+            breakdown: scene.breakdown.clone().unwrap_or_default(),
+            audio_mix: scene.audio_mix,
+            dropped: None,
+        })
+        .collect();
+
+    // 3.6 Require/Exclude keywords are hard filters, applied after every
+    // score adjustment so continuity protection cannot rescue a dropped scene.
+    let dropped = apply_keyword_filters(&mut scenes, intent, transcript);
+    if dropped > 0 {
+        let remaining: std::collections::HashSet<u64> =
+            scenes.iter().map(|s| s.start_time.to_bits()).collect();
+        for score in scored.iter_mut().filter(|s| !remaining.contains(&s.start.to_bits())) {
+            score.dropped = Some("failed the required/excluded keyword filter".to_string());
+        }
+        log(&format!(
+            "[SMART] 🔑 Keyword filters dropped {} scene(s) ({} remain)",
+            dropped,
+            scenes.len()
+        ));
+        if scenes.is_empty() {
+            return Err("No scenes satisfy the required/excluded keywords".into());
+        }
+    }
+
+    // 4. Filter scenes to keep (score > threshold)
+    let mut keep_threshold = config.min_scene_score;
+    let total_before_filtering = scenes.len();
+    let mut scenes_to_keep: Vec<Scene> = Vec::new();
+
+    // A target the whole source cannot reach would only walk the threshold
+    // down to zero; keep scenes on score alone instead.
+    let target_duration = match intent.target_duration {
+        Some((min_d, _)) if min_d >= total_duration => {
+            log(&format!(
+                "[SMART] 🎯 Target of at least {:.0}s is longer than the {:.1}s source; skipping duration targeting.",
+                min_d, total_duration
+            ));
+            None
+        }
+        target => target,
+    };
+
+    // Iterative Refinement for Duration Target
+    if let Some((min_d, max_d)) = target_duration {
+        log(&format!(
+            "[SMART] 🎯 Targeting duration: {:.0}s - {:.0}s",
+            min_d, max_d
+        ));
+
+        // Log score distribution
+        let scores: Vec<f64> = scenes.iter().map(|s| s.score).collect();
+        let min_s = scores.iter().cloned().fold(1.0, f64::min);
+        let max_s = scores.iter().cloned().fold(0.0, f64::max);
+        let avg_s = scores.iter().sum::<f64>() / scores.len() as f64;
+        log(&format!(
+            "[SMART] Score Stats: Min={:.2}, Max={:.2}, Avg={:.2}",
+            min_s, max_s, avg_s
+        ));
+
+        // Start strictly if we are way over duration
+        let mut step_size = 0.02;
+
+        for iteration in 1..=50 {
+            scenes_to_keep = scenes
+                .iter()
+                .cloned()
+                .filter(|s| s.score > keep_threshold)
+                .collect();
+            let current_duration: f64 = scenes_to_keep.iter().map(|s| s.duration).sum();
+
+            log(&format!(
+                "        - Iteration {}: Threshold={:.2}, Duration={:.0}s (Target: {:.0}-{:.0})",
+                iteration, keep_threshold, current_duration, min_d, max_d
+            ));
+
+            if current_duration < min_d {
+                // Too short, lower threshold to include more
+                if keep_threshold <= 0.0 {
+                    break;
+                }
+                keep_threshold = (keep_threshold - step_size).max(0.0);
+            } else if current_duration > max_d {
+                // Too long, raise threshold to be more selective
+                if keep_threshold >= 1.0 {
+                    break;
+                }
+                keep_threshold = (keep_threshold + step_size).min(1.0);
+            } else {
+                log(&format!(
+                    "[SMART] ✅ Target duration reached in {} attempts.",
+                    iteration
+                ));
+                break;
+            }
+
+            // Dynamic step size to avoid oscillation
+            if iteration > 10 {
+                step_size = 0.01;
+            }
+            if iteration > 30 {
+                step_size = 0.005;
+            }
+        }
+    } else {
+        scenes_to_keep = scenes
+            .iter()
+            .cloned()
+            .filter(|s| s.score > keep_threshold)
+            .collect();
+    }
+
+    // 4.1 — Minimum scene duration filter: remove micro-clips that flash by too fast.
+    //       Keep only scenes ≥ 3.5s.  If that would remove everything, skip this filter.
+    {
+        let before_min_dur = scenes_to_keep.len();
+        let filtered: Vec<Scene> = scenes_to_keep
+            .iter()
+            .cloned()
+            .filter(|s| s.duration >= 3.5 || scene_has_speech(s, transcript))
+            .collect();
+        if !filtered.is_empty() {
+            scenes_to_keep = filtered;
+            let removed_micro = before_min_dur - scenes_to_keep.len();
+            if removed_micro > 0 {
+                log(&format!(
+                    "[SMART] 🚫 Removed {} micro-clips (< 3.5s) to prevent choppy cuts",
+                    removed_micro
+                ));
+            }
+        }
+    }
+
+    let mut total_kept = scenes_to_keep.len();
+    let removed = total_before_filtering - total_kept;
+
+    if scenes_to_keep.is_empty() {
+        log("[SMART] ⚠️ All scenes were filtered out! Triggering Best-of Fallback...");
+        // Top 3 (or all if < 3) non-overlapping scenes, back in time order
+        scenes_to_keep = best_scenes(&scenes, 3);
+
+        total_kept = scenes_to_keep.len();
+        log(&format!(
+            "[SMART] 🎯 Fallback: Selected top {} highest-scoring segments.",
+            total_kept
+        ));
+    }
+
+    log(&format!(
+        "[SMART] Keeping {}/{} segments after refinement. Final duration: {:.2}s",
+        total_kept,
+        total_before_filtering,
+        scenes_to_keep.iter().map(|s| s.duration).sum::<f64>()
+    ));
+
+    if scenes_to_keep.is_empty() {
+        return Err("Fatal: Could not produce any segments even with fallback.".into());
+    }
+
+    // 4.5 — Merge neighboring kept-scenes that share a transcript sentence so
+    //        a single sentence never becomes multiple separate micro-clips.
+    //        Gap tolerance 4.0s (up from 2.0s) — natural speech pauses are 2-4s.
+    if let Some(t) = transcript {
+        let before_merge = scenes_to_keep.len();
+        scenes_to_keep = merge_neighboring_scenes(scenes_to_keep, t, 4.0);
+        if scenes_to_keep.len() < before_merge {
+            log(&format!(
+                "[SMART] 🔗 Sentence-merge: {} → {} scenes (grouped {} split sentences)",
+                before_merge,
+                scenes_to_keep.len(),
+                before_merge - scenes_to_keep.len()
+            ));
+        }
+    }
+
+    // 4.6 — Bridge large narrative gaps.
+    // If two consecutive kept scenes are more than max_jump_gap_secs apart we
+    // insert the best available scene from within that gap so the edit doesn't
+    // jump minutes ahead without any transitional context.
+    {
+        let before_bridge = scenes_to_keep.len();
+        scenes_to_keep = bridge_narrative_gaps(scenes_to_keep, &scenes, config.max_jump_gap_secs);
+        if scenes_to_keep.len() > before_bridge {
+            log(&format!(
+                "[SMART] 🌉 Gap-bridge: {} → {} scenes after inserting narrative bridges",
+                before_bridge,
+                scenes_to_keep.len()
+            ));
+        }
+    }
+
+    // Merging and bridging work on whole scenes and may have spanned an
+    // exclusion again; cut it back out.
+    if !excluded_spans.is_empty() {
+        scenes_to_keep = remove_spans(scenes_to_keep, &excluded_spans);
+    }
+
+    // Collect the removed gaps for the [CUT] marker step later.
+    // A gap exists wherever two consecutive kept-scenes are NOT touching in
+    // the original video timeline.
+    let mut cut_points: Vec<(f64, f64)> = Vec::new();
+    {
+        let mut prev_end: Option<f64> = None;
+        for sc in &scenes_to_keep {
+            if let Some(pe) = prev_end {
+                let gap = sc.start_time - pe;
+                if gap > 0.25 {
+                    cut_points.push((pe, sc.start_time));
                 }
-            } else {
-                log("[SMART] ⚠️ No subtitle entries generated (empty transcript after remapping).");
             }
+            prev_end = Some(sc.end_time);
         }
     }
+    log(&format!(
+        "[SMART] ✂️ {} cut point(s) in original video",
+        cut_points.len()
+    ));
 
-    // If we used a learned pattern to tune this config, persist it
-    // so the next edit starts with these refined parameters.
-    if learned_pattern.is_some() {
-        config.save_to_cortex();
+    // Combine strictly contiguous scenes in `scenes_to_keep` so we don't
+    // chop the video up into identical contiguous parts during extraction.
+    {
+        let before_contig = scenes_to_keep.len();
+        let mut merged: Vec<crate::agent::specialized::smart_editor::types::Scene> = Vec::new();
+        for sc in scenes_to_keep {
+            if let Some(last) = merged.last_mut() {
+                // If the start of this scene is basically the end of the last one
+                if sc.start_time - last.end_time <= 0.25 {
+                    last.end_time = sc.end_time;
+                    last.duration = last.end_time - last.start_time;
+                    continue;
+                }
+            }
+            merged.push(sc);
+        }
+        scenes_to_keep = merged;
+        log(&format!(
+            "[SMART] 🔗 Contiguous-merge: {} → {} physical segments for rendering",
+            before_contig,
+            scenes_to_keep.len()
+        ));
     }
 
-    // Clean up — remove entire temp dir (segments + WAVs).  Non-fatal so a
-    // missing dir from a previous run doesn't abort an otherwise-complete edit.
-    let _ = fs::remove_dir_all(&work_dir_buf);
+    // 4.7 — Trim heads and tails: start just before the first word and end
+    //        just after the last one instead of on the scene boundary padding.
+    if let Some(t) = transcript {
+        let before = (
+            scenes_to_keep.first().map(|s| s.start_time),
+            scenes_to_keep.last().map(|s| s.end_time),
+        );
+        trim_heads_and_tails(&mut scenes_to_keep, t, intent.edge_padding_secs);
+        let after = (
+            scenes_to_keep.first().map(|s| s.start_time),
+            scenes_to_keep.last().map(|s| s.end_time),
+        );
+        if before != after {
+            log(&format!(
+                "[SMART] 🎯 Heads/tails tightened: in {:.2}s → {:.2}s, out {:.2}s → {:.2}s (padding {:.2}s)",
+                before.0.unwrap_or(0.0),
+                after.0.unwrap_or(0.0),
+                before.1.unwrap_or(0.0),
+                after.1.unwrap_or(0.0),
+                intent.edge_padding_secs
+            ));
+        }
+    }
 
-    Ok(summary)
+    Ok(SceneSelection {
+        kept: scenes_to_keep,
+        cut_points,
+        removed,
+        scene_count: scenes.len(),
+        exclusions,
+        scoring: Some(ScoringReport {
+            scenes: scored,
+            keep_threshold,
+            source_duration: total_duration,
+        }),
+    })
 }
 
 /// Build a smooth xfade filter for transitions between trimmed segments.
@@ -1474,7 +2241,7 @@ async fn fallback_extract_and_concat(
         let permit = semaphore.clone().acquire_owned().await.unwrap();
 
         let handle = tokio::spawn(async move {
-            let mut cmd = tokio::process::Command::new("ffmpeg");
+            let mut cmd = proc::ffmpeg(ProcClass::Render);
             cmd.stealth();
             cmd.arg("-y")
                 .arg("-hide_banner")
@@ -1563,7 +2330,7 @@ async fn fallback_extract_and_concat(
         }
     }
 
-    let status = Command::new("ffmpeg")
+    let status = proc::ffmpeg(ProcClass::Render)
         .stealth()
         .arg("-y")
         .arg("-hide_banner")
@@ -1615,6 +2382,7 @@ async fn fallback_extract_and_concat(
 /// Returns the full SRT file content as a String.
 #[cfg(test)]
 mod tests {
+    use super::test_support::synthetic_timeline;
     use super::*;
 
     #[test]
@@ -1627,6 +2395,28 @@ mod tests {
         assert!(intent2.remove_silence);
     }
 
+    #[test]
+    fn test_spanish_and_german_intents_without_the_llm() {
+        let es = EditIntent::from_text("Quita las partes aburridas y los silencios, con subtítulos");
+        assert!(es.remove_boring);
+        assert!(es.remove_silence);
+        assert!(es.enable_subtitles);
+        assert!(!es.keep_action);
+        let es = EditIntent::from_text("Hazlo corto y rápido, de unos 10 minutos");
+        assert_eq!(es.density, EditDensity::Highlights);
+        assert!(es.keep_action);
+        assert_eq!(es.target_duration, Some((540.0, 660.0)));
+
+        let de = EditIntent::from_text("Entferne die langweiligen Teile und die Stille");
+        assert!(de.remove_boring);
+        assert!(de.remove_silence);
+        // German "fast" means "almost"
+        let de = EditIntent::from_text("Behalte fast alle Gespräche, ohne Füllmaterial");
+        assert!(!de.keep_action);
+        assert!(de.keep_speech);
+        assert!(de.ruthless);
+    }
+
     #[test]
     fn test_censor_detects_cuss_and_homosexual() {
         // This exact phrase comes from the user's prompt in offline/heuristic mode
@@ -1647,6 +2437,8 @@ mod tests {
             duration: 10.0,
             score: 0.5,
             vision_tags: Vec::new(),
+            breakdown: None,
+            audio_mix: None,
         }];
 
         let transcript = vec![
@@ -1684,6 +2476,250 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_trim_heads_and_tails() {
+        let mut scenes = vec![
+            Scene {
+                start_time: 0.0,
+                end_time: 6.0,
+                duration: 6.0,
+                score: 0.5,
+                vision_tags: Vec::new(),
+                breakdown: None,
+                audio_mix: None,
+            },
+            Scene {
+                start_time: 20.0,
+                end_time: 30.0,
+                duration: 10.0,
+                score: 0.5,
+                vision_tags: Vec::new(),
+                breakdown: None,
+                audio_mix: None,
+            },
+        ];
+        let transcript = vec![
+            TranscriptSegment {
+                start: 1.8,
+                end: 4.0,
+                text: "Okay so".to_string(),
+                words: Vec::new(),
+            },
+            TranscriptSegment {
+                start: 21.0,
+                end: 26.5,
+                text: "see you next time".to_string(),
+                words: Vec::new(),
+            },
+        ];
+
+        trim_heads_and_tails(&mut scenes, &transcript, 0.3);
+        assert!((scenes[0].start_time - 1.5).abs() < 1e-9);
+        assert!((scenes[0].duration - 4.5).abs() < 1e-9);
+        assert!((scenes[1].end_time - 26.8).abs() < 1e-9);
+
+        // Padding never pushes the bounds outward.
+        trim_heads_and_tails(&mut scenes, &transcript, 5.0);
+        assert!((scenes[0].start_time - 1.5).abs() < 1e-9);
+        assert!((scenes[1].end_time - 26.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_trim_heads_and_tails_keeps_edges_inside_speech() {
+        let mut scenes = vec![
+            Scene {
+                start_time: 10.0,
+                end_time: 20.0,
+                duration: 10.0,
+                score: 0.5,
+                vision_tags: Vec::new(),
+                breakdown: None,
+                audio_mix: None,
+            },
+            Scene {
+                start_time: 40.0,
+                end_time: 50.0,
+                duration: 10.0,
+                score: 0.5,
+                vision_tags: Vec::new(),
+                breakdown: None,
+                audio_mix: None,
+            },
+        ];
+        let transcript = vec![
+            // Already talking when the first scene starts...
+            TranscriptSegment {
+                start: 8.0,
+                end: 11.0,
+                text: "and that's why".to_string(),
+                words: Vec::new(),
+            },
+            TranscriptSegment {
+                start: 14.0,
+                end: 16.0,
+                text: "it matters".to_string(),
+                words: Vec::new(),
+            },
+            TranscriptSegment {
+                start: 42.0,
+                end: 44.0,
+                text: "one more thing".to_string(),
+                words: Vec::new(),
+            },
+            // ...and still talking when the last one ends.
+            TranscriptSegment {
+                start: 48.0,
+                end: 52.0,
+                text: "before we go".to_string(),
+                words: Vec::new(),
+            },
+        ];
+
+        trim_heads_and_tails(&mut scenes, &transcript, 0.3);
+        assert_eq!(scenes[0].start_time, 10.0);
+        assert_eq!(scenes[0].duration, 10.0);
+        assert_eq!(scenes[1].end_time, 50.0);
+        assert_eq!(scenes[1].duration, 10.0);
+    }
+
+    #[test]
+    fn test_one_second_input_passes_through() {
+        let intent = EditIntent::from_text("remove boring parts");
+        let selection = short_input_selection(1.0, &intent, None).unwrap();
+        let bounds: Vec<(f64, f64)> = selection.kept.iter().map(|s| (s.start_time, s.end_time)).collect();
+        assert_eq!(bounds, vec![(0.0, 1.0)]);
+        assert!(selection.cut_points.is_empty());
+        assert_eq!(selection.removed, 0);
+    }
+
+    #[test]
+    fn test_five_second_input_trims_silent_edges() {
+        let transcript = vec![TranscriptSegment {
+            start: 1.0,
+            end: 3.5,
+            text: "hello there".to_string(),
+            words: Vec::new(),
+        }];
+
+        let keep_all = EditIntent::from_text("remove boring parts");
+        let selection = short_input_selection(5.0, &keep_all, Some(&transcript)).unwrap();
+        assert_eq!((selection.kept[0].start_time, selection.kept[0].end_time), (0.0, 5.0));
+
+        let trim = EditIntent::from_text("remove silence");
+        let selection = short_input_selection(5.0, &trim, Some(&transcript)).unwrap();
+        assert_eq!(selection.kept.len(), 1);
+        assert!((selection.kept[0].start_time - 0.7).abs() < 1e-9);
+        assert!((selection.kept[0].end_time - 3.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_single_scene_input_survives_fallback() {
+        let mut scenes = vec![Scene {
+            start_time: 0.0,
+            end_time: 40.0,
+            duration: 40.0,
+            score: 0.5,
+            vision_tags: Vec::new(),
+            breakdown: None,
+            audio_mix: None,
+        }];
+        let intent = EditIntent::from_text("remove boring");
+        score_scenes(&mut scenes, &intent, None, &EditingStrategy::default(), 40.0);
+
+        let best = best_scenes(&scenes, 3);
+        assert_eq!(best.len(), 1);
+        assert_eq!((best[0].start_time, best[0].end_time), (0.0, 40.0));
+    }
+
+    #[test]
+    fn test_best_scenes_skip_overlaps() {
+        let scene = |start: f64, end: f64, score: f64| Scene {
+            start_time: start,
+            end_time: end,
+            duration: end - start,
+            score,
+            vision_tags: Vec::new(),
+            breakdown: None,
+            audio_mix: None,
+        };
+        let scenes = vec![scene(0.0, 4.0, 0.9), scene(3.0, 6.0, 0.8), scene(6.0, 9.0, 0.1)];
+        let bounds: Vec<(f64, f64)> = best_scenes(&scenes, 3)
+            .iter()
+            .map(|s| (s.start_time, s.end_time))
+            .collect();
+        assert_eq!(bounds, vec![(0.0, 4.0), (6.0, 9.0)]);
+    }
+
+    #[test]
+    fn test_remove_spans_splits_scenes() {
+        let scenes = vec![Scene {
+            start_time: 0.0,
+            end_time: 30.0,
+            duration: 30.0,
+            score: 0.5,
+            vision_tags: Vec::new(),
+            breakdown: None,
+            audio_mix: None,
+        }];
+        let kept = remove_spans(scenes, &[(10.0, 15.0), (29.8, 31.0)]);
+        let bounds: Vec<(f64, f64)> = kept.iter().map(|s| (s.start_time, s.end_time)).collect();
+        assert_eq!(bounds, vec![(0.0, 10.0), (15.0, 29.8)]);
+    }
+
+    #[test]
+    fn test_edit_plan_maps_output_time_to_source() {
+        let scene = |start: f64, end: f64| Scene {
+            start_time: start,
+            end_time: end,
+            duration: end - start,
+            score: 0.8,
+            vision_tags: Vec::new(),
+            breakdown: None,
+            audio_mix: None,
+        };
+        let kept = vec![scene(10.0, 20.0), scene(50.0, 55.0)];
+        let plan = EditPlan::from_kept_scenes(
+            Path::new("in.mp4"),
+            Path::new("out.mp4"),
+            &kept,
+            &[],
+            0.0,
+        );
+        assert_eq!(plan.source_time(0.0), Some(10.0));
+        assert_eq!(plan.source_time(9.5), Some(19.5));
+        assert_eq!(plan.source_time(12.0), Some(52.0));
+        assert_eq!(plan.segment_at(12.0), Some(1));
+        assert_eq!(EditPlan::path_for(Path::new("Video/out.mp4")), PathBuf::from("Video/out.plan.json"));
+    }
+
+    #[test]
+    fn test_right_on_the_action_sets_tight_padding() {
+        assert!(EditIntent::from_text("start right on the action").edge_padding_secs < 0.1);
+        assert!((EditIntent::from_text("make it snappy").edge_padding_secs - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_explicit_cuts_become_exclusions_not_target_lengths() {
+        let intent = EditIntent::from_text(
+            "Cut out 12:30 to 14:00 and the part where I drop the controller, skip the first 2 minutes",
+        );
+        assert_eq!(intent.exclude_ranges, vec![(750.0, 840.0), (0.0, 120.0)]);
+        assert_eq!(intent.exclude_phrases, vec!["drop the controller".to_string()]);
+        assert!(intent.target_duration.is_none());
+        assert!(intent.validate().is_ok());
+
+        let mut bad = intent.clone();
+        bad.exclude_ranges.push((50.0, 40.0));
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_pump_with_the_music_enables_audio_reactive() {
+        let intent = EditIntent::from_text("make it pump with the music");
+        assert_eq!(intent.audio_reactive, Some(Default::default()));
+        assert!(EditIntent::from_text("remove boring").audio_reactive.is_none());
+    }
+
     #[test]
     fn test_positional_scoring() {
         let mut scenes = vec![
@@ -1693,6 +2729,8 @@ mod tests {
                 duration: 10.0,
                 score: 0.5,
                 vision_tags: Vec::new(),
+                breakdown: None,
+                audio_mix: None,
             },
             Scene {
                 start_time: 900.0,
@@ -1700,6 +2738,8 @@ mod tests {
                 duration: 10.0,
                 score: 0.5,
                 vision_tags: Vec::new(),
+                breakdown: None,
+                audio_mix: None,
             },
         ];
 
@@ -1730,6 +2770,8 @@ mod tests {
             duration: 5.0,
             score: 0.5,
             vision_tags: Vec::new(),
+            breakdown: None,
+            audio_mix: None,
         }];
 
         let intent = EditIntent::from_text("remove boring");
@@ -1741,6 +2783,75 @@ mod tests {
         assert!(scenes[0].score >= 0.3);
     }
 
+    #[test]
+    fn test_score_breakdown_names_each_factor() {
+        let mut scenes = vec![Scene {
+            start_time: 900.0,
+            end_time: 940.0,
+            duration: 40.0,
+            score: 0.5,
+            vision_tags: Vec::new(),
+            breakdown: None,
+            audio_mix: None,
+        }];
+        let intent = EditIntent::from_text("remove boring");
+        let config = EditingStrategy::default();
+
+        let breakdowns = score_scenes(&mut scenes, &intent, None, &config, 1000.0);
+
+        let reasons: Vec<&str> = breakdowns[0].factors.iter().map(|f| f.reason.as_str()).collect();
+        assert!(reasons.contains(&"scene exceeded boring threshold"), "{:?}", reasons);
+        assert!(reasons.contains(&"late-video penalty"), "{:?}", reasons);
+        assert!(breakdowns[0].factors.iter().all(|f| f.delta != 0.0));
+        assert_eq!(breakdowns[0].score, scenes[0].score);
+        assert_eq!(scenes[0].breakdown.as_ref(), Some(&breakdowns[0]));
+        assert!(breakdowns[0].boring_penalty < 0.0);
+        assert!(breakdowns[0].positional < 0.0);
+    }
+
+    #[test]
+    fn test_audio_intents_score_labelled_scenes() {
+        use crate::agent::audio_classify::{AudioClass, AudioMix};
+        let labelled = |start: f64, mix: AudioMix| Scene {
+            start_time: start,
+            end_time: start + 8.0,
+            duration: 8.0,
+            score: 0.5,
+            vision_tags: Vec::new(),
+            breakdown: None,
+            audio_mix: Some(mix),
+        };
+        let scenes = vec![
+            labelled(100.0, AudioMix { music: 0.9, speech: 0.1, ..Default::default() }),
+            labelled(200.0, AudioMix { speech: 1.0, ..Default::default() }),
+            labelled(300.0, AudioMix { sfx: 0.6, music: 0.4, ..Default::default() }),
+        ];
+        let config = EditingStrategy::default();
+        let score = |text: &str| {
+            let mut scenes = scenes.clone();
+            score_scenes(&mut scenes, &EditIntent::from_text(text), None, &config, 400.0);
+            scenes
+        };
+
+        let music = score("keep the music sections");
+        assert!(music[0].score > music[1].score && music[0].score > music[2].score);
+        let reasons: Vec<&str> = music[0].breakdown.as_ref().unwrap().factors.iter().map(|f| f.reason.as_str()).collect();
+        assert!(reasons.contains(&"mostly music"), "{:?}", reasons);
+        assert!(music[2].breakdown.as_ref().unwrap().audio > 0.0, "40% music is some music");
+
+        let gameplay = score("cut everything that isn't gameplay audio");
+        assert!(gameplay[1].score < config.min_scene_score, "talking scene kept");
+        assert!(gameplay[0].score >= config.min_scene_score);
+        assert!(gameplay[2].score >= config.min_scene_score);
+
+        let intent = EditIntent::from_text("cut the talking");
+        assert_eq!(intent.cut_audio, vec![AudioClass::Speech]);
+        assert!(!intent.keep_speech);
+        let mut both = EditIntent::from_text("keep the music");
+        both.cut_audio.push(AudioClass::Music);
+        assert!(both.validate().is_err());
+    }
+
     #[test]
     fn test_word_level_censor_timestamps() {
         use crate::agent::tools::transcription::TranscriptSegment;
@@ -1808,6 +2919,8 @@ mod tests {
             duration: 2.0,
             score: 0.5,
             vision_tags: Vec::new(),
+            breakdown: None,
+            audio_mix: None,
         };
         let transcript = vec![TranscriptSegment {
             start: 2.5,
@@ -1825,4 +2938,238 @@ mod tests {
         }];
         assert!(!scene_has_speech(&scene, Some(&disjoint_transcript)));
     }
+
+    #[test]
+    fn test_speech_continuity_matches_linear_scan() {
+        use crate::agent::tools::transcription::TranscriptSegment;
+        // The previous two-pointer implementation, kept as the reference.
+        fn reference(scenes: &mut [Scene], transcript: &[TranscriptSegment], is_ruthless: bool) {
+            let mut scene_idx = 0;
+            for segment in transcript {
+                while scene_idx < scenes.len() && scenes[scene_idx].end_time <= segment.start {
+                    scene_idx += 1;
+                }
+                let mut overlapping = Vec::new();
+                let mut preserve = false;
+                for (i, scene) in scenes.iter().enumerate().skip(scene_idx) {
+                    if scene.start_time >= segment.end {
+                        break;
+                    }
+                    if segment.end.min(scene.end_time) > segment.start.max(scene.start_time) {
+                        overlapping.push(i);
+                        preserve |= scene.score > 0.3;
+                    }
+                }
+                if preserve {
+                    let floor = if is_ruthless { 0.35 } else { 0.45 };
+                    let max_score = overlapping
+                        .iter()
+                        .map(|&i| scenes[i].score)
+                        .fold(0.0_f64, f64::max)
+                        .max(floor);
+                    for &i in &overlapping {
+                        let current = scenes[i].score;
+                        if current < max_score {
+                            if is_ruthless {
+                                if current < 0.05 {
+                                    continue;
+                                }
+                                scenes[i].score = (current + max_score) / 2.0;
+                            } else {
+                                scenes[i].score = max_score;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let config = EditingStrategy::default();
+        for seed in 1..=40u64 {
+            for ruthless in [false, true] {
+                let (scenes, transcript) = synthetic_timeline(seed * 7919, 150);
+                let mut expected = scenes.clone();
+                reference(&mut expected, &transcript, ruthless);
+                let mut actual = scenes;
+                ensure_speech_continuity(&mut actual, &transcript, &config, ruthless);
+                let a: Vec<f64> = actual.iter().map(|s| s.score).collect();
+                let e: Vec<f64> = expected.iter().map(|s| s.score).collect();
+                assert_eq!(a, e, "seed {} ruthless {}", seed, ruthless);
+            }
+        }
+    }
+
+    #[test]
+    fn test_score_scenes_matches_sequential_scores() {
+        use crate::agent::tools::transcription::TranscriptSegment;
+        let seg = |start: f64, end: f64, text: &str| TranscriptSegment {
+            start,
+            end,
+            text: text.to_string(),
+            words: Vec::new(),
+        };
+        let spans = [
+            (0.0, 4.0, ""),
+            (4.0, 6.5, "game"),
+            (6.5, 25.0, ""),
+            (25.0, 28.0, "Discord"),
+            (28.0, 31.5, ""),
+            (31.5, 48.0, "main_app"),
+            (48.0, 49.2, ""),
+            (49.2, 60.0, "browser tab"),
+            (60.0, 63.0, "gameplay"),
+            (63.0, 70.0, ""),
+            (70.0, 100.0, ""),
+            (100.0, 104.0, ""),
+        ];
+        let transcript = vec![
+            seg(0.5, 3.5, " Hey everyone, welcome back."),
+            seg(4.2, 6.0, " look at that one"),
+            seg(7.0, 9.0, " So today we're going to talk."),
+            seg(26.0, 27.5, " brb checking chat"),
+            seg(29.0, 33.0, " that was the second try"),
+            seg(40.0, 47.0, " and then the boss fight starts"),
+            seg(61.0, 62.5, " there it is again"),
+            seg(64.0, 69.0, " we keep going to the exit"),
+            seg(101.0, 103.5, " thanks for watching, bye"),
+        ];
+
+        // Scores score_scenes gives this fixture today, pinned so any change
+        // to the scoring rules has to update them deliberately.
+        let expected: [(&str, bool, [f64; 12]); 6] = [
+            ("remove boring parts and keep the action", true,
+             [0.95, 1.0, 0.95, 0.95, 0.95, 0.95, 0.35, 0.0, 1.0, 0.95, 0.09086538461538457, 0.95]),
+            ("remove boring parts and keep the action", false,
+             [0.45, 0.8, 0.35, 0.0, 0.35, 0.44356971153846153, 0.35, 0.0, 0.55, 0.35, 0.09086538461538457, 0.35]),
+            ("ruthless highlights only, keep speech", true,
+             [0.9, 1.0, 0.9, 0.9, 0.9, 0.9, 0.2, 0.0, 0.9, 0.9, 0.2, 0.9]),
+            ("ruthless highlights only, keep speech", false,
+             [0.3, 0.5, 0.3, 0.0, 0.2, 0.4, 0.2, 0.0, 0.4, 0.2, 0.2, 0.2]),
+            ("keep everything, full cut", true,
+             [1.0, 1.0, 0.95, 0.95, 1.0, 1.0, 0.6, 0.0, 1.0, 1.0, 0.6, 1.0]),
+            ("keep everything, full cut", false,
+             [0.7, 0.9, 0.7, 0.0, 0.6, 0.8, 0.6, 0.0, 0.8, 0.6, 0.6, 0.6]),
+        ];
+
+        let config = EditingStrategy::default();
+        for (text, with_transcript, want) in expected {
+            let mut scenes: Vec<Scene> = spans
+                .iter()
+                .map(|&(start, end, tag)| Scene {
+                    start_time: start,
+                    end_time: end,
+                    duration: end - start,
+                    score: 0.0,
+                    vision_tags: if tag.is_empty() { Vec::new() } else { vec![tag.to_string()] },
+                    breakdown: None,
+                    audio_mix: None,
+                })
+                .collect();
+            let intent = EditIntent::from_text(text);
+            let transcript = with_transcript.then_some(transcript.as_slice());
+            score_scenes(&mut scenes, &intent, transcript, &config, 104.0);
+            for (i, (scene, want)) in scenes.iter().zip(want).enumerate() {
+                assert!(
+                    (scene.score - want).abs() < 1e-9,
+                    "{:?} (transcript: {}) scene {}: {} != {}",
+                    text, with_transcript, i, scene.score, want
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_srt_remap_matches_nested_scan() {
+        for seed in 1..=40u64 {
+            let (scenes, transcript) = synthetic_timeline(seed * 104_729, 200);
+            // Keep roughly half the scenes, as a real edit would
+            let kept: Vec<Scene> = scenes.into_iter().filter(|s| s.score > 0.5).collect();
+            let mut offsets = Vec::new();
+            let mut cursor = 0.0;
+            for scene in &kept {
+                offsets.push((scene.start_time, scene.end_time, cursor));
+                cursor += scene.duration - 0.3;
+            }
+
+            let mut expected = Vec::new();
+            for seg in &transcript {
+                for &(src_start, src_end, out_start) in &offsets {
+                    let clip_start = seg.start.max(src_start);
+                    let clip_end = seg.end.min(src_end);
+                    if clip_end <= clip_start {
+                        continue;
+                    }
+                    expected.push((
+                        out_start + (clip_start - src_start),
+                        out_start + (clip_end - src_start),
+                        seg.text.trim().to_string(),
+                    ));
+                }
+            }
+
+            assert_eq!(remap_transcript_entries(&transcript, &offsets), expected, "seed {}", seed);
+        }
+    }
+
+    /// Micro-benchmark on a synthetic 10k-scene timeline. Run with:
+    /// cargo test --release --lib bench_scene_scoring -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_scene_scoring_on_a_10k_scene_timeline() {
+        use std::time::Instant;
+
+        let (scenes, transcript) = synthetic_timeline(0x9E37_79B9_7F4A_7C15, 10_000);
+        println!(
+            "Synthetic timeline: {} scenes, {} transcript segments",
+            scenes.len(),
+            transcript.len()
+        );
+
+        let intent = EditIntent::from_text("remove boring parts and keep the funny moments");
+        let config = EditingStrategy::default();
+        let total = scenes.last().map(|s| s.end_time).unwrap_or(0.0);
+
+        let mut scored = scenes.clone();
+        let started = Instant::now();
+        score_scenes(&mut scored, &intent, Some(&transcript), &config, total);
+        println!("score_scenes (indexed, parallel): {:?}", started.elapsed());
+
+        let mut offsets = Vec::new();
+        let mut cursor = 0.0;
+        for scene in scored.iter().filter(|s| s.score > 0.5) {
+            offsets.push((scene.start_time, scene.end_time, cursor));
+            cursor += scene.duration;
+        }
+
+        // The pre-index remap: every segment against every kept scene.
+        let started = Instant::now();
+        let mut nested = Vec::new();
+        for seg in &transcript {
+            for &(src_start, src_end, out_start) in &offsets {
+                let clip_start = seg.start.max(src_start);
+                let clip_end = seg.end.min(src_end);
+                if clip_end > clip_start {
+                    nested.push((
+                        out_start + (clip_start - src_start),
+                        out_start + (clip_end - src_start),
+                        seg.text.trim().to_string(),
+                    ));
+                }
+            }
+        }
+        let nested_time = started.elapsed();
+
+        let started = Instant::now();
+        let indexed = remap_transcript_entries(&transcript, &offsets);
+        let indexed_time = started.elapsed();
+
+        assert_eq!(nested, indexed, "indexed remap must match the nested scan");
+        println!(
+            "SRT remap over {} kept scenes: nested {:?}, indexed {:?} ({:.1}x)",
+            offsets.len(),
+            nested_time,
+            indexed_time,
+            nested_time.as_secs_f64() / indexed_time.as_secs_f64().max(1e-9)
+        );
+    }
 }
@@ -0,0 +1,296 @@
+// SYNOID Smart Editor - Re-edit Detection
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Every finished smart edit is stamped with a `synoid` metadata tag holding
+// a small JSON record: the original source, the intent, and which finishing
+// passes ran. When a stamped file comes back in as an input, running the
+// whole pipeline again would compound transitions, burn a second set of
+// subtitles over the first and normalize audio that already is. The stamp
+// lets the editor warn, point at the original source when it still exists,
+// mux subtitles as a soft track instead of burning them, and skip loudness
+// normalization when the input is already within 1 LU of the target.
+// `--force-reprocess` (SYNOID_FORCE_REPROCESS) turns all of this off.
+
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::engines::process_utils::CommandExt;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Metadata key the stamp is stored under.
+pub const TAG: &str = "synoid";
+/// Loudness within this many LU of the target counts as normalized.
+pub const LOUDNESS_TOLERANCE_LU: f64 = 1.0;
+
+/// What a finished edit records about how it was made.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputStamp {
+    pub synoid_version: String,
+    /// The first input in the chain, not the edit this one was made from.
+    pub source: PathBuf,
+    pub intent: String,
+    /// 1 for an edit of a plain source, 2 for an edit of an edit, ...
+    pub generation: u32,
+    pub subtitles_burned: bool,
+    /// Integrated loudness (LUFS) the audio was normalized to, if it was.
+    pub loudness_target: Option<f64>,
+    pub edited_at: String,
+}
+
+impl OutputStamp {
+    /// The stamp for an edit of `input` with `intent`, continuing `previous`
+    /// when the input was itself an edit.
+    pub fn new(input: &Path, intent: &str, previous: Option<&OutputStamp>) -> Self {
+        Self {
+            synoid_version: env!("CARGO_PKG_VERSION").to_string(),
+            source: previous.map_or_else(
+                || std::fs::canonicalize(input).unwrap_or_else(|_| input.to_path_buf()),
+                |p| p.source.clone(),
+            ),
+            intent: intent.to_string(),
+            generation: previous.map_or(1, |p| p.generation + 1),
+            // Burned text stays in the picture however often it is re-edited
+            subtitles_burned: previous.is_some_and(|p| p.subtitles_burned),
+            loudness_target: previous.and_then(|p| p.loudness_target),
+            edited_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// How an edit of an already-edited input differs from a fresh one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reedit {
+    pub stamp: OutputStamp,
+    /// Mux subtitles as a soft track; the input already has some burned in.
+    pub skip_subtitle_burn: bool,
+    /// The input's audio is already at the loudness target.
+    pub skip_normalization: bool,
+    /// The original source, when it still exists.
+    pub original_source: Option<PathBuf>,
+}
+
+impl Reedit {
+    /// Decide for an input carrying `stamp`. `measured_lufs` is the input's
+    /// integrated loudness, `target` what normalization would aim for.
+    pub fn plan(
+        stamp: OutputStamp,
+        measured_lufs: Option<f64>,
+        target: f64,
+        source_exists: bool,
+    ) -> Self {
+        let skip_normalization = stamp.loudness_target.is_some()
+            && measured_lufs.is_some_and(|i| (i - target).abs() <= LOUDNESS_TOLERANCE_LU);
+        Self {
+            skip_subtitle_burn: stamp.subtitles_burned,
+            skip_normalization,
+            original_source: source_exists.then(|| stamp.source.clone()),
+            stamp,
+        }
+    }
+
+    /// What to tell the user before the edit starts.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "Input is already a SYNOID edit (generation {}, intent \"{}\"): cuts, transitions and effects will compound",
+            self.stamp.generation, self.stamp.intent
+        )];
+        if let Some(source) = &self.original_source {
+            lines.push(format!(
+                "The original source {:?} still exists; editing it instead gives a cleaner result",
+                source
+            ));
+        }
+        if self.skip_subtitle_burn {
+            lines.push(
+                "Subtitles are already burned in; new ones go in as a soft track instead"
+                    .to_string(),
+            );
+        }
+        if self.skip_normalization {
+            lines.push(
+                "Audio is already at the loudness target; skipping normalization".to_string(),
+            );
+        }
+        lines.push("Pass --force-reprocess to edit it like any other input".to_string());
+        lines
+    }
+}
+
+/// `--force-reprocess` / SYNOID_FORCE_REPROCESS: treat stamped inputs like
+/// any other.
+pub fn force_reprocess() -> bool {
+    crate::config::current().force_reprocess
+}
+
+/// The stamp in `path`, if SYNOID made it.
+pub async fn read_stamp(path: &Path) -> Option<OutputStamp> {
+    let out = proc::ffprobe()
+        .stealth()
+        .args(["-v", "error", "-show_entries"])
+        .arg(format!("format_tags={}", TAG))
+        .args(["-of", "default=noprint_wrappers=1:nokey=1"])
+        .arg(path)
+        .output()
+        .await
+        .ok()?;
+    if !out.status.success() {
+        return None;
+    }
+    serde_json::from_str(String::from_utf8_lossy(&out.stdout).trim()).ok()
+}
+
+/// Write `stamp` into the video at `output`, in place. Streams, chapters and
+/// other metadata are copied as they are.
+pub async fn write_stamp(
+    output: &Path,
+    stamp: &OutputStamp,
+    work_dir: &Path,
+) -> Result<(), String> {
+    let json = serde_json::to_string(stamp).map_err(|e| e.to_string())?;
+    let ext = output
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let tagged = work_dir.join(format!(
+        "stamped.{}",
+        if ext.is_empty() { "mp4" } else { &ext }
+    ));
+    let mut cmd = proc::ffmpeg(ProcClass::Render);
+    cmd.stealth()
+        .args(["-y", "-hide_banner", "-loglevel", "error", "-nostdin", "-i"])
+        .arg(output)
+        .args(["-map", "0", "-map_metadata", "0", "-c", "copy", "-metadata"])
+        .arg(format!("{}={}", TAG, json));
+    // MP4 drops tags it has no atom for unless told to keep them
+    if matches!(ext.as_str(), "mp4" | "m4v" | "mov") {
+        cmd.args(["-movflags", "+use_metadata_tags+faststart"]);
+    }
+    let result = async {
+        let status = cmd
+            .arg(&tagged)
+            .status()
+            .await
+            .map_err(|e| format!("ffmpeg failed: {}", e))?;
+        if !status.success() {
+            return Err("ffmpeg could not write the stamp".to_string());
+        }
+        // Copy rather than rename: WSL mounts reject cross-device renames
+        std::fs::copy(&tagged, output).map_err(|e| format!("Cannot replace the edit: {}", e))?;
+        Ok(())
+    }
+    .await;
+    let _ = std::fs::remove_file(&tagged);
+    result
+}
+
+/// Integrated loudness of `path` in LUFS, from ffmpeg's ebur128 filter.
+pub async fn measure_loudness(path: &Path) -> Result<f64, String> {
+    let out = proc::ffmpeg(ProcClass::Thumbnail)
+        .stealth()
+        .args(["-hide_banner", "-nostats", "-nostdin", "-i"])
+        .arg(path)
+        .args(["-vn", "-af", "ebur128=framelog=quiet", "-f", "null", "-"])
+        .output()
+        .await
+        .map_err(|e| format!("ffmpeg failed: {}", e))?;
+    parse_integrated_loudness(&String::from_utf8_lossy(&out.stderr))
+        .ok_or_else(|| "ffmpeg reported no integrated loudness".to_string())
+}
+
+/// The `I: <n> LUFS` line of an ebur128 summary.
+pub fn parse_integrated_loudness(stderr: &str) -> Option<f64> {
+    stderr.lines().rev().find_map(|line| {
+        let value = line
+            .trim()
+            .strip_prefix("I:")?
+            .trim()
+            .strip_suffix("LUFS")?;
+        value.trim().parse().ok()
+    })
+}
+
+/// Check `input` for a stamp and decide how to treat it. `None` for plain
+/// sources, or when reprocessing is forced.
+pub async fn detect(input: &Path, target_lufs: f64) -> Option<Reedit> {
+    if force_reprocess() {
+        return None;
+    }
+    let stamp = read_stamp(input).await?;
+    // Only worth an ebur128 pass when the earlier edit normalized
+    let measured = match stamp.loudness_target {
+        Some(_) => measure_loudness(input).await.ok(),
+        None => None,
+    };
+    let source_exists = stamp.source != input && stamp.source.exists();
+    Some(Reedit::plan(stamp, measured, target_lufs, source_exists))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An edit of an edit of `raw.mp4`, as stored in the second output.
+    const SECOND_GENERATION: &str = r#"{
+        "synoid_version": "2.0.0",
+        "source": "/videos/raw.mp4",
+        "intent": "tighten it further",
+        "generation": 2,
+        "subtitles_burned": true,
+        "loudness_target": -16.0,
+        "edited_at": "2026-10-17T10:00:00+00:00"
+    }"#;
+
+    #[test]
+    fn edits_of_edits_keep_the_first_source_and_their_finishing() {
+        let stamp: OutputStamp = serde_json::from_str(SECOND_GENERATION).unwrap();
+        let third = OutputStamp::new(Path::new("/out/second.mp4"), "shorter", Some(&stamp));
+        assert_eq!(third.source, PathBuf::from("/videos/raw.mp4"));
+        assert_eq!(third.generation, 3);
+        assert!(third.subtitles_burned);
+        assert_eq!(third.loudness_target, Some(-16.0));
+
+        let first = OutputStamp::new(Path::new("/nowhere/raw.mp4"), "cut", None);
+        assert_eq!(first.generation, 1);
+        assert!(!first.subtitles_burned);
+        assert_eq!(first.loudness_target, None);
+    }
+
+    #[test]
+    fn reedits_skip_passes_that_already_ran() {
+        let stamp: OutputStamp = serde_json::from_str(SECOND_GENERATION).unwrap();
+
+        let reedit = Reedit::plan(stamp.clone(), Some(-16.6), -16.0, true);
+        assert!(reedit.skip_subtitle_burn);
+        assert!(reedit.skip_normalization);
+        assert_eq!(
+            reedit.original_source,
+            Some(PathBuf::from("/videos/raw.mp4"))
+        );
+        let warnings = reedit.warnings().join("\n");
+        assert!(warnings.contains("generation 2"), "{}", warnings);
+        assert!(warnings.contains("raw.mp4"), "{}", warnings);
+        assert!(warnings.contains("--force-reprocess"), "{}", warnings);
+
+        // Off by more than 1 LU, or never measured: normalize again
+        assert!(!Reedit::plan(stamp.clone(), Some(-18.5), -16.0, true).skip_normalization);
+        assert!(!Reedit::plan(stamp.clone(), None, -16.0, true).skip_normalization);
+
+        // A moved-away source is not suggested
+        let unburned = OutputStamp {
+            subtitles_burned: false,
+            loudness_target: None,
+            ..stamp
+        };
+        let reedit = Reedit::plan(unburned, Some(-16.0), -16.0, false);
+        assert!(!reedit.skip_subtitle_burn);
+        assert!(!reedit.skip_normalization);
+        assert_eq!(reedit.original_source, None);
+        assert!(!reedit.warnings().join("\n").contains("still exists"));
+    }
+
+    #[test]
+    fn integrated_loudness_comes_from_the_summary() {
+        let stderr = "[Parsed_ebur128_0 @ 0x55] Summary:\n\n  Integrated loudness:\n    I:         -16.4 LUFS\n    Threshold: -26.6 LUFS\n\n  Loudness range:\n    LRA:         5.1 LU\n";
+        assert_eq!(parse_integrated_loudness(stderr), Some(-16.4));
+        assert_eq!(parse_integrated_loudness("no audio"), None);
+    }
+}
@@ -0,0 +1,531 @@
+// SYNOID Smart Editor - Terminal Scene Review
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// `synoid-core review` sits between the fully automatic edit and the GUI, for
+// headless and SSH sessions. It runs scene analysis only (`RenderMode::Analyze`),
+// lets the user step through the scored scenes in a terminal UI, previewing a
+// frame inline or the whole scene in an external player, and toggle each one
+// between kept and cut. The reviewed cuts are saved where a draft's would be,
+// so the final render that follows reuses them instead of analysing again.
+
+use super::draft::{DraftCuts, RenderMode};
+use super::edit_plan::EditPlan;
+use super::exclusions::format_timestamp;
+use super::explain::ScoringReport;
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::engines::process_utils::CommandExt;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::path::Path;
+use std::time::Duration;
+
+/// Kept spans closer than this are one segment; gaps wider are cut points,
+/// matching `select_scenes`.
+const MIN_GAP_SECS: f64 = 0.25;
+
+/// Factors shown beside each scene.
+const MAX_NOTES: usize = 2;
+
+/// One scored scene and the reviewer's decision on it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewScene {
+    pub start: f64,
+    pub end: f64,
+    pub score: f64,
+    /// Whether the automatic edit kept it.
+    pub initially_kept: bool,
+    pub keep: bool,
+    /// Strongest factors, or why a filter dropped it.
+    pub note: String,
+}
+
+/// Cursor and keep/drop decisions, independent of the terminal.
+#[derive(Debug, Clone)]
+pub struct ReviewState {
+    pub scenes: Vec<ReviewScene>,
+    pub cursor: usize,
+    pub keep_threshold: f64,
+    /// Segments the automatic edit chose; untouched scenes keep these exact
+    /// bounds, including any edge tightening.
+    base_kept: Vec<(f64, f64)>,
+}
+
+fn overlap(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.1.min(b.1) - a.0.max(b.0)).max(0.0)
+}
+
+/// `spans` with `cut` removed; slivers under `MIN_GAP_SECS` are dropped.
+fn subtract(spans: Vec<(f64, f64)>, cut: (f64, f64)) -> Vec<(f64, f64)> {
+    let mut out = Vec::with_capacity(spans.len() + 1);
+    for (start, end) in spans {
+        if overlap((start, end), cut) == 0.0 {
+            out.push((start, end));
+            continue;
+        }
+        if cut.0 - start > MIN_GAP_SECS {
+            out.push((start, cut.0));
+        }
+        if end - cut.1 > MIN_GAP_SECS {
+            out.push((cut.1, end));
+        }
+    }
+    out
+}
+
+/// Sort `spans` and join those that touch or overlap.
+fn merge(mut spans: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+    spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut out: Vec<(f64, f64)> = Vec::with_capacity(spans.len());
+    for (start, end) in spans {
+        match out.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => out.push((start, end)),
+        }
+    }
+    out
+}
+
+impl ReviewState {
+    /// A scene starts kept when most of it landed in the automatic cut.
+    pub fn new(scoring: &ScoringReport, kept: &[(f64, f64)]) -> Self {
+        let scenes = scoring
+            .scenes
+            .iter()
+            .map(|s| {
+                let covered: f64 = kept.iter().map(|&k| overlap(k, (s.start, s.end))).sum();
+                let initially_kept = covered * 2.0 > s.end - s.start;
+                let note = match &s.dropped {
+                    Some(reason) => reason.clone(),
+                    None => {
+                        let mut factors: Vec<_> = s.breakdown.factors.iter().collect();
+                        factors.sort_by(|a, b| b.delta.abs().total_cmp(&a.delta.abs()));
+                        factors
+                            .iter()
+                            .take(MAX_NOTES)
+                            .map(|f| f.reason.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    }
+                };
+                ReviewScene {
+                    start: s.start,
+                    end: s.end,
+                    score: s.breakdown.score,
+                    initially_kept,
+                    keep: initially_kept,
+                    note,
+                }
+            })
+            .collect();
+        Self {
+            scenes,
+            cursor: 0,
+            keep_threshold: scoring.keep_threshold,
+            base_kept: kept.to_vec(),
+        }
+    }
+
+    pub fn current(&self) -> Option<&ReviewScene> {
+        self.scenes.get(self.cursor)
+    }
+
+    pub fn next(&mut self) {
+        if self.cursor + 1 < self.scenes.len() {
+            self.cursor += 1;
+        }
+    }
+
+    pub fn prev(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn toggle(&mut self) {
+        if let Some(scene) = self.scenes.get_mut(self.cursor) {
+            scene.keep = !scene.keep;
+        }
+    }
+
+    /// Put every scene back to the automatic decision.
+    pub fn reset(&mut self) {
+        for scene in &mut self.scenes {
+            scene.keep = scene.initially_kept;
+        }
+    }
+
+    pub fn kept_count(&self) -> usize {
+        self.scenes.iter().filter(|s| s.keep).count()
+    }
+
+    pub fn changed(&self) -> bool {
+        self.scenes.iter().any(|s| s.keep != s.initially_kept)
+    }
+
+    /// The automatic segments with every toggled scene cut out or added in.
+    pub fn kept_spans(&self) -> Vec<(f64, f64)> {
+        let mut spans = self.base_kept.clone();
+        for scene in self.scenes.iter().filter(|s| s.keep != s.initially_kept) {
+            if scene.keep {
+                spans.push((scene.start, scene.end));
+            } else {
+                spans = subtract(spans, (scene.start, scene.end));
+            }
+        }
+        merge(spans)
+    }
+
+    /// Rewrite `cuts` to the reviewed decisions.
+    pub fn apply_to(&self, cuts: &mut DraftCuts) {
+        let kept = self.kept_spans();
+        cuts.cut_points = kept
+            .windows(2)
+            .filter(|w| w[1].0 - w[0].1 > MIN_GAP_SECS)
+            .map(|w| (w[0].1, w[1].0))
+            .collect();
+        cuts.kept = kept;
+        cuts.removed = self.scenes.len() - self.kept_count();
+    }
+}
+
+/// What the user decided when leaving the review screen.
+enum Outcome {
+    Render,
+    Cancel,
+}
+
+/// Analyse `input` for `intent`, review the scenes in the terminal, then
+/// render the reviewed cuts to `output`.
+pub async fn review(
+    input: &Path,
+    intent: &str,
+    output: &Path,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    super::smart_edit_with_mode(
+        input, intent, output, false, None, None, None, None, None, true, true,
+        RenderMode::Analyze,
+    )
+    .await?;
+
+    let input_parent = input.parent().ok_or("Input path has no parent")?;
+    let cuts_path = DraftCuts::path_for(input_parent, &super::job_prefix_for(input));
+    // Just written by the analysis above, under the settings the final
+    // render below will check them against
+    let mut cuts = DraftCuts::load(&cuts_path)
+        .filter(|cuts| cuts.key.intent == intent && cuts.source_unchanged(input))
+        .ok_or("Scene analysis left no cuts to review")?;
+    let scoring = EditPlan::load_for(output)
+        .and_then(|plan| plan.scoring)
+        .ok_or("Scene analysis recorded no scores")?;
+    if scoring.scenes.is_empty() {
+        return Err("No scenes to review".into());
+    }
+
+    let mut state = ReviewState::new(&scoring, &cuts.kept);
+    let frame_path = input_parent.join(format!("synoid_{}_review_frame.jpg", super::job_prefix_for(input)));
+    let mut terminal = ratatui::init();
+    let outcome = run(&mut terminal, &mut state, input, &frame_path).await;
+    ratatui::restore();
+    let _ = std::fs::remove_file(&frame_path);
+
+    match outcome? {
+        Outcome::Cancel => {
+            let _ = std::fs::remove_file(&cuts_path);
+            Ok("Review cancelled; nothing rendered".to_string())
+        }
+        Outcome::Render => {
+            if state.changed() {
+                state.apply_to(&mut cuts);
+                cuts.save(&cuts_path)?;
+            }
+            super::smart_edit_with_mode(
+                input, intent, output, false, None, None, None, None, None, true, true,
+                RenderMode::Final,
+            )
+            .await
+        }
+    }
+}
+
+/// The frame last dumped for preview and the scene it belongs to.
+struct Preview {
+    scene: usize,
+    image: image::RgbImage,
+}
+
+async fn run(
+    terminal: &mut DefaultTerminal,
+    state: &mut ReviewState,
+    input: &Path,
+    frame_path: &Path,
+) -> Result<Outcome, Box<dyn std::error::Error + Send + Sync>> {
+    let mut preview: Option<Preview> = None;
+    let mut status = String::new();
+    loop {
+        terminal.draw(|frame| draw(frame, state, input, preview.as_ref(), &status))?;
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => state.prev(),
+            KeyCode::Down | KeyCode::Char('j') => state.next(),
+            KeyCode::Char(' ') => state.toggle(),
+            KeyCode::Char('u') => state.reset(),
+            KeyCode::Char('f') => {
+                let Some(scene) = state.current() else { continue };
+                let mid = (scene.start + scene.end) / 2.0;
+                status = match dump_frame(input, mid, frame_path).await {
+                    Ok(image) => {
+                        preview = Some(Preview { scene: state.cursor, image });
+                        String::new()
+                    }
+                    Err(e) => format!("Frame preview failed: {}", e),
+                };
+            }
+            KeyCode::Char('p') => {
+                let Some(scene) = state.current() else { continue };
+                status = match play(input, scene.start, scene.end - scene.start) {
+                    Ok(()) => format!("Playing {} in ffplay", format_timestamp(scene.start)),
+                    Err(e) => format!("Could not start ffplay: {}", e),
+                };
+            }
+            KeyCode::Enter => return Ok(Outcome::Render),
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(Outcome::Cancel),
+            _ => {}
+        }
+    }
+}
+
+async fn dump_frame(
+    input: &Path,
+    at: f64,
+    frame_path: &Path,
+) -> Result<image::RgbImage, Box<dyn std::error::Error + Send + Sync>> {
+    let out = proc::ffmpeg(ProcClass::Thumbnail)
+        .stealth()
+        .args(["-y", "-ss", &format!("{:.3}", at), "-i"])
+        .arg(input)
+        .args(["-frames:v", "1", "-q:v", "2"])
+        .arg(frame_path)
+        .output()
+        .await?;
+    if !out.status.success() {
+        return Err("ffmpeg could not extract a frame".into());
+    }
+    Ok(image::open(frame_path)?.to_rgb8())
+}
+
+/// Play `duration` seconds from `start` in ffplay, detached from the review.
+fn play(input: &Path, start: f64, duration: f64) -> std::io::Result<()> {
+    // Unmanaged: the player is interactive and must open now, not queue
+    // behind renders; it is never waited on.
+    proc::unmanaged("ffplay")
+        .args(["-loglevel", "quiet", "-autoexit"])
+        .args(["-ss", &format!("{:.3}", start), "-t", &format!("{:.3}", duration)])
+        .arg(input)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+fn draw(frame: &mut Frame, state: &ReviewState, input: &Path, preview: Option<&Preview>, status: &str) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3), Constraint::Length(1)])
+        .split(frame.area());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(rows[1]);
+
+    let name = input
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let header = format!(
+        " Review {} — {}/{} scenes kept (threshold {:.2}){}",
+        name,
+        state.kept_count(),
+        state.scenes.len(),
+        state.keep_threshold,
+        if state.changed() { ", edited" } else { "" }
+    );
+    frame.render_widget(Paragraph::new(header).style(Style::default().add_modifier(Modifier::BOLD)), rows[0]);
+
+    let items: Vec<ListItem> = state
+        .scenes
+        .iter()
+        .map(|s| {
+            let (mark, color) = if s.keep { ("[keep]", Color::Green) } else { ("[cut] ", Color::Red) };
+            let changed = if s.keep != s.initially_kept { "*" } else { " " };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{}{} ", mark, changed), Style::default().fg(color)),
+                Span::raw(format!(
+                    "{:>8}–{:<8} {:>5.1}s  {:.2}  ",
+                    format_timestamp(s.start),
+                    format_timestamp(s.end),
+                    s.end - s.start,
+                    s.score
+                )),
+                Span::styled(s.note.clone(), Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+    let mut list_state = ListState::default().with_selected(Some(state.cursor));
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(" Scenes "))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+        columns[0],
+        &mut list_state,
+    );
+
+    let block = Block::default().borders(Borders::ALL).title(" Preview ");
+    let inner = block.inner(columns[1]);
+    frame.render_widget(block, columns[1]);
+    match preview.filter(|p| p.scene == state.cursor) {
+        Some(p) => frame.render_widget(Paragraph::new(half_blocks(&p.image, inner)), inner),
+        None => {
+            let detail = state
+                .current()
+                .map(|s| format!("Score {:.2}\n{}\n\nf: show a frame   p: play in ffplay", s.score, s.note))
+                .unwrap_or_default();
+            frame.render_widget(Paragraph::new(detail).wrap(Wrap { trim: true }), inner);
+        }
+    }
+
+    let footer = if status.is_empty() {
+        " ↑/↓ move  space keep/cut  u undo all  f frame  p play  enter render  q quit"
+    } else {
+        status
+    };
+    frame.render_widget(Paragraph::new(footer).style(Style::default().fg(Color::DarkGray)), rows[2]);
+}
+
+/// `image` scaled into `area`, two pixels per cell using "▀" with the top
+/// pixel as foreground and the bottom as background.
+fn half_blocks(image: &image::RgbImage, area: Rect) -> Vec<Line<'static>> {
+    if area.width == 0 || area.height == 0 {
+        return Vec::new();
+    }
+    let scaled = image::imageops::resize(
+        image,
+        u32::from(area.width),
+        u32::from(area.height) * 2,
+        image::imageops::FilterType::Triangle,
+    );
+    let rgb = |x: u32, y: u32| {
+        let p = scaled.get_pixel(x, y);
+        Color::Rgb(p[0], p[1], p[2])
+    };
+    (0..u32::from(area.height))
+        .map(|row| {
+            Line::from(
+                (0..scaled.width())
+                    .map(|x| Span::styled("▀", Style::default().fg(rgb(x, row * 2)).bg(rgb(x, row * 2 + 1))))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::draft::CutsKey;
+    use super::super::explain::SceneScore;
+    use super::super::scene_ops::ScoreBreakdown;
+
+    fn scored(start: f64, end: f64, score: f64) -> SceneScore {
+        SceneScore {
+            start,
+            end,
+            breakdown: ScoreBreakdown {
+                score,
+                ..Default::default()
+            },
+            audio_mix: None,
+            dropped: None,
+        }
+    }
+
+    fn state() -> ReviewState {
+        let scoring = ScoringReport {
+            scenes: vec![
+                scored(0.0, 10.0, 0.8),
+                scored(10.0, 20.0, 0.2),
+                scored(20.0, 30.0, 0.7),
+                scored(30.0, 40.0, 0.6),
+            ],
+            keep_threshold: 0.4,
+            source_duration: 40.0,
+        };
+        // Edge tightening trimmed the first kept segment's head
+        ReviewState::new(&scoring, &[(0.5, 10.0), (20.0, 40.0)])
+    }
+
+    #[test]
+    fn starts_from_the_automatic_decisions() {
+        let state = state();
+        let keep: Vec<bool> = state.scenes.iter().map(|s| s.keep).collect();
+        assert_eq!(keep, vec![true, false, true, true]);
+        assert!(!state.changed());
+        assert_eq!(state.kept_spans(), vec![(0.5, 10.0), (20.0, 40.0)]);
+    }
+
+    #[test]
+    fn cursor_stays_in_bounds() {
+        let mut state = state();
+        state.prev();
+        assert_eq!(state.cursor, 0);
+        for _ in 0..10 {
+            state.next();
+        }
+        assert_eq!(state.cursor, 3);
+    }
+
+    #[test]
+    fn toggles_add_and_remove_spans() {
+        let mut state = state();
+        state.next();
+        state.toggle(); // keep 10-20
+        state.next();
+        state.toggle(); // cut 20-30
+        assert!(state.changed());
+        assert_eq!(state.kept_count(), 3);
+        assert_eq!(state.kept_spans(), vec![(0.5, 20.0), (30.0, 40.0)]);
+
+        let mut cuts = DraftCuts {
+            key: CutsKey {
+                intent: "remove boring".to_string(),
+                ..Default::default()
+            },
+            source_len: 0,
+            source_modified: 0,
+            kept: Vec::new(),
+            cut_points: Vec::new(),
+            removed: 0,
+            scene_count: 4,
+            exclusions: Vec::new(),
+        };
+        state.apply_to(&mut cuts);
+        assert_eq!(cuts.kept, vec![(0.5, 20.0), (30.0, 40.0)]);
+        assert_eq!(cuts.cut_points, vec![(20.0, 30.0)]);
+        assert_eq!(cuts.removed, 1);
+
+        state.toggle();
+        state.reset();
+        assert!(!state.changed());
+        assert_eq!(state.kept_spans(), vec![(0.5, 10.0), (20.0, 40.0)]);
+    }
+}
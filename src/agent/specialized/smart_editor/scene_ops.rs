@@ -1,6 +1,11 @@
-use tokio::process::Command;
+use crate::agent::engines::proc::{self, ProcClass};
 use crate::agent::engines::process_utils::CommandExt;
 use super::types::{Scene, EditIntent, EditingStrategy, EditDensity};
+use super::funny;
+use super::interval_index::IntervalIndex;
+use super::keywords::{keyword_boost, matched_keywords, passes_keyword_filters, scene_words};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use crate::agent::tools::transcription::TranscriptSegment;
 use tracing::info;
 use std::path::Path;
@@ -129,7 +134,7 @@ pub async fn detect_scenes(
     );
 
     // Get total duration first
-    let duration_output = Command::new("ffprobe")
+    let duration_output = proc::ffprobe()
         .stealth()
         .args([
             "-v",
@@ -154,11 +159,11 @@ pub async fn detect_scenes(
 
     info!("[SMART] Video duration: {:.2}s", total_duration);
 
-    // Scale timeout to 3× video duration (min 30 min) so long videos don't false-timeout
-    let timeout_secs = (total_duration as u64 * 3).max(1800);
+    let timeout_secs = scan_timeout_secs(total_duration);
 
     // Use FFmpeg to detect scene changes; -hwaccel auto uses NVDEC on NVIDIA GPUs for fast decode
-    let child = Command::new("ffmpeg")
+    let mut detect = proc::ffmpeg(ProcClass::Thumbnail);
+    detect
         .stealth()
         .args([
             "-hwaccel",
@@ -170,10 +175,9 @@ pub async fn detect_scenes(
             "-f",
             "null",
             "-",
-        ])
-        .output();
+        ]);
 
-    let output = match tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), child).await {
+    let output = match tokio::time::timeout(std::time::Duration::from_secs(timeout_secs), detect.output()).await {
         Ok(res) => res?,
         Err(_) => return Err(format!(
             "FFmpeg scene detection timed out after {} minutes (video is {:.0}s)",
@@ -185,21 +189,37 @@ pub async fn detect_scenes(
     let stderr = String::from_utf8_lossy(&output.stderr);
 
     // Parse scene timestamps from showinfo output
-    let mut timestamps: Vec<f64> = vec![0.0]; // Start at 0
-
+    let mut cuts = Vec::new();
     for line in stderr.lines() {
         if line.contains("showinfo") && line.contains("pts_time:") {
             if let Some(pts_idx) = line.find("pts_time:") {
                 let rest = &line[pts_idx + 9..];
                 if let Some(space_idx) = rest.find(' ') {
                     if let Ok(ts) = rest[..space_idx].parse::<f64>() {
-                        timestamps.push(ts);
+                        cuts.push(ts);
                     }
                 }
             }
         }
     }
 
+    let scenes = scenes_from_cuts(&cuts, total_duration);
+    info!("[SMART] Detected {} scenes", scenes.len());
+    Ok(scenes)
+}
+
+/// Time allowed for one decode of a `total_duration`-second input: 3× its
+/// length, at least 30 minutes, so long videos don't false-timeout.
+pub fn scan_timeout_secs(total_duration: f64) -> u64 {
+    (total_duration as u64 * 3).max(1800)
+}
+
+/// Scenes between the scene-change timestamps `cuts` of a
+/// `total_duration`-second input. Slivers under 0.5 s are dropped; with no
+/// usable cut the whole input is one scene.
+pub fn scenes_from_cuts(cuts: &[f64], total_duration: f64) -> Vec<Scene> {
+    let mut timestamps: Vec<f64> = vec![0.0]; // Start at 0
+    timestamps.extend_from_slice(cuts);
     timestamps.push(total_duration); // End at total duration
     timestamps.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
     timestamps.dedup();
@@ -222,6 +242,8 @@ pub async fn detect_scenes(
             duration: dur,
             score: 0.5, // Neutral score initially
             vision_tags: Vec::new(),
+            breakdown: None,
+            audio_mix: None,
         });
     }
 
@@ -233,11 +255,11 @@ pub async fn detect_scenes(
             duration: total_duration,
             score: 1.0,
             vision_tags: Vec::new(),
+            breakdown: None,
+            audio_mix: None,
         });
     }
-
-    info!("[SMART] Detected {} scenes", scenes.len());
-    Ok(scenes)
+    scenes
 }
 
 pub fn ensure_speech_continuity(
@@ -251,37 +273,16 @@ pub fn ensure_speech_continuity(
     }
 
     info!(
-        "[SMART] 🔗 Enforcing Speech Continuity (O(N+M) optimized, Boost: {}, Ruthless: {})...",
+        "[SMART] 🔗 Enforcing Speech Continuity (interval-indexed, Boost: {}, Ruthless: {})...",
         config.continuity_boost, is_ruthless
     );
 
-    // Optimized O(N+M)
-    let mut scene_idx = 0;
+    // Scores are raised segment by segment and later segments see earlier
+    // boosts, so this stays sequential; only the overlap lookup is indexed.
+    let index = IntervalIndex::new(scenes.iter().map(|s| (s.start_time, s.end_time)));
     for segment in transcript {
-        // Advance scene_idx to first potential overlap
-        while scene_idx < scenes.len() && scenes[scene_idx].end_time <= segment.start {
-            scene_idx += 1;
-        }
-
-        let mut overlapping_indices = Vec::new();
-        let mut should_preserve_sentence = false;
-
-        for i in scene_idx..scenes.len() {
-            let scene = &scenes[i];
-            if scene.start_time >= segment.end {
-                break;
-            }
-
-            let overlap_start = segment.start.max(scene.start_time);
-            let overlap_end = segment.end.min(scene.end_time);
-
-            if overlap_end > overlap_start {
-                overlapping_indices.push(i);
-                if scene.score > 0.3 {
-                    should_preserve_sentence = true;
-                }
-            }
-        }
+        let overlapping_indices = index.overlapping(segment.start, segment.end);
+        let should_preserve_sentence = overlapping_indices.iter().any(|&i| scenes[i].score > 0.3);
 
         if should_preserve_sentence {
             let mut max_score: f64 = 0.0;
@@ -339,6 +340,8 @@ pub fn refine_scenes_with_transcript(
                     duration: segment.start - current_start,
                     score: 0.0, // Silence/Gap
                     vision_tags: scene.vision_tags.clone(),
+                    breakdown: None,
+                    audio_mix: None,
                 });
                 current_start = segment.start;
             }
@@ -352,6 +355,8 @@ pub fn refine_scenes_with_transcript(
                     duration: seg_end_bounded - current_start,
                     score: 0.5, // Initial neutral score
                     vision_tags: scene.vision_tags.clone(),
+                    breakdown: None,
+                    audio_mix: None,
                 });
                 current_start = seg_end_bounded;
             }
@@ -375,6 +380,8 @@ pub fn refine_scenes_with_transcript(
                 duration: scene.end_time - current_start,
                 score: 0.0,
                 vision_tags: scene.vision_tags.clone(),
+                breakdown: None,
+                audio_mix: None,
             });
         }
     }
@@ -384,179 +391,431 @@ pub fn refine_scenes_with_transcript(
     refined
 }
 
+/// Score every scene in place, recording on each how its score was reached.
+/// The breakdowns are also returned in scene order.
 pub fn score_scenes(
     scenes: &mut [Scene],
     intent: &EditIntent,
     transcript: Option<&[TranscriptSegment]>,
     config: &EditingStrategy,
     total_duration: f64,
-) {
+) -> Vec<ScoreBreakdown> {
     info!(
-        "[SMART] Scoring {} scenes (O(N+M) optimized)...",
+        "[SMART] Scoring {} scenes (interval-indexed, parallel)...",
         scenes.len()
     );
 
-    // Prepare transcript lookup for O(N+M)
-    let mut seg_ptr = 0;
+    // Each scene's score depends only on the scene and the transcript, so
+    // score them in parallel against a shared overlap index.
+    let index = transcript.map(|segs| IntervalIndex::new(segs.iter().map(|s| (s.start, s.end))));
+    let lookup = transcript.zip(index.as_ref());
+    let mut breakdowns: Vec<ScoreBreakdown> = scenes
+        .par_iter_mut()
+        .map(|scene| {
+            let breakdown = score_scene(scene, intent, lookup, config, total_duration);
+            scene.score = breakdown.score;
+            breakdown
+        })
+        .collect();
 
-    for scene in scenes.iter_mut() {
-        // Base score depends on density
-        let mut score: f64 = match intent.density {
-            EditDensity::Highlights => 0.25,
-            EditDensity::Balanced => 0.35,
-            EditDensity::Full => 0.60,
-        };
+    if let Some(segments) = transcript {
+        info!("[SMART] Applying speech continuity protection to prevent mid-word cuts.");
+        ensure_speech_continuity(scenes, segments, config, intent.ruthless);
+        for (scene, breakdown) in scenes.iter().zip(breakdowns.iter_mut()) {
+            breakdown.set(ScoreComponent::Continuity, "part of a kept sentence", scene.score);
+        }
+    }
+    for (scene, breakdown) in scenes.iter_mut().zip(&breakdowns) {
+        scene.breakdown = Some(breakdown.clone());
+    }
+    breakdowns
+}
 
-        let progress = if total_duration > 0.0 {
-            scene.start_time / total_duration
-        } else {
-            0.0
-        };
+/// Hard-apply the intent's Require/Exclude keywords: scenes missing a
+/// required term or containing an excluded one are removed outright, so no
+/// later fallback or gap-bridging pass can bring them back. Returns the number
+/// of scenes dropped.
+pub fn apply_keyword_filters(
+    scenes: &mut Vec<Scene>,
+    intent: &EditIntent,
+    transcript: Option<&[TranscriptSegment]>,
+) -> usize {
+    use super::keywords::KeywordMode;
+    if intent.keywords.iter().all(|k| k.mode == KeywordMode::Boost) {
+        return 0;
+    }
+    let index = transcript.map(|segs| IntervalIndex::new(segs.iter().map(|s| (s.start, s.end))));
+    let lookup = transcript.zip(index.as_ref());
+    let keep: Vec<bool> = scenes
+        .par_iter()
+        .map(|scene| {
+            let matched = matched_keywords(&intent.keywords, &scene_words(scene, lookup));
+            passes_keyword_filters(&intent.keywords, &matched)
+        })
+        .collect();
+    let before = scenes.len();
+    let mut keep = keep.into_iter();
+    scenes.retain(|_| keep.next().unwrap_or(false));
+    before - scenes.len()
+}
+
+/// Names of the intent keywords found in each scene, for the edit plan.
+pub fn scene_keyword_report(
+    scenes: &[Scene],
+    intent: &EditIntent,
+    transcript: Option<&[TranscriptSegment]>,
+) -> Vec<Vec<String>> {
+    if intent.keywords.is_empty() {
+        return vec![Vec::new(); scenes.len()];
+    }
+    let index = transcript.map(|segs| IntervalIndex::new(segs.iter().map(|s| (s.start, s.end))));
+    let lookup = transcript.zip(index.as_ref());
+    scenes
+        .par_iter()
+        .map(|scene| {
+            matched_keywords(&intent.keywords, &scene_words(scene, lookup))
+                .into_iter()
+                .map(|i| intent.keywords[i].term.clone())
+                .collect()
+        })
+        .collect()
+}
 
-        if progress < 0.2 {
-            score += 0.1;
+/// The part of scoring a factor belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreComponent {
+    /// Opening bonus and late-video penalty growth.
+    Positional,
+    BoringPenalty,
+    Action,
+    Vision,
+    Speech,
+    Silence,
+    Fun,
+    Keyword,
+    Ruthless,
+    /// Raised to keep a spoken sentence whole.
+    Continuity,
+    /// Learned pacing adjustments.
+    Pattern,
+    /// Replaced by the external scorer.
+    External,
+    /// Kept or cut audio classes ("keep the music sections").
+    Audio,
+}
+
+impl ScoreComponent {
+    pub const ALL: [ScoreComponent; 13] = [
+        ScoreComponent::Positional,
+        ScoreComponent::BoringPenalty,
+        ScoreComponent::Action,
+        ScoreComponent::Vision,
+        ScoreComponent::Speech,
+        ScoreComponent::Silence,
+        ScoreComponent::Fun,
+        ScoreComponent::Keyword,
+        ScoreComponent::Ruthless,
+        ScoreComponent::Continuity,
+        ScoreComponent::Pattern,
+        ScoreComponent::External,
+        ScoreComponent::Audio,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ScoreComponent::Positional => "positional",
+            ScoreComponent::BoringPenalty => "boring_penalty",
+            ScoreComponent::Action => "action",
+            ScoreComponent::Vision => "vision",
+            ScoreComponent::Speech => "speech",
+            ScoreComponent::Silence => "silence",
+            ScoreComponent::Fun => "fun",
+            ScoreComponent::Keyword => "keyword",
+            ScoreComponent::Ruthless => "ruthless",
+            ScoreComponent::Continuity => "continuity",
+            ScoreComponent::Pattern => "pattern",
+            ScoreComponent::External => "external",
+            ScoreComponent::Audio => "audio",
         }
+    }
+}
 
-        let penalty_multiplier = if progress > 0.2 {
-            1.0 + ((progress - 0.2) / 0.8) * 0.5
-        } else {
-            1.0
-        };
+/// One signed contribution to a scene's score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreFactor {
+    pub component: ScoreComponent,
+    pub reason: String,
+    pub delta: f64,
+}
 
-        if intent.remove_boring {
-            let boring_penalty = match intent.density {
-                EditDensity::Highlights => 0.4,
-                EditDensity::Balanced => 0.2,
-                EditDensity::Full => 0.05,
-            };
-            let effective_penalty = boring_penalty * penalty_multiplier;
-
-            if scene.duration > config.boring_penalty_threshold {
-                score -= effective_penalty;
-            } else if scene.duration > 15.0 {
-                score -= effective_penalty / 2.0;
-            }
+/// How a scene's score was reached, for tuning `EditingStrategy` and for
+/// the edit explanation. Each component field is the sum of its factors.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoreBreakdown {
+    /// Starting score for the intent's density.
+    pub base: f64,
+    pub positional: f64,
+    pub boring_penalty: f64,
+    pub action: f64,
+    pub vision: f64,
+    pub speech: f64,
+    pub silence: f64,
+    pub fun: f64,
+    pub keyword: f64,
+    pub ruthless: f64,
+    pub continuity: f64,
+    pub pattern: f64,
+    pub external: f64,
+    pub audio: f64,
+    /// Final score, the same value written to `Scene::score`.
+    pub score: f64,
+    /// Every adjustment in the order applied. Deltas are taken before the
+    /// final clamp to 0..1, so they may not sum exactly to `score - base`.
+    pub factors: Vec<ScoreFactor>,
+}
+
+impl ScoreBreakdown {
+    pub fn new(base: f64) -> Self {
+        Self {
+            base,
+            score: base,
+            ..Default::default()
         }
+    }
 
-        if intent.keep_action
-            && scene.duration < config.action_duration_threshold
-            && scene.duration >= 2.0
-        {
-            score += 0.15;
+    pub fn component(&self, component: ScoreComponent) -> f64 {
+        match component {
+            ScoreComponent::Positional => self.positional,
+            ScoreComponent::BoringPenalty => self.boring_penalty,
+            ScoreComponent::Action => self.action,
+            ScoreComponent::Vision => self.vision,
+            ScoreComponent::Speech => self.speech,
+            ScoreComponent::Silence => self.silence,
+            ScoreComponent::Fun => self.fun,
+            ScoreComponent::Keyword => self.keyword,
+            ScoreComponent::Ruthless => self.ruthless,
+            ScoreComponent::Continuity => self.continuity,
+            ScoreComponent::Pattern => self.pattern,
+            ScoreComponent::External => self.external,
+            ScoreComponent::Audio => self.audio,
         }
+    }
 
-        // Vision Heuristics
-        let mut has_bad_app = false;
-        let mut has_main_app = false;
-        for tag in &scene.vision_tags {
-            let t = tag.to_lowercase();
-            if t.contains("discord") || t.contains("browser") || t.contains("desktop") {
-                has_bad_app = true;
-            }
-            if t.contains("main_app") || t.contains("game") {
-                has_main_app = true;
-            }
+    fn component_mut(&mut self, component: ScoreComponent) -> &mut f64 {
+        match component {
+            ScoreComponent::Positional => &mut self.positional,
+            ScoreComponent::BoringPenalty => &mut self.boring_penalty,
+            ScoreComponent::Action => &mut self.action,
+            ScoreComponent::Vision => &mut self.vision,
+            ScoreComponent::Speech => &mut self.speech,
+            ScoreComponent::Silence => &mut self.silence,
+            ScoreComponent::Fun => &mut self.fun,
+            ScoreComponent::Keyword => &mut self.keyword,
+            ScoreComponent::Ruthless => &mut self.ruthless,
+            ScoreComponent::Continuity => &mut self.continuity,
+            ScoreComponent::Pattern => &mut self.pattern,
+            ScoreComponent::External => &mut self.external,
+            ScoreComponent::Audio => &mut self.audio,
         }
+    }
 
-        if has_bad_app {
-            score -= 1.0;
-            info!("[SMART] 🛑 Penalizing scene at {:.1}s due to detected background app.", scene.start_time);
-        } else if has_main_app {
-            score += 0.2;
+    /// Add `delta` to `component` under `reason`, folding it into an earlier
+    /// factor with the same reason.
+    pub fn add(&mut self, component: ScoreComponent, reason: &str, delta: f64) {
+        if delta == 0.0 {
+            return;
+        }
+        self.score += delta;
+        *self.component_mut(component) += delta;
+        match self.factors.iter_mut().find(|f| f.reason == reason) {
+            Some(factor) => factor.delta += delta,
+            None => self.factors.push(ScoreFactor {
+                component,
+                reason: reason.to_string(),
+                delta,
+            }),
         }
+    }
 
-        // Semantic Heuristics (Transcript Analysis) - OPTIMIZED O(N+M)
-        if let Some(segments) = transcript {
-            let mut speech_duration = 0.0;
-            let mut has_keyword = false;
-            let mut is_fun = false;
+    /// Record the change to `score` as a factor named `reason`.
+    pub fn set(&mut self, component: ScoreComponent, reason: &str, score: f64) {
+        self.add(component, reason, score - self.score);
+        self.score = score;
+    }
+}
 
-            // Advance pointer to first relevant segment
-            while seg_ptr < segments.len() && segments[seg_ptr].end <= scene.start_time {
-                seg_ptr += 1;
-            }
+/// Score a single scene. `transcript` pairs the segments with their overlap index.
+fn score_scene(
+    scene: &Scene,
+    intent: &EditIntent,
+    transcript: Option<(&[TranscriptSegment], &IntervalIndex)>,
+    config: &EditingStrategy,
+    total_duration: f64,
+) -> ScoreBreakdown {
+    use ScoreComponent::*;
+
+    // Base score depends on density
+    let mut b = ScoreBreakdown::new(match intent.density {
+        EditDensity::Highlights => 0.25,
+        EditDensity::Balanced => 0.35,
+        EditDensity::Full => 0.60,
+    });
+
+    let progress = if total_duration > 0.0 {
+        scene.start_time / total_duration
+    } else {
+        0.0
+    };
 
-            // Check all overlapping segments
-            for i in seg_ptr..segments.len() {
-                let seg = &segments[i];
-                if seg.start >= scene.end_time {
-                    break;
-                }
+    if progress < 0.2 {
+        b.add(Positional, "opening of the video", 0.1);
+    }
 
-                let overlap_start = seg.start.max(scene.start_time);
-                let overlap_end = seg.end.min(scene.end_time);
+    // Penalties grow up to 1.5× toward the end; the growth is reported
+    // separately as the late-video penalty.
+    let penalty_multiplier = if progress > 0.2 {
+        1.0 + ((progress - 0.2) / 0.8) * 0.5
+    } else {
+        1.0
+    };
+    let late = penalty_multiplier - 1.0;
 
-                if overlap_end > overlap_start {
-                    speech_duration += overlap_end - overlap_start;
-                    let text_lower = seg.text.to_lowercase();
+    if intent.remove_boring {
+        let boring_penalty = match intent.density {
+            EditDensity::Highlights => 0.4,
+            EditDensity::Balanced => 0.2,
+            EditDensity::Full => 0.05,
+        };
 
-                    if !intent.custom_keywords.is_empty() {
-                        for keyword in &intent.custom_keywords {
-                            if text_lower.contains(&keyword.to_lowercase()) {
-                                has_keyword = true;
-                            }
-                        }
-                    }
+        if scene.duration > config.boring_penalty_threshold {
+            b.add(BoringPenalty, "scene exceeded boring threshold", -boring_penalty);
+            b.add(Positional, "late-video penalty", -boring_penalty * late);
+        } else if scene.duration > 15.0 {
+            b.add(BoringPenalty, "long scene (over 15s)", -boring_penalty / 2.0);
+            b.add(Positional, "late-video penalty", -boring_penalty * late / 2.0);
+        }
+    }
 
-                    if seg.text.contains("!") || seg.text.contains("?!") {
-                        is_fun = true;
-                    }
-                    let fun_words = [
-                        "wow", "haha", "lol", "cool", "omg", "whoa", "crazy", "funny", "hilarious",
-                    ];
-                    if fun_words.iter().any(|&w| text_lower.contains(w)) {
-                        is_fun = true;
-                    }
-                }
-            }
+    if intent.keep_action
+        && scene.duration < config.action_duration_threshold
+        && scene.duration >= 2.0
+    {
+        b.add(Action, "action-length scene", 0.15);
+    }
 
-            let speech_ratio = speech_duration / scene.duration;
+    // Vision Heuristics
+    let mut has_bad_app = false;
+    let mut has_main_app = false;
+    for tag in &scene.vision_tags {
+        let t = tag.to_lowercase();
+        if t.contains("discord") || t.contains("browser") || t.contains("desktop") {
+            has_bad_app = true;
+        }
+        if t.contains("main_app") || t.contains("game") {
+            has_main_app = true;
+        }
+    }
 
-            if intent.keep_speech {
-                if speech_ratio > config.speech_ratio_threshold {
-                    score += config.speech_boost;
-                }
-            } else {
-                if speech_ratio > 0.3 {
-                    score += config.speech_boost;
-                } else if speech_ratio > 0.1 {
-                    score += config.speech_boost * 0.5;
-                }
-            }
+    if has_bad_app {
+        b.add(Vision, "background app on screen", -1.0);
+        info!("[SMART] 🛑 Penalizing scene at {:.1}s due to detected background app.", scene.start_time);
+    } else if has_main_app {
+        b.add(Vision, "main app or game on screen", 0.2);
+    }
 
-            if speech_ratio > 0.1 {
-                score = score.max(0.95);
-            }
+    // Semantic Heuristics (Transcript Analysis) - interval-indexed
+    if let Some((segments, index)) = transcript {
+        let mut speech_duration = 0.0;
+        let mut is_fun = false;
+
+        // Check all overlapping segments
+        for i in index.overlapping(scene.start_time, scene.end_time) {
+            let seg = &segments[i];
 
-            if intent.remove_silence {
-                let penalty = config.silence_penalty * penalty_multiplier;
-                if speech_ratio < 0.05 {
-                    score += penalty;
-                } else if speech_ratio < 0.2 {
-                    score += penalty / 2.0;
+            let overlap_start = seg.start.max(scene.start_time);
+            let overlap_end = seg.end.min(scene.end_time);
+
+            if overlap_end > overlap_start {
+                speech_duration += overlap_end - overlap_start;
+                if funny::classify(segments, i).is_some() {
+                    is_fun = true;
                 }
             }
+        }
+
+        let speech_ratio = speech_duration / scene.duration;
 
-            if has_keyword {
-                score += 0.5;
+        if intent.keep_speech {
+            if speech_ratio > config.speech_ratio_threshold {
+                b.add(Speech, "speech", config.speech_boost);
             }
+        } else {
+            if speech_ratio > 0.3 {
+                b.add(Speech, "speech", config.speech_boost);
+            } else if speech_ratio > 0.1 {
+                b.add(Speech, "some speech", config.speech_boost * 0.5);
+            }
+        }
+
+        if speech_ratio > 0.1 {
+            b.set(Speech, "speech is always kept", b.score.max(0.95));
+        }
 
-            if is_fun {
-                score += 0.25;
+        // A wordless stretch of music or game sound is not dead air
+        let scored_sound = scene
+            .audio_mix
+            .is_some_and(|mix| mix.music + mix.sfx >= 0.5);
+        if intent.remove_silence && !scored_sound {
+            if speech_ratio < 0.05 {
+                b.add(Silence, "no speech", config.silence_penalty);
+                b.add(Positional, "late-video penalty", config.silence_penalty * late);
+            } else if speech_ratio < 0.2 {
+                b.add(Silence, "little speech", config.silence_penalty / 2.0);
+                b.add(Positional, "late-video penalty", config.silence_penalty * late / 2.0);
             }
         }
 
-        if intent.ruthless || intent.density == EditDensity::Highlights {
-            score -= 0.05;
+        if is_fun {
+            b.add(Fun, "excited or funny moment", 0.25);
+        }
+    }
+
+    // Audio classes the intent keeps or cuts, once the scene is labelled
+    if let Some(mix) = &scene.audio_mix {
+        for &class in &intent.keep_audio {
+            let share = mix.share(class);
+            if share >= 0.5 {
+                b.add(Audio, &format!("mostly {}", class.name()), 0.4);
+            } else if share >= 0.2 {
+                b.add(Audio, &format!("some {}", class.name()), 0.2);
+            }
+        }
+        for &class in &intent.cut_audio {
+            if mix.share(class) >= 0.5 {
+                b.add(Audio, &format!("mostly {}, which is cut", class.name()), -0.8);
+            }
         }
+    }
 
-        scene.score = score.clamp(0.0, 1.0);
+    // Intent keywords (transcript words + vision tags), scaled by weight
+    if !intent.keywords.is_empty() {
+        let matched = matched_keywords(&intent.keywords, &scene_words(scene, transcript));
+        let terms: Vec<&str> = matched.iter().map(|&i| intent.keywords[i].term.as_str()).collect();
+        b.add(
+            Keyword,
+            &format!("mentions {}", terms.join(", ")),
+            keyword_boost(&intent.keywords, &matched),
+        );
     }
 
-    if let Some(segments) = transcript {
-        info!("[SMART] Applying speech continuity protection to prevent mid-word cuts.");
-        ensure_speech_continuity(scenes, segments, config, intent.ruthless);
+    if intent.ruthless || intent.density == EditDensity::Highlights {
+        b.add(Ruthless, "ruthless trim", -0.05);
     }
+
+    b.score = b.score.clamp(0.0, 1.0);
+    b
 }
 
 pub fn scene_has_speech(scene: &Scene, transcript: Option<&[TranscriptSegment]>) -> bool {
@@ -574,3 +833,124 @@ pub fn scene_has_speech(scene: &Scene, transcript: Option<&[TranscriptSegment]>)
     }
     false
 }
+
+/// Tighten the edit's in-point to `padding` seconds before the first spoken
+/// word and its out-point to `padding` after the last one, so outputs don't
+/// open on dead air or close on a long breath.
+///
+/// Only the first scene's start and the last scene's end move, and only
+/// inward — this adjusts the trim values fed to extraction, so no extra
+/// encode is needed. Word timestamps are used when available, otherwise
+/// segment bounds. An edge that falls inside a segment is left alone, since
+/// speech is already under way there.
+pub fn trim_heads_and_tails(scenes: &mut [Scene], transcript: &[TranscriptSegment], padding: f64) {
+    let padding = padding.max(0.0);
+    let spoken = |seg: &&TranscriptSegment| !seg.text.trim().is_empty();
+
+    if let Some(first) = scenes.first_mut() {
+        let mid_speech = transcript
+            .iter()
+            .filter(spoken)
+            .any(|seg| seg.start < first.start_time && seg.end > first.start_time);
+        let first_word = transcript
+            .iter()
+            .filter(spoken)
+            .filter(|seg| seg.end > first.start_time && seg.start < first.end_time)
+            .flat_map(|seg| {
+                seg.words
+                    .first()
+                    .map(|w| w.start)
+                    .into_iter()
+                    .chain(std::iter::once(seg.start))
+            })
+            .filter(|t| *t >= first.start_time)
+            .fold(f64::INFINITY, f64::min);
+        if !mid_speech && first_word.is_finite() {
+            let new_start = (first_word - padding).max(first.start_time);
+            if new_start < first.end_time - 0.5 {
+                first.start_time = new_start;
+                first.duration = first.end_time - first.start_time;
+            }
+        }
+    }
+
+    if let Some(last) = scenes.last_mut() {
+        let mid_speech = transcript
+            .iter()
+            .filter(spoken)
+            .any(|seg| seg.start < last.end_time && seg.end > last.end_time);
+        let last_word = transcript
+            .iter()
+            .filter(spoken)
+            .filter(|seg| seg.end > last.start_time && seg.start < last.end_time)
+            .flat_map(|seg| {
+                seg.words
+                    .last()
+                    .map(|w| w.end)
+                    .into_iter()
+                    .chain(std::iter::once(seg.end))
+            })
+            .filter(|t| *t <= last.end_time)
+            .fold(f64::NEG_INFINITY, f64::max);
+        if !mid_speech && last_word.is_finite() {
+            let new_end = (last_word + padding).min(last.end_time);
+            if new_end > last.start_time + 0.5 {
+                last.end_time = new_end;
+                last.duration = last.end_time - last.start_time;
+            }
+        }
+    }
+}
+
+/// Cut the given (start, end) spans out of the scene list, splitting scenes
+/// that straddle a span. Leftover slivers under 0.25 s are dropped.
+pub fn remove_spans(scenes: Vec<Scene>, spans: &[(f64, f64)]) -> Vec<Scene> {
+    let mut out = Vec::with_capacity(scenes.len());
+    for scene in scenes {
+        let mut pieces = vec![(scene.start_time, scene.end_time)];
+        for &(span_start, span_end) in spans {
+            pieces = pieces
+                .into_iter()
+                .flat_map(|(s, e)| {
+                    if span_end <= s || span_start >= e {
+                        vec![(s, e)]
+                    } else {
+                        vec![(s, span_start.max(s)), (span_end.min(e), e)]
+                    }
+                })
+                .filter(|(s, e)| e - s >= 0.25)
+                .collect();
+        }
+        for (start_time, end_time) in pieces {
+            out.push(Scene {
+                start_time,
+                end_time,
+                duration: end_time - start_time,
+                ..scene.clone()
+            });
+        }
+    }
+    out
+}
+
+/// The `n` highest-scoring scenes in source order, skipping any that overlap
+/// one already chosen (transcript refinement can leave overlapping pieces).
+pub fn best_scenes(scenes: &[Scene], n: usize) -> Vec<Scene> {
+    let mut ranked: Vec<&Scene> = scenes.iter().collect();
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let mut chosen: Vec<Scene> = Vec::with_capacity(n);
+    for scene in ranked {
+        if chosen.len() == n {
+            break;
+        }
+        let clear = chosen
+            .iter()
+            .all(|c| scene.end_time <= c.start_time || scene.start_time >= c.end_time);
+        if clear {
+            chosen.push(scene.clone());
+        }
+    }
+    chosen.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+    chosen
+}
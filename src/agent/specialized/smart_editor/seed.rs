@@ -0,0 +1,166 @@
+// SYNOID Smart Editor - Seeded Runs
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// `--seed` makes an edit reproducible for regression testing. A seeded run
+// parses the intent with the heuristic parser instead of the LLM, starts from
+// the default strategy rather than the learned one, and picks its transition
+// from the seed instead of the neuroplasticity level. Every one of those
+// choices, plus the kept cuts, is recorded in the edit plan; re-running with
+// the same seed, input and intent replays the recorded decisions, so the
+// output matches as long as the ffmpeg build does.
+
+use super::draft::DraftCuts;
+use super::edit_plan::EditPlan;
+use super::types::{EditIntent, EditingStrategy};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Transitions a seeded run chooses between, the same set the
+/// neuroplasticity levels map to.
+pub const SEEDED_TRANSITIONS: [&str; 4] = ["fade", "slideleft", "wiperight", "pixelize"];
+
+/// SplitMix64: tiny, fast, and identical on every platform.
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// One of `items`, chosen by the next draw. `items` must not be empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[(self.next_u64() % items.len() as u64) as usize]
+    }
+}
+
+/// The crossfade style a seeded run uses.
+pub fn seeded_transition(seed: u64) -> &'static str {
+    SeededRng::new(seed).pick::<&str>(&SEEDED_TRANSITIONS)
+}
+
+/// Everything a seeded edit decided that could differ between runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditDecisions {
+    pub seed: u64,
+    pub intent: EditIntent,
+    /// Scoring thresholds before any learned pattern was applied.
+    pub strategy: EditingStrategy,
+    pub transition: String,
+    /// The kept cuts, stamped with the source and intent they were made for.
+    pub cuts: DraftCuts,
+}
+
+impl EditDecisions {
+    /// Decisions recorded in the plan next to `output` by an earlier run with
+    /// this seed and external scorer over the same, unchanged input and
+    /// intent. The strategy is not compared: replaying restores the recorded one.
+    pub fn load_matching(
+        output: &Path,
+        seed: u64,
+        input: &Path,
+        intent_key: &str,
+        scorer: Option<&str>,
+    ) -> Option<Self> {
+        EditPlan::load_for(output)?.decisions.filter(|d| {
+            d.seed == seed
+                && d.cuts.key.intent == intent_key
+                && d.cuts.key.scorer.as_deref() == scorer
+                && d.cuts.source_unchanged(input)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::specialized::smart_editor::draft::CutsKey;
+    use crate::agent::specialized::smart_editor::types::{Scene, SceneSelection};
+
+    #[test]
+    fn same_seed_same_draws() {
+        let draws = |seed| {
+            let mut rng = SeededRng::new(seed);
+            (0..4).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(draws(42), draws(42));
+        assert_ne!(draws(42), draws(43));
+        let picks: std::collections::HashSet<_> = (0..32).map(seeded_transition).collect();
+        assert!(picks.len() > 1, "every seed picked the same transition");
+    }
+
+    #[test]
+    fn two_seeded_runs_produce_identical_plans() {
+        let dir = std::env::temp_dir().join(format!("synoid_seeded_plan_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("clip.mp4");
+        std::fs::write(&input, b"not really a video").unwrap();
+        let output = dir.join("clip_edited.mp4");
+        let intent_text = "remove boring parts and keep the action";
+        let scenes = vec![
+            Scene {
+                start_time: 2.0,
+                end_time: 6.5,
+                duration: 4.5,
+                score: 0.8,
+                vision_tags: Vec::new(),
+                breakdown: None,
+                audio_mix: None,
+            },
+            Scene {
+                start_time: 9.0,
+                end_time: 12.0,
+                duration: 3.0,
+                score: 0.6,
+                vision_tags: Vec::new(),
+                breakdown: None,
+                audio_mix: None,
+            },
+        ];
+
+        let run = |seed: u64| {
+            let selection = SceneSelection {
+                kept: scenes.clone(),
+                cut_points: vec![(6.5, 9.0)],
+                removed: 3,
+                scene_count: 5,
+                exclusions: Vec::new(),
+                scoring: None,
+            };
+            let mut plan = EditPlan::from_kept_scenes(&input, &output, &selection.kept, &[], 0.2);
+            plan.decisions = Some(EditDecisions {
+                seed,
+                intent: EditIntent::from_text(intent_text),
+                strategy: EditingStrategy::default(),
+                transition: seeded_transition(seed).to_string(),
+                cuts: DraftCuts::new(
+                    &input,
+                    CutsKey::new(intent_text, Some(seed), &EditingStrategy::default(), None),
+                    &selection,
+                ),
+            });
+            serde_json::to_string_pretty(&plan).unwrap()
+        };
+        assert_eq!(run(7), run(7));
+
+        let plan: EditPlan = serde_json::from_str(&run(7)).unwrap();
+        plan.save().unwrap();
+        let replayed = EditDecisions::load_matching(&output, 7, &input, intent_text, None).unwrap();
+        assert_eq!(replayed.transition, seeded_transition(7));
+        assert_eq!(replayed.cuts.kept, vec![(2.0, 6.5), (9.0, 12.0)]);
+        assert!(EditDecisions::load_matching(&output, 8, &input, intent_text, None).is_none());
+        assert!(EditDecisions::load_matching(&output, 7, &input, "keep everything", None).is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
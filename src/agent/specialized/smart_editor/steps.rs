@@ -0,0 +1,491 @@
+// SYNOID Smart Editor - Multi-Step Requests
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// "First remove silence, then make a 60-second highlights version, and also
+// give me the full cleaned version" is three asks, not one EditIntent. The
+// request is split into ordered steps, each parsed and edited on its own. A
+// step can take an earlier step's output as its input, so the highlights are
+// cut from the cleaned video rather than the raw source, and asking for an
+// intermediate result ("the full cleaned version") needs no extra render.
+// Every step writes its own file; a failed step only skips the steps built
+// on top of it, and finished outputs are never removed.
+
+use crate::agent::ai_systems::gpt_oss_bridge::SynoidAgent;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+/// Most steps one request may be split into.
+pub const MAX_STEPS: usize = 6;
+
+/// Phrases that join separate asks. Plain "and" is left alone: "cut the
+/// silence and the ums" is one edit.
+const STEP_SEPARATORS: [&str; 11] = [
+    ", and then ",
+    " and then ",
+    ", then ",
+    " then ",
+    ", after that ",
+    " after that ",
+    "; ",
+    ", and also ",
+    " and also ",
+    ", also ",
+    ", plus ",
+];
+
+/// Asks that tidy the whole video; later derived cuts start from their output.
+const CLEANUP_WORDS: [&str; 6] = ["silence", "silent", "filler", "pauses", "dead air", "clean"];
+
+/// Asks for a shorter cut made from what came before, and its file label.
+const DERIVED_WORDS: [(&str, &str); 7] = [
+    ("highlight", "highlights"),
+    ("trailer", "trailer"),
+    ("teaser", "teaser"),
+    ("recap", "recap"),
+    ("reel", "reel"),
+    ("short", "short"),
+    ("clip", "clip"),
+];
+
+/// One edit in a compound request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditStep {
+    /// This step's instruction on its own, e.g. "make a 60-second highlights
+    /// version".
+    pub intent: String,
+    /// Short label for the output file ("cleaned", "highlights"). Empty for
+    /// a single-step request, which keeps the requested output name.
+    #[serde(default)]
+    pub name: String,
+    /// Index of the earlier step whose output this step edits; `None` edits
+    /// the original source.
+    #[serde(default)]
+    pub input_from: Option<usize>,
+}
+
+impl EditStep {
+    /// The whole request as one step.
+    pub fn single(intent: &str) -> Self {
+        Self {
+            intent: intent.to_string(),
+            name: String::new(),
+            input_from: None,
+        }
+    }
+}
+
+/// What one step produced.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepOutcome {
+    pub index: usize,
+    pub name: String,
+    pub intent: String,
+    pub input: PathBuf,
+    pub output: PathBuf,
+    /// Why the step failed or was skipped; `None` when `output` was written.
+    pub error: Option<String>,
+}
+
+impl StepOutcome {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// One line for logs and the CLI.
+    pub fn describe(&self) -> String {
+        let label = if self.name.is_empty() {
+            "edit"
+        } else {
+            &self.name
+        };
+        match &self.error {
+            None => format!(
+                "✅ Step {} ({}): {}",
+                self.index + 1,
+                label,
+                self.output.display()
+            ),
+            Some(e) => format!("❌ Step {} ({}): {}", self.index + 1, label, e),
+        }
+    }
+}
+
+/// Whether `text` might hold more than one ask, so single edits skip the
+/// decomposition prompt entirely.
+pub fn looks_compound(text: &str) -> bool {
+    let lower = format!(" {} ", text.to_lowercase());
+    STEP_SEPARATORS.iter().any(|sep| lower.contains(sep))
+        || lower.trim_start().starts_with("first ")
+}
+
+/// Split `text` into steps without the LLM. Separator phrases mark step
+/// boundaries; derived cuts (highlights, trailer, ...) start from the most
+/// recent cleanup step, and a bare request for the cleaned/full version is
+/// answered by that cleanup step's own output.
+pub fn split_heuristic(text: &str) -> Vec<EditStep> {
+    let mut parts = vec![text.trim().to_string()];
+    for sep in STEP_SEPARATORS {
+        parts = parts
+            .iter()
+            .flat_map(|p| split_case_insensitive(p, sep))
+            .collect();
+    }
+
+    let mut steps: Vec<EditStep> = Vec::new();
+    let mut last_cleanup: Option<usize> = None;
+    for part in parts {
+        let part = strip_lead_in(&part);
+        if part.is_empty() {
+            continue;
+        }
+        let lower = part.to_lowercase();
+        let derived = DERIVED_WORDS
+            .iter()
+            .find(|(w, _)| lower.contains(w))
+            .map(|(_, n)| *n);
+        let cleanup = CLEANUP_WORDS.iter().any(|w| lower.contains(w));
+
+        if derived.is_none() && is_version_request(&lower) && last_cleanup.is_some() {
+            // "give me the full cleaned version": that file already exists
+            continue;
+        }
+
+        let index = steps.len();
+        let (name, input_from) = match derived {
+            Some(name) => (name.to_string(), last_cleanup),
+            None if cleanup => ("cleaned".to_string(), None),
+            None => (format!("step{}", index + 1), None),
+        };
+        if cleanup && derived.is_none() {
+            last_cleanup = Some(index);
+        }
+        steps.push(EditStep {
+            intent: part,
+            name,
+            input_from,
+        });
+    }
+
+    match steps.len() {
+        0 | 1 => vec![EditStep::single(text)],
+        _ => {
+            dedupe_names(&mut steps);
+            steps.truncate(MAX_STEPS);
+            steps
+        }
+    }
+}
+
+fn split_case_insensitive(text: &str, sep: &str) -> Vec<String> {
+    // Separators are ASCII, so byte offsets in the lowercase copy line up
+    let lower = text.to_ascii_lowercase();
+    let mut out = Vec::new();
+    let mut start = 0;
+    while let Some(pos) = lower[start..].find(sep) {
+        out.push(text[start..start + pos].to_string());
+        start += pos + sep.len();
+    }
+    out.push(text[start..].to_string());
+    out
+}
+
+fn strip_lead_in(part: &str) -> String {
+    let mut s = part
+        .trim()
+        .trim_matches(|c: char| c == ',' || c == '.' || c == ';');
+    for lead in ["first,", "first ", "finally,", "finally ", "also ", "and "] {
+        if s.get(..lead.len())
+            .is_some_and(|head| head.eq_ignore_ascii_case(lead))
+        {
+            s = s[lead.len()..].trim_start();
+        }
+    }
+    s.trim().to_string()
+}
+
+/// "the full cleaned version", "keep a clean copy": a request for an output,
+/// not a new edit.
+fn is_version_request(lower: &str) -> bool {
+    (lower.contains("version") || lower.contains("copy"))
+        && (lower.contains("full") || lower.contains("clean"))
+        && !lower.chars().any(|c| c.is_ascii_digit())
+}
+
+fn dedupe_names(steps: &mut [EditStep]) {
+    for i in 0..steps.len() {
+        if steps[..i].iter().any(|s| s.name == steps[i].name) {
+            steps[i].name = format!("{}{}", steps[i].name, i + 1);
+        }
+    }
+}
+
+/// Steps must be non-empty, at most `MAX_STEPS`, and only feed from earlier
+/// steps.
+pub fn validate_steps(steps: &[EditStep]) -> Result<(), String> {
+    if steps.is_empty() {
+        return Err("at least one step is required".to_string());
+    }
+    if steps.len() > MAX_STEPS {
+        return Err(format!(
+            "{} steps is more than the {} allowed",
+            steps.len(),
+            MAX_STEPS
+        ));
+    }
+    for (i, step) in steps.iter().enumerate() {
+        if step.intent.trim().is_empty() {
+            return Err(format!("step {} has an empty intent", i));
+        }
+        if steps.len() > 1 && step.name.trim().is_empty() {
+            return Err(format!("step {} needs a name", i));
+        }
+        if let Some(from) = step.input_from {
+            if from >= i {
+                return Err(format!(
+                    "step {} takes its input from step {}, which does not run before it",
+                    i, from
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct StepList {
+    steps: Vec<EditStep>,
+}
+
+/// Ask `agent` to split `text` into ordered steps, falling back to
+/// `split_heuristic` when the model is unavailable or its reply stays
+/// invalid. Requests without a step separator are returned whole.
+pub async fn decompose(agent: &SynoidAgent, text: &str) -> Vec<EditStep> {
+    if !looks_compound(text) {
+        return vec![EditStep::single(text)];
+    }
+
+    let prompt = format!(
+        r#"You split video editing requests into ordered steps. Reply with JSON only:
+{{"steps": [{{"intent": string, "name": string, "input_from": null or integer}}]}}
+- "intent": that step's instruction on its own, in the user's words.
+- "name": one lowercase word for the output file, e.g. "cleaned", "highlights".
+- "input_from": index of an earlier step whose output this step edits (e.g. highlights cut from the cleaned version), or null to edit the original video.
+- When the user only asks to also receive an earlier step's result ("give me the full cleaned version too"), do not add a step for it.
+- At most {} steps. A request that is really one edit is one step.
+
+User Request: "{}"
+"#,
+        MAX_STEPS, text
+    );
+
+    match agent.fast_reason(&prompt).await {
+        Ok(response) => {
+            let parsed = crate::agent::llm_json::parse_with_repair::<StepList, _, _, _>(
+                "edit_steps",
+                &prompt,
+                &response,
+                |list| validate_steps(&list.steps),
+                |repair| async move { agent.fast_reason(&repair).await },
+            )
+            .await;
+            match parsed {
+                Ok(list) if list.steps.len() == 1 => return vec![EditStep::single(text)],
+                Ok(mut list) => {
+                    dedupe_names(&mut list.steps);
+                    return list.steps;
+                }
+                Err(e) => tracing::warn!(
+                    "[SMART] Step split unusable ({}), falling back to heuristic splitting",
+                    e
+                ),
+            }
+        }
+        Err(e) => tracing::warn!(
+            "[SMART] Step split request failed: {}, falling back to heuristic splitting",
+            e
+        ),
+    }
+    split_heuristic(text)
+}
+
+/// Output file of step `index`: `output` itself for a single step, else
+/// `<stem>_<name>.<ext>` beside it.
+pub fn step_output(output: &Path, steps: &[EditStep], index: usize) -> PathBuf {
+    if steps.len() <= 1 {
+        return output.to_path_buf();
+    }
+    let stem = output
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "output".to_string());
+    let ext = output
+        .extension()
+        .map(|e| e.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "mp4".to_string());
+    let name: String = steps[index]
+        .name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    output.with_file_name(format!("{}_{}.{}", stem, name, ext))
+}
+
+/// Run `steps` in order with `run(index, input, step, output)`. Each step
+/// reads the source or the output of the step it names; a step whose input
+/// step failed is skipped, the rest still run.
+pub async fn run_steps<F, Fut>(
+    source: &Path,
+    steps: &[EditStep],
+    output: &Path,
+    mut run: F,
+) -> Vec<StepOutcome>
+where
+    F: FnMut(usize, PathBuf, EditStep, PathBuf) -> Fut,
+    Fut: Future<Output = Result<(), String>>,
+{
+    let mut outcomes: Vec<StepOutcome> = Vec::with_capacity(steps.len());
+    for (index, step) in steps.iter().enumerate() {
+        let step_out = step_output(output, steps, index);
+        let (input, blocked) = match step.input_from.and_then(|from| outcomes.get(from)) {
+            Some(prev) if prev.succeeded() => (prev.output.clone(), None),
+            Some(prev) => (
+                prev.output.clone(),
+                Some(format!(
+                    "skipped: step {} ({}) failed",
+                    prev.index + 1,
+                    prev.name
+                )),
+            ),
+            None => (source.to_path_buf(), None),
+        };
+        let error = match blocked {
+            Some(reason) => Some(reason),
+            None => run(index, input.clone(), step.clone(), step_out.clone())
+                .await
+                .err(),
+        };
+        outcomes.push(StepOutcome {
+            index,
+            name: step.name.clone(),
+            intent: step.intent.clone(),
+            input,
+            output: step_out,
+            error,
+        });
+    }
+    outcomes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    const COMPOUND: &str = "First remove silence, then make a 60-second highlights version, and also give me the full cleaned version";
+
+    #[test]
+    fn heuristic_split_chains_highlights_from_the_cleaned_cut() {
+        let steps = split_heuristic(COMPOUND);
+        assert_eq!(steps.len(), 2, "{:?}", steps);
+        assert_eq!(steps[0].intent, "remove silence");
+        assert_eq!(steps[0].name, "cleaned");
+        assert_eq!(steps[0].input_from, None);
+        assert_eq!(steps[1].intent, "make a 60-second highlights version");
+        assert_eq!(steps[1].name, "highlights");
+        assert_eq!(steps[1].input_from, Some(0));
+        assert!(validate_steps(&steps).is_ok());
+
+        // Ordinary requests stay whole
+        let single = "cut the silence and the ums, make it punchy";
+        assert!(!looks_compound(single));
+        assert_eq!(split_heuristic(single), vec![EditStep::single(single)]);
+    }
+
+    #[test]
+    fn validate_rejects_forward_references() {
+        let steps = vec![
+            EditStep {
+                intent: "a".into(),
+                name: "a".into(),
+                input_from: Some(1),
+            },
+            EditStep {
+                intent: "b".into(),
+                name: "b".into(),
+                input_from: None,
+            },
+        ];
+        assert!(validate_steps(&steps).is_err());
+        assert!(validate_steps(&[]).is_err());
+    }
+
+    #[tokio::test]
+    async fn failed_step_keeps_finished_outputs_and_skips_its_dependents() {
+        let steps = vec![
+            EditStep {
+                intent: "remove silence".into(),
+                name: "cleaned".into(),
+                input_from: None,
+            },
+            EditStep {
+                intent: "color grade".into(),
+                name: "graded".into(),
+                input_from: None,
+            },
+            EditStep {
+                intent: "highlights".into(),
+                name: "highlights".into(),
+                input_from: Some(1),
+            },
+            EditStep {
+                intent: "trailer".into(),
+                name: "trailer".into(),
+                input_from: Some(0),
+            },
+        ];
+        let inputs = Mutex::new(Vec::new());
+        let outcomes = run_steps(
+            Path::new("/videos/raw.mp4"),
+            &steps,
+            Path::new("/out/raw_edited.mp4"),
+            |index, input, _, _| {
+                inputs.lock().unwrap().push((index, input));
+                async move {
+                    if index == 1 {
+                        Err("render failed".to_string())
+                    } else {
+                        Ok(())
+                    }
+                }
+            },
+        )
+        .await;
+
+        assert_eq!(
+            outcomes[0].output,
+            PathBuf::from("/out/raw_edited_cleaned.mp4")
+        );
+        assert!(outcomes[0].succeeded());
+        assert_eq!(outcomes[1].error.as_deref(), Some("render failed"));
+        // Step 3 builds on the failed step, so it never ran
+        assert!(outcomes[2].error.as_deref().unwrap().starts_with("skipped"));
+        // Step 4 builds on the cleaned output and still runs
+        assert!(outcomes[3].succeeded());
+        assert_eq!(
+            *inputs.lock().unwrap(),
+            vec![
+                (0, PathBuf::from("/videos/raw.mp4")),
+                (1, PathBuf::from("/videos/raw.mp4")),
+                (3, PathBuf::from("/out/raw_edited_cleaned.mp4")),
+            ]
+        );
+    }
+}
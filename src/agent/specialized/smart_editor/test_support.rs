@@ -0,0 +1,56 @@
+// SYNOID Smart Editor - Test Support
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Deterministic random timelines shared by the interval-index property
+// tests, the scoring equivalence tests and the scene scoring benchmark.
+
+use super::Scene;
+use crate::agent::tools::transcription::TranscriptSegment;
+
+/// Small deterministic generator so the property tests need no extra crates.
+pub struct XorShift(pub u64);
+
+impl XorShift {
+    pub fn next_f64(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Contiguous scenes with random scores and sorted, non-overlapping
+/// transcript segments.
+pub fn synthetic_timeline(seed: u64, scene_count: usize) -> (Vec<Scene>, Vec<TranscriptSegment>) {
+    let mut rng = XorShift(seed);
+
+    let mut scenes = Vec::with_capacity(scene_count);
+    let mut t = 0.0;
+    for _ in 0..scene_count {
+        let d = 0.5 + rng.next_f64() * 8.0;
+        scenes.push(Scene {
+            start_time: t,
+            end_time: t + d,
+            duration: d,
+            score: rng.next_f64(),
+            vision_tags: Vec::new(),
+            breakdown: None,
+            audio_mix: None,
+        });
+        t += d;
+    }
+
+    let mut transcript = Vec::new();
+    let mut s = rng.next_f64() * 2.0;
+    while s < t {
+        let d = 0.2 + rng.next_f64() * 6.0;
+        transcript.push(TranscriptSegment {
+            start: s,
+            end: s + d,
+            text: format!(" line {} ", transcript.len()),
+            words: Vec::new(),
+        });
+        s += d + rng.next_f64() * 3.0;
+    }
+    (scenes, transcript)
+}
@@ -0,0 +1,160 @@
+// SYNOID Smart Editor - Scoring Statistics
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// `synoid-core tune` reads the score breakdowns recorded in an edit plan and
+// reports, per scoring component, how often it fired, how much it moved
+// scores and how often it flipped a keep/cut decision: whether the scene
+// would land on the other side of the keep threshold without that
+// component's contribution. Components that never flip anything are the
+// first candidates when retuning `EditingStrategy`.
+
+use super::edit_plan::EditPlan;
+use super::explain::ScoringReport;
+use super::scene_ops::ScoreComponent;
+use serde::Serialize;
+use std::path::Path;
+
+/// How one scoring component behaved across every scored scene.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ComponentStats {
+    pub component: ScoreComponent,
+    /// Scenes the component moved at all.
+    pub fired: usize,
+    pub mean_delta: f64,
+    /// Scenes kept only because of this component.
+    pub flipped_to_keep: usize,
+    /// Scenes cut only because of this component.
+    pub flipped_to_cut: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TuningStats {
+    /// Scenes judged on their score; keyword-filtered scenes are skipped.
+    pub scenes: usize,
+    pub kept: usize,
+    pub keep_threshold: f64,
+    pub components: Vec<ComponentStats>,
+}
+
+impl TuningStats {
+    pub fn from_scoring(scoring: &ScoringReport) -> Self {
+        let threshold = scoring.keep_threshold;
+        let judged: Vec<_> = scoring.scenes.iter().filter(|s| s.dropped.is_none()).collect();
+        let components = ScoreComponent::ALL
+            .iter()
+            .map(|&component| {
+                let mut stats = ComponentStats {
+                    component,
+                    fired: 0,
+                    mean_delta: 0.0,
+                    flipped_to_keep: 0,
+                    flipped_to_cut: 0,
+                };
+                let mut total = 0.0;
+                for scene in &judged {
+                    let delta = scene.breakdown.component(component);
+                    if delta == 0.0 {
+                        continue;
+                    }
+                    stats.fired += 1;
+                    total += delta;
+                    let kept = scene.breakdown.score > threshold;
+                    let kept_without = (scene.breakdown.score - delta).clamp(0.0, 1.0) > threshold;
+                    match (kept, kept_without) {
+                        (true, false) => stats.flipped_to_keep += 1,
+                        (false, true) => stats.flipped_to_cut += 1,
+                        _ => {}
+                    }
+                }
+                if stats.fired > 0 {
+                    stats.mean_delta = total / stats.fired as f64;
+                }
+                stats
+            })
+            .collect();
+        Self {
+            scenes: judged.len(),
+            kept: judged.iter().filter(|s| s.breakdown.score > threshold).count(),
+            keep_threshold: threshold,
+            components,
+        }
+    }
+
+    /// Read the scoring from an edit plan (`<output>.plan.json`) or from a
+    /// bare scoring report, e.g. the editor's `render/scenes` response.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let data = std::fs::read_to_string(path)?;
+        let scoring = match serde_json::from_str::<EditPlan>(&data) {
+            Ok(plan) => plan
+                .scoring
+                .ok_or_else(|| format!("{:?} has no score breakdowns (reused draft or seeded cuts?)", path))?,
+            Err(_) => serde_json::from_str::<ScoringReport>(&data)?,
+        };
+        Ok(Self::from_scoring(&scoring))
+    }
+
+    pub fn to_table(&self) -> String {
+        let mut out = format!(
+            "{} scene(s) scored, {} kept (threshold {:.2})\n\n{:<16} {:>6} {:>8} {:>8} {:>8}\n",
+            self.scenes, self.kept, self.keep_threshold, "component", "fired", "mean", "→keep", "→cut"
+        );
+        for c in &self.components {
+            out.push_str(&format!(
+                "{:<16} {:>6} {:>+8.3} {:>8} {:>8}\n",
+                c.component.name(),
+                c.fired,
+                c.mean_delta,
+                c.flipped_to_keep,
+                c.flipped_to_cut
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::explain::SceneScore;
+    use super::super::scene_ops::ScoreBreakdown;
+
+    fn scene(start: f64, adds: &[(ScoreComponent, f64)]) -> SceneScore {
+        let mut breakdown = ScoreBreakdown::new(0.35);
+        for &(component, delta) in adds {
+            breakdown.add(component, "test", delta);
+        }
+        SceneScore {
+            start,
+            end: start + 5.0,
+            breakdown,
+            audio_mix: None,
+            dropped: None,
+        }
+    }
+
+    #[test]
+    fn counts_components_that_flip_the_decision() {
+        use ScoreComponent::*;
+        let scoring = ScoringReport {
+            scenes: vec![
+                // Speech alone lifts it over 0.4
+                scene(0.0, &[(Speech, 0.3)]),
+                // Kept either way
+                scene(5.0, &[(Speech, 0.3), (Fun, 0.25)]),
+                // Silence alone sinks a scene that would have passed
+                scene(10.0, &[(Action, 0.15), (Silence, -0.3)]),
+            ],
+            keep_threshold: 0.4,
+            source_duration: 15.0,
+        };
+        let stats = TuningStats::from_scoring(&scoring);
+        let get = |c: ScoreComponent| stats.components.iter().find(|s| s.component == c).unwrap();
+
+        assert_eq!((stats.scenes, stats.kept), (3, 2));
+        assert_eq!((get(Speech).fired, get(Speech).flipped_to_keep), (2, 1));
+        assert_eq!(get(Fun).flipped_to_keep, 0);
+        assert_eq!(get(Silence).flipped_to_cut, 1);
+        assert_eq!(get(Action).flipped_to_cut, 0);
+        assert_eq!(get(Pattern).fired, 0);
+    }
+}
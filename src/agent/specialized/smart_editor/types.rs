@@ -1,6 +1,15 @@
+use super::exclusions::{parse_exclude_phrases, parse_exclude_ranges, Exclusion};
+use super::explain::ScoringReport;
+use super::scene_ops::ScoreBreakdown;
+use super::intent_language::IntentLanguage;
+use super::keywords::{deserialize_keywords, Keyword, KeywordMode};
+use crate::agent::tools::audio_classify::{self, AudioClass, AudioMix};
+use crate::agent::tools::production_tools::SpeechDucking;
+use crate::agent::video_processing::effects::AudioReactive;
 use serde::{Deserialize, Serialize};
 use regex::Captures;
 use std::fs;
+use std::path::PathBuf;
 use tracing::{info, warn};
 // SYNOID Smart Editor Refactoring
 
@@ -58,7 +67,7 @@ impl Default for EditingStrategy {
 impl EditingStrategy {
     pub fn load() -> Self {
         // First try the learned, cortex-cached strategy (compounding learning)
-        let suffix = std::env::var("SYNOID_INSTANCE_ID").unwrap_or_default();
+        let suffix = crate::config::current().instance_id.unwrap_or_default();
         let cache_dir = format!("cortex_cache{}", suffix);
         let cached_path = format!("{}/editing_strategy.json", cache_dir);
         if let Ok(content) = fs::read_to_string(&cached_path) {
@@ -81,7 +90,7 @@ impl EditingStrategy {
     }
 
     pub fn save_to_cortex(&self) {
-        let suffix = std::env::var("SYNOID_INSTANCE_ID").unwrap_or_default();
+        let suffix = crate::config::current().instance_id.unwrap_or_default();
         let cache_dir = format!("cortex_cache{}", suffix);
         let _ = fs::create_dir_all(&cache_dir);
         let path = format!("{}/editing_strategy.json", cache_dir);
@@ -103,7 +112,10 @@ pub struct EditIntent {
     pub keep_speech: bool,
     pub ruthless: bool,
     pub density: EditDensity,
-    pub custom_keywords: Vec<String>,
+    /// Weighted Boost/Require/Exclude terms. Older intents with a flat
+    /// `custom_keywords` string list still load as plain boosts.
+    #[serde(default, alias = "custom_keywords", deserialize_with = "deserialize_keywords")]
+    pub keywords: Vec<Keyword>,
     pub target_duration: Option<(f64, f64)>,
     #[serde(default = "default_censor_profanity")]
     pub censor_profanity: bool,
@@ -121,6 +133,42 @@ pub struct EditIntent {
     /// The specific Remotion template to use (e.g., "Intro", "StatsCard")
     #[serde(default)]
     pub remotion_template: Option<String>,
+    /// Seconds of air kept before the first word and after the last word of
+    /// the edit. "Start right on the action" drops this to near zero.
+    #[serde(default = "default_edge_padding_secs")]
+    pub edge_padding_secs: f64,
+    /// Zoom pulses on bass hits, applied in the final render pass.
+    #[serde(default)]
+    pub audio_reactive: Option<AudioReactive>,
+    /// Dip the music track under speech ("lower the music when I talk").
+    #[serde(default)]
+    pub duck_music: Option<SpeechDucking>,
+    /// Source spans to cut whatever their score ("cut 12:30 to 14:00").
+    /// Negative times count back from the end: `(-30.0, 0.0)` is the last
+    /// 30 seconds.
+    #[serde(default)]
+    pub exclude_ranges: Vec<(f64, f64)>,
+    /// Spoken lines whose transcript segments are cut ("the part where I
+    /// say goodbye" → "goodbye").
+    #[serde(default)]
+    pub exclude_phrases: Vec<String>,
+    /// Video put before the edit; `None` uses the configured `intro`.
+    #[serde(default)]
+    pub intro: Option<PathBuf>,
+    /// Video or image card put after the edit; `None` uses the configured
+    /// `outro`.
+    #[serde(default)]
+    pub outro: Option<PathBuf>,
+    /// Seconds over which the intro's sound fades into the first scene's;
+    /// `None` uses the configured `intro_crossfade`.
+    #[serde(default)]
+    pub intro_crossfade: Option<f64>,
+    /// Audio classes to favour ("keep the music sections").
+    #[serde(default)]
+    pub keep_audio: Vec<AudioClass>,
+    /// Audio classes to cut ("cut everything that isn't gameplay audio").
+    #[serde(default)]
+    pub cut_audio: Vec<AudioClass>,
 }
 
 fn default_enable_subtitles() -> bool {
@@ -133,6 +181,9 @@ fn default_show_cut_markers() -> bool {
 fn default_censor_profanity() -> bool {
     true
 }
+fn default_edge_padding_secs() -> f64 {
+    0.3
+}
 
 impl EditIntent {
     /// Parse natural language intent into structured intent using LLM.
@@ -140,17 +191,20 @@ impl EditIntent {
     /// Groq/Google are used only as cloud fallbacks if their keys are set AND
     /// Ollama is unreachable.
     pub async fn from_llm(text: &str) -> Self {
+        // Passing "default" makes the agent pick up SYNOID_MODEL from the
+        // environment (gemma4:26b by default) instead of pinning a tag.
+        Self::from_llm_with_model(text, "default").await
+    }
+
+    /// `from_llm` with the intent parsed by `model` rather than the default.
+    pub async fn from_llm_with_model(text: &str, model: &str) -> Self {
         use crate::agent::ai_systems::gpt_oss_bridge::SynoidAgent;
-        // Use the multi-provider bridge. Passing "default" forces the agent to
-        // pick up SYNOID_MODEL from the environment (gemma4:26b by default)
-        // instead of pinning a cloud-specific tag.
-        let api_url = std::env::var("SYNOID_API_URL")
-            .or_else(|_| std::env::var("OLLAMA_API_URL"))
-            .unwrap_or_else(|_| "http://localhost:11434".to_string());
-        let agent = SynoidAgent::new(&api_url, "default");
+        let api_url = crate::config::current().api_url;
+        let agent = SynoidAgent::new(&api_url, model);
 
         let prompt = format!(
             r#"You are a video editing AI assistant. Convert the user's natural language request into a JSON configuration for the EditIntent struct.
+The request may be written in any language; the JSON keys and enum values stay as shown.
 The JSON must strictly follow this structure and include nothing else:
 {{
     "remove_boring": bool,
@@ -159,13 +213,20 @@ The JSON must strictly follow this structure and include nothing else:
     "keep_speech": bool,
     "ruthless": bool,
     "density": "Highlights" | "Balanced" | "Full",
-    "custom_keywords": [string],
+    "keywords": [{{"term": string, "weight": float (1.0 normal, up to 3.0 for "especially"), "mode": "Boost" | "Require" | "Exclude"}}] (Require = only keep scenes mentioning it, Exclude = always cut scenes mentioning it),
     "target_duration": null or [min_secs_float, max_secs_float],
     "censor_profanity": bool,
     "profanity_replacement": null or string (e.g. "boing.wav"),
     "show_cut_markers": bool,
     "use_remotion": bool,
-    "remotion_template": null or "Intro" | "StatsCard" | "StepByStep" | "TitleCard"
+    "remotion_template": null or "Intro" | "StatsCard" | "StepByStep" | "TitleCard",
+    "edge_padding_secs": float (seconds of air before the first/after the last word; 0.3 normally, 0.05 for "start right on the action"),
+    "audio_reactive": null or {{"intensity": float (0.03 subtle to 0.3 max zoom), "attack_secs": float, "decay_secs": float}} (zoom pulses on the beat, e.g. "make it pump with the music"),
+    "duck_music": null or {{"depth_db": float (6 subtle to 20 strong), "attack_secs": float, "release_secs": float}} (lower background music under speech, e.g. "lower the music when I talk"),
+    "exclude_ranges": [[start_secs_float, end_secs_float]] (explicit spans to cut, e.g. "cut 12:30 to 14:00" → [[750.0, 840.0]]; "the last 30 seconds" → [[-30.0, 0.0]]),
+    "exclude_phrases": [string] (spoken lines marking parts to cut, e.g. "the part where I say goodbye" → ["goodbye"]),
+    "keep_audio": ["speech" | "music" | "sfx" | "silence"] (sections to favour by what is heard, e.g. "keep the music sections" → ["music"]),
+    "cut_audio": ["speech" | "music" | "sfx" | "silence"] (sections to cut by what is heard, e.g. "cut everything that isn't gameplay audio" → ["speech"] with keep_audio ["music", "sfx"])
 }}
 
 User Request: "{}"
@@ -175,27 +236,26 @@ User Request: "{}"
 
         match agent.fast_reason(&prompt).await {
             Ok(response) => {
-                // Extract the JSON object from the LLM response.
-                // Llama3 often prefixes its answer with prose like "Here is the JSON configuration:"
-                // so we search for the first {...} block instead of relying on the full string being JSON.
-                let extracted = if let Some(mat) = regex::Regex::new(r"(?s)\{.*\}")
-                    .ok()
-                    .and_then(|re| re.find(response.trim()))
-                {
-                    mat.as_str()
-                } else {
-                    response.trim()
-                };
-                let clean_json = extracted
-                    .trim_start_matches("```json")
-                    .trim_start_matches("```")
-                    .trim_end_matches("```")
-                    .trim();
-                if let Ok(intent) = serde_json::from_str::<EditIntent>(clean_json) {
-                    tracing::info!("[SMART] Successfully parsed EditIntent from LLM");
-                    return intent;
-                } else {
-                    tracing::warn!("[SMART] LLM intent JSON deserialization failed, falling back to heuristic parsing. Raw: {}", clean_json);
+                let parsed = crate::agent::llm_json::parse_with_repair::<EditIntent, _, _, _>(
+                    "edit_intent",
+                    &prompt,
+                    &response,
+                    |_| Ok(()),
+                    |repair| {
+                        let agent = &agent;
+                        async move { agent.fast_reason(&repair).await }
+                    },
+                )
+                .await;
+                match parsed {
+                    Ok(intent) => {
+                        tracing::info!("[SMART] Successfully parsed EditIntent from LLM");
+                        return intent.with_exclusions_from(text);
+                    }
+                    Err(e) => tracing::warn!(
+                        "[SMART] LLM intent JSON unusable ({}), falling back to heuristic parsing",
+                        e
+                    ),
                 }
             }
             Err(e) => tracing::warn!(
@@ -207,9 +267,95 @@ User Request: "{}"
         Self::from_text(text)
     }
 
+    /// Add the explicit ranges and spoken references found in `text` that the
+    /// LLM missed; they are cheap to find exactly and costly to drop.
+    pub fn with_exclusions_from(mut self, text: &str) -> Self {
+        let lower = text.to_lowercase();
+        let (ranges, _) = parse_exclude_ranges(&lower);
+        for range in ranges {
+            if !self.exclude_ranges.contains(&range) {
+                self.exclude_ranges.push(range);
+            }
+        }
+        for phrase in parse_exclude_phrases(&lower) {
+            if !self.exclude_phrases.contains(&phrase) {
+                self.exclude_phrases.push(phrase);
+            }
+        }
+        self
+    }
+
+    /// Reject intents that cannot be rendered or that ask for two opposite
+    /// things, for callers that build an intent without the LLM.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some((min, max)) = self.target_duration {
+            if !(min.is_finite() && max.is_finite() && min >= 0.0 && max > 0.0) {
+                return Err(format!("target_duration [{}, {}] must be positive seconds", min, max));
+            }
+            if min > max {
+                return Err(format!("target_duration minimum {}s is above its maximum {}s", min, max));
+            }
+        }
+        if !(self.edge_padding_secs.is_finite() && (0.0..=10.0).contains(&self.edge_padding_secs)) {
+            return Err(format!("edge_padding_secs {} must be between 0 and 10", self.edge_padding_secs));
+        }
+        if let Some(secs) = self.intro_crossfade {
+            if !(secs.is_finite() && secs >= 0.0) {
+                return Err(format!("intro_crossfade {} must be non-negative seconds", secs));
+            }
+        }
+        if self.ruthless && self.density == EditDensity::Full {
+            return Err("ruthless cutting contradicts density \"Full\"".to_string());
+        }
+        for k in &self.keywords {
+            if k.term.trim().is_empty() {
+                return Err("keywords must not be empty".to_string());
+            }
+            if !(k.weight.is_finite() && k.weight > 0.0) {
+                return Err(format!("keyword '{}' has an invalid weight {}", k.term, k.weight));
+            }
+            let opposite = self.keywords.iter().any(|other| {
+                other.term.eq_ignore_ascii_case(&k.term)
+                    && matches!(
+                        (k.mode, other.mode),
+                        (KeywordMode::Require, KeywordMode::Exclude)
+                    )
+            });
+            if opposite {
+                return Err(format!("keyword '{}' is both required and excluded", k.term));
+            }
+        }
+        for &(start, end) in &self.exclude_ranges {
+            let from_end = start < 0.0 && end <= 0.0;
+            if !(start.is_finite() && end.is_finite() && start < end && (start >= 0.0 || from_end)) {
+                return Err(format!("exclude range [{}, {}] is not a valid span", start, end));
+            }
+        }
+        if let Some(class) = self.keep_audio.iter().find(|c| self.cut_audio.contains(c)) {
+            return Err(format!("{} audio is both kept and cut", class.name()));
+        }
+        if let Some(duck) = &self.duck_music {
+            if !(duck.depth_db.is_finite() && duck.depth_db > 0.0) {
+                return Err(format!("duck_music depth_db {} must be above 0", duck.depth_db));
+            }
+        }
+        if let Some(pump) = &self.audio_reactive {
+            if !(pump.intensity.is_finite() && pump.intensity > 0.0) {
+                return Err(format!("audio_reactive intensity {} must be above 0", pump.intensity));
+            }
+        }
+        Ok(())
+    }
+
     /// Parse natural language intent into structured intent
     pub fn from_text(text: &str) -> Self {
         let lower = text.to_lowercase();
+        // Other languages are read through their keyword tables
+        let lower = IntentLanguage::resolve(&lower).gloss(&lower);
+        // Explicit cuts come out first so "skip the first 2 minutes" is not
+        // also read as a 2 minute target length.
+        let (exclude_ranges, rest) = parse_exclude_ranges(&lower);
+        let (keep_audio, cut_audio) = audio_classify::preferences_from_text(&lower);
 
         // Density detection
         let mut density = EditDensity::Balanced;
@@ -260,21 +406,22 @@ User Request: "{}"
                 || lower.contains("dead air")
                 || lower.contains("silent parts")
                 || lower.contains("viral clip"),
-            keep_speech: lower.contains("speech")
+            keep_speech: (lower.contains("speech")
                 || lower.contains("talking")
                 || lower.contains("dialogue")
                 || lower.contains("conversation")
                 || lower.contains("voice")
                 || lower.contains("transcript")
-                || lower.contains("engaging"),
+                || lower.contains("engaging"))
+                && !cut_audio.contains(&AudioClass::Speech),
             ruthless: lower.contains("ruthless")
                 || lower.contains("aggressive")
                 || lower.contains("fast-paced")
                 || lower.contains("no filler")
                 || lower.contains("remove all silence"),
             density,
-            custom_keywords: vec![],
-            target_duration: Self::parse_duration_range(&lower),
+            keywords: vec![],
+            target_duration: Self::parse_duration_range(&rest),
             censor_profanity: true, // Always-on: safety-first, never let slurs through
             profanity_replacement: None, // Use built-in 1kHz sine wave (broadcast standard)
             enable_subtitles: lower.contains("sub") || lower.contains("caption") || lower.contains("text"),
@@ -295,6 +442,47 @@ User Request: "{}"
             } else {
                 None
             },
+            edge_padding_secs: if lower.contains("right on the action")
+                || lower.contains("start immediately")
+                || lower.contains("no padding")
+            {
+                0.05
+            } else {
+                default_edge_padding_secs()
+            },
+            audio_reactive: if ["pump", "pulse", "with the music", "on the beat", "beat drop", "bass hit"]
+                .iter()
+                .any(|w| lower.contains(w))
+            {
+                Some(AudioReactive::default())
+            } else {
+                None
+            },
+            duck_music: if [
+                "duck the music",
+                "ducking",
+                "lower the music",
+                "music lower",
+                "music quieter",
+                "quieter music",
+                "turn down the music",
+                "music down when",
+                "music under",
+            ]
+            .iter()
+            .any(|w| lower.contains(w))
+            {
+                Some(SpeechDucking::default())
+            } else {
+                None
+            },
+            exclude_ranges,
+            exclude_phrases: parse_exclude_phrases(&lower),
+            intro: None,
+            outro: None,
+            intro_crossfade: None,
+            keep_audio,
+            cut_audio,
         }
     }
 
@@ -347,11 +535,33 @@ User Request: "{}"
 }
 
 /// Represents a detected scene in the video
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Scene {
     pub start_time: f64,
     pub end_time: f64,
     pub duration: f64,
     pub score: f64, // 0.0 = definitely remove, 1.0 = definitely keep
     pub vision_tags: Vec<String>,
+    /// How `score` was reached; set by `score_scenes`.
+    pub breakdown: Option<ScoreBreakdown>,
+    /// What the scene's audio is, once classified.
+    #[serde(default)]
+    pub audio_mix: Option<AudioMix>,
+}
+
+/// Outcome of scene analysis: what to render and what was cut.
+#[derive(Debug, Clone)]
+pub struct SceneSelection {
+    /// Physical segments to render, in source order.
+    pub kept: Vec<Scene>,
+    /// Source-time gaps between kept segments, for [CUT] markers.
+    pub cut_points: Vec<(f64, f64)>,
+    /// Scenes dropped by scoring and filtering.
+    pub removed: usize,
+    /// Spans cut by explicit ranges or transcript references.
+    pub exclusions: Vec<Exclusion>,
+    /// Scenes detected before any filtering.
+    pub scene_count: usize,
+    /// Per-scene score breakdowns; `None` when the cuts were reused.
+    pub scoring: Option<ScoringReport>,
 }
@@ -3,6 +3,7 @@
 // Copyright (c) 2026 xingxerx_The_Creator | SYNOID
 
 use anyhow::{Context, Result};
+use crate::agent::engines::proc::{self, ProcClass};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -34,7 +35,6 @@ impl SynoidFrame {
 
     /// Extract frame from video using FFmpeg
     pub async fn from_video(video_path: &Path, timestamp: f64) -> Result<Self> {
-        use tokio::process::Command;
 
         let temp_dir = std::env::temp_dir().join("synoid_frames");
         std::fs::create_dir_all(&temp_dir)?;
@@ -42,7 +42,7 @@ impl SynoidFrame {
         let frame_path = temp_dir.join(format!("frame_{:.3}.raw", timestamp));
 
         // Extract raw RGB frame at timestamp
-        let output = Command::new("ffmpeg")
+        let output = proc::ffmpeg(ProcClass::Thumbnail)
             .args([
                 "-ss",
                 &timestamp.to_string(),
@@ -65,7 +65,7 @@ impl SynoidFrame {
         }
 
         // Probe video dimensions
-        let probe_output = Command::new("ffprobe")
+        let probe_output = proc::ffprobe()
             .args([
                 "-v",
                 "error",
@@ -233,12 +233,11 @@ impl SynoidLink {
         request: &KernelRequest,
         progress_callback: Option<Arc<dyn Fn(f64) + Send + Sync>>,
     ) -> Result<()> {
-        use tokio::process::Command;
 
         info!("[SYNOID-LINK] Processing video: {:?}", input_path);
 
         // Get video duration and frame rate
-        let probe_output = Command::new("ffprobe")
+        let probe_output = proc::ffprobe()
             .args([
                 "-v",
                 "error",
@@ -267,7 +266,7 @@ impl SynoidLink {
         info!("[SYNOID-LINK] Using filter: {}", filter);
 
         // Execute with FFmpeg
-        let output = Command::new("ffmpeg")
+        let output = proc::ffmpeg(ProcClass::Render)
             .args([
                 "-y",
                 "-i",
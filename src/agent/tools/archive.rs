@@ -0,0 +1,275 @@
+// SYNOID Zip Archives
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Handoff bundles (`POST /sessions/:id/archive`, `project archive`) are
+// plain zip files, read and written with the `zip` crate. Entries are
+// stored, not deflated: the bulk is video that is already compressed.
+// `ZipWriter::new_stream` puts sizes and CRC after the data, so a
+// multi-gigabyte session never sits in memory and the output can be a
+// socket. Reading accepts deflated entries too, so an archive unpacked and
+// re-zipped with a desktop tool still imports.
+//
+// What this module adds is the checking: entry names that stay inside the
+// destination, and entries that cannot inflate past their declared size.
+
+use std::fs::File;
+use std::io::{self, Read, Seek, Write};
+use std::path::{Component, Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::CompressionMethod;
+
+pub use zip::write::StreamWriter;
+pub use zip::{ZipArchive, ZipWriter};
+
+/// Largest entry `read_entry` will load into memory.
+const MAX_IN_MEMORY: u64 = 64 * 1024 * 1024;
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+/// Start entry `name` (forward slashes, relative) of `size` bytes: stored,
+/// with ZIP64 records only when it outgrows the classic format.
+fn start_entry<W: Write + Seek>(zip: &mut ZipWriter<W>, name: &str, size: u64) -> io::Result<()> {
+    if entry_path(Path::new(""), name).is_none() {
+        return Err(invalid(format!("'{}' is not a relative entry name", name)));
+    }
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Stored)
+        .large_file(size >= 0xFFFF_FFFF);
+    Ok(zip.start_file(name, options)?)
+}
+
+pub fn add_bytes<W: Write + Seek>(
+    zip: &mut ZipWriter<W>,
+    name: &str,
+    data: &[u8],
+) -> io::Result<()> {
+    start_entry(zip, name, data.len() as u64)?;
+    zip.write_all(data)
+}
+
+pub fn add_file<W: Write + Seek>(
+    zip: &mut ZipWriter<W>,
+    name: &str,
+    path: &Path,
+) -> io::Result<()> {
+    let mut file = File::open(path)?;
+    let size = file.metadata()?.len();
+    start_entry(zip, name, size)?;
+    let copied = io::copy(&mut (&mut file).take(size), zip)?;
+    if copied != size {
+        return Err(invalid(format!(
+            "'{}' changed while being archived ({} of {} bytes)",
+            name, copied, size
+        )));
+    }
+    Ok(())
+}
+
+pub fn open(path: &Path) -> io::Result<ZipArchive<File>> {
+    Ok(ZipArchive::new(File::open(path)?)?)
+}
+
+/// Entry names in archive order.
+pub fn entry_names<R: Read + Seek>(zip: &ZipArchive<R>) -> Vec<String> {
+    zip.file_names().map(str::to_string).collect()
+}
+
+/// What unpacking every entry may write: `copy_entry` refuses data past an
+/// entry's declared size.
+pub fn unpacked_size<R: Read + Seek>(zip: &mut ZipArchive<R>) -> io::Result<u64> {
+    let mut total = 0u64;
+    for i in 0..zip.len() {
+        total = total.saturating_add(zip.by_index_raw(i)?.size());
+    }
+    Ok(total)
+}
+
+/// Copy entry `name` to `out`. Data that inflates past the entry's declared
+/// size is refused before it is written.
+pub fn copy_entry<R: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+    name: &str,
+    out: &mut impl Write,
+) -> io::Result<u64> {
+    let file = zip.by_name(name)?;
+    let size = file.size();
+    // One byte past the declared size tells a deflate bomb, and the read
+    // that comes back empty is the one that checks the CRC
+    let mut reader = file.take(size.saturating_add(1));
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut copied = 0u64;
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if copied + n as u64 > size {
+            return Err(invalid(format!(
+                "'{}' inflates past its declared {} bytes",
+                name, size
+            )));
+        }
+        out.write_all(&buf[..n])?;
+        copied += n as u64;
+    }
+    Ok(copied)
+}
+
+/// Extract entry `name` to the file `dest`, creating its directory.
+pub fn extract<R: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+    name: &str,
+    dest: &Path,
+) -> io::Result<u64> {
+    if let Some(dir) = dest.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = io::BufWriter::new(File::create(dest)?);
+    let copied = copy_entry(zip, name, &mut file).and_then(|n| file.flush().map(|_| n));
+    if copied.is_err() {
+        drop(file);
+        let _ = std::fs::remove_file(dest);
+    }
+    copied
+}
+
+/// The contents of entry `name`, for small metadata files.
+pub fn read_entry<R: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+    name: &str,
+) -> io::Result<Option<Vec<u8>>> {
+    let size = match zip.by_name(name) {
+        Ok(file) => file.size(),
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    if size > MAX_IN_MEMORY {
+        return Err(invalid(format!("'{}' is too large to load", name)));
+    }
+    let mut data = Vec::with_capacity(size as usize);
+    copy_entry(zip, name, &mut data)?;
+    Ok(Some(data))
+}
+
+/// `name` with everything but letters, digits, `.`, `-` and `_` replaced, for
+/// use as one component of an entry name.
+pub fn entry_name(name: &str) -> String {
+    let safe: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if safe.is_empty() || safe.chars().all(|c| c == '.') {
+        "_".to_string()
+    } else {
+        safe
+    }
+}
+
+/// Where entry `name` lands under `root`, or `None` when it would escape it
+/// (absolute paths, `..`, drive prefixes).
+pub fn entry_path(root: &Path, name: &str) -> Option<PathBuf> {
+    if name.is_empty() || name.contains('\\') || name.contains(':') {
+        return None;
+    }
+    let mut path = root.to_path_buf();
+    for component in Path::new(name).components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn written_archives_read_back_and_reject_escapes() {
+        let dir = std::env::temp_dir().join(format!("synoid_zip_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let video = dir.join("clip.mp4");
+        std::fs::write(&video, vec![7u8; 200_000]).unwrap();
+
+        let mut zip = ZipWriter::new_stream(Vec::new());
+        add_bytes(&mut zip, "session.json", br#"{"format": 1}"#).unwrap();
+        add_file(&mut zip, "assets/clip.mp4", &video).unwrap();
+        add_bytes(&mut zip, "notes/café.txt", b"").unwrap();
+        assert!(add_bytes(&mut zip, "../escape.txt", b"x").is_err());
+        let bytes = zip.finish().unwrap().into_inner();
+
+        let mut reader = ZipArchive::new(Cursor::new(bytes.clone())).unwrap();
+        assert_eq!(
+            entry_names(&reader),
+            vec!["session.json", "assets/clip.mp4", "notes/café.txt"]
+        );
+        assert_eq!(
+            read_entry(&mut reader, "session.json").unwrap().unwrap(),
+            br#"{"format": 1}"#
+        );
+        let out = dir.join("out").join("clip.mp4");
+        assert_eq!(
+            extract(&mut reader, "assets/clip.mp4", &out).unwrap(),
+            200_000
+        );
+        assert_eq!(std::fs::read(&out).unwrap(), vec![7u8; 200_000]);
+        assert!(read_entry(&mut reader, "missing").unwrap().is_none());
+        assert_eq!(unpacked_size(&mut reader).unwrap(), 200_000 + 13);
+
+        // A flipped byte in the stored data fails the checksum
+        let mut corrupt = bytes;
+        let data_at = corrupt.windows(4).position(|w| w == [7, 7, 7, 7]).unwrap();
+        corrupt[data_at] = 8;
+        let mut reader = ZipArchive::new(Cursor::new(corrupt)).unwrap();
+        assert!(copy_entry(&mut reader, "assets/clip.mp4", &mut io::sink()).is_err());
+
+        assert_eq!(entry_name("my clip (final).mp4"), "my_clip__final_.mp4");
+        assert_eq!(entry_name(".."), "_");
+
+        let root = Path::new("/tmp/session");
+        assert_eq!(
+            entry_path(root, "assets/a.mp4"),
+            Some(root.join("assets/a.mp4"))
+        );
+        for bad in ["../x", "/etc/passwd", "a/../../x", "C:\\x", "a\\..\\x", ""] {
+            assert_eq!(entry_path(root, bad), None, "{}", bad);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn entries_that_inflate_past_their_size_are_refused() {
+        let size: u32 = 10 * 1024 * 1024;
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        zip.start_file("bomb.bin", options).unwrap();
+        zip.write_all(&vec![0u8; size as usize]).unwrap();
+        let mut bytes = zip.finish().unwrap().into_inner();
+        // Claim 1 KiB wherever the headers record the real size
+        let real = size.to_le_bytes();
+        let mut at = 0;
+        while let Some(i) = bytes[at..].windows(4).position(|w| w == real) {
+            bytes[at + i..at + i + 4].copy_from_slice(&1024u32.to_le_bytes());
+            at += i + 4;
+        }
+
+        let mut reader = ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut out = Vec::new();
+        let err = copy_entry(&mut reader, "bomb.bin", &mut out).unwrap_err();
+        assert!(err.to_string().contains("inflates past"), "{}", err);
+        assert!(out.len() <= 1024);
+    }
+}
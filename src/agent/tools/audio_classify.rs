@@ -0,0 +1,472 @@
+// SYNOID Audio Classification - Speech, music, sound effects or silence
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Scoring knew how loud a scene was and whether the transcript had words in
+// it, but not what the sound was, so "keep the music sections" had nothing
+// to match against. `classify` decodes the first audio stream to 16 kHz mono
+// and labels every second from a few cheap features of its 20 ms frames:
+// overall level (silence), how often the level dips between syllables
+// (speech), and the energy-weighted zero-crossing rate, a stand-in for how
+// bright or noisy the spectrum is (sound effects). Anything sustained and
+// tonal is music. `label_scenes` folds the windows into each scene's
+// `AudioMix`; `scan_cache::audio_classes` stores the windows per input.
+
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::production_tools::safe_arg_path;
+use crate::agent::specialized::smart_editor::Scene;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Stdio;
+use std::str::FromStr;
+use tokio::io::AsyncReadExt;
+use tracing::info;
+
+type ClassifyResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+pub const SAMPLE_RATE: u32 = 16_000;
+/// Length of one labelled window.
+pub const WINDOW_SECS: f64 = 1.0;
+const FRAME_SECS: f64 = 0.02;
+/// A window quieter than this overall is silence.
+const SILENCE_DBFS: f64 = -45.0;
+/// A frame below this share of the window's mean frame level is a dip.
+const DIP_LEVEL: f64 = 0.25;
+/// Share of dipping frames above which a window reads as speech.
+const SPEECH_DIP_RATIO: f64 = 0.15;
+/// Energy-weighted zero crossings per sample above which a window is noisy
+/// enough to be a sound effect rather than voice or music.
+const SFX_ZCR: f64 = 0.15;
+/// A trailing partial window shorter than this is not labelled.
+const MIN_TAIL_SECS: f64 = 0.25;
+
+/// What a stretch of audio mostly is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioClass {
+    Speech,
+    Music,
+    #[serde(alias = "noise")]
+    Sfx,
+    Silence,
+}
+
+impl AudioClass {
+    pub const ALL: [AudioClass; 4] = [
+        AudioClass::Speech,
+        AudioClass::Music,
+        AudioClass::Sfx,
+        AudioClass::Silence,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AudioClass::Speech => "speech",
+            AudioClass::Music => "music",
+            AudioClass::Sfx => "sfx",
+            AudioClass::Silence => "silence",
+        }
+    }
+}
+
+impl FromStr for AudioClass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "speech" => Ok(AudioClass::Speech),
+            "music" => Ok(AudioClass::Music),
+            "sfx" | "noise" => Ok(AudioClass::Sfx),
+            "silence" => Ok(AudioClass::Silence),
+            other => Err(format!(
+                "unknown audio class '{}' (speech, music, sfx or silence)",
+                other
+            )),
+        }
+    }
+}
+
+/// One labelled window of source audio.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AudioWindow {
+    pub start: f64,
+    pub end: f64,
+    pub class: AudioClass,
+}
+
+/// Share of a span's audio in each class; the four add up to 1.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct AudioMix {
+    pub speech: f64,
+    pub music: f64,
+    pub sfx: f64,
+    pub silence: f64,
+}
+
+impl AudioMix {
+    pub fn share(&self, class: AudioClass) -> f64 {
+        match class {
+            AudioClass::Speech => self.speech,
+            AudioClass::Music => self.music,
+            AudioClass::Sfx => self.sfx,
+            AudioClass::Silence => self.silence,
+        }
+    }
+
+    fn share_mut(&mut self, class: AudioClass) -> &mut f64 {
+        match class {
+            AudioClass::Speech => &mut self.speech,
+            AudioClass::Music => &mut self.music,
+            AudioClass::Sfx => &mut self.sfx,
+            AudioClass::Silence => &mut self.silence,
+        }
+    }
+
+    /// The class with the largest share.
+    pub fn dominant(&self) -> AudioClass {
+        AudioClass::ALL
+            .into_iter()
+            .max_by(|a, b| self.share(*a).total_cmp(&self.share(*b)))
+            .unwrap_or(AudioClass::Silence)
+    }
+
+    /// "music 80%, speech 20%": the classes present, largest first.
+    pub fn describe(&self) -> String {
+        let mut classes: Vec<AudioClass> = AudioClass::ALL
+            .into_iter()
+            .filter(|c| self.share(*c) >= 0.005)
+            .collect();
+        classes.sort_by(|a, b| self.share(*b).total_cmp(&self.share(*a)));
+        classes
+            .iter()
+            .map(|c| format!("{} {:.0}%", c.name(), self.share(*c) * 100.0))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Label every WINDOW_SECS of the first audio stream of `input`. The decode
+/// is streamed, so long inputs are never held in memory.
+pub async fn classify(input: &Path) -> ClassifyResult<Vec<AudioWindow>> {
+    info!("[AUDIO] Classifying the audio of {:?}", input);
+    let mut decode = proc::ffmpeg(ProcClass::Thumbnail);
+    decode
+        .stealth()
+        .args(["-v", "error", "-nostdin", "-i"])
+        .arg(safe_arg_path(input))
+        .args(["-vn", "-map", "0:a:0", "-ac", "1", "-ar"])
+        .arg(SAMPLE_RATE.to_string())
+        .args(["-f", "f32le", "-"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = decode.spawn().await?;
+    let mut stdout = child.stdout.take().ok_or("ffmpeg has no stdout")?;
+
+    let window_len = (SAMPLE_RATE as f64 * WINDOW_SECS) as usize;
+    let mut bytes = vec![0u8; window_len * 4];
+    let mut windows = Vec::new();
+    let mut start = 0.0;
+    loop {
+        let mut filled = 0;
+        while filled < bytes.len() {
+            let n = stdout.read(&mut bytes[filled..]).await?;
+            if n == 0 {
+                break;
+            }
+            filled += n;
+        }
+        let samples: Vec<f32> = bytes[..filled - filled % 4]
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+        let secs = samples.len() as f64 / SAMPLE_RATE as f64;
+        if secs >= MIN_TAIL_SECS {
+            windows.push(AudioWindow {
+                start,
+                end: start + secs,
+                class: classify_window(&samples, SAMPLE_RATE),
+            });
+        }
+        start += secs;
+        if filled < bytes.len() {
+            break;
+        }
+    }
+
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = pipe.read_to_string(&mut stderr).await;
+    }
+    let status = child.wait().await?;
+    if !status.success() && windows.is_empty() {
+        return Err(format!(
+            "Cannot read audio from {:?}: {}",
+            input,
+            stderr.lines().last().unwrap_or("no audio stream")
+        )
+        .into());
+    }
+    info!("[AUDIO] Labelled {} windows of {:?}", windows.len(), input);
+    Ok(windows)
+}
+
+/// [`classify`] on decoded mono samples.
+pub fn classify_pcm(pcm: &[f32], sample_rate: u32) -> Vec<AudioWindow> {
+    let window_len = ((sample_rate as f64 * WINDOW_SECS) as usize).max(1);
+    pcm.chunks(window_len)
+        .enumerate()
+        .filter(|(_, chunk)| chunk.len() as f64 / sample_rate as f64 >= MIN_TAIL_SECS)
+        .map(|(i, chunk)| {
+            let start = (i * window_len) as f64 / sample_rate as f64;
+            AudioWindow {
+                start,
+                end: start + chunk.len() as f64 / sample_rate as f64,
+                class: classify_window(chunk, sample_rate),
+            }
+        })
+        .collect()
+}
+
+/// Label one window of mono samples.
+pub fn classify_window(samples: &[f32], sample_rate: u32) -> AudioClass {
+    let frame_len = ((sample_rate as f64 * FRAME_SECS) as usize).max(2);
+    let frames: Vec<(f64, f64)> = samples
+        .chunks(frame_len)
+        .filter(|f| f.len() >= 2)
+        .map(|f| {
+            let rms = (f.iter().map(|s| (*s as f64).powi(2)).sum::<f64>() / f.len() as f64).sqrt();
+            let crossings = f
+                .windows(2)
+                .filter(|w| (w[0] >= 0.0) != (w[1] >= 0.0))
+                .count();
+            (rms, crossings as f64 / (f.len() - 1) as f64)
+        })
+        .collect();
+    if frames.is_empty() {
+        return AudioClass::Silence;
+    }
+
+    let power = samples.iter().map(|s| (*s as f64).powi(2)).sum::<f64>() / samples.len() as f64;
+    let dbfs = 10.0 * power.max(1e-12).log10();
+    if dbfs < SILENCE_DBFS {
+        return AudioClass::Silence;
+    }
+
+    let total_rms: f64 = frames.iter().map(|(rms, _)| rms).sum();
+    let mean_rms = total_rms / frames.len() as f64;
+    let zcr = frames.iter().map(|(rms, zcr)| rms * zcr).sum::<f64>() / total_rms.max(1e-12);
+    if zcr >= SFX_ZCR {
+        return AudioClass::Sfx;
+    }
+    let dips = frames
+        .iter()
+        .filter(|(rms, _)| *rms < mean_rms * DIP_LEVEL)
+        .count();
+    if dips as f64 / frames.len() as f64 >= SPEECH_DIP_RATIO {
+        AudioClass::Speech
+    } else {
+        AudioClass::Music
+    }
+}
+
+/// Share of each class over `start..end`; `None` where no window overlaps.
+pub fn mix(windows: &[AudioWindow], start: f64, end: f64) -> Option<AudioMix> {
+    let mut mix = AudioMix::default();
+    let mut covered = 0.0;
+    for w in windows {
+        let overlap = w.end.min(end) - w.start.max(start);
+        if overlap > 0.0 {
+            *mix.share_mut(w.class) += overlap;
+            covered += overlap;
+        }
+    }
+    if covered <= 0.0 {
+        return None;
+    }
+    for class in AudioClass::ALL {
+        *mix.share_mut(class) /= covered;
+    }
+    Some(mix)
+}
+
+/// Set each scene's `audio_mix` from `windows`.
+pub fn label_scenes(scenes: &mut [Scene], windows: &[AudioWindow]) {
+    for scene in scenes {
+        scene.audio_mix = mix(windows, scene.start_time, scene.end_time);
+    }
+}
+
+/// Audio classes a request asks to keep and to cut, from phrases like
+/// "keep the music sections" or "cut everything that isn't gameplay audio".
+/// A class asked for both ways is kept.
+pub fn preferences_from_text(lower: &str) -> (Vec<AudioClass>, Vec<AudioClass>) {
+    const SOUNDS: &str = r"(?:music|songs?|soundtrack|sound effects|sfx|(?:game ?play |game )?(?:audio|sounds?)|talking|speech|commentary|dialogue|voice ?over|narration)";
+    let mut keep = Vec::new();
+    let mut cut = Vec::new();
+
+    // "cut everything that isn't X" keeps X and cuts the rest
+    let negated = regex::Regex::new(&format!(
+        r"(?:cut|remove|drop|skip)\s+(?:everything|anything|all)\s+(?:that\s+)?(?:isn't|isnt|is not|without|but|except)\s+(?:the\s+)?({})",
+        SOUNDS
+    ))
+    .expect("valid regex");
+    let mut rest = lower.to_string();
+    for caps in negated.captures_iter(lower) {
+        let wanted = classes_named(&caps[1]);
+        for class in [AudioClass::Speech, AudioClass::Music, AudioClass::Sfx] {
+            if wanted.contains(&class) {
+                keep.push(class);
+            } else {
+                cut.push(class);
+            }
+        }
+        rest = rest.replace(&caps[0], " ");
+    }
+
+    let keeps = regex::Regex::new(&format!(
+        r"(?:keep|leave|want|prefer)\s+(?:in\s+)?(?:all\s+)?(?:the\s+)?(?:parts?\s+with\s+(?:the\s+)?)?({})",
+        SOUNDS
+    ))
+    .expect("valid regex");
+    let cuts = regex::Regex::new(&format!(
+        r"(?:cut|remove|drop|skip|lose)\s+(?:out\s+)?(?:all\s+)?(?:the\s+)?(?:parts?\s+with\s+(?:the\s+)?)?({})",
+        SOUNDS
+    ))
+    .expect("valid regex");
+    for caps in keeps.captures_iter(&rest) {
+        keep.extend(classes_named(&caps[1]));
+    }
+    for caps in cuts.captures_iter(&rest) {
+        cut.extend(classes_named(&caps[1]));
+    }
+
+    keep.sort_by_key(|c| c.name());
+    keep.dedup();
+    cut.retain(|c| !keep.contains(c));
+    cut.sort_by_key(|c| c.name());
+    cut.dedup();
+    (keep, cut)
+}
+
+fn classes_named(phrase: &str) -> Vec<AudioClass> {
+    let phrase = phrase.trim();
+    if phrase.starts_with("game") {
+        return vec![AudioClass::Sfx, AudioClass::Music];
+    }
+    if phrase.starts_with("music") || phrase.starts_with("song") || phrase == "soundtrack" {
+        return vec![AudioClass::Music];
+    }
+    if phrase == "sound effects" || phrase == "sfx" || phrase.starts_with("sound") {
+        return vec![AudioClass::Sfx];
+    }
+    if phrase == "audio" {
+        return Vec::new();
+    }
+    vec![AudioClass::Speech]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 16_000;
+
+    fn tone(secs: f64, hz: f64, amp: f32) -> Vec<f32> {
+        (0..(secs * RATE as f64) as usize)
+            .map(|i| amp * (2.0 * std::f64::consts::PI * hz * i as f64 / RATE as f64).sin() as f32)
+            .collect()
+    }
+
+    fn noise(secs: f64, amp: f32) -> Vec<f32> {
+        let mut state: u32 = 0x1234_5678;
+        (0..(secs * RATE as f64) as usize)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                amp * (state as f32 / u32::MAX as f32 * 2.0 - 1.0)
+            })
+            .collect()
+    }
+
+    /// 150 ms on, 100 ms off, like syllables.
+    fn bursts(secs: f64, on: impl Fn(f64) -> Vec<f32>) -> Vec<f32> {
+        let mut out = Vec::new();
+        while (out.len() as f64) < secs * RATE as f64 {
+            out.extend(on(0.15));
+            out.extend(vec![0.0; (0.1 * RATE as f64) as usize]);
+        }
+        out.truncate((secs * RATE as f64) as usize);
+        out
+    }
+
+    #[test]
+    fn synthetic_signals_get_the_expected_class() {
+        assert_eq!(
+            classify_window(&tone(1.0, 440.0, 0.5), RATE),
+            AudioClass::Music
+        );
+        assert_eq!(classify_window(&noise(1.0, 0.5), RATE), AudioClass::Sfx);
+        assert_eq!(
+            classify_window(&vec![0.0; RATE as usize], RATE),
+            AudioClass::Silence
+        );
+        assert_eq!(
+            classify_window(&tone(1.0, 440.0, 0.001), RATE),
+            AudioClass::Silence
+        );
+        assert_eq!(
+            classify_window(&bursts(1.0, |s| tone(s, 200.0, 0.5)), RATE),
+            AudioClass::Speech
+        );
+        assert_eq!(
+            classify_window(&bursts(1.0, |s| noise(s, 0.5)), RATE),
+            AudioClass::Sfx
+        );
+    }
+
+    #[test]
+    fn scenes_get_the_share_of_each_class_they_overlap() {
+        let mut pcm = tone(2.0, 440.0, 0.5);
+        pcm.extend(vec![0.0; 2 * RATE as usize]);
+        pcm.extend(vec![0.0; (0.1 * RATE as f64) as usize]);
+        let windows = classify_pcm(&pcm, RATE);
+        assert_eq!(windows.len(), 4, "a 0.1s tail is not labelled");
+        assert_eq!(windows[3].class, AudioClass::Silence);
+
+        let half = mix(&windows, 1.0, 3.0).unwrap();
+        assert!((half.music - 0.5).abs() < 1e-9);
+        assert!((half.silence - 0.5).abs() < 1e-9);
+        assert_eq!(
+            mix(&windows, 0.0, 1.5).unwrap().dominant(),
+            AudioClass::Music
+        );
+        assert_eq!(mix(&windows, 0.0, 1.5).unwrap().describe(), "music 100%");
+        assert!(mix(&windows, 10.0, 12.0).is_none());
+    }
+
+    #[test]
+    fn requests_map_to_audio_classes() {
+        use AudioClass::*;
+        assert_eq!(
+            preferences_from_text("keep the music sections"),
+            (vec![Music], vec![])
+        );
+        assert_eq!(
+            preferences_from_text("cut everything that isn't gameplay audio"),
+            (vec![Music, Sfx], vec![Speech])
+        );
+        assert_eq!(
+            preferences_from_text("remove the talking and keep the sound effects"),
+            (vec![Sfx], vec![Speech])
+        );
+        assert_eq!(
+            preferences_from_text("cut the music but keep the music at the end"),
+            (vec![Music], vec![])
+        );
+        assert_eq!(preferences_from_text("make it snappy"), (vec![], vec![]));
+        assert_eq!("noise".parse::<AudioClass>(), Ok(Sfx));
+        assert!("jazz".parse::<AudioClass>().is_err());
+    }
+}
@@ -2,9 +2,9 @@
 // Copyright (c) 2026 xingxerx_The_Creator | SYNOID
 
 use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
-use tokio::process::Command as AsyncCommand;
 use tracing::info;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,7 +33,7 @@ pub async fn scan_audio(
 
     // Use real FFmpeg ebur128 analysis instead of hardcoded values
     let safe_path = crate::agent::production_tools::safe_arg_path(path);
-    let output = tokio::process::Command::new("ffmpeg")
+    let output = proc::ffmpeg(ProcClass::Thumbnail)
         .stealth()
         .args(["-v", "error", "-i"])
         .arg(&safe_path)
@@ -42,28 +42,356 @@ pub async fn scan_audio(
         .await?;
 
     let stderr = String::from_utf8_lossy(&output.stderr);
-    let mut average_loudness = -14.0;
-    for line in stderr.lines().rev() {
-        if line.contains("I:") && line.contains("LUFS") {
-            if let Some(idx) = line.find("I:") {
-                let parts: Vec<&str> = line[idx..].split_whitespace().collect();
-                if parts.len() >= 2 {
-                    if let Ok(val) = parts[1].parse::<f64>() {
-                        average_loudness = val;
-                        break;
-                    }
-                }
-            }
-        }
-    }
-
     Ok(AudioAnalysis {
         duration,
-        average_loudness,
+        average_loudness: ebur128_summary_value(&stderr, "I:", "LUFS").unwrap_or(-14.0),
         transients: Vec::new(),
     })
 }
 
+/// A value from the summary `ebur128` logs when it finishes, e.g. `I:` in
+/// `LUFS` (integrated loudness) or `LRA:` in `LU` (loudness range). The
+/// last match wins, as the summary follows any per-frame lines.
+pub fn ebur128_summary_value(stderr: &str, key: &str, unit: &str) -> Option<f64> {
+    stderr.lines().rev().find_map(|line| {
+        let idx = line.find(key)?;
+        let mut parts = line[idx + key.len()..].split_whitespace();
+        let value = parts.next()?.parse::<f64>().ok()?;
+        (parts.next() == Some(unit)).then_some(value)
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Loudness & Clipping Report
+// One decode through `astats` (sample peaks, full-scale hits) and `loudnorm`
+// in print mode (EBU R128 integrated loudness, true peak) before mixing.
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Sample peaks at or above this level count as clipped.
+pub const CLIP_THRESHOLD_DBFS: f64 = -0.1;
+/// Integrated loudness below this is too quiet for any delivery target.
+pub const QUIET_LUFS: f64 = -24.0;
+/// True peak ceiling kept when suggesting gain.
+pub const TRUE_PEAK_CEILING_DBTP: f64 = -1.0;
+
+/// Loudness and clipping measurements for one file's first audio stream.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoudnessReport {
+    /// EBU R128 integrated loudness (LUFS); `-inf` for silence.
+    pub integrated_lufs: f64,
+    /// Inter-sample (true) peak in dBTP.
+    pub true_peak_dbtp: f64,
+    /// Loudness range in LU.
+    pub loudness_range_lu: f64,
+    /// Highest sample peak in dBFS.
+    pub peak_dbfs: f64,
+    /// Average RMS level in dBFS.
+    pub rms_dbfs: f64,
+    /// Samples sitting at full scale.
+    pub clipped_samples: u64,
+}
+
+impl LoudnessReport {
+    pub fn is_clipping(&self) -> bool {
+        self.clipped_samples > 0
+    }
+
+    pub fn is_too_quiet(&self) -> bool {
+        self.integrated_lufs < QUIET_LUFS
+    }
+
+    /// Gain (dB) that brings the file to `target_lufs` without pushing the
+    /// true peak past [`TRUE_PEAK_CEILING_DBTP`].
+    pub fn suggested_gain_db(&self, target_lufs: f64) -> f64 {
+        if !self.integrated_lufs.is_finite() {
+            return 0.0;
+        }
+        let gain = target_lufs - self.integrated_lufs;
+        if self.true_peak_dbtp.is_finite() {
+            gain.min(TRUE_PEAK_CEILING_DBTP - self.true_peak_dbtp)
+        } else {
+            gain
+        }
+    }
+
+    /// One line per problem, empty when the audio is fine.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if self.is_clipping() {
+            lines.push(format!(
+                "{} sample(s) clipped (peak {:.1} dBFS)",
+                self.clipped_samples, self.peak_dbfs
+            ));
+        } else if self.true_peak_dbtp > 0.0 {
+            lines.push(format!(
+                "True peak {:.1} dBTP will clip after lossy encoding",
+                self.true_peak_dbtp
+            ));
+        }
+        if self.is_too_quiet() {
+            lines.push(format!(
+                "Integrated loudness {:.1} LUFS is below {:.0} LUFS",
+                self.integrated_lufs, QUIET_LUFS
+            ));
+        }
+        lines
+    }
+}
+
+/// Measure loudness, peaks and clipping of `input` in one ffmpeg pass.
+pub async fn analyze_loudness(
+    input: &Path,
+) -> Result<LoudnessReport, Box<dyn std::error::Error + Send + Sync>> {
+    info!("[EARS] Measuring loudness and clipping: {:?}", input);
+    let safe_path = crate::agent::production_tools::safe_arg_path(input);
+    // astats first: loudnorm hands on resampled, gain-ridden audio
+    let output = proc::ffmpeg(ProcClass::Thumbnail)
+        .stealth()
+        .args(["-hide_banner", "-nostats", "-nostdin", "-i"])
+        .arg(&safe_path)
+        .args([
+            "-vn",
+            "-map",
+            "0:a:0",
+            "-af",
+            "astats,loudnorm=print_format=json",
+            "-f",
+            "null",
+            "-",
+        ])
+        .output()
+        .await?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        let reason = stderr.lines().last().unwrap_or("no audio stream");
+        return Err(format!("Loudness analysis failed: {}", reason).into());
+    }
+    parse_loudness_report(&stderr).ok_or_else(|| "ffmpeg reported no audio statistics".into())
+}
+
+/// Build a report from the `astats` summary and `loudnorm` JSON in ffmpeg's
+/// stderr. The loudnorm fields stay `-inf` when only astats is present.
+pub fn parse_loudness_report(stderr: &str) -> Option<LoudnessReport> {
+    let stats = parse_astats_overall(stderr)?;
+    let field = |key: &str| stats.get(key).and_then(|v| v.parse::<f64>().ok());
+    let peak_dbfs = field("Peak level dB")?;
+    // astats counts how often the signal hit its own peak; that is only
+    // clipping when the peak is at full scale
+    let clipped_samples = if peak_dbfs >= CLIP_THRESHOLD_DBFS {
+        field("Peak count").map_or(0, |n| n as u64)
+    } else {
+        0
+    };
+
+    let loudnorm = parse_loudnorm_json(stderr);
+    let measured = |key: &str| {
+        loudnorm
+            .as_ref()
+            .and_then(|j| j.get(key))
+            .and_then(|v| v.as_str())
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .unwrap_or(f64::NEG_INFINITY)
+    };
+
+    Some(LoudnessReport {
+        integrated_lufs: measured("input_i"),
+        true_peak_dbtp: measured("input_tp"),
+        loudness_range_lu: measured("input_lra").max(0.0),
+        peak_dbfs,
+        rms_dbfs: field("RMS level dB").unwrap_or(f64::NEG_INFINITY),
+        clipped_samples,
+    })
+}
+
+/// `key: value` pairs from the "Overall" block of an astats summary.
+fn parse_astats_overall(stderr: &str) -> Option<std::collections::HashMap<String, String>> {
+    let mut overall = None;
+    for line in stderr.lines() {
+        // Lines look like "[Parsed_astats_0 @ 0x55d0] Peak level dB: -0.4"
+        let Some(body) = line
+            .split_once("astats")
+            .and_then(|(_, rest)| rest.split_once(']'))
+            .map(|(_, body)| body.trim())
+        else {
+            continue;
+        };
+        if body == "Overall" {
+            overall = Some(std::collections::HashMap::new());
+        } else if body.starts_with("Channel:") {
+            overall = None;
+        } else if let (Some(map), Some((key, value))) = (overall.as_mut(), body.split_once(':')) {
+            map.insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+    overall.filter(|m| !m.is_empty())
+}
+
+/// The JSON object loudnorm prints after its `[Parsed_loudnorm_N @ ...]` line.
+fn parse_loudnorm_json(stderr: &str) -> Option<serde_json::Value> {
+    let start = stderr.find("Parsed_loudnorm")?;
+    let rest = &stderr[start..];
+    let open = rest.find('{')?;
+    let close = rest[open..].find('}')?;
+    serde_json::from_str(&rest[open..=open + close]).ok()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Audio Sync
+// Lines up two recordings of the same event (camera scratch audio and an
+// external recorder, two cameras) by cross-correlating onset envelopes: a
+// clap or a plosive is a sharp rise in both, whatever the mics sound like.
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Sample rate the tracks are decoded at for syncing.
+pub const SYNC_SAMPLE_RATE: u32 = 8000;
+/// Envelope hop; offsets are found to this resolution, then refined.
+pub const SYNC_HOP_SECS: f64 = 0.005;
+/// Largest offset searched either way.
+pub const MAX_SYNC_OFFSET_SECS: f64 = 30.0;
+/// Seconds of each track decoded for the search.
+pub const SYNC_ANALYSIS_SECS: f64 = 120.0;
+/// Normalised correlation the best lag must reach to count as a match.
+pub const MIN_SYNC_CORRELATION: f64 = 0.3;
+/// The best lag must beat any other peak this far away by 10 %; repetitive
+/// audio (a beat, a metronome) lines up equally well at several lags.
+const SYNC_PEAK_SEPARATION_SECS: f64 = 0.25;
+/// Tracks must overlap at least this long, and by half the shorter one, at
+/// a candidate lag; a sliver of overlap holding one clap correlates
+/// perfectly by accident.
+const MIN_SYNC_OVERLAP_SECS: f64 = 2.0;
+
+/// How far `b` lags `a`, in seconds: an event at `t` in `a` is heard at
+/// `t + offset` in `b`. Errors rather than guessing when the two do not
+/// correlate (different takes, music against dialogue, silence).
+pub async fn sync_offset(
+    a: &Path,
+    b: &Path,
+) -> Result<f64, Box<dyn std::error::Error + Send + Sync>> {
+    let (pcm_a, pcm_b) = tokio::try_join!(decode_for_sync(a), decode_for_sync(b))?;
+    let offset = offset_from_pcm(&pcm_a, &pcm_b, SYNC_SAMPLE_RATE)?;
+    info!("[SYNC] {:?} lags {:?} by {:+.3}s", b, a, offset);
+    Ok(offset)
+}
+
+/// The first audio stream of `path` as mono f32 samples at
+/// [`SYNC_SAMPLE_RATE`].
+async fn decode_for_sync(path: &Path) -> Result<Vec<f32>, Box<dyn std::error::Error + Send + Sync>> {
+    let safe_path = crate::agent::production_tools::safe_arg_path(path);
+    let output = proc::ffmpeg(ProcClass::Thumbnail)
+        .stealth()
+        .args(["-v", "error", "-nostdin", "-t"])
+        .arg(SYNC_ANALYSIS_SECS.to_string())
+        .arg("-i")
+        .arg(&safe_path)
+        .args(["-vn", "-map", "0:a:0", "-ac", "1", "-ar"])
+        .arg(SYNC_SAMPLE_RATE.to_string())
+        .args(["-f", "f32le", "-"])
+        .output()
+        .await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "Cannot read audio from {:?}: {}",
+            path,
+            stderr.lines().last().unwrap_or("no audio stream")
+        )
+        .into());
+    }
+    Ok(output
+        .stdout
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect())
+}
+
+/// [`sync_offset`] on decoded mono samples.
+pub fn offset_from_pcm(a: &[f32], b: &[f32], sample_rate: u32) -> Result<f64, String> {
+    let hop = ((sample_rate as f64 * SYNC_HOP_SECS).round() as usize).max(1);
+    let hop_secs = hop as f64 / sample_rate as f64;
+    let env_a = onset_envelope(a, hop);
+    let env_b = onset_envelope(b, hop);
+
+    let max_lag = (MAX_SYNC_OFFSET_SECS / hop_secs) as isize;
+    let min_overlap =
+        ((MIN_SYNC_OVERLAP_SECS / hop_secs) as usize).max(env_a.len().min(env_b.len()) / 2);
+    let scores: Vec<(isize, f64)> = (-max_lag..=max_lag)
+        .filter_map(|lag| correlation_at(&env_a, &env_b, lag, min_overlap).map(|c| (lag, c)))
+        .collect();
+    let Some(&(best_lag, best)) = scores.iter().max_by(|x, y| x.1.total_cmp(&y.1)) else {
+        return Err("Recordings are too short to sync".to_string());
+    };
+    if best < MIN_SYNC_CORRELATION {
+        return Err(format!(
+            "Recordings do not correlate (best match {:.2}, need {:.2})",
+            best, MIN_SYNC_CORRELATION
+        ));
+    }
+    let separation = (SYNC_PEAK_SEPARATION_SECS / hop_secs) as isize;
+    let rival = scores
+        .iter()
+        .filter(|(lag, _)| (lag - best_lag).abs() > separation)
+        .map(|(_, c)| *c)
+        .fold(f64::NEG_INFINITY, f64::max);
+    if rival * 1.1 > best {
+        return Err(format!(
+            "Sync is ambiguous: offsets more than {:.2}s apart match about as well",
+            SYNC_PEAK_SEPARATION_SECS
+        ));
+    }
+
+    // Parabola through the peak and its neighbours for sub-hop precision
+    let at = |lag: isize| scores.iter().find(|(l, _)| *l == lag).map(|(_, c)| *c);
+    let refine = match (at(best_lag - 1), at(best_lag + 1)) {
+        (Some(left), Some(right)) => {
+            let curve = left - 2.0 * best + right;
+            if curve < 0.0 {
+                (0.5 * (left - right) / curve).clamp(-0.5, 0.5)
+            } else {
+                0.0
+            }
+        }
+        _ => 0.0,
+    };
+    Ok((best_lag as f64 + refine) * hop_secs)
+}
+
+/// Positive changes in short-term RMS, one value per `hop` samples.
+fn onset_envelope(pcm: &[f32], hop: usize) -> Vec<f64> {
+    let rms: Vec<f64> = pcm
+        .chunks(hop)
+        .map(|c| (c.iter().map(|s| (*s as f64).powi(2)).sum::<f64>() / c.len() as f64).sqrt())
+        .collect();
+    let mut onsets = Vec::with_capacity(rms.len());
+    onsets.push(0.0);
+    onsets.extend(rms.windows(2).map(|w| (w[1] - w[0]).max(0.0)));
+    onsets
+}
+
+/// Pearson correlation of `a[i]` with `b[i + lag]` over their overlap.
+fn correlation_at(a: &[f64], b: &[f64], lag: isize, min_overlap: usize) -> Option<f64> {
+    let start = 0.max(-lag) as usize;
+    let end = (a.len() as isize).min(b.len() as isize - lag);
+    if end <= start as isize || (end as usize - start) < min_overlap {
+        return None;
+    }
+    let pairs = (start..end as usize).map(|i| (a[i], b[(i as isize + lag) as usize]));
+    let n = (end as usize - start) as f64;
+    let (sum_a, sum_b) = pairs.clone().fold((0.0, 0.0), |(x, y), (p, q)| (x + p, y + q));
+    let (mean_a, mean_b) = (sum_a / n, sum_b / n);
+    let (mut cov, mut var_a, mut var_b) = (0.0, 0.0, 0.0);
+    for (p, q) in pairs {
+        cov += (p - mean_a) * (q - mean_b);
+        var_a += (p - mean_a).powi(2);
+        var_b += (q - mean_b).powi(2);
+    }
+    if var_a <= f64::EPSILON || var_b <= f64::EPSILON {
+        // Silence on either side matches nothing
+        return Some(0.0);
+    }
+    Some(cov / (var_a * var_b).sqrt())
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // AI Dialogue Matcher (Feature 5a)
 // Normalises the tonal character and room ambience of clips recorded in
@@ -106,7 +434,7 @@ pub async fn match_dialogue(
         gain_correction, ref_lufs
     );
 
-    let status = AsyncCommand::new("ffmpeg")
+    let status = proc::ffmpeg(ProcClass::Render)
         .stealth()
         .args(["-y", "-i"])
         .arg(source_path)
@@ -126,7 +454,7 @@ pub async fn match_dialogue(
 /// Measure integrated loudness (LUFS) of an audio/video file.
 async fn measure_lufs(path: &Path) -> Result<f64, Box<dyn std::error::Error + Send + Sync>> {
     let safe_path = crate::agent::production_tools::safe_arg_path(path);
-    let output = AsyncCommand::new("ffmpeg")
+    let output = proc::ffmpeg(ProcClass::Thumbnail)
         .stealth()
         .args(["-v", "error", "-i"])
         .arg(&safe_path)
@@ -199,7 +527,7 @@ pub async fn apply_spatial_pan(
 
     if keyframes.is_empty() {
         info!("[SPATIAL-PAN] No keyframes; passing through unchanged.");
-        let status = AsyncCommand::new("ffmpeg")
+        let status = proc::ffmpeg(ProcClass::Render)
             .stealth()
             .args(["-y", "-i"])
             .arg(input_path)
@@ -233,7 +561,7 @@ pub async fn apply_spatial_pan(
         balance
     );
 
-    let status = AsyncCommand::new("ffmpeg")
+    let status = proc::ffmpeg(ProcClass::Render)
         .stealth()
         .args(["-y", "-i"])
         .arg(input_path)
@@ -256,7 +584,7 @@ pub async fn get_audio_tracks(
 ) -> Result<Vec<AudioTrack>, Box<dyn std::error::Error + Send + Sync>> {
     let safe_path = crate::agent::production_tools::safe_arg_path(path);
 
-    let output = tokio::process::Command::new("ffprobe")
+    let output = proc::ffprobe()
         .stealth()
         .args([
             "-v",
@@ -300,3 +628,130 @@ pub async fn get_audio_tracks(
 
     Ok(tracks)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Trimmed stderr of `-af astats,loudnorm=print_format=json` on a
+    /// stereo clip driven into full scale.
+    const CLIPPED_MIX: &str = r#"Stream mapping:
+  Stream #0:1 -> #0:0 (aac (native) -> pcm_s16le (native))
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] Channel: 1
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] DC offset: 0.000012
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] Peak level dB: 0.000000
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] RMS level dB: -11.842310
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] Peak count: 31
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] Channel: 2
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] Peak level dB: -0.012000
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] Peak count: 16
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] Overall
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] DC offset: 0.000010
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] Min level: -1.000000
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] Max level: 0.999969
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] Peak level dB: 0.000000
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] RMS level dB: -12.104077
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] Flat factor: 3.201880
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] Peak count: 47.000000
+[Parsed_astats_0 @ 0x55d0c1a2b3c0] Number of samples: 2880000
+[Parsed_loudnorm_1 @ 0x55d0c1a2c480]
+{
+	"input_i" : "-9.87",
+	"input_tp" : "0.42",
+	"input_lra" : "4.10",
+	"input_thresh" : "-19.95",
+	"output_i" : "-24.02",
+	"output_tp" : "-2.00",
+	"output_lra" : "3.90",
+	"output_thresh" : "-34.07",
+	"normalization_type" : "dynamic",
+	"target_offset" : "0.02"
+}
+"#;
+
+    #[test]
+    fn astats_and_loudnorm_output_fill_the_report() {
+        let report = parse_loudness_report(CLIPPED_MIX).unwrap();
+        assert_eq!(
+            report,
+            LoudnessReport {
+                integrated_lufs: -9.87,
+                true_peak_dbtp: 0.42,
+                loudness_range_lu: 4.1,
+                peak_dbfs: 0.0,
+                rms_dbfs: -12.104077,
+                clipped_samples: 47,
+            }
+        );
+        assert!(report.is_clipping());
+        assert!(!report.is_too_quiet());
+        // Bringing it down to -14 LUFS is limited by nothing
+        assert!((report.suggested_gain_db(-14.0) + 4.13).abs() < 1e-9);
+        assert!(report.warnings()[0].contains("47 sample(s) clipped"));
+    }
+
+    const TAKE_ONE: [f64; 7] = [1.3, 3.9, 4.6, 8.2, 11.75, 13.1, 17.4];
+    const TAKE_TWO: [f64; 7] = [2.2, 5.0, 6.1, 9.9, 12.4, 15.8, 18.3];
+
+    /// Claps at `times` over a noise floor, `secs` long.
+    fn claps(secs: f64, rate: u32, seed: u64, times: &[f64]) -> Vec<f32> {
+        let mut state = seed;
+        let mut noise = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 33) as f32 / (1u64 << 31) as f32) - 0.5
+        };
+        let len = (secs * rate as f64) as usize;
+        let mut pcm: Vec<f32> = (0..len).map(|_| noise() * 0.02).collect();
+        for clap in times {
+            let at = (clap * rate as f64) as usize;
+            for (i, s) in pcm.iter_mut().skip(at).take(rate as usize / 20).enumerate() {
+                *s += noise() * (1.0 - i as f32 / (rate as f32 / 20.0));
+            }
+        }
+        pcm
+    }
+
+    #[test]
+    fn a_known_offset_is_recovered_from_a_synthetic_pair() {
+        let rate = 8000;
+        let source = claps(20.0, rate, 7, &TAKE_ONE);
+        // The recorder started 1.234 s before the camera and hears its own noise
+        let lead = (1.234 * rate as f64) as usize;
+        let mut recorder = claps(1.234, rate, 99, &[]);
+        let floor = claps(20.0, rate, 3, &[]);
+        recorder.extend(source.iter().zip(floor).map(|(s, n)| s * 0.6 + n * 0.5));
+
+        let offset = offset_from_pcm(&source, &recorder, rate).unwrap();
+        assert!((offset - lead as f64 / rate as f64).abs() < 0.005, "{}", offset);
+        let back = offset_from_pcm(&recorder, &source, rate).unwrap();
+        assert!((back + 1.234).abs() < 0.005, "{}", back);
+    }
+
+    #[test]
+    fn unrelated_or_silent_recordings_do_not_sync() {
+        let rate = 8000;
+        let a = claps(20.0, rate, 7, &TAKE_ONE);
+        let unrelated = claps(20.0, rate, 11, &TAKE_TWO);
+        assert!(offset_from_pcm(&a, &unrelated, rate).is_err());
+        assert!(offset_from_pcm(&a, &vec![0.0; a.len()], rate).is_err());
+        assert!(offset_from_pcm(&a[..1000], &a[..1000], rate).is_err());
+    }
+
+    #[test]
+    fn quiet_audio_below_full_scale_is_not_clipping() {
+        let stderr = r#"[Parsed_astats_0 @ 0x1] Overall
+[Parsed_astats_0 @ 0x1] Peak level dB: -18.300000
+[Parsed_astats_0 @ 0x1] RMS level dB: -38.500000
+[Parsed_astats_0 @ 0x1] Peak count: 2.000000
+[Parsed_loudnorm_1 @ 0x2]
+{"input_i" : "-35.20", "input_tp" : "-17.90", "input_lra" : "2.00"}
+"#;
+        let report = parse_loudness_report(stderr).unwrap();
+        assert_eq!(report.clipped_samples, 0);
+        assert!(report.is_too_quiet());
+        // +21.2 dB would reach -14 LUFS but the true peak caps it at +16.9
+        assert!((report.suggested_gain_db(-14.0) - 16.9).abs() < 1e-9);
+
+        assert_eq!(parse_loudness_report("Output file is empty"), None);
+    }
+}
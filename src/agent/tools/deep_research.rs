@@ -0,0 +1,422 @@
+// SYNOID Deep Research - Transcripts and comments behind search results
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// `research --deep` goes past titles and URLs: for the top few results it
+// fetches the auto-generated subtitles (and optionally the top comments)
+// without downloading the video, has the Brain boil each one down to concrete
+// editing techniques, and writes one report per topic to cortex_cache/research/
+// as Markdown and JSON. The autonomous learner studies a fresh report instead
+// of downloading that topic's videos.
+
+use crate::agent::ai_systems::gpt_oss_bridge::SynoidAgent;
+use crate::agent::tools::source_tools::{self, SourceInfo};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Results deep-fetched per run unless `--deep-limit` says otherwise.
+pub const DEFAULT_DEEP_ITEMS: usize = 3;
+
+/// Hard cap on deep-fetched results per run.
+pub const MAX_DEEP_ITEMS: usize = 8;
+
+/// Pause between yt-dlp fetches, so a run never hammers YouTube.
+pub const FETCH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Top comments read per video when comments are requested.
+pub const TOP_COMMENTS: usize = 20;
+
+/// Transcript characters handed to the Brain per video.
+const MAX_TRANSCRIPT_CHARS: usize = 12_000;
+
+/// A report stands in for the videos while it is this fresh...
+const REPORT_FRESH_DAYS: i64 = 30;
+
+/// ...and names at least this many techniques.
+const MIN_TECHNIQUES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeepOptions {
+    /// Results to fetch text for; capped at `MAX_DEEP_ITEMS`.
+    pub max_items: usize,
+    /// Also read each video's top comments.
+    pub comments: bool,
+}
+
+impl Default for DeepOptions {
+    fn default() -> Self {
+        Self {
+            max_items: DEFAULT_DEEP_ITEMS,
+            comments: false,
+        }
+    }
+}
+
+/// What one video taught.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeepItem {
+    pub title: String,
+    pub url: String,
+    /// Transcript characters read; 0 when the video has no subtitles.
+    pub transcript_chars: usize,
+    pub comments_read: usize,
+    pub techniques: Vec<String>,
+    /// Why nothing was learned from this video, when nothing was.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResearchReport {
+    pub topic: String,
+    pub created_at: DateTime<Utc>,
+    pub items: Vec<DeepItem>,
+}
+
+pub fn research_dir() -> PathBuf {
+    PathBuf::from("cortex_cache").join("research")
+}
+
+/// File-name form of a topic: lowercase words joined by `_`.
+pub(crate) fn topic_slug(topic: &str) -> String {
+    let slug: String = topic
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    slug.chars().take(60).collect()
+}
+
+impl ResearchReport {
+    pub fn json_path(topic: &str) -> PathBuf {
+        research_dir().join(format!("{}.json", topic_slug(topic)))
+    }
+
+    pub fn markdown_path(topic: &str) -> PathBuf {
+        research_dir().join(format!("{}.md", topic_slug(topic)))
+    }
+
+    /// The saved report for `topic`, if one exists.
+    pub fn load(topic: &str) -> Option<Self> {
+        serde_json::from_str(&std::fs::read_to_string(Self::json_path(topic)).ok()?).ok()
+    }
+
+    /// Write the JSON and Markdown reports; returns their paths.
+    pub fn save(&self) -> std::io::Result<(PathBuf, PathBuf)> {
+        std::fs::create_dir_all(research_dir())?;
+        let json_path = Self::json_path(&self.topic);
+        let md_path = Self::markdown_path(&self.topic);
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(&json_path, json)?;
+        std::fs::write(&md_path, self.to_markdown())?;
+        Ok((json_path, md_path))
+    }
+
+    /// Every distinct technique across the videos, in first-seen order.
+    pub fn techniques(&self) -> Vec<String> {
+        let mut seen = std::collections::HashSet::new();
+        self.items
+            .iter()
+            .flat_map(|item| &item.techniques)
+            .filter(|t| seen.insert(t.trim().to_lowercase()))
+            .cloned()
+            .collect()
+    }
+
+    /// True when the report is recent and concrete enough to learn from
+    /// without downloading the videos behind it.
+    pub fn suffices(&self, now: DateTime<Utc>) -> bool {
+        now.signed_duration_since(self.created_at).num_days() < REPORT_FRESH_DAYS
+            && self.techniques().len() >= MIN_TECHNIQUES
+    }
+
+    /// Identifies this report in the learner's processed set, so each
+    /// report is studied once.
+    pub fn study_key(&self) -> String {
+        format!("research:{}:{}", topic_slug(&self.topic), self.created_at.timestamp())
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!(
+            "# Research: {}\n\n_{} · {} video(s)_\n\n## Key techniques\n\n",
+            self.topic,
+            self.created_at.format("%Y-%m-%d %H:%M UTC"),
+            self.items.len()
+        );
+        let techniques = self.techniques();
+        if techniques.is_empty() {
+            md.push_str("_None found._\n");
+        }
+        for t in &techniques {
+            md.push_str(&format!("- {}\n", t));
+        }
+        md.push_str("\n## Sources\n");
+        for item in &self.items {
+            md.push_str(&format!(
+                "\n### [{}]({})\n\n{} transcript chars, {} comments\n\n",
+                item.title, item.url, item.transcript_chars, item.comments_read
+            ));
+            if let Some(e) = &item.error {
+                md.push_str(&format!("_Skipped: {}_\n", e));
+            }
+            for t in &item.techniques {
+                md.push_str(&format!("- {}\n", t));
+            }
+        }
+        md
+    }
+}
+
+/// Plain text of a WebVTT file. Auto-generated captions repeat each line as
+/// it scrolls and carry inline word timings; both are dropped.
+pub fn vtt_to_text(vtt: &str) -> String {
+    let tag = regex::Regex::new(r"<[^>]*>").unwrap();
+    let mut lines: Vec<String> = Vec::new();
+    for raw in vtt.lines() {
+        let line = raw.trim();
+        if line.is_empty()
+            || line == "WEBVTT"
+            || line.contains("-->")
+            || line.starts_with("Kind:")
+            || line.starts_with("Language:")
+            || line.starts_with("NOTE")
+            || line.chars().all(|c| c.is_ascii_digit())
+        {
+            continue;
+        }
+        let text = tag.replace_all(line, "").trim().to_string();
+        if !text.is_empty() && lines.last() != Some(&text) {
+            lines.push(text);
+        }
+    }
+    lines.join(" ")
+}
+
+/// Comment texts from a yt-dlp info JSON, most-liked first.
+pub fn top_comments(info_json: &str, limit: usize) -> Vec<String> {
+    let Ok(info) = serde_json::from_str::<serde_json::Value>(info_json) else {
+        return Vec::new();
+    };
+    let mut comments: Vec<(i64, String)> = info["comments"]
+        .as_array()
+        .map(|all| {
+            all.iter()
+                .filter_map(|c| {
+                    let text = c["text"].as_str()?.trim();
+                    (!text.is_empty()).then(|| (c["like_count"].as_i64().unwrap_or(0), text.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    comments.sort_by_key(|(likes, _)| std::cmp::Reverse(*likes));
+    comments.into_iter().take(limit).map(|(_, text)| text).collect()
+}
+
+fn truncate_chars(s: &str, max: usize) -> &str {
+    match s.char_indices().nth(max) {
+        Some((i, _)) => &s[..i],
+        None => s,
+    }
+}
+
+#[derive(Deserialize)]
+struct TechniqueReply {
+    techniques: Vec<String>,
+}
+
+/// Ask the Brain's model for the concrete techniques a video teaches.
+async fn summarize(
+    agent: &SynoidAgent,
+    title: &str,
+    transcript: &str,
+    comments: &[String],
+) -> Result<Vec<String>, String> {
+    let mut prompt = format!(
+        r#"You are studying a video editing tutorial. List the concrete, reusable editing techniques it teaches (cut timing, transitions, grading, audio, pacing), each as one short imperative sentence. Skip greetings, sponsors and channel plugs.
+Reply with ONLY this JSON: {{"techniques": [string]}}
+
+Title: {}
+Transcript: {}
+"#,
+        title,
+        truncate_chars(transcript, MAX_TRANSCRIPT_CHARS)
+    );
+    if !comments.is_empty() {
+        prompt.push_str("Top viewer comments:\n");
+        for c in comments {
+            prompt.push_str(&format!("- {}\n", truncate_chars(c, 300)));
+        }
+    }
+
+    let response = agent.reason(&prompt).await?;
+    let reply: TechniqueReply = crate::agent::llm_json::parse_with_repair(
+        "research_techniques",
+        &prompt,
+        &response,
+        |r: &TechniqueReply| {
+            if r.techniques.iter().all(|t| t.trim().is_empty()) {
+                Err("techniques must list at least one technique".to_string())
+            } else {
+                Ok(())
+            }
+        },
+        |repair| async move { agent.reason(&repair).await },
+    )
+    .await?;
+    Ok(reply
+        .techniques
+        .into_iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect())
+}
+
+/// File stem for one video's fetched text, from its YouTube id.
+fn source_stem(url: &str) -> PathBuf {
+    let id: String = url
+        .rsplit("v=")
+        .next()
+        .unwrap_or(url)
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .take(32)
+        .collect();
+    research_dir().join("sources").join(id)
+}
+
+async fn read_and_remove(path: &Option<PathBuf>) -> String {
+    let Some(path) = path else { return String::new() };
+    let text = tokio::fs::read_to_string(path).await.unwrap_or_default();
+    let _ = tokio::fs::remove_file(path).await;
+    text
+}
+
+/// Fetch the text behind the first `options.max_items` results and
+/// summarize each into techniques. Failures are recorded per item rather
+/// than aborting the report.
+pub async fn deep_research(
+    topic: &str,
+    results: &[SourceInfo],
+    options: DeepOptions,
+    agent: &SynoidAgent,
+) -> ResearchReport {
+    let max_items = options.max_items.clamp(1, MAX_DEEP_ITEMS);
+    let mut items = Vec::new();
+    for (i, source) in results
+        .iter()
+        .filter(|s| s.original_url.is_some())
+        .take(max_items)
+        .enumerate()
+    {
+        if i > 0 {
+            tokio::time::sleep(FETCH_INTERVAL).await;
+        }
+        let url = source.original_url.clone().unwrap_or_default();
+        info!("[RESEARCH] 📜 Reading '{}' ({}/{})", source.title, i + 1, max_items);
+        let mut item = DeepItem {
+            title: source.title.clone(),
+            url: url.clone(),
+            transcript_chars: 0,
+            comments_read: 0,
+            techniques: Vec::new(),
+            error: None,
+        };
+
+        let max_comments = options.comments.then_some(TOP_COMMENTS);
+        match source_tools::fetch_video_text(&url, &source_stem(&url), max_comments).await {
+            Ok((subs, info_json)) => {
+                let transcript = vtt_to_text(&read_and_remove(&subs).await);
+                let comments = top_comments(&read_and_remove(&info_json).await, TOP_COMMENTS);
+                item.transcript_chars = transcript.chars().count();
+                item.comments_read = comments.len();
+                if transcript.is_empty() && comments.is_empty() {
+                    item.error = Some("no subtitles or comments available".to_string());
+                } else {
+                    match summarize(agent, &source.title, &transcript, &comments).await {
+                        Ok(techniques) => item.techniques = techniques,
+                        Err(e) => item.error = Some(format!("summary failed: {}", e)),
+                    }
+                }
+            }
+            Err(e) => item.error = Some(e.to_string()),
+        }
+        if let Some(e) = &item.error {
+            warn!("[RESEARCH] ⚠️ '{}': {}", source.title, e);
+        }
+        items.push(item);
+    }
+
+    ResearchReport {
+        topic: topic.to_string(),
+        created_at: Utc::now(),
+        items,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_caption_vtt_becomes_plain_text() {
+        let vtt = "WEBVTT\nKind: captions\nLanguage: en\n\n\
+            00:00:00.000 --> 00:00:02.000 align:start position:0%\n\
+            cut<00:00:00.500><c> on</c><00:00:01.000><c> the</c><00:00:01.500><c> beat</c>\n\n\
+            00:00:02.000 --> 00:00:02.010\n\
+            cut on the beat\n\n\
+            00:00:02.010 --> 00:00:04.000\n\
+            cut on the beat\n\
+            then match the motion\n";
+        assert_eq!(vtt_to_text(vtt), "cut on the beat then match the motion");
+    }
+
+    #[test]
+    fn comments_are_ranked_by_likes() {
+        let info = r#"{"comments": [
+            {"text": "first!", "like_count": 1},
+            {"text": "the J-cut at 4:10 is the trick", "like_count": 90},
+            {"text": "  ", "like_count": 500},
+            {"text": "use speed ramps sparingly", "like_count": 40}
+        ]}"#;
+        assert_eq!(
+            top_comments(info, 2),
+            vec!["the J-cut at 4:10 is the trick", "use speed ramps sparingly"]
+        );
+        assert!(top_comments("not json", 5).is_empty());
+    }
+
+    #[test]
+    fn fresh_reports_with_enough_techniques_suffice() {
+        let item = |techniques: &[&str]| DeepItem {
+            title: "Cinematic B-roll".to_string(),
+            url: "https://www.youtube.com/watch?v=abc".to_string(),
+            transcript_chars: 1200,
+            comments_read: 0,
+            techniques: techniques.iter().map(|t| t.to_string()).collect(),
+            error: None,
+        };
+        let now = Utc::now();
+        let mut report = ResearchReport {
+            topic: "How to edit cinematic B-roll".to_string(),
+            created_at: now,
+            items: vec![
+                item(&["Cut on motion", "Shoot at 60fps for slow motion"]),
+                item(&["cut on motion", "Grade shadows cooler than highlights"]),
+            ],
+        };
+        assert_eq!(report.techniques().len(), 3);
+        assert!(report.suffices(now));
+        assert!(ResearchReport::json_path(&report.topic).ends_with("how_to_edit_cinematic_b_roll.json"));
+        assert!(report.to_markdown().contains("- Grade shadows cooler than highlights"));
+
+        report.created_at = now - chrono::Duration::days(REPORT_FRESH_DAYS);
+        assert!(!report.suffices(now));
+        report.created_at = now;
+        report.items.pop();
+        assert!(!report.suffices(now));
+    }
+}
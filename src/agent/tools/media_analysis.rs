@@ -0,0 +1,334 @@
+// SYNOID Media Analysis - One decode for every scan
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Scene detection, the visual scan and the audio scan each decoded the whole
+// input on their own, so an edit read a large file three times. `analyze`
+// does it once: one ffprobe for the duration and streams, then one ffmpeg
+// run that scores scene changes on the first video stream and measures
+// EBU R128 loudness on the first audio stream side by side. Scene changes
+// are kept down to SCENE_FLOOR, so any detection threshold at or above it
+// can be answered from the same analysis; `scan_cache::analysis` stores it
+// per input.
+
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::production_tools::safe_arg_path;
+use crate::agent::specialized::smart_editor::{self, Scene};
+use crate::agent::tools::audio_tools::{self, AudioAnalysis};
+use crate::agent::tools::vision_tools::{self, VisualScene};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::info;
+
+type AnalysisResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// Lowest scene-change score recorded. Thresholds below it need a
+/// dedicated `detect_scenes` pass.
+pub const SCENE_FLOOR: f64 = 0.1;
+
+/// One stream as ffprobe reports it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamInfo {
+    pub index: usize,
+    /// `video`, `audio`, `subtitle`, ...
+    pub codec_type: String,
+    pub codec_name: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u32>,
+}
+
+/// A frame whose scene-change score passed SCENE_FLOOR.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SceneCut {
+    pub time: f64,
+    pub score: f64,
+}
+
+/// EBU R128 loudness of the first audio stream.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AudioStats {
+    pub integrated_lufs: f64,
+    pub loudness_range_lu: Option<f64>,
+}
+
+/// Everything the scans need to know about one input.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MediaAnalysis {
+    pub duration: f64,
+    pub streams: Vec<StreamInfo>,
+    pub scene_cuts: Vec<SceneCut>,
+    /// `None` without an audio stream, or when ebur128 printed no summary.
+    pub audio: Option<AudioStats>,
+}
+
+impl MediaAnalysis {
+    /// Probe and decode `input` once.
+    pub async fn analyze(input: &Path) -> AnalysisResult<Self> {
+        info!("[ANALYZE] Analyzing {:?} in one pass", input);
+        let safe_path = safe_arg_path(input);
+
+        let probe = proc::ffprobe()
+            .stealth()
+            .args([
+                "-v",
+                "error",
+                "-show_entries",
+                "format=duration:stream=index,codec_type,codec_name,width,height,sample_rate,channels",
+                "-of",
+                "json",
+            ])
+            .arg(&safe_path)
+            .output()
+            .await?;
+        if !probe.status.success() {
+            return Err(format!(
+                "ffprobe could not read {:?}: {}",
+                input,
+                String::from_utf8_lossy(&probe.stderr).trim()
+            )
+            .into());
+        }
+        let (duration, streams) = parse_probe(&String::from_utf8_lossy(&probe.stdout))?;
+
+        let has = |kind: &str| streams.iter().any(|s| s.codec_type == kind);
+        let has_audio = has("audio");
+        let mut graph = Vec::new();
+        if has("video") {
+            graph.push(format!(
+                "[0:v:0]select='gt(scene,{})',metadata=print:file=-[v]",
+                SCENE_FLOOR
+            ));
+        }
+        // Per-frame lines at verbose keep stderr to the summary
+        if has_audio {
+            graph.push("[0:a:0]ebur128=framelog=verbose[a]".to_string());
+        }
+        if graph.is_empty() {
+            return Err(format!("{:?} has no video or audio stream", input).into());
+        }
+
+        let mut decode = proc::ffmpeg(ProcClass::Thumbnail);
+        decode
+            .stealth()
+            .args(["-hide_banner", "-nostats", "-hwaccel", "auto", "-i"])
+            .arg(&safe_path)
+            .arg("-filter_complex")
+            .arg(graph.join(";"));
+        for label in ["[v]", "[a]"] {
+            if graph.iter().any(|chain| chain.ends_with(label)) {
+                decode.args(["-map", label]);
+            }
+        }
+        decode.args(["-f", "null", "-"]);
+
+        let timeout_secs = smart_editor::scan_timeout_secs(duration);
+        let output = match tokio::time::timeout(
+            std::time::Duration::from_secs(timeout_secs),
+            decode.output(),
+        )
+        .await
+        {
+            Ok(res) => res?,
+            Err(_) => {
+                return Err(format!(
+                    "Media analysis timed out after {} minutes (input is {:.0}s)",
+                    timeout_secs / 60,
+                    duration
+                )
+                .into())
+            }
+        };
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
+            return Err(format!("FFmpeg analysis failed: {}", stderr.trim()).into());
+        }
+
+        let analysis = Self {
+            duration,
+            streams,
+            scene_cuts: vision_tools::parse_scene_scores(&String::from_utf8_lossy(&output.stdout))
+                .into_iter()
+                .map(|(time, score)| SceneCut { time, score })
+                .collect(),
+            audio: has_audio.then(|| parse_audio_stats(&stderr)).flatten(),
+        };
+        info!(
+            "[ANALYZE] {:.1}s, {} stream(s), {} scene change(s) above {}",
+            analysis.duration,
+            analysis.streams.len(),
+            analysis.scene_cuts.len(),
+            SCENE_FLOOR
+        );
+        Ok(analysis)
+    }
+
+    pub fn has_video(&self) -> bool {
+        self.streams.iter().any(|s| s.codec_type == "video")
+    }
+
+    pub fn has_audio(&self) -> bool {
+        self.streams.iter().any(|s| s.codec_type == "audio")
+    }
+
+    /// Scene changes scoring above `threshold` (at least SCENE_FLOOR).
+    fn cuts_above(&self, threshold: f64) -> impl Iterator<Item = &SceneCut> {
+        self.scene_cuts.iter().filter(move |c| c.score > threshold)
+    }
+
+    /// What `detect_scenes(input, threshold)` returns, for `threshold` at or
+    /// above SCENE_FLOOR.
+    pub fn scenes(&self, threshold: f64) -> Vec<Scene> {
+        let cuts: Vec<f64> = self.cuts_above(threshold).map(|c| c.time).collect();
+        smart_editor::scenes_from_cuts(&cuts, self.duration)
+    }
+
+    /// What `scan_visual` returns.
+    pub fn visual_scenes(&self) -> Vec<VisualScene> {
+        let cuts: Vec<(f64, f64)> = self
+            .cuts_above(vision_tools::VISUAL_SCENE_THRESHOLD)
+            .map(|c| (c.time, c.score))
+            .collect();
+        vision_tools::visual_scenes_from_cuts(&cuts)
+    }
+
+    /// What `scan_audio` returns.
+    pub fn audio_analysis(&self) -> AudioAnalysis {
+        AudioAnalysis {
+            duration: self.duration,
+            average_loudness: self.audio.map_or(-14.0, |a| a.integrated_lufs),
+            transients: Vec::new(),
+        }
+    }
+}
+
+/// Duration and streams from `ffprobe -of json` output.
+fn parse_probe(json: &str) -> AnalysisResult<(f64, Vec<StreamInfo>)> {
+    let probe: serde_json::Value = serde_json::from_str(json)?;
+    // ffprobe prints numbers it reads from the container as strings
+    let number = |v: &serde_json::Value| {
+        v.as_f64()
+            .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+    };
+    let duration = number(&probe["format"]["duration"]).unwrap_or(0.0);
+    if duration <= 0.0 {
+        return Err("Could not determine the input's duration".into());
+    }
+
+    let streams = probe["streams"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|s| {
+            let text = |key: &str| s[key].as_str().unwrap_or_default().to_string();
+            let count = |key: &str| number(&s[key]).map(|n| n as u32);
+            StreamInfo {
+                index: s["index"].as_u64().unwrap_or_default() as usize,
+                codec_type: text("codec_type"),
+                codec_name: text("codec_name"),
+                width: count("width"),
+                height: count("height"),
+                sample_rate: count("sample_rate"),
+                channels: count("channels"),
+            }
+        })
+        .collect();
+    Ok((duration, streams))
+}
+
+fn parse_audio_stats(stderr: &str) -> Option<AudioStats> {
+    Some(AudioStats {
+        integrated_lufs: audio_tools::ebur128_summary_value(stderr, "I:", "LUFS")?,
+        loudness_range_lu: audio_tools::ebur128_summary_value(stderr, "LRA:", "LU"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROBE: &str = r#"{
+        "streams": [
+            {"index": 0, "codec_name": "h264", "codec_type": "video", "width": 1920, "height": 1080},
+            {"index": 1, "codec_name": "aac", "codec_type": "audio", "sample_rate": "48000", "channels": 2}
+        ],
+        "format": {"duration": "12.500000"}
+    }"#;
+
+    /// Trimmed output of the combined pass on a clip with two hard cuts.
+    const SCENE_METADATA: &str = "frame:0    pts:61440   pts_time:4.8\n\
+        lavfi.scene_score=0.912000\n\
+        frame:1    pts:62464   pts_time:4.88\n\
+        lavfi.scene_score=0.150000\n\
+        frame:2    pts:104448  pts_time:8.16\n\
+        lavfi.scene_score=0.270000\n\
+        frame:3    pts:110080  pts_time:8.6\n\
+        lavfi.scene_score=0.640000\n";
+
+    const EBUR128: &str = "[Parsed_ebur128_1 @ 0x5633] Summary:\n\
+        \n  Integrated loudness:\n    I:         -19.4 LUFS\n    Threshold: -29.6 LUFS\n\
+        \n  Loudness range:\n    LRA:         6.2 LU\n    Threshold: -39.8 LUFS\n";
+
+    fn analysis() -> MediaAnalysis {
+        let (duration, streams) = parse_probe(PROBE).unwrap();
+        MediaAnalysis {
+            duration,
+            streams,
+            scene_cuts: vision_tools::parse_scene_scores(SCENE_METADATA)
+                .into_iter()
+                .map(|(time, score)| SceneCut { time, score })
+                .collect(),
+            audio: parse_audio_stats(EBUR128),
+        }
+    }
+
+    #[test]
+    fn one_pass_fills_every_part_of_the_analysis() {
+        let analysis = analysis();
+        assert_eq!(analysis.duration, 12.5);
+        assert!(analysis.has_video() && analysis.has_audio());
+        assert_eq!(analysis.streams[0].width, Some(1920));
+        assert_eq!(analysis.streams[1].sample_rate, Some(48000));
+        assert_eq!(analysis.streams[1].channels, Some(2));
+        assert_eq!(analysis.scene_cuts.len(), 4);
+        assert_eq!(
+            analysis.audio,
+            Some(AudioStats {
+                integrated_lufs: -19.4,
+                loudness_range_lu: Some(6.2),
+            })
+        );
+
+        assert!(parse_probe(r#"{"streams": [], "format": {}}"#).is_err());
+        assert_eq!(parse_audio_stats("no summary here"), None);
+    }
+
+    #[test]
+    fn each_scan_reads_its_own_threshold_from_the_shared_cuts() {
+        let analysis = analysis();
+
+        // detect_scenes at the smart editor's 0.25: cuts at 4.8, 8.16 and 8.6,
+        // but 8.16..8.6 is a sliver and dropped
+        let times: Vec<(f64, f64)> = analysis
+            .scenes(0.25)
+            .iter()
+            .map(|s| (s.start_time, s.end_time))
+            .collect();
+        assert_eq!(times, vec![(0.0, 4.8), (4.8, 8.16), (8.6, 12.5)]);
+        // A stricter threshold sees fewer cuts
+        assert_eq!(analysis.scenes(0.7).len(), 2);
+
+        // scan_visual's 0.3: the start plus the two strong cuts
+        let visual: Vec<f64> = analysis
+            .visual_scenes()
+            .iter()
+            .map(|s| s.timestamp)
+            .collect();
+        assert_eq!(visual, vec![0.0, 4.8, 8.6]);
+
+        let audio = analysis.audio_analysis();
+        assert_eq!((audio.duration, audio.average_loudness), (12.5, -19.4));
+    }
+}
@@ -0,0 +1,280 @@
+// SYNOID Output Naming - Templated output paths
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Every command that writes a file without an explicit `--output` names it
+// through one template, so outputs land somewhere predictable with names that
+// say what made them. The `output_template` setting (SYNOID_OUTPUT_TEMPLATE)
+// sets the template; outputs land beside their input.
+//
+// With `outputs_dir` configured, outputs are filed by month and source
+// instead, `<outputs_dir>/<yyyy-mm>/<stem>/<op>_<n>.<ext>`, numbered so
+// nothing is overwritten; the template then doesn't apply.
+//
+// Tokens: {stem} input name without extension, {op} the operation ("clip",
+// "compressed", "edited", ...), {intent} a slug of the intent text, {date}
+// YYYY-MM-DD, {time} HHMMSS, {ext} the output extension. Unknown tokens are
+// left as written.
+
+use chrono::{DateTime, Local};
+use std::path::{Path, PathBuf};
+
+/// Reproduces the names outputs had before templates, e.g. `raw_clip.mp4`.
+pub const DEFAULT_TEMPLATE: &str = "{stem}_{op}.{ext}";
+
+/// Intent slugs are cut to this many characters.
+const MAX_INTENT_CHARS: usize = 40;
+
+/// What one output is named after.
+#[derive(Debug, Clone, Copy)]
+pub struct NameTokens<'a> {
+    pub stem: &'a str,
+    pub op: &'a str,
+    pub intent: Option<&'a str>,
+    pub ext: &'a str,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamingTemplate {
+    pub template: String,
+    /// Folder for outputs; `None` writes beside the input.
+    pub dir: Option<PathBuf>,
+    /// Root of the month/source layout; takes precedence over the other two.
+    pub outputs_dir: Option<PathBuf>,
+}
+
+impl Default for NamingTemplate {
+    fn default() -> Self {
+        Self {
+            template: DEFAULT_TEMPLATE.to_string(),
+            dir: None,
+            outputs_dir: None,
+        }
+    }
+}
+
+impl NamingTemplate {
+    /// The configured `output_template` and `outputs_dir`.
+    pub fn configured() -> Self {
+        let config = crate::config::current();
+        Self {
+            template: config
+                .output_template
+                .unwrap_or_else(|| DEFAULT_TEMPLATE.to_string()),
+            dir: None,
+            outputs_dir: config.outputs_dir.map(PathBuf::from),
+        }
+    }
+
+    /// The file name for `tokens` at time `now`. Token values are sanitized,
+    /// so the result never contains a path separator or `..`.
+    pub fn expand(&self, tokens: &NameTokens, now: DateTime<Local>) -> String {
+        let token_re = regex::Regex::new(r"\{([a-z]+)\}").unwrap();
+        let expanded = token_re.replace_all(&self.template, |caps: &regex::Captures| {
+            match &caps[1] {
+                "stem" => sanitize(tokens.stem),
+                "op" => sanitize(tokens.op),
+                "intent" => tokens.intent.map(intent_slug).unwrap_or_default(),
+                "date" => now.format("%Y-%m-%d").to_string(),
+                "time" => now.format("%H%M%S").to_string(),
+                "ext" => sanitize(tokens.ext.trim_start_matches('.')),
+                _ => sanitize(&caps[0]),
+            }
+        });
+        let name = tidy(&sanitize(&expanded));
+        if name.is_empty() || name.starts_with('.') {
+            format!("output{}", name)
+        } else {
+            name
+        }
+    }
+
+    /// Where an output of `input` should go: the next free name in its
+    /// `outputs_dir` folder (created if need be), else the template's name in
+    /// the configured folder or beside `input`.
+    pub fn output_for(&self, input: &Path, op: &str, intent: Option<&str>, ext: &str) -> PathBuf {
+        if let Some(root) = &self.outputs_dir {
+            let dir = organized_dir(root, input, Local::now());
+            if !dir.is_dir() {
+                match std::fs::create_dir_all(&dir) {
+                    Ok(()) => tracing::info!("[OUTPUT] 📁 Created output folder {:?}", dir),
+                    Err(e) => tracing::warn!("[OUTPUT] Cannot create {:?}: {}", dir, e),
+                }
+            }
+            return next_free(&dir, op, ext);
+        }
+        let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+        let name = self.expand(
+            &NameTokens {
+                stem: &stem,
+                op,
+                intent,
+                ext,
+            },
+            Local::now(),
+        );
+        let dir = self
+            .dir
+            .clone()
+            .unwrap_or_else(|| input.parent().map(Path::to_path_buf).unwrap_or_default());
+        dir.join(name)
+    }
+}
+
+/// `output_for` with the configured template.
+pub fn default_output(input: &Path, op: &str, intent: Option<&str>, ext: &str) -> PathBuf {
+    NamingTemplate::configured().output_for(input, op, intent, ext)
+}
+
+/// The folder outputs of `input` made at `now` are filed in under `root`:
+/// `<root>/<yyyy-mm>/<stem>`.
+pub fn organized_dir(root: &Path, input: &Path, now: DateTime<Local>) -> PathBuf {
+    let stem = tidy(&sanitize(&input.file_stem().unwrap_or_default().to_string_lossy()));
+    let stem = if stem.is_empty() || stem.starts_with('.') {
+        format!("output{}", stem)
+    } else {
+        stem
+    };
+    root.join(now.format("%Y-%m").to_string()).join(stem)
+}
+
+/// `<op>_<n>.<ext>` in `dir` for the lowest `n` from 1 not yet taken.
+fn next_free(dir: &Path, op: &str, ext: &str) -> PathBuf {
+    let op = match tidy(&sanitize(op)) {
+        op if op.is_empty() => "output".to_string(),
+        op => op,
+    };
+    let ext = sanitize(ext.trim_start_matches('.'));
+    (1u32..)
+        .map(|n| dir.join(format!("{}_{}.{}", op, n, ext)))
+        .find(|path| !path.exists())
+        .expect("some number is free")
+}
+
+/// Drop characters no file system accepts in a name, and path separators.
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_control())
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect::<String>()
+        .replace("..", "_")
+}
+
+/// Intent text as a short lowercase slug: "Remove the boring parts!" →
+/// "remove_the_boring_parts".
+fn intent_slug(intent: &str) -> String {
+    let slug = intent
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    slug.chars()
+        .take(MAX_INTENT_CHARS)
+        .collect::<String>()
+        .trim_end_matches('_')
+        .to_string()
+}
+
+/// Collapse the doubled separators an empty token leaves behind and trim
+/// them from the ends of the name.
+fn tidy(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.trim().chars() {
+        let sep = matches!(c, '_' | '-' | ' ');
+        if sep && out.ends_with(['_', '-', ' ']) {
+            continue;
+        }
+        if c == '.' && out.ends_with(['_', '-', ' ']) {
+            out.pop();
+        }
+        out.push(c);
+    }
+    out.trim_matches(['_', '-', ' ']).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at_noon() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 3, 14, 12, 5, 9).unwrap()
+    }
+
+    #[test]
+    fn template_expands_tokens_and_sanitizes_them() {
+        let template = NamingTemplate {
+            template: "{stem}_{intent}_{date}_{time}.{ext}".to_string(),
+            dir: None,
+            outputs_dir: None,
+        };
+        let tokens = NameTokens {
+            stem: "vlog",
+            op: "edited",
+            intent: Some("Cut the boring parts / keep ../../etc: \"jokes\"!"),
+            ext: "mp4",
+        };
+        assert_eq!(
+            template.expand(&tokens, at_noon()),
+            "vlog_cut_the_boring_parts_keep_etc_jokes_2026-03-14_120509.mp4"
+        );
+
+        let default = NamingTemplate::default();
+        let clip = NameTokens {
+            stem: "a/b\\c:d",
+            op: "clip",
+            intent: None,
+            ext: ".mp4",
+        };
+        assert_eq!(default.expand(&clip, at_noon()), "a_b_c_d_clip.mp4");
+
+        // An absent intent leaves no stray separator, and unknown tokens stay put
+        let no_intent = NamingTemplate {
+            template: "{stem}-{intent}-{op}{nope}.{ext}".to_string(),
+            dir: Some(PathBuf::from("renders")),
+            outputs_dir: None,
+        };
+        assert_eq!(no_intent.expand(&clip, at_noon()), "a_b_c_d-clip{nope}.mp4");
+        assert_eq!(
+            no_intent.output_for(Path::new("in/raw.mov"), "clip", None, "mp4"),
+            PathBuf::from("renders/raw-clip{nope}.mp4")
+        );
+        assert_eq!(
+            default.output_for(Path::new("in/raw.mov"), "compressed", None, "mp4"),
+            PathBuf::from("in/raw_compressed.mp4")
+        );
+    }
+
+    #[test]
+    fn outputs_dir_files_outputs_by_month_and_source() {
+        let root = std::env::temp_dir().join(format!("synoid_outputs_{}", std::process::id()));
+        let input = Path::new("/downloads/read-only/My Vlog: day 1.mov");
+        let dir = organized_dir(&root, input, at_noon());
+        assert_eq!(dir, root.join("2026-03").join("My Vlog_day 1"));
+
+        let template = NamingTemplate {
+            // Ignored while outputs_dir is set
+            dir: Some(PathBuf::from("renders")),
+            outputs_dir: Some(root.clone()),
+            ..Default::default()
+        };
+        let month = Local::now().format("%Y-%m").to_string();
+        let first = template.output_for(input, "edited", Some("cut it"), "mp4");
+        assert_eq!(first, root.join(&month).join("My Vlog_day 1").join("edited_1.mp4"));
+        assert!(first.parent().unwrap().is_dir());
+
+        // Existing outputs are never overwritten
+        std::fs::write(&first, b"").unwrap();
+        let second = template.output_for(input, "edited", None, ".mp4");
+        assert_eq!(second.file_name().unwrap(), "edited_2.mp4");
+        assert_eq!(
+            template.output_for(input, "clip", None, "mp4").file_name().unwrap(),
+            "clip_1.mp4"
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,383 @@
+// SYNOID Platform Presets - Export-to-Social
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// One preset per destination platform bundling resolution/aspect, loudness
+// target, duration cap, codec and bitrate, so `Publish` can produce an
+// upload-ready file without the user memorising each platform's specs.
+//
+// Platforms with an upload size cap (Discord, Twitter) get their bitrate cut
+// to fit, and step down the same resolution ladder as `compress` when that
+// bitrate is too thin for the frame.
+
+use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::tools::production_tools::{
+    safe_arg_path, scaled_to_short_side, ProductionResult, ResolutionLadder,
+};
+use crate::agent::tools::source_tools::get_video_duration;
+use std::path::Path;
+use tracing::{info, warn};
+
+/// Supported publishing destinations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    YouTubeShorts,
+    TikTok,
+    InstagramReels,
+    YouTube,
+    Discord,
+    Twitter,
+}
+
+impl Platform {
+    /// Parse a CLI name ("shorts", "tiktok", "reels", "youtube", ...).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().replace(['-', '_', ' '], "").as_str() {
+            "shorts" | "youtubeshorts" | "ytshorts" => Some(Self::YouTubeShorts),
+            "tiktok" | "tt" => Some(Self::TikTok),
+            "reels" | "instagram" | "instagramreels" | "ig" => Some(Self::InstagramReels),
+            "youtube" | "yt" | "youtubefull" => Some(Self::YouTube),
+            "discord" => Some(Self::Discord),
+            "twitter" | "x" => Some(Self::Twitter),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::YouTubeShorts => "YouTube Shorts",
+            Self::TikTok => "TikTok",
+            Self::InstagramReels => "Instagram Reels",
+            Self::YouTube => "YouTube",
+            Self::Discord => "Discord",
+            Self::Twitter => "Twitter",
+        }
+    }
+
+    /// Export specs for this platform.
+    pub fn preset(&self) -> PlatformPreset {
+        match self {
+            Self::YouTubeShorts => PlatformPreset {
+                platform: *self,
+                width: 1080,
+                height: 1920,
+                fps: 30,
+                loudness_lufs: -14.0,
+                true_peak_db: -1.0,
+                max_duration_secs: Some(60.0),
+                video_codec: "libx264",
+                video_bitrate_kbps: 10_000,
+                audio_bitrate_kbps: 192,
+                max_size_mb: None,
+            },
+            Self::TikTok => PlatformPreset {
+                platform: *self,
+                width: 1080,
+                height: 1920,
+                fps: 30,
+                loudness_lufs: -14.0,
+                true_peak_db: -1.0,
+                max_duration_secs: Some(600.0),
+                video_codec: "libx264",
+                video_bitrate_kbps: 8_000,
+                audio_bitrate_kbps: 128,
+                max_size_mb: None,
+            },
+            Self::InstagramReels => PlatformPreset {
+                platform: *self,
+                width: 1080,
+                height: 1920,
+                fps: 30,
+                loudness_lufs: -14.0,
+                true_peak_db: -1.0,
+                max_duration_secs: Some(90.0),
+                video_codec: "libx264",
+                video_bitrate_kbps: 5_000,
+                audio_bitrate_kbps: 128,
+                max_size_mb: None,
+            },
+            Self::YouTube => PlatformPreset {
+                platform: *self,
+                width: 1920,
+                height: 1080,
+                fps: 30,
+                loudness_lufs: -14.0,
+                true_peak_db: -1.0,
+                max_duration_secs: None,
+                video_codec: "libx264",
+                video_bitrate_kbps: 12_000,
+                audio_bitrate_kbps: 384,
+                max_size_mb: None,
+            },
+            Self::Discord => PlatformPreset {
+                platform: *self,
+                width: 1920,
+                height: 1080,
+                fps: 30,
+                loudness_lufs: -14.0,
+                true_peak_db: -1.0,
+                max_duration_secs: None,
+                video_codec: "libx264",
+                video_bitrate_kbps: 6_000,
+                audio_bitrate_kbps: 96,
+                max_size_mb: Some(10.0),
+            },
+            Self::Twitter => PlatformPreset {
+                platform: *self,
+                width: 1920,
+                height: 1080,
+                fps: 30,
+                loudness_lufs: -14.0,
+                true_peak_db: -1.0,
+                max_duration_secs: Some(140.0),
+                video_codec: "libx264",
+                video_bitrate_kbps: 8_000,
+                audio_bitrate_kbps: 128,
+                max_size_mb: Some(512.0),
+            },
+        }
+    }
+}
+
+/// Resolution, loudness and encoding targets for one platform.
+#[derive(Debug, Clone)]
+pub struct PlatformPreset {
+    pub platform: Platform,
+    pub width: u32,
+    pub height: u32,
+    pub fps: u32,
+    /// Integrated loudness target (LUFS).
+    pub loudness_lufs: f64,
+    /// True-peak ceiling (dBTP).
+    pub true_peak_db: f64,
+    /// Longest upload the platform accepts (None = effectively unlimited).
+    pub max_duration_secs: Option<f64>,
+    pub video_codec: &'static str,
+    pub video_bitrate_kbps: u32,
+    pub audio_bitrate_kbps: u32,
+    /// Largest upload the platform accepts, in MB (None = no practical cap).
+    pub max_size_mb: Option<f64>,
+}
+
+impl PlatformPreset {
+    /// Reduced aspect ratio, e.g. (9, 16).
+    pub fn aspect(&self) -> (u32, u32) {
+        fn gcd(a: u32, b: u32) -> u32 {
+            if b == 0 {
+                a
+            } else {
+                gcd(b, a % b)
+            }
+        }
+        let g = gcd(self.width, self.height).max(1);
+        (self.width / g, self.height / g)
+    }
+
+    /// Reframe filter: scale to cover the target frame, then centre-crop.
+    pub fn reframe_filter(&self) -> String {
+        format!(
+            "scale={w}:{h}:force_original_aspect_ratio=increase,crop={w}:{h},setsar=1,fps={fps}",
+            w = self.width,
+            h = self.height,
+            fps = self.fps
+        )
+    }
+
+    /// Cut the video bitrate so `duration_secs` fits under `max_size_mb`
+    /// (keeping 5% for container overhead), then step the frame down
+    /// `ladder` if that bitrate is too thin for it. Returns the new frame
+    /// size when it changed.
+    pub fn fit_to_size(&mut self, duration_secs: f64, ladder: ResolutionLadder) -> Option<(u32, u32)> {
+        let max_mb = self.max_size_mb?;
+        if duration_secs <= 0.0 {
+            return None;
+        }
+        let total_kbps = max_mb * 8192.0 * 0.95 / duration_secs;
+        let video_kbps = (total_kbps - self.audio_bitrate_kbps as f64).max(100.0);
+        self.video_bitrate_kbps = self.video_bitrate_kbps.min(video_kbps as u32);
+
+        let short = ladder.choose(
+            self.width,
+            self.height,
+            self.fps as f64,
+            self.video_bitrate_kbps as f64,
+        )?;
+        let (width, height) = scaled_to_short_side(self.width, self.height, short);
+        self.width = width;
+        self.height = height;
+        Some((width, height))
+    }
+
+    pub fn loudness_filter(&self) -> String {
+        format!(
+            "loudnorm=I={:.1}:TP={:.1}:LRA=11",
+            self.loudness_lufs, self.true_peak_db
+        )
+    }
+}
+
+/// Render `input` to `output` using `platform`'s preset.
+///
+/// Sources longer than the platform's cap are first cut down with a
+/// highlights smart edit; anything still over the cap is hard-trimmed.
+/// Platforms with a size cap may step the frame down `ladder`.
+pub async fn publish(
+    input: &Path,
+    platform: Platform,
+    output: &Path,
+    ladder: ResolutionLadder,
+) -> Result<ProductionResult, Box<dyn std::error::Error + Send + Sync>> {
+    let mut preset = platform.preset();
+    info!(
+        "[PUBLISH] {:?} → {} ({}x{}, {:.0} LUFS)",
+        input,
+        platform.label(),
+        preset.width,
+        preset.height,
+        preset.loudness_lufs
+    );
+
+    let duration = get_video_duration(input).await.unwrap_or(0.0);
+    let work_dir = output.parent().unwrap_or(Path::new(".")).to_path_buf();
+    let highlight_path = work_dir.join(format!(
+        ".synoid_publish_{}.mp4",
+        output
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default()
+    ));
+
+    let mut source = input.to_path_buf();
+    if let Some(max) = preset.max_duration_secs {
+        if duration > max {
+            warn!(
+                "[PUBLISH] Source is {:.0}s but {} allows {:.0}s — building a highlight reel first.",
+                duration,
+                platform.label(),
+                max
+            );
+            let intent = format!(
+                "short highlights, ruthless, keep only the best moments, under {} seconds",
+                max as u64
+            );
+            match crate::agent::smart_editor::smart_edit(
+                input,
+                &intent,
+                &highlight_path,
+                false,
+                None,
+                None,
+                None,
+                None,
+                None,
+                false,
+                true,
+            )
+            .await
+            {
+                Ok(_) if highlight_path.exists() => source = highlight_path.clone(),
+                Ok(_) => warn!("[PUBLISH] Highlight reel produced no file; trimming instead."),
+                Err(e) => warn!("[PUBLISH] Highlight reel failed ({}); trimming instead.", e),
+            }
+        }
+    }
+
+    if preset.max_size_mb.is_some() {
+        let mut length = get_video_duration(&source).await.unwrap_or(duration);
+        if let Some(max) = preset.max_duration_secs {
+            length = length.min(max);
+        }
+        let requested = (preset.width, preset.height);
+        match preset.fit_to_size(length, ladder) {
+            Some((w, h)) => info!(
+                "[PUBLISH] 📐 {} kbps is too thin for {}x{}; exporting at {}x{}",
+                preset.video_bitrate_kbps, requested.0, requested.1, w, h
+            ),
+            None => info!(
+                "[PUBLISH] 📐 Exporting at {}x{}, {} kbps",
+                preset.width, preset.height, preset.video_bitrate_kbps
+            ),
+        }
+    }
+
+    let mut cmd = proc::ffmpeg(ProcClass::Render);
+    cmd.stealth()
+        .args(["-y", "-nostdin", "-i"])
+        .arg(safe_arg_path(&source))
+        .args(["-vf", &preset.reframe_filter()])
+        .args(["-af", &preset.loudness_filter()]);
+    if let Some(max) = preset.max_duration_secs {
+        cmd.args(["-t", &format!("{:.3}", max)]);
+    }
+    cmd.args([
+        "-c:v",
+        preset.video_codec,
+        "-preset",
+        "medium",
+        "-b:v",
+        &format!("{}k", preset.video_bitrate_kbps),
+        "-maxrate",
+        &format!("{}k", preset.video_bitrate_kbps * 3 / 2),
+        "-bufsize",
+        &format!("{}k", preset.video_bitrate_kbps * 2),
+        "-pix_fmt",
+        "yuv420p",
+        "-c:a",
+        "aac",
+        "-b:a",
+        &format!("{}k", preset.audio_bitrate_kbps),
+        "-ar",
+        "48000",
+        "-movflags",
+        "+faststart",
+    ])
+    .arg(safe_arg_path(output));
+
+    let status = cmd.status().await;
+    if source != input {
+        let _ = std::fs::remove_file(&highlight_path);
+    }
+    if !status?.success() {
+        return Err(format!("FFmpeg export for {} failed", platform.label()).into());
+    }
+
+    let size_mb = tokio::fs::metadata(output).await?.len() as f64 / 1_048_576.0;
+    info!("[PUBLISH] Ready for {}: {:?} ({:.2} MB)", platform.label(), output, size_mb);
+
+    Ok(ProductionResult {
+        output_path: output.to_path_buf(),
+        size_mb,
+        duration: get_video_duration(output).await.unwrap_or(0.0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tiktok_preset_is_vertical_with_streaming_loudness() {
+        let preset = Platform::parse("TikTok").unwrap().preset();
+        assert_eq!(preset.aspect(), (9, 16));
+        assert_eq!(preset.loudness_lufs, -14.0);
+        assert!(preset.loudness_filter().starts_with("loudnorm=I=-14.0"));
+        assert_eq!(Platform::parse("youtube").unwrap().preset().aspect(), (16, 9));
+    }
+
+    #[test]
+    fn test_discord_preset_fits_its_size_cap_and_steps_down() {
+        let mut short_clip = Platform::Discord.preset();
+        assert_eq!(short_clip.fit_to_size(10.0, ResolutionLadder::default()), None);
+        assert_eq!(short_clip.video_bitrate_kbps, 6_000);
+
+        // Two minutes in 10 MB leaves ~550 kbps, too thin for 1080p or 720p
+        let mut long_clip = Platform::Discord.preset();
+        assert_eq!(long_clip.fit_to_size(120.0, ResolutionLadder::default()), Some((854, 480)));
+        assert!(long_clip.video_bitrate_kbps < 600);
+        assert!(long_clip.reframe_filter().starts_with("scale=854:480"));
+
+        let mut kept = Platform::Discord.preset();
+        assert_eq!(kept.fit_to_size(120.0, ResolutionLadder::disabled()), None);
+        assert_eq!((kept.width, kept.height), (1920, 1080));
+    }
+}
@@ -0,0 +1,352 @@
+// SYNOID Post-Render Actions - Clipboard and file manager hand-off
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// What happens once a render is written: copy its path, copy a summary
+// ready to paste into an upload description (length, size, chapters), or
+// show it in the file manager. `after_render` / SYNOID_AFTER_RENDER sets the
+// default list, `--then` overrides it per run, and the GUI's completion toast
+// offers each as a button. Without a display or clipboard the actions are
+// skipped with a note rather than failing the render.
+
+use crate::agent::engines::proc;
+use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::specialized::smart_editor::chapters::{self, Chapter};
+use crate::agent::tools::{source_tools, timecode};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PostRenderAction {
+    /// Show the output in the platform's file manager.
+    Reveal,
+    /// Put the output's full path on the clipboard.
+    CopyPath,
+    /// Put a paste-ready description (length, size, chapters) on the clipboard.
+    CopySummary,
+}
+
+impl PostRenderAction {
+    pub const ALL: [PostRenderAction; 3] = [Self::CopyPath, Self::Reveal, Self::CopySummary];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Reveal => "reveal",
+            Self::CopyPath => "copy-path",
+            Self::CopySummary => "copy-summary",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Reveal => "📂 Reveal",
+            Self::CopyPath => "📋 Copy Path",
+            Self::CopySummary => "📝 Copy Summary",
+        }
+    }
+}
+
+impl std::str::FromStr for PostRenderAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "reveal" | "open" => Ok(Self::Reveal),
+            "copy-path" | "path" => Ok(Self::CopyPath),
+            "copy-summary" | "summary" => Ok(Self::CopySummary),
+            other => Err(format!(
+                "Unknown post-render action '{}' (expected reveal, copy-path or copy-summary)",
+                other
+            )),
+        }
+    }
+}
+
+/// Actions from a comma-separated list such as `reveal,copy-path`. Repeats
+/// are dropped; an empty list (or `none`) means no actions.
+pub fn parse_list(text: &str) -> Result<Vec<PostRenderAction>, String> {
+    let mut actions = Vec::new();
+    for part in text.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        if part.eq_ignore_ascii_case("none") {
+            continue;
+        }
+        let action = part.parse()?;
+        if !actions.contains(&action) {
+            actions.push(action);
+        }
+    }
+    Ok(actions)
+}
+
+/// How one action went.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionOutcome {
+    Done(String),
+    /// Nothing to act on here (no display, no clipboard); not an error.
+    Skipped(String),
+    Failed(String),
+}
+
+impl ActionOutcome {
+    /// One line for logs and the GUI.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Done(what) => format!("✅ {}", what),
+            Self::Skipped(why) => format!("⏭️ {}", why),
+            Self::Failed(why) => format!("❌ {}", why),
+        }
+    }
+}
+
+/// Whether a desktop session is there to show windows and own a clipboard.
+pub fn has_display() -> bool {
+    if cfg!(any(target_os = "windows", target_os = "macos")) {
+        return true;
+    }
+    ["DISPLAY", "WAYLAND_DISPLAY"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some_and(|v| !v.is_empty()))
+}
+
+/// What the upload description needs to know about a finished render.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderSummary {
+    pub path: PathBuf,
+    pub duration_secs: Option<f64>,
+    pub size_bytes: Option<u64>,
+    pub chapters: Vec<Chapter>,
+}
+
+impl RenderSummary {
+    /// Probe `output` for its length and chapters. Anything that cannot be
+    /// read is left out of the summary.
+    pub async fn collect(output: &Path) -> Self {
+        Self {
+            path: output.to_path_buf(),
+            duration_secs: source_tools::get_video_duration(output)
+                .await
+                .ok()
+                .filter(|d| *d > 0.0),
+            size_bytes: std::fs::metadata(output).ok().map(|m| m.len()),
+            chapters: chapters::read(output).await.unwrap_or_default(),
+        }
+    }
+
+    /// File name, length and size, then chapters as `m:ss Title` lines,
+    /// which YouTube turns into chapter markers. The first chapter is
+    /// pinned to 0:00 as YouTube requires.
+    pub fn text(&self) -> String {
+        let name = self
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.display().to_string());
+        let mut facts = Vec::new();
+        if let Some(secs) = self.duration_secs {
+            facts.push(format!("Length: {}", timecode::format(secs.round())));
+        }
+        if let Some(bytes) = self.size_bytes {
+            facts.push(format!("Size: {:.1} MB", bytes as f64 / 1_048_576.0));
+        }
+
+        let mut lines = vec![name];
+        if !facts.is_empty() {
+            lines.push(facts.join(" · "));
+        }
+        if !self.chapters.is_empty() {
+            lines.push(String::new());
+            lines.push("Chapters:".to_string());
+            for (i, chapter) in self.chapters.iter().enumerate() {
+                let start = if i == 0 { 0.0 } else { chapter.start.floor() };
+                lines.push(format!("{} {}", timecode::format(start), chapter.title));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// Run `actions` for `output` in order. `from_cli` marks a process that exits
+/// right after: on Linux a clipboard empties when its owner exits, so the
+/// text is handed to wl-copy or xclip when one is installed.
+pub async fn run(
+    actions: &[PostRenderAction],
+    output: &Path,
+    from_cli: bool,
+) -> Vec<ActionOutcome> {
+    let mut outcomes = Vec::new();
+    for action in actions {
+        let outcome = match action {
+            PostRenderAction::Reveal => reveal(output).await,
+            PostRenderAction::CopyPath => {
+                let path = std::fs::canonicalize(output).unwrap_or_else(|_| output.to_path_buf());
+                copy_text(&path.to_string_lossy(), "output path", from_cli).await
+            }
+            // No point probing the output for a clipboard that is not there
+            PostRenderAction::CopySummary if !has_display() => {
+                copy_text("", "render summary", from_cli).await
+            }
+            PostRenderAction::CopySummary => {
+                let summary = RenderSummary::collect(output).await;
+                copy_text(&summary.text(), "render summary", from_cli).await
+            }
+        };
+        outcomes.push(outcome);
+    }
+    outcomes
+}
+
+/// Put `text` on the clipboard; `what` names it in the outcome.
+pub async fn copy_text(text: &str, what: &str, from_cli: bool) -> ActionOutcome {
+    if !has_display() {
+        return ActionOutcome::Skipped(format!("No display, so the {} was not copied", what));
+    }
+    if from_cli && cfg!(target_os = "linux") {
+        for (program, args) in [
+            ("wl-copy", &[][..]),
+            ("xclip", &["-selection", "clipboard"][..]),
+        ] {
+            if pipe_to(program, args, text).await {
+                return ActionOutcome::Done(format!("Copied the {} to the clipboard", what));
+            }
+        }
+    }
+    let copied = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text));
+    match copied {
+        Ok(()) if from_cli && cfg!(target_os = "linux") => ActionOutcome::Done(format!(
+            "Copied the {} (install wl-clipboard or xclip to keep it after SYNOID exits)",
+            what
+        )),
+        Ok(()) => ActionOutcome::Done(format!("Copied the {} to the clipboard", what)),
+        Err(e) => ActionOutcome::Skipped(format!(
+            "No clipboard available ({}); the {} was not copied",
+            e, what
+        )),
+    }
+}
+
+/// Feed `text` to a clipboard helper; false when it is missing or fails.
+async fn pipe_to(program: &str, args: &[&str], text: &str) -> bool {
+    // Clipboard helpers fork to hold the selection, so they must not queue
+    // behind renders for a governor slot.
+    let mut cmd = proc::unmanaged(program);
+    cmd.stealth()
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    let Ok(mut child) = cmd.spawn() else {
+        return false;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        if stdin.write_all(text.as_bytes()).await.is_err() {
+            return false;
+        }
+    }
+    child.wait().await.is_ok_and(|s| s.success())
+}
+
+/// Show `path` selected in the file manager, or its folder where the file
+/// manager cannot select.
+pub async fn reveal(path: &Path) -> ActionOutcome {
+    if !has_display() {
+        return ActionOutcome::Skipped("No display, so the output was not revealed".to_string());
+    }
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let folder = path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    // The file manager outlives this run; it must not hold a governor slot.
+    let spawned = if cfg!(target_os = "windows") {
+        proc::unmanaged("explorer")
+            .arg(format!("/select,{}", path.display()))
+            .spawn()
+            .map(|_| ())
+    } else if cfg!(target_os = "macos") {
+        proc::unmanaged("open")
+            .arg("-R")
+            .arg(&path)
+            .spawn()
+            .map(|_| ())
+    } else {
+        open::that_detached(&folder)
+    };
+    match spawned {
+        Ok(()) => ActionOutcome::Done(format!("Revealed {}", path.display())),
+        Err(e) => ActionOutcome::Failed(format!("Could not open {}: {}", folder.display(), e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_lists_parse_from_flags_and_settings() {
+        assert_eq!(
+            parse_list("reveal, copy-path,reveal"),
+            Ok(vec![PostRenderAction::Reveal, PostRenderAction::CopyPath])
+        );
+        assert_eq!(
+            parse_list("copy_summary"),
+            Ok(vec![PostRenderAction::CopySummary])
+        );
+        assert_eq!(parse_list(""), Ok(vec![]));
+        assert_eq!(parse_list("none"), Ok(vec![]));
+        assert!(parse_list("reveal,upload").unwrap_err().contains("upload"));
+        for action in PostRenderAction::ALL {
+            assert_eq!(action.name().parse(), Ok(action));
+            let json = serde_json::to_string(&action).unwrap();
+            assert_eq!(json, format!("\"{}\"", action.name()));
+        }
+    }
+
+    #[test]
+    fn summaries_are_ready_to_paste_into_a_description() {
+        let chapter = |start: f64, title: &str| Chapter {
+            start,
+            end: start + 60.0,
+            title: title.to_string(),
+        };
+        let summary = RenderSummary {
+            path: PathBuf::from("/renders/vlog_edited.mp4"),
+            duration_secs: Some(754.4),
+            size_bytes: Some(50_541_363),
+            chapters: vec![
+                chapter(0.4, "Intro"),
+                chapter(83.7, "Setup"),
+                chapter(3725.0, "Outro"),
+            ],
+        };
+        assert_eq!(
+            summary.text(),
+            "vlog_edited.mp4\nLength: 12:34 · Size: 48.2 MB\n\nChapters:\n0:00 Intro\n1:23 Setup\n1:02:05 Outro"
+        );
+
+        let bare = RenderSummary {
+            path: PathBuf::from("clip.mp4"),
+            duration_secs: None,
+            size_bytes: None,
+            chapters: Vec::new(),
+        };
+        assert_eq!(bare.text(), "clip.mp4");
+    }
+
+    #[tokio::test]
+    async fn headless_systems_skip_instead_of_failing() {
+        if has_display() {
+            return;
+        }
+        let outcomes = run(&PostRenderAction::ALL, Path::new("missing.mp4"), true).await;
+        assert_eq!(outcomes.len(), 3);
+        assert!(
+            outcomes
+                .iter()
+                .all(|o| matches!(o, ActionOutcome::Skipped(_))),
+            "{:?}",
+            outcomes
+        );
+    }
+}
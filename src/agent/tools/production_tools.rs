@@ -5,9 +5,11 @@
 // intelligent compression to target file sizes.
 
 use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::tools::audio_tools::{self, AudioTrack};
 use crate::agent::tools::source_tools::get_video_duration;
+use crate::agent::tools::transcription::TranscriptSegment;
 use std::path::{Path, PathBuf};
-use tokio::process::Command;
 use tracing::{info, warn};
 
 /// Result of a production operation
@@ -56,7 +58,7 @@ pub async fn trim_video(
     let safe_input = safe_arg_path(input);
     let safe_output = safe_arg_path(output);
 
-    let status = Command::new("ffmpeg")
+    let status = proc::ffmpeg(ProcClass::Render)
         .stealth()
         .arg("-y")
         .arg("-ss")
@@ -106,7 +108,7 @@ pub async fn apply_anamorphic_mask(
     let safe_input = safe_arg_path(input);
     let safe_output = safe_arg_path(output);
 
-    let status = Command::new("ffmpeg")
+    let status = proc::ffmpeg(ProcClass::Render)
         .stealth()
         .arg("-y")
         .arg("-i")
@@ -121,12 +123,103 @@ pub async fn apply_anamorphic_mask(
     Ok(())
 }
 
-/// Compress video to target file size (in MB)
-/// Uses 2-pass encoding for precision if size is critical
+// ─────────────────────────────────────────────────────────────────────────────
+// Resolution Ladder
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Short-side heights tried, largest first, when the bitrate is too thin for
+/// the source resolution.
+pub const RESOLUTION_RUNGS: [u32; 4] = [1440, 1080, 720, 480];
+
+/// Below this many bits per pixel per frame, H.264 at normal settings turns
+/// to blocks; a smaller frame at the same bitrate looks better.
+pub const MIN_BITS_PER_PIXEL: f64 = 0.05;
+
+/// How far a size-targeted encode may shrink the frame to keep the bits per
+/// pixel above [`MIN_BITS_PER_PIXEL`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolutionLadder {
+    /// Smallest short side to step down to.
+    pub min_height: u32,
+    /// `false` keeps the source resolution whatever the bitrate.
+    pub enabled: bool,
+}
+
+impl Default for ResolutionLadder {
+    fn default() -> Self {
+        Self {
+            min_height: 480,
+            enabled: true,
+        }
+    }
+}
+
+impl ResolutionLadder {
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+
+    /// Bits per pixel per frame of `width`×`height` at `fps` and `video_kbps`.
+    pub fn bits_per_pixel(width: u32, height: u32, fps: f64, video_kbps: f64) -> f64 {
+        let pixels_per_sec = width as f64 * height as f64 * fps.max(1.0);
+        if pixels_per_sec <= 0.0 {
+            return 0.0;
+        }
+        video_kbps * 1000.0 / pixels_per_sec
+    }
+
+    /// Short side to encode a `width`×`height` source at, or `None` to keep
+    /// it. Steps down the rungs until the floor is met; if none meets it, the
+    /// smallest allowed rung is used.
+    pub fn choose(&self, width: u32, height: u32, fps: f64, video_kbps: f64) -> Option<u32> {
+        let short = width.min(height);
+        if !self.enabled
+            || short == 0
+            || Self::bits_per_pixel(width, height, fps, video_kbps) >= MIN_BITS_PER_PIXEL
+        {
+            return None;
+        }
+        let mut chosen = None;
+        for rung in RESOLUTION_RUNGS {
+            if rung >= short || rung < self.min_height {
+                continue;
+            }
+            chosen = Some(rung);
+            let (w, h) = scaled_to_short_side(width, height, rung);
+            if Self::bits_per_pixel(w, h, fps, video_kbps) >= MIN_BITS_PER_PIXEL {
+                break;
+            }
+        }
+        chosen
+    }
+}
+
+/// `width`×`height` scaled so the short side is `short`, both sides even.
+pub fn scaled_to_short_side(width: u32, height: u32, short: u32) -> (u32, u32) {
+    let even = |v: f64| ((v / 2.0).round() as u32 * 2).max(2);
+    let factor = short as f64 / width.min(height).max(1) as f64;
+    (even(width as f64 * factor), even(height as f64 * factor))
+}
+
+/// Compress video to target file size (in MB), stepping down the default
+/// [`ResolutionLadder`] if the bitrate is too thin for the source size.
 pub async fn compress_video(
     input: &Path,
     target_size_mb: f64,
     output: &Path,
+) -> Result<ProductionResult, Box<dyn std::error::Error + Send + Sync>> {
+    compress_video_with(input, target_size_mb, output, ResolutionLadder::default()).await
+}
+
+/// [`compress_video`] with an explicit resolution ladder.
+pub async fn compress_video_with(
+    input: &Path,
+    target_size_mb: f64,
+    output: &Path,
+    ladder: ResolutionLadder,
 ) -> Result<ProductionResult, Box<dyn std::error::Error + Send + Sync>> {
     info!(
         "[PROD] Compressing video: {:?} -> {:.2} MB",
@@ -151,13 +244,42 @@ pub async fn compress_video(
     // Single pass CRF (Consistant Rate Factor) capped by maxrate is usually better/faster for modern codecs
     // but 2-pass is standard for strict control is requested.
 
+    let mut scale_filter = None;
+    match probe_dimensions(input).await {
+        Ok((width, height)) => {
+            let fps = probe_frame_rate(input).await.unwrap_or(30.0);
+            let bpp = ResolutionLadder::bits_per_pixel(width, height, fps, video_bitrate_kbps);
+            match ladder.choose(width, height, fps, video_bitrate_kbps) {
+                Some(short) => {
+                    let (w, h) = scaled_to_short_side(width, height, short);
+                    info!(
+                        "[PROD] 📐 {:.3} bpp at {}x{} is below the {:.2} floor; encoding at {}x{} ({:.3} bpp)",
+                        bpp,
+                        width,
+                        height,
+                        MIN_BITS_PER_PIXEL,
+                        w,
+                        h,
+                        ResolutionLadder::bits_per_pixel(w, h, fps, video_bitrate_kbps)
+                    );
+                    scale_filter = Some(format!("scale={}:{}", w, h));
+                }
+                None => info!(
+                    "[PROD] 📐 Keeping source resolution {}x{} ({:.3} bpp)",
+                    width, height, bpp
+                ),
+            }
+        }
+        Err(e) => warn!("[PROD] Could not read source resolution ({}); keeping it.", e),
+    }
+
     let safe_input = safe_arg_path(input);
     let safe_output = safe_arg_path(output);
 
     let gpu_ctx = crate::gpu_backend::get_gpu_context().await;
     let neuro = crate::agent::neuroplasticity::Neuroplasticity::new();
 
-    let mut cmd = Command::new("ffmpeg");
+    let mut cmd = proc::ffmpeg(ProcClass::Render);
     cmd.stealth();
     cmd.arg("-y");
 
@@ -167,6 +289,9 @@ pub async fn compress_video(
     }
 
     cmd.arg("-i").arg(&safe_input);
+    if let Some(filter) = &scale_filter {
+        cmd.args(["-vf", filter]);
+    }
 
     cmd.arg("-c:v").arg(gpu_ctx.ffmpeg_encoder());
     for flag in gpu_ctx.neuroplastic_ffmpeg_flags(neuro.current_speed()) {
@@ -204,6 +329,9 @@ pub async fn compress_video(
     })
 }
 
+/// Integrated loudness `enhance_audio` normalizes to, in LUFS.
+pub const ENHANCED_LOUDNESS_LUFS: f64 = -16.0;
+
 /// Enhance audio using vocal processing chain (EQ -> Compression -> Normalization)
 pub async fn enhance_audio(
     input: &Path,
@@ -229,7 +357,7 @@ pub async fn enhance_audio(
     let safe_input = safe_arg_path(input);
     let safe_output = safe_arg_path(output);
 
-    let status = Command::new("ffmpeg")
+    let status = proc::ffmpeg(ProcClass::Render)
         .stealth()
         .args(["-y", "-nostdin", "-i"])
         .arg(&safe_input)
@@ -256,17 +384,32 @@ pub async fn enhance_audio(
 }
 
 /// Combine a video file with an external audio file
-/// Replaces the video's original audio with the new audio track.
+/// Replaces the video's original audio with the new audio track. With
+/// `auto_sync` the external audio is first lined up with the video's
+/// scratch audio; when the two do not correlate it is used as it is.
 pub async fn combine_av(
     video_path: &Path,
     audio_path: &Path,
     output_path: &Path,
+    auto_sync: bool,
 ) -> Result<ProductionResult, Box<dyn std::error::Error + Send + Sync>> {
     info!(
         "[PROD] Combining Video: {:?} + Audio: {:?}",
         video_path, audio_path
     );
 
+    let offset = if auto_sync {
+        match crate::agent::audio_tools::sync_offset(video_path, audio_path).await {
+            Ok(offset) => offset,
+            Err(e) => {
+                warn!("[PROD] ⚠️ Audio not synced, combining as recorded: {}", e);
+                0.0
+            }
+        }
+    } else {
+        0.0
+    };
+
     let safe_video = safe_arg_path(video_path);
     let safe_audio = safe_arg_path(audio_path);
     let safe_output = safe_arg_path(output_path);
@@ -277,28 +420,21 @@ pub async fn combine_av(
     // -c:v copy (Copy video stream directly - fast!)
     // -c:a aac (Re-encode audio to AAC for compatibility)
     // -shortest (Finish when the shortest stream ends)
-
-    let status = Command::new("ffmpeg")
-        .stealth()
-        .arg("-y")
-        .arg("-i")
-        .arg(&safe_video)
-        .arg("-i")
-        .arg(&safe_audio)
-        .args([
-            "-map",
-            "0:v",
-            "-map",
-            "1:a",
-            "-c:v",
-            "copy",
-            "-c:a",
-            "aac",
-            "-shortest",
-        ])
-        .arg(&safe_output)
-        .status()
-        .await?;
+    // A recorder that started early is trimmed; one that started late is
+    // padded with silence
+    let mut cmd = proc::ffmpeg(ProcClass::Render);
+    cmd.stealth().arg("-y").arg("-i").arg(&safe_video);
+    if offset > 0.0 {
+        cmd.arg("-ss").arg(format!("{:.3}", offset));
+    }
+    cmd.arg("-i").arg(&safe_audio).args([
+        "-map", "0:v", "-map", "1:a", "-c:v", "copy", "-c:a", "aac", "-shortest",
+    ]);
+    if offset < 0.0 {
+        cmd.arg("-af")
+            .arg(format!("adelay={}:all=1", (-offset * 1000.0).round() as u64));
+    }
+    let status = cmd.arg(&safe_output).status().await?;
 
     if !status.success() {
         return Err("FFmpeg combine failed".into());
@@ -386,6 +522,92 @@ pub fn build_transition_filter(
     filter
 }
 
+/// Formats `extract_audio` writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    Mp3,
+    Aac,
+    Flac,
+    Wav,
+    Opus,
+}
+
+impl AudioFormat {
+    pub const ALL: [AudioFormat; 5] = [Self::Mp3, Self::Aac, Self::Flac, Self::Wav, Self::Opus];
+
+    /// File extension for the format; AAC goes in an `.m4a` container.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Mp3 => "mp3",
+            Self::Aac => "m4a",
+            Self::Flac => "flac",
+            Self::Wav => "wav",
+            Self::Opus => "opus",
+        }
+    }
+
+    /// The ffmpeg encoder.
+    pub fn codec(&self) -> &'static str {
+        match self {
+            Self::Mp3 => "libmp3lame",
+            Self::Aac => "aac",
+            Self::Flac => "flac",
+            Self::Wav => "pcm_s16le",
+            Self::Opus => "libopus",
+        }
+    }
+
+    /// Bitrate used when none is asked for; `None` for lossless formats,
+    /// which take no bitrate.
+    pub fn default_bitrate(&self) -> Option<&'static str> {
+        match self {
+            Self::Mp3 | Self::Aac => Some("192k"),
+            Self::Opus => Some("128k"),
+            Self::Flac | Self::Wav => None,
+        }
+    }
+
+    /// Encoder arguments: the codec and, for lossy formats, `bitrate` or the
+    /// default one.
+    pub fn codec_args(&self, bitrate: Option<&str>) -> Vec<String> {
+        let mut args = vec!["-c:a".to_string(), self.codec().to_string()];
+        if let Some(default) = self.default_bitrate() {
+            args.extend(["-b:a".to_string(), bitrate.unwrap_or(default).to_string()]);
+        }
+        args
+    }
+}
+
+impl std::str::FromStr for AudioFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "mp3" => Ok(Self::Mp3),
+            "aac" | "m4a" => Ok(Self::Aac),
+            "flac" => Ok(Self::Flac),
+            "wav" => Ok(Self::Wav),
+            "opus" => Ok(Self::Opus),
+            other => Err(format!(
+                "Unknown audio format '{}' (expected mp3, aac, flac, wav or opus)",
+                other
+            )),
+        }
+    }
+}
+
+/// Extract the first audio track of `input` into `output` as `format`.
+/// `bitrate` (e.g. "320k") applies to lossy formats only.
+pub async fn extract_audio(
+    input: &Path,
+    output: &Path,
+    format: AudioFormat,
+    bitrate: Option<&str>,
+) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+    info!("[PRODUCTION] Extracting {:?} audio from {:?}", format, input);
+    run_audio_extraction(input, output, &format.codec_args(bitrate)).await
+}
+
 /// Extract audio as 16kHz Mono PCM WAV (Ideal for Whisper)
 pub async fn extract_audio_wav(
     input_video: &Path,
@@ -395,31 +617,135 @@ pub async fn extract_audio_wav(
         "[PRODUCTION] Extracting audio for Whisper: {:?}",
         input_video
     );
+    let mut args = AudioFormat::Wav.codec_args(None);
+    args.extend(["-ar", "16000", "-ac", "1"].map(String::from));
+    run_audio_extraction(input_video, output_wav, &args).await
+}
+
+/// File name of the `n`th audio stream (1-based) of a source called `stem`:
+/// `<stem>_track<n>[_<language>].wav`. Undetermined languages are left out.
+pub fn stem_file_name(stem: &str, n: usize, track: &AudioTrack) -> String {
+    let language: String = track
+        .language
+        .as_deref()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    match language.as_str() {
+        "" | "und" => format!("{}_track{}.{}", stem, n, AudioFormat::Wav.extension()),
+        lang => format!("{}_track{}_{}.{}", stem, n, lang, AudioFormat::Wav.extension()),
+    }
+}
+
+/// Export every audio stream of `input` to its own WAV in `output_dir`, for
+/// remixing. One ffmpeg pass writes them all; returns the files in stream
+/// order.
+pub async fn export_stems(
+    input: &Path,
+    output_dir: &Path,
+) -> Result<Vec<PathBuf>, Box<dyn std::error::Error + Send + Sync>> {
+    let tracks = audio_tools::get_audio_tracks(input).await?;
+    if tracks.is_empty() {
+        return Err(format!("{} has no audio streams", input.display()).into());
+    }
+    std::fs::create_dir_all(output_dir)?;
+    let stem = input
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "audio".to_string());
+    info!(
+        "[PRODUCTION] Exporting {} audio stem(s) of {:?} to {:?}",
+        tracks.len(),
+        input,
+        output_dir
+    );
+
+    let mut cmd = proc::ffmpeg(ProcClass::Render);
+    cmd.stealth()
+        .args(["-y", "-nostdin", "-i"])
+        .arg(safe_arg_path(input));
+    let mut outputs = Vec::with_capacity(tracks.len());
+    for (i, track) in tracks.iter().enumerate() {
+        let path = output_dir.join(stem_file_name(&stem, i + 1, track));
+        cmd.arg("-map")
+            .arg(format!("0:{}", track.index))
+            .args(AudioFormat::Wav.codec_args(None))
+            .arg(safe_arg_path(&path));
+        outputs.push(path);
+    }
+
+    let result = cmd.output().await?;
+    if !result.status.success() {
+        let err = String::from_utf8_lossy(&result.stderr);
+        warn!("[PRODUCTION] FFmpeg stem export failed: {}", err);
+        return Err(format!("FFmpeg error: {}", err).into());
+    }
+    Ok(outputs)
+}
 
-    let output = Command::new("ffmpeg")
+/// `count` evenly spaced frames of `input`, each scaled to `width` pixels
+/// wide, tiled left to right into the single image `output`.
+pub async fn thumbnail_strip(
+    input: &Path,
+    output: &Path,
+    count: u32,
+    width: u32,
+) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+    let count = count.max(1);
+    let duration = get_video_duration(input).await?;
+    if duration <= 0.0 {
+        return Err("Could not determine video duration".into());
+    }
+    info!("[PRODUCTION] Thumbnail strip of {} frame(s): {:?}", count, input);
+
+    let filter = format!(
+        "fps={}/{:.3},scale={}:-2,tile={}x1",
+        count,
+        duration,
+        width.max(2) & !1,
+        count
+    );
+    let result = proc::ffmpeg(ProcClass::Thumbnail)
         .stealth()
-        .arg("-y")
-        .arg("-i")
-        .arg(safe_arg_path(input_video))
-        .arg("-vn") // No video
-        .arg("-acodec")
-        .arg("pcm_s16le") // 16-bit PCM
-        .arg("-ar")
-        .arg("16000") // 16kHz
-        .arg("-ac")
-        .arg("1") // Mono
-        .arg(safe_arg_path(output_wav))
+        .args(["-y", "-nostdin", "-v", "error", "-i"])
+        .arg(safe_arg_path(input))
+        .args(["-vf", &filter, "-frames:v", "1"])
+        .arg(safe_arg_path(output))
         .output()
         .await?;
 
-    if !output.status.success() {
+    if !result.status.success() {
+        let err = String::from_utf8_lossy(&result.stderr);
+        return Err(format!("FFmpeg thumbnail strip failed: {}", err).into());
+    }
+    Ok(output.to_path_buf())
+}
+
+async fn run_audio_extraction(
+    input: &Path,
+    output: &Path,
+    codec_args: &[String],
+) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
+    let result = proc::ffmpeg(ProcClass::Render)
+        .stealth()
+        .args(["-y", "-nostdin", "-i"])
+        .arg(safe_arg_path(input))
+        .args(["-vn", "-map", "0:a:0"])
+        .args(codec_args)
+        .arg(safe_arg_path(output))
+        .output()
+        .await?;
+
+    if !result.status.success() {
         warn!("[PRODUCTION] FFmpeg audio extraction failed!");
-        let err = String::from_utf8_lossy(&output.stderr);
+        let err = String::from_utf8_lossy(&result.stderr);
         warn!("{}", err);
         return Err(format!("FFmpeg error: {}", err).into());
     }
 
-    Ok(output_wav.to_path_buf())
+    Ok(output.to_path_buf())
 }
 
 /// Burn subtitles onto a video using FFmpeg.
@@ -476,7 +802,7 @@ pub async fn burn_subtitles(
     let gpu_ctx = crate::gpu_backend::get_gpu_context().await;
     let neuro = crate::agent::neuroplasticity::Neuroplasticity::new();
 
-    let mut cmd = Command::new("ffmpeg");
+    let mut cmd = proc::ffmpeg(ProcClass::Render);
     cmd.stealth();
     cmd.arg("-y")
         .arg("-hide_banner")
@@ -536,6 +862,46 @@ pub async fn burn_subtitles(
     })
 }
 
+/// Add an SRT to a video as a soft subtitle track. Video and audio are
+/// copied, not re-encoded.
+pub async fn mux_subtitles(
+    input_video: &Path,
+    input_srt: &Path,
+    output_video: &Path,
+) -> Result<ProductionResult, Box<dyn std::error::Error + Send + Sync>> {
+    info!(
+        "[PRODUCTION] Muxing subtitles from {:?} into {:?}",
+        input_srt, input_video
+    );
+    let result = proc::ffmpeg(ProcClass::Render)
+        .stealth()
+        .args(["-y", "-hide_banner", "-loglevel", "error", "-nostdin", "-i"])
+        .arg(safe_arg_path(input_video))
+        .arg("-i")
+        .arg(safe_arg_path(input_srt))
+        .args([
+            "-map", "0:v?", "-map", "0:a?", "-map", "1:s", "-c", "copy", "-c:s", "mov_text",
+            "-movflags", "+faststart",
+        ])
+        .arg(safe_arg_path(output_video))
+        .output()
+        .await?;
+    if !result.status.success() {
+        let err = String::from_utf8_lossy(&result.stderr);
+        warn!("[PRODUCTION] FFmpeg mux_subtitles FAILED:\n{}", err);
+        return Err(format!("FFmpeg subtitle mux error: {}", err).into());
+    }
+
+    Ok(ProductionResult {
+        output_path: output_video.to_path_buf(),
+        size_mb: tokio::fs::metadata(output_video)
+            .await
+            .map(|m| m.len() as f64 / 1_048_576.0)
+            .unwrap_or(0.0),
+        duration: get_video_duration(output_video).await.unwrap_or(0.0),
+    })
+}
+
 /// Convert an SRT subtitle string into a styled ASS (Advanced SubStation Alpha) string.
 /// All visual styles are embedded in the ASS header — no FFmpeg filter options needed.
 fn srt_to_ass(srt: &str) -> String {
@@ -635,7 +1001,7 @@ pub async fn apply_audio_censor(
         // Nothing to censor — just copy input to output
         let safe_input = safe_arg_path(input_audio);
         let safe_output = safe_arg_path(output_audio);
-        let output = Command::new("ffmpeg")
+        let output = proc::ffmpeg(ProcClass::Render)
             .stealth()
             .args(["-y", "-i"])
             .arg(&safe_input)
@@ -654,7 +1020,7 @@ pub async fn apply_audio_censor(
     let safe_input = safe_arg_path(input_audio);
     let safe_output = safe_arg_path(output_audio);
 
-    let mut cmd = Command::new("ffmpeg");
+    let mut cmd = proc::ffmpeg(ProcClass::Render);
     cmd.stealth();
     cmd.arg("-y").arg("-i").arg(&safe_input);
 
@@ -758,3 +1124,582 @@ pub async fn apply_audio_censor(
 
     Ok(())
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Speech Ducking
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Track titles that mark a stream as background music.
+const MUSIC_TRACK_WORDS: [&str; 5] = ["music", "background", "bgm", "song", "soundtrack"];
+/// Track titles that mark a stream as the voice to keep clear.
+const VOICE_TRACK_WORDS: [&str; 8] = [
+    "voice", "mic", "dialog", "speech", "commentary", "narration", "player", "vo",
+];
+
+/// How far and how fast the music dips while someone is talking.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SpeechDucking {
+    /// Gain reduction under speech, in dB.
+    #[serde(default = "default_duck_depth")]
+    pub depth_db: f64,
+    /// Fade-down time before speech starts, in seconds.
+    #[serde(default = "default_duck_attack")]
+    pub attack_secs: f64,
+    /// Fade-up time after speech ends, in seconds.
+    #[serde(default = "default_duck_release")]
+    pub release_secs: f64,
+}
+
+fn default_duck_depth() -> f64 {
+    12.0
+}
+fn default_duck_attack() -> f64 {
+    0.2
+}
+fn default_duck_release() -> f64 {
+    0.6
+}
+
+impl Default for SpeechDucking {
+    fn default() -> Self {
+        Self {
+            depth_db: default_duck_depth(),
+            attack_secs: default_duck_attack(),
+            release_secs: default_duck_release(),
+        }
+    }
+}
+
+/// Which filter `duck_music_under_speech` used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuckingMethod {
+    /// `volume` automation built from known speech regions.
+    Envelope,
+    /// `sidechaincompress` keyed on the voice stem.
+    Sidechain,
+}
+
+/// Speech spans from a transcript, sorted with overlapping segments merged.
+pub fn speech_regions(transcript: &[TranscriptSegment]) -> Vec<(f64, f64)> {
+    let mut spans: Vec<(f64, f64)> = transcript
+        .iter()
+        .filter(|s| s.end > s.start && !s.text.trim().is_empty())
+        .map(|s| (s.start, s.end))
+        .collect();
+    spans.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    let mut merged: Vec<(f64, f64)> = Vec::new();
+    for (s, e) in spans {
+        match merged.last_mut() {
+            Some(last) if s <= last.1 => last.1 = last.1.max(e),
+            _ => merged.push((s, e)),
+        }
+    }
+    merged
+}
+
+/// The (voice, music) stream indices of a multi-track source, judged by
+/// track titles. With exactly two tracks, naming one of them is enough.
+pub fn identify_stems(tracks: &[AudioTrack]) -> Option<(usize, usize)> {
+    let titled = |words: &[&str], t: &AudioTrack| {
+        let title = t.title.to_lowercase();
+        title
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|w| words.contains(&w))
+    };
+    let music = tracks.iter().find(|t| titled(&MUSIC_TRACK_WORDS, t));
+    let voice = tracks
+        .iter()
+        .find(|t| titled(&VOICE_TRACK_WORDS, t) && Some(t.index) != music.map(|m| m.index));
+    match (voice, music, tracks) {
+        (Some(v), Some(m), _) => Some((v.index, m.index)),
+        (None, Some(m), [a, b]) => Some((if a.index == m.index { b } else { a }.index, m.index)),
+        (Some(v), None, [a, b]) => Some((v.index, if a.index == v.index { b } else { a }.index)),
+        _ => None,
+    }
+}
+
+/// A `volume` chain that fades down over `attack_secs` into each speech
+/// region, holds `depth_db` down through it, and fades back up over
+/// `release_secs`. Regions closer than a full fade cycle are joined so the
+/// music doesn't bob up between sentences.
+pub fn ducking_envelope_filter(regions: &[(f64, f64)], duck: &SpeechDucking) -> String {
+    let attack = duck.attack_secs.max(0.01);
+    let release = duck.release_secs.max(0.01);
+    let mut joined: Vec<(f64, f64)> = Vec::new();
+    for &(s, e) in regions {
+        match joined.last_mut() {
+            Some(last) if s - attack <= last.1 + release => last.1 = last.1.max(e),
+            _ => joined.push((s, e)),
+        }
+    }
+    if joined.is_empty() {
+        return "anull".to_string();
+    }
+
+    let d = duck.depth_db;
+    let mut parts = Vec::with_capacity(joined.len() * 3);
+    for (s, e) in joined {
+        let a0 = (s - attack).max(0.0);
+        if s > a0 {
+            parts.push(format!(
+                "volume='pow(10,-{d:.2}*(t-{a0:.3})/{:.3}/20)':eval=frame:enable='gte(t,{a0:.3})*lt(t,{s:.3})'",
+                s - a0
+            ));
+        }
+        parts.push(format!(
+            "volume={:.4}:enable='between(t,{s:.3},{e:.3})'",
+            10f64.powf(-d / 20.0)
+        ));
+        parts.push(format!(
+            "volume='pow(10,-{d:.2}*(1-(t-{e:.3})/{release:.3})/20)':eval=frame:enable='gt(t,{e:.3})*lt(t,{:.3})'",
+            e + release
+        ));
+    }
+    parts.join(",")
+}
+
+/// Graph that ducks `[music]` under `[voice]` with `sidechaincompress`,
+/// leaving `[voice]` and `[ducked]` for the mix. The ratio follows
+/// `depth_db` (depth / 2, clamped to 2-20).
+pub fn sidechain_duck_filter(voice: &str, music: &str, duck: &SpeechDucking) -> String {
+    format!(
+        "[{voice}]asplit=2[voice][key];[{music}][key]sidechaincompress=threshold=0.03:ratio={:.1}:attack={:.0}:release={:.0}[ducked]",
+        (duck.depth_db / 2.0).clamp(2.0, 20.0),
+        (duck.attack_secs * 1000.0).clamp(0.01, 2000.0),
+        (duck.release_secs * 1000.0).clamp(0.01, 9000.0),
+    )
+}
+
+/// Duck the music track of a multi-track source under its speech and mix
+/// every track down to one. With `speech` regions (from a transcript) the
+/// music follows a volume envelope; without them the voice stem drives a
+/// sidechain compressor. A `.wav` output gets the mix only; anything else
+/// keeps the video stream.
+pub async fn duck_music_under_speech(
+    input: &Path,
+    output: &Path,
+    speech: &[(f64, f64)],
+    duck: &SpeechDucking,
+) -> Result<DuckingMethod, Box<dyn std::error::Error + Send + Sync>> {
+    let tracks = audio_tools::get_audio_tracks(input).await?;
+    let (voice, music) = identify_stems(&tracks).ok_or_else(|| {
+        format!(
+            "{} has {} audio track(s) and no separate music track; ducking a mixed track would lower the dialog too",
+            input.display(),
+            tracks.len()
+        )
+    })?;
+
+    let (method, mut graph, mut mix) = if speech.is_empty() {
+        (
+            DuckingMethod::Sidechain,
+            sidechain_duck_filter(&format!("0:{}", voice), &format!("0:{}", music), duck),
+            vec!["[voice]".to_string(), "[ducked]".to_string()],
+        )
+    } else {
+        (
+            DuckingMethod::Envelope,
+            format!("[0:{}]{}[ducked]", music, ducking_envelope_filter(speech, duck)),
+            vec![format!("[0:{}]", voice), "[ducked]".to_string()],
+        )
+    };
+    mix.extend(
+        tracks
+            .iter()
+            .filter(|t| t.index != voice && t.index != music)
+            .map(|t| format!("[0:{}]", t.index)),
+    );
+    graph.push_str(&format!(
+        ";{}amix=inputs={}:duration=longest:normalize=0[aout]",
+        mix.concat(),
+        mix.len()
+    ));
+
+    info!(
+        "[PROD] 🎚️ Ducking music (stream {}) under voice (stream {}) by {:.1} dB via {:?}",
+        music, voice, duck.depth_db, method
+    );
+
+    // Long envelopes overrun the Windows command-line limit
+    let script = output.with_extension("duck_filter.txt");
+    std::fs::write(&script, &graph)?;
+    let mut cmd = proc::ffmpeg(ProcClass::Render);
+    cmd.stealth()
+        .args(["-y", "-nostdin", "-i"])
+        .arg(safe_arg_path(input))
+        .arg("-filter_complex_script")
+        .arg(&script)
+        .args(["-map", "[aout]"]);
+    let wav_out = output
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("wav"));
+    if wav_out {
+        cmd.args(["-vn", "-c:a", "pcm_s16le", "-ar", "48000"]);
+    } else {
+        cmd.args(["-map", "0:v?", "-c:v", "copy", "-c:a", "aac", "-b:a", "192k"]);
+    }
+    let result = cmd.arg(safe_arg_path(output)).output().await;
+    let _ = std::fs::remove_file(&script);
+
+    let result = result?;
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        return Err(format!("Music ducking failed: {}", stderr.trim()).into());
+    }
+    Ok(method)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Quality Comparison (PSNR / SSIM / VMAF)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Files longer than this also get a per-window breakdown.
+const QUALITY_WINDOW_THRESHOLD_SECS: f64 = 300.0;
+/// Number and length of the sampled windows for long files.
+const QUALITY_WINDOWS: usize = 6;
+const QUALITY_WINDOW_SECS: f64 = 10.0;
+
+/// Objective quality scores for one comparison (whole file or a window).
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct QualityScores {
+    /// Average PSNR in dB (higher is better; inf for identical frames).
+    pub psnr: Option<f64>,
+    /// Average SSIM, 0-1 (higher is better).
+    pub ssim: Option<f64>,
+    /// VMAF, 0-100 — only when FFmpeg is built with libvmaf.
+    pub vmaf: Option<f64>,
+}
+
+/// Scores for one sampled window of a long file.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct QualityWindow {
+    pub start: f64,
+    pub duration: f64,
+    pub scores: QualityScores,
+}
+
+/// Result of `compare_quality`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct QualityReport {
+    pub reference: PathBuf,
+    pub candidate: PathBuf,
+    /// Resolution both streams were compared at (the reference's).
+    pub compared_at: (u32, u32),
+    pub overall: QualityScores,
+    pub windows: Vec<QualityWindow>,
+}
+
+impl QualityReport {
+    /// One-line human summary, e.g. "PSNR 38.21 dB | SSIM 0.9712 | VMAF 94.1".
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(p) = self.overall.psnr {
+            parts.push(format!("PSNR {:.2} dB", p));
+        }
+        if let Some(s) = self.overall.ssim {
+            parts.push(format!("SSIM {:.4}", s));
+        }
+        if let Some(v) = self.overall.vmaf {
+            parts.push(format!("VMAF {:.1}", v));
+        }
+        if parts.is_empty() {
+            "no metrics".to_string()
+        } else {
+            parts.join(" | ")
+        }
+    }
+}
+
+/// Compare `candidate` against `reference` with FFmpeg's psnr/ssim filters
+/// (plus libvmaf when available). The candidate is scaled to the
+/// reference's resolution first, so upscales and downscales compare cleanly.
+pub async fn compare_quality(
+    reference: &Path,
+    candidate: &Path,
+) -> Result<QualityReport, Box<dyn std::error::Error + Send + Sync>> {
+    let (width, height) = probe_dimensions(reference).await?;
+    let has_vmaf = ffmpeg_has_filter("libvmaf").await;
+    info!(
+        "[QUALITY] Comparing {:?} against {:?} at {}x{} (VMAF: {})",
+        candidate, reference, width, height, has_vmaf
+    );
+
+    let overall = measure_window(reference, candidate, (width, height), None, has_vmaf).await?;
+
+    let mut windows = Vec::new();
+    let duration = get_video_duration(reference).await.unwrap_or(0.0);
+    if duration > QUALITY_WINDOW_THRESHOLD_SECS {
+        let stride = duration / QUALITY_WINDOWS as f64;
+        for i in 0..QUALITY_WINDOWS {
+            let start = stride * i as f64 + (stride - QUALITY_WINDOW_SECS).max(0.0) / 2.0;
+            let range = (start, QUALITY_WINDOW_SECS);
+            match measure_window(reference, candidate, (width, height), Some(range), has_vmaf)
+                .await
+            {
+                Ok(scores) => windows.push(QualityWindow {
+                    start,
+                    duration: QUALITY_WINDOW_SECS,
+                    scores,
+                }),
+                Err(e) => warn!("[QUALITY] Window at {:.0}s failed: {}", start, e),
+            }
+        }
+    }
+
+    let report = QualityReport {
+        reference: reference.to_path_buf(),
+        candidate: candidate.to_path_buf(),
+        compared_at: (width, height),
+        overall,
+        windows,
+    };
+    info!("[QUALITY] {}", report.summary());
+    Ok(report)
+}
+
+async fn measure_window(
+    reference: &Path,
+    candidate: &Path,
+    (width, height): (u32, u32),
+    range: Option<(f64, f64)>,
+    with_vmaf: bool,
+) -> Result<QualityScores, Box<dyn std::error::Error + Send + Sync>> {
+    let branches = if with_vmaf { 3 } else { 2 };
+    let mut graph = format!(
+        "[1:v]scale={w}:{h}:flags=bicubic,setsar=1,format=yuv420p,split={n}[d1][d2]{d3};\
+         [0:v]setsar=1,format=yuv420p,split={n}[r1][r2]{r3};\
+         [d1][r1]psnr[o1];[d2][r2]ssim[o2]",
+        w = width,
+        h = height,
+        n = branches,
+        d3 = if with_vmaf { "[d3]" } else { "" },
+        r3 = if with_vmaf { "[r3]" } else { "" },
+    );
+    if with_vmaf {
+        graph.push_str(";[d3][r3]libvmaf[o3]");
+    }
+
+    let mut cmd = proc::ffmpeg(ProcClass::Thumbnail);
+    cmd.stealth().args(["-hide_banner", "-nostdin"]);
+    for input in [reference, candidate] {
+        if let Some((start, dur)) = range {
+            cmd.args(["-ss", &format!("{:.3}", start), "-t", &format!("{:.3}", dur)]);
+        }
+        cmd.arg("-i").arg(safe_arg_path(input));
+    }
+    cmd.args(["-filter_complex", &graph]);
+    cmd.args(["-map", "[o1]", "-f", "null", "-", "-map", "[o2]", "-f", "null", "-"]);
+    if with_vmaf {
+        cmd.args(["-map", "[o3]", "-f", "null", "-"]);
+    }
+
+    let output = cmd.output().await?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(format!(
+            "FFmpeg quality comparison failed: {}",
+            stderr.lines().last().unwrap_or("")
+        )
+        .into());
+    }
+    Ok(parse_quality_output(&stderr))
+}
+
+/// Pull PSNR/SSIM/VMAF summary values out of FFmpeg's stderr.
+fn parse_quality_output(stderr: &str) -> QualityScores {
+    let value_after = |line: &str, key: &str| -> Option<f64> {
+        let rest = &line[line.find(key)? + key.len()..];
+        let token = rest.split_whitespace().next()?;
+        match token {
+            "inf" => Some(f64::INFINITY),
+            t => t.parse().ok(),
+        }
+    };
+
+    let mut scores = QualityScores::default();
+    for line in stderr.lines() {
+        if line.contains("PSNR ") && line.contains("average:") {
+            scores.psnr = value_after(line, "average:");
+        } else if line.contains("SSIM ") && line.contains("All:") {
+            scores.ssim = value_after(line, "All:");
+        } else if line.contains("VMAF score") {
+            scores.vmaf = value_after(line, "VMAF score:").or_else(|| value_after(line, "VMAF score = "));
+        }
+    }
+    scores
+}
+
+async fn probe_dimensions(
+    path: &Path,
+) -> Result<(u32, u32), Box<dyn std::error::Error + Send + Sync>> {
+    let output = proc::ffprobe()
+        .stealth()
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "v:0",
+            "-show_entries",
+            "stream=width,height",
+            "-of",
+            "csv=s=x:p=0",
+        ])
+        .arg(safe_arg_path(path))
+        .output()
+        .await?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let (w, h) = text
+        .trim()
+        .split_once('x')
+        .ok_or_else(|| format!("Could not read resolution of {:?}", path))?;
+    Ok((w.trim().parse()?, h.trim().parse()?))
+}
+
+/// Average frame rate of the first video stream.
+async fn probe_frame_rate(path: &Path) -> Option<f64> {
+    let output = proc::ffprobe()
+        .stealth()
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "v:0",
+            "-show_entries",
+            "stream=avg_frame_rate",
+            "-of",
+            "default=noprint_wrappers=1:nokey=1",
+        ])
+        .arg(safe_arg_path(path))
+        .output()
+        .await
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let (num, den) = text.trim().split_once('/')?;
+    let (num, den): (f64, f64) = (num.parse().ok()?, den.parse().ok()?);
+    (num > 0.0 && den > 0.0).then(|| num / den)
+}
+
+async fn ffmpeg_has_filter(name: &str) -> bool {
+    match proc::ffmpeg(ProcClass::Probe)
+        .stealth()
+        .args(["-hide_banner", "-filters"])
+        .output()
+        .await
+    {
+        Ok(out) => String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .any(|l| l.split_whitespace().nth(1) == Some(name)),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stems_are_named_by_track_and_language() {
+        let track = |language: Option<&str>| AudioTrack {
+            index: 3,
+            title: "Unknown".to_string(),
+            language: language.map(str::to_string),
+        };
+        assert_eq!(stem_file_name("show", 1, &track(Some("eng"))), "show_track1_eng.wav");
+        assert_eq!(stem_file_name("show", 2, &track(Some("und"))), "show_track2.wav");
+        assert_eq!(stem_file_name("show", 3, &track(None)), "show_track3.wav");
+        // Tags are not trusted as path parts
+        assert_eq!(stem_file_name("show", 4, &track(Some("../FR"))), "show_track4_fr.wav");
+    }
+
+    #[test]
+    fn audio_formats_select_their_codec_and_bitrate() {
+        let args = |f: AudioFormat, b: Option<&str>| f.codec_args(b).join(" ");
+        assert_eq!(args(AudioFormat::Mp3, None), "-c:a libmp3lame -b:a 192k");
+        assert_eq!(args(AudioFormat::Mp3, Some("320k")), "-c:a libmp3lame -b:a 320k");
+        assert_eq!(args(AudioFormat::Aac, None), "-c:a aac -b:a 192k");
+        assert_eq!(args(AudioFormat::Opus, Some("96k")), "-c:a libopus -b:a 96k");
+        // Lossless formats ignore a bitrate
+        assert_eq!(args(AudioFormat::Flac, Some("320k")), "-c:a flac");
+        assert_eq!(args(AudioFormat::Wav, None), "-c:a pcm_s16le");
+
+        for format in AudioFormat::ALL {
+            assert_eq!(format.extension().parse::<AudioFormat>(), Ok(format));
+        }
+        assert_eq!(" AAC ".parse::<AudioFormat>(), Ok(AudioFormat::Aac));
+        assert!("ogg".parse::<AudioFormat>().is_err());
+    }
+
+    #[test]
+    fn test_parse_quality_output() {
+        let stderr = "\
+[Parsed_psnr_6 @ 0x55] PSNR y:38.102 u:42.551 v:43.004 average:39.217 min:31.552 max:48.100\n\
+[Parsed_ssim_7 @ 0x56] SSIM Y:0.971234 (15.389) U:0.982 (17.4) V:0.984 (17.9) All:0.975321 (16.069)\n\
+[Parsed_libvmaf_8 @ 0x57] VMAF score: 93.481234\n";
+        let scores = parse_quality_output(stderr);
+        assert_eq!(scores.psnr, Some(39.217));
+        assert_eq!(scores.ssim, Some(0.975321));
+        assert_eq!(scores.vmaf, Some(93.481234));
+
+        let identical = parse_quality_output("PSNR y:inf u:inf v:inf average:inf min:inf max:inf");
+        assert_eq!(identical.psnr, Some(f64::INFINITY));
+        assert!(identical.ssim.is_none());
+    }
+
+    fn track(index: usize, title: &str) -> AudioTrack {
+        AudioTrack {
+            index,
+            title: title.to_string(),
+            language: None,
+        }
+    }
+
+    #[test]
+    fn test_identify_stems_by_title() {
+        let obs = [track(1, "Game"), track(2, "Background Music"), track(3, "Mic")];
+        assert_eq!(identify_stems(&obs), Some((3, 2)));
+        // Two tracks: naming either one is enough
+        assert_eq!(identify_stems(&[track(1, "Unknown"), track(2, "BGM")]), Some((1, 2)));
+        assert_eq!(identify_stems(&[track(1, "Voice"), track(2, "Unknown")]), Some((1, 2)));
+        assert_eq!(identify_stems(&[track(1, "Unknown")]), None);
+        assert_eq!(identify_stems(&[track(1, "Unknown"), track(2, "Unknown")]), None);
+    }
+
+    #[test]
+    fn test_ducking_envelope_ramps_and_joins_close_regions() {
+        let duck = SpeechDucking {
+            depth_db: 12.0,
+            attack_secs: 0.2,
+            release_secs: 0.5,
+        };
+        // 1.0s apart: far enough for two dips; 0.3s apart: one joined dip
+        let filter = ducking_envelope_filter(&[(2.0, 4.0), (5.0, 6.0), (6.3, 7.0)], &duck);
+        let parts: Vec<&str> = filter.split(",volume").collect();
+        assert_eq!(parts.len(), 6, "{}", filter);
+        assert!(filter.starts_with(
+            "volume='pow(10,-12.00*(t-1.800)/0.200/20)':eval=frame:enable='gte(t,1.800)*lt(t,2.000)'"
+        ));
+        assert!(filter.contains("volume=0.2512:enable='between(t,2.000,4.000)'"));
+        assert!(filter.contains("enable='between(t,5.000,7.000)'"));
+        assert!(filter.contains("enable='gt(t,7.000)*lt(t,7.500)'"));
+
+        assert_eq!(ducking_envelope_filter(&[], &duck), "anull");
+    }
+
+    #[test]
+    fn test_resolution_ladder_steps_down_until_the_floor_is_met() {
+        let ladder = ResolutionLadder::default();
+        // 4K squeezed to ~1.2 Mbit/s: 1440p and 1080p and 720p are all too thin
+        assert_eq!(ladder.choose(3840, 2160, 30.0, 1237.0), Some(480));
+        // Enough bitrate for 1080p
+        assert_eq!(ladder.choose(3840, 2160, 30.0, 4000.0), Some(1080));
+        // Already fine at source size
+        assert_eq!(ladder.choose(1920, 1080, 30.0, 8000.0), None);
+        // Portrait sources step down their short side
+        assert_eq!(ladder.choose(1080, 1920, 30.0, 1500.0), Some(720));
+        assert_eq!(scaled_to_short_side(1080, 1920, 720), (720, 1280));
+
+        let floor_720 = ResolutionLadder { min_height: 720, enabled: true };
+        assert_eq!(floor_720.choose(3840, 2160, 30.0, 500.0), Some(720));
+        assert_eq!(ResolutionLadder::disabled().choose(3840, 2160, 30.0, 500.0), None);
+    }
+}
@@ -0,0 +1,319 @@
+// SYNOID Scan Cache - Reuse scans of unchanged inputs
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Scene detection, visual and audio scans, transcription and audio
+// enhancement all depend only on the input's bytes, yet every edit used to
+// redo them. Results are kept under cortex_cache/scans/<sha256>/, one JSON
+// file per kind of scan, so iterating on the intent for one video pays for
+// the analysis once. Scenes, visual and audio scans all come from one
+// combined `MediaAnalysis`, so a fresh input is decoded once for the three.
+// A changed file hashes differently and misses. Hashing a large video takes
+// a while too, so the hash is remembered against the file's path, size and
+// modification time in scans/index.json.
+
+use crate::agent::core_systems::project::sha256_file;
+use crate::agent::tools::audio_classify::{self, AudioWindow};
+use crate::agent::specialized::smart_editor::{self, Scene};
+use crate::agent::tools::audio_tools::AudioAnalysis;
+use crate::agent::tools::media_analysis::{MediaAnalysis, SCENE_FLOOR};
+use crate::agent::tools::vision_tools::VisualScene;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tracing::{info, warn};
+
+type ScanResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+pub fn scan_cache_dir() -> PathBuf {
+    PathBuf::from("cortex_cache").join("scans")
+}
+
+/// What a path looked like when it was last hashed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Fingerprint {
+    len: u64,
+    modified_ns: u128,
+    sha256: String,
+}
+
+pub struct ScanCache {
+    dir: PathBuf,
+}
+
+impl Default for ScanCache {
+    fn default() -> Self {
+        Self::new(scan_cache_dir())
+    }
+}
+
+impl ScanCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn index(&self) -> HashMap<String, Fingerprint> {
+        std::fs::read_to_string(self.dir.join("index.json"))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// Index entry name and current `(len, modified_ns)` of `input`.
+    fn stat(input: &Path, meta: &std::fs::Metadata) -> (String, u64, u128) {
+        let modified_ns = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_nanos());
+        let path = std::fs::canonicalize(input)
+            .unwrap_or_else(|_| input.to_path_buf())
+            .to_string_lossy()
+            .to_string();
+        (path, meta.len(), modified_ns)
+    }
+
+    /// Content hash of `input`, read from the index while the file's size
+    /// and modification time are unchanged.
+    pub async fn key(&self, input: &Path) -> io::Result<String> {
+        let meta = tokio::fs::metadata(input).await?;
+        let (path, len, modified_ns) = Self::stat(input, &meta);
+
+        let index_path = self.dir.join("index.json");
+        let mut index = self.index();
+        if let Some(known) = index.get(&path) {
+            if known.len == len && known.modified_ns == modified_ns {
+                return Ok(known.sha256.clone());
+            }
+        }
+
+        let owned = input.to_path_buf();
+        let sha256 = tokio::task::spawn_blocking(move || sha256_file(&owned))
+            .await
+            .map_err(io::Error::other)??;
+        index.insert(
+            path,
+            Fingerprint {
+                len,
+                modified_ns,
+                sha256: sha256.clone(),
+            },
+        );
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(&index_path, serde_json::to_string_pretty(&index)?)?;
+        Ok(sha256)
+    }
+
+    /// The hash `key` would return, if it is already in the index. Never
+    /// reads the file's contents, for callers that must not scan.
+    pub fn known_key(&self, input: &Path) -> Option<String> {
+        let meta = std::fs::metadata(input).ok()?;
+        let (path, len, modified_ns) = Self::stat(input, &meta);
+        self.index()
+            .remove(&path)
+            .filter(|known| known.len == len && known.modified_ns == modified_ns)
+            .map(|known| known.sha256)
+    }
+
+    /// A file slot for a scan product too big for JSON (e.g. a WAV), in the
+    /// directory for `key`.
+    pub fn artifact(&self, key: &str, name: &str) -> PathBuf {
+        let dir = self.dir.join(key);
+        let _ = std::fs::create_dir_all(&dir);
+        dir.join(name)
+    }
+
+    pub fn load<T: DeserializeOwned>(&self, key: &str, kind: &str) -> Option<T> {
+        let data =
+            std::fs::read_to_string(self.dir.join(key).join(format!("{}.json", kind))).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
+    pub fn store<T: Serialize>(&self, key: &str, kind: &str, value: &T) {
+        let path = self.artifact(key, &format!("{}.json", kind));
+        let written = serde_json::to_string(value)
+            .map_err(io::Error::other)
+            .and_then(|json| std::fs::write(&path, json));
+        if let Err(e) = written {
+            warn!("[SCANS] Could not cache {} scan at {:?}: {}", kind, path, e);
+        }
+    }
+
+    /// The `kind` scan of `input` from the cache, or from `scan` (which is
+    /// then cached). Failed scans are not cached, and an input that cannot be
+    /// hashed is scanned every time.
+    pub async fn get_or_scan<T, F, Fut>(&self, input: &Path, kind: &str, scan: F) -> ScanResult<T>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ScanResult<T>>,
+    {
+        let key = match self.key(input).await {
+            Ok(key) => key,
+            Err(e) => {
+                warn!(
+                    "[SCANS] Could not hash {:?} ({}); scanning uncached.",
+                    input, e
+                );
+                return scan().await;
+            }
+        };
+        if let Some(hit) = self.load(&key, kind) {
+            info!("[SCANS] ⚡ Reusing cached {} scan of {:?}", kind, input);
+            return Ok(hit);
+        }
+        let value = scan().await?;
+        self.store(&key, kind, &value);
+        Ok(value)
+    }
+}
+
+/// `MediaAnalysis::analyze`, cached.
+pub async fn analysis(input: &Path) -> ScanResult<MediaAnalysis> {
+    ScanCache::default()
+        .get_or_scan(input, "analysis", || MediaAnalysis::analyze(input))
+        .await
+}
+
+/// `smart_editor::detect_scenes`, from the cached analysis. Thresholds below
+/// its SCENE_FLOOR get their own pass, cached per threshold.
+pub async fn scenes(input: &Path, threshold: f64) -> ScanResult<Vec<Scene>> {
+    if threshold >= SCENE_FLOOR {
+        return Ok(analysis(input).await?.scenes(threshold));
+    }
+    ScanCache::default()
+        .get_or_scan(input, &format!("scenes_{:.3}", threshold), || {
+            smart_editor::detect_scenes(input, threshold)
+        })
+        .await
+}
+
+/// The scenes `scenes` would return for `input`, if an earlier edit cached
+/// them; never decodes or hashes the file.
+pub fn cached_scenes(input: &Path, threshold: f64) -> Option<Vec<Scene>> {
+    let cache = ScanCache::default();
+    let key = cache.known_key(input)?;
+    if threshold >= SCENE_FLOOR {
+        return Some(cache.load::<MediaAnalysis>(&key, "analysis")?.scenes(threshold));
+    }
+    cache.load(&key, &format!("scenes_{:.3}", threshold))
+}
+
+/// `vision_tools::scan_visual`, from the cached analysis.
+pub async fn visual(input: &Path) -> ScanResult<Vec<VisualScene>> {
+    Ok(analysis(input).await?.visual_scenes())
+}
+
+/// `audio_tools::scan_audio`, from the cached analysis.
+pub async fn audio(input: &Path) -> ScanResult<AudioAnalysis> {
+    Ok(analysis(input).await?.audio_analysis())
+}
+
+/// `audio_classify::classify`, cached.
+pub async fn audio_classes(input: &Path) -> ScanResult<Vec<AudioWindow>> {
+    ScanCache::default()
+        .get_or_scan(input, "audio_classes", || audio_classify::classify(input))
+        .await
+}
+
+/// The audio classes of `input` if an earlier edit cached them; never
+/// decodes or hashes the file.
+pub fn cached_audio_classes(input: &Path) -> Option<Vec<AudioWindow>> {
+    let cache = ScanCache::default();
+    cache.load(&cache.known_key(input)?, "audio_classes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn a_second_scan_of_an_unchanged_file_hits_the_cache() {
+        let root = std::env::temp_dir().join(format!("synoid_scan_cache_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        let input = root.join("take.mp4");
+        std::fs::write(&input, b"first take").unwrap();
+        let cache = ScanCache::new(root.join("scans"));
+
+        let scans = AtomicUsize::new(0);
+        let scan = || async {
+            let n = scans.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok::<_, Box<dyn std::error::Error + Send + Sync>>(vec![n as f64])
+        };
+
+        assert_eq!(
+            cache.get_or_scan(&input, "visual", scan).await.unwrap(),
+            vec![1.0]
+        );
+        assert_eq!(
+            cache.get_or_scan(&input, "visual", scan).await.unwrap(),
+            vec![1.0]
+        );
+        assert_eq!(
+            scans.load(Ordering::SeqCst),
+            1,
+            "second scan was not served from the cache"
+        );
+
+        // Other kinds of scan are cached separately
+        assert_eq!(
+            cache.get_or_scan(&input, "audio", scan).await.unwrap(),
+            vec![2.0]
+        );
+
+        // A changed file hashes differently and is scanned again
+        std::fs::write(&input, b"second take, re-exported").unwrap();
+        assert_eq!(
+            cache.get_or_scan(&input, "visual", scan).await.unwrap(),
+            vec![3.0]
+        );
+        assert_eq!(
+            cache.get_or_scan(&input, "visual", scan).await.unwrap(),
+            vec![3.0]
+        );
+
+        // Failures are not cached
+        let failing = || async { Err::<Vec<f64>, _>("ffmpeg missing".into()) };
+        assert!(cache
+            .get_or_scan(&input, "scenes_0.250", failing)
+            .await
+            .is_err());
+        assert_eq!(
+            cache
+                .get_or_scan(&input, "scenes_0.250", scan)
+                .await
+                .unwrap(),
+            vec![4.0]
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn the_hash_is_remembered_until_the_file_changes() {
+        let root = std::env::temp_dir().join(format!("synoid_scan_key_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        let input = root.join("take.mp4");
+        std::fs::write(&input, b"abc").unwrap();
+        let cache = ScanCache::new(root.join("scans"));
+
+        let key = cache.key(&input).await.unwrap();
+        assert_eq!(key, sha256_file(&input).unwrap());
+        assert!(root.join("scans").join("index.json").exists());
+        assert_eq!(cache.key(&input).await.unwrap(), key);
+        assert_eq!(cache.known_key(&input), Some(key.clone()));
+
+        std::fs::write(&input, b"abcd").unwrap();
+        assert_ne!(cache.known_key(&input), Some(key.clone()));
+        assert_ne!(cache.key(&input).await.unwrap(), key);
+        assert!(cache.key(&root.join("missing.mp4")).await.is_err());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,638 @@
+// SYNOID Search Providers - Pluggable Research Backends
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// `Research` used to be hard-wired to yt-dlp's YouTube search. Providers
+// implement `SearchProvider` and are looked up by name in a registry, so new
+// backends (a local index, a web search API) can be added without touching
+// the callers.
+//
+// YouTube throttles yt-dlp's search for hours at a time, so `youtube` is a
+// fallback chain: yt-dlp, then the Invidious API across a rotating list of
+// instances, then the last results saved for the same query (with a
+// staleness warning). Each result names the provider that served it, and
+// rate-limit responses are recorded so the learner can rest longer.
+
+use crate::agent::deep_research::topic_slug;
+use crate::agent::global_discovery::GlobalDiscovery;
+use crate::agent::tools::source_tools::{self, SourceInfo};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::Instant;
+use tracing::{info, warn};
+
+pub type SearchResult = Result<Vec<SourceInfo>, Box<dyn std::error::Error + Send + Sync>>;
+pub type SearchFuture<'a> = Pin<Box<dyn Future<Output = SearchResult> + Send + 'a>>;
+
+/// A backend that can answer research queries.
+pub trait SearchProvider: Send + Sync {
+    /// Name used to select the provider (`--provider <name>`).
+    fn name(&self) -> &str;
+
+    fn search<'a>(&'a self, query: &'a str, limit: usize) -> SearchFuture<'a>;
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Rate limits
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A provider refused the search because it is being throttled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimited {
+    pub provider: String,
+    pub detail: String,
+}
+
+impl RateLimited {
+    /// Also records the hit for [`rate_limited_since`].
+    pub fn new(provider: &str, detail: &str) -> Self {
+        *last_rate_limit().lock().unwrap_or_else(|e| e.into_inner()) = Some(Instant::now());
+        Self {
+            provider: provider.to_string(),
+            detail: detail.lines().last().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is rate-limited: {}", self.provider, self.detail)
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// Whether an error message reads like throttling rather than a real failure.
+pub fn looks_rate_limited(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    [
+        "429",
+        "too many requests",
+        "rate limit",
+        "rate-limit",
+        "ratelimit",
+        "not a bot",
+    ]
+    .iter()
+    .any(|marker| lower.contains(marker))
+}
+
+fn last_rate_limit() -> &'static Mutex<Option<Instant>> {
+    static LAST: OnceLock<Mutex<Option<Instant>>> = OnceLock::new();
+    LAST.get_or_init(|| Mutex::new(None))
+}
+
+/// True when any provider was rate-limited at or after `since`, even if a
+/// fallback then answered the search.
+pub fn rate_limited_since(since: Instant) -> bool {
+    last_rate_limit()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .is_some_and(|at| at >= since)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Built-in providers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// YouTube search through yt-dlp.
+pub struct YouTubeProvider;
+
+impl SearchProvider for YouTubeProvider {
+    fn name(&self) -> &str {
+        "yt-dlp"
+    }
+
+    fn search<'a>(&'a self, query: &'a str, limit: usize) -> SearchFuture<'a> {
+        Box::pin(source_tools::search_youtube(query, limit))
+    }
+}
+
+/// Searches videos already on disk (the learner's Download folder and the
+/// project's Video folder) by filename.
+pub struct LocalLibraryProvider {
+    pub roots: Vec<PathBuf>,
+}
+
+impl Default for LocalLibraryProvider {
+    fn default() -> Self {
+        Self {
+            roots: vec![
+                crate::agent::video_style_learner::get_download_dir(),
+                PathBuf::from("Video"),
+            ],
+        }
+    }
+}
+
+const LOCAL_VIDEO_EXTENSIONS: &[&str] = &["mp4", "mov", "mkv", "avi", "webm"];
+
+impl SearchProvider for LocalLibraryProvider {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    fn search<'a>(&'a self, query: &'a str, limit: usize) -> SearchFuture<'a> {
+        Box::pin(async move {
+            let mut discovery = GlobalDiscovery::new();
+            discovery.search_paths = self.roots.iter().filter(|r| r.exists()).cloned().collect();
+            discovery.scan().await;
+
+            let words: Vec<String> = query
+                .split_whitespace()
+                .map(|w| w.to_lowercase())
+                .collect();
+            // Rank by how many query words appear in the filename
+            let mut hits: Vec<(usize, _)> = discovery
+                .get_all()
+                .await
+                .into_iter()
+                .filter(|f| LOCAL_VIDEO_EXTENSIONS.contains(&f.extension.as_str()))
+                .map(|f| {
+                    let name = f.name.to_lowercase();
+                    (words.iter().filter(|w| name.contains(w.as_str())).count(), f)
+                })
+                .filter(|(score, _)| *score > 0)
+                .collect();
+            hits.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+
+            let mut results = Vec::new();
+            for (_, file) in hits.into_iter().take(limit) {
+                let duration = source_tools::get_video_duration(&file.path)
+                    .await
+                    .unwrap_or(0.0);
+                results.push(SourceInfo {
+                    title: file.name,
+                    duration,
+                    width: 0,
+                    height: 0,
+                    local_path: file.path,
+                    original_url: None,
+                    format: file.extension,
+                    provider: "local".to_string(),
+                });
+            }
+            info!("[SEARCH] Local library: {} match(es) for '{}'", results.len(), query);
+            Ok(results)
+        })
+    }
+}
+
+/// Public instances tried when SYNOID_INVIDIOUS_URLS is unset.
+pub const DEFAULT_INVIDIOUS_URLS: &[&str] = &[
+    "https://inv.nadeko.net",
+    "https://yewtu.be",
+    "https://invidious.nerdvpn.de",
+];
+
+/// YouTube search through the Invidious API. Instances come and go, so a
+/// failing one hands over to the next, and the last one that answered is
+/// tried first next time.
+pub struct InvidiousProvider {
+    pub instances: Vec<String>,
+    next: AtomicUsize,
+}
+
+impl InvidiousProvider {
+    pub fn new(instances: Vec<String>) -> Self {
+        Self {
+            instances,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// The configured `invidious_urls`, or the defaults.
+    pub fn from_config() -> Self {
+        let configured = crate::config::current().invidious_urls;
+        if configured.is_empty() {
+            Self::new(DEFAULT_INVIDIOUS_URLS.iter().map(|u| u.to_string()).collect())
+        } else {
+            Self::new(configured)
+        }
+    }
+
+    async fn search_instance(
+        client: &reqwest::Client,
+        instance: &str,
+        query: &str,
+        limit: usize,
+    ) -> SearchResult {
+        let url = format!(
+            "{}/api/v1/search?q={}&type=video",
+            instance,
+            urlencoding::encode(query)
+        );
+        let response = client.get(&url).send().await?;
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(RateLimited::new("invidious", &format!("{} answered 429", instance)).into());
+        }
+        if !response.status().is_success() {
+            return Err(format!("{} answered {}", instance, response.status()).into());
+        }
+        Ok(parse_invidious(&response.json().await?, limit))
+    }
+}
+
+impl SearchProvider for InvidiousProvider {
+    fn name(&self) -> &str {
+        "invidious"
+    }
+
+    fn search<'a>(&'a self, query: &'a str, limit: usize) -> SearchFuture<'a> {
+        Box::pin(async move {
+            if self.instances.is_empty() {
+                return Err("No Invidious instances configured".into());
+            }
+            let client = reqwest::Client::builder()
+                .connect_timeout(std::time::Duration::from_secs(10))
+                .timeout(std::time::Duration::from_secs(20))
+                .build()?;
+            let start = self.next.load(Ordering::Relaxed);
+            let mut errors = Vec::new();
+            for i in 0..self.instances.len() {
+                let index = (start + i) % self.instances.len();
+                let instance = &self.instances[index];
+                match Self::search_instance(&client, instance, query, limit).await {
+                    Ok(results) => {
+                        self.next.store(index, Ordering::Relaxed);
+                        info!("[SEARCH] Invidious ({}): {} result(s)", instance, results.len());
+                        return Ok(results);
+                    }
+                    Err(e) => {
+                        warn!("[SEARCH] ⚠️ Invidious instance {} failed: {}", instance, e);
+                        errors.push(e.to_string());
+                    }
+                }
+            }
+            Err(format!("All Invidious instances failed: {}", errors.join("; ")).into())
+        })
+    }
+}
+
+/// Videos from an Invidious `/api/v1/search` response, shaped like yt-dlp's.
+pub fn parse_invidious(json: &serde_json::Value, limit: usize) -> Vec<SourceInfo> {
+    json.as_array()
+        .into_iter()
+        .flatten()
+        .filter(|item| item["type"].as_str().is_none_or(|t| t == "video"))
+        .filter_map(|item| {
+            let id = item["videoId"].as_str().filter(|id| !id.is_empty())?;
+            let duration = item["lengthSeconds"].as_f64().unwrap_or(0.0);
+            // Live streams and premieres report 0, as they do through yt-dlp
+            (duration > 0.0).then(|| SourceInfo {
+                title: item["title"].as_str().unwrap_or_default().to_string(),
+                duration,
+                width: 0,
+                height: 0,
+                local_path: PathBuf::new(),
+                original_url: Some(format!("https://www.youtube.com/watch?v={}", id)),
+                format: "online".to_string(),
+                provider: "invidious".to_string(),
+            })
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Cached results older than this come with a staleness warning.
+pub const SEARCH_CACHE_STALE_HOURS: i64 = 24;
+
+/// The last live results for a query, kept for when every live provider fails.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedSearch {
+    pub query: String,
+    pub saved_at: DateTime<Utc>,
+    pub results: Vec<SourceInfo>,
+}
+
+/// Answers from [`CachedSearch`] files in `dir`.
+pub struct SearchCacheProvider {
+    pub dir: PathBuf,
+}
+
+impl Default for SearchCacheProvider {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from("cortex_cache").join("search"),
+        }
+    }
+}
+
+impl SearchCacheProvider {
+    fn path(&self, query: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", topic_slug(query)))
+    }
+
+    pub fn load(&self, query: &str) -> Option<CachedSearch> {
+        serde_json::from_str(&std::fs::read_to_string(self.path(query)).ok()?).ok()
+    }
+
+    pub fn save(&self, query: &str, results: &[SourceInfo]) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let cached = CachedSearch {
+            query: query.to_string(),
+            saved_at: Utc::now(),
+            results: results.to_vec(),
+        };
+        let json = serde_json::to_string_pretty(&cached)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(self.path(query), json)
+    }
+}
+
+impl SearchProvider for SearchCacheProvider {
+    fn name(&self) -> &str {
+        "cache"
+    }
+
+    fn search<'a>(&'a self, query: &'a str, limit: usize) -> SearchFuture<'a> {
+        Box::pin(async move {
+            let cached = self
+                .load(query)
+                .ok_or_else(|| format!("No cached results for '{}'", query))?;
+            let age = Utc::now().signed_duration_since(cached.saved_at);
+            if age.num_hours() >= SEARCH_CACHE_STALE_HOURS {
+                warn!(
+                    "[SEARCH] ⚠️ Using results for '{}' cached {} day(s) ago; they may be stale",
+                    query,
+                    age.num_days().max(1)
+                );
+            } else {
+                info!("[SEARCH] Using results for '{}' cached {}h ago", query, age.num_hours());
+            }
+            Ok(cached
+                .results
+                .into_iter()
+                .take(limit)
+                .map(|source| SourceInfo {
+                    provider: "cache".to_string(),
+                    ..source
+                })
+                .collect())
+        })
+    }
+}
+
+/// Tries `providers` in order until one returns results. Live results are
+/// saved to `cache` so a later outage can fall back on them.
+pub struct FallbackProvider {
+    pub name: String,
+    pub providers: Vec<Arc<dyn SearchProvider>>,
+    pub cache: Option<Arc<SearchCacheProvider>>,
+}
+
+impl FallbackProvider {
+    /// `youtube`: yt-dlp, then Invidious, then the cache.
+    pub fn youtube() -> Self {
+        let cache = Arc::new(SearchCacheProvider::default());
+        Self {
+            name: "youtube".to_string(),
+            providers: vec![
+                Arc::new(YouTubeProvider),
+                Arc::new(InvidiousProvider::from_config()),
+                cache.clone(),
+            ],
+            cache: Some(cache),
+        }
+    }
+}
+
+impl SearchProvider for FallbackProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn search<'a>(&'a self, query: &'a str, limit: usize) -> SearchFuture<'a> {
+        Box::pin(async move {
+            let mut errors = Vec::new();
+            for provider in &self.providers {
+                match provider.search(query, limit).await {
+                    Ok(mut results) if !results.is_empty() => {
+                        for result in results.iter_mut().filter(|r| r.provider.is_empty()) {
+                            result.provider = provider.name().to_string();
+                        }
+                        let from_cache = self
+                            .cache
+                            .as_ref()
+                            .is_some_and(|c| c.name() == provider.name());
+                        if let (Some(cache), false) = (&self.cache, from_cache) {
+                            if let Err(e) = cache.save(query, &results) {
+                                warn!("[SEARCH] ⚠️ Could not cache results: {}", e);
+                            }
+                        }
+                        return Ok(results);
+                    }
+                    Ok(_) => errors.push(format!("{}: no results", provider.name())),
+                    Err(e) => {
+                        warn!("[SEARCH] ⚠️ {} search failed, falling back: {}", provider.name(), e);
+                        errors.push(format!("{}: {}", provider.name(), e));
+                    }
+                }
+            }
+            Err(format!("{} search failed ({})", self.name, errors.join("; ")).into())
+        })
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Registry
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Providers by name.
+pub struct SearchRegistry {
+    providers: BTreeMap<String, Arc<dyn SearchProvider>>,
+}
+
+impl Default for SearchRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Arc::new(FallbackProvider::youtube()));
+        registry.register(Arc::new(YouTubeProvider));
+        registry.register(Arc::new(InvidiousProvider::from_config()));
+        registry.register(Arc::new(SearchCacheProvider::default()));
+        registry.register(Arc::new(LocalLibraryProvider::default()));
+        registry
+    }
+}
+
+impl SearchRegistry {
+    pub fn empty() -> Self {
+        Self {
+            providers: BTreeMap::new(),
+        }
+    }
+
+    /// Add a provider, replacing any existing one with the same name.
+    pub fn register(&mut self, provider: Arc<dyn SearchProvider>) {
+        self.providers
+            .insert(provider.name().to_lowercase(), provider);
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn SearchProvider>> {
+        self.providers.get(&name.to_lowercase()).cloned()
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.providers.keys().cloned().collect()
+    }
+
+    pub async fn search(&self, provider: &str, query: &str, limit: usize) -> SearchResult {
+        let backend = self.get(provider).ok_or_else(|| {
+            format!(
+                "Unknown search provider '{}' (available: {})",
+                provider,
+                self.names().join(", ")
+            )
+        })?;
+        backend.search(query, limit).await
+    }
+}
+
+fn global_registry() -> &'static RwLock<SearchRegistry> {
+    static REGISTRY: OnceLock<RwLock<SearchRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(SearchRegistry::default()))
+}
+
+/// Make a provider available process-wide.
+pub fn register_provider(provider: Arc<dyn SearchProvider>) {
+    global_registry()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .register(provider);
+}
+
+/// Search with the named process-wide provider.
+pub async fn search(provider: &str, query: &str, limit: usize) -> SearchResult {
+    let backend = global_registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(provider);
+    match backend {
+        Some(backend) => backend.search(query, limit).await,
+        // Fall through to the registry's error message
+        None => SearchRegistry::empty().search(provider, query, limit).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockProvider;
+
+    impl SearchProvider for MockProvider {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        fn search<'a>(&'a self, query: &'a str, limit: usize) -> SearchFuture<'a> {
+            Box::pin(async move {
+                Ok((0..limit)
+                    .map(|i| SourceInfo {
+                        title: format!("{} #{}", query, i),
+                        duration: 60.0,
+                        width: 0,
+                        height: 0,
+                        local_path: PathBuf::new(),
+                        original_url: None,
+                        format: "mock".to_string(),
+                        provider: String::new(),
+                    })
+                    .collect())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn registered_provider_receives_search_calls() {
+        let mut registry = SearchRegistry::default();
+        registry.register(Arc::new(MockProvider));
+        assert!(registry.names().contains(&"youtube".to_string()));
+
+        let results = registry.search("MOCK", "match cuts", 2).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].title, "match cuts #0");
+
+        assert!(registry.search("nope", "x", 1).await.is_err());
+
+        register_provider(Arc::new(MockProvider));
+        assert_eq!(search("mock", "global", 1).await.unwrap()[0].format, "mock");
+    }
+
+    /// Always throttled, like yt-dlp during a YouTube search outage.
+    struct ThrottledProvider;
+
+    impl SearchProvider for ThrottledProvider {
+        fn name(&self) -> &str {
+            "throttled"
+        }
+
+        fn search<'a>(&'a self, _query: &'a str, _limit: usize) -> SearchFuture<'a> {
+            Box::pin(async move {
+                Err(RateLimited::new("throttled", "ERROR: HTTP Error 429: Too Many Requests").into())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn throttled_searches_fall_back_and_then_use_the_cache() {
+        let dir = std::env::temp_dir().join(format!("synoid_search_cache_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let cache = Arc::new(SearchCacheProvider { dir: dir.clone() });
+        let started = Instant::now();
+
+        let chain = FallbackProvider {
+            name: "youtube".to_string(),
+            providers: vec![Arc::new(ThrottledProvider), Arc::new(MockProvider), cache.clone()],
+            cache: Some(cache.clone()),
+        };
+        let results = chain.search("match cuts", 2).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.provider == "mock"));
+        assert!(rate_limited_since(started));
+
+        // With every live provider down, the saved results answer
+        let outage = FallbackProvider {
+            name: "youtube".to_string(),
+            providers: vec![Arc::new(ThrottledProvider), cache.clone()],
+            cache: Some(cache.clone()),
+        };
+        let cached = outage.search("Match Cuts", 5).await.unwrap();
+        assert_eq!(cached.len(), 2);
+        assert_eq!(cached[0].title, "match cuts #0");
+        assert!(cached.iter().all(|r| r.provider == "cache"));
+
+        let err = outage.search("never searched", 5).await.unwrap_err().to_string();
+        assert!(err.contains("throttled is rate-limited"), "{}", err);
+        assert!(err.contains("No cached results"), "{}", err);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn invidious_results_look_like_yt_dlp_results() {
+        let json = serde_json::json!([
+            {"type": "video", "title": "Match cuts explained", "videoId": "abc123", "lengthSeconds": 412},
+            {"type": "channel", "author": "Editors"},
+            {"type": "video", "title": "Live now", "videoId": "live1", "lengthSeconds": 0},
+            {"type": "video", "title": "J and L cuts", "videoId": "def456", "lengthSeconds": 95}
+        ]);
+        let results = parse_invidious(&json, 5);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].original_url.as_deref(), Some("https://www.youtube.com/watch?v=abc123"));
+        assert_eq!(results[0].duration, 412.0);
+        assert_eq!(results[1].title, "J and L cuts");
+        assert!(results.iter().all(|r| r.provider == "invidious"));
+        assert_eq!(parse_invidious(&json, 1).len(), 1);
+        assert!(parse_invidious(&serde_json::json!({"error": "blocked"}), 5).is_empty());
+
+        assert!(looks_rate_limited("ERROR: [youtube:search] HTTP Error 429: Too Many Requests"));
+        assert!(looks_rate_limited("Sign in to confirm you're not a bot"));
+        assert!(!looks_rate_limited("ERROR: Unable to download webpage: timed out"));
+    }
+}
@@ -8,11 +8,18 @@
 // 4. YouTube Search via ytsearch
 
 use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::security::download_guard::DownloadGuard;
 use crate::agent::tools::production_tools::safe_arg_path;
+use crate::agent::tools::search_providers;
 use std::path::{Path, PathBuf};
-use tokio::process::Command;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, BufReader};
 use tracing::info;
 
+/// Receives download progress as a percentage (0–100).
+pub type DownloadProgress = Arc<dyn Fn(f32) + Send + Sync>;
+
 pub(crate) fn sanitize_title_for_filename(title: &str) -> String {
     let sanitized: String = title
         .chars()
@@ -56,7 +63,8 @@ fn find_deno_path() -> Option<String> {
 
     // Fallback: resolve via PATH
     let resolver = if cfg!(windows) { "where" } else { "which" };
-    if let Ok(out) = std::process::Command::new(resolver).stealth().arg("deno").output() {
+    // Unmanaged: a PATH lookup
+    if let Ok(out) = proc::blocking_unmanaged(resolver).stealth().arg("deno").output() {
         if out.status.success() {
             let path = String::from_utf8_lossy(&out.stdout)
                 .lines()
@@ -76,7 +84,7 @@ fn find_deno_path() -> Option<String> {
 }
 
 #[allow(dead_code)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SourceInfo {
     pub title: String,
     pub duration: f64,
@@ -85,6 +93,10 @@ pub struct SourceInfo {
     pub local_path: PathBuf,
     pub original_url: Option<String>,
     pub format: String,
+    /// Search provider that found it (`yt-dlp`, `invidious`, `cache`,
+    /// `local`); empty for downloads.
+    #[serde(default)]
+    pub provider: String,
 }
 
 /// Find the available python command (python3, python, or py).
@@ -106,10 +118,12 @@ pub async fn get_python_command() -> String {
             "~/.local/bin/yt-dlp",
         ]
     };
+    // Unmanaged below: `--version` probes locate the tools and must not
+    // queue behind running downloads
     for &bin in standalone_candidates {
         // Toki's Command on Windows might fail to execute python scripts with shebangs if running in some mixed WSL setups.
         // First try it natively.
-        match Command::new(bin).stealth().arg("--version").output().await {
+        match proc::unmanaged(bin).stealth().arg("--version").output().await {
             Ok(output) => {
                 if output.status.success() {
                     tracing::info!("[SOURCE] ✅ Found standalone 'yt-dlp' binary at '{}'", bin);
@@ -131,7 +145,7 @@ pub async fn get_python_command() -> String {
                 // If execution failed (e.g., Exec format error or not found), try explicitly with python3
                 if e.kind() != std::io::ErrorKind::NotFound {
                     tracing::info!("[SOURCE] Trying to execute '{}' via python3...", bin);
-                    if let Ok(py_out) = Command::new("python3")
+                    if let Ok(py_out) = proc::unmanaged("python3")
                         .stealth()
                         .arg(bin)
                         .arg("--version")
@@ -153,12 +167,12 @@ pub async fn get_python_command() -> String {
     }
 
     // 1.5 Try to find yt-dlp using 'which'
-    if let Ok(output) = Command::new("which").stealth().arg("yt-dlp").output().await {
+    if let Ok(output) = proc::unmanaged("which").stealth().arg("yt-dlp").output().await {
         if output.status.success() {
             let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
             if !path.is_empty() {
                 // Test the found path
-                match Command::new(&path).stealth().arg("--version").output().await {
+                match proc::unmanaged(&path).stealth().arg("--version").output().await {
                     Ok(out) => {
                         if out.status.success() {
                             tracing::info!(
@@ -198,7 +212,7 @@ pub async fn get_python_command() -> String {
     for cmd in candidates {
         // Check if command exists
         let check_args = vec!["--version"];
-        match Command::new(cmd).stealth().args(&check_args).output().await {
+        match proc::unmanaged(cmd).stealth().args(&check_args).output().await {
             Ok(output) => {
                 if output.status.success() {
                     // Command exists, record it as a fallback
@@ -208,7 +222,7 @@ pub async fn get_python_command() -> String {
 
                     // Now check for yt-dlp module
                     let module_args = vec!["-m", "yt_dlp", "--version"];
-                    match Command::new(cmd).stealth().args(&module_args).output().await {
+                    match proc::unmanaged(cmd).stealth().args(&module_args).output().await {
                         Ok(mod_out) => {
                             if mod_out.status.success() {
                                 tracing::info!(
@@ -259,7 +273,8 @@ pub async fn check_ytdlp() -> bool {
     }
 
     // Otherwise it's a python interpreter, check module
-    Command::new(&cmd)
+    // Unmanaged: a `--version` probe, not a download
+    proc::unmanaged(&cmd)
         .stealth()
         .args(["-m", "yt_dlp", "--version"])
         .stdin(std::process::Stdio::null())
@@ -269,10 +284,61 @@ pub async fn check_ytdlp() -> bool {
         .unwrap_or(false)
 }
 
+/// Network options for yt-dlp: bandwidth cap and proxy.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DownloadOptions {
+    /// Maximum download rate in yt-dlp syntax (e.g. `2M`, `500K`).
+    pub limit_rate: Option<String>,
+    /// Proxy URL (e.g. `socks5://127.0.0.1:1080`).
+    pub proxy: Option<String>,
+}
+
+impl DownloadOptions {
+    /// Defaults from the config: the `proxy` setting.
+    pub fn from_config() -> Self {
+        Self {
+            limit_rate: None,
+            proxy: crate::config::current().proxy,
+        }
+    }
+
+    /// Config defaults with explicit (e.g. CLI) values taking precedence.
+    pub fn with_overrides(limit_rate: Option<String>, proxy: Option<String>) -> Self {
+        let env = Self::from_config();
+        Self {
+            limit_rate: limit_rate.or(env.limit_rate),
+            proxy: proxy.or(env.proxy),
+        }
+    }
+
+    fn push_proxy_arg(
+        &self,
+        args: &mut Vec<String>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(proxy) = &self.proxy {
+            reject_flag_like("Proxy", proxy)?;
+            args.push("--proxy".to_string());
+            args.push(proxy.clone());
+        }
+        Ok(())
+    }
+}
+
+fn reject_flag_like(
+    what: &str,
+    value: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if value.starts_with('-') {
+        return Err(format!("{} cannot start with '-'", what).into());
+    }
+    Ok(())
+}
+
 fn build_ytdlp_info_args(
     command: &str,
     url: &str,
     auth_browser: Option<&str>,
+    options: &DownloadOptions,
 ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
     let mut args = Vec::new();
 
@@ -317,17 +383,133 @@ fn build_ytdlp_info_args(
         "--no-download".to_string(),
     ]);
 
+    // The info fetch goes over the same network path as the download
+    options.push_proxy_arg(&mut args)?;
+
     args.push("--".to_string());
     args.push(url.to_string());
 
     Ok(args)
 }
 
+/// yt-dlp arguments that fetch only the text around a video: English
+/// auto-subtitles as VTT and, with `max_comments`, the top comments in the
+/// info JSON. Files land at `<output_stem>.*`.
+fn build_ytdlp_text_args(
+    command: &str,
+    url: &str,
+    output_stem: &Path,
+    auth_browser: Option<&str>,
+    options: &DownloadOptions,
+    max_comments: Option<usize>,
+) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut args = Vec::new();
+    if !command.ends_with("yt-dlp") {
+        args.push("-m".to_string());
+        args.push("yt_dlp".to_string());
+    }
+    if let Some(deno) = find_deno_path() {
+        args.push("--js-runtimes".to_string());
+        args.push(format!("deno:{}", deno));
+    }
+
+    let mut extractor = Vec::new();
+    if let Some(browser) = auth_browser {
+        reject_flag_like("Browser name", browser)?;
+        args.push("--cookies-from-browser".to_string());
+        args.push(browser.to_string());
+    } else {
+        extractor.push("player_client=ios,android".to_string());
+    }
+    if let Some(n) = max_comments {
+        extractor.push(format!("max_comments={},all,0,0", n));
+        extractor.push("comment_sort=top".to_string());
+        args.push("--write-comments".to_string());
+        args.push("--write-info-json".to_string());
+    }
+    if !extractor.is_empty() {
+        args.push("--extractor-args".to_string());
+        args.push(format!("youtube:{}", extractor.join(";")));
+    }
+
+    args.extend_from_slice(&[
+        "--no-warnings".to_string(),
+        "--skip-download".to_string(),
+        "--write-auto-subs".to_string(),
+        "--sub-langs".to_string(),
+        "en.*,en".to_string(),
+        "--sub-format".to_string(),
+        "vtt".to_string(),
+        "-o".to_string(),
+        format!("{}.%(ext)s", output_stem.display()),
+    ]);
+    options.push_proxy_arg(&mut args)?;
+    args.push("--".to_string());
+    args.push(url.to_string());
+    Ok(args)
+}
+
+/// Fetch a video's auto-subtitles (and top comments when `max_comments` is
+/// set) without downloading the video. Returns the files written next to
+/// `output_stem`: the `.vtt` subtitles and the `.info.json` with comments.
+pub async fn fetch_video_text(
+    url: &str,
+    output_stem: &Path,
+    max_comments: Option<usize>,
+) -> Result<(Option<PathBuf>, Option<PathBuf>), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(parent) = output_stem.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let python = get_python_command().await;
+    let browser = detect_browser();
+    let args = build_ytdlp_text_args(
+        &python,
+        url,
+        output_stem,
+        browser.as_deref(),
+        &DownloadOptions::from_config(),
+        max_comments,
+    )?;
+
+    let mut cmd = proc::command(&python, ProcClass::Download);
+    cmd.stealth().args(&args).stdin(std::process::Stdio::null());
+    let output = tokio::time::timeout(tokio::time::Duration::from_secs(180), cmd.output())
+        .await
+        .map_err(|_| "yt-dlp subtitle fetch timed out after 180s".to_string())??;
+    if !output.status.success() {
+        return Err(format!(
+            "yt-dlp subtitle fetch failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+        .into());
+    }
+
+    // Subtitles are named `<stem>.<lang>.vtt`; the language varies
+    let stem_name = output_stem
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut subs = None;
+    if let Some(dir) = output_stem.parent() {
+        let mut entries = tokio::fs::read_dir(dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with(&format!("{}.", stem_name)) && name.ends_with(".vtt") {
+                subs = Some(entry.path());
+                break;
+            }
+        }
+    }
+    let info = PathBuf::from(format!("{}.info.json", output_stem.display()));
+    Ok((subs, info.exists().then_some(info)))
+}
+
 fn build_ytdlp_download_args(
     command: &str,
     url: &str,
     output_path: &Path,
     auth_browser: Option<&str>,
+    options: &DownloadOptions,
 ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
     let mut args = Vec::new();
 
@@ -358,23 +540,96 @@ fn build_ytdlp_download_args(
 
     args.extend_from_slice(&[
         "--no-warnings".to_string(),
+        // Resume a previous partial download instead of starting over
+        "--continue".to_string(),
+        // One progress line per update instead of carriage-return redraws
+        "--newline".to_string(),
         "-f".to_string(),
         "bestvideo[ext=mp4]+bestaudio[ext=m4a]/best[ext=mp4]/best".to_string(),
         "-o".to_string(),
         safe_arg_path(output_path).to_string_lossy().to_string(),
     ]);
 
+    options.push_proxy_arg(&mut args)?;
+    if let Some(rate) = &options.limit_rate {
+        reject_flag_like("Rate limit", rate)?;
+        args.push("--limit-rate".to_string());
+        args.push(rate.clone());
+    }
+
     args.push("--".to_string());
     args.push(url.to_string());
 
     Ok(args)
 }
 
+/// Extract the percentage from a yt-dlp progress line such as
+/// `[download]  42.7% of 120.50MiB at 2.30MiB/s ETA 00:41`.
+pub fn parse_ytdlp_progress(line: &str) -> Option<f32> {
+    let rest = line.trim().strip_prefix("[download]")?.trim_start();
+    let (pct, _) = rest.split_once('%')?;
+    let pct: f32 = pct.trim().parse().ok()?;
+    Some(pct.clamp(0.0, 100.0))
+}
+
+/// How many times a download is attempted before a corrupt result is reported.
+const DOWNLOAD_ATTEMPTS: u32 = 3;
+
+/// Run one yt-dlp download, forwarding progress lines to `progress`.
+async fn run_ytdlp_download(
+    python: &str,
+    download_args: &[String],
+    progress: Option<&DownloadProgress>,
+) -> Result<std::process::ExitStatus, Box<dyn std::error::Error + Send + Sync>> {
+    let mut child = proc::command(python, ProcClass::Download)
+        .stealth()
+        .args(download_args)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .await?;
+    let stdout = child.stdout.take();
+
+    let download = async {
+        if let Some(stdout) = stdout {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if let (Some(pct), Some(cb)) = (parse_ytdlp_progress(&line), progress) {
+                    cb(pct);
+                }
+            }
+        }
+        child.wait().await
+    };
+
+    let status = tokio::time::timeout(
+        tokio::time::Duration::from_secs(1800), // 30 mins
+        download,
+    )
+    .await
+    .map_err(|_| format!("yt-dlp download command timed out"))??;
+    Ok(status)
+}
+
 /// Download a YouTube video using yt-dlp
 pub async fn download_youtube(
     url: &str,
     output_dir: &Path,
     auth_browser: Option<&str>,
+) -> Result<SourceInfo, Box<dyn std::error::Error + Send + Sync>> {
+    download_youtube_with_progress(url, output_dir, auth_browser, &DownloadOptions::from_config(), None)
+        .await
+}
+
+/// Download a YouTube video using yt-dlp, forwarding the download percentage
+/// to `progress` as yt-dlp reports it.
+pub async fn download_youtube_with_progress(
+    url: &str,
+    output_dir: &Path,
+    auth_browser: Option<&str>,
+    options: &DownloadOptions,
+    progress: Option<DownloadProgress>,
 ) -> Result<SourceInfo, Box<dyn std::error::Error + Send + Sync>> {
     info!(
         "[SOURCE] Downloading from YouTube: {} (Auth: {:?})",
@@ -386,16 +641,17 @@ pub async fn download_youtube(
 
     // Construct info arguments using helper
     let python = get_python_command().await; // Get command ONCE
-    let args = build_ytdlp_info_args(&python, url, auth_browser)?;
+    let args = build_ytdlp_info_args(&python, url, auth_browser, options)?;
 
     // First, get video info without downloading
+    let mut info_cmd = proc::command(&python, ProcClass::Download);
+    info_cmd
+        .stealth()
+        .args(&args)
+        .stdin(std::process::Stdio::null());
     let info_output = tokio::time::timeout(
         tokio::time::Duration::from_secs(120),
-        Command::new(&python)
-            .stealth()
-            .args(&args)
-            .stdin(std::process::Stdio::null())
-            .output(),
+        info_cmd.output(),
     )
     .await
     .map_err(|_| format!("yt-dlp info command timed out after 120s"))??;
@@ -423,23 +679,40 @@ pub async fn download_youtube(
     let output_template = output_path.to_string_lossy().to_string();
 
     // Construct download arguments using helper
-    let download_args = build_ytdlp_download_args(&python, url, &output_path, auth_browser)?;
+    let download_args =
+        build_ytdlp_download_args(&python, url, &output_path, auth_browser, options)?;
 
     info!("[SOURCE] Starting download to: {}", output_template);
-    // Reuse python command
-    let status = tokio::time::timeout(
-        tokio::time::Duration::from_secs(1800), // 30 mins
-        Command::new(&python)
-            .stealth()
-            .args(&download_args)
-            .stdin(std::process::Stdio::null())
-            .status(),
-    )
-    .await
-    .map_err(|_| format!("yt-dlp download command timed out"))??;
+    let mut attempt = 1;
+    loop {
+        let status = run_ytdlp_download(&python, &download_args, progress.as_ref()).await?;
+        if !status.success() {
+            return Err("Download process failed".into());
+        }
 
-    if !status.success() {
-        return Err("Download process failed".into());
+        // A truncated or corrupt file must never reach the pipeline
+        let check_path = output_path.clone();
+        let expected = (duration > 0.0).then_some(duration);
+        let verdict = tokio::task::spawn_blocking(move || {
+            DownloadGuard::check_decodable(&check_path, expected)
+        })
+        .await?;
+        match verdict {
+            Ok(_) => break,
+            Err(e) if attempt < DOWNLOAD_ATTEMPTS => {
+                tracing::warn!(
+                    "[SOURCE] ⚠️ Downloaded file failed integrity check ({}), retrying ({}/{})",
+                    e, attempt, DOWNLOAD_ATTEMPTS
+                );
+                // Resuming onto a corrupt file would just reproduce it
+                let _ = tokio::fs::remove_file(&output_path).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&output_path).await;
+                return Err(format!("Downloaded file is corrupt after {} attempts: {}", attempt, e).into());
+            }
+        }
     }
 
     Ok(SourceInfo {
@@ -450,14 +723,15 @@ pub async fn download_youtube(
         local_path: output_path,
         original_url: Some(url.to_string()),
         format: "mp4".to_string(),
+        provider: String::new(),
     })
 }
 
 /// Detect the first available browser for --cookies-from-browser on this machine.
 /// Returns e.g. "chrome", "edge", "firefox", or None if nothing found.
 pub fn detect_browser() -> Option<String> {
-    // Check env override first
-    if let Ok(b) = std::env::var("SYNOID_BROWSER") {
+    // The configured `browser` wins
+    if let Some(b) = crate::config::current().browser {
         return Some(b);
     }
 
@@ -543,24 +817,25 @@ pub async fn search_youtube(
     ]);
     args.push(search_query);
 
+    let mut search_cmd = proc::command(&python, ProcClass::Download);
+    search_cmd
+        .stealth()
+        .args(&args)
+        .stdin(std::process::Stdio::null());
     let output = tokio::time::timeout(
         tokio::time::Duration::from_secs(120),
-        Command::new(&python)
-            .stealth()
-            .args(&args)
-            .stdin(std::process::Stdio::null())
-            .output(),
+        search_cmd.output(),
     )
     .await
     .map_err(|_| format!("Search command timed out after 120s"))??;
 
     if !output.status.success() {
-        return Err(format!(
-            "Search failed with command '{}': {}",
-            python,
-            String::from_utf8_lossy(&output.stderr)
-        )
-        .into());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // Throttling is worth telling apart: the caller falls back and backs off
+        if search_providers::looks_rate_limited(&stderr) {
+            return Err(search_providers::RateLimited::new("yt-dlp", stderr.trim()).into());
+        }
+        return Err(format!("Search failed with command '{}': {}", python, stderr).into());
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -589,6 +864,7 @@ pub async fn search_youtube(
                     local_path: PathBuf::new(),
                     original_url: Some(url),
                     format: "online".to_string(),
+                    provider: "yt-dlp".to_string(),
                 });
             }
         }
@@ -608,7 +884,7 @@ pub async fn get_video_duration(
     // Getting duration from header is usually instant.
     let output = tokio::time::timeout(
         tokio::time::Duration::from_secs(10),
-        Command::new("ffprobe")
+        proc::ffprobe()
             .stealth()
             .kill_on_drop(true) // Ensure process is killed if timeout occurs
             .args([
@@ -737,26 +1013,48 @@ pub async fn web_search(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_ytdlp_progress() {
+        assert_eq!(
+            parse_ytdlp_progress("[download]  42.7% of  120.50MiB at  2.30MiB/s ETA 00:41"),
+            Some(42.7)
+        );
+        assert_eq!(
+            parse_ytdlp_progress("[download] 100% of 120.50MiB in 00:00:52 at 2.31MiB/s"),
+            Some(100.0)
+        );
+        assert_eq!(parse_ytdlp_progress("[download]   0.0% of ~  3.10GiB at Unknown B/s"), Some(0.0));
+        assert_eq!(parse_ytdlp_progress("[download] Destination: clip.mp4"), None);
+        assert_eq!(parse_ytdlp_progress("[youtube] abc: Downloading webpage"), None);
+    }
+
     #[test]
     fn test_build_ytdlp_info_args() {
         // Test with "python"
-        let args =
-            build_ytdlp_info_args("python", "https://youtube.com/watch?v=123", Some("chrome"))
-                .unwrap();
+        let args = build_ytdlp_info_args(
+            "python",
+            "https://youtube.com/watch?v=123",
+            Some("chrome"),
+            &DownloadOptions::default(),
+        )
+        .unwrap();
 
         assert!(args.contains(&"-m".to_string()));
         assert!(args.contains(&"yt_dlp".to_string()));
         assert!(args.contains(&"--".to_string()));
 
         // Test with standalone "yt-dlp"
-        let args_standalone = build_ytdlp_info_args("yt-dlp", "https://youtube.com", None).unwrap();
+        let args_standalone =
+            build_ytdlp_info_args("yt-dlp", "https://youtube.com", None, &DownloadOptions::default())
+                .unwrap();
         assert!(!args_standalone.contains(&"-m".to_string()));
     }
 
     #[test]
     fn test_build_ytdlp_info_args_injection() {
         // Try to inject a flag via URL
-        let args = build_ytdlp_info_args("python", "-v", None).unwrap();
+        let args =
+            build_ytdlp_info_args("python", "-v", None, &DownloadOptions::default()).unwrap();
 
         // Verify -v is after --
         let separator_idx = args.iter().position(|r| r == "--").unwrap();
@@ -764,24 +1062,47 @@ mod tests {
         assert!(separator_idx < url_idx);
     }
 
+    #[test]
+    fn test_build_ytdlp_text_args_skip_the_video() {
+        let stem = Path::new("cortex_cache/research/abc");
+        let opts = DownloadOptions::default();
+        let args = build_ytdlp_text_args("yt-dlp", "https://youtube.com/watch?v=abc", stem, None, &opts, None)
+            .unwrap();
+        assert!(args.contains(&"--skip-download".to_string()));
+        assert!(args.contains(&"--write-auto-subs".to_string()));
+        assert!(!args.contains(&"--write-comments".to_string()));
+
+        let args = build_ytdlp_text_args("yt-dlp", "https://youtube.com/watch?v=abc", stem, None, &opts, Some(20))
+            .unwrap();
+        assert!(args.contains(&"--write-comments".to_string()));
+        assert!(args.contains(
+            &"youtube:player_client=ios,android;max_comments=20,all,0,0;comment_sort=top".to_string()
+        ));
+        assert_eq!(args[args.len() - 2], "--");
+    }
+
     #[test]
     fn test_build_ytdlp_download_args() {
         let path = Path::new("out.mp4");
         // Test with "python"
-        let args = build_ytdlp_download_args("python", "https://youtube.com", path, None).unwrap();
+        let opts = DownloadOptions::default();
+        let args = build_ytdlp_download_args("python", "https://youtube.com", path, None, &opts)
+            .unwrap();
         assert!(args.contains(&"-m".to_string()));
         assert!(args.contains(&"yt_dlp".to_string()));
 
         // Test with standalone
         let args_sa =
-            build_ytdlp_download_args("yt-dlp", "https://youtube.com", path, None).unwrap();
+            build_ytdlp_download_args("yt-dlp", "https://youtube.com", path, None, &opts).unwrap();
         assert!(!args_sa.contains(&"-m".to_string()));
     }
 
     #[test]
     fn test_build_ytdlp_download_args_injection() {
         let path = Path::new("-out.mp4");
-        let args = build_ytdlp_download_args("python", "https://youtube.com", path, None).unwrap();
+        let opts = DownloadOptions::default();
+        let args = build_ytdlp_download_args("python", "https://youtube.com", path, None, &opts)
+            .unwrap();
         // Should be sanitized to ./ -out.mp4 or similar to prevent flag interpretation
         // safe_arg_path turns "-out.mp4" into "./-out.mp4"
         assert!(
@@ -789,9 +1110,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_download_options_append_ytdlp_args() {
+        let path = Path::new("out.mp4");
+        let opts = DownloadOptions {
+            limit_rate: Some("2M".to_string()),
+            proxy: Some("socks5://127.0.0.1:1080".to_string()),
+        };
+        let args =
+            build_ytdlp_download_args("yt-dlp", "https://youtube.com", path, None, &opts).unwrap();
+        let separator = args.iter().position(|a| a == "--").unwrap();
+        let rate = args.iter().position(|a| a == "--limit-rate").unwrap();
+        let proxy = args.iter().position(|a| a == "--proxy").unwrap();
+        assert_eq!(args[rate + 1], "2M");
+        assert_eq!(args[proxy + 1], "socks5://127.0.0.1:1080");
+        assert!(rate < separator && proxy < separator);
+
+        // Info lookups use the proxy but aren't throttled
+        let info = build_ytdlp_info_args("yt-dlp", "https://youtube.com", None, &opts).unwrap();
+        assert!(info.contains(&"--proxy".to_string()));
+        assert!(!info.contains(&"--limit-rate".to_string()));
+
+        // No options, no extra flags
+        let plain = build_ytdlp_download_args(
+            "yt-dlp",
+            "https://youtube.com",
+            path,
+            None,
+            &DownloadOptions::default(),
+        )
+        .unwrap();
+        assert!(!plain.contains(&"--proxy".to_string()));
+        assert!(!plain.contains(&"--limit-rate".to_string()));
+
+        let bad = DownloadOptions {
+            limit_rate: Some("--exec=rm".to_string()),
+            proxy: None,
+        };
+        assert!(build_ytdlp_download_args("yt-dlp", "u", path, None, &bad).is_err());
+    }
+
     #[test]
     fn test_bad_browser_name() {
-        let res = build_ytdlp_info_args("python", "url", Some("-bad"));
+        let res = build_ytdlp_info_args("python", "url", Some("-bad"), &DownloadOptions::default());
         assert!(res.is_err());
     }
 
@@ -0,0 +1,402 @@
+// SYNOID Subtitle Import - Tolerant SRT/VTT/ASS parsing
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Subtitle files made by other tools arrive with byte-order marks, CRLF line
+// endings, HTML-ish styling, ASS override blocks, and cues that overlap or run
+// backwards. Parsing keeps every cue it can recover as a plain-text
+// TranscriptSegment and records each repair, so the editor can show what
+// changed before the cues are edited and re-burned.
+
+use super::timecode;
+use super::transcription::TranscriptSegment;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+    Ass,
+}
+
+impl SubtitleFormat {
+    /// Format by file extension: `.srt`, `.vtt`, or `.ass`/`.ssa`.
+    pub fn from_filename(name: &str) -> Option<Self> {
+        let ext = std::path::Path::new(name)
+            .extension()?
+            .to_string_lossy()
+            .to_ascii_lowercase();
+        match ext.as_str() {
+            "srt" => Some(Self::Srt),
+            "vtt" => Some(Self::Vtt),
+            "ass" | "ssa" => Some(Self::Ass),
+            _ => None,
+        }
+    }
+}
+
+/// One repair made while importing.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CueFix {
+    /// 1-based position of the cue in the source file.
+    pub cue: usize,
+    pub fix: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportedSubtitles {
+    /// Plain-text cues in time order, never overlapping.
+    pub cues: Vec<TranscriptSegment>,
+    /// The validation report: every cue that was changed or dropped.
+    pub fixes: Vec<CueFix>,
+}
+
+struct RawCue {
+    origin: usize,
+    start: f64,
+    end: f64,
+    text: String,
+}
+
+/// Parse a subtitle file into clean, ordered, non-overlapping cues.
+/// Fails only when no cue at all can be recovered.
+pub fn parse_subtitles(content: &str, format: SubtitleFormat) -> Result<ImportedSubtitles> {
+    let content = content
+        .trim_start_matches('\u{feff}')
+        .replace("\r\n", "\n")
+        .replace('\r', "\n");
+    let mut fixes = Vec::new();
+    let raw = match format {
+        SubtitleFormat::Srt | SubtitleFormat::Vtt => parse_blocks(&content, &mut fixes),
+        SubtitleFormat::Ass => parse_ass_events(&content, &mut fixes),
+    };
+
+    let mut cues: Vec<RawCue> = Vec::new();
+    let mut latest_start = f64::NEG_INFINITY;
+    for mut cue in raw {
+        if cue.text.is_empty() {
+            fixes.push(fix(cue.origin, "dropped: no text"));
+            continue;
+        }
+        if cue.end < cue.start {
+            std::mem::swap(&mut cue.start, &mut cue.end);
+            fixes.push(fix(cue.origin, "swapped start and end times"));
+        }
+        if cue.end == cue.start {
+            fixes.push(fix(cue.origin, "dropped: zero duration"));
+            continue;
+        }
+        if cue.start < latest_start {
+            fixes.push(fix(cue.origin, "out of order; moved by start time"));
+        }
+        latest_start = latest_start.max(cue.start);
+        cues.push(cue);
+    }
+    cues.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    let mut resolved: Vec<RawCue> = Vec::with_capacity(cues.len());
+    for cue in cues {
+        if let Some(prev) = resolved.last_mut() {
+            if prev.end > cue.start {
+                if prev.start < cue.start {
+                    prev.end = cue.start;
+                    fixes.push(fix(
+                        prev.origin,
+                        &format!("end trimmed to {:.3}s where cue {} begins", cue.start, cue.origin),
+                    ));
+                } else {
+                    prev.end = prev.end.max(cue.end);
+                    prev.text = format!("{}\n{}", prev.text, cue.text);
+                    fixes.push(fix(
+                        cue.origin,
+                        &format!("merged into cue {}, which starts at the same time", prev.origin),
+                    ));
+                    continue;
+                }
+            }
+        }
+        resolved.push(cue);
+    }
+
+    if resolved.is_empty() {
+        bail!("no readable cues");
+    }
+    fixes.sort_by_key(|f| f.cue);
+    Ok(ImportedSubtitles {
+        cues: resolved
+            .into_iter()
+            .map(|c| TranscriptSegment {
+                start: c.start,
+                end: c.end,
+                text: c.text,
+                words: Vec::new(),
+            })
+            .collect(),
+        fixes,
+    })
+}
+
+fn fix(cue: usize, what: &str) -> CueFix {
+    CueFix {
+        cue,
+        fix: what.to_string(),
+    }
+}
+
+/// SRT and WebVTT: blank-line separated blocks around a `start --> end` line.
+/// Blocks without a timing line (VTT headers, NOTE and STYLE blocks) are skipped.
+fn parse_blocks(content: &str, fixes: &mut Vec<CueFix>) -> Vec<RawCue> {
+    let mut cues = Vec::new();
+    let mut block: Vec<&str> = Vec::new();
+    let mut origin = 0;
+    for line in content.lines().chain(std::iter::once("")) {
+        if !line.trim().is_empty() {
+            block.push(line);
+            continue;
+        }
+        let Some(timing) = block.iter().position(|l| l.contains("-->")) else {
+            block.clear();
+            continue;
+        };
+        origin += 1;
+        let (start, end) = block[timing].split_once("-->").unwrap_or_default();
+        match (parse_timestamp(start), parse_timestamp(end)) {
+            (Some(start), Some(end)) => {
+                let text = clean_markup(&block[timing + 1..].join("\n"), origin, fixes);
+                cues.push(RawCue {
+                    origin,
+                    start,
+                    end,
+                    text,
+                });
+            }
+            _ => fixes.push(fix(
+                origin,
+                &format!("dropped: unreadable timing '{}'", block[timing].trim()),
+            )),
+        }
+        block.clear();
+    }
+    cues
+}
+
+/// ASS/SSA: `Dialogue:` lines in `[Events]`, laid out by its `Format:` line.
+fn parse_ass_events(content: &str, fixes: &mut Vec<CueFix>) -> Vec<RawCue> {
+    let mut fields: Vec<String> = "layer,start,end,style,name,marginl,marginr,marginv,effect,text"
+        .split(',')
+        .map(str::to_string)
+        .collect();
+    let mut in_events = false;
+    let mut cues = Vec::new();
+    let mut origin = 0;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_events = line.eq_ignore_ascii_case("[events]");
+            continue;
+        }
+        if !in_events {
+            continue;
+        }
+        if let Some(format) = line.strip_prefix("Format:") {
+            fields = format.split(',').map(|f| f.trim().to_ascii_lowercase()).collect();
+            continue;
+        }
+        let Some(dialogue) = line.strip_prefix("Dialogue:") else {
+            continue;
+        };
+        origin += 1;
+        // Text is the last field and may itself contain commas
+        let values: Vec<&str> = dialogue.splitn(fields.len(), ',').collect();
+        let field = |name: &str| {
+            fields
+                .iter()
+                .position(|f| f == name)
+                .and_then(|i| values.get(i))
+                .map(|v| v.trim())
+        };
+        match (
+            field("start").and_then(parse_timestamp),
+            field("end").and_then(parse_timestamp),
+        ) {
+            (Some(start), Some(end)) => {
+                let text = clean_markup(field("text").unwrap_or(""), origin, fixes);
+                cues.push(RawCue {
+                    origin,
+                    start,
+                    end,
+                    text,
+                });
+            }
+            _ => fixes.push(fix(origin, "dropped: unreadable timing")),
+        }
+    }
+    cues
+}
+
+/// Seconds from `HH:MM:SS,mmm`, `MM:SS.mmm` or ASS `H:MM:SS.cc`. Anything
+/// after the first space (VTT cue settings) is ignored.
+fn parse_timestamp(s: &str) -> Option<f64> {
+    let token = s.split_whitespace().next()?.replace(',', ".");
+    if !(1..=2).contains(&token.matches(':').count()) {
+        return None;
+    }
+    timecode::parse(&token).ok()
+}
+
+/// Plain text from a cue: HTML-ish tags, `{...}` override blocks and ASS
+/// escapes are removed, entities decoded, blank lines dropped.
+fn clean_markup(text: &str, origin: usize, fixes: &mut Vec<CueFix>) -> String {
+    let markup = regex::Regex::new(r"<[^>]*>|\{[^}]*\}").unwrap();
+    let stripped = markup.replace_all(text, "");
+    let decoded = stripped
+        .replace("\\N", "\n")
+        .replace("\\n", "\n")
+        .replace("\\h", " ")
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&");
+    let cleaned = decoded
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if stripped != text && !cleaned.is_empty() {
+        fixes.push(fix(origin, "removed formatting tags"));
+    }
+    cleaned
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Cue Editing
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Changes to one cue; omitted fields keep their value.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CueEdit {
+    pub text: Option<String>,
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CueEditError {
+    NoSuchCue,
+    Invalid(String),
+}
+
+/// Apply `edit` to the 1-based cue `number`. The cue must keep text, a
+/// positive duration, and clear of its neighbours, or nothing changes.
+pub fn edit_cue(
+    cues: &mut [TranscriptSegment],
+    number: usize,
+    edit: CueEdit,
+) -> std::result::Result<(), CueEditError> {
+    let i = number.checked_sub(1).filter(|&i| i < cues.len()).ok_or(CueEditError::NoSuchCue)?;
+    let start = edit.start.unwrap_or(cues[i].start);
+    let end = edit.end.unwrap_or(cues[i].end);
+    let text = edit
+        .text
+        .map(|t| t.trim().to_string())
+        .unwrap_or_else(|| cues[i].text.clone());
+    let invalid = |msg: String| Err(CueEditError::Invalid(msg));
+
+    if !start.is_finite() || !end.is_finite() || start < 0.0 {
+        return invalid("start and end must be non-negative seconds".to_string());
+    }
+    if end <= start {
+        return invalid(format!("end ({:.3}s) must be after start ({:.3}s)", end, start));
+    }
+    if text.is_empty() {
+        return invalid("text must not be empty".to_string());
+    }
+    if i > 0 && start < cues[i - 1].end {
+        return invalid(format!("cue would overlap cue {}", number - 1));
+    }
+    if i + 1 < cues.len() && end > cues[i + 1].start {
+        return invalid(format!("cue would overlap cue {}", number + 1));
+    }
+
+    let cue = &mut cues[i];
+    cue.start = start;
+    cue.end = end;
+    cue.text = text;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messy_srt_is_repaired_and_reported() {
+        let srt = "\u{feff}1\r\n00:00:01,000 --> 00:00:04,000\r\n<i>Hello</i> <font color=\"red\">there</font>\r\n\r\n\
+                   2\r\n00:00:03,500 --> 00:00:05,000\r\n{\\an8}General Kenobi\r\n\r\n\
+                   3\r\n00:00:09,000 --> 00:00:07,000\r\nbackwards\r\n\r\n\
+                   4\r\nbroken --> timing\r\nlost\r\n\r\n\
+                   5\r\n00:00:06,000 --> 00:00:06,500\r\n<b></b>\r\n";
+        let imported = parse_subtitles(srt, SubtitleFormat::Srt).unwrap();
+
+        let cues: Vec<(f64, f64, &str)> = imported
+            .cues
+            .iter()
+            .map(|c| (c.start, c.end, c.text.as_str()))
+            .collect();
+        assert_eq!(
+            cues,
+            vec![
+                (1.0, 3.5, "Hello there"),
+                (3.5, 5.0, "General Kenobi"),
+                (7.0, 9.0, "backwards"),
+            ]
+        );
+        let fixed: Vec<usize> = imported.fixes.iter().map(|f| f.cue).collect();
+        assert_eq!(fixed, vec![1, 1, 2, 3, 4, 5]);
+        assert!(imported.fixes[1].fix.contains("trimmed"));
+        assert!(imported.fixes[3].fix.contains("swapped"));
+    }
+
+    #[test]
+    fn vtt_and_ass_share_the_same_cues() {
+        let vtt = "WEBVTT\n\nNOTE exported\n\n00:01.000 --> 00:02.500 align:start\n<v Sam>Cut here</v>\n";
+        let ass = "[Script Info]\nTitle: demo\n\n[Events]\n\
+                   Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n\
+                   Dialogue: 0,0:00:01.00,0:00:02.50,Default,,0,0,0,,{\\b1}Cut here\n\
+                   Comment: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,ignored\n";
+        for (content, format) in [(vtt, SubtitleFormat::Vtt), (ass, SubtitleFormat::Ass)] {
+            let imported = parse_subtitles(content, format).unwrap();
+            assert_eq!(imported.cues.len(), 1);
+            assert_eq!((imported.cues[0].start, imported.cues[0].end), (1.0, 2.5));
+            assert_eq!(imported.cues[0].text, "Cut here");
+        }
+        assert_eq!(SubtitleFormat::from_filename("Movie.EN.ass"), Some(SubtitleFormat::Ass));
+        assert!(parse_subtitles("WEBVTT\n", SubtitleFormat::Vtt).is_err());
+    }
+
+    #[test]
+    fn edits_keep_cues_valid() {
+        let cue = |start, end, text: &str| TranscriptSegment {
+            start,
+            end,
+            text: text.to_string(),
+            words: Vec::new(),
+        };
+        let mut cues = vec![cue(0.0, 2.0, "one"), cue(2.0, 4.0, "two")];
+        let edit = |text: Option<&str>, start, end| CueEdit {
+            text: text.map(str::to_string),
+            start,
+            end,
+        };
+
+        assert_eq!(edit_cue(&mut cues, 3, CueEdit::default()), Err(CueEditError::NoSuchCue));
+        assert!(matches!(
+            edit_cue(&mut cues, 1, edit(None, None, Some(2.5))),
+            Err(CueEditError::Invalid(_))
+        ));
+        assert!(edit_cue(&mut cues, 2, edit(Some("   "), None, None)).is_err());
+        assert_eq!(edit_cue(&mut cues, 2, edit(Some(" TWO "), Some(2.5), Some(6.0))), Ok(()));
+        assert_eq!((cues[1].start, cues[1].end, cues[1].text.as_str()), (2.5, 6.0, "TWO"));
+    }
+}
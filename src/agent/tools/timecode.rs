@@ -0,0 +1,178 @@
+// SYNOID Timecodes - Flexible time strings
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Times as people type them: "1:23:45.5", "23:45", "90" or "5025.5".
+// Shared by the clip command and panel, exclude-range intents and subtitle
+// cue timings so they all accept and reject the same strings.
+
+/// Longest time accepted, about 10,000 hours. Anything past it is a typo, and
+/// keeping well inside f64's exact integers keeps the arithmetic honest.
+pub const MAX_SECS: f64 = 36_000_000.0;
+
+/// Seconds from `hh:mm:ss(.ms)`, `mm:ss(.ms)` or plain seconds. The leading
+/// field may be any size ("90:00" is 90 minutes) but the ones after it must
+/// be below 60, and only the last may carry a fraction.
+pub fn parse(text: &str) -> Result<f64, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("no time given".to_string());
+    }
+    let fields: Vec<&str> = text.split(':').collect();
+    if fields.len() > 3 {
+        return Err(format!("'{}' has too many ':' fields; use hh:mm:ss", text));
+    }
+
+    let (last, leading) = fields.split_last().expect("split always yields a field");
+    let mut secs = 0.0;
+    for (i, field) in leading.iter().enumerate() {
+        if field.is_empty() || !field.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("'{}' in '{}' is not a whole number", field, text));
+        }
+        let value: u64 = field
+            .parse()
+            .map_err(|_| format!("'{}' in '{}' is out of range", field, text))?;
+        if i > 0 && value >= 60 {
+            return Err(format!("{} minutes in '{}' must be below 60", value, text));
+        }
+        secs = secs * 60.0 + value as f64;
+    }
+
+    let digits = last.replacen('.', "", 1);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!(
+            "'{}' in '{}' is not a number of seconds",
+            last, text
+        ));
+    }
+    let last_secs: f64 = last
+        .parse()
+        .map_err(|_| format!("'{}' in '{}' is not a number of seconds", last, text))?;
+    if !leading.is_empty() && last_secs >= 60.0 {
+        return Err(format!("{} seconds in '{}' must be below 60", last, text));
+    }
+
+    let total = secs * 60.0 + last_secs;
+    if !total.is_finite() || total > MAX_SECS {
+        return Err(format!("'{}' is out of range", text));
+    }
+    Ok(total)
+}
+
+/// Length of a clip that starts at `start` and is given either by its
+/// `duration` or by the time it `end`s — exactly one of the two.
+pub fn clip_length(start: f64, duration: Option<f64>, end: Option<f64>) -> Result<f64, String> {
+    let length = match (duration, end) {
+        (Some(_), Some(_)) => return Err("give a duration or an end time, not both".to_string()),
+        (None, None) => return Err("give a duration or an end time".to_string()),
+        (Some(duration), None) => duration,
+        (None, Some(end)) if end <= start => {
+            return Err(format!(
+                "end {} is not after start {}",
+                format(end),
+                format(start)
+            ))
+        }
+        (None, Some(end)) => end - start,
+    };
+    if length <= 0.0 {
+        return Err("the duration must be above zero".to_string());
+    }
+    Ok(length)
+}
+
+/// `h:mm:ss.ms` (or `m:ss.ms` under an hour), trailing zeros trimmed.
+pub fn format(secs: f64) -> String {
+    let millis = (secs.max(0.0) * 1000.0).round() as u64;
+    let (whole, ms) = (millis / 1000, millis % 1000);
+    let (h, m, s) = (whole / 3600, whole / 60 % 60, whole % 60);
+    let clock = if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    };
+    if ms == 0 {
+        clock
+    } else {
+        format!("{}.{}", clock, format!("{:03}", ms).trim_end_matches('0'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_accepted_shape_reads_as_seconds() {
+        assert_eq!(parse("1:23:45"), Ok(5025.0));
+        assert_eq!(parse("1:23:45.5"), Ok(5025.5));
+        assert_eq!(parse("01:02:03.250"), Ok(3723.25));
+        assert_eq!(parse("23:45"), Ok(1425.0));
+        assert_eq!(parse("0:05.5"), Ok(5.5));
+        assert_eq!(parse("5025.5"), Ok(5025.5));
+        assert_eq!(parse(" 90 "), Ok(90.0));
+        assert_eq!(parse("0"), Ok(0.0));
+        assert_eq!(parse(".5"), Ok(0.5));
+        // Only the leading field is unbounded
+        assert_eq!(parse("90:00"), Ok(5400.0));
+        assert_eq!(parse("100:00:00"), Ok(360_000.0));
+    }
+
+    #[test]
+    fn malformed_times_are_rejected_with_the_reason() {
+        let err = |s: &str| parse(s).unwrap_err();
+        assert!(err("1:90").contains("below 60"));
+        assert!(err("1:60:00").contains("below 60"));
+        assert!(err("1:59:60").contains("below 60"));
+        assert!(err("").contains("no time"));
+        assert!(err("1:2:3:4").contains("too many"));
+        assert!(err("1::30").contains("whole number"));
+        assert!(err(":30").contains("whole number"));
+        assert!(err("1:").contains("seconds"));
+        assert!(err("1.5:30").contains("whole number"));
+        assert!(err("-5").contains("seconds"));
+        assert!(err("1:-5").contains("seconds"));
+        assert!(err("1.2.3").contains("seconds"));
+        assert!(err("abc").contains("seconds"));
+        for float_only in ["1e3", "inf", "NaN", "+5"] {
+            assert!(parse(float_only).is_err(), "{} parsed", float_only);
+        }
+    }
+
+    #[test]
+    fn overflowing_times_are_out_of_range() {
+        assert!(parse("99999999999999999999999:00")
+            .unwrap_err()
+            .contains("out of range"));
+        assert!(parse(&"9".repeat(400))
+            .unwrap_err()
+            .contains("out of range"));
+        assert!(parse("10001:00:00").unwrap_err().contains("out of range"));
+        assert_eq!(parse("10000:00:00"), Ok(MAX_SECS));
+    }
+
+    #[test]
+    fn clips_take_a_duration_or_an_end_but_not_both() {
+        assert_eq!(clip_length(10.0, Some(5.0), None), Ok(5.0));
+        assert_eq!(clip_length(10.0, None, Some(25.5)), Ok(15.5));
+        assert!(clip_length(10.0, Some(5.0), Some(15.0))
+            .unwrap_err()
+            .contains("not both"));
+        assert!(clip_length(10.0, None, None).is_err());
+        assert!(clip_length(10.0, None, Some(10.0))
+            .unwrap_err()
+            .contains("not after"));
+        assert!(clip_length(10.0, Some(0.0), None)
+            .unwrap_err()
+            .contains("above zero"));
+    }
+
+    #[test]
+    fn formatted_times_parse_back() {
+        assert_eq!(format(5025.5), "1:23:45.5");
+        assert_eq!(format(65.0), "1:05");
+        assert_eq!(format(3723.25), "1:02:03.25");
+        for secs in [0.0, 5.5, 59.999, 3600.0, 5025.125] {
+            assert_eq!(parse(&format(secs)), Ok(secs));
+        }
+    }
+}
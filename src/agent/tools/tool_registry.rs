@@ -0,0 +1,397 @@
+// SYNOID Tool Registry - Pluggable Processing Steps
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// A processing step is anything that turns one media file into another:
+// an FFmpeg filter, a Rust transform, a call out to another program. Steps
+// implement `Tool` and are looked up by name, so the Brain's fast path
+// ("trim clip.mp4 start=5 duration=10") and the `Process` pipeline
+// (`--stages trim:start=5;duration=10,encode`) can run tools they were
+// never compiled against. Programs embedding synoid-core add their own
+// with `register_tool`; the built-ins (trim, compress, reframe, grade) are
+// registered the same way.
+
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::tools::output_naming;
+use crate::agent::tools::platform_presets::Platform;
+use crate::agent::tools::production_tools::{self, safe_arg_path};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock, RwLock};
+use tracing::info;
+
+pub type ToolResult = Result<PathBuf, Box<dyn std::error::Error + Send + Sync>>;
+pub type ToolFuture<'a> = Pin<Box<dyn Future<Output = ToolResult> + Send + 'a>>;
+
+/// `key=value` settings for one run. `output`, when set, is where the
+/// result must be written.
+pub type ToolParams = BTreeMap<String, String>;
+
+/// A named processing step.
+pub trait Tool: Send + Sync {
+    /// Name used to invoke the tool (`trim`, `grade`, ...).
+    fn name(&self) -> &str;
+
+    /// One line for listings: what it does and which params it takes.
+    fn describe(&self) -> &str;
+
+    /// Process `input` and return the file written.
+    fn run<'a>(&'a self, input: &'a Path, params: &'a ToolParams) -> ToolFuture<'a>;
+}
+
+/// Parse `start=5;duration=10` (or space-separated pairs). Words without
+/// an `=` are ignored.
+pub fn parse_params(spec: &str) -> ToolParams {
+    spec.split(|c: char| c == ';' || c.is_whitespace())
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+        .filter(|(k, _)| !k.is_empty())
+        .collect()
+}
+
+/// Numeric param `key`, or `default` when it is absent.
+pub fn param_f64(
+    params: &ToolParams,
+    key: &str,
+    default: Option<f64>,
+) -> Result<f64, Box<dyn std::error::Error + Send + Sync>> {
+    match params.get(key) {
+        Some(v) => v
+            .parse()
+            .map_err(|_| format!("'{}' must be a number, got '{}'", key, v).into()),
+        None => default.ok_or_else(|| format!("missing required param '{}'", key).into()),
+    }
+}
+
+/// `params["output"]`, else the configured output name for `op`.
+pub fn output_path(input: &Path, params: &ToolParams, op: &str) -> PathBuf {
+    params
+        .get("output")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| output_naming::default_output(input, op, None, "mp4"))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Built-in tools
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Cut `duration` seconds starting at `start`.
+pub struct TrimTool;
+
+impl Tool for TrimTool {
+    fn name(&self) -> &str {
+        "trim"
+    }
+
+    fn describe(&self) -> &str {
+        "Cut a range: start=<secs> (default 0) duration=<secs>"
+    }
+
+    fn run<'a>(&'a self, input: &'a Path, params: &'a ToolParams) -> ToolFuture<'a> {
+        Box::pin(async move {
+            let start = param_f64(params, "start", Some(0.0))?;
+            let duration = param_f64(params, "duration", None)?;
+            let output = output_path(input, params, "trim");
+            Ok(
+                production_tools::trim_video(input, start, duration, &output)
+                    .await?
+                    .output_path,
+            )
+        })
+    }
+}
+
+/// Re-encode to fit a target size.
+pub struct CompressTool;
+
+impl Tool for CompressTool {
+    fn name(&self) -> &str {
+        "compress"
+    }
+
+    fn describe(&self) -> &str {
+        "Fit a file size: size_mb=<MB>"
+    }
+
+    fn run<'a>(&'a self, input: &'a Path, params: &'a ToolParams) -> ToolFuture<'a> {
+        Box::pin(async move {
+            let size_mb = param_f64(params, "size_mb", None)?;
+            let output = output_path(input, params, "compressed");
+            Ok(production_tools::compress_video(input, size_mb, &output)
+                .await?
+                .output_path)
+        })
+    }
+}
+
+/// Scale and centre-crop to a platform's frame.
+pub struct ReframeTool;
+
+impl Tool for ReframeTool {
+    fn name(&self) -> &str {
+        "reframe"
+    }
+
+    fn describe(&self) -> &str {
+        "Crop to a platform's frame: platform=<shorts|tiktok|reels|youtube|...> (default shorts)"
+    }
+
+    fn run<'a>(&'a self, input: &'a Path, params: &'a ToolParams) -> ToolFuture<'a> {
+        Box::pin(async move {
+            let name = params
+                .get("platform")
+                .map(String::as_str)
+                .unwrap_or("shorts");
+            let platform =
+                Platform::parse(name).ok_or_else(|| format!("Unknown platform '{}'", name))?;
+            let output = output_path(input, params, "reframed");
+            filter_video(input, &platform.preset().reframe_filter(), &output).await?;
+            Ok(output)
+        })
+    }
+}
+
+/// Named colour looks, as FFmpeg video filters.
+const LOOKS: &[(&str, &str)] = &[
+    (
+        "cinematic",
+        "eq=contrast=1.1:saturation=0.9,colorbalance=rs=-0.05:bs=0.08:rh=0.06:bh=-0.04",
+    ),
+    ("warm", "colortemperature=temperature=5000"),
+    ("cool", "colortemperature=temperature=8000"),
+    ("vibrant", "eq=saturation=1.35:contrast=1.05"),
+    ("bw", "hue=s=0,eq=contrast=1.1"),
+];
+
+/// Apply a colour look or a 3D LUT.
+pub struct GradeTool;
+
+impl Tool for GradeTool {
+    fn name(&self) -> &str {
+        "grade"
+    }
+
+    fn describe(&self) -> &str {
+        "Colour grade: look=<cinematic|warm|cool|vibrant|bw> (default cinematic) or lut=<.cube file>"
+    }
+
+    fn run<'a>(&'a self, input: &'a Path, params: &'a ToolParams) -> ToolFuture<'a> {
+        Box::pin(async move {
+            let filter = match params.get("lut") {
+                Some(lut) => format!("lut3d='{}'", lut.replace('\\', "/").replace('\'', "\\'")),
+                None => {
+                    let look = params
+                        .get("look")
+                        .map(String::as_str)
+                        .unwrap_or("cinematic");
+                    LOOKS
+                        .iter()
+                        .find(|(name, _)| name.eq_ignore_ascii_case(look))
+                        .map(|(_, filter)| filter.to_string())
+                        .ok_or_else(|| format!("Unknown look '{}'", look))?
+                }
+            };
+            let output = output_path(input, params, "graded");
+            filter_video(input, &filter, &output).await?;
+            Ok(output)
+        })
+    }
+}
+
+/// Re-encode the video through `filter`, copying the audio.
+async fn filter_video(
+    input: &Path,
+    filter: &str,
+    output: &Path,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    info!("[TOOLS] {:?} -vf {} -> {:?}", input, filter, output);
+    let status = proc::ffmpeg(ProcClass::Render)
+        .stealth()
+        .args(["-y", "-nostdin", "-i"])
+        .arg(safe_arg_path(input))
+        .args(["-vf", filter])
+        .args([
+            "-c:v", "libx264", "-preset", "medium", "-crf", "20", "-pix_fmt", "yuv420p",
+        ])
+        .args(["-c:a", "copy"])
+        .arg(safe_arg_path(output))
+        .status()
+        .await?;
+    if !status.success() {
+        return Err(format!("ffmpeg filter '{}' failed", filter).into());
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Registry
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Tools by name.
+pub struct ToolRegistry {
+    tools: BTreeMap<String, Arc<dyn Tool>>,
+}
+
+impl Default for ToolRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Arc::new(TrimTool));
+        registry.register(Arc::new(CompressTool));
+        registry.register(Arc::new(ReframeTool));
+        registry.register(Arc::new(GradeTool));
+        registry
+    }
+}
+
+impl ToolRegistry {
+    pub fn empty() -> Self {
+        Self {
+            tools: BTreeMap::new(),
+        }
+    }
+
+    /// Add a tool, replacing any existing one with the same name.
+    pub fn register(&mut self, tool: Arc<dyn Tool>) {
+        self.tools.insert(tool.name().to_lowercase(), tool);
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Tool>> {
+        self.tools.get(&name.to_lowercase()).cloned()
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.tools.keys().cloned().collect()
+    }
+
+    /// `(name, describe())` for every tool, by name.
+    pub fn descriptions(&self) -> Vec<(String, String)> {
+        self.tools
+            .iter()
+            .map(|(name, tool)| (name.clone(), tool.describe().to_string()))
+            .collect()
+    }
+
+    pub async fn run(&self, name: &str, input: &Path, params: &ToolParams) -> ToolResult {
+        let tool = self.get(name).ok_or_else(|| {
+            format!(
+                "Unknown tool '{}' (available: {})",
+                name,
+                self.names().join(", ")
+            )
+        })?;
+        info!("[TOOLS] Running '{}' on {:?}", tool.name(), input);
+        tool.run(input, params).await
+    }
+}
+
+fn global_registry() -> &'static RwLock<ToolRegistry> {
+    static REGISTRY: OnceLock<RwLock<ToolRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(ToolRegistry::default()))
+}
+
+/// Make a tool available process-wide: to the Brain and to `Process`.
+pub fn register_tool(tool: Arc<dyn Tool>) {
+    global_registry()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .register(tool);
+}
+
+/// The process-wide tool called `name`.
+pub fn get_tool(name: &str) -> Option<Arc<dyn Tool>> {
+    global_registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(name)
+}
+
+/// Every process-wide tool as `(name, description)`.
+pub fn descriptions() -> Vec<(String, String)> {
+    global_registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .descriptions()
+}
+
+/// Run the named process-wide tool.
+pub async fn run_tool(name: &str, input: &Path, params: &ToolParams) -> ToolResult {
+    match get_tool(name) {
+        Some(tool) => {
+            info!("[TOOLS] Running '{}' on {:?}", tool.name(), input);
+            tool.run(input, params).await
+        }
+        // Fall through to the registry's error message
+        None => ToolRegistry::empty().run(name, input, params).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Copies its input and records the params in the output name.
+    struct StampTool;
+
+    impl Tool for StampTool {
+        fn name(&self) -> &str {
+            "stamp"
+        }
+
+        fn describe(&self) -> &str {
+            "Test tool: label=<text>"
+        }
+
+        fn run<'a>(&'a self, input: &'a Path, params: &'a ToolParams) -> ToolFuture<'a> {
+            Box::pin(async move {
+                let label = params.get("label").cloned().unwrap_or_default();
+                let output = input.with_file_name(format!("stamped_{}.txt", label));
+                std::fs::copy(input, &output)?;
+                Ok(output)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn a_registered_tool_is_discoverable_and_runs_by_name() {
+        let dir = std::env::temp_dir().join(format!("synoid_tools_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("in.txt");
+        std::fs::write(&input, "frames").unwrap();
+
+        let mut registry = ToolRegistry::default();
+        registry.register(Arc::new(StampTool));
+        assert_eq!(
+            registry.names(),
+            ["compress", "grade", "reframe", "stamp", "trim"]
+        );
+        assert!(registry
+            .descriptions()
+            .contains(&("stamp".to_string(), "Test tool: label=<text>".to_string())));
+
+        let params = parse_params("label=hello; ignored");
+        let out = registry.run("STAMP", &input, &params).await.unwrap();
+        assert_eq!(out, dir.join("stamped_hello.txt"));
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), "frames");
+        assert!(registry.run("nope", &input, &params).await.is_err());
+
+        register_tool(Arc::new(StampTool));
+        assert!(get_tool("stamp").is_some());
+        let out = run_tool("stamp", &input, &parse_params("label=global"))
+            .await
+            .unwrap();
+        assert!(out.exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn params_parse_and_validate() {
+        let params = parse_params("start=5 Duration=10.5");
+        assert_eq!(param_f64(&params, "duration", None).unwrap(), 10.5);
+        assert_eq!(param_f64(&params, "missing", Some(1.0)).unwrap(), 1.0);
+        assert!(param_f64(&params, "missing", None).is_err());
+        assert!(param_f64(&parse_params("start=soon"), "start", None).is_err());
+    }
+}
@@ -2,14 +2,20 @@
 // Native Rust implementation of Whisper for local, private transcription.
 
 use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::project::sha256_file;
 use crate::gpu_backend::get_gpu_context;
 use anyhow::{Context, Result};
 use hf_hub::api::sync::Api;
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::future::Future;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use tracing::info;
-use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+use whisper_rs::{
+    FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters, WhisperState,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranscriptSegment {
@@ -31,9 +37,268 @@ pub struct TranscriptionEngine {
     model_path: PathBuf,
 }
 
+/// Where Whisper model `name` is (or will be) kept, in the configured
+/// models directory. A custom model's path is returned as given.
+pub fn model_path(name: &str) -> PathBuf {
+    model_path_in(&crate::config::current().models_dir(), name)
+}
+
+/// `model_path` under an explicit models directory.
+pub fn model_path_in(models_dir: &Path, name: &str) -> PathBuf {
+    if is_custom_model(name) {
+        return PathBuf::from(name);
+    }
+    models_dir.join(format!("ggml-{}.bin", name))
+}
+
+/// Whether `whisper_model` names a model file of the user's own (a path, or
+/// a `.bin` / `.gguf` file name) rather than one of the published models.
+pub fn is_custom_model(name: &str) -> bool {
+    name.contains('/')
+        || name.contains('\\')
+        || [".bin", ".gguf"]
+            .iter()
+            .any(|ext| name.to_ascii_lowercase().ends_with(ext))
+}
+
+/// Check that `path` is a Whisper model whisper.cpp can load: a ggml file
+/// (what whisper.cpp's converters write) or GGUF.
+pub fn check_model_file(path: &Path) -> Result<()> {
+    let mut magic = [0u8; 4];
+    let mut file = fs::File::open(path)
+        .with_context(|| format!("Whisper model {} does not exist", path.display()))?;
+    std::io::Read::read_exact(&mut file, &mut magic)
+        .with_context(|| format!("Whisper model {} is empty", path.display()))?;
+    // ggml's magic is the u32 0x67676d6c, so "lmgg" on disk
+    if &magic == b"lmgg" || &magic == b"GGUF" {
+        Ok(())
+    } else {
+        anyhow::bail!(
+            "{} is not a Whisper model (expected a ggml or GGUF file)",
+            path.display()
+        )
+    }
+}
+
+/// A published Whisper model and the SHA-256 of its ggml file.
+pub struct WhisperModel {
+    pub name: &'static str,
+    pub sha256: &'static str,
+}
+
+/// Whisper models offered for download, smallest first. The checksums are
+/// pinned to the files ggerganov/whisper.cpp publishes on Hugging Face.
+pub const WHISPER_MODELS: [WhisperModel; 5] = [
+    WhisperModel {
+        name: "tiny",
+        sha256: "be07e048e1e599ad46341c8d2a135645097a538221678b7acdd1b1919c6e1b21",
+    },
+    WhisperModel {
+        name: "base",
+        sha256: "60ed5bc3dd14eea856493d334349b405782ddcaf0028d4b5df4088345fba2efe",
+    },
+    WhisperModel {
+        name: "small",
+        sha256: "1be3a9b2063867b937e64e2ec7483364a79917e157fa98c5d94b5c1fffea987b",
+    },
+    WhisperModel {
+        name: "medium",
+        sha256: "6c14d5adee5f86394037b4e4e8b59f1673b6cee10e3cf0b11bbdbee79c156208",
+    },
+    WhisperModel {
+        name: "large-v3",
+        sha256: "64d182b440b98d5203c4f9bd541544d84c605196c4f7b845dfa11fb23594d1e2",
+    },
+];
+
+/// Smallest Whisper model, used when the configured one cannot be fetched
+/// and offered for download when none is available.
+pub const FALLBACK_MODEL: &str = "tiny";
+
+enum Notice {
+    Quiet,
+    Pending(String),
+    Shown,
+}
+
+static UNAVAILABLE: std::sync::Mutex<Notice> = std::sync::Mutex::new(Notice::Quiet);
+
+/// Record that transcription could not run, so the CLI and the GUI can say
+/// so prominently. Only the first reason of a session is kept.
+pub fn report_unavailable(reason: &str) {
+    let mut notice = UNAVAILABLE.lock().unwrap_or_else(|e| e.into_inner());
+    if matches!(*notice, Notice::Quiet) {
+        *notice = Notice::Pending(reason.to_string());
+    }
+}
+
+/// The reason passed to `report_unavailable`, returned once per session.
+pub fn take_unavailable_notice() -> Option<String> {
+    let mut notice = UNAVAILABLE.lock().unwrap_or_else(|e| e.into_inner());
+    match std::mem::replace(&mut *notice, Notice::Shown) {
+        Notice::Pending(reason) => Some(reason),
+        other => {
+            *notice = other;
+            None
+        }
+    }
+}
+
+/// Download Whisper model `name` into `models_dir`, reporting
+/// `(bytes so far, total)` as it goes. The file is checked against the
+/// SHA-256 pinned in `WHISPER_MODELS` (or, for a model not listed there,
+/// the one Hugging Face publishes) and fetched again on a mismatch.
+/// Used by `setup`; transcription itself fetches a missing model through
+/// hf-hub.
+pub async fn download_model(
+    models_dir: &Path,
+    name: &str,
+    mut progress: impl FnMut(u64, Option<u64>) + Send,
+) -> Result<PathBuf> {
+    if is_custom_model(name) {
+        anyhow::bail!("{} is a custom model file; it cannot be downloaded", name);
+    }
+    let path = model_path_in(models_dir, name);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let url = format!(
+        "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-{}.bin",
+        name
+    );
+    let expected = match WHISPER_MODELS.iter().find(|m| m.name == name) {
+        Some(model) => Some(model.sha256.to_string()),
+        None => published_sha256(&url).await,
+    };
+    if expected.is_none() {
+        tracing::warn!(
+            "[SOVEREIGN] No checksum published for ggml-{}.bin; it will not be verified",
+            name
+        );
+    }
+
+    // Write beside the final name so an interrupted download is never mistaken for a model
+    let partial = path.with_extension("bin.part");
+    let mut fetcher = HttpFetcher {
+        client: crate::net::build_client(std::time::Duration::from_secs(6 * 60 * 60)),
+    };
+    fetch_verified(
+        &mut fetcher,
+        &url,
+        &partial,
+        expected.as_deref(),
+        &mut progress,
+    )
+    .await?;
+    tokio::fs::rename(&partial, &path)
+        .await
+        .with_context(|| format!("moving {:?} into place", partial))?;
+    info!("[SOVEREIGN] Model secured: {:?}", path);
+    Ok(path)
+}
+
+/// Downloads of one model before a checksum mismatch is given up on.
+const DOWNLOAD_ATTEMPTS: usize = 3;
+
+pub type FetchFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+/// Where model files come from; tests stand in for Hugging Face.
+pub trait ModelFetcher: Send {
+    /// Write the file at `url` to `dest`, reporting `(bytes so far, total)`.
+    fn fetch<'a>(
+        &'a mut self,
+        url: &'a str,
+        dest: &'a Path,
+        progress: &'a mut (dyn FnMut(u64, Option<u64>) + Send),
+    ) -> FetchFuture<'a>;
+}
+
+struct HttpFetcher {
+    client: reqwest::Client,
+}
+
+impl ModelFetcher for HttpFetcher {
+    fn fetch<'a>(
+        &'a mut self,
+        url: &'a str,
+        dest: &'a Path,
+        progress: &'a mut (dyn FnMut(u64, Option<u64>) + Send),
+    ) -> FetchFuture<'a> {
+        Box::pin(async move {
+            use tokio::io::AsyncWriteExt;
+
+            let mut resp = self.client.get(url).send().await?.error_for_status()?;
+            let total = resp.content_length();
+            let mut file = tokio::fs::File::create(dest).await?;
+            let mut done = 0u64;
+            while let Some(chunk) = resp.chunk().await? {
+                file.write_all(&chunk).await?;
+                done += chunk.len() as u64;
+                progress(done, total);
+            }
+            file.flush().await?;
+            Ok(())
+        })
+    }
+}
+
+/// Fetch `url` into `dest` until its SHA-256 is `expected` (any file passes
+/// when it is `None`), at most DOWNLOAD_ATTEMPTS times. A corrupt file is
+/// deleted rather than left to fail when Whisper loads it.
+pub async fn fetch_verified(
+    fetcher: &mut dyn ModelFetcher,
+    url: &str,
+    dest: &Path,
+    expected: Option<&str>,
+    progress: &mut (dyn FnMut(u64, Option<u64>) + Send),
+) -> Result<()> {
+    for attempt in 1..=DOWNLOAD_ATTEMPTS {
+        fetcher.fetch(url, dest, progress).await?;
+        let Some(expected) = expected else {
+            return Ok(());
+        };
+        let file = dest.to_path_buf();
+        let actual = tokio::task::spawn_blocking(move || sha256_file(&file)).await??;
+        if actual.eq_ignore_ascii_case(expected) {
+            return Ok(());
+        }
+        tracing::warn!(
+            "[SOVEREIGN] {} failed its checksum (attempt {}/{}): expected {}, got {}",
+            url,
+            attempt,
+            DOWNLOAD_ATTEMPTS,
+            expected,
+            actual
+        );
+        let _ = tokio::fs::remove_file(dest).await;
+    }
+    anyhow::bail!(
+        "{} failed its checksum {} times; the download is corrupt",
+        url,
+        DOWNLOAD_ATTEMPTS
+    )
+}
+
+/// The SHA-256 Hugging Face publishes for an LFS file, from the
+/// `x-linked-etag` header on its (unfollowed) redirect.
+async fn published_sha256(url: &str) -> Option<String> {
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .ok()?;
+    let resp = client.head(url).send().await.ok()?;
+    let etag = resp.headers().get("x-linked-etag")?.to_str().ok()?;
+    let hash = etag
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .to_ascii_lowercase();
+    (hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())).then_some(hash)
+}
+
 impl TranscriptionEngine {
     pub async fn new(model_name: Option<String>) -> Result<Self> {
-        let model_name = model_name.unwrap_or_else(|| "large-v3".to_string());
+        let model_name = model_name.unwrap_or_else(|| crate::config::current().whisper_model);
 
         // Locate or download the model in blocking task
         let model_path =
@@ -42,22 +307,25 @@ impl TranscriptionEngine {
         Ok(Self { model_path })
     }
 
+    /// The model file this engine transcribes with.
+    pub fn model_path(&self) -> &Path {
+        &self.model_path
+    }
+
     /// Ensure the GGML model is present (Sovereign Ear - ModelDownloader)
     fn ensure_model(model_name: &str) -> Result<PathBuf> {
-        // Use environment variable for cache dir if available
-        let base_dir = if let Ok(cache_env) = std::env::var("SYNOID_CACHE_DIR") {
-            PathBuf::from(cache_env).join("models")
-        } else {
-            dirs::cache_dir()
-                .unwrap_or_else(|| PathBuf::from("."))
-                .join("synoid")
-                .join("models")
-        };
-
-        fs::create_dir_all(&base_dir)?;
-
+        let model_path = model_path(model_name);
+        // A custom model is used as given: never downloaded, never swapped
+        // for the fallback
+        if is_custom_model(model_name) {
+            check_model_file(&model_path)?;
+            info!("[SOVEREIGN] Using custom Whisper model: {:?}", model_path);
+            return Ok(model_path);
+        }
+        if let Some(dir) = model_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
         let filename = format!("ggml-{}.bin", model_name);
-        let model_path = base_dir.join(&filename);
 
         if model_path.exists() {
             info!("[SOVEREIGN] Found cached Whisper model: {:?}", model_path);
@@ -67,9 +335,26 @@ impl TranscriptionEngine {
         info!("[SOVEREIGN] Downloading Whisper model: {}...", filename);
 
         // Use hf-hub to fetch from ggerganov/whisper.cpp
-        let api = Api::new()?;
-        let repo = api.model("ggerganov/whisper.cpp".to_string());
-        let downloaded_path = repo.get(&filename)?;
+        let fetched = Api::new()
+            .map_err(anyhow::Error::from)
+            .and_then(|api| Ok(api.model("ggerganov/whisper.cpp".to_string()).get(&filename)?));
+        let downloaded_path = match fetched {
+            Ok(path) => path,
+            Err(e) => {
+                // A smaller model already on disk beats no transcript at all
+                let fallback = self::model_path(FALLBACK_MODEL);
+                if model_name != FALLBACK_MODEL && fallback.exists() {
+                    tracing::warn!(
+                        "[SOVEREIGN] Could not download {} ({}); using the cached {} model.",
+                        filename,
+                        e,
+                        FALLBACK_MODEL
+                    );
+                    return Ok(fallback);
+                }
+                return Err(e.context(format!("Whisper model {} is not downloaded", model_name)));
+            }
+        };
 
         // Copy/Move to our cache location for persistence/control
         fs::copy(&downloaded_path, &model_path)?;
@@ -139,15 +424,31 @@ impl TranscriptionEngine {
         info!("[SOVEREIGN] ⚠️ Cloud transcription unavailable or failed. Falling back to local Sovereign Ear.");
 
         // Check for GPU availability
-        let gpu = get_gpu_context().await;
-        let use_gpu = gpu.has_gpu();
+        let use_gpu = get_gpu_context().await.has_gpu();
+        self.transcribe_local(audio_path, use_gpu).await
+    }
 
+    /// Transcribe with the local Whisper model only, never the cloud, on the
+    /// GPU when `use_gpu` and the build allows it.
+    pub async fn transcribe_local(
+        &self,
+        audio_path: &Path,
+        use_gpu: bool,
+    ) -> Result<Vec<TranscriptSegment>> {
         if use_gpu {
             info!("[SOVEREIGN] 🚀 GPU Acceleration ENABLED for Whisper");
         } else {
             info!("[SOVEREIGN] 🐌 Using CPU for transcription");
         }
 
+        // Long recordings go a window at a time instead of all in memory
+        let secs = hound::WavReader::open(audio_path)
+            .map(|r| r.duration() as f64 / r.spec().sample_rate as f64)
+            .unwrap_or(0.0);
+        if secs > 2.0 * STREAM_WINDOW_SECS {
+            return self.stream_local(audio_path, use_gpu, |_| {}).await;
+        }
+
         // 1. Prepare Audio
         // Running CPU-heavy audio processing in blocking thread
         let audio_path_buf = audio_path.to_path_buf();
@@ -165,6 +466,57 @@ impl TranscriptionEngine {
         Ok(segments)
     }
 
+    /// Transcribe with the local model in windows of STREAM_WINDOW_SECS,
+    /// calling `on_chunk` as each one finishes so results arrive long before
+    /// a multi-hour file is done. Only one window of audio is in memory at a
+    /// time. Windows overlap by STREAM_OVERLAP_SECS on each side so a
+    /// sentence across a boundary is heard whole by one of them; each
+    /// segment is kept by the window holding its midpoint. Returns every
+    /// segment, in order.
+    pub async fn transcribe_streaming(
+        &self,
+        audio_path: &Path,
+        on_chunk: impl FnMut(&TranscriptChunk),
+    ) -> Result<Vec<TranscriptSegment>> {
+        let use_gpu = get_gpu_context().await.has_gpu();
+        self.stream_local(audio_path, use_gpu, on_chunk).await
+    }
+
+    async fn stream_local(
+        &self,
+        audio_path: &Path,
+        use_gpu: bool,
+        mut on_chunk: impl FnMut(&TranscriptChunk),
+    ) -> Result<Vec<TranscriptSegment>> {
+        let audio_path_buf = audio_path.to_path_buf();
+        let model_path = self.model_path.clone();
+        let (tx, mut rx) = tokio::sync::mpsc::channel(2);
+
+        let worker = tokio::task::spawn_blocking(move || {
+            Self::transcribe_windows_blocking(&model_path, &audio_path_buf, use_gpu, tx)
+        });
+
+        let mut segments = Vec::new();
+        while let Some(chunk) = rx.recv().await {
+            let chunk: TranscriptChunk = chunk;
+            info!(
+                "[SOVEREIGN] Transcribed {:.0}s–{:.0}s: {} segments",
+                chunk.start,
+                chunk.end,
+                chunk.segments.len()
+            );
+            on_chunk(&chunk);
+            segments.extend(chunk.segments);
+        }
+        worker.await??;
+
+        info!(
+            "[SOVEREIGN] Streaming Transcription Complete: {} segments.",
+            segments.len()
+        );
+        Ok(segments)
+    }
+
     fn transcribe_blocking(
         model_path: &Path,
         audio_path: &Path,
@@ -174,67 +526,75 @@ impl TranscriptionEngine {
         let mut reader = hound::WavReader::open(audio_path).context("Open WAV")?;
         let spec = reader.spec();
 
-        let mut pcm_data: Vec<f32>;
-
-        let is_16k_mono = spec.sample_rate == 16000 && spec.channels == 1;
-
-        if is_16k_mono {
+        if spec.sample_rate == 16000 && spec.channels == 1 {
             info!("[SOVEREIGN] 🎧 Native 16kHz mono detected. Fast-path memory loading...");
-            // Pre-allocate for exactly the number of samples
-            pcm_data = Vec::with_capacity(reader.duration() as usize);
-
-            // Read directly into f32 vec
-            for sample in reader.samples::<i16>() {
-                if let Ok(s) = sample {
-                    pcm_data.push((s as f32) / 32768.0);
-                }
-            }
         } else {
             info!("[SOVEREIGN] 🐌 Downmixingxerx/resampling in memory. (Channels: {}, Rate: {}). This uses significant RAM.", spec.channels, spec.sample_rate);
+        }
+        let pcm_data = read_pcm(&mut reader, u32::MAX);
 
-            // Manual conversion and downmix to mono simultaneously
-            let channels = spec.channels as usize;
-            let mut f32_samples = Vec::with_capacity((reader.duration() as usize) / channels);
-            let mut sample_iter = reader.samples::<i16>();
-
-            while let Some(Ok(first_sample)) = sample_iter.next() {
-                let mut sum = first_sample as f32;
-                // Accumulate other channels
-                for _ in 1..channels {
-                    if let Some(Ok(s)) = sample_iter.next() {
-                        sum += s as f32;
-                    }
-                }
-                f32_samples.push((sum / channels as f32) / 32768.0);
-            }
+        let ctx = Self::load_context(model_path, use_gpu)?;
+        let mut state = ctx.create_state().context("Create state")?;
+        let segments = Self::run_whisper(&mut state, &pcm_data)?;
+        Ok(filter_hallucinations(segments))
+    }
 
-            // Resample if needed (Naive linear)
-            if spec.sample_rate != 16000 {
-                let ratio = 16000.0 / spec.sample_rate as f32;
-                let new_len = (f32_samples.len() as f32 * ratio) as usize;
-                pcm_data = Vec::with_capacity(new_len);
-                for i in 0..new_len {
-                    let src_idx = (i as f32 / ratio) as usize;
-                    if src_idx < f32_samples.len() {
-                        pcm_data.push(f32_samples[src_idx]);
-                    }
-                }
+    /// The blocking half of `transcribe_streaming`: one Whisper state reused
+    /// for every window, each window's segments sent as soon as they exist.
+    fn transcribe_windows_blocking(
+        model_path: &Path,
+        audio_path: &Path,
+        use_gpu: bool,
+        tx: tokio::sync::mpsc::Sender<TranscriptChunk>,
+    ) -> Result<()> {
+        let mut reader = hound::WavReader::open(audio_path).context("Open WAV")?;
+        let rate = reader.spec().sample_rate;
+        let total_secs = reader.duration() as f64 / rate as f64;
+
+        let ctx = Self::load_context(model_path, use_gpu)?;
+        let mut state = ctx.create_state().context("Create state")?;
+
+        let mut last_end = f64::NEG_INFINITY;
+        for window in stream_windows(total_secs, STREAM_WINDOW_SECS, STREAM_OVERLAP_SECS) {
+            reader
+                .seek((window.decode_start * rate as f64) as u32)
+                .context("Seek WAV")?;
+            let frames = ((window.decode_end - window.decode_start) * rate as f64).ceil() as u32;
+            let pcm = read_pcm(&mut reader, frames);
+            let heard = if pcm.is_empty() {
+                Vec::new()
             } else {
-                pcm_data = f32_samples;
+                filter_hallucinations(Self::run_whisper(&mut state, &pcm)?)
+            };
+            let segments = place_in_window(heard, &window, last_end);
+            if let Some(last) = segments.last() {
+                last_end = last.end;
+            }
+            let chunk = TranscriptChunk {
+                start: window.start,
+                end: window.end.min(total_secs),
+                segments,
+            };
+            // The caller stopped listening
+            if tx.blocking_send(chunk).is_err() {
+                break;
             }
         }
+        Ok(())
+    }
 
-        // Initialize Whisper with GPU parameters if requested
+    /// Load the model, on the GPU when `use_gpu` and the build allows it.
+    fn load_context(model_path: &Path, use_gpu: bool) -> Result<WhisperContext> {
         let params = WhisperContextParameters {
             use_gpu,
             ..Default::default()
         };
+        WhisperContext::new_with_params(model_path.to_str().unwrap(), params)
+            .map_err(|e| anyhow::anyhow!("Failed to load model: {:?}", e))
+    }
 
-        let ctx = WhisperContext::new_with_params(model_path.to_str().unwrap(), params)
-            .map_err(|e| anyhow::anyhow!("Failed to load model: {:?}", e))?;
-
-        let mut state = ctx.create_state().context("Create state")?;
-
+    /// Run Whisper on 16kHz mono `pcm`; times are relative to its start.
+    fn run_whisper(state: &mut WhisperState, pcm_data: &[f32]) -> Result<Vec<TranscriptSegment>> {
         let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
         params.set_print_special(false);
         params.set_no_context(true);
@@ -249,7 +609,7 @@ impl TranscriptionEngine {
         params.set_n_threads(num_threads);
 
         // Run
-        state.full(params, &pcm_data).context("Running inference")?;
+        state.full(params, pcm_data).context("Running inference")?;
 
         // Extract segments + word-level timestamps from tokens
         let num_segments = state.full_n_segments().context("Get segments count")?;
@@ -306,10 +666,130 @@ impl TranscriptionEngine {
             });
         }
 
-        Ok(filter_hallucinations(segments))
+        Ok(segments)
+    }
+}
+
+/// Read up to `max_frames` frames from `reader`'s position as 16kHz mono.
+fn read_pcm(reader: &mut hound::WavReader<std::io::BufReader<fs::File>>, max_frames: u32) -> Vec<f32> {
+    let spec = reader.spec();
+    let channels = spec.channels.max(1) as usize;
+    let limit = (max_frames as usize).saturating_mul(channels);
+
+    if spec.sample_rate == 16000 && channels == 1 {
+        // Read directly into f32 vec
+        return reader
+            .samples::<i16>()
+            .take(limit)
+            .filter_map(|s| s.ok())
+            .map(|s| s as f32 / 32768.0)
+            .collect();
+    }
+
+    // Manual conversion and downmix to mono simultaneously
+    let mut f32_samples = Vec::new();
+    let mut sample_iter = reader.samples::<i16>().take(limit);
+    while let Some(Ok(first_sample)) = sample_iter.next() {
+        let mut sum = first_sample as f32;
+        // Accumulate other channels
+        for _ in 1..channels {
+            if let Some(Ok(s)) = sample_iter.next() {
+                sum += s as f32;
+            }
+        }
+        f32_samples.push((sum / channels as f32) / 32768.0);
+    }
+
+    // Resample if needed (Naive linear)
+    if spec.sample_rate == 16000 {
+        return f32_samples;
     }
+    let ratio = 16000.0 / spec.sample_rate as f32;
+    let new_len = (f32_samples.len() as f32 * ratio) as usize;
+    (0..new_len)
+        .map(|i| (i as f32 / ratio) as usize)
+        .filter(|&src_idx| src_idx < f32_samples.len())
+        .map(|src_idx| f32_samples[src_idx])
+        .collect()
+}
+
+/// Seconds of audio per window of `transcribe_streaming`.
+pub const STREAM_WINDOW_SECS: f64 = 300.0;
+/// Extra audio decoded on each side of a window, so speech across its edges
+/// is heard whole.
+pub const STREAM_OVERLAP_SECS: f64 = 5.0;
+
+/// Segments of one finished window of `transcribe_streaming`, in source time.
+#[derive(Debug, Clone)]
+pub struct TranscriptChunk {
+    pub start: f64,
+    pub end: f64,
+    pub segments: Vec<TranscriptSegment>,
+}
+
+/// One window of a streamed transcription: the span it owns, and the wider
+/// span decoded around it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StreamWindow {
+    pub start: f64,
+    pub end: f64,
+    pub decode_start: f64,
+    pub decode_end: f64,
+}
+
+/// Windows of `window_secs` covering `total_secs`, each decoded with
+/// `overlap_secs` of context on both sides.
+pub fn stream_windows(total_secs: f64, window_secs: f64, overlap_secs: f64) -> Vec<StreamWindow> {
+    let mut windows = Vec::new();
+    let mut start = 0.0;
+    while start < total_secs {
+        let end = start + window_secs;
+        windows.push(StreamWindow {
+            start,
+            end,
+            decode_start: (start - overlap_secs).max(0.0),
+            decode_end: (end + overlap_secs).min(total_secs),
+        });
+        start = end;
+    }
+    windows
+}
+
+/// Move segments heard in `window`'s decoded audio to source time and keep
+/// the ones it owns: those whose midpoint falls in the window (the first and
+/// last window own everything before and after them). A segment starting
+/// before `last_end`, the end of the previous window's last segment, was
+/// already heard there and is dropped.
+pub fn place_in_window(
+    segments: Vec<TranscriptSegment>,
+    window: &StreamWindow,
+    last_end: f64,
+) -> Vec<TranscriptSegment> {
+    let first = window.start <= 0.0;
+    let last = window.decode_end <= window.end;
+    segments
+        .into_iter()
+        .map(|mut seg| {
+            seg.start += window.decode_start;
+            seg.end += window.decode_start;
+            for word in &mut seg.words {
+                word.start += window.decode_start;
+                word.end += window.decode_start;
+            }
+            seg
+        })
+        .filter(|seg| {
+            let mid = (seg.start + seg.end) / 2.0;
+            (first || mid >= window.start) && (last || mid < window.end)
+        })
+        .filter(|seg| seg.start >= last_end - STITCH_TOLERANCE_SECS)
+        .collect()
 }
 
+/// How far the two windows around a boundary may disagree on where a
+/// segment starts before it counts as heard twice.
+const STITCH_TOLERANCE_SECS: f64 = 0.25;
+
 /// Detect and strip Whisper hallucination loops.
 ///
 /// Whisper sometimes gets stuck repeating the same phrase for the rest of a
@@ -393,6 +873,22 @@ pub fn generate_srt(segments: &[TranscriptSegment]) -> String {
     srt_out
 }
 
+/// WebVTT for the same cues as `generate_srt`: a `WEBVTT` header and `.`
+/// as the millisecond separator.
+pub fn generate_vtt(segments: &[TranscriptSegment]) -> String {
+    let mut vtt_out = String::from("WEBVTT\n\n");
+    for (i, seg) in segments.iter().enumerate() {
+        vtt_out.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            i + 1,
+            format_vtt_time(seg.start),
+            format_vtt_time(seg.end),
+            seg.text.trim()
+        ));
+    }
+    vtt_out
+}
+
 /// Parse an SRT file into TranscriptSegments
 /// This allows reusing existing SRT files instead of re-transcribing
 pub fn parse_srt(srt_content: &str) -> Result<Vec<TranscriptSegment>> {
@@ -461,14 +957,164 @@ fn parse_srt_timestamp(timestamp: &str) -> Result<f64> {
 }
 
 fn format_srt_time(seconds: f64) -> String {
-    let hours = (seconds / 3600.0) as u32;
-    let mins = ((seconds % 3600.0) / 60.0) as u32;
-    let secs = (seconds % 60.0) as u32;
-    let millis = ((seconds.fract()) * 1000.0) as u32;
-
+    let (hours, mins, secs, millis) = split_cue_time(seconds);
     format!("{:02}:{:02}:{:02},{:03}", hours, mins, secs, millis)
 }
 
+fn format_vtt_time(seconds: f64) -> String {
+    let (hours, mins, secs, millis) = split_cue_time(seconds);
+    format!("{:02}:{:02}:{:02}.{:03}", hours, mins, secs, millis)
+}
+
+/// Hours, minutes, seconds and milliseconds, rounded to the nearest
+/// millisecond so 0.29s is 290ms rather than 289.
+fn split_cue_time(seconds: f64) -> (u64, u64, u64, u64) {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    (
+        total_ms / 3_600_000,
+        total_ms / 60_000 % 60,
+        total_ms / 1000 % 60,
+        total_ms % 1000,
+    )
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Subtitle Translation
+// Translate an existing SRT through the LLM without re-transcribing.
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Cues sent to the LLM per request — enough context for consistent phrasing
+/// without blowing the prompt budget.
+const TRANSLATE_BATCH_SIZE: usize = 25;
+
+/// Cues after translation. Cues the translator failed on keep their
+/// original text and are counted in `untranslated`.
+#[derive(Debug, Clone)]
+pub struct TranslatedCues {
+    pub segments: Vec<TranscriptSegment>,
+    pub untranslated: usize,
+}
+
+/// Translate cue texts in batches with `translate`, keeping every timing.
+///
+/// `translate` receives a batch of cue texts and must return the same number
+/// of translations. A batch that comes back with the wrong count is retried
+/// one cue at a time so a single bad response can't shift the timings.
+pub async fn translate_segments<F, Fut>(
+    segments: &[TranscriptSegment],
+    translate: F,
+) -> Result<TranslatedCues>
+where
+    F: Fn(Vec<String>) -> Fut,
+    Fut: std::future::Future<Output = Result<Vec<String>>>,
+{
+    let mut out = Vec::with_capacity(segments.len());
+    let mut untranslated = 0;
+
+    for batch in segments.chunks(TRANSLATE_BATCH_SIZE) {
+        let texts: Vec<String> = batch.iter().map(|s| s.text.clone()).collect();
+        let translated = match translate(texts.clone()).await {
+            Ok(t) if t.len() == texts.len() => t,
+            _ => {
+                tracing::warn!(
+                    "[TRANSLATE] Batch of {} cues came back malformed; retrying cue by cue",
+                    texts.len()
+                );
+                let mut single = Vec::with_capacity(texts.len());
+                for text in texts {
+                    match translate(vec![text.clone()]).await {
+                        Ok(mut t) if t.len() == 1 => single.push(t.remove(0)),
+                        _ => {
+                            untranslated += 1;
+                            single.push(text);
+                        }
+                    }
+                }
+                single
+            }
+        };
+
+        for (seg, text) in batch.iter().zip(translated) {
+            out.push(TranscriptSegment {
+                start: seg.start,
+                end: seg.end,
+                text,
+                words: Vec::new(),
+            });
+        }
+    }
+
+    if untranslated > 0 {
+        tracing::warn!(
+            "[TRANSLATE] {} of {} cues could not be translated and keep their original text",
+            untranslated,
+            segments.len()
+        );
+    }
+    Ok(TranslatedCues {
+        segments: out,
+        untranslated,
+    })
+}
+
+/// Ask the LLM to translate a batch of cues; expects a JSON array back.
+async fn llm_translate_batch(
+    agent: &crate::agent::gpt_oss_bridge::SynoidAgent,
+    target_lang: &str,
+    texts: Vec<String>,
+) -> Result<Vec<String>> {
+    let numbered = serde_json::to_string(&texts)?;
+    let prompt = format!(
+        "Translate each subtitle line in this JSON array into {}. \
+         Keep line breaks inside a line, keep names and on-screen terms as-is, \
+         and return ONLY a JSON array of {} strings in the same order.\n\n{}",
+        target_lang,
+        texts.len(),
+        numbered
+    );
+
+    let response = agent
+        .fast_reason(&prompt)
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let start = response.find('[').context("No JSON array in translation")?;
+    let end = response.rfind(']').context("No JSON array in translation")?;
+    let parsed: Vec<String> = serde_json::from_str(&response[start..=end])
+        .context("Parsing translated cues")?;
+    Ok(parsed)
+}
+
+/// Translate `input_srt` into `target_lang` and write `output_srt`,
+/// preserving cue count and timings. The file is written even when some cues
+/// failed; the result says how many.
+pub async fn translate_srt(
+    input_srt: &Path,
+    target_lang: &str,
+    output_srt: &Path,
+) -> Result<TranslatedCues> {
+    let content = fs::read_to_string(input_srt)
+        .with_context(|| format!("Reading {:?}", input_srt))?;
+    let segments = parse_srt(&content)?;
+    info!(
+        "[TRANSLATE] {} cues → {} ({} per batch)",
+        segments.len(),
+        target_lang,
+        TRANSLATE_BATCH_SIZE
+    );
+
+    let api_url = crate::config::current().api_url;
+    let agent = crate::agent::gpt_oss_bridge::SynoidAgent::new(&api_url, "default");
+
+    let translated =
+        translate_segments(&segments, |texts| llm_translate_batch(&agent, target_lang, texts))
+            .await?;
+
+    fs::write(output_srt, generate_srt(&translated.segments))
+        .with_context(|| format!("Writing {:?}", output_srt))?;
+    info!("[TRANSLATE] Wrote {:?}", output_srt);
+    Ok(translated)
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Script-Based Editing (Feature 1)
 // Users delete sentences from the transcript; SYNOID converts those removals
@@ -564,7 +1210,6 @@ impl ScriptEditor {
         input_path: &std::path::Path,
         output_path: &std::path::Path,
     ) -> Result<()> {
-        use tokio::process::Command;
 
         let concat_script = self.build_ffmpeg_concat_script(input_path);
         if concat_script.is_empty() {
@@ -582,7 +1227,7 @@ impl ScriptEditor {
             output_path
         );
 
-        let status = Command::new("ffmpeg")
+        let status = proc::ffmpeg(ProcClass::Render)
             .stealth()
             .args(["-y", "-f", "concat", "-safe", "0", "-i"])
             .arg(&concat_file)
@@ -615,3 +1260,259 @@ fn uuid_simple() -> String {
         .subsec_nanos();
     format!("{:x}", t)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serves `payloads` in turn, one per fetch.
+    struct FakeFetcher {
+        payloads: Vec<&'static [u8]>,
+        fetches: usize,
+    }
+
+    impl FakeFetcher {
+        fn serving(payloads: Vec<&'static [u8]>) -> Self {
+            Self {
+                payloads,
+                fetches: 0,
+            }
+        }
+    }
+
+    impl ModelFetcher for FakeFetcher {
+        fn fetch<'a>(
+            &'a mut self,
+            _url: &'a str,
+            dest: &'a Path,
+            progress: &'a mut (dyn FnMut(u64, Option<u64>) + Send),
+        ) -> FetchFuture<'a> {
+            Box::pin(async move {
+                let payload = self.payloads[self.fetches.min(self.payloads.len() - 1)];
+                self.fetches += 1;
+                fs::write(dest, payload)?;
+                progress(payload.len() as u64, Some(payload.len() as u64));
+                Ok(())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checksum_mismatch_downloads_again() {
+        const URL: &str = "https://models/ggml-tiny.bin";
+        let dest =
+            std::env::temp_dir().join(format!("synoid_fetch_{}.bin.part", std::process::id()));
+        let good: &[u8] = b"lmgg whisper weights";
+        let expected = sha256_of(good);
+        let mut progress = |_: u64, _: Option<u64>| {};
+
+        // A corrupt first download is replaced by a good second one
+        let mut fetcher = FakeFetcher::serving(vec![b"truncated", good]);
+        fetch_verified(&mut fetcher, URL, &dest, Some(&expected), &mut progress)
+            .await
+            .unwrap();
+        assert_eq!(fetcher.fetches, 2);
+        assert_eq!(fs::read(&dest).unwrap(), good);
+
+        // One that never matches gives up and leaves nothing behind
+        let mut fetcher = FakeFetcher::serving(vec![b"truncated"]);
+        let err = fetch_verified(&mut fetcher, URL, &dest, Some(&expected), &mut progress)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("checksum"), "{}", err);
+        assert_eq!(fetcher.fetches, DOWNLOAD_ATTEMPTS);
+        assert!(!dest.exists());
+
+        // Without a published hash the first download is kept
+        let mut fetcher = FakeFetcher::serving(vec![b"truncated"]);
+        fetch_verified(&mut fetcher, URL, &dest, None, &mut progress)
+            .await
+            .unwrap();
+        assert_eq!(fetcher.fetches, 1);
+        let _ = fs::remove_file(&dest);
+    }
+
+    fn sha256_of(bytes: &[u8]) -> String {
+        use sha2::{Digest, Sha256};
+        format!("{:x}", Sha256::digest(bytes))
+    }
+
+    #[tokio::test]
+    async fn test_custom_model_path_is_used_as_given() {
+        let dir =
+            std::env::temp_dir().join(format!("synoid_custom_whisper_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let model = dir.join("medical-small.bin");
+        fs::write(&model, b"lmgg\0\0\0\0").unwrap();
+        let spec = model.to_string_lossy().to_string();
+
+        assert!(is_custom_model(&spec));
+        assert!(!is_custom_model("large-v3"));
+        assert_eq!(model_path_in(Path::new("/elsewhere"), &spec), model);
+        let engine = TranscriptionEngine::new(Some(spec)).await.unwrap();
+        assert_eq!(engine.model_path(), model.as_path());
+
+        let junk = dir.join("notes.bin");
+        fs::write(&junk, b"not a model").unwrap();
+        let err = match TranscriptionEngine::new(Some(junk.to_string_lossy().to_string())).await {
+            Ok(_) => panic!("a file that is not a model was accepted"),
+            Err(e) => e.to_string(),
+        };
+        assert!(err.contains("not a Whisper model"), "{}", err);
+        let missing = dir.join("missing.gguf").to_string_lossy().to_string();
+        assert!(TranscriptionEngine::new(Some(missing)).await.is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_published_models_have_pinned_checksums() {
+        for model in &WHISPER_MODELS {
+            assert!(!is_custom_model(model.name));
+            assert_eq!(model.sha256.len(), 64, "{}", model.name);
+            assert!(
+                model.sha256.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()),
+                "{}",
+                model.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_subtitle_cues_are_numbered_and_timestamped() {
+        let segments: Vec<TranscriptSegment> = [(0.0, 1.29, " hello "), (61.5, 3725.004, "bye")]
+            .iter()
+            .map(|&(start, end, text)| TranscriptSegment {
+                start,
+                end,
+                text: text.to_string(),
+                words: Vec::new(),
+            })
+            .collect();
+
+        let srt = generate_srt(&segments);
+        let timing = regex::Regex::new(r"^\d{2}:\d{2}:\d{2},\d{3} --> \d{2}:\d{2}:\d{2},\d{3}$").unwrap();
+        let cues: Vec<Vec<&str>> = srt.trim_end().split("\n\n").map(|c| c.lines().collect()).collect();
+        assert_eq!(cues.len(), 2);
+        for (i, cue) in cues.iter().enumerate() {
+            assert_eq!(cue[0], (i + 1).to_string());
+            assert!(timing.is_match(cue[1]), "bad timing line {:?}", cue[1]);
+        }
+        assert_eq!(cues[0][1], "00:00:00,000 --> 00:00:01,290");
+        assert_eq!(cues[0][2], "hello");
+        assert_eq!(cues[1][1], "00:01:01,500 --> 01:02:05,004");
+        assert!((parse_srt(&srt).unwrap()[1].end - 3725.004).abs() < 1e-9);
+
+        let vtt = generate_vtt(&segments);
+        assert!(vtt.starts_with("WEBVTT\n\n1\n00:00:00.000 --> 00:00:01.290\nhello\n"));
+        assert!(vtt.contains("\n2\n00:01:01.500 --> 01:02:05.004\nbye\n"));
+    }
+
+    #[test]
+    fn test_streamed_windows_are_offset_and_stitched() {
+        let seg = |start: f64, end: f64, text: &str| TranscriptSegment {
+            start,
+            end,
+            text: text.to_string(),
+            words: vec![WordTimestamp { word: text.to_string(), start, end }],
+        };
+        let windows = stream_windows(25.0, 10.0, 2.0);
+        let spans: Vec<(f64, f64, f64, f64)> = windows
+            .iter()
+            .map(|w| (w.start, w.end, w.decode_start, w.decode_end))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![(0.0, 10.0, 0.0, 12.0), (10.0, 20.0, 8.0, 22.0), (20.0, 30.0, 18.0, 25.0)]
+        );
+
+        // Window one hears 0–12s; "across" runs 9–11s over the boundary.
+        let one = place_in_window(
+            vec![seg(1.0, 4.0, "first"), seg(9.0, 11.0, "across"), seg(11.2, 11.9, "early")],
+            &windows[0],
+            f64::NEG_INFINITY,
+        );
+        let texts: Vec<&str> = one.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["first"], "segments past the midpoint belong to the next window");
+
+        // Window two's audio starts at 8s, so its times are shifted by 8.
+        let two = place_in_window(
+            vec![seg(1.0, 3.0, "across"), seg(3.2, 3.9, "early"), seg(5.0, 7.0, "second")],
+            &windows[1],
+            one.last().unwrap().end,
+        );
+        let placed: Vec<(f64, f64, &str)> =
+            two.iter().map(|s| (s.start, s.end, s.text.as_str())).collect();
+        assert_eq!(
+            placed,
+            vec![(9.0, 11.0, "across"), (11.2, 11.9, "early"), (13.0, 15.0, "second")]
+        );
+        assert_eq!((two[2].words[0].start, two[2].words[0].end), (13.0, 15.0));
+        assert!(two[0].start >= one.last().unwrap().end, "chunk two follows chunk one");
+
+        // A segment the last window re-hears at the boundary is not repeated.
+        let three = place_in_window(
+            vec![seg(1.0, 3.0, "again"), seg(3.0, 6.0, "last")],
+            &windows[2],
+            21.0,
+        );
+        let texts: Vec<&str> = three.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["last"]);
+    }
+
+    #[tokio::test]
+    async fn test_translation_preserves_timings_and_count() {
+        let srt = (1..=60)
+            .map(|i| {
+                format!(
+                    "{}\n00:00:{:02},000 --> 00:00:{:02},500\nline {}\n\n",
+                    i,
+                    i - 1,
+                    i - 1,
+                    i
+                )
+            })
+            .collect::<String>();
+        let segments = parse_srt(&srt).unwrap();
+
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+        let translated = translate_segments(&segments, |texts| {
+            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            async move { Ok(texts.iter().map(|t| format!("[fr] {}", t)).collect()) }
+        })
+        .await
+        .unwrap();
+        assert_eq!(translated.untranslated, 0);
+        let translated = translated.segments;
+
+        assert_eq!(translated.len(), segments.len());
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 3); // batched, not per line
+        for (orig, new) in segments.iter().zip(&translated) {
+            assert_eq!(orig.start, new.start);
+            assert_eq!(orig.end, new.end);
+            assert_eq!(new.text, format!("[fr] {}", orig.text));
+        }
+        assert_eq!(parse_srt(&generate_srt(&translated)).unwrap().len(), 60);
+    }
+
+    #[tokio::test]
+    async fn test_translation_counts_cues_left_untranslated() {
+        let srt = "1\n00:00:00,000 --> 00:00:01,000\nhello\n\n\
+                   2\n00:00:01,000 --> 00:00:02,000\nbroken\n\n\
+                   3\n00:00:02,000 --> 00:00:03,000\nbye\n\n";
+        let segments = parse_srt(srt).unwrap();
+
+        // The batch fails as a whole, then "broken" fails on its own too
+        let translated = translate_segments(&segments, |texts| async move {
+            if texts.len() > 1 || texts[0] == "broken" {
+                anyhow::bail!("model unavailable");
+            }
+            Ok(texts.iter().map(|t| format!("[fr] {}", t)).collect())
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(translated.untranslated, 1);
+        let texts: Vec<&str> = translated.segments.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["[fr] hello", "broken", "[fr] bye"]);
+    }
+}
@@ -2,9 +2,9 @@
 // Copyright (c) 2026 xingxerx_The_Creator | SYNOID
 
 use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
-use tokio::process::Command;
 use tracing::info;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +14,9 @@ pub struct VisualScene {
     pub scene_score: f64,
 }
 
+/// Scene-change score above which `scan_visual` reports a new scene.
+pub const VISUAL_SCENE_THRESHOLD: f64 = 0.3;
+
 /// Scan video for visual scenes using FFmpeg/FFprobe
 /// In a real implementation this might call Cuda kernels, but here we perform a simulated scan
 /// or use ffprobe's scene detection filter.
@@ -24,13 +27,13 @@ pub async fn scan_visual(
 
     // Using ffmpeg to detect scene changes (>0.3 difference)
     // metadata=print:file=- outputs metadata to stdout
-    let output = Command::new("ffmpeg")
+    let output = proc::ffmpeg(ProcClass::Thumbnail)
         .stealth()
         .args(["-v", "error", "-i"])
         .arg(path)
         .args([
             "-vf",
-            "select='gt(scene,0.3)',metadata=print:file=-",
+            &format!("select='gt(scene,{})',metadata=print:file=-", VISUAL_SCENE_THRESHOLD),
             "-f",
             "null",
             "-",
@@ -47,18 +50,19 @@ pub async fn scan_visual(
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let mut scenes = Vec::new();
+    let scenes = visual_scenes_from_cuts(&parse_scene_scores(&stdout));
 
-    // Always add start as a scene
-    scenes.push(VisualScene {
-        timestamp: 0.0,
-        motion_score: 0.0,
-        scene_score: 1.0,
-    });
+    info!("[EYES] Detected {} scenes.", scenes.len());
+    Ok(scenes)
+}
 
+/// `(pts_time, scene score)` of every frame in `metadata=print` output
+/// from a `select='gt(scene,..)'` filter, in order.
+pub fn parse_scene_scores(metadata: &str) -> Vec<(f64, f64)> {
+    let mut cuts = Vec::new();
     let mut current_pts: Option<f64> = None;
 
-    for line in stdout.lines() {
+    for line in metadata.lines() {
         // FFmpeg metadata output looks like:
         // frame:0    pts:21      pts_time:0.021029
         // lavfi.scene_score=0.450000
@@ -72,32 +76,36 @@ pub async fn scan_visual(
         } else if line.contains("lavfi.scene_score=") {
             if let (Some(ts), Some(score_str)) = (current_pts, line.split('=').last()) {
                 if let Ok(score) = score_str.trim().parse::<f64>() {
-                    // Avoid duplicate 0.0 or very close timestamps
-                    if !scenes.is_empty() && (ts - scenes.last().unwrap().timestamp).abs() < 0.5 {
-                        continue;
-                    }
-
-                    scenes.push(VisualScene {
-                        timestamp: ts,
-                        motion_score: score,
-                        scene_score: score,
-                    });
+                    cuts.push((ts, score));
                 }
             }
         }
     }
+    cuts
+}
+
+/// Visual scenes from `(timestamp, score)` scene changes: the start, then
+/// each change at least 0.5 s after the previous one.
+pub fn visual_scenes_from_cuts(cuts: &[(f64, f64)]) -> Vec<VisualScene> {
+    // Always add start as a scene
+    let mut scenes = vec![VisualScene {
+        timestamp: 0.0,
+        motion_score: 0.0,
+        scene_score: 1.0,
+    }];
 
-    // Fallback if no scenes detected (e.g. short video or no changes) - ensure at least start is there
-    if scenes.is_empty() {
+    for &(ts, score) in cuts {
+        // Avoid duplicate 0.0 or very close timestamps
+        if (ts - scenes.last().map_or(f64::NEG_INFINITY, |s| s.timestamp)).abs() < 0.5 {
+            continue;
+        }
         scenes.push(VisualScene {
-            timestamp: 0.0,
-            motion_score: 0.0,
-            scene_score: 1.0,
+            timestamp: ts,
+            motion_score: score,
+            scene_score: score,
         });
     }
-
-    info!("[EYES] Detected {} scenes.", scenes.len());
-    Ok(scenes)
+    scenes
 }
 
 /// Analyze an image using the local Ollama VLM (e.g. llava:latest).
@@ -114,10 +122,11 @@ pub async fn analyze_image_gemini(
     let image_bytes = std::fs::read(image_path)?;
     let image_base64 = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &image_bytes);
 
-    let ollama_url = std::env::var("SYNOID_API_URL")
-        .unwrap_or_else(|_| "http://localhost:11434".to_string());
-    let vision_model = std::env::var("SYNOID_VISION_MODEL")
-        .unwrap_or_else(|_| "llava:latest".to_string());
+    let crate::config::SynoidConfig {
+        api_url: ollama_url,
+        vision_model,
+        ..
+    } = crate::config::current();
     let base = ollama_url.trim_end_matches('/').trim_end_matches("/v1");
 
     let payload = serde_json::json!({
@@ -232,6 +241,20 @@ impl SemanticIndex {
         scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
         scored
     }
+
+    /// Merge an OCR timeline (see `ocr_timeline`) so on-screen text is
+    /// searchable next to the VLM descriptions.
+    pub fn add_on_screen_text(&mut self, entries: &[(f64, String)]) {
+        for (timestamp, text) in entries {
+            self.frames.push(FrameMetadata {
+                timestamp: *timestamp,
+                description: text.clone(),
+                tags: vec!["on_screen_text".to_string()],
+            });
+        }
+        self.frames
+            .sort_by(|a, b| a.timestamp.partial_cmp(&b.timestamp).unwrap_or(std::cmp::Ordering::Equal));
+    }
 }
 
 /// Sample one frame every `interval_secs` seconds, ask the local Ollama VLM
@@ -333,13 +356,279 @@ pub async fn describe_frame_multi_provider(
     })
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// On-Screen Text (OCR)
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Sample one frame every `interval_secs` seconds and OCR it with the
+/// `tesseract` CLI. Returns `(timestamp, text)` pairs; frames with no text
+/// are dropped and consecutive identical readings (a slide held on screen)
+/// collapse into the first occurrence.
+pub async fn ocr_timeline(
+    video_path: &Path,
+    interval_secs: f64,
+) -> Result<Vec<(f64, String)>, Box<dyn std::error::Error + Send + Sync>> {
+    if interval_secs <= 0.0 {
+        return Err("OCR interval must be positive".into());
+    }
+    info!(
+        "[OCR] Extracting on-screen text from {:?} (interval: {:.1}s)",
+        video_path, interval_secs
+    );
+
+    let duration = crate::agent::source_tools::get_video_duration(video_path).await?;
+
+    let tmp_dir = std::env::temp_dir().join(format!("synoid_ocr_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&tmp_dir)?;
+
+    let mut readings = Vec::new();
+    let mut t = 0.0f64;
+    while t < duration {
+        let frame_path = tmp_dir.join(format!("frame_{:.3}.jpg", t));
+        extract_frame(video_path, t, &frame_path).await.ok();
+
+        if frame_path.exists() {
+            // Unmanaged: OCR of one extracted still, like the image decode
+            // beside it
+            let output = proc::unmanaged("tesseract")
+                .stealth()
+                .arg(&frame_path)
+                .args(["stdout", "--psm", "3"])
+                .output()
+                .await;
+            let _ = std::fs::remove_file(&frame_path);
+
+            match output {
+                Ok(out) if out.status.success() => {
+                    readings.push((t, String::from_utf8_lossy(&out.stdout).to_string()));
+                }
+                Ok(out) => {
+                    tracing::warn!(
+                        "[OCR] tesseract failed at {:.1}s: {}",
+                        t,
+                        String::from_utf8_lossy(&out.stderr).trim()
+                    );
+                }
+                Err(e) => {
+                    let _ = std::fs::remove_dir_all(&tmp_dir);
+                    return Err(format!("tesseract not available: {}", e).into());
+                }
+            }
+        }
+
+        t += interval_secs;
+    }
+
+    let _ = std::fs::remove_dir_all(&tmp_dir);
+    let timeline = collapse_ocr_readings(readings);
+    info!("[OCR] {} distinct text blocks found.", timeline.len());
+    Ok(timeline)
+}
+
+/// Normalise whitespace, drop empty readings and collapse runs of identical text.
+fn collapse_ocr_readings(readings: Vec<(f64, String)>) -> Vec<(f64, String)> {
+    let mut out: Vec<(f64, String)> = Vec::new();
+    for (t, raw) in readings {
+        let text = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() {
+            continue;
+        }
+        if out.last().map(|(_, prev)| *prev == text).unwrap_or(false) {
+            continue;
+        }
+        out.push((t, text));
+    }
+    out
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Sponsor / Logo Detection
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Normalised cross-correlation above which a logo counts as on screen.
+pub const SPONSOR_MATCH_THRESHOLD: f64 = 0.8;
+
+/// Width frames are downscaled to before template matching.
+const LOGO_SCAN_WIDTH: u32 = 320;
+
+/// Best normalised cross-correlation of `template` anywhere inside `frame`,
+/// clamped to [0, 1]. Flat (zero-variance) templates never match.
+pub fn template_match_score(frame: &image::GrayImage, template: &image::GrayImage) -> f64 {
+    let (fw, fh) = frame.dimensions();
+    let (tw, th) = template.dimensions();
+    if tw == 0 || th == 0 || tw > fw || th > fh {
+        return 0.0;
+    }
+
+    let n = (tw * th) as f64;
+    let t_mean = template.pixels().map(|p| p[0] as f64).sum::<f64>() / n;
+    let t_dev: Vec<f64> = template.pixels().map(|p| p[0] as f64 - t_mean).collect();
+    let t_norm = t_dev.iter().map(|d| d * d).sum::<f64>().sqrt();
+    if t_norm < 1e-6 {
+        return 0.0;
+    }
+
+    let frame_raw = frame.as_raw();
+    let mut best = 0.0f64;
+    for y in 0..=(fh - th) {
+        for x in 0..=(fw - tw) {
+            let mut sum = 0.0;
+            for ty in 0..th {
+                let row = ((y + ty) * fw + x) as usize;
+                for tx in 0..tw {
+                    sum += frame_raw[row + tx as usize] as f64;
+                }
+            }
+            let f_mean = sum / n;
+
+            let mut cross = 0.0;
+            let mut f_sq = 0.0;
+            for ty in 0..th {
+                let row = ((y + ty) * fw + x) as usize;
+                for tx in 0..tw {
+                    let fd = frame_raw[row + tx as usize] as f64 - f_mean;
+                    cross += fd * t_dev[(ty * tw + tx) as usize];
+                    f_sq += fd * fd;
+                }
+            }
+            if f_sq > 1e-6 {
+                best = best.max(cross / (f_sq.sqrt() * t_norm));
+            }
+        }
+    }
+    best.clamp(0.0, 1.0)
+}
+
+/// Sample one frame every `interval_secs` seconds and return the spans
+/// (start, end) in which any logo template from `logo_dir` is on screen.
+///
+/// Templates are PNG/JPEG crops of the logo at the source video's
+/// resolution; each is also tried at 75% and 125% to tolerate rescaled
+/// overlays. Adjacent hits merge into one span.
+pub async fn detect_sponsor_spans(
+    video_path: &Path,
+    logo_dir: &Path,
+    interval_secs: f64,
+) -> Result<Vec<(f64, f64)>, Box<dyn std::error::Error + Send + Sync>> {
+    let interval_secs = interval_secs.max(0.25);
+    let template_paths: Vec<PathBuf> = std::fs::read_dir(logo_dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.extension()
+                .and_then(|e| e.to_str())
+                .map(|e| matches!(e.to_lowercase().as_str(), "png" | "jpg" | "jpeg"))
+                .unwrap_or(false)
+        })
+        .collect();
+    if template_paths.is_empty() {
+        return Err(format!("No logo templates (png/jpg) found in {:?}", logo_dir).into());
+    }
+    info!(
+        "[SPONSOR] Scanning {:?} for {} logo template(s) every {:.1}s",
+        video_path,
+        template_paths.len(),
+        interval_secs
+    );
+
+    // Source width decides how far templates shrink to match the scan frames.
+    let probe = proc::ffprobe()
+        .stealth()
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "v:0",
+            "-show_entries",
+            "stream=width",
+            "-of",
+            "csv=p=0",
+        ])
+        .arg(video_path)
+        .output()
+        .await?;
+    let source_width: f64 = String::from_utf8_lossy(&probe.stdout)
+        .trim()
+        .parse()
+        .unwrap_or(1920.0);
+    let base_scale = LOGO_SCAN_WIDTH as f64 / source_width.max(1.0);
+
+    let mut templates = Vec::new();
+    for path in &template_paths {
+        let img = image::open(path)?.to_luma8();
+        for factor in [1.0, 0.75, 1.25] {
+            let w = (img.width() as f64 * base_scale * factor).round() as u32;
+            let h = (img.height() as f64 * base_scale * factor).round() as u32;
+            if w >= 4 && h >= 4 {
+                templates.push(image::imageops::resize(
+                    &img,
+                    w,
+                    h,
+                    image::imageops::FilterType::Triangle,
+                ));
+            }
+        }
+    }
+
+    let tmp_dir = std::env::temp_dir().join(format!("synoid_sponsor_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&tmp_dir)?;
+    let status = proc::ffmpeg(ProcClass::Thumbnail)
+        .stealth()
+        .args(["-y", "-v", "error", "-i"])
+        .arg(video_path)
+        .args([
+            "-vf",
+            &format!("fps=1/{},scale={}:-2", interval_secs, LOGO_SCAN_WIDTH),
+            "-q:v",
+            "3",
+        ])
+        .arg(tmp_dir.join("%06d.jpg"))
+        .status()
+        .await?;
+    if !status.success() {
+        let _ = std::fs::remove_dir_all(&tmp_dir);
+        return Err("FFmpeg frame sampling for sponsor scan failed".into());
+    }
+
+    let scan_dir = tmp_dir.clone();
+    let hits = tokio::task::spawn_blocking(move || {
+        let mut frames: Vec<PathBuf> = std::fs::read_dir(&scan_dir)
+            .map(|rd| rd.filter_map(|e| e.ok().map(|e| e.path())).collect())
+            .unwrap_or_default();
+        frames.sort();
+        frames
+            .iter()
+            .enumerate()
+            .filter_map(|(i, path)| {
+                let frame = image::open(path).ok()?.to_luma8();
+                let matched = templates
+                    .iter()
+                    .any(|t| template_match_score(&frame, t) >= SPONSOR_MATCH_THRESHOLD);
+                matched.then_some(i as f64 * interval_secs)
+            })
+            .collect::<Vec<f64>>()
+    })
+    .await?;
+    let _ = std::fs::remove_dir_all(&tmp_dir);
+
+    let mut spans: Vec<(f64, f64)> = Vec::new();
+    for t in hits {
+        let (start, end) = ((t - interval_secs * 0.5).max(0.0), t + interval_secs * 0.5);
+        match spans.last_mut() {
+            Some(last) if start <= last.1 + 1e-6 => last.1 = end,
+            _ => spans.push((start, end)),
+        }
+    }
+    info!("[SPONSOR] {} sponsor span(s) detected.", spans.len());
+    Ok(spans)
+}
+
 /// Extract a single JPEG frame from a video at `time_secs`.
 async fn extract_frame(
     video_path: &Path,
     time_secs: f64,
     output: &PathBuf,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    Command::new("ffmpeg")
+    proc::ffmpeg(ProcClass::Thumbnail)
         .stealth()
         .args(["-y", "-ss", &time_secs.to_string(), "-i"])
         .arg(video_path)
@@ -485,7 +774,7 @@ pub async fn generative_extend(
                 synth_clip.display()
             ),
         )?;
-        let status = Command::new("ffmpeg")
+        let status = proc::ffmpeg(ProcClass::Render)
             .stealth()
             .args(["-y", "-f", "concat", "-safe", "0", "-i"])
             .arg(&concat_list)
@@ -499,7 +788,7 @@ pub async fn generative_extend(
     } else {
         // Fallback: freeze-frame extend using FFmpeg's tpad filter
         info!("[GEN-EXTEND] ComfyUI unavailable; falling back to freeze-frame extend.");
-        let status = Command::new("ffmpeg")
+        let status = proc::ffmpeg(ProcClass::Render)
             .stealth()
             .args(["-y", "-i"])
             .arg(input_path)
@@ -601,7 +890,7 @@ async fn request_comfyui_extension(
     // As a simple heuristic: check if ComfyUI wrote an output we can convert
     let comfy_out = PathBuf::from("/tmp/comfyui_output/synoid_ext_00001.webp");
     if comfy_out.exists() {
-        Command::new("ffmpeg")
+        proc::ffmpeg(ProcClass::Render)
             .stealth()
             .args(["-y", "-i"])
             .arg(&comfy_out)
@@ -642,7 +931,7 @@ pub async fn correct_eye_contact(
     }
 
     // Non-destructive copy (preserves original while infra is wired up)
-    let status = Command::new("ffmpeg")
+    let status = proc::ffmpeg(ProcClass::Render)
         .stealth()
         .args(["-y", "-i"])
         .arg(input_path)
@@ -704,3 +993,52 @@ pub fn calculate_optical_flow_diff(frame1: &Path, frame2: &Path) -> f64 {
         0.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consecutive_ocr_readings_collapse() {
+        let readings = vec![
+            (0.0, "Intro to Rust\n".to_string()),
+            (2.0, "Intro  to Rust".to_string()),
+            (4.0, "   ".to_string()),
+            (6.0, "fn main() {}".to_string()),
+            (8.0, "Intro to Rust".to_string()),
+        ];
+        let timeline = collapse_ocr_readings(readings);
+        assert_eq!(
+            timeline,
+            vec![
+                (0.0, "Intro to Rust".to_string()),
+                (6.0, "fn main() {}".to_string()),
+                (8.0, "Intro to Rust".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_template_match_flags_logo_frame() {
+        // Checkerboard "logo" stamped into an otherwise noisy-gradient frame.
+        let logo = image::GrayImage::from_fn(8, 8, |x, y| {
+            image::Luma([if (x / 2 + y / 2) % 2 == 0 { 240 } else { 15 }])
+        });
+        let background = |x: u32, y: u32| image::Luma([((x * 7 + y * 3) % 90 + 60) as u8]);
+
+        let mut with_logo = image::GrayImage::from_fn(48, 32, background);
+        image::imageops::replace(&mut with_logo, &logo, 20, 10);
+        let without_logo = image::GrayImage::from_fn(48, 32, background);
+
+        assert!(template_match_score(&with_logo, &logo) >= SPONSOR_MATCH_THRESHOLD);
+        assert!(template_match_score(&without_logo, &logo) < SPONSOR_MATCH_THRESHOLD);
+    }
+
+    #[test]
+    fn test_on_screen_text_is_searchable() {
+        let mut index = SemanticIndex::default();
+        index.add_on_screen_text(&[(12.0, "cargo build --release".to_string())]);
+        let hits = index.search("release build");
+        assert_eq!(hits.first().map(|h| h.0), Some(12.0));
+    }
+}
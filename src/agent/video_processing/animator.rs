@@ -1,6 +1,6 @@
+use crate::agent::engines::proc;
 use crate::agent::engines::process_utils::CommandExt;
 use std::path::{Path, PathBuf};
-use tokio::process::Command;
 use tracing::{info, warn};
 
 pub struct Animator {
@@ -45,10 +45,11 @@ impl Animator {
         }
 
         // npx remotion render src/index.tsx <CompositionName> <Output> --props <Payload>
+        // Unmanaged: Remotion schedules its own render workers
         let mut cmd = if cfg!(windows) {
-            Command::new("cmd")
+            proc::unmanaged("cmd")
         } else {
-            Command::new("sh")
+            proc::unmanaged("sh")
         };
         cmd.stealth();
 
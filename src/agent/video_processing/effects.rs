@@ -0,0 +1,448 @@
+// SYNOID Effects Registry — Render-Stage Visual Effects
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Effects that run as one extra FFmpeg pass over a finished render. Each
+// effect turns the probed stream facts (size, frame rate, musical onsets)
+// into a filter; the registry chains every active effect into a single
+// `-filter_script:v` so the output is re-encoded once however many are on.
+//
+// The first entry is `audio_reactive`: a zoom pulse on every bass hit, for
+// music-heavy montages ("make it pump with the music").
+
+use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::video_processing::vector_engine::probe_geometry;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::info;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Registry
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// What an effect can key off when building its filter.
+#[derive(Debug, Clone, Default)]
+pub struct EffectContext {
+    pub width: usize,
+    pub height: usize,
+    pub fps: f64,
+    /// Detected musical onsets in seconds, ascending.
+    pub onsets: Vec<f64>,
+}
+
+pub trait VideoEffect: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Whether `EffectContext::onsets` must be filled before `filter` runs.
+    fn needs_onsets(&self) -> bool {
+        false
+    }
+
+    /// FFmpeg video filter for this context, or `None` when the effect has
+    /// nothing to do (e.g. no onsets were found).
+    fn filter(&self, ctx: &EffectContext) -> Option<String>;
+}
+
+#[derive(Default)]
+pub struct EffectsRegistry {
+    effects: Vec<Box<dyn VideoEffect>>,
+}
+
+impl EffectsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an effect; one with the same name replaces the earlier entry.
+    pub fn register(&mut self, effect: Box<dyn VideoEffect>) {
+        self.effects.retain(|e| e.name() != effect.name());
+        self.effects.push(effect);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn VideoEffect> {
+        self.effects.iter().find(|e| e.name() == name).map(|e| e.as_ref())
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.effects.iter().map(|e| e.name()).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    fn needs_onsets(&self) -> bool {
+        self.effects.iter().any(|e| e.needs_onsets())
+    }
+
+    /// Comma-joined filter chain of every effect that produced a filter.
+    pub fn build_chain(&self, ctx: &EffectContext) -> Option<String> {
+        let parts: Vec<String> = self.effects.iter().filter_map(|e| e.filter(ctx)).collect();
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join(","))
+        }
+    }
+
+    /// Render `input` through every registered effect into `output`. Returns
+    /// `Ok(false)` without writing anything when no effect had work to do.
+    pub async fn apply(&self, input: &Path, output: &Path, work_dir: &Path) -> Result<bool> {
+        if self.is_empty() {
+            return Ok(false);
+        }
+        let geo = probe_geometry(input).await?;
+        let mut ctx = EffectContext {
+            width: geo.width,
+            height: geo.height,
+            fps: geo.fps,
+            onsets: Vec::new(),
+        };
+        if self.needs_onsets() {
+            ctx.onsets = detect_onsets(input).await?;
+            info!("[EFFECTS] 🥁 {} onset(s) detected", ctx.onsets.len());
+        }
+        let Some(chain) = self.build_chain(&ctx) else {
+            return Ok(false);
+        };
+
+        // Onset expressions run to hundreds of KB, far past the Windows
+        // command-line limit, so the graph goes through a script file.
+        let script = work_dir.join("effects_filter.txt");
+        std::fs::write(&script, &chain).context("Writing effects filter script")?;
+
+        info!("[EFFECTS] 🎛️ Applying {} → {:?}", self.names().join(", "), output);
+        let status = proc::ffmpeg(ProcClass::Render)
+            .stealth()
+            .args(["-y", "-hide_banner", "-loglevel", "error", "-nostdin", "-i"])
+            .arg(input)
+            .arg("-filter_script:v")
+            .arg(&script)
+            .args([
+                "-c:v", "libx264", "-preset", "fast", "-crf", "18", "-pix_fmt", "yuv420p",
+                "-c:a", "copy",
+            ])
+            .arg(output)
+            .status()
+            .await
+            .context("Running ffmpeg effects pass")?;
+        let _ = std::fs::remove_file(&script);
+
+        if !status.success() {
+            return Err(anyhow::anyhow!("ffmpeg effects pass exited with {}", status));
+        }
+        Ok(true)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Audio-reactive zoom
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Largest zoom a pulse may add (30%). The zoom never drops below 1.0, so the
+/// crop window always stays inside the frame.
+pub const MAX_PUMP_INTENSITY: f64 = 0.3;
+
+/// Only the strongest onsets are kept; every one adds a term that FFmpeg
+/// evaluates per frame.
+const MAX_PUMP_ONSETS: usize = 600;
+
+/// Zoom pulse keyed to detected onsets: ramps in over `attack_secs` before
+/// each hit, peaks at `1 + intensity` on it, then decays exponentially.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AudioReactive {
+    #[serde(default = "default_pump_intensity")]
+    pub intensity: f64,
+    #[serde(default = "default_pump_attack")]
+    pub attack_secs: f64,
+    #[serde(default = "default_pump_decay")]
+    pub decay_secs: f64,
+}
+
+fn default_pump_intensity() -> f64 {
+    0.06
+}
+fn default_pump_attack() -> f64 {
+    0.04
+}
+fn default_pump_decay() -> f64 {
+    0.18
+}
+
+impl Default for AudioReactive {
+    fn default() -> Self {
+        Self {
+            intensity: default_pump_intensity(),
+            attack_secs: default_pump_attack(),
+            decay_secs: default_pump_decay(),
+        }
+    }
+}
+
+impl AudioReactive {
+    /// Intensity, attack and decay forced into ranges that keep the zoom
+    /// within `[1, 1 + MAX_PUMP_INTENSITY]` and the expression finite.
+    pub fn clamped(&self) -> Self {
+        let finite = |v: f64, fallback: f64| if v.is_finite() { v } else { fallback };
+        Self {
+            intensity: finite(self.intensity, 0.0).clamp(0.0, MAX_PUMP_INTENSITY),
+            attack_secs: finite(self.attack_secs, default_pump_attack()).clamp(0.01, 1.0),
+            decay_secs: finite(self.decay_secs, default_pump_decay()).clamp(0.02, 2.0),
+        }
+    }
+
+    /// Zoom factor as an FFmpeg expression of the input time `it`.
+    pub fn zoom_expression(&self, onsets: &[f64]) -> String {
+        let p = self.clamped();
+        let (a, d) = (p.attack_secs, p.decay_secs);
+        let terms: Vec<String> = onsets
+            .iter()
+            .filter(|o| o.is_finite() && **o >= 0.0)
+            .map(|&o| {
+                format!(
+                    "if(between(it,{s:.4},{o:.4}),(it-{s:.4})/{a:.4},if(between(it,{o:.4},{e:.4}),exp(({o:.4}-it)/{d:.4}),0))",
+                    s = (o - a).max(0.0),
+                    o = o,
+                    e = o + 4.0 * d,
+                    a = a,
+                    d = d,
+                )
+            })
+            .collect();
+        if terms.is_empty() || p.intensity == 0.0 {
+            return "1".to_string();
+        }
+        format!("1+{:.4}*min(1,{})", p.intensity, terms.join("+"))
+    }
+
+    /// `zoompan` filter centring the pulse on the frame.
+    pub fn filter_for(&self, ctx: &EffectContext) -> String {
+        format!(
+            "zoompan=z='{}':x='iw/2-(iw/zoom/2)':y='ih/2-(ih/zoom/2)':d=1:s={}x{}:fps={:.3}",
+            self.zoom_expression(&ctx.onsets),
+            ctx.width,
+            ctx.height,
+            ctx.fps
+        )
+    }
+}
+
+impl VideoEffect for AudioReactive {
+    fn name(&self) -> &'static str {
+        "audio_reactive"
+    }
+
+    fn needs_onsets(&self) -> bool {
+        true
+    }
+
+    fn filter(&self, ctx: &EffectContext) -> Option<String> {
+        if ctx.onsets.is_empty() || self.clamped().intensity == 0.0 {
+            return None;
+        }
+        Some(self.filter_for(ctx))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Onset detection
+// ─────────────────────────────────────────────────────────────────────────────
+
+const ONSET_SAMPLE_RATE: u32 = 4000;
+const ONSET_HOP_SECS: f64 = 0.02;
+const ONSET_MIN_GAP_SECS: f64 = 0.2;
+
+/// Bass-band onsets of `input`'s audio, in seconds.
+pub async fn detect_onsets(input: &Path) -> Result<Vec<f64>> {
+    let output = proc::ffmpeg(ProcClass::Probe)
+        .stealth()
+        .args(["-hide_banner", "-loglevel", "error", "-nostdin", "-i"])
+        .arg(input)
+        .args(["-vn", "-ac", "1", "-af", "lowpass=f=150", "-ar"])
+        .arg(ONSET_SAMPLE_RATE.to_string())
+        .args(["-f", "s16le", "-"])
+        .output()
+        .await
+        .context("Running ffmpeg audio decode")?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "ffmpeg could not decode audio: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let samples: Vec<i16> = output
+        .stdout
+        .chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    Ok(onsets_from_samples(&samples, ONSET_SAMPLE_RATE))
+}
+
+/// Energy-flux onset picker: a hop is an onset when its rise in RMS energy
+/// is a local peak well above the surrounding second's average rise.
+pub fn onsets_from_samples(samples: &[i16], sample_rate: u32) -> Vec<f64> {
+    let hop = ((sample_rate as f64 * ONSET_HOP_SECS) as usize).max(1);
+    let energy: Vec<f64> = samples
+        .chunks(hop)
+        .map(|c| (c.iter().map(|&s| (s as f64).powi(2)).sum::<f64>() / c.len() as f64).sqrt())
+        .collect();
+    if energy.len() < 3 {
+        return Vec::new();
+    }
+    let flux: Vec<f64> = std::iter::once(0.0)
+        .chain(energy.windows(2).map(|w| (w[1] - w[0]).max(0.0)))
+        .collect();
+
+    let radius = (0.5 / ONSET_HOP_SECS) as usize;
+    let floor = energy.iter().sum::<f64>() / energy.len() as f64 * 0.1;
+    let mut picked: Vec<(usize, f64)> = Vec::new();
+    for i in 1..flux.len() - 1 {
+        let lo = i.saturating_sub(radius);
+        let hi = (i + radius + 1).min(flux.len());
+        let local_mean = flux[lo..hi].iter().sum::<f64>() / (hi - lo) as f64;
+        let is_peak = flux[i] > flux[i - 1] && flux[i] >= flux[i + 1];
+        if is_peak && flux[i] > local_mean * 1.5 && flux[i] > floor {
+            let min_gap = (ONSET_MIN_GAP_SECS / ONSET_HOP_SECS) as usize;
+            match picked.last_mut() {
+                Some(last) if i - last.0 < min_gap => {
+                    if flux[i] > last.1 {
+                        *last = (i, flux[i]);
+                    }
+                }
+                _ => picked.push((i, flux[i])),
+            }
+        }
+    }
+
+    if picked.len() > MAX_PUMP_ONSETS {
+        picked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        picked.truncate(MAX_PUMP_ONSETS);
+        picked.sort_by_key(|p| p.0);
+    }
+    picked.into_iter().map(|(i, _)| i as f64 * ONSET_HOP_SECS).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal check of FFmpeg expression grammar: known functions and
+    /// variables only, numbers without exponents, balanced parentheses and
+    /// no empty arguments.
+    fn assert_valid_ffmpeg_expr(expr: &str) {
+        const FUNCS: &[&str] = &["if", "between", "exp", "min"];
+        let mut depth = 0i32;
+        let mut prev = ' ';
+        let mut chars = expr.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    assert!(prev != ',' && prev != '(', "empty argument in {}", expr);
+                    depth -= 1;
+                    assert!(depth >= 0, "unbalanced ')' in {}", expr);
+                }
+                ',' => assert!(depth > 0 && prev != ',' && prev != '(', "stray ',' in {}", expr),
+                '+' | '-' | '*' | '/' => {}
+                '0'..='9' | '.' => {}
+                c if c.is_ascii_alphabetic() => {
+                    let mut ident = c.to_string();
+                    while let Some(&n) = chars.peek() {
+                        if !n.is_ascii_alphabetic() {
+                            break;
+                        }
+                        ident.push(n);
+                        chars.next();
+                    }
+                    if ident == "it" {
+                        prev = 't';
+                        continue;
+                    }
+                    assert!(FUNCS.contains(&ident.as_str()), "unknown identifier {}", ident);
+                    assert_eq!(chars.peek(), Some(&'('), "{} must be called", ident);
+                    prev = 'f';
+                    continue;
+                }
+                other => panic!("unexpected {:?} in {}", other, expr),
+            }
+            prev = c;
+        }
+        assert_eq!(depth, 0, "unbalanced '(' in {}", expr);
+    }
+
+    #[test]
+    fn zoom_expression_is_valid_ffmpeg_syntax() {
+        let effect = AudioReactive::default();
+        let expr = effect.zoom_expression(&[0.0, 0.5, 1.25, 2.0]);
+        assert_valid_ffmpeg_expr(&expr);
+        assert!(expr.starts_with("1+0.0600*min(1,"));
+        assert!(expr.contains("if(between(it,0.4600,0.5000),(it-0.4600)/0.0400"));
+        assert!(expr.contains("exp((2.0000-it)/0.1800)"));
+        assert_eq!(effect.zoom_expression(&[]), "1");
+
+        let ctx = EffectContext {
+            width: 1920,
+            height: 1080,
+            fps: 30.0,
+            onsets: vec![1.0],
+        };
+        let filter = effect.filter(&ctx).unwrap();
+        assert!(filter.starts_with("zoompan=z='1+"));
+        assert!(filter.ends_with(":d=1:s=1920x1080:fps=30.000"));
+    }
+
+    #[test]
+    fn intensity_is_clamped_so_the_crop_stays_in_frame() {
+        let wild = AudioReactive {
+            intensity: 5.0,
+            attack_secs: 0.0,
+            decay_secs: f64::NAN,
+        };
+        let c = wild.clamped();
+        assert_eq!(c.intensity, MAX_PUMP_INTENSITY);
+        assert!(c.attack_secs > 0.0 && c.decay_secs > 0.0);
+        let expr = wild.zoom_expression(&[1.0]);
+        assert_valid_ffmpeg_expr(&expr);
+        assert!(expr.starts_with("1+0.3000*min(1,"));
+
+        let negative = AudioReactive {
+            intensity: -1.0,
+            ..AudioReactive::default()
+        };
+        assert_eq!(negative.zoom_expression(&[1.0]), "1");
+    }
+
+    #[test]
+    fn onsets_found_on_synthetic_kicks() {
+        let sr = ONSET_SAMPLE_RATE;
+        let mut samples = vec![0i16; sr as usize * 4];
+        // A decaying 60 Hz kick every half second from 0.5 s
+        for k in 1..8 {
+            let start = (k as f64 * 0.5 * sr as f64) as usize;
+            for n in 0..(sr as usize / 10) {
+                let t = n as f64 / sr as f64;
+                let v = (2.0 * std::f64::consts::PI * 60.0 * t).sin() * (-t * 30.0).exp();
+                samples[start + n] = (v * 20_000.0) as i16;
+            }
+        }
+        let onsets = onsets_from_samples(&samples, sr);
+        assert_eq!(onsets.len(), 7, "{:?}", onsets);
+        for (k, o) in onsets.iter().enumerate() {
+            let expected = (k + 1) as f64 * 0.5;
+            assert!((o - expected).abs() <= 0.06, "onset {} at {}", k, o);
+        }
+    }
+
+    #[test]
+    fn registry_replaces_effects_by_name() {
+        let mut registry = EffectsRegistry::new();
+        registry.register(Box::new(AudioReactive::default()));
+        registry.register(Box::new(AudioReactive {
+            intensity: 0.1,
+            ..AudioReactive::default()
+        }));
+        assert_eq!(registry.names(), vec!["audio_reactive"]);
+        assert!(registry.build_chain(&EffectContext::default()).is_none());
+    }
+}
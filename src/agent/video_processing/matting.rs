@@ -0,0 +1,363 @@
+// SYNOID Matting Engine — Background Removal
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Per-frame person segmentation for creator content: cut the subject out of
+// the frame and either keep an alpha channel or composite it over a chosen
+// background.
+//
+// Segmentation runs through `rembg` with an ONNX human-segmentation model.
+// onnxruntime picks CUDA when `rembg[gpu]` is installed and falls back to CPU
+// otherwise. Raw per-frame masks flicker at the edges, so they are smoothed
+// over time before compositing.
+
+use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::video_processing::upscale_engine::which_exists;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Default rembg model for people (downloaded by rembg into `~/.u2net`).
+const DEFAULT_MODEL: &str = "u2net_human_seg";
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Background
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// What to put behind the matted subject.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MatteBackground {
+    /// Keep the alpha channel (ProRes 4444 .mov output).
+    Transparent,
+    /// Solid colour, in any form FFmpeg's `color` source accepts ("green", "0x1A1A1A").
+    Color(String),
+    /// Still image scaled to the frame size.
+    Image(PathBuf),
+}
+
+impl MatteBackground {
+    /// Parse a CLI value: "transparent", an existing image path, or a colour.
+    pub fn parse(s: &str) -> Self {
+        let trimmed = s.trim();
+        if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("transparent") {
+            return Self::Transparent;
+        }
+        let path = Path::new(trimmed);
+        if path.is_file() {
+            return Self::Image(path.to_path_buf());
+        }
+        Self::Color(ffmpeg_color(trimmed.trim_start_matches('#')))
+    }
+}
+
+/// "1a1a1a" → "0x1a1a1a" so FFmpeg reads it as hex; colour names pass through.
+fn ffmpeg_color(s: &str) -> String {
+    if s.len() == 6 && s.chars().all(|c| c.is_ascii_hexdigit()) {
+        format!("0x{}", s)
+    } else {
+        s.to_string()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// MattingConfig
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MattingConfig {
+    pub background: MatteBackground,
+    /// Temporal smoothing strength in [0, 1). 0 = raw per-frame masks;
+    /// higher values blend more of the previous frame's mask in.
+    pub smoothing: f32,
+    /// CRF for the composited output (ignored for transparent output).
+    pub encode_crf: u32,
+}
+
+impl Default for MattingConfig {
+    fn default() -> Self {
+        Self {
+            background: MatteBackground::Transparent,
+            smoothing: 0.6,
+            encode_crf: 18,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Temporal smoothing
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Exponential moving average of the mask over time, in place:
+/// `cur = strength * prev + (1 - strength) * cur`.
+///
+/// Masks are 8-bit luma buffers of equal length; mismatched sizes are left
+/// untouched (the segmentation model can occasionally emit a resized frame).
+pub fn smooth_mask(prev: &[u8], cur: &mut [u8], strength: f32) {
+    if prev.len() != cur.len() {
+        return;
+    }
+    let s = strength.clamp(0.0, 0.99);
+    for (c, p) in cur.iter_mut().zip(prev.iter()) {
+        let blended = s * (*p as f32) + (1.0 - s) * (*c as f32);
+        *c = blended.round() as u8;
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// MattingEngine
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub struct MattingEngine;
+
+impl MattingEngine {
+    /// The configured `matting_model`, otherwise rembg's cached
+    /// human-segmentation model.
+    fn model_path() -> Option<PathBuf> {
+        if let Some(custom) = crate::config::current().matting_model {
+            return Some(PathBuf::from(custom));
+        }
+        dirs::home_dir().map(|h| h.join(".u2net").join(format!("{}.onnx", DEFAULT_MODEL)))
+    }
+
+    /// True when `rembg` is on PATH and a segmentation model is present.
+    /// Callers skip matting entirely when this is false.
+    pub fn is_available() -> bool {
+        which_exists("rembg") && Self::model_path().map(|p| p.exists()).unwrap_or(false)
+    }
+
+    /// Remove the background from `input_path` and write the result to `output_path`.
+    ///
+    /// Transparent output should use a `.mov` extension (ProRes 4444 carries alpha).
+    pub async fn remove_background(
+        input_path: &Path,
+        output_path: &Path,
+        config: &MattingConfig,
+    ) -> Result<()> {
+        if !Self::is_available() {
+            return Err(anyhow::anyhow!(
+                "Background removal unavailable: install `rembg` and its {} model (or set SYNOID_MATTING_MODEL).",
+                DEFAULT_MODEL
+            ));
+        }
+
+        info!("[MATTING] {:?} → {:?}", input_path, output_path);
+
+        let tmp_dir = std::env::temp_dir().join(format!("synoid_matting_{}", uuid::Uuid::new_v4()));
+        let frames_dir = tmp_dir.join("frames");
+        let masks_dir = tmp_dir.join("masks");
+        std::fs::create_dir_all(&frames_dir).context("Creating matting frame dir")?;
+        std::fs::create_dir_all(&masks_dir).context("Creating matting mask dir")?;
+
+        let result = Self::run(input_path, output_path, config, &frames_dir, &masks_dir).await;
+        let _ = std::fs::remove_dir_all(&tmp_dir);
+        result
+    }
+
+    async fn run(
+        input_path: &Path,
+        output_path: &Path,
+        config: &MattingConfig,
+        frames_dir: &Path,
+        masks_dir: &Path,
+    ) -> Result<()> {
+        // 1. Extract frames
+        info!("[MATTING] Extracting frames…");
+        let fps = probe_fps(input_path).await.unwrap_or(30.0);
+        let status = proc::ffmpeg(ProcClass::Render)
+            .stealth()
+            .args(["-y", "-i"])
+            .arg(input_path)
+            .args(["-qscale:v", "1"])
+            .arg(frames_dir.join("%06d.png"))
+            .status()
+            .await
+            .context("Frame extraction for matting")?;
+        if !status.success() {
+            return Err(anyhow::anyhow!("FFmpeg frame extraction failed for matting."));
+        }
+
+        // 2. Segment every frame (mask only)
+        info!("[MATTING] Segmenting frames…");
+        // Unmanaged: rembg runs on the GPU; the classes cover the ffmpeg family
+        let mut seg = proc::unmanaged("rembg");
+        seg.stealth().args(["p", "-om"]);
+        match crate::config::current().matting_model {
+            Some(custom) => {
+                let extras = serde_json::json!({ "model_path": custom }).to_string();
+                seg.args(["-m", "u2net_custom", "-x", &extras]);
+            }
+            None => {
+                seg.args(["-m", DEFAULT_MODEL]);
+            }
+        }
+        let seg_ok = seg
+            .arg(frames_dir)
+            .arg(masks_dir)
+            .status()
+            .await
+            .map(|s| s.success())
+            .unwrap_or(false);
+        if !seg_ok {
+            return Err(anyhow::anyhow!("rembg segmentation failed."));
+        }
+
+        // 3. Temporal smoothing
+        if config.smoothing > 0.0 {
+            info!("[MATTING] Smoothing masks (strength {:.2})…", config.smoothing);
+            let masks_dir = masks_dir.to_path_buf();
+            let strength = config.smoothing;
+            tokio::task::spawn_blocking(move || smooth_mask_sequence(&masks_dir, strength))
+                .await
+                .context("Mask smoothing task")??;
+        }
+
+        // 4. Composite
+        info!("[MATTING] Compositing…");
+        let fps_str = fps.to_string();
+        let mut cmd = proc::ffmpeg(ProcClass::Render);
+        cmd.stealth()
+            .args(["-y", "-framerate", &fps_str, "-i"])
+            .arg(frames_dir.join("%06d.png"))
+            .args(["-framerate", &fps_str, "-i"])
+            .arg(masks_dir.join("%06d.png"))
+            .arg("-i")
+            .arg(input_path);
+
+        let crf = config.encode_crf.to_string();
+        match &config.background {
+            MatteBackground::Transparent => {
+                cmd.args([
+                    "-filter_complex",
+                    "[1:v]format=gray[m];[0:v][m]alphamerge[out]",
+                    "-map",
+                    "[out]",
+                    "-map",
+                    "2:a?",
+                    "-c:v",
+                    "prores_ks",
+                    "-profile:v",
+                    "4444",
+                    "-pix_fmt",
+                    "yuva444p10le",
+                ]);
+            }
+            MatteBackground::Color(color) => {
+                let graph = format!(
+                    "[1:v]format=gray[m];[0:v][m]alphamerge[fg];\
+                     color=c={}:r={}[bgsrc];[bgsrc][fg]scale2ref[bg][fg2];\
+                     [bg][fg2]overlay=shortest=1,format=yuv420p[out]",
+                    color, fps_str
+                );
+                cmd.args(["-filter_complex", &graph, "-map", "[out]", "-map", "2:a?"]);
+                cmd.args(["-c:v", "libx264", "-crf", &crf, "-preset", "medium"]);
+            }
+            MatteBackground::Image(path) => {
+                cmd.args(["-loop", "1", "-i"]).arg(path);
+                let graph = "[1:v]format=gray[m];[0:v][m]alphamerge[fg];\
+                             [3:v][fg]scale2ref[bg][fg2];\
+                             [bg][fg2]overlay=shortest=1,format=yuv420p[out]";
+                cmd.args(["-filter_complex", graph, "-map", "[out]", "-map", "2:a?"]);
+                cmd.args(["-c:v", "libx264", "-crf", &crf, "-preset", "medium"]);
+            }
+        }
+
+        let status = cmd
+            .args(["-c:a", "aac", "-shortest"])
+            .arg(output_path)
+            .status()
+            .await
+            .context("FFmpeg matting composite")?;
+        if !status.success() {
+            return Err(anyhow::anyhow!("FFmpeg matting composite failed."));
+        }
+
+        info!("[MATTING] Complete: {:?}", output_path);
+        Ok(())
+    }
+}
+
+/// Apply `smooth_mask` across every mask PNG in `dir`, in frame order.
+fn smooth_mask_sequence(dir: &Path, strength: f32) -> Result<()> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().map(|e| e == "png").unwrap_or(false))
+        .collect();
+    files.sort();
+
+    let mut prev: Option<Vec<u8>> = None;
+    for file in files {
+        let mut mask = image::open(&file)
+            .with_context(|| format!("Reading mask {:?}", file))?
+            .to_luma8();
+        if let Some(p) = &prev {
+            smooth_mask(p, &mut mask, strength);
+            mask.save(&file)
+                .with_context(|| format!("Writing mask {:?}", file))?;
+        }
+        prev = Some(mask.into_raw());
+    }
+    Ok(())
+}
+
+async fn probe_fps(path: &Path) -> Option<f64> {
+    let out = proc::ffprobe()
+        .stealth()
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "v:0",
+            "-show_entries",
+            "stream=r_frame_rate",
+            "-of",
+            "csv=p=0",
+        ])
+        .arg(path)
+        .output()
+        .await
+        .ok()?;
+    let s = String::from_utf8_lossy(&out.stdout);
+    let mut parts = s.trim().split('/');
+    let num: f64 = parts.next()?.parse().ok()?;
+    match parts.next() {
+        Some(den) => {
+            let den: f64 = den.parse().ok()?;
+            (den != 0.0).then(|| num / den)
+        }
+        None => Some(num),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smoothing_blends_toward_previous_mask() {
+        let prev = vec![255u8, 255, 0, 0];
+        let mut cur = vec![0u8, 255, 255, 0];
+        smooth_mask(&prev, &mut cur, 0.5);
+        // A pixel flickering 255 → 0 lands halfway instead of dropping out.
+        assert_eq!(cur, vec![128, 255, 128, 0]);
+    }
+
+    #[test]
+    fn zero_strength_keeps_raw_mask_and_size_mismatch_is_ignored() {
+        let prev = vec![255u8; 4];
+        let mut cur = vec![10u8, 20, 30, 40];
+        smooth_mask(&prev, &mut cur, 0.0);
+        assert_eq!(cur, vec![10, 20, 30, 40]);
+
+        let mut other = vec![1u8, 2];
+        smooth_mask(&prev, &mut other, 0.9);
+        assert_eq!(other, vec![1, 2]);
+    }
+
+    #[test]
+    fn background_parsing() {
+        assert_eq!(MatteBackground::parse("transparent"), MatteBackground::Transparent);
+        assert_eq!(MatteBackground::parse("#1a1a1a"), MatteBackground::Color("0x1a1a1a".into()));
+        assert_eq!(MatteBackground::parse("green"), MatteBackground::Color("green".into()));
+    }
+}
@@ -8,9 +8,9 @@
 // mirroring SYNOID's Multicam SmartSwitch workflow.
 
 use anyhow::{Context, Result};
+use crate::agent::engines::proc::{self, ProcClass};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
-use tokio::process::Command;
 use crate::agent::engines::process_utils::CommandExt;
 use tracing::info;
 
@@ -227,7 +227,7 @@ impl MulticamEngine {
             let actual_start = (start - offset).max(0.0);
             let duration = end - start;
 
-            let status = Command::new("ffmpeg")
+            let status = proc::ffmpeg(ProcClass::Render)
                 .stealth()
                 .args(["-y", "-ss", &actual_start.to_string(), "-i"])
                 .arg(&track.path)
@@ -259,7 +259,7 @@ impl MulticamEngine {
         let list_path = tmp_dir.join("concat_list.txt");
         std::fs::write(&list_path, &concat_txt).context("Writing concat list")?;
 
-        let status = Command::new("ffmpeg")
+        let status = proc::ffmpeg(ProcClass::Render)
             .stealth()
             .args(["-y", "-f", "concat", "-safe", "0", "-i"])
             .arg(&list_path)
@@ -288,7 +288,7 @@ impl MulticamEngine {
 
     /// Use FFmpeg's `astats` filter to extract per-frame RMS energy.
     async fn extract_energy_profile(path: &Path) -> Result<Vec<EnergyFrame>> {
-        let output = Command::new("ffmpeg")
+        let output = proc::ffmpeg(ProcClass::Thumbnail)
             .stealth()
             .args(["-v", "error", "-i"])
             .arg(path)
@@ -0,0 +1,320 @@
+// SYNOID Preview Audio
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Sound for the GUI preview. ffmpeg decodes the file's audio from the same
+// offset as the video decoder, resampled to the output device's format, into
+// a bounded queue that a cpal output stream drains. The samples actually
+// handed to the device are the audio clock: `VideoPlayer` re-anchors its
+// frame clock to it, so the picture follows the sound. When the queue runs
+// dry the device gets silence and the clock holds instead of racing ahead.
+
+use crate::agent::engines::proc;
+use crate::agent::engines::process_utils::CommandExt;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, Sample, SizedSample};
+use std::collections::VecDeque;
+use std::io::Read;
+use std::process::{Child, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Seconds of decoded audio buffered ahead of the device.
+const BUFFER_SECS: f64 = 1.0;
+
+/// State shared by the decoder thread, the device callback and the player.
+struct Shared {
+    queue: Mutex<VecDeque<f32>>,
+    channels: usize,
+    rate: u32,
+    /// Interleaved samples handed to the device so far.
+    played: AtomicU64,
+    /// The decoder reached the end of the audio (or there was none).
+    decoded_all: AtomicBool,
+    /// The output stream reported an error.
+    failed: AtomicBool,
+    stopping: AtomicBool,
+}
+
+impl Shared {
+    fn new(channels: usize, rate: u32) -> Self {
+        Self {
+            queue: Mutex::new(VecDeque::new()),
+            channels: channels.max(1),
+            rate: rate.max(1),
+            played: AtomicU64::new(0),
+            decoded_all: AtomicBool::new(false),
+            failed: AtomicBool::new(false),
+            stopping: AtomicBool::new(false),
+        }
+    }
+
+    /// Seconds of audio played, or `None` once the audio can no longer
+    /// drive playback: the device failed, or everything decoded has played.
+    fn clock(&self) -> Option<f64> {
+        if self.failed.load(Ordering::Relaxed) {
+            return None;
+        }
+        if self.decoded_all.load(Ordering::Acquire)
+            && self.queue.lock().map_or(true, |q| q.is_empty())
+        {
+            return None;
+        }
+        let frames = self.played.load(Ordering::Relaxed) / self.channels as u64;
+        Some(frames as f64 / self.rate as f64)
+    }
+
+    /// Fill a device buffer from the queue, whole sample frames only, with
+    /// silence for whatever the queue can't cover. The real-time callback
+    /// never waits for the lock; a contended buffer is simply silent.
+    fn fill<T: Sample + FromSample<f32>>(&self, out: &mut [T]) {
+        let mut taken = 0;
+        if let Ok(mut queue) = self.queue.try_lock() {
+            taken = queue.len().min(out.len()) / self.channels * self.channels;
+            for (slot, sample) in out.iter_mut().zip(queue.drain(..taken)) {
+                *slot = T::from_sample(sample);
+            }
+        }
+        for slot in &mut out[taken..] {
+            *slot = T::EQUILIBRIUM;
+        }
+        self.played.fetch_add(taken as u64, Ordering::Relaxed);
+    }
+}
+
+/// Audio playback for one `VideoPlayer`. Dropping it stops the sound.
+pub struct PreviewAudio {
+    shared: Arc<Shared>,
+    decoder: Option<Child>,
+    /// Dropping this ends the thread that owns the output stream.
+    stream_stop: Option<mpsc::Sender<()>>,
+}
+
+impl PreviewAudio {
+    /// Start playing the audio of `path` from `timestamp` on the default
+    /// output device.
+    pub fn start(path: &str, timestamp: f64) -> Result<Self, String> {
+        let device = cpal::default_host()
+            .default_output_device()
+            .ok_or("no audio output device")?;
+        let supported = device
+            .default_output_config()
+            .map_err(|e| format!("no usable output format: {}", e))?;
+        let format = supported.sample_format();
+        let config: cpal::StreamConfig = supported.into();
+        let shared = Arc::new(Shared::new(config.channels as usize, config.sample_rate.0));
+
+        // Unmanaged, like the ffplay this replaced: it rides along with the
+        // video decoder holding the Preview slot, and queueing for a second
+        // slot would stall the GUI thread
+        let mut decoder = proc::blocking_unmanaged("ffmpeg")
+            .stealth()
+            .args(["-v", "error", "-nostdin", "-ss"])
+            .arg(format!("{:.3}", timestamp))
+            .arg("-i")
+            .arg(path)
+            .args(["-vn", "-sn", "-f", "f32le", "-ac"])
+            .arg(shared.channels.to_string())
+            .arg("-ar")
+            .arg(shared.rate.to_string())
+            .arg("-")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("cannot start ffmpeg: {}", e))?;
+        let stdout = decoder.stdout.take().ok_or("ffmpeg has no stdout")?;
+        let reader = shared.clone();
+        thread::spawn(move || decode_into(stdout, &reader));
+
+        // cpal streams can't move between threads on every platform, so one
+        // thread owns it until told to stop.
+        let (ready_tx, ready_rx) = mpsc::channel();
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        let stream_shared = shared.clone();
+        thread::spawn(move || {
+            let stream = match open_stream(&device, &config, format, stream_shared) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                    return;
+                }
+            };
+            let _ = ready_tx.send(Ok(()));
+            // Blocks until the sender is dropped
+            let _ = stop_rx.recv();
+            drop(stream);
+        });
+
+        let mut audio = Self {
+            shared,
+            decoder: Some(decoder),
+            stream_stop: Some(stop_tx),
+        };
+        match ready_rx.recv() {
+            Ok(Ok(())) => Ok(audio),
+            Ok(Err(e)) => {
+                audio.stop();
+                Err(e)
+            }
+            Err(_) => {
+                audio.stop();
+                Err("audio output thread exited".to_string())
+            }
+        }
+    }
+
+    /// Seconds of audio played since the start, while the audio is what
+    /// keeps time; `None` after it ends or fails.
+    pub fn clock(&self) -> Option<f64> {
+        self.shared.clock()
+    }
+
+    pub fn stop(&mut self) {
+        self.shared.stopping.store(true, Ordering::Relaxed);
+        self.stream_stop = None;
+        if let Some(mut decoder) = self.decoder.take() {
+            let _ = decoder.kill();
+            let _ = decoder.wait();
+        }
+    }
+}
+
+impl Drop for PreviewAudio {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Move ffmpeg's f32le output into the queue, at most `BUFFER_SECS` ahead.
+fn decode_into(mut stdout: impl Read, shared: &Shared) {
+    let limit = (shared.rate as f64 * shared.channels as f64 * BUFFER_SECS) as usize;
+    let mut bytes = vec![0u8; 16 * 1024];
+    let mut carry = Vec::with_capacity(4);
+    'decode: while !shared.stopping.load(Ordering::Relaxed) {
+        let n = match stdout.read(&mut bytes) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        // Reads can split a sample; finish it with the next read
+        carry.extend_from_slice(&bytes[..n]);
+        let whole = carry.len() / 4 * 4;
+        let samples: Vec<f32> = carry[..whole]
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+        carry.drain(..whole);
+
+        while shared.queue.lock().map_or(0, |q| q.len()) >= limit {
+            if shared.stopping.load(Ordering::Relaxed) {
+                break 'decode;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        match shared.queue.lock() {
+            Ok(mut queue) => queue.extend(samples),
+            Err(_) => break,
+        }
+    }
+    shared.decoded_all.store(true, Ordering::Release);
+}
+
+fn open_stream(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    format: cpal::SampleFormat,
+    shared: Arc<Shared>,
+) -> Result<cpal::Stream, String> {
+    let stream = match format {
+        cpal::SampleFormat::F32 => build_stream::<f32>(device, config, shared),
+        cpal::SampleFormat::I16 => build_stream::<i16>(device, config, shared),
+        cpal::SampleFormat::U16 => build_stream::<u16>(device, config, shared),
+        cpal::SampleFormat::I32 => build_stream::<i32>(device, config, shared),
+        other => return Err(format!("unsupported sample format {:?}", other)),
+    }
+    .map_err(|e| format!("cannot open audio output: {}", e))?;
+    stream
+        .play()
+        .map_err(|e| format!("cannot start audio output: {}", e))?;
+    Ok(stream)
+}
+
+fn build_stream<T: SizedSample + FromSample<f32>>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    shared: Arc<Shared>,
+) -> Result<cpal::Stream, cpal::BuildStreamError> {
+    let errors = shared.clone();
+    device.build_output_stream(
+        config,
+        move |out: &mut [T], _| shared.fill(out),
+        move |e| {
+            tracing::warn!("[PreviewAudio] Output stream error: {}", e);
+            errors.failed.store(true, Ordering::Relaxed);
+        },
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_clock_counts_only_samples_the_device_played() {
+        let shared = Shared::new(2, 1000);
+        // Nothing decoded yet: the device gets silence and time stands still
+        let mut out = [1.0f32; 8];
+        shared.fill(&mut out);
+        assert_eq!(out, [0.0; 8]);
+        assert_eq!(shared.clock(), Some(0.0));
+
+        // Five samples queued: only two whole stereo frames are played, the
+        // odd sample waits so left and right never swap
+        shared.queue.lock().unwrap().extend([0.5f32; 5]);
+        let mut out = [0i16; 8];
+        shared.fill(&mut out);
+        assert!(out[..4].iter().all(|&s| s > 0));
+        assert!(out[4..].iter().all(|&s| s == 0));
+        assert_eq!(shared.clock(), Some(2.0 / 1000.0));
+
+        shared.queue.lock().unwrap().extend([0.25f32; 2001]);
+        let mut out = vec![0.0f32; 2000];
+        shared.fill(&mut out);
+        assert_eq!(shared.clock(), Some(1002.0 / 1000.0));
+
+        // Once everything decoded has played, the audio stops keeping time
+        shared.decoded_all.store(true, Ordering::Release);
+        assert!(shared.clock().is_some());
+        shared.fill(&mut out);
+        assert_eq!(shared.clock(), None);
+
+        let failed = Shared::new(1, 1000);
+        failed.failed.store(true, Ordering::Relaxed);
+        assert_eq!(failed.clock(), None);
+    }
+
+    #[test]
+    fn decoding_splits_no_samples_across_reads() {
+        // One byte at a time is the worst case for sample boundaries
+        struct Trickle(Vec<u8>, usize);
+        impl Read for Trickle {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                if self.1 >= self.0.len() || buf.is_empty() {
+                    return Ok(0);
+                }
+                buf[0] = self.0[self.1];
+                self.1 += 1;
+                Ok(1)
+            }
+        }
+        let values = [0.5f32, -0.25, 1.0];
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let shared = Shared::new(1, 48_000);
+        decode_into(Trickle(bytes, 0), &shared);
+        let queue: Vec<f32> = shared.queue.lock().unwrap().iter().copied().collect();
+        assert_eq!(queue, values);
+        assert!(shared.decoded_all.load(Ordering::Acquire));
+    }
+}
@@ -0,0 +1,752 @@
+// SYNOID Timeline Render — Explicit NLE Timelines
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Renders the clip arrangement the user built in the React editor, as opposed
+// to the intent-driven smart edit. Each clip is one FFmpeg input cut to its
+// in/out points; clips on a video track are concatenated with transparent
+// gaps, tracks are overlaid bottom-up (V1 under V2 under V3) onto a black
+// canvas, and every clip's audio is delayed to its timeline position and
+// mixed. Caption clips are ignored here; captions burn in separately.
+//
+// Per-clip effects (speed, scale, opacity, audio fades) become filters on the
+// clip's own chain. A clip's `transition` joins it to the clip directly
+// before it on the same track with `xfade`; the incoming clip's first frame
+// is held for the transition so both clips keep their timeline positions.
+//
+// Tracks can carry an audio `role`. When the timeline has both a music and a
+// voice track, the music bus is ducked under the voice bus with
+// `sidechaincompress` before the final mix.
+//
+// A draft render uses the same clip cuts on a 480p canvas with the
+// smart editor's fast draft encode settings.
+
+use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::specialized::smart_editor::draft::{
+    DRAFT_AUDIO_BITRATE, DRAFT_HEIGHT, DRAFT_VIDEO_ARGS,
+};
+use crate::agent::tools::production_tools::{sidechain_duck_filter, SpeechDucking};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Gaps and overlaps shorter than this are treated as touching.
+const EPSILON: f64 = 1e-3;
+
+/// One clip as sent by the editor (`TimelineClip` in editor/src/types.ts).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineClip {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub asset_id: String,
+    #[serde(default = "default_track")]
+    pub track_id: String,
+    /// Position on the timeline, in seconds.
+    pub start: f64,
+    /// Source in/out points, in seconds.
+    #[serde(default)]
+    pub in_point: f64,
+    pub out_point: f64,
+    #[serde(default = "default_volume")]
+    pub volume: f64,
+    /// Playback rate; 2.0 plays the in/out range in half the time.
+    #[serde(default = "default_speed")]
+    pub speed: f64,
+    #[serde(default)]
+    pub transform: ClipTransform,
+    /// Transition in from the previous clip on the same track.
+    #[serde(default)]
+    pub transition: Option<ClipTransition>,
+    /// Audio role of the clip's track. Set on one clip, it applies to every
+    /// clip on that track that has no role of its own.
+    #[serde(default)]
+    pub role: Option<AudioRole>,
+    /// Seconds over which the clip's sound fades in and out.
+    #[serde(default)]
+    pub audio_fade_in: f64,
+    #[serde(default)]
+    pub audio_fade_out: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioRole {
+    Music,
+    Voice,
+    Sfx,
+}
+
+/// The parts of the editor's `ClipTransform` the renderer honours.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct ClipTransform {
+    pub scale: f64,
+    pub opacity: f64,
+}
+
+impl Default for ClipTransform {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            opacity: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TransitionKind {
+    Crossfade,
+    DipToBlack,
+}
+
+impl TransitionKind {
+    /// The matching `xfade` transition.
+    fn xfade_name(self) -> &'static str {
+        match self {
+            TransitionKind::Crossfade => "fade",
+            TransitionKind::DipToBlack => "fadeblack",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct ClipTransition {
+    #[serde(rename = "type")]
+    pub kind: TransitionKind,
+    /// Seconds of timeline time.
+    pub duration: f64,
+}
+
+fn default_track() -> String {
+    "V1".to_string()
+}
+
+fn default_volume() -> f64 {
+    1.0
+}
+
+fn default_speed() -> f64 {
+    1.0
+}
+
+/// `atempo` stages for `speed`; one stage only goes down to half speed.
+fn atempo_chain(speed: f64) -> String {
+    let mut stages = Vec::new();
+    let mut rest = speed;
+    while rest < 0.5 {
+        stages.push("atempo=0.5".to_string());
+        rest /= 0.5;
+    }
+    stages.push(format!("atempo={:.4}", rest));
+    stages.join(",")
+}
+
+impl TimelineClip {
+    /// Length on the timeline, after speed.
+    pub fn duration(&self) -> f64 {
+        (self.out_point - self.in_point) / self.speed
+    }
+
+    pub fn end(&self) -> f64 {
+        self.start + self.duration()
+    }
+
+    /// Stacking order for video tracks ("V2" → 2); `None` for audio and
+    /// caption tracks.
+    fn video_layer(&self) -> Option<u32> {
+        self.track_id.strip_prefix('V')?.parse().ok()
+    }
+
+    fn is_audio_track(&self) -> bool {
+        self.track_id.starts_with('A')
+    }
+}
+
+/// What the renderer needs to know about a clip's asset.
+#[derive(Debug, Clone)]
+pub struct ClipSource {
+    pub path: PathBuf,
+    /// "video", "audio" or "image", as in `AssetMeta::kind`.
+    pub kind: String,
+    pub has_audio: bool,
+}
+
+/// Output frame geometry.
+#[derive(Debug, Clone, Copy)]
+pub struct Canvas {
+    pub width: u32,
+    pub height: u32,
+    pub fps: f64,
+}
+
+impl Default for Canvas {
+    fn default() -> Self {
+        Self {
+            width: 1920,
+            height: 1080,
+            fps: 30.0,
+        }
+    }
+}
+
+impl Canvas {
+    /// The same canvas shrunk to the draft height, width kept even.
+    pub fn draft(self) -> Self {
+        if self.height <= DRAFT_HEIGHT {
+            return self;
+        }
+        let width = (self.width as f64 * DRAFT_HEIGHT as f64 / self.height as f64).round() as u32;
+        Self {
+            width: (width + 1) & !1,
+            height: DRAFT_HEIGHT,
+            fps: self.fps,
+        }
+    }
+}
+
+/// A timeline resolved into FFmpeg inputs and a `filter_complex` graph.
+#[derive(Debug, Clone)]
+pub struct TimelinePlan {
+    /// One input per clip: the file and whether it is a still to loop.
+    pub inputs: Vec<(PathBuf, bool)>,
+    pub filtergraph: String,
+    pub has_audio: bool,
+    pub duration: f64,
+}
+
+/// Parse and check the editor's `clips` array. Caption clips (no asset) are
+/// dropped; a clip with a negative position, an out point not after its in
+/// point, or an out-of-range speed, scale, opacity or transition is an error.
+pub fn parse_clips(value: &serde_json::Value) -> Result<Vec<TimelineClip>, String> {
+    let clips: Vec<TimelineClip> =
+        serde_json::from_value(value.clone()).map_err(|e| format!("invalid clips: {}", e))?;
+    let clips: Vec<TimelineClip> = clips
+        .into_iter()
+        .filter(|c| !c.asset_id.is_empty() && !c.track_id.starts_with('T'))
+        .collect();
+    for c in &clips {
+        let ok = [c.start, c.in_point, c.out_point, c.volume]
+            .iter()
+            .all(|v| v.is_finite());
+        if !(c.speed.is_finite() && c.speed > 0.0) {
+            return Err(format!("clip {} has an invalid speed {}", c.id, c.speed));
+        }
+        if !ok || c.start < 0.0 || c.in_point < 0.0 || c.duration() <= EPSILON {
+            return Err(format!(
+                "clip {} has an invalid range (start {}, in {}, out {})",
+                c.id, c.start, c.in_point, c.out_point
+            ));
+        }
+        let ClipTransform { scale, opacity } = c.transform;
+        if !(scale.is_finite() && scale > 0.0 && (0.0..=1.0).contains(&opacity)) {
+            return Err(format!(
+                "clip {} has an invalid transform (scale {}, opacity {})",
+                c.id, scale, opacity
+            ));
+        }
+        let fades = [c.audio_fade_in, c.audio_fade_out];
+        if !fades.iter().all(|f| f.is_finite() && (0.0..=c.duration()).contains(f)) {
+            return Err(format!(
+                "clip {} has an invalid audio fade (in {}, out {})",
+                c.id, c.audio_fade_in, c.audio_fade_out
+            ));
+        }
+        if let Some(t) = c.transition {
+            if !(t.duration.is_finite() && t.duration > EPSILON) {
+                return Err(format!("clip {} has an invalid transition duration {}", c.id, t.duration));
+            }
+        }
+    }
+    Ok(clips)
+}
+
+/// Build the inputs and filtergraph for `clips`. Every clip's asset must be
+/// in `sources`. Output pads are `[vout]` and, when any clip has sound,
+/// `[aout]`.
+pub fn build_plan(
+    clips: &[TimelineClip],
+    sources: &HashMap<String, ClipSource>,
+    canvas: Canvas,
+) -> Result<TimelinePlan, String> {
+    if clips.is_empty() {
+        return Err("timeline has no clips".to_string());
+    }
+    let Canvas { width: w, height: h, fps } = canvas;
+    let duration = clips.iter().map(TimelineClip::end).fold(0.0, f64::max);
+
+    let mut inputs = Vec::with_capacity(clips.len());
+    let mut layers: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+    let mut audio = Vec::new();
+    for (i, clip) in clips.iter().enumerate() {
+        let source = sources
+            .get(&clip.asset_id)
+            .ok_or_else(|| format!("clip {} references unknown asset {}", clip.id, clip.asset_id))?;
+        inputs.push((source.path.clone(), source.kind == "image"));
+        if let Some(layer) = clip.video_layer().filter(|_| source.kind != "audio") {
+            layers.entry(layer).or_default().push(i);
+        }
+        if source.has_audio && (clip.video_layer().is_some() || clip.is_audio_track()) {
+            audio.push(i);
+        }
+    }
+
+    let mut graph = String::new();
+    let mut gap = 0;
+    let _ = writeln!(graph, "color=c=black:s={}x{}:r={}:d={:.3}[base0];", w, h, fps, duration);
+    let mut base = 0;
+    for (layer, mut members) in layers {
+        members.sort_by(|a, b| clips[*a].start.total_cmp(&clips[*b].start));
+        let mut segments = Vec::new();
+        let mut cursor = 0.0;
+        // The clip that ends the last segment and the length of that segment,
+        // for joining the next clip to it with a transition.
+        let mut previous: Option<usize> = None;
+        let mut run_len = 0.0;
+        for i in members {
+            let clip = &clips[i];
+            if clip.start < cursor - EPSILON {
+                return Err(format!("clips overlap on track {} at {:.3}s", clip.track_id, clip.start));
+            }
+            if clip.start > cursor + EPSILON {
+                let _ = writeln!(
+                    graph,
+                    "color=c=black@0.0:s={}x{}:r={}:d={:.3},format=yuva420p[gap{}];",
+                    w, h, fps, clip.start - cursor, gap
+                );
+                segments.push(format!("[gap{}]", gap));
+                gap += 1;
+                previous = None;
+            }
+
+            let transition = match (clip.transition, previous) {
+                (Some(t), Some(prev)) => {
+                    let limit = clip.duration().min(clips[prev].duration());
+                    if t.duration > limit + EPSILON {
+                        return Err(format!(
+                            "transition into clip {} lasts {:.3}s but the clips either side are only {:.3}s long",
+                            clip.id, t.duration, limit
+                        ));
+                    }
+                    Some(t)
+                }
+                (Some(t), None) if t.kind == TransitionKind::Crossfade => {
+                    return Err(format!(
+                        "clip {} crossfades but has no clip directly before it on track {}",
+                        clip.id, clip.track_id
+                    ));
+                }
+                _ => None,
+            };
+
+            let mut chain = format!(
+                "[{i}:v]trim=start={:.3}:end={:.3},",
+                clip.in_point, clip.out_point
+            );
+            if (clip.speed - 1.0).abs() > EPSILON {
+                let _ = write!(chain, "setpts=(PTS-STARTPTS)/{:.4}", clip.speed);
+            } else {
+                chain.push_str("setpts=PTS-STARTPTS");
+            }
+            let _ = write!(chain, ",fps={fps}");
+            let scale = clip.transform.scale;
+            if (scale - 1.0).abs() > EPSILON {
+                let (sw, sh) = ((w as f64 * scale).round(), (h as f64 * scale).round());
+                let _ = write!(chain, ",scale={sw}:{sh}:force_original_aspect_ratio=decrease");
+                if scale > 1.0 {
+                    let _ = write!(chain, ",crop=w='min(iw,{w})':h='min(ih,{h})'");
+                }
+            } else {
+                let _ = write!(chain, ",scale={w}:{h}:force_original_aspect_ratio=decrease");
+            }
+            let _ = write!(
+                chain,
+                ",format=yuva420p,pad={w}:{h}:(ow-iw)/2:(oh-ih)/2:color=black@0.0,setsar=1"
+            );
+            if clip.transform.opacity < 1.0 {
+                let _ = write!(chain, ",colorchannelmixer=aa={:.3}", clip.transform.opacity);
+            }
+            match (clip.transition, transition) {
+                // Hold the first frame so the blend ends where the clip starts
+                (_, Some(t)) => {
+                    let _ = write!(chain, ",tpad=start_duration={:.3}:start_mode=clone", t.duration);
+                }
+                // A dip to black with nothing before it fades in from black
+                (Some(t), None) => {
+                    let _ = write!(chain, ",fade=t=in:st=0:d={:.3}", t.duration);
+                }
+                (None, None) => {}
+            }
+            let _ = writeln!(graph, "{}[v{}];", chain, i);
+
+            match transition {
+                Some(t) => {
+                    let run = segments.pop().unwrap_or_default();
+                    let _ = writeln!(
+                        graph,
+                        "{}[v{}]xfade=transition={}:duration={:.3}:offset={:.3}[x{}];",
+                        run,
+                        i,
+                        t.kind.xfade_name(),
+                        t.duration,
+                        run_len - t.duration,
+                        i
+                    );
+                    segments.push(format!("[x{}]", i));
+                    run_len += clip.duration();
+                }
+                None => {
+                    segments.push(format!("[v{}]", i));
+                    run_len = clip.duration();
+                }
+            }
+            previous = Some(i);
+            cursor = clip.end();
+        }
+        let _ = writeln!(
+            graph,
+            "{}concat=n={}:v=1:a=0[layer{}];",
+            segments.concat(),
+            segments.len(),
+            layer
+        );
+        let _ = writeln!(
+            graph,
+            "[base{}][layer{}]overlay=eof_action=pass[base{}];",
+            base,
+            layer,
+            base + 1
+        );
+        base += 1;
+    }
+    let _ = write!(graph, "[base{}]format=yuv420p[vout]", base);
+
+    for &i in &audio {
+        let clip = &clips[i];
+        let delay_ms = (clip.start * 1000.0).round() as u64;
+        let _ = write!(
+            graph,
+            ";\n[{i}:a]atrim=start={:.3}:end={:.3},asetpts=PTS-STARTPTS,",
+            clip.in_point, clip.out_point,
+        );
+        if (clip.speed - 1.0).abs() > EPSILON {
+            let _ = write!(graph, "{},", atempo_chain(clip.speed));
+        }
+        let _ = write!(graph, "volume={:.3},", clip.volume);
+        if clip.audio_fade_in > EPSILON {
+            let _ = write!(graph, "afade=t=in:st=0:d={:.3},", clip.audio_fade_in);
+        }
+        if clip.audio_fade_out > EPSILON {
+            let _ = write!(
+                graph,
+                "afade=t=out:st={:.3}:d={:.3},",
+                clip.duration() - clip.audio_fade_out,
+                clip.audio_fade_out
+            );
+        }
+        let _ = write!(graph, "adelay={}:all=1[a{i}]", delay_ms);
+    }
+    if !audio.is_empty() {
+        let mut track_roles: HashMap<&str, AudioRole> = HashMap::new();
+        for clip in clips {
+            if let Some(role) = clip.role {
+                track_roles.entry(clip.track_id.as_str()).or_insert(role);
+            }
+        }
+        let role_of = |i: usize| clips[i].role.or_else(|| track_roles.get(clips[i].track_id.as_str()).copied());
+        let pads_with = |role: Option<AudioRole>| -> Vec<String> {
+            audio
+                .iter()
+                .filter(|&&i| role_of(i) == role)
+                .map(|i| format!("[a{}]", i))
+                .collect()
+        };
+        let (voice, music) = (pads_with(Some(AudioRole::Voice)), pads_with(Some(AudioRole::Music)));
+
+        let mut pads = Vec::new();
+        if !voice.is_empty() && !music.is_empty() {
+            let _ = write!(
+                graph,
+                ";\n{}amix=inputs={}:duration=longest:normalize=0[voicebus]",
+                voice.concat(),
+                voice.len()
+            );
+            let _ = write!(
+                graph,
+                ";\n{}amix=inputs={}:duration=longest:normalize=0[musicbus]",
+                music.concat(),
+                music.len()
+            );
+            let _ = write!(
+                graph,
+                ";\n{}",
+                sidechain_duck_filter("voicebus", "musicbus", &SpeechDucking::default())
+            );
+            pads.push("[voice]".to_string());
+            pads.push("[ducked]".to_string());
+            info!("[TIMELINE] 🔉 Ducking {} music clip(s) under {} voice clip(s)", music.len(), voice.len());
+        } else {
+            pads.extend(voice);
+            pads.extend(music);
+        }
+        pads.extend(pads_with(Some(AudioRole::Sfx)));
+        pads.extend(pads_with(None));
+        let _ = write!(
+            graph,
+            ";\n{}amix=inputs={}:duration=longest:normalize=0[aout]",
+            pads.concat(),
+            pads.len()
+        );
+    }
+
+    Ok(TimelinePlan {
+        inputs,
+        filtergraph: graph,
+        has_audio: !audio.is_empty(),
+        duration,
+    })
+}
+
+/// Whether `path` has an audio stream.
+pub async fn probe_has_audio(path: &Path) -> bool {
+    proc::ffprobe()
+        .stealth()
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "a",
+            "-show_entries",
+            "stream=index",
+            "-of",
+            "csv=p=0",
+        ])
+        .arg(path)
+        .output()
+        .await
+        .map(|o| o.status.success() && !o.stdout.trim_ascii().is_empty())
+        .unwrap_or(false)
+}
+
+/// Encode `plan` to `output`. The graph goes through a script file in
+/// `work_dir`; long timelines overrun the Windows command-line limit.
+/// `draft` swaps in the fast preview encode; build the plan on a
+/// `Canvas::draft` canvas to go with it.
+pub async fn render(plan: &TimelinePlan, output: &Path, work_dir: &Path, draft: bool) -> Result<()> {
+    let script = work_dir.join("timeline_filter.txt");
+    std::fs::write(&script, &plan.filtergraph).context("Writing timeline filter script")?;
+
+    info!(
+        "[TIMELINE] 🎞️ Rendering {} clip(s), {:.2}s → {:?}",
+        plan.inputs.len(),
+        plan.duration,
+        output
+    );
+    let mut cmd = proc::ffmpeg(ProcClass::Render);
+    cmd.stealth()
+        .args(["-y", "-hide_banner", "-loglevel", "error", "-nostdin"]);
+    for (path, still) in &plan.inputs {
+        if *still {
+            cmd.args(["-loop", "1"]);
+        }
+        cmd.arg("-i").arg(path);
+    }
+    cmd.arg("-filter_complex_script").arg(&script);
+    cmd.args(["-map", "[vout]"]);
+    let audio_bitrate = if draft { DRAFT_AUDIO_BITRATE } else { "192k" };
+    if plan.has_audio {
+        cmd.args(["-map", "[aout]", "-c:a", "aac", "-b:a", audio_bitrate]);
+    }
+    if draft {
+        cmd.args(DRAFT_VIDEO_ARGS);
+    } else {
+        cmd.args(["-c:v", "libx264", "-preset", "fast", "-crf", "18", "-pix_fmt", "yuv420p"]);
+    }
+    let status = cmd
+        .args(["-t", &format!("{:.3}", plan.duration)])
+        .arg(output)
+        .status()
+        .await
+        .context("Running ffmpeg timeline render")?;
+    let _ = std::fs::remove_file(&script);
+
+    if !status.success() {
+        return Err(anyhow::anyhow!("ffmpeg timeline render exited with {}", status));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sources() -> HashMap<String, ClipSource> {
+        let video = |name: &str| ClipSource {
+            path: PathBuf::from(name),
+            kind: "video".to_string(),
+            has_audio: true,
+        };
+        HashMap::from([
+            ("a".to_string(), video("a.mp4")),
+            ("b".to_string(), video("b.mp4")),
+        ])
+    }
+
+    #[test]
+    fn two_clip_timeline_trims_each_clip_at_its_in_out() {
+        let clips = parse_clips(&json!([
+            {"id": "c1", "assetId": "a", "trackId": "V1", "start": 0.0, "duration": 3.0, "inPoint": 2.0, "outPoint": 5.0},
+            {"id": "c2", "assetId": "b", "trackId": "V1", "start": 4.0, "duration": 2.5, "inPoint": 10.0, "outPoint": 12.5},
+            {"id": "cap", "assetId": "", "trackId": "T1", "start": 0.0, "duration": 6.5, "inPoint": 0.0, "outPoint": 6.5}
+        ]))
+        .unwrap();
+        assert_eq!(clips.len(), 2, "caption clip is not rendered here");
+
+        let plan = build_plan(&clips, &sources(), Canvas::default()).unwrap();
+        let g = &plan.filtergraph;
+        assert!(g.contains("[0:v]trim=start=2.000:end=5.000,setpts=PTS-STARTPTS"), "{}", g);
+        assert!(g.contains("[1:v]trim=start=10.000:end=12.500,setpts=PTS-STARTPTS"), "{}", g);
+        assert!(g.contains("[0:a]atrim=start=2.000:end=5.000"), "{}", g);
+        assert!(g.contains("[1:a]atrim=start=10.000:end=12.500"), "{}", g);
+        // The one-second hole between the clips is filled, then concatenated
+        assert!(g.contains("d=1.000,format=yuva420p[gap0]"), "{}", g);
+        assert!(g.contains("[v0][gap0][v1]concat=n=3:v=1:a=0[layer1]"), "{}", g);
+        assert!(g.contains("adelay=4000:all=1[a1]"), "{}", g);
+        assert!(g.ends_with("amix=inputs=2:duration=longest:normalize=0[aout]"), "{}", g);
+        assert_eq!(plan.duration, 6.5);
+        assert_eq!(plan.inputs.len(), 2);
+    }
+
+    #[test]
+    fn upper_tracks_overlay_lower_ones() {
+        let clips = parse_clips(&json!([
+            {"assetId": "b", "trackId": "V2", "start": 1.0, "inPoint": 0.0, "outPoint": 1.0},
+            {"assetId": "a", "trackId": "V1", "start": 0.0, "inPoint": 0.0, "outPoint": 3.0}
+        ]))
+        .unwrap();
+        let g = build_plan(&clips, &sources(), Canvas::default()).unwrap().filtergraph;
+        let v1 = g.find("[base0][layer1]overlay").unwrap();
+        let v2 = g.find("[base1][layer2]overlay").unwrap();
+        assert!(v1 < v2, "{}", g);
+    }
+
+    #[test]
+    fn rejects_bad_ranges_and_overlaps() {
+        assert!(parse_clips(&json!([
+            {"assetId": "a", "start": 0.0, "inPoint": 5.0, "outPoint": 5.0}
+        ]))
+        .is_err());
+
+        let overlapping = parse_clips(&json!([
+            {"assetId": "a", "start": 0.0, "inPoint": 0.0, "outPoint": 3.0},
+            {"assetId": "b", "start": 2.0, "inPoint": 0.0, "outPoint": 3.0}
+        ]))
+        .unwrap();
+        assert!(build_plan(&overlapping, &sources(), Canvas::default()).is_err());
+    }
+
+    #[test]
+    fn draft_canvas_keeps_aspect_and_cut_points() {
+        let draft = Canvas::default().draft();
+        assert_eq!((draft.width, draft.height), (854, 480));
+        let small = Canvas { width: 640, height: 360, fps: 25.0 };
+        assert_eq!(small.draft().height, 360);
+
+        let clips = parse_clips(&json!([
+            {"assetId": "a", "start": 0.0, "inPoint": 2.0, "outPoint": 5.0}
+        ]))
+        .unwrap();
+        let full = build_plan(&clips, &sources(), Canvas::default()).unwrap();
+        let preview = build_plan(&clips, &sources(), draft).unwrap();
+        assert!(preview.filtergraph.contains("trim=start=2.000:end=5.000"));
+        assert_eq!(full.duration, preview.duration);
+    }
+
+    #[test]
+    fn crossfade_becomes_xfade_ending_at_the_cut() {
+        let clips = parse_clips(&json!([
+            {"id": "c1", "assetId": "a", "start": 0.0, "inPoint": 0.0, "outPoint": 4.0},
+            {"id": "c2", "assetId": "b", "start": 4.0, "inPoint": 1.0, "outPoint": 4.0,
+             "transition": {"type": "crossfade", "duration": 1.5}}
+        ]))
+        .unwrap();
+        let plan = build_plan(&clips, &sources(), Canvas::default()).unwrap();
+        let g = &plan.filtergraph;
+        assert!(g.contains("tpad=start_duration=1.500:start_mode=clone[v1]"), "{}", g);
+        assert!(
+            g.contains("[v0][v1]xfade=transition=fade:duration=1.500:offset=2.500[x1]"),
+            "{}",
+            g
+        );
+        assert!(g.contains("[x1]concat=n=1:v=1:a=0[layer1]"), "{}", g);
+        assert_eq!(plan.duration, 7.0);
+
+        let too_long = parse_clips(&json!([
+            {"assetId": "a", "start": 0.0, "inPoint": 0.0, "outPoint": 1.0},
+            {"assetId": "b", "start": 1.0, "inPoint": 0.0, "outPoint": 4.0,
+             "transition": {"type": "dip-to-black", "duration": 2.0}}
+        ]))
+        .unwrap();
+        assert!(build_plan(&too_long, &sources(), Canvas::default()).is_err());
+
+        let after_gap = parse_clips(&json!([
+            {"assetId": "a", "start": 0.0, "inPoint": 0.0, "outPoint": 2.0},
+            {"assetId": "b", "start": 3.0, "inPoint": 0.0, "outPoint": 2.0,
+             "transition": {"type": "crossfade", "duration": 0.5}}
+        ]))
+        .unwrap();
+        assert!(build_plan(&after_gap, &sources(), Canvas::default()).is_err());
+    }
+
+    #[test]
+    fn music_under_voice_is_sidechain_ducked_by_the_voice_bus() {
+        let clips = parse_clips(&json!([
+            {"assetId": "a", "trackId": "A1", "start": 0.0, "inPoint": 0.0, "outPoint": 5.0, "role": "voice"},
+            {"assetId": "b", "trackId": "A2", "start": 0.0, "inPoint": 0.0, "outPoint": 5.0, "role": "music"},
+            {"assetId": "b", "trackId": "A2", "start": 5.0, "inPoint": 5.0, "outPoint": 8.0},
+            {"assetId": "a", "trackId": "V1", "start": 0.0, "inPoint": 0.0, "outPoint": 2.0}
+        ]))
+        .unwrap();
+        let g = build_plan(&clips, &sources(), Canvas::default()).unwrap().filtergraph;
+        assert!(g.contains("[a0]amix=inputs=1:duration=longest:normalize=0[voicebus]"), "{}", g);
+        // The untagged A2 clip inherits its track's music role
+        assert!(g.contains("[a1][a2]amix=inputs=2:duration=longest:normalize=0[musicbus]"), "{}", g);
+        assert!(g.contains("[voicebus]asplit=2[voice][key]"), "{}", g);
+        assert!(g.contains("[musicbus][key]sidechaincompress="), "{}", g);
+        assert!(g.ends_with("[voice][ducked][a3]amix=inputs=3:duration=longest:normalize=0[aout]"), "{}", g);
+
+        let music_only = parse_clips(&json!([
+            {"assetId": "b", "trackId": "A2", "start": 0.0, "inPoint": 0.0, "outPoint": 5.0, "role": "music"}
+        ]))
+        .unwrap();
+        let g = build_plan(&music_only, &sources(), Canvas::default()).unwrap().filtergraph;
+        assert!(!g.contains("sidechaincompress"), "{}", g);
+    }
+
+    #[test]
+    fn speed_scale_and_opacity_map_to_filters() {
+        let clips = parse_clips(&json!([
+            {"assetId": "a", "start": 0.0, "inPoint": 0.0, "outPoint": 4.0, "speed": 2.0,
+             "transform": {"x": 0, "y": 0, "scale": 0.5, "rotation": 0, "opacity": 0.25}},
+            {"assetId": "b", "start": 2.0, "inPoint": 0.0, "outPoint": 1.0, "speed": 0.25}
+        ]))
+        .unwrap();
+        assert_eq!(clips[0].duration(), 2.0);
+        let g = build_plan(&clips, &sources(), Canvas::default()).unwrap().filtergraph;
+        assert!(g.contains("setpts=(PTS-STARTPTS)/2.0000,fps=30,scale=960:540:"), "{}", g);
+        assert!(g.contains("colorchannelmixer=aa=0.250[v0]"), "{}", g);
+        assert!(g.contains("atempo=2.0000,volume"), "{}", g);
+        assert!(g.contains("atempo=0.5,atempo=0.5000,volume"), "{}", g);
+
+        assert!(parse_clips(&json!([
+            {"assetId": "a", "start": 0.0, "inPoint": 0.0, "outPoint": 1.0, "speed": 0.0}
+        ]))
+        .is_err());
+    }
+}
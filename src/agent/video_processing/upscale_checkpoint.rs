@@ -0,0 +1,418 @@
+// SYNOID Upscale Checkpoints
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Frame-by-frame upscales (SeedVR2, Real-ESRGAN, Vector) of a long clip run
+// for hours. Each job keeps its frames in a workspace next to the input and
+// records the frames it has finished in `checkpoint.json`, saved every few
+// dozen frames. Rerunning the same upscale with the same parameters picks the
+// checkpoint up and only works on the frames that are left; any change to the
+// input or to a parameter that affects the frames (the parameters hash) starts
+// the job over. Assembly refuses to encode while a frame is missing.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// `(done, total)` after every finished frame.
+pub type FrameProgress = Arc<dyn Fn(usize, usize) + Send + Sync>;
+
+const CHECKPOINT_FILE: &str = "checkpoint.json";
+/// Save after this many newly finished frames...
+const SAVE_EVERY_FRAMES: usize = 50;
+/// ...or this long after the last save, whichever comes first.
+const SAVE_EVERY: Duration = Duration::from_secs(30);
+
+// ─────────────────────────────────────────────────────────────────────────────
+// FrameRanges
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A set of frame numbers, stored as sorted, merged inclusive ranges so a
+/// 30 000-frame checkpoint stays a few bytes long.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FrameRanges(Vec<(u64, u64)>);
+
+impl FrameRanges {
+    pub fn insert(&mut self, frame: u64) {
+        let i = self
+            .0
+            .partition_point(|&(_, end)| end.saturating_add(1) < frame);
+        match self.0.get_mut(i) {
+            Some((start, end)) if *start <= frame.saturating_add(1) => {
+                *start = (*start).min(frame);
+                *end = (*end).max(frame);
+                // Filling a one-frame gap joins two ranges
+                if let Some(&(next_start, next_end)) = self.0.get(i + 1) {
+                    if next_start <= self.0[i].1.saturating_add(1) {
+                        self.0[i].1 = self.0[i].1.max(next_end);
+                        self.0.remove(i + 1);
+                    }
+                }
+            }
+            _ => self.0.insert(i, (frame, frame)),
+        }
+    }
+
+    pub fn contains(&self, frame: u64) -> bool {
+        let i = self.0.partition_point(|&(_, end)| end < frame);
+        self.0.get(i).is_some_and(|&(start, _)| start <= frame)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.iter().map(|&(s, e)| (e - s + 1) as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Collapse sorted frame numbers into "12-40, 97" for error messages.
+fn describe_frames(frames: &[u64]) -> String {
+    let mut ranges = FrameRanges::default();
+    frames.iter().for_each(|&f| ranges.insert(f));
+    let shown: Vec<String> = ranges
+        .0
+        .iter()
+        .take(8)
+        .map(|&(s, e)| {
+            if s == e {
+                s.to_string()
+            } else {
+                format!("{}-{}", s, e)
+            }
+        })
+        .collect();
+    let more = if ranges.0.len() > shown.len() {
+        ", ..."
+    } else {
+        ""
+    };
+    format!("{}{}", shown.join(", "), more)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// UpscaleJob
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Checkpoint {
+    params_hash: String,
+    /// Frames extracted from the input; `None` until extraction finished.
+    total_frames: Option<usize>,
+    completed: FrameRanges,
+}
+
+struct JobState {
+    checkpoint: Checkpoint,
+    unsaved: usize,
+    last_save: Instant,
+}
+
+/// One upscale's workspace and checkpoint. Shared by the workers that
+/// finish frames, hence `&self` throughout.
+pub struct UpscaleJob {
+    dir: PathBuf,
+    resumed: usize,
+    state: Mutex<JobState>,
+}
+
+impl UpscaleJob {
+    /// Open the workspace at `dir` for a job with `params_hash`, resuming
+    /// its checkpoint when the hash matches and starting clean otherwise.
+    pub fn open(dir: &Path, params_hash: &str) -> Result<Self> {
+        let path = dir.join(CHECKPOINT_FILE);
+        let previous: Option<Checkpoint> = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok());
+        let checkpoint = match previous {
+            Some(c) if c.params_hash == params_hash => {
+                info!(
+                    "[UPSCALE] Resuming {:?}: {} frame(s) already upscaled",
+                    dir,
+                    c.completed.len()
+                );
+                c
+            }
+            other => {
+                if other.is_some() {
+                    warn!(
+                        "[UPSCALE] Input or parameters changed since the checkpoint in {:?}; starting over",
+                        dir
+                    );
+                }
+                if dir.exists() {
+                    std::fs::remove_dir_all(dir)
+                        .with_context(|| format!("Clearing stale workspace {:?}", dir))?;
+                }
+                Checkpoint {
+                    params_hash: params_hash.to_string(),
+                    ..Default::default()
+                }
+            }
+        };
+        std::fs::create_dir_all(dir).with_context(|| format!("Creating workspace {:?}", dir))?;
+
+        let job = Self {
+            dir: dir.to_path_buf(),
+            resumed: checkpoint.completed.len(),
+            state: Mutex::new(JobState {
+                checkpoint,
+                unsaved: 0,
+                last_save: Instant::now(),
+            }),
+        };
+        job.save()?;
+        Ok(job)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Frames that were already finished when the job was opened.
+    pub fn resumed(&self) -> usize {
+        self.resumed
+    }
+
+    /// Frames finished so far, including resumed ones.
+    pub fn completed(&self) -> usize {
+        self.lock().checkpoint.completed.len()
+    }
+
+    pub fn total_frames(&self) -> Option<usize> {
+        self.lock().checkpoint.total_frames
+    }
+
+    pub fn set_total_frames(&self, total: usize) -> Result<()> {
+        self.lock().checkpoint.total_frames = Some(total);
+        self.save()
+    }
+
+    pub fn is_done(&self, frame: u64) -> bool {
+        self.lock().checkpoint.completed.contains(frame)
+    }
+
+    /// Record a finished frame; the checkpoint is saved every
+    /// `SAVE_EVERY_FRAMES` frames or `SAVE_EVERY`.
+    pub fn complete(&self, frame: u64) {
+        let due = {
+            let mut state = self.lock();
+            state.checkpoint.completed.insert(frame);
+            state.unsaved += 1;
+            state.unsaved >= SAVE_EVERY_FRAMES || state.last_save.elapsed() >= SAVE_EVERY
+        };
+        if due {
+            if let Err(e) = self.save() {
+                warn!("[UPSCALE] Could not save checkpoint: {}", e);
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let mut state = self.lock();
+        let json = serde_json::to_string(&state.checkpoint)?;
+        let tmp = self.dir.join(format!("{}.tmp", CHECKPOINT_FILE));
+        std::fs::write(&tmp, json).context("Writing checkpoint")?;
+        std::fs::rename(&tmp, self.dir.join(CHECKPOINT_FILE)).context("Replacing checkpoint")?;
+        state.unsaved = 0;
+        state.last_save = Instant::now();
+        Ok(())
+    }
+
+    /// Fail unless every one of `frames` is checkpointed and still `exists`
+    /// on disk. Run right before the final encode.
+    pub fn verify(
+        &self,
+        frames: impl IntoIterator<Item = u64>,
+        exists: impl Fn(u64) -> bool,
+    ) -> Result<()> {
+        let missing: Vec<u64> = {
+            let state = self.lock();
+            frames
+                .into_iter()
+                .filter(|&f| !state.checkpoint.completed.contains(f) || !exists(f))
+                .collect()
+        };
+        if missing.is_empty() {
+            return Ok(());
+        }
+        Err(anyhow::anyhow!(
+            "{} upscaled frame(s) missing ({}); not encoding. Rerun the same command to redo them",
+            missing.len(),
+            describe_frames(&missing)
+        ))
+    }
+
+    /// The output is written: drop the workspace.
+    pub fn finish(self) {
+        if let Err(e) = std::fs::remove_dir_all(&self.dir) {
+            warn!("[UPSCALE] Could not remove workspace {:?}: {}", self.dir, e);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, JobState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Where the upscale of `input` keeps its frames: `<stem>_upscale/` next to
+/// the input, so an interrupted run is found again whatever the output is
+/// called.
+pub fn workspace_for(input: &Path) -> PathBuf {
+    let stem = input
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "video".to_string());
+    input.with_file_name(format!("synoid_upscale_{}", stem))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// ETA
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Frames per second over the last `window`, for an ETA that follows
+/// slowdowns instead of averaging them away. Frames skipped on resume never
+/// show up as a burst because only the change in `done` counts.
+pub struct RollingRate {
+    window: Duration,
+    samples: VecDeque<(Instant, usize)>,
+}
+
+impl RollingRate {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: VecDeque::new(),
+        }
+    }
+
+    pub fn record(&mut self, now: Instant, done: usize) {
+        self.samples.push_back((now, done));
+        // Keep one sample at or beyond the window edge as the baseline
+        while self.samples.len() > 2
+            && self
+                .samples
+                .get(1)
+                .is_some_and(|&(t, _)| now.duration_since(t) >= self.window)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn fps(&self) -> Option<f64> {
+        let (&(t0, d0), &(t1, d1)) = (self.samples.front()?, self.samples.back()?);
+        let secs = t1.duration_since(t0).as_secs_f64();
+        (secs > 0.0 && d1 > d0).then(|| (d1 - d0) as f64 / secs)
+    }
+
+    pub fn eta(&self, done: usize, total: usize) -> Option<Duration> {
+        let fps = self.fps()?;
+        Some(Duration::from_secs_f64(
+            total.saturating_sub(done) as f64 / fps,
+        ))
+    }
+}
+
+/// "3h 05m", "12m 30s" or "45s".
+pub fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    match (secs / 3600, secs / 60 % 60, secs % 60) {
+        (0, 0, s) => format!("{}s", s),
+        (0, m, s) => format!("{}m {:02}s", m, s),
+        (h, m, _) => format!("{}h {:02}m", h, m),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_ranges_merge_as_frames_finish_out_of_order() {
+        let mut ranges = FrameRanges::default();
+        for f in [5, 1, 2, 9, 3, 7] {
+            ranges.insert(f);
+        }
+        assert_eq!(ranges, FrameRanges(vec![(1, 3), (5, 5), (7, 7), (9, 9)]));
+        ranges.insert(6);
+        ranges.insert(4);
+        ranges.insert(8);
+        assert_eq!(ranges, FrameRanges(vec![(1, 9)]));
+        ranges.insert(3);
+        assert_eq!(ranges.len(), 9);
+        assert!(ranges.contains(1) && ranges.contains(9));
+        assert!(!ranges.contains(0) && !ranges.contains(10));
+        assert_eq!(describe_frames(&[2, 3, 4, 8, 11, 12]), "2-4, 8, 11-12");
+    }
+
+    #[test]
+    fn a_checkpoint_resumes_only_with_the_same_parameters() {
+        let dir = std::env::temp_dir().join(format!("synoid_ckpt_{}", uuid::Uuid::new_v4()));
+
+        let job = UpscaleJob::open(&dir, "abc").unwrap();
+        job.set_total_frames(10).unwrap();
+        for f in 1..=6 {
+            job.complete(f);
+        }
+        job.save().unwrap();
+        let err = job.verify(1..=10, |_| true).unwrap_err().to_string();
+        assert!(
+            err.contains("4 upscaled frame(s) missing (7-10)"),
+            "{}",
+            err
+        );
+        drop(job);
+
+        // Same parameters: the finished frames are skipped
+        let job = UpscaleJob::open(&dir, "abc").unwrap();
+        assert_eq!(job.resumed(), 6);
+        assert_eq!(job.total_frames(), Some(10));
+        assert!(job.is_done(6) && !job.is_done(7));
+        // A checkpointed frame whose file vanished still counts as missing
+        for f in 7..=10 {
+            job.complete(f);
+        }
+        assert!(job.verify(1..=10, |f| f != 3).is_err());
+        assert!(job.verify(1..=10, |_| true).is_ok());
+        std::fs::write(dir.join("000001.png"), b"frame").unwrap();
+        drop(job);
+
+        // Different parameters: everything is thrown away
+        let job = UpscaleJob::open(&dir, "xyz").unwrap();
+        assert_eq!(job.resumed(), 0);
+        assert_eq!(job.total_frames(), None);
+        assert!(!dir.join("000001.png").exists());
+        job.finish();
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn the_eta_follows_the_recent_frame_rate() {
+        let t0 = Instant::now();
+        let at = |s: u64| t0 + Duration::from_secs(s);
+        let mut rate = RollingRate::new(Duration::from_secs(10));
+        // Resumed at 500 frames: no rate until frames actually finish
+        rate.record(at(0), 500);
+        assert_eq!(rate.fps(), None);
+        rate.record(at(5), 510);
+        assert_eq!(rate.fps(), Some(2.0));
+        assert_eq!(rate.eta(510, 610), Some(Duration::from_secs(50)));
+
+        // Slows to 1 fps; after a full window only the slow rate counts
+        for s in 6..=20 {
+            rate.record(at(s), 510 + (s - 5) as usize);
+        }
+        assert_eq!(rate.fps(), Some(1.0));
+
+        assert_eq!(format_eta(Duration::from_secs(45)), "45s");
+        assert_eq!(format_eta(Duration::from_secs(750)), "12m 30s");
+        assert_eq!(
+            format_eta(Duration::from_secs(3 * 3600 + 5 * 60 + 9)),
+            "3h 05m"
+        );
+    }
+}
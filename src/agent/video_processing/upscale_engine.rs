@@ -15,12 +15,22 @@
 // accordingly, falling back gracefully when a model is missing.
 
 use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::video_processing::upscale_checkpoint::{self, FrameProgress, UpscaleJob};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tokio::process::Command;
 use tracing::info;
 
+/// Frames handed to a neural backend per run; the checkpoint advances once
+/// per chunk.
+const CHUNK_FRAMES: usize = 100;
+/// How often a running chunk is checked for finished frames.
+const PROGRESS_POLL: Duration = Duration::from_millis(500);
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Upscale Mode
 // ─────────────────────────────────────────────────────────────────────────────
@@ -66,6 +76,9 @@ pub struct UpscaleConfig {
     pub encode_crf: u32,
     /// H.264 preset for encoding speed/quality trade-off.
     pub encode_preset: String,
+    /// Tracing/style settings used when `mode` is `Vector`.
+    #[serde(default)]
+    pub vector: crate::agent::video_processing::vector_engine::VectorConfig,
 }
 
 impl Default for UpscaleConfig {
@@ -76,10 +89,43 @@ impl Default for UpscaleConfig {
             mode: UpscaleMode::SeedVR2,
             encode_crf: 18,
             encode_preset: "slow".to_string(),
+            vector: Default::default(),
         }
     }
 }
 
+impl UpscaleConfig {
+    /// Hash of the input file and every setting that changes the upscaled
+    /// frames. Encode settings are left out: they only apply once the frames
+    /// are done, so changing them still resumes.
+    pub fn params_hash(&self, input: &Path) -> String {
+        let meta = std::fs::metadata(input).ok();
+        let modified = meta
+            .as_ref()
+            .and_then(|m| m.modified().ok())
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs());
+        let vector = (self.mode == UpscaleMode::Vector).then(|| {
+            let mut vector = self.vector.clone();
+            vector.encode_crf = 0;
+            vector.jobs = 0;
+            vector
+        });
+        let identity = serde_json::json!({
+            "input": std::fs::canonicalize(input).unwrap_or_else(|_| input.to_path_buf()),
+            "size": meta.map(|m| m.len()),
+            "modified": modified,
+            "mode": self.mode,
+            "width": self.target_width,
+            "height": self.target_height,
+            "vector": vector,
+        });
+        let mut hasher = Sha256::new();
+        hasher.update(identity.to_string().as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // UpscaleEngine
 // ─────────────────────────────────────────────────────────────────────────────
@@ -99,6 +145,20 @@ impl UpscaleEngine {
         input_path: &Path,
         output_path: &Path,
         config: &UpscaleConfig,
+    ) -> Result<()> {
+        Self::upscale_with_progress(input_path, output_path, config, None).await
+    }
+
+    /// `upscale`, reporting `(done, total)` frames to `progress`.
+    ///
+    /// The frame-based backends (Vector, SeedVR2, Real-ESRGAN) checkpoint
+    /// their finished frames to a workspace next to the input; rerunning with
+    /// the same input and parameters resumes from it.
+    pub async fn upscale_with_progress(
+        input_path: &Path,
+        output_path: &Path,
+        config: &UpscaleConfig,
+        progress: Option<FrameProgress>,
     ) -> Result<()> {
         info!(
             "[UPSCALE] {} → {:?} (mode: {})",
@@ -109,17 +169,38 @@ impl UpscaleEngine {
 
         match &config.mode {
             UpscaleMode::Vector => {
-                // Delegate to the existing vector pipeline (frame-by-frame SVG conversion).
+                // Frame-by-frame SVG conversion, re-rendered at the target size.
                 info!("[UPSCALE] Routing to Vector pipeline.");
-                Self::upscale_via_lanczos(input_path, output_path, config)
-                    .await
-                    .context("Vector/Lanczos fallback")?;
+                let mut vector = config.vector.clone();
+                vector.encode_crf = config.encode_crf;
+                let job = Self::open_job(input_path, config)?;
+                let result = crate::agent::video_processing::vector_engine::vector_upscale(
+                    input_path,
+                    output_path,
+                    config.target_width,
+                    config.target_height,
+                    &vector,
+                    &job,
+                    progress,
+                )
+                .await;
+                match result {
+                    Ok(()) => job.finish(),
+                    Err(e) if job.completed() > 0 => return Err(Self::resumable(e, &job)),
+                    Err(e) => {
+                        job.finish();
+                        info!("[UPSCALE] Vector pipeline failed ({}); falling back to Lanczos.", e);
+                        Self::upscale_via_lanczos(input_path, output_path, config)
+                            .await
+                            .context("Vector/Lanczos fallback")?;
+                    }
+                }
             }
             UpscaleMode::SeedVR2 => {
-                Self::upscale_via_seedvr2(input_path, output_path, config).await?;
+                Self::upscale_via_seedvr2(input_path, output_path, config, progress).await?;
             }
             UpscaleMode::RealEsrgan => {
-                Self::upscale_via_realesrgan(input_path, output_path, config).await?;
+                Self::upscale_via_realesrgan(input_path, output_path, config, progress).await?;
             }
             UpscaleMode::Lanczos => {
                 Self::upscale_via_lanczos(input_path, output_path, config).await?;
@@ -144,96 +225,169 @@ impl UpscaleEngine {
         available
     }
 
-    // ── SeedVR2 Backend ──────────────────────────────────────────────────────
+    // ── Checkpointed frame jobs ──────────────────────────────────────────────
 
-    /// Run SeedVR2 upscaling.
-    ///
-    /// SeedVR2 is invoked as a CLI tool (`seedvr2` on PATH) or via a Python
-    /// inference script (`seedvr2_infer.py`).  The engine handles both cases.
+    fn open_job(input_path: &Path, config: &UpscaleConfig) -> Result<UpscaleJob> {
+        UpscaleJob::open(
+            &upscale_checkpoint::workspace_for(input_path),
+            &config.params_hash(input_path),
+        )
+    }
+
+    /// A failure after some frames finished: keep the workspace and say how
+    /// to pick it up again instead of throwing the work away on a fallback.
+    fn resumable(e: anyhow::Error, job: &UpscaleJob) -> anyhow::Error {
+        e.context(format!(
+            "Upscale stopped after {} frame(s); rerun the same command to resume from {:?}",
+            job.completed(),
+            job.dir()
+        ))
+    }
+
+    /// Run a frame-directory upscaler (SeedVR2, Real-ESRGAN) over `input_path`.
     ///
-    /// If SeedVR2 is not available the function falls back to Lanczos.
-    async fn upscale_via_seedvr2(
+    /// `tool(frames_in, frames_out)` builds the command for one chunk. A
+    /// backend that fails before finishing a single frame falls back to
+    /// Lanczos, as a missing one does.
+    async fn upscale_frames(
         input_path: &Path,
         output_path: &Path,
         config: &UpscaleConfig,
+        progress: Option<FrameProgress>,
+        name: &str,
+        tool: impl Fn(&Path, &Path) -> Command,
     ) -> Result<()> {
-        if !Self::check_seedvr2_available().await {
-            info!("[UPSCALE] SeedVR2 not found; falling back to Lanczos.");
-            return Self::upscale_via_lanczos(input_path, output_path, config).await;
+        let job = Self::open_job(input_path, config)?;
+        match Self::run_frame_job(input_path, output_path, config, &job, progress, name, &tool).await
+        {
+            Ok(true) => {
+                job.finish();
+                Ok(())
+            }
+            Ok(false) if job.completed() == 0 => {
+                info!("[UPSCALE] {} execution failed; falling back to Lanczos.", name);
+                job.finish();
+                Self::upscale_via_lanczos(input_path, output_path, config).await
+            }
+            Ok(false) => Err(Self::resumable(
+                anyhow::anyhow!("{} execution failed", name),
+                &job,
+            )),
+            Err(e) => Err(Self::resumable(e, &job)),
         }
+    }
 
-        info!("[UPSCALE] Running SeedVR2…");
-
-        let tmp_dir = std::env::temp_dir().join("synoid_seedvr2");
-        std::fs::create_dir_all(&tmp_dir).context("Creating SeedVR2 tmp dir")?;
-
-        let frames_in = tmp_dir.join("frames_in");
-        let frames_out = tmp_dir.join("frames_out");
+    /// Extract (once), upscale the frames still missing `CHUNK_FRAMES` at a
+    /// time, then verify and re-assemble. `Ok(false)` when the tool failed.
+    async fn run_frame_job(
+        input_path: &Path,
+        output_path: &Path,
+        config: &UpscaleConfig,
+        job: &UpscaleJob,
+        progress: Option<FrameProgress>,
+        name: &str,
+        tool: &impl Fn(&Path, &Path) -> Command,
+    ) -> Result<bool> {
+        let frames_in = job.dir().join("frames_in");
+        let frames_out = job.dir().join("frames_out");
+        let chunk_in = job.dir().join("chunk_in");
         std::fs::create_dir_all(&frames_in)?;
         std::fs::create_dir_all(&frames_out)?;
 
-        // 1. Extract frames
-        info!("[UPSCALE-SEEDVR2] Extracting frames…");
-        let fps = Self::probe_fps(input_path).await.unwrap_or(30.0);
-        let status = Command::new("ffmpeg")
-            .stealth()
-            .args(["-y", "-i"])
-            .arg(input_path)
-            .args(["-vf", "scale=iw:ih", "-qscale:v", "1"])
-            .arg(frames_in.join("%06d.png"))
-            .status()
-            .await
-            .context("Frame extraction for SeedVR2")?;
+        // 1. Extract frames, unless an earlier run already did; finished
+        //    frames' inputs are deleted as it goes
+        let extracted = job.total_frames().filter(|&total| {
+            (1..=total as u64).all(|f| job.is_done(f) || frames_in.join(frame_name(f)).exists())
+        });
+        let total = match extracted {
+            Some(total) => total,
+            None => {
+                info!("[UPSCALE-{}] Extracting frames…", name);
+                let status = proc::ffmpeg(ProcClass::Render)
+                    .stealth()
+                    .args(["-y", "-i"])
+                    .arg(input_path)
+                    .args(["-qscale:v", "1"])
+                    .arg(frames_in.join("%06d.png"))
+                    .status()
+                    .await
+                    .with_context(|| format!("Frame extraction for {}", name))?;
+                if !status.success() {
+                    return Err(anyhow::anyhow!("FFmpeg frame extraction failed for {}.", name));
+                }
+                let total = std::fs::read_dir(&frames_in)?
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.path().extension().is_some_and(|x| x == "png"))
+                    .count();
+                job.set_total_frames(total)?;
+                total
+            }
+        };
 
-        if !status.success() {
-            return Err(anyhow::anyhow!(
-                "FFmpeg frame extraction failed for SeedVR2."
-            ));
-        }
+        // 2. Upscale what is left, chunk by chunk
+        let pending: Vec<u64> = (1..=total as u64)
+            .filter(|&f| !(job.is_done(f) && frames_out.join(frame_name(f)).exists()))
+            .collect();
+        let mut done = total - pending.len();
+        let report = |done: usize| {
+            if let Some(cb) = &progress {
+                cb(done, total);
+            }
+        };
+        report(done);
+        info!(
+            "[UPSCALE-{}] Running inference on {} of {} frame(s) (this may be slow)…",
+            name,
+            pending.len(),
+            total
+        );
 
-        // 2. Run SeedVR2 on the extracted frames
-        info!("[UPSCALE-SEEDVR2] Running inference (this may be slow)…");
-        let scale = format!("{}x{}", config.target_width, config.target_height);
+        for chunk in pending.chunks(CHUNK_FRAMES) {
+            let _ = std::fs::remove_dir_all(&chunk_in);
+            std::fs::create_dir_all(&chunk_in)?;
+            for &f in chunk {
+                let (from, to) = (frames_in.join(frame_name(f)), chunk_in.join(frame_name(f)));
+                std::fs::hard_link(&from, &to)
+                    .or_else(|_| std::fs::copy(&from, &to).map(|_| ()))
+                    .with_context(|| format!("Staging {:?}", from))?;
+            }
 
-        // Try CLI binary first, then Python fallback
-        let seedvr2_ok = if which_exists("seedvr2") {
-            Command::new("seedvr2")
-                .stealth()
-                .args(["--input", &frames_in.to_string_lossy()])
-                .args(["--output", &frames_out.to_string_lossy()])
-                .args(["--resolution", &scale])
-                .status()
-                .await
-                .map(|s| s.success())
-                .unwrap_or(false)
-        } else {
-            // Python fallback
-            Command::new("python3")
-                .stealth()
-                .args([
-                    "seedvr2_infer.py",
-                    "--input",
-                    &frames_in.to_string_lossy(),
-                    "--output",
-                    &frames_out.to_string_lossy(),
-                    "--resolution",
-                    &scale,
-                ])
-                .status()
-                .await
-                .map(|s| s.success())
-                .unwrap_or(false)
-        };
+            let Ok(mut child) = tool(&chunk_in, &frames_out).spawn() else {
+                return Ok(false);
+            };
+            let finished = |f: &&u64| frames_out.join(frame_name(**f)).exists();
+            let mut shown = 0;
+            let status = loop {
+                tokio::select! {
+                    status = child.wait() => break status,
+                    _ = tokio::time::sleep(PROGRESS_POLL) => {
+                        let n = chunk.iter().filter(finished).count();
+                        if n > shown {
+                            shown = n;
+                            report(done + n);
+                        }
+                    }
+                }
+            };
+            if !status.is_ok_and(|s| s.success()) {
+                return Ok(false);
+            }
 
-        if !seedvr2_ok {
-            info!("[UPSCALE-SEEDVR2] Inference failed; falling back to Lanczos.");
-            let _ = std::fs::remove_dir_all(&tmp_dir);
-            return Self::upscale_via_lanczos(input_path, output_path, config).await;
+            for &f in chunk.iter().filter(finished) {
+                job.complete(f);
+                let _ = std::fs::remove_file(frames_in.join(frame_name(f)));
+                done += 1;
+            }
+            job.save()?;
+            report(done);
         }
+        let _ = std::fs::remove_dir_all(&chunk_in);
 
-        // 3. Re-assemble frames + original audio
-        info!("[UPSCALE-SEEDVR2] Re-assembling video…");
-        let status = Command::new("ffmpeg")
+        // 3. Re-assemble frames + original audio, only when none is missing
+        job.verify(1..=total as u64, |f| frames_out.join(frame_name(f)).exists())?;
+        info!("[UPSCALE-{}] Re-assembling video…", name);
+        let fps = Self::probe_fps(input_path).await.unwrap_or(30.0);
+        let status = proc::ffmpeg(ProcClass::Render)
             .stealth()
             .args(["-y", "-framerate", &fps.to_string(), "-i"])
             .arg(frames_out.join("%06d.png"))
@@ -258,15 +412,56 @@ impl UpscaleEngine {
             .arg(output_path)
             .status()
             .await
-            .context("FFmpeg re-assembly after SeedVR2")?;
-
-        let _ = std::fs::remove_dir_all(&tmp_dir);
+            .with_context(|| format!("FFmpeg re-assembly after {}", name))?;
 
         if !status.success() {
-            return Err(anyhow::anyhow!("FFmpeg re-assembly failed after SeedVR2."));
+            return Err(anyhow::anyhow!("FFmpeg re-assembly failed after {}.", name));
         }
 
-        Ok(())
+        Ok(true)
+    }
+
+    // ── SeedVR2 Backend ──────────────────────────────────────────────────────
+
+    /// Run SeedVR2 upscaling.
+    ///
+    /// SeedVR2 is invoked as a CLI tool (`seedvr2` on PATH) or via a Python
+    /// inference script (`seedvr2_infer.py`).  The engine handles both cases.
+    ///
+    /// If SeedVR2 is not available the function falls back to Lanczos.
+    async fn upscale_via_seedvr2(
+        input_path: &Path,
+        output_path: &Path,
+        config: &UpscaleConfig,
+        progress: Option<FrameProgress>,
+    ) -> Result<()> {
+        if !Self::check_seedvr2_available().await {
+            info!("[UPSCALE] SeedVR2 not found; falling back to Lanczos.");
+            return Self::upscale_via_lanczos(input_path, output_path, config).await;
+        }
+
+        info!("[UPSCALE] Running SeedVR2…");
+        let scale = format!("{}x{}", config.target_width, config.target_height);
+        // Try CLI binary first, then Python fallback
+        let cli = which_exists("seedvr2");
+        let tool = |frames_in: &Path, frames_out: &Path| {
+            // Unmanaged: GPU inference between the governed ffmpeg passes
+            let mut cmd = if cli {
+                proc::unmanaged("seedvr2")
+            } else {
+                let mut python = proc::unmanaged("python3");
+                python.arg("seedvr2_infer.py");
+                python
+            };
+            cmd.stealth()
+                .arg("--input")
+                .arg(frames_in)
+                .arg("--output")
+                .arg(frames_out)
+                .args(["--resolution", &scale]);
+            cmd
+        };
+        Self::upscale_frames(input_path, output_path, config, progress, "SEEDVR2", tool).await
     }
 
     // ── Real-ESRGAN Backend ──────────────────────────────────────────────────
@@ -275,6 +470,7 @@ impl UpscaleEngine {
         input_path: &Path,
         output_path: &Path,
         config: &UpscaleConfig,
+        progress: Option<FrameProgress>,
     ) -> Result<()> {
         if !Self::check_realesrgan_available().await {
             info!("[UPSCALE] Real-ESRGAN not found; falling back to Lanczos.");
@@ -283,75 +479,21 @@ impl UpscaleEngine {
 
         info!("[UPSCALE] Running Real-ESRGAN…");
 
-        let tmp_dir = std::env::temp_dir().join("synoid_realesrgan");
-        std::fs::create_dir_all(&tmp_dir)?;
-
-        let frames_in = tmp_dir.join("frames_in");
-        let frames_out = tmp_dir.join("frames_out");
-        std::fs::create_dir_all(&frames_in)?;
-        std::fs::create_dir_all(&frames_out)?;
-
-        let fps = Self::probe_fps(input_path).await.unwrap_or(30.0);
-
-        Command::new("ffmpeg")
-            .stealth()
-            .args(["-y", "-i"])
-            .arg(input_path)
-            .args(["-qscale:v", "1"])
-            .arg(frames_in.join("%06d.png"))
-            .status()
-            .await
-            .context("Frame extraction for ESRGAN")?;
-
         // Determine integer scale factor from target resolution
         let scale_factor = Self::compute_scale_factor(input_path, config).await;
-
-        let esrgan_ok = Command::new("realesrgan-ncnn-vulkan")
-            .stealth()
-            .args(["-i", &frames_in.to_string_lossy()])
-            .args(["-o", &frames_out.to_string_lossy()])
-            .args(["-s", &scale_factor.to_string()])
-            .args(["-n", "realesrgan-x4plus"])
-            .status()
-            .await
-            .map(|s| s.success())
-            .unwrap_or(false);
-
-        if !esrgan_ok {
-            info!("[UPSCALE] Real-ESRGAN execution failed; falling back to Lanczos.");
-            let _ = std::fs::remove_dir_all(&tmp_dir);
-            return Self::upscale_via_lanczos(input_path, output_path, config).await;
-        }
-
-        Command::new("ffmpeg")
-            .stealth()
-            .args(["-y", "-framerate", &fps.to_string(), "-i"])
-            .arg(frames_out.join("%06d.png"))
-            .args(["-i"])
-            .arg(input_path)
-            .args([
-                "-map",
-                "0:v",
-                "-map",
-                "1:a?",
-                "-c:v",
-                "libx264",
-                "-preset",
-                &config.encode_preset,
-                "-crf",
-                &config.encode_crf.to_string(),
-                "-pix_fmt",
-                "yuv420p",
-                "-c:a",
-                "copy",
-            ])
-            .arg(output_path)
-            .status()
-            .await
-            .context("FFmpeg re-assembly after ESRGAN")?;
-
-        let _ = std::fs::remove_dir_all(&tmp_dir);
-        Ok(())
+        let tool = |frames_in: &Path, frames_out: &Path| {
+            // Unmanaged: GPU inference between the governed ffmpeg passes
+            let mut cmd = proc::unmanaged("realesrgan-ncnn-vulkan");
+            cmd.stealth()
+                .arg("-i")
+                .arg(frames_in)
+                .arg("-o")
+                .arg(frames_out)
+                .args(["-s", &scale_factor.to_string()])
+                .args(["-n", "realesrgan-x4plus"]);
+            cmd
+        };
+        Self::upscale_frames(input_path, output_path, config, progress, "ESRGAN", tool).await
     }
 
     // ── Lanczos Fallback ─────────────────────────────────────────────────────
@@ -375,7 +517,7 @@ impl UpscaleEngine {
             )
         };
 
-        let status = Command::new("ffmpeg")
+        let status = proc::ffmpeg(ProcClass::Render)
             .stealth()
             .args(["-y", "-i"])
             .arg(input_path)
@@ -418,7 +560,7 @@ impl UpscaleEngine {
     // ── Helpers ──────────────────────────────────────────────────────────────
 
     async fn probe_fps(path: &Path) -> Option<f64> {
-        let out = Command::new("ffprobe")
+        let out = proc::ffprobe()
             .stealth()
             .args([
                 "-v",
@@ -454,7 +596,7 @@ impl UpscaleEngine {
 
     async fn compute_scale_factor(input_path: &Path, config: &UpscaleConfig) -> u32 {
         // Ask ffprobe for the source width
-        let out = Command::new("ffprobe")
+        let out = proc::ffprobe()
             .stealth()
             .args([
                 "-v",
@@ -489,7 +631,7 @@ impl UpscaleEngine {
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// Check whether an executable exists on PATH without spawning it.
-fn which_exists(name: &str) -> bool {
+pub(crate) fn which_exists(name: &str) -> bool {
     if let Ok(path_env) = std::env::var("PATH") {
         for dir in path_env.split(':') {
             let candidate = PathBuf::from(dir).join(name);
@@ -500,3 +642,41 @@ fn which_exists(name: &str) -> bool {
     }
     false
 }
+
+/// File name of extracted frame `n` (1-based, as FFmpeg's `%06d` numbers them).
+fn frame_name(n: u64) -> String {
+    format!("{:06}.png", n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_frame_settings_change_the_params_hash() {
+        let input = Path::new("/videos/clip.mp4");
+        let config = UpscaleConfig::default();
+        let hash = config.params_hash(input);
+        assert_eq!(hash, config.params_hash(input));
+
+        // Encoding happens after the frames: still resumable
+        let reencoded = UpscaleConfig {
+            encode_crf: 23,
+            encode_preset: "fast".to_string(),
+            ..config.clone()
+        };
+        assert_eq!(reencoded.params_hash(input), hash);
+
+        let wider = UpscaleConfig {
+            target_width: 1920,
+            ..config.clone()
+        };
+        assert_ne!(wider.params_hash(input), hash);
+        assert_ne!(config.params_hash(Path::new("/videos/other.mp4")), hash);
+        let esrgan = UpscaleConfig {
+            mode: UpscaleMode::RealEsrgan,
+            ..config
+        };
+        assert_ne!(esrgan.params_hash(input), hash);
+    }
+}
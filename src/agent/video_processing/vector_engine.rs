@@ -0,0 +1,624 @@
+// SYNOID Vector Engine — Frame-by-Frame SVG Conversion
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Turns video frames into resolution-independent SVGs (the artistic
+// "Vector" look). Frames are streamed out of FFmpeg as raw RGBA instead of
+// being dumped to PNG first, and traced by a bounded pool of blocking
+// workers so long videos neither fill the disk nor run serially.
+//
+// `rasterize_svgs` closes the loop: it renders the SVGs back to pixels at any
+// target resolution (after an optional style pass) and pipes them into an
+// FFmpeg encode, which is what makes vector "upscaling" possible.
+
+use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
+use crate::agent::video_processing::upscale_checkpoint::{FrameProgress, UpscaleJob};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::Semaphore;
+use tracing::{info, warn};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// VectorConfig
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Configuration for `vectorize_video`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VectorConfig {
+    /// Start of the range to vectorize, in seconds (None = from the start).
+    pub start_secs: Option<f64>,
+    /// End of the range to vectorize, in seconds (None = to the end).
+    pub end_secs: Option<f64>,
+    /// Keep every Nth frame (1 = every frame).
+    pub frame_step: u32,
+    /// Output file name; `{n}` is replaced by the zero-padded source frame number.
+    pub name_pattern: String,
+    /// Concurrent tracing workers (0 = one per CPU core).
+    pub jobs: usize,
+    /// Colour quantisation precision passed to the tracer (1-8, higher = more colours).
+    pub color_precision: i32,
+    /// Discard traced patches smaller than this many pixels.
+    pub filter_speckle: usize,
+    /// Style pass: multiply every `stroke-width` by this factor; values
+    /// above 1.0 also outline fill-only paths for an inked look.
+    pub stroke_scale: f32,
+    /// Style pass: posterize fill/stroke colours to this many levels per
+    /// channel (None = keep the traced palette).
+    pub palette_levels: Option<u8>,
+    /// CRF for the reassembled video.
+    pub encode_crf: u32,
+}
+
+impl Default for VectorConfig {
+    fn default() -> Self {
+        Self {
+            start_secs: None,
+            end_secs: None,
+            frame_step: 1,
+            name_pattern: "frame_{n}.svg".to_string(),
+            jobs: 0,
+            color_precision: 6,
+            filter_speckle: 4,
+            stroke_scale: 1.0,
+            palette_levels: None,
+            encode_crf: 18,
+        }
+    }
+}
+
+impl VectorConfig {
+    /// File name for a given source frame number.
+    pub fn file_name(&self, frame: u64) -> String {
+        let padded = format!("{:06}", frame);
+        if self.name_pattern.contains("{n}") {
+            self.name_pattern.replace("{n}", &padded)
+        } else {
+            format!("{}_{}.svg", self.name_pattern, padded)
+        }
+    }
+
+    fn worker_count(&self) -> usize {
+        if self.jobs == 0 {
+            num_cpus::get().max(1)
+        } else {
+            self.jobs
+        }
+    }
+
+    fn has_style_pass(&self) -> bool {
+        (self.stroke_scale - 1.0).abs() > f32::EPSILON || self.palette_levels.is_some()
+    }
+
+    fn tracer_config(&self) -> vtracer::Config {
+        vtracer::Config {
+            color_precision: self.color_precision.clamp(1, 8),
+            filter_speckle: self.filter_speckle,
+            ..Default::default()
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Probing
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Basic stream facts needed to slice raw frames out of FFmpeg's output.
+#[derive(Debug, Clone, Copy)]
+pub struct VideoGeometry {
+    pub width: usize,
+    pub height: usize,
+    pub fps: f64,
+    pub duration: f64,
+}
+
+pub async fn probe_geometry(input: &Path) -> Result<VideoGeometry> {
+    let out = proc::ffprobe()
+        .stealth()
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "v:0",
+            "-show_entries",
+            "stream=width,height,r_frame_rate:format=duration",
+            "-of",
+            "default=noprint_wrappers=1",
+        ])
+        .arg(input)
+        .output()
+        .await
+        .context("Running ffprobe")?;
+
+    let text = String::from_utf8_lossy(&out.stdout);
+    let mut geo = VideoGeometry {
+        width: 0,
+        height: 0,
+        fps: 30.0,
+        duration: 0.0,
+    };
+    for line in text.lines() {
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key {
+            "width" => geo.width = value.trim().parse().unwrap_or(0),
+            "height" => geo.height = value.trim().parse().unwrap_or(0),
+            "duration" => geo.duration = value.trim().parse().unwrap_or(0.0),
+            "r_frame_rate" => {
+                if let Some((n, d)) = value.trim().split_once('/') {
+                    let (n, d): (f64, f64) = (n.parse().unwrap_or(0.0), d.parse().unwrap_or(1.0));
+                    if n > 0.0 && d > 0.0 {
+                        geo.fps = n / d;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if geo.width == 0 || geo.height == 0 {
+        return Err(anyhow::anyhow!("Could not read video dimensions for {:?}", input));
+    }
+    Ok(geo)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Vectorize
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Number of frames `vectorize_video` will emit for this geometry and config.
+pub fn expected_frame_count(geo: &VideoGeometry, config: &VectorConfig) -> usize {
+    let start = config.start_secs.unwrap_or(0.0).max(0.0);
+    let end = config
+        .end_secs
+        .unwrap_or(geo.duration)
+        .min(if geo.duration > 0.0 { geo.duration } else { f64::MAX });
+    let span = (end - start).max(0.0);
+    let frames = (span * geo.fps).ceil() as usize;
+    frames.div_ceil(config.frame_step.max(1) as usize)
+}
+
+/// Convert frames of `input` into SVG files inside `output_dir`.
+///
+/// Returns the number of SVGs written. `progress` receives `(done, total)`
+/// after every finished frame; `total` is an estimate from the probed
+/// duration and frame rate.
+pub async fn vectorize_video(
+    input: &Path,
+    output_dir: &Path,
+    config: &VectorConfig,
+    progress: Option<Arc<dyn Fn(usize, usize) + Send + Sync>>,
+) -> Result<usize> {
+    vectorize_frames(input, output_dir, config, progress, None)
+        .await
+        .map(|run| run.written)
+}
+
+/// What `vectorize_frames` streamed and wrote.
+struct Vectorized {
+    /// SVGs written (or, when resuming, already there).
+    written: usize,
+    /// Source frame number of every frame streamed, in order.
+    frames: Vec<u64>,
+}
+
+/// `vectorize_video`, optionally as part of a checkpointed upscale: frames
+/// `job` already finished are read past without tracing, and every newly
+/// written SVG is recorded in it.
+async fn vectorize_frames(
+    input: &Path,
+    output_dir: &Path,
+    config: &VectorConfig,
+    progress: Option<Arc<dyn Fn(usize, usize) + Send + Sync>>,
+    job: Option<&UpscaleJob>,
+) -> Result<Vectorized> {
+    let geo = probe_geometry(input).await?;
+    let step = config.frame_step.max(1);
+    let total = expected_frame_count(&geo, config);
+    let workers = config.worker_count();
+    std::fs::create_dir_all(output_dir).context("Creating vector output dir")?;
+
+    info!(
+        "[VECTOR] {:?} → {:?}: {}x{} @ {:.2}fps, every {} frame(s), ~{} frames, {} workers",
+        input, output_dir, geo.width, geo.height, geo.fps, step, total, workers
+    );
+
+    let mut cmd = proc::ffmpeg(ProcClass::Render);
+    cmd.stealth().args(["-v", "error", "-nostdin"]);
+    let start = config.start_secs.unwrap_or(0.0).max(0.0);
+    if start > 0.0 {
+        cmd.args(["-ss", &format!("{:.3}", start)]);
+    }
+    cmd.arg("-i").arg(input);
+    if let Some(end) = config.end_secs {
+        cmd.args(["-t", &format!("{:.3}", (end - start).max(0.0))]);
+    }
+    if step > 1 {
+        cmd.args(["-vf", &format!("select='not(mod(n\\,{}))'", step), "-vsync", "vfr"]);
+    }
+    cmd.args(["-f", "rawvideo", "-pix_fmt", "rgba", "pipe:1"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+
+    let mut child = cmd.spawn().await.context("Spawning FFmpeg frame stream")?;
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow::anyhow!("FFmpeg stdout unavailable"))?;
+
+    let frame_bytes = geo.width * geo.height * 4;
+    let first_frame = (start * geo.fps).round() as u64;
+    let semaphore = Arc::new(Semaphore::new(workers));
+    let resumed = job.map_or(0, |j| j.completed());
+    let done = Arc::new(AtomicUsize::new(resumed));
+    if let (Some(cb), true) = (&progress, resumed > 0) {
+        cb(resumed, total.max(resumed));
+    }
+    let mut tasks = VecDeque::new();
+    let mut frames = Vec::new();
+    let mut written = 0;
+    let mut index: u64 = 0;
+
+    loop {
+        // Waiting for a permit before reading bounds the number of decoded
+        // frames held in memory to the worker count.
+        let permit = semaphore.clone().acquire_owned().await?;
+        let mut buf = vec![0u8; frame_bytes];
+        match stdout.read_exact(&mut buf).await {
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e).context("Reading FFmpeg frame stream"),
+        }
+
+        let source_frame = first_frame + index * step as u64;
+        let path = output_dir.join(config.file_name(source_frame));
+        frames.push(source_frame);
+        index += 1;
+        if job.is_some_and(|j| j.is_done(source_frame)) && path.exists() {
+            written += 1;
+            continue;
+        }
+        let tracer = config.tracer_config();
+        let (width, height) = (geo.width, geo.height);
+        let done = done.clone();
+        let progress = progress.clone();
+
+        tasks.push_back((source_frame, tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            let img = vtracer::ColorImage {
+                pixels: buf,
+                width,
+                height,
+            };
+            let result = vtracer::convert(img, tracer)
+                .map_err(|e| anyhow::anyhow!(e))
+                .and_then(|svg| {
+                    std::fs::write(&path, svg.to_string())
+                        .with_context(|| format!("Writing {:?}", path))
+                });
+            let n = done.fetch_add(1, Ordering::SeqCst) + 1;
+            if let Some(cb) = progress {
+                cb(n, total.max(n));
+            }
+            result
+        })));
+
+        // Checkpoint frames as they finish, not only at the end
+        while tasks.front().is_some_and(|(_, t)| t.is_finished()) {
+            let (frame, task) = tasks.pop_front().unwrap();
+            written += finish_frame(frame, task.await?, job);
+        }
+    }
+
+    let status = child.wait().await?;
+    for (frame, task) in tasks {
+        written += finish_frame(frame, task.await?, job);
+    }
+
+    if !status.success() && written == 0 {
+        return Err(anyhow::anyhow!("FFmpeg frame stream failed for {:?}", input));
+    }
+
+    info!("[VECTOR] Wrote {} SVG frame(s) to {:?}", written, output_dir);
+    Ok(Vectorized { written, frames })
+}
+
+/// Count a traced frame, recording it in `job`; 1 when it was written.
+fn finish_frame(frame: u64, result: Result<()>, job: Option<&UpscaleJob>) -> usize {
+    match result {
+        Ok(()) => {
+            if let Some(job) = job {
+                job.complete(frame);
+            }
+            1
+        }
+        Err(e) => {
+            warn!("[VECTOR] Frame failed: {}", e);
+            0
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Style pass
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Snap one 0-255 channel to `levels` evenly spaced values.
+fn posterize(channel: u8, levels: u8) -> u8 {
+    let levels = levels.max(2) as f32;
+    let step = 255.0 / (levels - 1.0);
+    ((channel as f32 / step).round() * step).round().clamp(0.0, 255.0) as u8
+}
+
+fn quantize_hex(hex: &str, levels: u8) -> String {
+    let parse = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap_or(0);
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        posterize(parse(0), levels),
+        posterize(parse(2), levels),
+        posterize(parse(4), levels)
+    )
+}
+
+/// Apply the `VectorConfig` style pass (stroke scaling, palette
+/// quantization) to one SVG document.
+pub fn style_svg(svg: &str, config: &VectorConfig) -> String {
+    let mut out = svg.to_string();
+
+    if let Some(levels) = config.palette_levels {
+        let re = regex::Regex::new(r##"(fill|stroke)="#([0-9A-Fa-f]{6})""##).unwrap();
+        out = re
+            .replace_all(&out, |c: &regex::Captures| {
+                format!(r#"{}="{}""#, &c[1], quantize_hex(&c[2], levels))
+            })
+            .into_owned();
+    }
+
+    if (config.stroke_scale - 1.0).abs() > f32::EPSILON {
+        let scale = config.stroke_scale.max(0.0);
+        let re = regex::Regex::new(r#"stroke-width="([0-9.]+)""#).unwrap();
+        out = re
+            .replace_all(&out, |c: &regex::Captures| {
+                let w: f32 = c[1].parse().unwrap_or(1.0);
+                format!(r#"stroke-width="{}""#, w * scale)
+            })
+            .into_owned();
+
+        if scale > 1.0 {
+            // Fill-only paths get an outline in their own colour.
+            let re = regex::Regex::new(r##"<path ([^>]*?)fill="(#[0-9A-Fa-f]{6})"([^>]*?)/>"##).unwrap();
+            out = re
+                .replace_all(&out, |c: &regex::Captures| {
+                    let attrs = format!("{}{}", &c[1], &c[3]);
+                    if attrs.contains("stroke") {
+                        c[0].to_string()
+                    } else {
+                        format!(
+                            r#"<path {}fill="{}" stroke="{}" stroke-width="{}"{}/>"#,
+                            &c[1],
+                            &c[2],
+                            &c[2],
+                            scale - 1.0,
+                            &c[3]
+                        )
+                    }
+                })
+                .into_owned();
+        }
+    }
+
+    out
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Reassembly
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Render one SVG file to a `width`×`height` RGBA buffer on a black canvas.
+fn render_svg(path: &Path, width: u32, height: u32, config: &VectorConfig) -> Result<Vec<u8>> {
+    use resvg::{tiny_skia, usvg};
+
+    let raw = std::fs::read_to_string(path).with_context(|| format!("Reading {:?}", path))?;
+    let svg = if config.has_style_pass() {
+        style_svg(&raw, config)
+    } else {
+        raw
+    };
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default())
+        .with_context(|| format!("Parsing {:?}", path))?;
+
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| anyhow::anyhow!("Invalid render size {}x{}", width, height))?;
+    pixmap.fill(tiny_skia::Color::BLACK);
+    let size = tree.size();
+    let transform = tiny_skia::Transform::from_scale(
+        width as f32 / size.width(),
+        height as f32 / size.height(),
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+    Ok(pixmap.take())
+}
+
+/// Render every SVG in `dir` (in file-name order) at `width`×`height` and
+/// encode them at `fps` into `output`.
+///
+/// Frames are rendered in parallel batches of `config.jobs` workers and
+/// written to FFmpeg's stdin in order. When `audio_source` is given its
+/// audio track is muxed into the result.
+pub async fn rasterize_svgs(
+    dir: &Path,
+    width: u32,
+    height: u32,
+    fps: f64,
+    output: &Path,
+    audio_source: Option<&Path>,
+    config: &VectorConfig,
+) -> Result<usize> {
+    let mut frames: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Reading SVG dir {:?}", dir))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().map(|e| e == "svg").unwrap_or(false))
+        .collect();
+    frames.sort();
+    if frames.is_empty() {
+        return Err(anyhow::anyhow!("No SVG frames found in {:?}", dir));
+    }
+
+    // Even dimensions keep yuv420p encoders happy.
+    let (width, height) = (width.max(2) & !1, height.max(2) & !1);
+    let workers = config.worker_count();
+    info!(
+        "[VECTOR] Rasterizing {} SVG frame(s) at {}x{} @ {:.2}fps → {:?}",
+        frames.len(),
+        width,
+        height,
+        fps,
+        output
+    );
+
+    let mut cmd = proc::ffmpeg(ProcClass::Render);
+    cmd.stealth()
+        .args(["-y", "-v", "error", "-f", "rawvideo", "-pix_fmt", "rgba"])
+        .args(["-s", &format!("{}x{}", width, height)])
+        .args(["-r", &format!("{:.3}", fps), "-i", "pipe:0"]);
+    if let Some(src) = audio_source {
+        cmd.arg("-i").arg(src).args(["-map", "0:v", "-map", "1:a?", "-c:a", "aac", "-shortest"]);
+    }
+    cmd.args([
+        "-c:v",
+        "libx264",
+        "-crf",
+        &config.encode_crf.to_string(),
+        "-preset",
+        "medium",
+        "-pix_fmt",
+        "yuv420p",
+    ])
+    .arg(output)
+    .stdin(Stdio::piped())
+    .stderr(Stdio::null());
+
+    let mut child = cmd.spawn().await.context("Spawning FFmpeg encoder")?;
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| anyhow::anyhow!("FFmpeg stdin unavailable"))?;
+
+    let mut written = 0;
+    for batch in frames.chunks(workers) {
+        let renders: Vec<_> = batch
+            .iter()
+            .cloned()
+            .map(|path| {
+                let config = config.clone();
+                tokio::task::spawn_blocking(move || render_svg(&path, width, height, &config))
+            })
+            .collect();
+        for render in renders {
+            let rgba = render.await??;
+            stdin
+                .write_all(&rgba)
+                .await
+                .context("Writing frame to FFmpeg")?;
+            written += 1;
+        }
+    }
+    drop(stdin);
+
+    let status = child.wait().await?;
+    if !status.success() {
+        return Err(anyhow::anyhow!("FFmpeg encode of rasterized SVGs failed."));
+    }
+    info!("[VECTOR] Reassembled {} frame(s) into {:?}", written, output);
+    Ok(written)
+}
+
+/// Vectorize `input` and render it back at `width`×`height`, keeping the
+/// source audio — resolution-independent upscaling.
+///
+/// The SVGs live in `job`'s workspace and each one is checkpointed, so a
+/// rerun with the same parameters only traces the frames that are left.
+/// Nothing is encoded while a frame is missing.
+pub async fn vector_upscale(
+    input: &Path,
+    output: &Path,
+    width: u32,
+    height: u32,
+    config: &VectorConfig,
+    job: &UpscaleJob,
+    progress: Option<FrameProgress>,
+) -> Result<()> {
+    let geo = probe_geometry(input).await?;
+    let height = if height == 0 {
+        (width as f64 * geo.height as f64 / geo.width as f64).round() as u32
+    } else {
+        height
+    };
+
+    let svg_dir = job.dir().join("svg");
+    let run = vectorize_frames(input, &svg_dir, config, progress, Some(job)).await?;
+    job.save()?;
+    job.verify(run.frames.iter().copied(), |f| {
+        svg_dir.join(config.file_name(f)).exists()
+    })?;
+    let fps = geo.fps / config.frame_step.max(1) as f64;
+    rasterize_svgs(&svg_dir, width, height, fps, output, Some(input), config).await?;
+    Ok(())
+}
+
+/// Default output directory next to the input: `<stem>_vector/`.
+pub fn default_output_dir(input: &Path) -> PathBuf {
+    let stem = input
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "video".to_string());
+    input.with_file_name(format!("{}_vector", stem))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_naming_and_count() {
+        let mut config = VectorConfig::default();
+        assert_eq!(config.file_name(42), "frame_000042.svg");
+        config.name_pattern = "shot".to_string();
+        assert_eq!(config.file_name(7), "shot_000007.svg");
+
+        let geo = VideoGeometry {
+            width: 64,
+            height: 64,
+            fps: 30.0,
+            duration: 10.0,
+        };
+        config.start_secs = Some(2.0);
+        config.end_secs = Some(4.0);
+        config.frame_step = 4;
+        assert_eq!(expected_frame_count(&geo, &config), 15);
+    }
+
+    #[test]
+    fn test_style_pass() {
+        let svg = r##"<path d="M0 0" fill="#C01010" transform="translate(0,0)"/>
+<path d="M1 1" fill="#000000" stroke="#FFFFFF" stroke-width="2"/>"##;
+
+        let config = VectorConfig {
+            palette_levels: Some(2),
+            stroke_scale: 2.0,
+            ..Default::default()
+        };
+        let styled = style_svg(svg, &config);
+        assert!(styled.contains(r##"fill="#ff0000" stroke="#ff0000" stroke-width="1""##));
+        assert!(styled.contains(r##"stroke="#ffffff" stroke-width="4""##));
+
+        // Default config leaves the document untouched.
+        assert_eq!(style_svg(svg, &VectorConfig::default()), svg);
+    }
+}
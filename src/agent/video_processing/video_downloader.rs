@@ -3,8 +3,8 @@
 // Uses yt-dlp for safe, legal video downloads from approved sources
 
 use std::path::{Path, PathBuf};
+use crate::agent::engines::proc::{self, ProcClass};
 use std::fs;
-use tokio::process::Command;
 use tracing::{info, warn, error};
 use serde::{Deserialize, Serialize};
 
@@ -178,7 +178,7 @@ async fn download_video(
     config: &DownloadConfig,
 ) -> Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> {
     // Check if yt-dlp is installed
-    let check_ytdlp = Command::new("yt-dlp")
+    let check_ytdlp = proc::command("yt-dlp", ProcClass::Probe)
         .arg("--version")
         .output()
         .await;
@@ -192,7 +192,7 @@ async fn download_video(
 
     info!("[DOWNLOADER] 📥 Downloading from: {}", url);
 
-    let output = Command::new("yt-dlp")
+    let output = proc::command("yt-dlp", ProcClass::Download)
         .args([
             "--format", "bestvideo[height<=720]+bestaudio/best[height<=720]",
             "--merge-output-format", "mp4",
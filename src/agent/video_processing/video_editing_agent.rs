@@ -10,6 +10,7 @@ use crate::agent::core_systems::autonomous_learner::AutonomousLearner;
 use crate::agent::core_systems::brain::Brain;
 use crate::agent::core_systems::gepa::GepaLoop;
 use crate::agent::specialized::smart_editor;
+use crate::agent::tools::scan_cache;
 use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -173,7 +174,8 @@ impl VideoEditingAgent {
 
         // kept_ratio: if we have both input and output scene counts, compare them
         let input_scenes = if success && output != input {
-            smart_editor::detect_scenes(input, 0.4)
+            // The input was analyzed for the edit; reuse that pass
+            scan_cache::scenes(input, 0.4)
                 .await
                 .map(|s| s.len())
                 .unwrap_or(scene_count)
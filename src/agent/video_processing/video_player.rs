@@ -1,31 +1,225 @@
+use super::preview_audio::PreviewAudio;
+use crate::agent::specialized::smart_editor::EditPlan;
+use crate::agent::engines::proc::{self, BlockingManagedChild, ProcClass};
 use std::io::Read;
-use std::process::{Child, Command, Stdio};
+use std::path::Path;
+use std::process::Stdio;
 use crate::agent::engines::process_utils::CommandExt;
 use std::sync::mpsc::{sync_channel, Receiver, TryRecvError};
 use std::thread;
 use std::time::{Duration, Instant};
 
+/// Paces playback against wall-clock time, independent of how often the UI
+/// asks for a frame. The first decoded frame starts the clock; after that,
+/// frame `n` is due `n / fps` seconds later. With sound, `sync_to` keeps the
+/// clock on the audio device's time instead.
+#[derive(Debug, Clone)]
+pub struct FrameClock {
+    fps: f64,
+    started: Option<Instant>,
+    shown: u64,
+}
+
+impl FrameClock {
+    pub fn new(fps: f64) -> Self {
+        let fps = if fps.is_finite() && fps > 0.0 { fps } else { 30.0 };
+        Self {
+            fps,
+            started: None,
+            shown: 0,
+        }
+    }
+
+    /// Frames to take from the decoder at `now` to be on time: one to start
+    /// with, then however many have fallen due since the last call.
+    pub fn due(&self, now: Instant) -> u64 {
+        match self.started {
+            None => 1,
+            Some(start) => {
+                let elapsed = now.saturating_duration_since(start).as_secs_f64();
+                ((elapsed * self.fps).floor() as u64 + 1).saturating_sub(self.shown)
+            }
+        }
+    }
+
+    /// Re-anchor the clock so that `elapsed` seconds of playback have passed
+    /// at `now`. Does nothing before the first frame.
+    pub fn sync_to(&mut self, elapsed: f64, now: Instant) {
+        if self.started.is_none() || !elapsed.is_finite() {
+            return;
+        }
+        if let Some(start) = now.checked_sub(Duration::from_secs_f64(elapsed.max(0.0))) {
+            self.started = Some(start);
+        }
+    }
+
+    /// Record that `frames` more were taken at `now`.
+    pub fn advance(&mut self, frames: u64, now: Instant) {
+        if frames > 0 {
+            self.started.get_or_insert(now);
+            self.shown += frames;
+        }
+    }
+
+    /// Seconds of video played since the first frame.
+    pub fn played(&self) -> f64 {
+        self.shown.saturating_sub(1) as f64 / self.fps
+    }
+}
+
+/// How long the playhead must rest before a seek restarts the decoders.
+const SEEK_SETTLE: Duration = Duration::from_millis(120);
+/// Longest a drag can go without a seek, so scrubbing shows frames as it goes.
+const SEEK_MAX_WAIT: Duration = Duration::from_millis(400);
+
+/// Coalesces a burst of seek requests, such as a playhead drag, into one
+/// restart of the decoders: the latest target wins, and it is due once the
+/// requests pause for `settle` or have kept coming for `max_wait`.
+#[derive(Debug, Clone)]
+pub struct SeekDebouncer {
+    settle: Duration,
+    max_wait: Duration,
+    target: Option<f64>,
+    first: Option<Instant>,
+    last: Option<Instant>,
+}
+
+impl SeekDebouncer {
+    pub fn new(settle: Duration, max_wait: Duration) -> Self {
+        Self {
+            settle,
+            max_wait,
+            target: None,
+            first: None,
+            last: None,
+        }
+    }
+
+    /// Ask to seek to `timestamp`, replacing any target not yet due.
+    pub fn request(&mut self, timestamp: f64, now: Instant) {
+        self.target = Some(timestamp);
+        self.first.get_or_insert(now);
+        self.last = Some(now);
+    }
+
+    /// The target waiting to be applied, due or not.
+    pub fn pending(&self) -> Option<f64> {
+        self.target
+    }
+
+    /// Take the target if it is due at `now`.
+    pub fn due(&mut self, now: Instant) -> Option<f64> {
+        let (first, last) = (self.first?, self.last?);
+        let settled = now.saturating_duration_since(last) >= self.settle;
+        let overdue = now.saturating_duration_since(first) >= self.max_wait;
+        if !settled && !overdue {
+            return None;
+        }
+        self.first = None;
+        self.last = None;
+        self.target.take()
+    }
+}
+
+/// Shortest range worth looping; anything less would restart constantly.
+const MIN_LOOP_SECS: f64 = 0.2;
+/// How close to the end of a loop the decoder for its start is spawned, so
+/// its first frames are buffered by the time playback wraps.
+const LOOP_PRELOAD_SECS: f64 = 1.0;
+
+/// A span of the file played over and over.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoopRange {
+    pub start: f64,
+    pub end: f64,
+}
+
+impl LoopRange {
+    /// The range between two points in either order; `None` when it is too
+    /// short to loop.
+    pub fn new(a: f64, b: f64) -> Option<Self> {
+        let (start, end) = (a.min(b).max(0.0), a.max(b));
+        (start.is_finite() && end.is_finite() && end - start >= MIN_LOOP_SECS)
+            .then_some(Self { start, end })
+    }
+
+    /// Playback at `position` has reached the end and goes back to `start`.
+    pub fn wraps_at(&self, position: f64) -> bool {
+        position >= self.end
+    }
+
+    /// What playback at `position` should do next. `ended` is set when the
+    /// file ran out before the range did; `preloaded` once a decoder for
+    /// `start` is waiting.
+    pub fn step(&self, position: f64, ended: bool, preloaded: bool) -> LoopStep {
+        let preload_from = self.end - LOOP_PRELOAD_SECS.min((self.end - self.start) / 2.0);
+        if ended || self.wraps_at(position) {
+            LoopStep::Wrap
+        } else if !preloaded && position >= preload_from {
+            LoopStep::Preload
+        } else {
+            LoopStep::Play
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoopStep {
+    Play,
+    /// Start the decoder for the loop's start.
+    Preload,
+    /// Go back to the loop's start.
+    Wrap,
+}
+
 pub struct VideoPlayer {
     receiver: Receiver<Vec<u8>>,
-    process: Option<Child>,
+    process: Option<BlockingManagedChild>,
     pub width: usize,
     pub height: usize,
     pub fps: f64,
-    last_frame_time: Option<Instant>,
+    /// Where in the file playback started.
+    start: f64,
+    clock: FrameClock,
     current_frame: Option<Vec<u8>>,
     pub playing: bool,
+    /// Sound, when this player has it; its clock paces the frames.
+    audio: Option<PreviewAudio>,
+    path: String,
+    with_audio: bool,
+    seeks: SeekDebouncer,
+    loop_range: Option<LoopRange>,
+    /// Silent decoder already started at the loop's start, swapped in when
+    /// playback wraps.
+    standby: Option<Box<VideoPlayer>>,
 }
 
 impl VideoPlayer {
     pub fn new(
         path: &str,
         timestamp: f64,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Self::spawn(path, timestamp, true)
+    }
+
+    /// Video-only decoder, for a second stream played alongside another.
+    pub fn new_silent(
+        path: &str,
+        timestamp: f64,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Self::spawn(path, timestamp, false)
+    }
+
+    fn spawn(
+        path: &str,
+        timestamp: f64,
+        with_audio: bool,
     ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
         let width = 640;
         let height = 360;
         let fps = 30.0;
 
-        let mut child = Command::new("ffmpeg")
+        let mut child = proc::blocking_ffmpeg(ProcClass::Preview)
             .stealth()
             .arg("-hwaccel").arg("none")   // force software decode — hardware decoders can silently fail on piped raw output
             .arg("-ss")
@@ -96,20 +290,14 @@ impl VideoPlayer {
             }
         });
 
-        // Also spawn a detatched audio player
-        // Note: -vn disables video stream entirely, -sn disables subtitle processing
-        let _audio_process = Command::new("ffplay")
-            .stealth()
-            .arg("-nodisp")
-            .arg("-autoexit")
-            .arg("-vn")        // Disable video stream (audio only)
-            .arg("-sn")        // Disable subtitle processing to prevent console spam
-            .arg("-ss")
-            .arg(format!("{:.3}", timestamp))
-            .arg(path)
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .spawn();
+        // A machine without sound still gets a (silent) preview
+        let audio = if with_audio {
+            PreviewAudio::start(path, timestamp)
+                .map_err(|e| tracing::warn!("[VideoPlayer] Playing without sound: {}", e))
+                .ok()
+        } else {
+            None
+        };
 
         Ok(Self {
             receiver: rx,
@@ -117,9 +305,16 @@ impl VideoPlayer {
             width,
             height,
             fps,
-            last_frame_time: None,
+            start: timestamp,
+            clock: FrameClock::new(fps),
             current_frame: None,
             playing: true,
+            audio,
+            path: path.to_string(),
+            with_audio,
+            seeks: SeekDebouncer::new(SEEK_SETTLE, SEEK_MAX_WAIT),
+            loop_range: None,
+            standby: None,
         })
     }
 
@@ -127,58 +322,290 @@ impl VideoPlayer {
         if let Some(mut child) = self.process.take() {
             let _ = child.kill();
         }
+        self.standby = None;
         self.playing = false;
-        // Kill ffplay instances just in case
-        let _ = Command::new("pkill").stealth().arg("ffplay").spawn();
-        #[cfg(target_os = "windows")]
-        let _ = Command::new("taskkill")
-            .stealth()
-            .arg("/F")
-            .arg("/IM")
-            .arg("ffplay.exe")
-            .spawn();
+        if let Some(mut audio) = self.audio.take() {
+            audio.stop();
+        }
     }
 
-    pub fn get_next_frame(&mut self) -> Option<(bool, &Vec<u8>)> {
-        if !self.playing {
-            return self.current_frame.as_ref().map(|f| (false, f));
+    /// Timestamp in the file of the frame on screen, or of the seek about
+    /// to replace it.
+    pub fn position(&self) -> f64 {
+        self.seeks
+            .pending()
+            .unwrap_or(self.start + self.clock.played())
+    }
+
+    /// Jump playback to `timestamp`. Calls in quick succession, as while
+    /// dragging the playhead, are coalesced: the decoders restart at the
+    /// latest target once the requests settle (see `SeekDebouncer`).
+    pub fn seek(&mut self, timestamp: f64) {
+        if timestamp.is_finite() {
+            self.seeks.request(timestamp.max(0.0), Instant::now());
         }
+    }
 
-        let now = Instant::now();
-        let frame_duration = Duration::from_secs_f64(1.0 / self.fps);
+    /// Repeat playback between `start` and `end` (seconds into the file).
+    /// Playback outside the range jumps to its start. Ranges shorter than
+    /// `MIN_LOOP_SECS` clear the loop.
+    pub fn set_loop_range(&mut self, start: f64, end: f64) {
+        let range = LoopRange::new(start, end);
+        if range == self.loop_range {
+            return;
+        }
+        self.loop_range = range;
+        self.standby = None;
+        if let Some(range) = range {
+            let position = self.position();
+            if position < range.start || range.wraps_at(position) {
+                self.seek(range.start);
+            }
+        }
+    }
 
-        // Always try to drain the receiver to prevent buffer buildup
-        let mut got_new_frame = false;
+    pub fn clear_loop_range(&mut self) {
+        self.loop_range = None;
+        self.standby = None;
+    }
 
-        // Drain all available frames (keep the latest one)
-        loop {
+    pub fn loop_range(&self) -> Option<LoopRange> {
+        self.loop_range
+    }
+
+    /// A seek is waiting for the requests to settle.
+    pub fn is_seeking(&self) -> bool {
+        self.seeks.pending().is_some()
+    }
+
+    /// Restart the decoders at a seek target that has fallen due. The old
+    /// decoder goes first so that its Preview slot is free for the new one.
+    fn apply_seek(&mut self, now: Instant) {
+        let Some(timestamp) = self.seeks.due(now) else {
+            return;
+        };
+        self.stop();
+        match Self::spawn(&self.path, timestamp, self.with_audio) {
+            Ok(fresh) => self.replace_with(fresh),
+            Err(e) => {
+                tracing::error!("[VideoPlayer] Seek to {:.3}s failed: {}", timestamp, e);
+                self.start = timestamp;
+                self.clock = FrameClock::new(self.fps);
+            }
+        }
+    }
+
+    /// Near the end of the loop, start a silent decoder at its start while
+    /// a Preview slot is free; at the end, swap it in with fresh sound. With
+    /// its frames already buffered the wrap shows no gap.
+    fn follow_loop(&mut self) {
+        let Some(range) = self.loop_range else {
+            return;
+        };
+        let ended = !self.playing && self.process.is_some();
+        match range.step(self.position(), ended, self.standby.is_some()) {
+            LoopStep::Play => return,
+            LoopStep::Preload => {
+                if preview_slot_free() {
+                    self.standby = Self::spawn(&self.path, range.start, false).ok().map(Box::new);
+                }
+                return;
+            }
+            LoopStep::Wrap => {}
+        }
+
+        let standby = self.standby.take();
+        self.stop();
+        let fresh = match standby {
+            Some(player) => Ok(*player),
+            None => Self::spawn(&self.path, range.start, false),
+        };
+        match fresh {
+            Ok(mut fresh) => {
+                if self.with_audio {
+                    fresh.audio = PreviewAudio::start(&self.path, range.start)
+                        .map_err(|e| tracing::warn!("[VideoPlayer] Looping without sound: {}", e))
+                        .ok();
+                }
+                self.replace_with(fresh);
+            }
+            Err(e) => tracing::error!("[VideoPlayer] Cannot restart the loop: {}", e),
+        }
+    }
+
+    /// Become `fresh`, a decoder started elsewhere in the same file, keeping
+    /// this player's settings and its frame on screen until the new one
+    /// decodes.
+    fn replace_with(&mut self, mut fresh: VideoPlayer) {
+        fresh.current_frame = self.current_frame.take();
+        fresh.with_audio = self.with_audio;
+        fresh.seeks = self.seeks.clone();
+        fresh.loop_range = self.loop_range;
+        *self = fresh;
+    }
+
+    /// The frame to show now and whether it changed since the last call.
+    /// Frames are taken only as fast as the audio (or, without it, the wall
+    /// clock) allows; when the UI falls behind, the late ones are skipped.
+    pub fn get_next_frame(&mut self) -> Option<(bool, &Vec<u8>)> {
+        let now = Instant::now();
+        self.apply_seek(now);
+        if !self.playing || self.seeks.pending().is_some() {
+            return self.current_frame.as_ref().map(|f| (false, f));
+        }
+
+        if let Some(elapsed) = self.audio.as_ref().and_then(PreviewAudio::clock) {
+            self.clock.sync_to(elapsed, now);
+        }
+        let mut taken = 0;
+        while taken < self.clock.due(now) {
             match self.receiver.try_recv() {
                 Ok(frame) => {
                     self.current_frame = Some(frame);
-                    got_new_frame = true;
-                }
-                Err(TryRecvError::Empty) => {
-                    break;
+                    taken += 1;
                 }
+                Err(TryRecvError::Empty) => break,
                 Err(TryRecvError::Disconnected) => {
                     self.playing = false;
                     break;
                 }
             }
         }
+        self.clock.advance(taken, now);
+        self.follow_loop();
+        self.current_frame.as_ref().map(|f| (taken > 0, f))
+    }
+}
 
-        if got_new_frame {
-            self.last_frame_time = Some(now);
-            self.current_frame.as_ref().map(|f| (true, f))
-        } else {
-            // Check if we should wait based on frame timing
-            if let Some(last) = self.last_frame_time {
-                if now.duration_since(last) < frame_duration {
-                    return self.current_frame.as_ref().map(|f| (false, f));
+/// Whether another preview decoder can start without waiting.
+fn preview_slot_free() -> bool {
+    let governor = proc::governor();
+    governor.active(ProcClass::Preview) < governor.limit(ProcClass::Preview)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Before/After Split Preview
+// Plays an edit's output with audio and a silent decoder on the source, kept
+// in lockstep through the edit plan's offset table. The source decoder is
+// re-seeked whenever the output crosses into a different kept segment.
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// How the two frames are combined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SplitLayout {
+    /// Source on the left, output on the right.
+    SideBySide,
+    /// Source left of the divider, output right of it (0.0–1.0 across).
+    Wipe(f32),
+}
+
+pub struct SplitPlayer {
+    output: VideoPlayer,
+    source: VideoPlayer,
+    source_path: String,
+    plan: EditPlan,
+    segment: Option<usize>,
+    /// Output timestamp of the most recent output frame.
+    pub position: f64,
+}
+
+impl SplitPlayer {
+    /// Start both decoders at `output_time` of the edit at `output_path`.
+    /// The edit plan must sit next to the output.
+    pub fn new(
+        output_path: &str,
+        output_time: f64,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let plan = EditPlan::load_for(Path::new(output_path))
+            .ok_or("No edit plan found next to the output")?;
+        let source_path = plan.source.to_string_lossy().to_string();
+        let source_time = plan.source_time(output_time).unwrap_or(output_time);
+        Ok(Self {
+            output: VideoPlayer::new(output_path, output_time)?,
+            source: VideoPlayer::new_silent(&source_path, source_time)?,
+            segment: plan.segment_at(output_time),
+            source_path,
+            plan,
+            position: output_time,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.output.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.output.height
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.output.playing
+    }
+
+    pub fn fps(&self) -> f64 {
+        self.output.fps
+    }
+
+    pub fn stop(&mut self) {
+        self.source.stop();
+        self.output.stop();
+    }
+
+    /// Advance both streams and return `(is_new, composed_rgb)`.
+    pub fn get_next_frame(&mut self, layout: SplitLayout) -> Option<(bool, Vec<u8>)> {
+        let (is_new, out_frame) = match self.output.get_next_frame() {
+            Some((is_new, frame)) => (is_new, frame.clone()),
+            None => return None,
+        };
+        if is_new {
+            self.position = self.output.position();
+            let segment = self.plan.segment_at(self.position);
+            if segment != self.segment {
+                // The output just cut to a new span: jump the source there.
+                self.segment = segment;
+                if let Some(t) = self.plan.source_time(self.position) {
+                    if let Ok(player) = VideoPlayer::new_silent(&self.source_path, t) {
+                        self.source = player;
+                    }
                 }
             }
-            // Return current frame even if no new frame (prevents black screen)
-            self.current_frame.as_ref().map(|f| (false, f))
+        }
+        let src_frame = match self.source.get_next_frame() {
+            Some((_, frame)) => frame.clone(),
+            None => vec![0u8; out_frame.len()],
+        };
+        let (w, h) = (self.output.width, self.output.height);
+        Some((is_new, compose_split(&src_frame, &out_frame, w, h, layout)))
+    }
+}
+
+/// Combine two same-sized RGB24 frames. Side-by-side output is `2 * width`
+/// wide; a wipe keeps the original size.
+pub fn compose_split(
+    before: &[u8],
+    after: &[u8],
+    width: usize,
+    height: usize,
+    layout: SplitLayout,
+) -> Vec<u8> {
+    let row = width * 3;
+    match layout {
+        SplitLayout::SideBySide => {
+            let mut out = Vec::with_capacity(row * 2 * height);
+            for y in 0..height {
+                out.extend_from_slice(&before[y * row..(y + 1) * row]);
+                out.extend_from_slice(&after[y * row..(y + 1) * row]);
+            }
+            out
+        }
+        SplitLayout::Wipe(at) => {
+            let split = ((at.clamp(0.0, 1.0) * width as f32) as usize) * 3;
+            let mut out = after.to_vec();
+            for y in 0..height {
+                let start = y * row;
+                out[start..start + split].copy_from_slice(&before[start..start + split]);
+            }
+            out
         }
     }
 }
@@ -188,3 +615,162 @@ impl Drop for VideoPlayer {
         self.stop();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compose_split_layouts() {
+        // 2x1 frames: before is all 10s, after is all 200s.
+        let before = vec![10u8; 6];
+        let after = vec![200u8; 6];
+        let side = compose_split(&before, &after, 2, 1, SplitLayout::SideBySide);
+        assert_eq!(side, vec![10, 10, 10, 10, 10, 10, 200, 200, 200, 200, 200, 200]);
+        let wipe = compose_split(&before, &after, 2, 1, SplitLayout::Wipe(0.5));
+        assert_eq!(wipe, vec![10, 10, 10, 200, 200, 200]);
+    }
+
+    /// Frames taken while the UI redraws every `refresh` for `secs`.
+    fn frames_played(refresh: Duration, secs: f64) -> u64 {
+        let t0 = Instant::now();
+        let end = t0 + Duration::from_secs_f64(secs);
+        let mut clock = FrameClock::new(30.0);
+        let mut now = t0;
+        while now < end {
+            clock.advance(clock.due(now), now);
+            now += refresh;
+        }
+        clock.advance(clock.due(end), end);
+        clock.shown
+    }
+
+    #[test]
+    fn playback_follows_the_wall_clock_not_the_refresh_rate() {
+        let t0 = Instant::now();
+        let mut clock = FrameClock::new(30.0);
+        assert_eq!(clock.due(t0), 1);
+        clock.advance(1, t0);
+        // Not yet time for the second frame
+        assert_eq!(clock.due(t0 + Duration::from_millis(20)), 0);
+        assert_eq!(clock.due(t0 + Duration::from_millis(34)), 1);
+        clock.advance(1, t0 + Duration::from_millis(34));
+        // A half-second stall makes the missed frames due at once
+        assert_eq!(clock.due(t0 + Duration::from_millis(534)), 15);
+        assert!((clock.played() - 1.0 / 30.0).abs() < 1e-9);
+
+        // Two seconds play 61 frames (0 s through 2 s) at any refresh rate
+        for hz in [20, 60, 144, 240] {
+            let refresh = Duration::from_secs_f64(1.0 / hz as f64);
+            assert_eq!(frames_played(refresh, 2.0), 61, "{} Hz", hz);
+        }
+    }
+
+    #[test]
+    fn frames_follow_the_audio_clock() {
+        let t0 = Instant::now();
+        let ms = |n: u64| t0 + Duration::from_millis(n);
+        let mut clock = FrameClock::new(30.0);
+        // Syncing before the first frame changes nothing
+        clock.sync_to(5.0, t0);
+        assert_eq!(clock.due(t0), 1);
+        clock.advance(1, t0);
+
+        // The device is still buffering: the first frame holds however much
+        // wall-clock time passes
+        clock.sync_to(0.0, ms(400));
+        assert_eq!(clock.due(ms(400)), 0);
+
+        // Audio has played 0.1 s: frames 0..=3 are due, whatever the wall
+        // clock says
+        clock.sync_to(0.1, ms(500));
+        assert_eq!(clock.due(ms(500)), 3);
+        clock.advance(3, ms(500));
+        assert!((clock.played() - 0.1).abs() < 1e-9);
+
+        // Audio running behind the picture holds the picture back
+        clock.sync_to(0.05, ms(600));
+        assert_eq!(clock.due(ms(600)), 0);
+
+        // When the audio ends, the wall clock carries on from its last time
+        clock.sync_to(1.0, ms(700));
+        assert_eq!(clock.due(ms(700)), 31 - 4);
+        clock.advance(27, ms(700));
+        assert_eq!(clock.due(ms(700) + Duration::from_millis(500)), 15);
+    }
+
+    #[test]
+    fn looping_wraps_the_position_from_end_to_start() {
+        assert_eq!(LoopRange::new(3.0, 3.1), None);
+        let range = LoopRange::new(12.0, 10.0).unwrap();
+        assert_eq!((range.start, range.end), (10.0, 12.0));
+
+        // Play the range twice at 30 fps, restarting the clock at each wrap
+        // the way the player swaps in the decoder for the start
+        let t0 = Instant::now();
+        let mut start = 10.0;
+        let mut clock = FrameClock::new(30.0);
+        let mut preloaded = false;
+        let (mut positions, mut preloads, mut wraps) = (Vec::new(), Vec::new(), 0);
+        for frame in 0..130u64 {
+            let now = t0 + Duration::from_secs_f64(frame as f64 / 30.0);
+            clock.advance(clock.due(now), now);
+            let position = start + clock.played();
+            positions.push(position);
+            match range.step(position, false, preloaded) {
+                LoopStep::Play => {}
+                LoopStep::Preload => {
+                    preloads.push(position);
+                    preloaded = true;
+                }
+                LoopStep::Wrap => {
+                    wraps += 1;
+                    start = range.start;
+                    clock = FrameClock::new(30.0);
+                    preloaded = false;
+                }
+            }
+        }
+        assert_eq!(wraps, 2);
+        assert!(positions.iter().all(|&p| (10.0..=12.0 + 1e-9).contains(&p)));
+        // The frame after the end is the start again
+        let end = positions.iter().position(|&p| range.wraps_at(p)).unwrap();
+        assert!((positions[end + 1] - 10.0).abs() < 1e-9);
+        // The start's decoder was readied once per pass, a second ahead
+        assert_eq!(preloads.len(), 2);
+        assert!(preloads.iter().all(|&p| (p - 11.0).abs() < 1e-9));
+
+        // A file that runs out inside the range wraps too
+        assert_eq!(range.step(10.5, true, false), LoopStep::Wrap);
+    }
+
+    #[test]
+    fn rapid_seeks_coalesce_to_the_latest_target() {
+        let t0 = Instant::now();
+        let ms = |n: u64| t0 + Duration::from_millis(n);
+        let mut seeks = SeekDebouncer::new(Duration::from_millis(100), Duration::from_millis(300));
+        assert_eq!(seeks.due(t0), None);
+
+        // A drag sends a burst of targets; none applies while it continues
+        seeks.request(5.0, ms(0));
+        seeks.request(6.0, ms(40));
+        seeks.request(7.5, ms(80));
+        assert_eq!(seeks.due(ms(150)), None);
+        assert_eq!(seeks.pending(), Some(7.5));
+
+        // Once it rests, only the last target is applied, and only once
+        assert_eq!(seeks.due(ms(180)), Some(7.5));
+        assert_eq!(seeks.due(ms(500)), None);
+        assert_eq!(seeks.pending(), None);
+
+        // A drag that never rests still seeks every `max_wait`
+        let mut applied = Vec::new();
+        for step in 0..20u64 {
+            let now = ms(1000 + step * 50);
+            seeks.request(step as f64, now);
+            applied.extend(seeks.due(now));
+        }
+        assert_eq!(applied, vec![6.0, 13.0]);
+        assert_eq!(seeks.due(ms(1950) + Duration::from_millis(100)), Some(19.0));
+    }
+}
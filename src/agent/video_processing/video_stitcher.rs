@@ -6,9 +6,9 @@
 // the resulting file has zero quality loss and near-zero CPU cost.
 
 use crate::agent::engines::process_utils::CommandExt;
+use crate::agent::engines::proc::{self, ProcClass};
 use std::fs;
 use std::path::{Path, PathBuf};
-use tokio::process::Command;
 use tracing::{error, info};
 
 pub struct VideoStitcher;
@@ -48,7 +48,7 @@ impl VideoStitcher {
             manifest_path
         );
 
-        let status = Command::new("ffmpeg")
+        let status = proc::ffmpeg(ProcClass::Render)
             .stealth()
             .args(["-y", "-f", "concat", "-safe", "0", "-i"])
             .arg(&manifest_path)
@@ -143,7 +143,7 @@ struct LearnedVideoCache(HashMap<String, LearnedEntry>);
 
 impl LearnedVideoCache {
     fn path() -> PathBuf {
-        let suffix = std::env::var("SYNOID_INSTANCE_ID").unwrap_or_default();
+        let suffix = crate::config::current().instance_id.unwrap_or_default();
         let dir = PathBuf::from(format!("cortex_cache{}", suffix));
         let _ = std::fs::create_dir_all(&dir);
         dir.join("learned_videos.json")
@@ -0,0 +1,311 @@
+// SYNOID Live Collaboration — per-session event fan-out for editor sessions
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Every editor session gets a broadcast channel. The editor API publishes
+// asset additions, render progress, chat messages and project saves into it;
+// WebSocket clients (and the in-process GUI) subscribe to stay in sync
+// without polling. Render progress is also mirrored onto the core event bus
+// as editor job events, so process-wide observers see editor work too.
+
+use crate::events::{EventBus, JobOrigin, SynoidEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// Maximum simultaneous WebSocket peers per editor session.
+pub const MAX_CONNECTIONS_PER_SESSION: usize = 8;
+
+/// Events buffered per session before slow subscribers start lagging.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// An event fanned out to every client connected to an editor session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CollabEvent {
+    /// Someone connected or disconnected — carries the full peer list.
+    Presence { peers: Vec<String> },
+    /// A new asset was uploaded into the session.
+    AssetAdded { asset_id: String, filename: String },
+    /// A reviewer kept/dropped/retimed a scene.
+    SceneOverride {
+        from: String,
+        scene_index: usize,
+        action: String,
+    },
+    /// Render or auto-edit progress for the session's job.
+    RenderProgress { progress: f32, status: String },
+    /// Chat message (from a peer or from the AI assistant).
+    Chat { from: String, text: String },
+    /// The project file was overwritten (last writer wins).
+    ProjectSaved { revision: u64, by: String },
+}
+
+/// Messages a WebSocket client may send; the server stamps `from` and relays.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Chat { text: String },
+    SceneOverride { scene_index: usize, action: String },
+}
+
+impl ClientMessage {
+    pub fn into_event(self, from: &str) -> CollabEvent {
+        match self {
+            ClientMessage::Chat { text } => CollabEvent::Chat {
+                from: from.to_string(),
+                text,
+            },
+            ClientMessage::SceneOverride {
+                scene_index,
+                action,
+            } => CollabEvent::SceneOverride {
+                from: from.to_string(),
+                scene_index,
+                action,
+            },
+        }
+    }
+}
+
+struct SessionChannel {
+    tx: broadcast::Sender<CollabEvent>,
+    /// connection id → display name
+    peers: HashMap<String, String>,
+}
+
+impl SessionChannel {
+    fn new() -> Self {
+        let (tx, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            tx,
+            peers: HashMap::new(),
+        }
+    }
+
+    fn peer_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.peers.values().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+/// A peer's seat in a session, returned by `CollabHub::join`.
+pub struct PeerHandle {
+    pub conn_id: String,
+    pub name: String,
+    pub rx: broadcast::Receiver<CollabEvent>,
+}
+
+/// Registry of live session channels, shared by the editor API and the GUI.
+#[derive(Default)]
+pub struct CollabHub {
+    sessions: Mutex<HashMap<String, SessionChannel>>,
+    events: Option<EventBus>,
+    /// Sessions with a job between its first progress event and done/error.
+    rendering: Mutex<HashSet<String>>,
+}
+
+impl CollabHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A hub that also reports render progress to `bus`.
+    pub fn with_events(bus: EventBus) -> Self {
+        Self {
+            events: Some(bus),
+            ..Self::default()
+        }
+    }
+
+    /// Broadcast an event to everyone watching `session_id`.
+    /// A session nobody is watching silently drops the event.
+    pub fn publish(&self, session_id: &str, event: CollabEvent) {
+        if let CollabEvent::RenderProgress { progress, status } = &event {
+            self.mirror_render(session_id, *progress, status);
+        }
+        let sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(channel) = sessions.get(session_id) {
+            let _ = channel.tx.send(event);
+        }
+    }
+
+    /// The first progress event of a job starts it on the bus; `done` or
+    /// `error` finishes it.
+    fn mirror_render(&self, session_id: &str, progress: f32, status: &str) {
+        let Some(bus) = &self.events else {
+            return;
+        };
+        let job = session_id.to_string();
+        let mut rendering = self.rendering.lock().unwrap_or_else(|e| e.into_inner());
+        if matches!(status, "done" | "error") {
+            rendering.remove(session_id);
+            bus.publish(SynoidEvent::JobDone {
+                origin: JobOrigin::Editor,
+                job,
+                ok: status == "done",
+                detail: status.to_string(),
+            });
+            return;
+        }
+        if rendering.insert(job.clone()) {
+            bus.publish(SynoidEvent::JobStarted {
+                origin: JobOrigin::Editor,
+                job: job.clone(),
+                label: status.to_string(),
+            });
+        }
+        bus.publish(SynoidEvent::JobProgress {
+            origin: JobOrigin::Editor,
+            job,
+            progress,
+            status: status.to_string(),
+        });
+    }
+
+    /// Passive subscription (no presence entry) — used by the in-process GUI.
+    pub fn subscribe(&self, session_id: &str) -> broadcast::Receiver<CollabEvent> {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        sessions
+            .entry(session_id.to_string())
+            .or_insert_with(SessionChannel::new)
+            .tx
+            .subscribe()
+    }
+
+    /// Register a connected peer. Fails when the session is already at
+    /// `MAX_CONNECTIONS_PER_SESSION`.
+    pub fn join(&self, session_id: &str, name: Option<&str>) -> Result<PeerHandle, String> {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        let channel = sessions
+            .entry(session_id.to_string())
+            .or_insert_with(SessionChannel::new);
+
+        if channel.peers.len() >= MAX_CONNECTIONS_PER_SESSION {
+            return Err(format!(
+                "session {} already has {} connected peers",
+                session_id, MAX_CONNECTIONS_PER_SESSION
+            ));
+        }
+
+        let conn_id = Uuid::new_v4().to_string();
+        let name = match name.map(str::trim).filter(|n| !n.is_empty()) {
+            Some(n) => n.chars().take(32).collect(),
+            None => format!("guest-{}", &conn_id[..4]),
+        };
+        channel.peers.insert(conn_id.clone(), name.clone());
+
+        let rx = channel.tx.subscribe();
+        let _ = channel.tx.send(CollabEvent::Presence {
+            peers: channel.peer_names(),
+        });
+
+        Ok(PeerHandle { conn_id, name, rx })
+    }
+
+    /// Remove a peer, announce the new presence list, and drop the channel
+    /// once nobody (peer or passive subscriber) is left on it.
+    pub fn leave(&self, session_id: &str, conn_id: &str) {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(channel) = sessions.get_mut(session_id) else {
+            return;
+        };
+        if channel.peers.remove(conn_id).is_some() {
+            let _ = channel.tx.send(CollabEvent::Presence {
+                peers: channel.peer_names(),
+            });
+        }
+        if channel.peers.is_empty() && channel.tx.receiver_count() == 0 {
+            sessions.remove(session_id);
+        }
+    }
+
+    /// Names of everyone currently connected to `session_id`.
+    pub fn peers(&self, session_id: &str) -> Vec<String> {
+        let sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        sessions
+            .get(session_id)
+            .map(|c| c.peer_names())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_is_capped_per_session() {
+        let hub = CollabHub::new();
+        let mut handles = Vec::new();
+        for i in 0..MAX_CONNECTIONS_PER_SESSION {
+            handles.push(hub.join("s1", Some(&format!("peer{}", i))).unwrap());
+        }
+        assert!(hub.join("s1", Some("late")).is_err());
+        // Other sessions are unaffected.
+        assert!(hub.join("s2", None).is_ok());
+    }
+
+    #[test]
+    fn leave_updates_presence_and_cleans_up() {
+        let hub = CollabHub::new();
+        let alice = hub.join("s1", Some("alice")).unwrap();
+        let bob = hub.join("s1", Some("bob")).unwrap();
+        assert_eq!(hub.peers("s1"), vec!["alice", "bob"]);
+
+        let mut rx = hub.subscribe("s1");
+        hub.leave("s1", &bob.conn_id);
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            CollabEvent::Presence {
+                peers: vec!["alice".to_string()]
+            }
+        );
+
+        drop(rx);
+        drop(bob);
+        hub.leave("s1", &alice.conn_id);
+        drop(alice);
+        assert!(hub.peers("s1").is_empty());
+    }
+
+    #[test]
+    fn client_messages_are_stamped_with_sender() {
+        let msg: ClientMessage =
+            serde_json::from_str(r#"{"type":"scene_override","scene_index":3,"action":"drop"}"#)
+                .unwrap();
+        assert_eq!(
+            msg.into_event("alice"),
+            CollabEvent::SceneOverride {
+                from: "alice".to_string(),
+                scene_index: 3,
+                action: "drop".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn render_progress_is_mirrored_as_editor_job_events() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+        let hub = CollabHub::with_events(bus);
+        let progress = |progress, status: &str| CollabEvent::RenderProgress {
+            progress,
+            status: status.to_string(),
+        };
+        // Nobody is watching the session, the bus still hears about it
+        hub.publish("s1", progress(0.0, "running"));
+        hub.publish("s1", progress(0.5, "running"));
+        hub.publish("s1", progress(1.0, "done"));
+
+        let kinds: Vec<String> = std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|e| serde_json::to_value(e).unwrap()["type"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            kinds,
+            ["job_started", "job_progress", "job_progress", "job_done"]
+        );
+    }
+}
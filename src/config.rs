@@ -0,0 +1,740 @@
+// SYNOID Configuration — one place for the core service settings
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Every setting below resolves through four layers, later ones winning:
+// built-in defaults → JSON config file (`--config <path>`, SYNOID_CONFIG, or
+// `synoid.json` in the working directory, which `setup` writes) → SYNOID_*
+// environment variables → command-line flags. The file and flag
+// layers are fixed at start-up by `init`; the environment is read on every
+// `current()` call, so `.env` files and per-instance variables set while
+// running are still honoured.
+//
+// Feature knobs live here too, so code reads `current()` instead of its own
+// SYNOID_* variable. The instance id comes from SYNOID_INSTANCE_ID, or from
+// `--port` for the commands that take one.
+
+use crate::agent::engines::proc::ProcClass;
+use crate::agent::post_render::{self, PostRenderAction};
+use crate::agent::specialized::smart_editor::chapters::ChapterMode;
+use crate::agent::specialized::smart_editor::external_scorer;
+use crate::agent::specialized::smart_editor::intent_language::IntentLanguage;
+use crate::logging::LogFormat;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+pub const DEFAULT_API_URL: &str = "http://localhost:11434";
+pub const DEFAULT_MODEL: &str = "gemma4:26b";
+pub const DEFAULT_VISION_MODEL: &str = "llava:latest";
+/// Developer key accepted by the dashboard when SYNOID_API_KEY is unset.
+pub const DEFAULT_API_KEY: &str = "synoid_secret_v1";
+pub const DEFAULT_LOG_DIR: &str = "logs";
+pub const DEFAULT_WHISPER_MODEL: &str = "large-v3";
+/// Config file read when neither `--config` nor SYNOID_CONFIG names one.
+pub const DEFAULT_CONFIG_FILE: &str = "synoid.json";
+pub const DEFAULT_SHORT_INPUT_SECS: f64 = 15.0;
+pub const DEFAULT_MAX_UPLOAD_MB: u64 = 2048;
+pub const DEFAULT_PROJECT_HISTORY: usize = 20;
+
+/// The resolved settings.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SynoidConfig {
+    /// Ollama endpoint. `api_url` / SYNOID_API_URL; OLLAMA_API_URL is still
+    /// read when SYNOID_API_URL is unset. Default: http://localhost:11434
+    pub api_url: String,
+    /// Reasoning model for intent parsing, scoring and agents.
+    /// `model` / SYNOID_MODEL / `--model`. Default: gemma4:26b
+    pub model: String,
+    /// Multimodal model for frame analysis.
+    /// `vision_model` / SYNOID_VISION_MODEL. Default: llava:latest
+    pub vision_model: String,
+    /// Dashboard API key; `None` means the developer key is in use.
+    /// `api_key` / SYNOID_API_KEY.
+    pub api_key: Option<String>,
+    /// Run the Cyberdefense Sentinel. `enable_sentinel` /
+    /// SYNOID_ENABLE_SENTINEL (`true` or `1`). Default: off
+    pub enable_sentinel: bool,
+    /// Apply the WSL GUI workarounds (X11 backend, Glow renderer).
+    /// `wsl` / SYNOID_WSL. Default: detected from WSL_DISTRO_NAME and
+    /// /proc/version.
+    pub wsl: bool,
+    /// Directory for the daily-rotated log files; empty turns file logging
+    /// off. `log_dir` / SYNOID_LOG_DIR. Default: logs
+    pub log_dir: String,
+    /// `text` or `json` (one object per event, for log aggregators).
+    /// `log_format` / SYNOID_LOG_FORMAT / `--log-format`. Default: text
+    pub log_format: LogFormat,
+    /// Where the GUI's file dialogs start. `video_dir` / SYNOID_VIDEO_DIR.
+    /// Default: unset (the OS default)
+    pub video_dir: Option<String>,
+    /// Model and download cache. `cache_dir` / SYNOID_CACHE_DIR.
+    /// Default: the OS cache directory + `synoid`
+    pub cache_dir: String,
+    /// Where Whisper models are downloaded and looked for.
+    /// `models_dir` / SYNOID_MODELS_DIR. Default: `<cache_dir>/models`
+    pub models_dir: Option<String>,
+    /// Local Whisper model for transcription: a name (`ggml-<name>.bin` in
+    /// the models directory) or the path of a custom model file.
+    /// `whisper_model` / SYNOID_WHISPER_MODEL. Default: large-v3
+    pub whisper_model: String,
+    /// Video put before every smart edit. `intro` / SYNOID_INTRO /
+    /// `--intro`. Default: none
+    pub intro: Option<String>,
+    /// Video or image card put after every smart edit. `outro` /
+    /// SYNOID_OUTRO / `--outro`. Default: none
+    pub outro: Option<String>,
+    /// Seconds over which the intro's sound fades out as the first scene's
+    /// fades in. `intro_crossfade` / SYNOID_INTRO_CROSSFADE /
+    /// `--intro-crossfade`. Default: 0 (a hard cut)
+    pub intro_crossfade: f64,
+    /// What smart edits do with the source's chapter markers: `preserve`
+    /// remaps them onto the edit, `off` drops them. `chapters` /
+    /// SYNOID_CHAPTERS. Default: preserve
+    pub chapters: ChapterMode,
+    /// Command (scene JSON on stdin) or http(s) URL (JSON POST) that rescores
+    /// smart-edit scenes. `external_scorer` / SYNOID_EXTERNAL_SCORER /
+    /// `--external-scorer`. Default: none
+    pub external_scorer: Option<String>,
+    /// Seconds to wait for the external scorer before keeping the built-in
+    /// scores. `external_scorer_timeout` / SYNOID_EXTERNAL_SCORER_TIMEOUT.
+    /// Default: 30
+    pub external_scorer_timeout: f64,
+    /// Directory of logo templates (png/jpg); spans of a smart edit where one
+    /// is on screen are cut. `sponsor_logos` / SYNOID_SPONSOR_LOGOS /
+    /// `--sponsor-logos`. Default: none
+    pub sponsor_logos: Option<String>,
+    /// Render smart edits as a small, fast draft (480p, ultrafast) whose cuts
+    /// the next final render reuses. `draft` / SYNOID_DRAFT / `--draft`.
+    /// Default: off
+    pub draft: bool,
+    /// In a draft, play long uncut stretches at 2x. `draft_fast` /
+    /// SYNOID_DRAFT_FAST / `--draft-fast`. Default: off
+    pub draft_fast: bool,
+    /// Edit inputs SYNOID stamped as its own output like any other input.
+    /// `force_reprocess` / SYNOID_FORCE_REPROCESS / `--force-reprocess`.
+    /// Default: off
+    pub force_reprocess: bool,
+    /// Make smart edits reproducible: the heuristic intent parser, the
+    /// default strategy and a seed-picked transition, replaying the cuts an
+    /// earlier run with the same seed recorded. `seed` / SYNOID_SEED /
+    /// `--seed`. Default: none
+    pub seed: Option<u64>,
+    /// Root for outputs written without `--output`, organized as
+    /// `<yyyy-mm>/<source stem>/<op>_<n>.<ext>`; a leading `~` is the home
+    /// directory. `outputs_dir` / SYNOID_OUTPUTS_DIR (SYNOID_OUTPUT_DIR is
+    /// still read when that is unset); empty, or `--here`, writes beside the
+    /// input. Default: none
+    pub outputs_dir: Option<String>,
+    /// What to do once a render is written: `reveal`, `copy-path`,
+    /// `copy-summary`. `after_render` / SYNOID_AFTER_RENDER (comma-separated)
+    /// / `--then`. Default: nothing
+    pub after_render: Vec<PostRenderAction>,
+    /// Language of the GUI's labels (`en`, `es`, `de`). `locale` /
+    /// SYNOID_LOCALE. Default: from LANG, else English
+    pub locale: Option<String>,
+    /// Language the heuristic intent parser reads (`en`, `es`, `de`).
+    /// `intent_language` / SYNOID_INTENT_LANGUAGE. Default: detected per
+    /// intent
+    pub intent_language: Option<IntentLanguage>,
+    /// Suffix that keeps this instance's state files apart from other
+    /// instances'. SYNOID_INSTANCE_ID; `gui`, `autonomous` and `gepa` derive
+    /// `_<port>` from a `--port` other than 3000. Default: none
+    pub instance_id: Option<String>,
+    /// Download missing Ollama models without asking. `auto_pull` /
+    /// SYNOID_AUTO_PULL. Default: off
+    pub auto_pull: bool,
+    /// Have the Brain reword edit explanations. `explain_polish` /
+    /// SYNOID_EXPLAIN_POLISH. Default: off
+    pub explain_polish: bool,
+    /// Inputs shorter than this many seconds skip scene analysis; 0 turns
+    /// the cutoff off. `short_input_secs` / SYNOID_SHORT_INPUT_SECS.
+    /// Default: 15
+    pub short_input_secs: f64,
+    /// Largest editor upload, in MB. `max_upload_mb` / SYNOID_MAX_UPLOAD_MB.
+    /// Default: 2048
+    pub max_upload_mb: u64,
+    /// Project snapshots kept per editor session. `project_history` /
+    /// SYNOID_PROJECT_HISTORY. Default: 20
+    pub project_history: usize,
+    /// Concurrent GPU encodes. `gpu_slots` / SYNOID_GPU_SLOTS. Default: one
+    /// per NVIDIA GPU
+    pub gpu_slots: Option<usize>,
+    /// Concurrent external processes per class (`probe`, `thumbnail`,
+    /// `preview`, `render`, `download`, `scorer`). `proc_limits` /
+    /// SYNOID_PROC_LIMIT_<CLASS>. Default: sized from the CPU count
+    pub proc_limits: BTreeMap<String, usize>,
+    /// Names of outputs written without `--output`; see `output_naming`.
+    /// `output_template` / SYNOID_OUTPUT_TEMPLATE. Default:
+    /// `{stem}_{op}.{ext}`
+    pub output_template: Option<String>,
+    /// Autonomous learner downloads per day. `learner_max_downloads` /
+    /// SYNOID_LEARNER_MAX_DOWNLOADS / `--max-downloads-per-day`.
+    /// Default: unlimited
+    pub learner_max_downloads: Option<u32>,
+    /// Autonomous learner processing minutes per day.
+    /// `learner_max_process_minutes` / SYNOID_LEARNER_MAX_PROCESS_MINUTES /
+    /// `--max-process-minutes-per-day`. Default: unlimited
+    pub learner_max_process_minutes: Option<f64>,
+    /// Hold each learner cycle until its plan is approved.
+    /// `learner_supervised` / SYNOID_LEARNER_SUPERVISED / `--supervised`.
+    /// Default: off
+    pub learner_supervised: bool,
+    /// Invidious instances for YouTube search. `invidious_urls` /
+    /// SYNOID_INVIDIOUS_URLS (comma-separated). Default: the built-in list
+    pub invidious_urls: Vec<String>,
+    /// Proxy for downloads, e.g. `socks5://127.0.0.1:1080`. `proxy` /
+    /// SYNOID_PROXY / `--proxy`. Default: none
+    pub proxy: Option<String>,
+    /// Browser whose cookies yt-dlp uses. `browser` / SYNOID_BROWSER.
+    /// Default: the first one found
+    pub browser: Option<String>,
+    /// Custom ONNX model for background removal. `matting_model` /
+    /// SYNOID_MATTING_MODEL. Default: rembg's u2net_human_seg
+    pub matting_model: Option<String>,
+    /// Log the GUI's frame rate. `gui_frame_counter` /
+    /// SYNOID_GUI_FRAME_COUNTER. Default: off
+    pub gui_frame_counter: bool,
+}
+
+/// One layer of settings; unset fields fall through to the layer below.
+/// This is also the config file format.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigLayer {
+    pub api_url: Option<String>,
+    pub model: Option<String>,
+    pub vision_model: Option<String>,
+    pub api_key: Option<String>,
+    pub enable_sentinel: Option<bool>,
+    pub wsl: Option<bool>,
+    pub log_dir: Option<String>,
+    pub log_format: Option<LogFormat>,
+    pub video_dir: Option<String>,
+    pub cache_dir: Option<String>,
+    pub models_dir: Option<String>,
+    pub whisper_model: Option<String>,
+    pub intro: Option<String>,
+    pub outro: Option<String>,
+    pub intro_crossfade: Option<f64>,
+    pub chapters: Option<ChapterMode>,
+    pub external_scorer: Option<String>,
+    pub external_scorer_timeout: Option<f64>,
+    pub sponsor_logos: Option<String>,
+    pub draft: Option<bool>,
+    pub draft_fast: Option<bool>,
+    pub force_reprocess: Option<bool>,
+    pub seed: Option<u64>,
+    pub outputs_dir: Option<String>,
+    pub after_render: Option<Vec<PostRenderAction>>,
+    pub locale: Option<String>,
+    pub intent_language: Option<IntentLanguage>,
+    pub instance_id: Option<String>,
+    pub auto_pull: Option<bool>,
+    pub explain_polish: Option<bool>,
+    pub short_input_secs: Option<f64>,
+    pub max_upload_mb: Option<u64>,
+    pub project_history: Option<usize>,
+    pub gpu_slots: Option<usize>,
+    pub proc_limits: Option<BTreeMap<String, usize>>,
+    pub output_template: Option<String>,
+    pub learner_max_downloads: Option<u32>,
+    pub learner_max_process_minutes: Option<f64>,
+    pub learner_supervised: Option<bool>,
+    pub invidious_urls: Option<Vec<String>>,
+    pub proxy: Option<String>,
+    pub browser: Option<String>,
+    pub matting_model: Option<String>,
+    pub gui_frame_counter: Option<bool>,
+}
+
+impl ConfigLayer {
+    /// Read a JSON config file.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let data = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read config {:?}: {}", path, e))?;
+        serde_json::from_str(&data).map_err(|e| format!("Invalid config {:?}: {}", path, e).into())
+    }
+
+    /// Write the set fields as a JSON config file.
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(fields) = value.as_object_mut() {
+            fields.retain(|_, v| !v.is_null());
+        }
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&value)? + "\n")
+            .map_err(|e| format!("Cannot write config {:?}: {}", path, e).into())
+    }
+
+    /// The SYNOID_* variables, looked up through `var` so tests need not
+    /// touch the process environment.
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+        let flag = |name: &str| {
+            var(name).map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+        };
+        fn number<T: std::str::FromStr>(value: Option<String>) -> Option<T> {
+            value?.trim().parse().ok()
+        }
+        let proc_limits: BTreeMap<String, usize> = ProcClass::ALL
+            .iter()
+            .filter_map(|class| {
+                let limit = number(var(&format!("SYNOID_PROC_LIMIT_{}", class.label().to_uppercase())))?;
+                Some((class.label().to_string(), limit))
+            })
+            .collect();
+        Self {
+            api_url: var("SYNOID_API_URL").or_else(|| var("OLLAMA_API_URL")),
+            model: var("SYNOID_MODEL"),
+            vision_model: var("SYNOID_VISION_MODEL"),
+            api_key: var("SYNOID_API_KEY"),
+            enable_sentinel: flag("SYNOID_ENABLE_SENTINEL"),
+            wsl: flag("SYNOID_WSL"),
+            log_dir: var("SYNOID_LOG_DIR"),
+            // An unknown value falls back to text rather than aborting start-up
+            log_format: var("SYNOID_LOG_FORMAT").and_then(|v| v.parse().ok()),
+            video_dir: var("SYNOID_VIDEO_DIR"),
+            cache_dir: var("SYNOID_CACHE_DIR"),
+            models_dir: var("SYNOID_MODELS_DIR"),
+            whisper_model: var("SYNOID_WHISPER_MODEL"),
+            intro: var("SYNOID_INTRO"),
+            outro: var("SYNOID_OUTRO"),
+            intro_crossfade: var("SYNOID_INTRO_CROSSFADE").and_then(|v| v.parse().ok()),
+            chapters: var("SYNOID_CHAPTERS").and_then(|v| v.parse().ok()),
+            external_scorer: var("SYNOID_EXTERNAL_SCORER"),
+            external_scorer_timeout: var("SYNOID_EXTERNAL_SCORER_TIMEOUT")
+                .and_then(|v| v.parse().ok()),
+            sponsor_logos: var("SYNOID_SPONSOR_LOGOS"),
+            draft: flag("SYNOID_DRAFT"),
+            draft_fast: flag("SYNOID_DRAFT_FAST"),
+            force_reprocess: flag("SYNOID_FORCE_REPROCESS"),
+            seed: var("SYNOID_SEED").and_then(|v| v.trim().parse().ok()),
+            outputs_dir: var("SYNOID_OUTPUTS_DIR").or_else(|| var("SYNOID_OUTPUT_DIR")),
+            after_render: var("SYNOID_AFTER_RENDER")
+                .and_then(|v| post_render::parse_list(&v).ok()),
+            locale: var("SYNOID_LOCALE"),
+            intent_language: var("SYNOID_INTENT_LANGUAGE").and_then(|v| v.parse().ok()),
+            instance_id: var("SYNOID_INSTANCE_ID"),
+            auto_pull: flag("SYNOID_AUTO_PULL"),
+            explain_polish: flag("SYNOID_EXPLAIN_POLISH"),
+            short_input_secs: number(var("SYNOID_SHORT_INPUT_SECS")),
+            max_upload_mb: number(var("SYNOID_MAX_UPLOAD_MB")),
+            project_history: number(var("SYNOID_PROJECT_HISTORY")),
+            gpu_slots: number(var("SYNOID_GPU_SLOTS")),
+            proc_limits: (!proc_limits.is_empty()).then_some(proc_limits),
+            output_template: var("SYNOID_OUTPUT_TEMPLATE"),
+            learner_max_downloads: number(var("SYNOID_LEARNER_MAX_DOWNLOADS")),
+            learner_max_process_minutes: number(var("SYNOID_LEARNER_MAX_PROCESS_MINUTES")),
+            learner_supervised: flag("SYNOID_LEARNER_SUPERVISED"),
+            invidious_urls: var("SYNOID_INVIDIOUS_URLS")
+                .map(|v| v.split(',').map(|url| url.trim().to_string()).collect()),
+            proxy: var("SYNOID_PROXY"),
+            browser: var("SYNOID_BROWSER"),
+            matting_model: var("SYNOID_MATTING_MODEL"),
+            gui_frame_counter: flag("SYNOID_GUI_FRAME_COUNTER"),
+        }
+    }
+
+    /// `over`'s fields where set, else this layer's.
+    pub fn merge(self, over: ConfigLayer) -> Self {
+        Self {
+            api_url: over.api_url.or(self.api_url),
+            model: over.model.or(self.model),
+            vision_model: over.vision_model.or(self.vision_model),
+            api_key: over.api_key.or(self.api_key),
+            enable_sentinel: over.enable_sentinel.or(self.enable_sentinel),
+            wsl: over.wsl.or(self.wsl),
+            log_dir: over.log_dir.or(self.log_dir),
+            log_format: over.log_format.or(self.log_format),
+            video_dir: over.video_dir.or(self.video_dir),
+            cache_dir: over.cache_dir.or(self.cache_dir),
+            models_dir: over.models_dir.or(self.models_dir),
+            whisper_model: over.whisper_model.or(self.whisper_model),
+            intro: over.intro.or(self.intro),
+            outro: over.outro.or(self.outro),
+            intro_crossfade: over.intro_crossfade.or(self.intro_crossfade),
+            chapters: over.chapters.or(self.chapters),
+            external_scorer: over.external_scorer.or(self.external_scorer),
+            external_scorer_timeout: over
+                .external_scorer_timeout
+                .or(self.external_scorer_timeout),
+            sponsor_logos: over.sponsor_logos.or(self.sponsor_logos),
+            draft: over.draft.or(self.draft),
+            draft_fast: over.draft_fast.or(self.draft_fast),
+            force_reprocess: over.force_reprocess.or(self.force_reprocess),
+            seed: over.seed.or(self.seed),
+            outputs_dir: over.outputs_dir.or(self.outputs_dir),
+            after_render: over.after_render.or(self.after_render),
+            locale: over.locale.or(self.locale),
+            intent_language: over.intent_language.or(self.intent_language),
+            instance_id: over.instance_id.or(self.instance_id),
+            auto_pull: over.auto_pull.or(self.auto_pull),
+            explain_polish: over.explain_polish.or(self.explain_polish),
+            short_input_secs: over.short_input_secs.or(self.short_input_secs),
+            max_upload_mb: over.max_upload_mb.or(self.max_upload_mb),
+            project_history: over.project_history.or(self.project_history),
+            gpu_slots: over.gpu_slots.or(self.gpu_slots),
+            // Limits merge class by class
+            proc_limits: match (self.proc_limits, over.proc_limits) {
+                (Some(mut under), Some(over)) => {
+                    under.extend(over);
+                    Some(under)
+                }
+                (under, over) => over.or(under),
+            },
+            output_template: over.output_template.or(self.output_template),
+            learner_max_downloads: over.learner_max_downloads.or(self.learner_max_downloads),
+            learner_max_process_minutes: over
+                .learner_max_process_minutes
+                .or(self.learner_max_process_minutes),
+            learner_supervised: over.learner_supervised.or(self.learner_supervised),
+            invidious_urls: over.invidious_urls.or(self.invidious_urls),
+            proxy: over.proxy.or(self.proxy),
+            browser: over.browser.or(self.browser),
+            matting_model: over.matting_model.or(self.matting_model),
+            gui_frame_counter: over.gui_frame_counter.or(self.gui_frame_counter),
+        }
+    }
+}
+
+impl SynoidConfig {
+    /// Defaults → file → env → CLI.
+    pub fn resolve(file: &ConfigLayer, env: ConfigLayer, cli: &ConfigLayer) -> Self {
+        let layer = file.clone().merge(env).merge(cli.clone());
+        Self {
+            api_url: layer.api_url.unwrap_or_else(|| DEFAULT_API_URL.to_string()),
+            model: layer.model.unwrap_or_else(|| DEFAULT_MODEL.to_string()),
+            vision_model: layer
+                .vision_model
+                .unwrap_or_else(|| DEFAULT_VISION_MODEL.to_string()),
+            api_key: layer.api_key,
+            enable_sentinel: layer.enable_sentinel.unwrap_or(false),
+            wsl: layer.wsl.unwrap_or_else(detect_wsl),
+            log_dir: layer.log_dir.unwrap_or_else(|| DEFAULT_LOG_DIR.to_string()),
+            log_format: layer.log_format.unwrap_or_default(),
+            video_dir: layer.video_dir,
+            cache_dir: layer.cache_dir.unwrap_or_else(default_cache_dir),
+            models_dir: layer.models_dir.filter(|d| !d.trim().is_empty()),
+            whisper_model: layer
+                .whisper_model
+                .unwrap_or_else(|| DEFAULT_WHISPER_MODEL.to_string()),
+            intro: layer.intro,
+            outro: layer.outro,
+            intro_crossfade: layer.intro_crossfade.unwrap_or(0.0),
+            chapters: layer.chapters.unwrap_or_default(),
+            external_scorer: layer.external_scorer,
+            external_scorer_timeout: layer
+                .external_scorer_timeout
+                .unwrap_or(external_scorer::DEFAULT_TIMEOUT_SECS),
+            sponsor_logos: layer.sponsor_logos,
+            draft: layer.draft.unwrap_or(false),
+            draft_fast: layer.draft_fast.unwrap_or(false),
+            force_reprocess: layer.force_reprocess.unwrap_or(false),
+            seed: layer.seed,
+            // An empty value in a higher layer switches a lower one off
+            outputs_dir: non_empty(layer.outputs_dir).map(|d| expand_home(&d)),
+            after_render: layer.after_render.unwrap_or_default(),
+            locale: layer.locale,
+            intent_language: layer.intent_language,
+            instance_id: layer.instance_id.filter(|id| !id.is_empty()),
+            auto_pull: layer.auto_pull.unwrap_or(false),
+            explain_polish: layer.explain_polish.unwrap_or(false),
+            short_input_secs: layer
+                .short_input_secs
+                .filter(|&secs| secs >= 0.0)
+                .unwrap_or(DEFAULT_SHORT_INPUT_SECS),
+            max_upload_mb: layer
+                .max_upload_mb
+                .filter(|&mb| mb > 0)
+                .unwrap_or(DEFAULT_MAX_UPLOAD_MB),
+            project_history: layer
+                .project_history
+                .filter(|&n| n > 0)
+                .unwrap_or(DEFAULT_PROJECT_HISTORY),
+            gpu_slots: layer.gpu_slots.filter(|&n| n > 0),
+            proc_limits: layer
+                .proc_limits
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|&(_, n)| n > 0)
+                .collect(),
+            output_template: non_empty(layer.output_template),
+            learner_max_downloads: layer.learner_max_downloads,
+            learner_max_process_minutes: layer.learner_max_process_minutes,
+            learner_supervised: layer.learner_supervised.unwrap_or(false),
+            invidious_urls: layer
+                .invidious_urls
+                .unwrap_or_default()
+                .into_iter()
+                .map(|url| url.trim().trim_end_matches('/').to_string())
+                .filter(|url| !url.is_empty())
+                .collect(),
+            proxy: non_empty(layer.proxy),
+            browser: non_empty(layer.browser),
+            matting_model: non_empty(layer.matting_model),
+            gui_frame_counter: layer.gui_frame_counter.unwrap_or(false),
+        }
+    }
+
+    /// The dashboard key to check requests against.
+    pub fn api_key_or_default(&self) -> &str {
+        self.api_key.as_deref().unwrap_or(DEFAULT_API_KEY)
+    }
+
+    /// `models_dir`, or `models` under the cache directory.
+    pub fn models_dir(&self) -> PathBuf {
+        match &self.models_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => Path::new(&self.cache_dir).join("models"),
+        }
+    }
+}
+
+/// `path` with a leading `~` replaced by the home directory.
+fn expand_home(path: &str) -> String {
+    let rest = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => rest,
+        _ => return path.to_string(),
+    };
+    match dirs::home_dir() {
+        Some(home) => format!("{}{}", home.to_string_lossy(), rest),
+        None => path.to_string(),
+    }
+}
+
+/// Trimmed, with blank values treated as unset.
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+fn default_cache_dir() -> String {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("synoid")
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn detect_wsl() -> bool {
+    std::env::var("WSL_DISTRO_NAME").is_ok()
+        || std::fs::read_to_string("/proc/version")
+            .map(|s| s.contains("Microsoft") || s.contains("WSL"))
+            .unwrap_or(false)
+}
+
+/// File and command-line layers, fixed by `init`.
+static STARTUP_LAYERS: OnceLock<(ConfigLayer, ConfigLayer)> = OnceLock::new();
+
+/// The config file in use: SYNOID_CONFIG, else `synoid.json`. It need not
+/// exist yet.
+pub fn file_path() -> PathBuf {
+    std::env::var_os("SYNOID_CONFIG")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_FILE))
+}
+
+/// Load the config file (`path`, else `file_path()`) and record the
+/// command-line layer. Call once, early in `main`; later calls are ignored.
+/// Only a file that was named explicitly has to exist.
+pub fn init(
+    path: Option<&Path>,
+    cli: ConfigLayer,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(path) = path {
+        // Spawned workers (autonomous learner) read the same file
+        std::env::set_var("SYNOID_CONFIG", path);
+    }
+    let path = file_path();
+    let file = if std::env::var_os("SYNOID_CONFIG").is_some() || path.exists() {
+        ConfigLayer::load(&path)?
+    } else {
+        ConfigLayer::default()
+    };
+    let _ = STARTUP_LAYERS.set((file, cli));
+    Ok(())
+}
+
+/// The settings as of now. Before `init` only defaults and env apply.
+pub fn current() -> SynoidConfig {
+    let env = ConfigLayer::from_env(|name| std::env::var(name).ok());
+    match STARTUP_LAYERS.get() {
+        Some((file, cli)) => SynoidConfig::resolve(file, env, cli),
+        None => SynoidConfig::resolve(&ConfigLayer::default(), env, &ConfigLayer::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn later_layers_win_field_by_field() {
+        let file = ConfigLayer {
+            api_url: Some("http://file:11434".into()),
+            model: Some("file-model".into()),
+            vision_model: Some("file-vision".into()),
+            wsl: Some(false),
+            ..Default::default()
+        };
+        let env_vars: HashMap<&str, &str> = [
+            ("OLLAMA_API_URL", "http://legacy:11434"),
+            ("SYNOID_MODEL", "env-model"),
+            ("SYNOID_VISION_MODEL", "env-vision"),
+            ("SYNOID_ENABLE_SENTINEL", "1"),
+            ("SYNOID_LOG_FORMAT", "json"),
+        ]
+        .into();
+        let env = ConfigLayer::from_env(|name| env_vars.get(name).map(|v| v.to_string()));
+        let cli = ConfigLayer {
+            model: Some("cli-model".into()),
+            ..Default::default()
+        };
+
+        let config = SynoidConfig::resolve(&file, env, &cli);
+        // CLI beats env beats file
+        assert_eq!(config.model, "cli-model");
+        // Env beats file; OLLAMA_API_URL stands in for SYNOID_API_URL
+        assert_eq!(config.vision_model, "env-vision");
+        assert_eq!(config.api_url, "http://legacy:11434");
+        assert!(config.enable_sentinel);
+        assert_eq!(config.log_format, LogFormat::Json);
+        // File beats defaults
+        assert!(!config.wsl);
+        // Defaults fill the rest
+        assert_eq!(config.api_key, None);
+        assert_eq!(config.api_key_or_default(), DEFAULT_API_KEY);
+
+        let defaults = SynoidConfig::resolve(
+            &ConfigLayer::default(),
+            ConfigLayer::default(),
+            &ConfigLayer::default(),
+        );
+        assert_eq!(defaults.api_url, DEFAULT_API_URL);
+        assert_eq!(defaults.model, DEFAULT_MODEL);
+        assert!(!defaults.enable_sentinel);
+        assert_eq!(defaults.log_format, LogFormat::Text);
+        assert_eq!(defaults.whisper_model, DEFAULT_WHISPER_MODEL);
+        assert!(defaults.cache_dir.ends_with("synoid"));
+        assert_eq!(defaults.models_dir(), Path::new(&defaults.cache_dir).join("models"));
+        let env = ConfigLayer::from_env(|name| {
+            (name == "SYNOID_MODELS_DIR").then(|| "/srv/whisper".to_string())
+        });
+        let moved = SynoidConfig::resolve(&ConfigLayer::default(), env, &ConfigLayer::default());
+        assert_eq!(moved.models_dir(), PathBuf::from("/srv/whisper"));
+        assert_eq!(defaults.video_dir, None);
+        assert!(defaults.after_render.is_empty());
+    }
+
+    #[test]
+    fn after_render_actions_come_from_env_and_the_file() {
+        let layer: ConfigLayer =
+            serde_json::from_str(r#"{"after_render": ["copy-path", "reveal"]}"#).unwrap();
+        let env = ConfigLayer::from_env(|name| {
+            (name == "SYNOID_AFTER_RENDER").then(|| "copy-summary".to_string())
+        });
+        let none = ConfigLayer::default;
+
+        assert_eq!(
+            SynoidConfig::resolve(&layer, none(), &none()).after_render,
+            vec![PostRenderAction::CopyPath, PostRenderAction::Reveal]
+        );
+        assert_eq!(
+            SynoidConfig::resolve(&layer, env, &none()).after_render,
+            vec![PostRenderAction::CopySummary]
+        );
+        // `--then none` switches the file's actions off for one run
+        let cli = ConfigLayer {
+            after_render: Some(Vec::new()),
+            ..Default::default()
+        };
+        assert!(SynoidConfig::resolve(&layer, none(), &cli).after_render.is_empty());
+    }
+
+    #[test]
+    fn an_empty_outputs_dir_switches_a_lower_layer_off() {
+        let file = ConfigLayer {
+            outputs_dir: Some("/srv/renders".into()),
+            ..Default::default()
+        };
+        let none = ConfigLayer::default;
+        let config = SynoidConfig::resolve(&file, none(), &none());
+        assert_eq!(config.outputs_dir.as_deref(), Some("/srv/renders"));
+
+        // What `--here` sets
+        let here = ConfigLayer {
+            outputs_dir: Some(String::new()),
+            ..Default::default()
+        };
+        assert_eq!(SynoidConfig::resolve(&file, none(), &here).outputs_dir, None);
+
+        // The older SYNOID_OUTPUT_DIR name fills in, and `~` is the home directory
+        let env = ConfigLayer::from_env(|name| {
+            (name == "SYNOID_OUTPUT_DIR").then(|| "~/Videos/SYNOID".to_string())
+        });
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(
+            SynoidConfig::resolve(&none(), env, &none()).outputs_dir.map(PathBuf::from),
+            Some(home.join("Videos/SYNOID"))
+        );
+        assert_eq!(expand_home("~user/x"), "~user/x");
+    }
+
+    #[test]
+    fn feature_knobs_fall_back_to_their_defaults() {
+        let none = ConfigLayer::default;
+        let defaults = SynoidConfig::resolve(&none(), none(), &none());
+        assert_eq!(defaults.short_input_secs, DEFAULT_SHORT_INPUT_SECS);
+        assert_eq!(defaults.max_upload_mb, DEFAULT_MAX_UPLOAD_MB);
+        assert_eq!(defaults.project_history, DEFAULT_PROJECT_HISTORY);
+        assert!(defaults.proc_limits.is_empty());
+
+        let file: ConfigLayer =
+            serde_json::from_str(r#"{"proc_limits": {"render": 3, "probe": 6}, "max_upload_mb": 0}"#)
+                .unwrap();
+        let env_vars: HashMap<&str, &str> = [
+            ("SYNOID_PROC_LIMIT_PROBE", "2"),
+            ("SYNOID_SHORT_INPUT_SECS", "-1"),
+            ("SYNOID_INVIDIOUS_URLS", "https://a.example/, ,https://b.example"),
+            ("SYNOID_AUTO_PULL", "yes"),
+        ]
+        .into();
+        let env = ConfigLayer::from_env(|name| env_vars.get(name).map(|v| v.to_string()));
+        let config = SynoidConfig::resolve(&file, env, &none());
+        // Limits merge class by class
+        assert_eq!(config.proc_limits.get("render"), Some(&3));
+        assert_eq!(config.proc_limits.get("probe"), Some(&2));
+        // Out-of-range values keep the default
+        assert_eq!(config.max_upload_mb, DEFAULT_MAX_UPLOAD_MB);
+        assert_eq!(config.short_input_secs, DEFAULT_SHORT_INPUT_SECS);
+        assert_eq!(config.invidious_urls, vec!["https://a.example", "https://b.example"]);
+        assert!(config.auto_pull);
+    }
+
+    #[test]
+    fn config_file_rejects_unknown_keys() {
+        let layer: ConfigLayer = serde_json::from_str(r#"{"model": "qwen3:8b"}"#).unwrap();
+        assert_eq!(layer.model.as_deref(), Some("qwen3:8b"));
+        assert!(serde_json::from_str::<ConfigLayer>(r#"{"modle": "qwen3:8b"}"#).is_err());
+    }
+
+    #[test]
+    fn saved_files_hold_only_the_set_fields_and_load_back() {
+        let path = std::env::temp_dir()
+            .join(format!("synoid_config_{}", std::process::id()))
+            .join("synoid.json");
+        let layer = ConfigLayer {
+            model: Some("qwen3:8b".into()),
+            whisper_model: Some("base".into()),
+            ..Default::default()
+        };
+        layer.save(&path).unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(!text.contains("null"), "{}", text);
+        assert_eq!(ConfigLayer::load(&path).unwrap(), layer);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -3,22 +3,49 @@
 
 use axum::{
     body::Body,
-    extract::{Multipart, Path, State},
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        DefaultBodyLimit, Multipart, Path, Query, State,
+    },
     http::{header, HeaderMap, StatusCode},
-    response::{IntoResponse, Response},
-    routing::{delete, get, post},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::{delete, get, patch, post, put},
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::agent::engines::proc::{self, ProcClass};
 use serde_json::{json, Value};
+use futures_util::Stream;
 use std::{
     collections::HashMap,
+    convert::Infallible,
     path::PathBuf,
-    sync::{Arc, Mutex},
+    sync::Arc,
 };
-use tokio::{fs as tfs, process::Command};
+use tokio::{
+    fs as tfs,
+    io::{AsyncSeekExt, AsyncWriteExt},
+    sync::{broadcast, RwLock},
+};
+use crate::agent::ai_systems::hive_mind::HiveMind;
+use crate::agent::archive;
 use crate::agent::engines::process_utils::CommandExt;
-use tracing::{error, info};
+use crate::agent::output_naming::NamingTemplate;
+use crate::agent::project::sha256_file;
+use crate::agent::smart_editor::bookends::Bookend;
+use crate::agent::smart_editor::{explanation_path, EditIntent, EditPlan, RenderMode};
+use crate::agent::subtitle_import::{self, CueEdit, CueEditError, ImportedSubtitles, SubtitleFormat};
+use crate::agent::timeline_render;
+use crate::agent::transcription::{self, TranscriptSegment};
+use crate::collab::{ClientMessage, CollabEvent, CollabHub, PeerHandle, MAX_CONNECTIONS_PER_SESSION};
+use crate::gpu_backend;
+use tower_http::limit::RequestBodyLimitLayer;
+use tower_http::timeout::TimeoutLayer;
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
 // ─── Shared state for session tracking ────────────────────────────────────────
@@ -43,6 +70,8 @@ pub struct AssetMeta {
     pub thumbnail_url: Option<String>,
     pub stream_url: String,
     pub fps: f64,
+    /// SHA-256 of the file contents; identical uploads reuse one asset.
+    pub content_hash: String,
 }
 
 #[derive(Debug, Default)]
@@ -51,6 +80,14 @@ pub struct RenderJob {
     pub status: String,
     pub output_path: Option<PathBuf>,
     pub error: Option<String>,
+    /// Markdown "explain this edit" report, for smart edits.
+    pub explanation: Option<String>,
+    /// Per-step results when an auto-edit request held several edits.
+    pub artifacts: Vec<crate::agent::smart_editor::StepOutcome>,
+    /// PSNR/SSIM/VMAF of a compressed asset against its source, when asked for.
+    pub quality: Option<crate::agent::production_tools::QualityReport>,
+    /// Asset a job registered in the session (compress actions).
+    pub asset_id: Option<String>,
 }
 
 #[derive(Debug, Default)]
@@ -58,9 +95,29 @@ pub struct EditorStore {
     pub sessions: HashMap<String, SessionState>,
     pub assets: HashMap<String, Vec<AssetMeta>>, // session_id → assets
     pub jobs: HashMap<String, RenderJob>,        // session_id → render job
+    pub project_revisions: HashMap<String, u64>, // session_id → last saved revision
+    pub uploads: HashMap<String, PendingUpload>, // upload_id → chunked upload in progress
+    pub subtitles: HashMap<String, ImportedSubtitles>, // asset_id → imported cues
+}
+
+/// A chunked upload between `assets/init` and `assets/:id/complete`.
+#[derive(Debug, Clone)]
+pub struct PendingUpload {
+    pub session_id: String,
+    pub filename: String,
+    pub temp_path: PathBuf,
+    /// Chunks are appended strictly in order; this is the index expected next.
+    pub next_chunk: u32,
+    pub received_bytes: u64,
+    /// Set while a chunk is being appended so a retry cannot interleave.
+    pub writing: bool,
+    /// When the upload was started or last received a chunk.
+    pub last_activity: std::time::Instant,
 }
 
-pub type SharedEditorStore = Arc<Mutex<EditorStore>>;
+/// Handlers take the lock only to copy data in or out, never across an
+/// `.await`; file and ffmpeg work happens after the guard is dropped.
+pub type SharedEditorStore = Arc<RwLock<EditorStore>>;
 
 // ─── Request/Response types ───────────────────────────────────────────────────
 #[derive(Deserialize)]
@@ -69,18 +126,40 @@ pub struct TranscribeRequest {
     pub asset_id: String,
 }
 
+/// `?format=` of the subtitles export; SRT when absent.
+#[derive(Deserialize)]
+pub struct SubtitlesQuery {
+    pub format: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct AiChatRequest {
     pub message: String,
+    /// Answer with this installed model instead of the brain's default.
+    pub model: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Debug, Deserialize)]
 pub struct AutoEditRequest {
     pub intent: String,
     #[serde(rename = "assetId")]
     pub asset_id: Option<String>,
     #[serde(rename = "outputPath")]
     pub output_path: Option<String>,
+    /// Parse the intent with this installed model instead of the default.
+    pub model: Option<String>,
+}
+
+/// Body of `POST /sessions/:id/ai/smart-edit`: every `EditIntent` field at
+/// the top level, plus which asset to edit and where to write it.
+#[derive(Debug, Deserialize)]
+pub struct SmartEditRequest {
+    #[serde(rename = "assetId")]
+    pub asset_id: Option<String>,
+    #[serde(rename = "outputPath")]
+    pub output_path: Option<String>,
+    #[serde(flatten)]
+    pub intent: EditIntent,
 }
 
 #[derive(Deserialize)]
@@ -91,26 +170,122 @@ pub struct RenderRequest {
     pub clips: Option<Value>,
     #[serde(rename = "captionData")]
     pub caption_data: Option<Value>,
+    /// Fast 480p preview, saved under the "preview" name; the cut decisions
+    /// carry over to the next full render.
+    pub draft: Option<bool>,
+    /// Put an imported subtitle asset's (edited) cues on a plain render.
+    pub subtitles: Option<SubtitleRenderOption>,
+    /// Video to put before an intent render, instead of the configured intro.
+    pub intro: Option<String>,
+    /// Video or image card to put after an intent render, instead of the
+    /// configured outro.
+    pub outro: Option<String>,
+    /// Seconds over which the intro's sound fades into the first scene's.
+    #[serde(rename = "introCrossfade")]
+    pub intro_crossfade: Option<f64>,
+}
+
+#[derive(Deserialize)]
+pub struct SubtitleRenderOption {
+    #[serde(rename = "assetId")]
+    pub asset_id: String,
+    /// "burn" draws the cues into the picture; "mux" adds a soft subtitle
+    /// track players can toggle. Defaults to "burn".
+    pub mode: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct InitUploadRequest {
+    pub filename: String,
+    /// Declared total size, checked against the upload cap up front.
+    pub size: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct WsParams {
+    /// Display name shown to other reviewers in the presence list.
+    pub name: Option<String>,
 }
 
 // ─── App state ────────────────────────────────────────────────────────────────
+/// Where the editor keeps its sessions and how large an upload it takes.
+#[derive(Debug, Clone)]
+pub struct EditorSettings {
+    /// Folder holding every session's assets and in-flight uploads.
+    pub sessions_root: PathBuf,
+    /// Largest upload or imported archive, in bytes. Uploads stream to
+    /// disk, so this is the guard against filling it.
+    pub max_upload_bytes: u64,
+}
+
+impl EditorSettings {
+    /// `cortex_cache/editor_sessions` and the `max_upload_mb` setting.
+    pub fn from_config() -> Self {
+        Self {
+            sessions_root: PathBuf::from("cortex_cache").join("editor_sessions"),
+            max_upload_bytes: crate::config::current().max_upload_mb.saturating_mul(1024 * 1024),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct EditorState {
     pub store: SharedEditorStore,
     pub core: Arc<crate::agent::core::AgentCore>,
+    pub hub: Arc<CollabHub>,
+    pub settings: Arc<EditorSettings>,
+}
+
+impl EditorState {
+    /// The folder of session `session_id`.
+    fn session_dir(&self, session_id: &str) -> PathBuf {
+        self.settings.sessions_root.join(session_id)
+    }
 }
 
 // ─── Router Factory ──────────────────────────────────────────────────────────
-pub fn router(core: Arc<crate::agent::core::AgentCore>) -> Router {
+pub fn router(core: Arc<crate::agent::core::AgentCore>, settings: EditorSettings) -> Router {
+    let max_upload_bytes = settings.max_upload_bytes;
     let state = EditorState {
-        store: Arc::new(Mutex::new(EditorStore::default())),
+        store: Arc::new(RwLock::new(EditorStore::default())),
+        hub: core.collab.clone(),
         core,
+        settings: Arc::new(settings),
     };
 
+    let store = state.store.clone();
+    let sessions_root = state.settings.sessions_root.clone();
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(UPLOAD_SWEEP_INTERVAL);
+        loop {
+            ticks.tick().await;
+            sweep_stale_uploads(&store, &sessions_root, UPLOAD_IDLE_TTL).await;
+        }
+    });
+
+    // Uploads and imports stream to disk under the upload cap and can take
+    // longer than a request is otherwise given; every other route keeps the
+    // server's body cap and timeout.
+    let transfers = Router::new()
+        .route("/sessions/import", post(import_session))
+        .route(
+            "/sessions/:id/assets",
+            post(upload_asset).layer(DefaultBodyLimit::max(max_upload_bytes as usize)),
+        )
+        .route(
+            "/sessions/:id/assets/:asset_id/chunk/:n",
+            put(upload_chunk).layer(DefaultBodyLimit::max(MAX_CHUNK_BYTES)),
+        );
+
     Router::new()
         .route("/sessions", post(create_session))
         .route("/sessions/:id", get(get_session))
-        .route("/sessions/:id/assets", post(upload_asset).get(list_assets))
+        .route("/sessions/:id/archive", post(archive_session))
+        .route("/sessions/:id/ws", get(session_ws))
+        .route("/events", get(event_stream))
+        .route("/sessions/:id/assets", get(list_assets))
+        .route("/sessions/:id/assets/init", post(init_upload))
+        .route("/sessions/:id/assets/:asset_id/complete", post(complete_upload))
         .route("/sessions/:id/assets/:asset_id", delete(delete_asset))
         .route("/sessions/:id/assets/:asset_id/stream", get(stream_asset))
         .route(
@@ -118,27 +293,46 @@ pub fn router(core: Arc<crate::agent::core::AgentCore>) -> Router {
             get(get_thumbnail),
         )
         .route("/sessions/:id/transcribe", post(transcribe_asset))
+        .route(
+            "/sessions/:id/assets/:asset_id/subtitles",
+            post(export_subtitles),
+        )
+        .route("/sessions/:id/assets/:asset_id/cues", get(list_cues))
+        .route("/sessions/:id/assets/:asset_id/cues/:n", patch(edit_subtitle_cue))
         .route("/sessions/:id/ai/chat", post(ai_chat))
         .route("/sessions/:id/ai/auto-edit", post(ai_auto_edit))
+        .route("/sessions/:id/ai/smart-edit", post(ai_smart_edit))
+        .route("/sessions/:id/ai/action", post(ai_action))
         .route("/sessions/:id/render", post(start_render))
         .route("/sessions/:id/render/status", get(render_status))
+        .route("/sessions/:id/render/output", get(render_output))
+        .route("/sessions/:id/render/scenes", get(render_scenes))
         .route("/sessions/:id/project/save", post(save_project))
         .route("/sessions/:id/project/load", get(load_project))
+        .route("/sessions/:id/project/history", get(project_history))
+        .route("/gpu/slots", get(gpu_slot_status))
+        .layer(RequestBodyLimitLayer::new(crate::server::REQUEST_BODY_LIMIT))
+        .layer(TimeoutLayer::new(crate::server::REQUEST_TIMEOUT))
+        .merge(transfers)
         .with_state(state)
 }
 
 // ─── Session Handlers ─────────────────────────────────────────────────────────
 async fn create_session(State(s): State<EditorState>) -> impl IntoResponse {
+    let session = open_session(&s).await;
+    info!("[EDITOR-API] Created session {}", session.id);
+    Json(json!({ "id": session.id, "status": "active" }))
+}
+
+/// Register a new, empty session and create its asset folder.
+async fn open_session(s: &EditorState) -> SessionState {
     let id = Uuid::new_v4().to_string();
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
         .as_secs();
 
-    let asset_dir = PathBuf::from("cortex_cache")
-        .join("editor_sessions")
-        .join(&id)
-        .join("assets");
+    let asset_dir = s.session_dir(&id).join("assets");
     let _ = tfs::create_dir_all(&asset_dir).await;
 
     let session = SessionState {
@@ -146,24 +340,121 @@ async fn create_session(State(s): State<EditorState>) -> impl IntoResponse {
         created_at: now,
         asset_dir,
     };
-    {
-        let mut store = s.store.lock().unwrap();
-        store.sessions.insert(id.clone(), session);
-    }
-
-    info!("[EDITOR-API] Created session {}", id);
-    Json(json!({ "id": id, "status": "active" }))
+    s.store.write().await.sessions.insert(id, session.clone());
+    session
 }
 
 async fn get_session(Path(id): Path<String>, State(s): State<EditorState>) -> impl IntoResponse {
-    let store = s.store.lock().unwrap();
+    let store = s.store.read().await;
     if store.sessions.contains_key(&id) {
-        Json(json!({ "id": id, "status": "active" })).into_response()
+        Json(json!({ "id": id, "status": "active", "peers": s.hub.peers(&id) })).into_response()
     } else {
         StatusCode::NOT_FOUND.into_response()
     }
 }
 
+// ─── Live Collaboration ───────────────────────────────────────────────────────
+async fn session_ws(
+    Path(session_id): Path<String>,
+    Query(params): Query<WsParams>,
+    State(s): State<EditorState>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    if !s.store.read().await.sessions.contains_key(&session_id) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    if s.hub.peers(&session_id).len() >= MAX_CONNECTIONS_PER_SESSION {
+        return (StatusCode::TOO_MANY_REQUESTS, "Session is full").into_response();
+    }
+
+    let hub = s.hub.clone();
+    ws.on_upgrade(move |socket| run_session_socket(socket, hub, session_id, params.name))
+        .into_response()
+}
+
+async fn run_session_socket(
+    mut socket: WebSocket,
+    hub: Arc<CollabHub>,
+    session_id: String,
+    name: Option<String>,
+) {
+    // Re-check the cap atomically now that the upgrade has completed.
+    let PeerHandle { conn_id, name, mut rx } = match hub.join(&session_id, name.as_deref()) {
+        Ok(peer) => peer,
+        Err(e) => {
+            warn!("[EDITOR-API] Rejecting live peer: {}", e);
+            let _ = socket.send(Message::Close(None)).await;
+            return;
+        }
+    };
+    info!("[EDITOR-API] Live peer '{}' joined session {}", name, session_id);
+
+    let welcome = json!({ "type": "welcome", "you": name, "peers": hub.peers(&session_id) });
+    let _ = socket.send(Message::Text(welcome.to_string())).await;
+
+    loop {
+        tokio::select! {
+            event = rx.recv() => match event {
+                Ok(event) => {
+                    let Ok(text) = serde_json::to_string(&event) else { continue };
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("[EDITOR-API] Live peer '{}' lagged, skipped {} events", name, skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<ClientMessage>(&text) {
+                    Ok(msg) => hub.publish(&session_id, msg.into_event(&name)),
+                    Err(e) => warn!("[EDITOR-API] Ignoring malformed live message: {}", e),
+                },
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {} // ping/pong/binary
+            },
+        }
+    }
+
+    // Drop our receiver first so the hub can reclaim an empty channel.
+    drop(rx);
+    hub.leave(&session_id, &conn_id);
+    info!("[EDITOR-API] Live peer '{}' left session {}", name, session_id);
+}
+
+/// Every core event bus event (jobs from any subsystem, health ticks,
+/// learner cycles, integrity violations) as server-sent JSON.
+async fn event_stream(
+    State(s): State<EditorState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let rx = s.core.events.subscribe();
+    let stream = futures_util::stream::unfold(rx, |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    let Ok(data) = serde_json::to_string(&event) else { continue };
+                    return Some((Ok(Event::default().data(data)), rx));
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("[EDITOR-API] Event stream lagged, skipped {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Name used when a REST call (rather than a live peer) triggers an event.
+fn client_name(headers: &HeaderMap) -> String {
+    headers
+        .get("x-synoid-client")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.chars().take(32).collect())
+        .unwrap_or_else(|| "editor".to_string())
+}
+
 // ─── Asset Handlers ───────────────────────────────────────────────────────────
 async fn upload_asset(
     Path(session_id): Path<String>,
@@ -171,7 +462,7 @@ async fn upload_asset(
     mut multipart: Multipart,
 ) -> impl IntoResponse {
     let asset_dir = {
-        let store = s.store.lock().unwrap();
+        let store = s.store.read().await;
         match store.sessions.get(&session_id) {
             Some(sess) => sess.asset_dir.clone(),
             None => return (StatusCode::NOT_FOUND, "Session not found").into_response(),
@@ -180,337 +471,1469 @@ async fn upload_asset(
 
     let _ = tfs::create_dir_all(&asset_dir).await;
 
-    while let Ok(Some(field)) = multipart.next_field().await {
-        let filename = field.file_name().unwrap_or("upload").to_string();
-        let data = match field.bytes().await {
-            Ok(b) => b,
+    let max_bytes = s.settings.max_upload_bytes;
+    // A body over the route's limit can surface here, before any chunk
+    let mut field = match multipart.next_field().await {
+        Ok(Some(field)) => field,
+        Ok(None) => return (StatusCode::BAD_REQUEST, "No file provided").into_response(),
+        Err(e) => {
+            error!("[EDITOR-API] Upload read error: {}", e);
+            return (e.status(), "Failed to read upload").into_response();
+        }
+    };
+    let filename = field.file_name().unwrap_or("upload").to_string();
+
+    let asset_id = Uuid::new_v4().to_string();
+    let safe_name = sanitize_filename(&filename);
+    let file_path = asset_dir.join(format!("{}_{}", asset_id, safe_name));
+
+    // Stream to disk, counting bytes, so an oversized upload is cut off
+    // at the cap instead of being buffered whole.
+    let mut file = match tfs::File::create(&file_path).await {
+        Ok(f) => f,
+        Err(e) => {
+            error!("[EDITOR-API] Failed to create asset: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let mut size = 0u64;
+    let mut hasher = Sha256::new();
+    loop {
+        let chunk = match field.chunk().await {
+            Ok(Some(c)) => c,
+            Ok(None) => break,
             Err(e) => {
+                drop(file);
+                let _ = tfs::remove_file(&file_path).await;
                 error!("[EDITOR-API] Upload read error: {}", e);
-                return (StatusCode::BAD_REQUEST, "Failed to read upload").into_response();
+                return (e.status(), "Failed to read upload").into_response();
             }
         };
-
-        let asset_id = Uuid::new_v4().to_string();
-        let safe_name = sanitize_filename(&filename);
-        let file_path = asset_dir.join(format!("{}_{}", asset_id, safe_name));
-        let size = data.len() as u64;
-
-        if let Err(e) = tfs::write(&file_path, &data).await {
+        size += chunk.len() as u64;
+        if size > max_bytes {
+            drop(file);
+            let _ = tfs::remove_file(&file_path).await;
+            warn!("[EDITOR-API] Rejected upload '{}': over {} bytes", filename, max_bytes);
+            return (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("Upload exceeds the {} MB limit", max_bytes / (1024 * 1024)),
+            )
+                .into_response();
+        }
+        hasher.update(&chunk);
+        if let Err(e) = file.write_all(&chunk).await {
+            drop(file);
+            let _ = tfs::remove_file(&file_path).await;
             error!("[EDITOR-API] Failed to write asset: {}", e);
             return StatusCode::INTERNAL_SERVER_ERROR.into_response();
         }
+    }
+    if let Err(e) = file.flush().await {
+        error!("[EDITOR-API] Failed to write asset: {}", e);
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    drop(file);
 
-        info!("[EDITOR-API] Saved asset {} → {:?}", asset_id, file_path);
-
-        // Probe video metadata with ffprobe
-        let (duration, width, height, fps) = probe_video_meta(&file_path).await;
+    let content_hash = format!("{:x}", hasher.finalize());
+    let finalized = finalize_asset(&s, &session_id, &asset_id, &filename, &file_path, size, content_hash).await;
+    finalized_response(&s, finalized).await
+}
 
-        // Extract thumbnail
-        let thumb_path = asset_dir.join(format!("{}_thumb.jpg", asset_id));
-        extract_thumbnail(&file_path, &thumb_path, 1.0).await;
+// ─── Chunked Uploads ──────────────────────────────────────────────────────────
+// `POST assets/init` → `PUT assets/:upload_id/chunk/:n` (n = 0, 1, 2, …) →
+// `POST assets/:upload_id/complete`. Chunks append to a temp file outside the
+// asset directory. Re-sending a chunk that already landed is a no-op, so a
+// client that lost its connection can resume from the `nextChunk` any chunk
+// response (or a 409) reports.
+
+/// Largest single chunk body; clients should send a few MB at a time.
+const MAX_CHUNK_BYTES: usize = 64 * 1024 * 1024;
+
+/// A chunked upload that receives nothing for this long is abandoned.
+const UPLOAD_IDLE_TTL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// How often abandoned uploads are swept.
+const UPLOAD_SWEEP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+/// Forget chunked uploads idle for longer than `ttl` and delete their `.part`
+/// files, along with `.part` files older than `ttl` that no upload owns (left
+/// by a previous run). Returns how many temp files were removed.
+pub async fn sweep_stale_uploads(
+    store: &SharedEditorStore,
+    sessions_root: &std::path::Path,
+    ttl: std::time::Duration,
+) -> usize {
+    let (stale, live) = {
+        let mut store = store.write().await;
+        let mut stale = Vec::new();
+        store.uploads.retain(|_, p| {
+            let keep = p.writing || p.last_activity.elapsed() < ttl;
+            if !keep {
+                stale.push(p.temp_path.clone());
+            }
+            keep
+        });
+        let live: std::collections::HashSet<PathBuf> =
+            store.uploads.values().map(|p| p.temp_path.clone()).collect();
+        (stale, live)
+    };
 
-        let kind = infer_asset_type(&filename);
-        let stream_url = format!(
-            "/api/editor/sessions/{}/assets/{}/stream",
-            session_id, asset_id
-        );
-        let thumbnail_url = if thumb_path.exists() {
-            Some(format!(
-                "/api/editor/sessions/{}/assets/{}/thumbnail",
-                session_id, asset_id
-            ))
-        } else {
-            None
-        };
+    let mut removed = 0;
+    for path in &stale {
+        if tfs::remove_file(path).await.is_ok() {
+            removed += 1;
+        }
+    }
 
-        let meta = AssetMeta {
-            id: asset_id.clone(),
-            session_id: session_id.clone(),
-            filename: filename.clone(),
-            kind,
-            duration,
-            width,
-            height,
-            size,
-            fps,
-            thumbnail_url,
-            stream_url,
-        };
+    if let Ok(mut sessions) = tfs::read_dir(sessions_root).await {
+        while let Ok(Some(session)) = sessions.next_entry().await {
+            let Ok(mut files) = tfs::read_dir(session.path().join("uploads")).await else {
+                continue;
+            };
+            while let Ok(Some(file)) = files.next_entry().await {
+                let path = file.path();
+                if path.extension().is_none_or(|e| e != "part") || live.contains(&path) {
+                    continue;
+                }
+                let expired = file
+                    .metadata()
+                    .await
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|t| t.elapsed().ok())
+                    .is_some_and(|age| age >= ttl);
+                if expired && tfs::remove_file(&path).await.is_ok() {
+                    removed += 1;
+                }
+            }
+        }
+    }
 
-        {
-            let mut store = s.store.lock().unwrap();
-            store
-                .assets
-                .entry(session_id.clone())
-                .or_default()
-                .push(meta.clone());
-        }
-
-        return Json(json!({
-            "id": meta.id,
-            "type": meta.kind,
-            "filename": meta.filename,
-            "duration": meta.duration,
-            "width": meta.width,
-            "height": meta.height,
-            "size": meta.size,
-            "fps": meta.fps,
-            "thumbnailUrl": meta.thumbnail_url,
-            "streamUrl": meta.stream_url,
-            "aiGenerated": false,
-        }))
-        .into_response();
+    if removed > 0 {
+        info!("[EDITOR-API] Swept {} abandoned upload file(s)", removed);
     }
+    removed
+}
 
-    (StatusCode::BAD_REQUEST, "No file provided").into_response()
+/// Clears `PendingUpload::writing` if the chunk handler never gets to: a
+/// panic, or a client that disconnects and drops the handler future
+/// mid-write. The handler clears the flag itself under the store lock and
+/// then disarms the guard.
+struct ChunkWriteGuard {
+    store: SharedEditorStore,
+    upload_id: String,
+    armed: bool,
 }
 
-async fn list_assets(
-    Path(session_id): Path<String>,
-    State(s): State<EditorState>,
-) -> impl IntoResponse {
-    let store = s.store.lock().unwrap();
-    let assets = store.assets.get(&session_id).cloned().unwrap_or_default();
-    let json_assets: Vec<Value> = assets
-        .iter()
-        .map(|m| {
-            json!({
-                "id": m.id,
-                "type": m.kind,
-                "filename": m.filename,
-                "duration": m.duration,
-                "width": m.width,
-                "height": m.height,
-                "size": m.size,
-                "thumbnailUrl": m.thumbnail_url,
-                "streamUrl": m.stream_url,
-                "aiGenerated": false,
-            })
-        })
-        .collect();
-    Json(json_assets)
+impl ChunkWriteGuard {
+    fn disarm(mut self) {
+        self.armed = false;
+    }
 }
 
-async fn delete_asset(
-    Path((session_id, asset_id)): Path<(String, String)>,
-    State(s): State<EditorState>,
-) -> impl IntoResponse {
-    let asset_dir = {
-        let store = s.store.lock().unwrap();
-        store.sessions.get(&session_id).map(|s| s.asset_dir.clone())
-    };
-    if let Some(dir) = asset_dir {
-        // Try to delete all files with this asset_id prefix
-        if let Ok(mut entries) = tfs::read_dir(&dir).await {
-            while let Ok(Some(entry)) = entries.next_entry().await {
-                let name = entry.file_name();
-                let name_str = name.to_string_lossy();
-                if name_str.starts_with(&asset_id) {
-                    let _ = tfs::remove_file(entry.path()).await;
-                }
-            }
+impl Drop for ChunkWriteGuard {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
         }
-        let mut store = s.store.lock().unwrap();
-        if let Some(assets) = store.assets.get_mut(&session_id) {
-            assets.retain(|a| a.id != asset_id);
+        let clear = |store: &mut EditorStore, upload_id: &str| {
+            if let Some(pending) = store.uploads.get_mut(upload_id) {
+                pending.writing = false;
+            }
+        };
+        // Drop can't await: clear it now if the store is free, else as soon
+        // as it is.
+        if let Ok(mut store) = self.store.try_write() {
+            clear(&mut store, &self.upload_id);
+        } else if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let store = self.store.clone();
+            let upload_id = std::mem::take(&mut self.upload_id);
+            runtime.spawn(async move { clear(&mut *store.write().await, &upload_id) });
         }
     }
-    StatusCode::NO_CONTENT
 }
 
-async fn stream_asset(
-    Path((session_id, asset_id)): Path<(String, String)>,
+fn upload_progress(upload_id: &str, pending: &PendingUpload) -> Value {
+    json!({
+        "uploadId": upload_id,
+        "nextChunk": pending.next_chunk,
+        "receivedBytes": pending.received_bytes,
+    })
+}
+
+async fn init_upload(
+    Path(session_id): Path<String>,
     State(s): State<EditorState>,
-    headers: HeaderMap,
+    Json(req): Json<InitUploadRequest>,
 ) -> impl IntoResponse {
-    let file_path = find_asset_path(&s, &session_id, &asset_id).await;
-    match file_path {
-        Some(path) => {
-            let content_type = mime_guess::from_path(&path)
-                .first_or_octet_stream()
-                .to_string();
-            serve_file_with_range(&path, &headers, &content_type)
-                .await
-                .into_response()
+    let asset_dir = {
+        let store = s.store.read().await;
+        match store.sessions.get(&session_id) {
+            Some(sess) => sess.asset_dir.clone(),
+            None => return (StatusCode::NOT_FOUND, "Session not found").into_response(),
         }
-        None => StatusCode::NOT_FOUND.into_response(),
+    };
+
+    let max_bytes = s.settings.max_upload_bytes;
+    if req.size.is_some_and(|size| size > max_bytes) {
+        return (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("Upload exceeds the {} MB limit", max_bytes / (1024 * 1024)),
+        )
+            .into_response();
+    }
+
+    let upload_id = Uuid::new_v4().to_string();
+    let upload_dir = asset_dir
+        .parent()
+        .map(|p| p.join("uploads"))
+        .unwrap_or_else(|| asset_dir.join("uploads"));
+    let temp_path = upload_dir.join(format!("{}.part", upload_id));
+    let created = async {
+        tfs::create_dir_all(&upload_dir).await?;
+        tfs::File::create(&temp_path).await
+    }
+    .await;
+    if let Err(e) = created {
+        error!("[EDITOR-API] Failed to start chunked upload: {}", e);
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
     }
+
+    let pending = PendingUpload {
+        session_id,
+        filename: req.filename,
+        temp_path,
+        next_chunk: 0,
+        received_bytes: 0,
+        writing: false,
+        last_activity: std::time::Instant::now(),
+    };
+    info!(
+        "[EDITOR-API] Chunked upload {} started for '{}'",
+        upload_id, pending.filename
+    );
+    let body = upload_progress(&upload_id, &pending);
+    s.store.write().await.uploads.insert(upload_id, pending);
+    Json(body).into_response()
 }
 
-async fn get_thumbnail(
-    Path((session_id, asset_id)): Path<(String, String)>,
+async fn upload_chunk(
+    Path((session_id, upload_id, n)): Path<(String, String, u32)>,
     State(s): State<EditorState>,
+    body: axum::body::Bytes,
 ) -> impl IntoResponse {
-    let dir = {
-        let store = s.store.lock().unwrap();
-        store
-            .sessions
-            .get(&session_id)
-            .map(|sess| sess.asset_dir.clone())
-    };
-    if let Some(asset_dir) = dir {
-        let thumb_path = asset_dir.join(format!("{}_thumb.jpg", asset_id));
-        if thumb_path.exists() {
-            if let Ok(bytes) = tfs::read(&thumb_path).await {
-                return ([(header::CONTENT_TYPE, "image/jpeg")], bytes).into_response();
-            }
+    let max_bytes = s.settings.max_upload_bytes;
+    let (temp_path, offset) = {
+        let mut store = s.store.write().await;
+        let Some(pending) = store
+            .uploads
+            .get_mut(&upload_id)
+            .filter(|p| p.session_id == session_id)
+        else {
+            return (StatusCode::NOT_FOUND, "Upload not found").into_response();
+        };
+        if pending.writing {
+            return (StatusCode::CONFLICT, Json(upload_progress(&upload_id, pending))).into_response();
         }
+        if n < pending.next_chunk {
+            // Retry of a chunk that already landed
+            return Json(upload_progress(&upload_id, pending)).into_response();
+        }
+        if n > pending.next_chunk {
+            return (StatusCode::CONFLICT, Json(upload_progress(&upload_id, pending))).into_response();
+        }
+        if pending.received_bytes + body.len() as u64 > max_bytes {
+            return (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("Upload exceeds the {} MB limit", max_bytes / (1024 * 1024)),
+            )
+                .into_response();
+        }
+        pending.writing = true;
+        (pending.temp_path.clone(), pending.received_bytes)
+    };
+    let guard = ChunkWriteGuard {
+        store: s.store.clone(),
+        upload_id: upload_id.clone(),
+        armed: true,
+    };
+
+    // Truncate to the acknowledged length first, dropping any torn write
+    // from a chunk whose request died mid-append.
+    let written = async {
+        let mut file = tfs::OpenOptions::new().write(true).open(&temp_path).await?;
+        file.set_len(offset).await?;
+        file.seek(std::io::SeekFrom::Start(offset)).await?;
+        file.write_all(&body).await?;
+        file.flush().await
     }
-    StatusCode::NOT_FOUND.into_response()
+    .await;
+
+    let mut store = s.store.write().await;
+    // Nothing awaits between here and clearing `writing`, so the request
+    // can no longer be dropped with the flag set
+    guard.disarm();
+    let Some(pending) = store.uploads.get_mut(&upload_id) else {
+        return (StatusCode::NOT_FOUND, "Upload not found").into_response();
+    };
+    pending.writing = false;
+    if let Err(e) = written {
+        error!("[EDITOR-API] Failed to append chunk {} of {}: {}", n, upload_id, e);
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    pending.next_chunk += 1;
+    pending.received_bytes += body.len() as u64;
+    pending.last_activity = std::time::Instant::now();
+    Json(upload_progress(&upload_id, pending)).into_response()
 }
 
-// ─── Transcription ─────────────────────────────────────────────────────────────
-async fn transcribe_asset(
-    Path(session_id): Path<String>,
+async fn complete_upload(
+    Path((session_id, upload_id)): Path<(String, String)>,
     State(s): State<EditorState>,
-    Json(req): Json<TranscribeRequest>,
 ) -> impl IntoResponse {
-    let file_path = find_asset_path(&s, &session_id, &req.asset_id).await;
-    let file_path = match file_path {
-        Some(p) => p,
-        None => {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(json!({"error": "Asset not found"})),
-            )
-                .into_response()
+    let (pending, asset_dir) = {
+        let mut store = s.store.write().await;
+        let Some(asset_dir) = store.sessions.get(&session_id).map(|s| s.asset_dir.clone()) else {
+            return (StatusCode::NOT_FOUND, "Session not found").into_response();
+        };
+        match store.uploads.get(&upload_id) {
+            Some(p) if p.session_id != session_id => {
+                return (StatusCode::NOT_FOUND, "Upload not found").into_response()
+            }
+            Some(p) if p.writing => {
+                return (StatusCode::CONFLICT, Json(upload_progress(&upload_id, p))).into_response()
+            }
+            Some(_) => {}
+            None => return (StatusCode::NOT_FOUND, "Upload not found").into_response(),
         }
+        (store.uploads.remove(&upload_id).unwrap(), asset_dir)
     };
 
+    let _ = tfs::create_dir_all(&asset_dir).await;
+    let file_path = asset_dir.join(format!(
+        "{}_{}",
+        upload_id,
+        sanitize_filename(&pending.filename)
+    ));
+    if let Err(e) = tfs::rename(&pending.temp_path, &file_path).await {
+        error!("[EDITOR-API] Failed to finalize upload {}: {}", upload_id, e);
+        let _ = tfs::remove_file(&pending.temp_path).await;
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
     info!(
-        "[EDITOR-API] Transcribing asset {} in session {}",
-        req.asset_id, session_id
+        "[EDITOR-API] Chunked upload {} complete: {} chunk(s), {} bytes",
+        upload_id, pending.next_chunk, pending.received_bytes
     );
 
-    // Extract audio to WAV for Whisper
-    let wav_path = file_path.with_extension("_transcribe.wav");
-    let extract_ok = Command::new("ffmpeg")
-        .stealth()
-        .args(["-y", "-i"])
-        .arg(&file_path)
-        .args(["-ar", "16000", "-ac", "1", "-f", "wav"])
-        .arg(&wav_path)
-        .status()
+    let content_hash = match hash_asset_file(&file_path).await {
+        Ok(hash) => hash,
+        Err(e) => {
+            error!("[EDITOR-API] Failed to hash upload {}: {}", upload_id, e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let finalized = finalize_asset(
+        &s,
+        &session_id,
+        &upload_id,
+        &pending.filename,
+        &file_path,
+        pending.received_bytes,
+        content_hash,
+    )
+    .await;
+    finalized_response(&s, finalized).await
+}
+
+/// SHA-256 of a file already on disk, hashed off the async runtime.
+async fn hash_asset_file(path: &std::path::Path) -> std::io::Result<String> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || sha256_file(&path))
+        .await
+        .map_err(std::io::Error::other)?
+}
+
+/// Asset already in `session_id` with these exact contents.
+async fn find_duplicate(s: &EditorState, session_id: &str, content_hash: &str) -> Option<AssetMeta> {
+    let store = s.store.read().await;
+    store
+        .assets
+        .get(session_id)?
+        .iter()
+        .find(|a| a.content_hash == content_hash)
+        .cloned()
+}
+
+fn asset_json(meta: &AssetMeta) -> Value {
+    json!({
+        "id": meta.id,
+        "type": meta.kind,
+        "filename": meta.filename,
+        "duration": meta.duration,
+        "width": meta.width,
+        "height": meta.height,
+        "size": meta.size,
+        "fps": meta.fps,
+        "thumbnailUrl": meta.thumbnail_url,
+        "streamUrl": meta.stream_url,
+        "contentHash": meta.content_hash,
+        "aiGenerated": false,
+    })
+}
+
+/// The answer to an upload: the asset, plus the cue count and import report
+/// for subtitles.
+async fn finalized_response(s: &EditorState, finalized: Result<AssetMeta, Response>) -> Response {
+    let meta = match finalized {
+        Ok(meta) => meta,
+        Err(response) => return response,
+    };
+    let mut body = asset_json(&meta);
+    if let Some(imported) = s.store.read().await.subtitles.get(&meta.id) {
+        body["cues"] = json!(imported.cues.len());
+        body["fixes"] = json!(imported.fixes);
+    }
+    Json(body).into_response()
+}
+
+/// Validate a fully received upload and register it as a session asset.
+/// Files ffprobe cannot parse are deleted and refused with 415; a file whose
+/// contents match an existing asset is deleted and that asset is returned.
+async fn finalize_asset(
+    s: &EditorState,
+    session_id: &str,
+    asset_id: &str,
+    filename: &str,
+    file_path: &PathBuf,
+    size: u64,
+    content_hash: String,
+) -> Result<AssetMeta, Response> {
+    let asset_dir = file_path.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+
+    if let Some(existing) = find_duplicate(s, session_id, &content_hash).await {
+        let _ = tfs::remove_file(file_path).await;
+        info!(
+            "[EDITOR-API] Upload '{}' duplicates asset {}; reusing it",
+            filename, existing.id
+        );
+        return Ok(existing);
+    }
+
+    if let Some(format) = SubtitleFormat::from_filename(filename) {
+        return finalize_subtitle_asset(s, session_id, asset_id, filename, file_path, size, content_hash, format)
+            .await;
+    }
+
+    // The filename is client-controlled; trust only what ffprobe can parse.
+    let Some(kind) = probe_media_kind(file_path).await else {
+        let _ = tfs::remove_file(file_path).await;
+        warn!("[EDITOR-API] Rejected upload '{}': not a media file", filename);
+        return Err((
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Upload is not a recognised video, audio or image file",
+        )
+            .into_response());
+    };
+
+    info!("[EDITOR-API] Saved {} asset {} → {:?}", kind, asset_id, file_path);
+
+    // Probe video metadata with ffprobe
+    let (duration, width, height, fps) = probe_video_meta(file_path).await;
+
+    // Extract thumbnail
+    let thumb_path = asset_dir.join(format!("{}_thumb.jpg", asset_id));
+    extract_thumbnail(file_path, &thumb_path, 1.0).await;
+    let stream_url = format!(
+        "/api/editor/sessions/{}/assets/{}/stream",
+        session_id, asset_id
+    );
+    let thumbnail_url = if tfs::try_exists(&thumb_path).await.unwrap_or(false) {
+        Some(format!(
+            "/api/editor/sessions/{}/assets/{}/thumbnail",
+            session_id, asset_id
+        ))
+    } else {
+        None
+    };
+
+    let meta = AssetMeta {
+        id: asset_id.to_string(),
+        session_id: session_id.to_string(),
+        filename: filename.to_string(),
+        kind,
+        duration,
+        width,
+        height,
+        size,
+        fps,
+        thumbnail_url,
+        stream_url,
+        content_hash,
+    };
+
+    // An identical upload may have finished while this one was probing
+    let duplicate = {
+        let mut store = s.store.write().await;
+        let assets = store.assets.entry(session_id.to_string()).or_default();
+        let existing = assets.iter().find(|a| a.content_hash == meta.content_hash).cloned();
+        if existing.is_none() {
+            assets.push(meta.clone());
+        }
+        existing
+    };
+    if let Some(existing) = duplicate {
+        let _ = tfs::remove_file(file_path).await;
+        let _ = tfs::remove_file(&thumb_path).await;
+        return Ok(existing);
+    }
+    s.hub.publish(
+        session_id,
+        CollabEvent::AssetAdded {
+            asset_id: meta.id.clone(),
+            filename: meta.filename.clone(),
+        },
+    );
+
+    Ok(meta)
+}
+
+/// Register an SRT, VTT or ASS upload as a "subtitle" asset. Its cues are
+/// parsed leniently into the store with the validation report of every cue
+/// that had to be fixed or dropped.
+#[allow(clippy::too_many_arguments)]
+async fn finalize_subtitle_asset(
+    s: &EditorState,
+    session_id: &str,
+    asset_id: &str,
+    filename: &str,
+    file_path: &PathBuf,
+    size: u64,
+    content_hash: String,
+    format: SubtitleFormat,
+) -> Result<AssetMeta, Response> {
+    let parsed = match tfs::read(file_path).await {
+        Ok(bytes) => subtitle_import::parse_subtitles(&String::from_utf8_lossy(&bytes), format)
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    let imported = match parsed {
+        Ok(imported) => imported,
+        Err(e) => {
+            let _ = tfs::remove_file(file_path).await;
+            warn!("[EDITOR-API] Rejected subtitle upload '{}': {}", filename, e);
+            return Err(action_error(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!("Subtitle file could not be read: {}", e),
+            ));
+        }
+    };
+    info!(
+        "[EDITOR-API] Imported {} cue(s) from '{}' ({} fixed)",
+        imported.cues.len(),
+        filename,
+        imported.fixes.len()
+    );
+
+    let meta = AssetMeta {
+        id: asset_id.to_string(),
+        session_id: session_id.to_string(),
+        filename: filename.to_string(),
+        kind: "subtitle".to_string(),
+        duration: imported.cues.last().map(|c| c.end).unwrap_or(0.0),
+        width: 0,
+        height: 0,
+        size,
+        fps: 0.0,
+        thumbnail_url: None,
+        stream_url: format!(
+            "/api/editor/sessions/{}/assets/{}/stream",
+            session_id, asset_id
+        ),
+        content_hash,
+    };
+    {
+        let mut store = s.store.write().await;
+        store
+            .assets
+            .entry(session_id.to_string())
+            .or_default()
+            .push(meta.clone());
+        store.subtitles.insert(meta.id.clone(), imported);
+    }
+    s.hub.publish(
+        session_id,
+        CollabEvent::AssetAdded {
+            asset_id: meta.id.clone(),
+            filename: meta.filename.clone(),
+        },
+    );
+    Ok(meta)
+}
+
+fn cue_json(number: usize, cue: &TranscriptSegment) -> Value {
+    json!({
+        "index": number,
+        "start": cue.start,
+        "end": cue.end,
+        "text": cue.text,
+    })
+}
+
+/// Imported cues of a subtitle asset, with its import report.
+async fn list_cues(
+    Path((session_id, asset_id)): Path<(String, String)>,
+    State(s): State<EditorState>,
+) -> Response {
+    if session_asset(&s, &session_id, Some(&asset_id)).await.is_none() {
+        return action_error(StatusCode::NOT_FOUND, "Asset not found");
+    }
+    let store = s.store.read().await;
+    let Some(imported) = store.subtitles.get(&asset_id) else {
+        return action_error(StatusCode::NOT_FOUND, "Asset has no imported subtitles");
+    };
+    Json(json!({
+        "cues": imported
+            .cues
+            .iter()
+            .enumerate()
+            .map(|(i, cue)| cue_json(i + 1, cue))
+            .collect::<Vec<_>>(),
+        "fixes": imported.fixes,
+    }))
+    .into_response()
+}
+
+/// Change the text or timing of cue `n` (1-based).
+async fn edit_subtitle_cue(
+    Path((session_id, asset_id, n)): Path<(String, String, usize)>,
+    State(s): State<EditorState>,
+    Json(edit): Json<CueEdit>,
+) -> Response {
+    if session_asset(&s, &session_id, Some(&asset_id)).await.is_none() {
+        return action_error(StatusCode::NOT_FOUND, "Asset not found");
+    }
+    let mut store = s.store.write().await;
+    let Some(imported) = store.subtitles.get_mut(&asset_id) else {
+        return action_error(StatusCode::NOT_FOUND, "Asset has no imported subtitles");
+    };
+    match subtitle_import::edit_cue(&mut imported.cues, n, edit) {
+        Ok(()) => Json(cue_json(n, &imported.cues[n - 1])).into_response(),
+        Err(CueEditError::NoSuchCue) => {
+            action_error(StatusCode::NOT_FOUND, format!("No cue {}", n))
+        }
+        Err(CueEditError::Invalid(msg)) => action_error(StatusCode::BAD_REQUEST, msg),
+    }
+}
+
+async fn list_assets(
+    Path(session_id): Path<String>,
+    State(s): State<EditorState>,
+) -> impl IntoResponse {
+    let store = s.store.read().await;
+    let assets = store.assets.get(&session_id).cloned().unwrap_or_default();
+    let json_assets: Vec<Value> = assets
+        .iter()
+        .map(|m| {
+            json!({
+                "id": m.id,
+                "type": m.kind,
+                "filename": m.filename,
+                "duration": m.duration,
+                "width": m.width,
+                "height": m.height,
+                "size": m.size,
+                "thumbnailUrl": m.thumbnail_url,
+                "streamUrl": m.stream_url,
+                "aiGenerated": false,
+            })
+        })
+        .collect();
+    Json(json_assets)
+}
+
+async fn delete_asset(
+    Path((session_id, asset_id)): Path<(String, String)>,
+    State(s): State<EditorState>,
+) -> impl IntoResponse {
+    let asset_dir = {
+        let store = s.store.read().await;
+        store.sessions.get(&session_id).map(|s| s.asset_dir.clone())
+    };
+    if let Some(dir) = asset_dir {
+        // Try to delete all files with this asset_id prefix
+        if let Ok(mut entries) = tfs::read_dir(&dir).await {
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                let name = entry.file_name();
+                let name_str = name.to_string_lossy();
+                if name_str.starts_with(&asset_id) {
+                    let _ = tfs::remove_file(entry.path()).await;
+                }
+            }
+        }
+        let mut store = s.store.write().await;
+        if let Some(assets) = store.assets.get_mut(&session_id) {
+            assets.retain(|a| a.id != asset_id);
+        }
+        store.subtitles.remove(&asset_id);
+    }
+    StatusCode::NO_CONTENT
+}
+
+async fn stream_asset(
+    Path((session_id, asset_id)): Path<(String, String)>,
+    State(s): State<EditorState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let file_path = find_asset_path(&s, &session_id, &asset_id).await;
+    match file_path {
+        Some(path) => {
+            let content_type = mime_guess::from_path(&path)
+                .first_or_octet_stream()
+                .to_string();
+            serve_file_with_range(&path, &headers, &content_type)
+                .await
+                .into_response()
+        }
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+async fn get_thumbnail(
+    Path((session_id, asset_id)): Path<(String, String)>,
+    State(s): State<EditorState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let dir = {
+        let store = s.store.read().await;
+        store
+            .sessions
+            .get(&session_id)
+            .map(|sess| sess.asset_dir.clone())
+    };
+    if let Some(asset_dir) = dir {
+        let thumb_path = asset_dir.join(format!("{}_thumb.jpg", asset_id));
+        if let Ok(meta) = tfs::metadata(&thumb_path).await {
+            let validators = FileValidators::of(&meta);
+            if validators.not_modified(&headers) {
+                return validators.not_modified_response();
+            }
+            if let Ok(bytes) = tfs::read(&thumb_path).await {
+                return validators
+                    .apply(Response::builder())
+                    .header(header::CONTENT_TYPE, "image/jpeg")
+                    .body(Body::from(bytes))
+                    .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
+        }
+    }
+    StatusCode::NOT_FOUND.into_response()
+}
+
+// ─── Transcription ─────────────────────────────────────────────────────────────
+async fn transcribe_asset(
+    Path(session_id): Path<String>,
+    State(s): State<EditorState>,
+    Json(req): Json<TranscribeRequest>,
+) -> impl IntoResponse {
+    let file_path = find_asset_path(&s, &session_id, &req.asset_id).await;
+    let file_path = match file_path {
+        Some(p) => p,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(json!({"error": "Asset not found"})),
+            )
+                .into_response()
+        }
+    };
+
+    info!(
+        "[EDITOR-API] Transcribing asset {} in session {}",
+        req.asset_id, session_id
+    );
+    let segments = match transcribe_file(&file_path).await {
+        Ok(segments) => segments,
+        Err(resp) => return resp,
+    };
+
+    // Build word-level approximation (distribute words evenly within each segment)
+    let mut words = Vec::new();
+    for seg in &segments {
+        let seg_words: Vec<&str> = seg.text.trim().split_whitespace().collect();
+        let n = seg_words.len().max(1);
+        let dur = (seg.end - seg.start) / n as f64;
+        for (i, word) in seg_words.iter().enumerate() {
+            words.push(json!({
+                "text": word,
+                "start": seg.start + i as f64 * dur,
+                "end": seg.start + (i + 1) as f64 * dur,
+            }));
+        }
+    }
+
+    let response = json!({
+        "segments": segments.iter().map(|s| json!({
+            "start": s.start,
+            "end": s.end,
+            "text": s.text,
+        })).collect::<Vec<_>>(),
+        "words": words,
+    });
+
+    Json(response).into_response()
+}
+
+/// `POST /sessions/:id/assets/:asset_id/subtitles?format=srt|vtt`: the
+/// asset's captions as a downloadable subtitle file, without the word
+/// timings `transcribe` returns or any render.
+async fn export_subtitles(
+    Path((session_id, asset_id)): Path<(String, String)>,
+    State(s): State<EditorState>,
+    Query(query): Query<SubtitlesQuery>,
+) -> Response {
+    let format = query.format.as_deref().unwrap_or("srt").to_ascii_lowercase();
+    let (content_type, render): (&str, fn(&[TranscriptSegment]) -> String) = match format.as_str() {
+        "srt" => ("application/x-subrip", transcription::generate_srt),
+        "vtt" => ("text/vtt", transcription::generate_vtt),
+        other => {
+            return action_error(
+                StatusCode::BAD_REQUEST,
+                format!("unsupported subtitle format '{}' (use srt or vtt)", other),
+            )
+        }
+    };
+    let (Some(asset), Some(file_path)) = (
+        session_asset(&s, &session_id, Some(&asset_id)).await,
+        find_asset_path(&s, &session_id, &asset_id).await,
+    ) else {
+        return action_error(StatusCode::NOT_FOUND, "Asset not found");
+    };
+
+    info!(
+        "[EDITOR-API] Exporting {} subtitles for asset {} in session {}",
+        format, asset_id, session_id
+    );
+    // Imported subtitle assets export their (edited) cues as they stand
+    let imported = s.store.read().await.subtitles.get(&asset_id).map(|i| i.cues.clone());
+    let segments = match imported {
+        Some(cues) => cues,
+        None => match transcribe_file(&file_path).await {
+            Ok(segments) => segments,
+            Err(resp) => return resp,
+        },
+    };
+    let stem = std::path::Path::new(&asset.filename)
+        .file_stem()
+        .map(|s| s.to_string_lossy().replace('"', ""))
+        .unwrap_or_else(|| "subtitles".to_string());
+    (
+        [
+            (header::CONTENT_TYPE, format!("{}; charset=utf-8", content_type)),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}.{}\"", stem, format),
+            ),
+        ],
+        render(&segments),
+    )
+        .into_response()
+}
+
+/// Extract the audio of `file_path` for Whisper and transcribe it.
+async fn transcribe_file(file_path: &std::path::Path) -> Result<Vec<TranscriptSegment>, Response> {
+    // Extract audio to WAV for Whisper
+    let wav_path = file_path.with_extension("_transcribe.wav");
+    let extract_ok = proc::ffmpeg(ProcClass::Render)
+        .stealth()
+        .args(["-y", "-i"])
+        .arg(file_path)
+        .args(["-ar", "16000", "-ac", "1", "-f", "wav"])
+        .arg(&wav_path)
+        .status()
         .await
         .map(|s| s.success())
         .unwrap_or(false);
 
     if !extract_ok {
-        return (
+        return Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(json!({"error": "Audio extraction failed"})),
         )
-            .into_response();
+            .into_response());
     }
 
-    let engine = match crate::agent::transcription::TranscriptionEngine::new(None).await {
+    let engine = match transcription::TranscriptionEngine::new(None).await {
         Ok(e) => e,
         Err(e) => {
             error!("[EDITOR-API] Transcription engine init failed: {}", e);
-            return (
+            return Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({"error": e.to_string()})),
             )
-                .into_response();
+                .into_response());
+        }
+    };
+
+    let segments = match engine.transcribe(&wav_path).await {
+        Ok(s) => s,
+        Err(e) => {
+            let _ = tfs::remove_file(&wav_path).await;
+            error!("[EDITOR-API] Transcription failed: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": e.to_string()})),
+            )
+                .into_response());
         }
     };
+    let _ = tfs::remove_file(&wav_path).await;
+    Ok(segments)
+}
+
+// ─── AI Chat ──────────────────────────────────────────────────────────────────
+async fn ai_chat(
+    Path(session_id): Path<String>,
+    State(s): State<EditorState>,
+    headers: HeaderMap,
+    Json(req): Json<AiChatRequest>,
+) -> impl IntoResponse {
+    info!(
+        "[EDITOR-API] AI chat in session {}: {}",
+        session_id, req.message
+    );
+    let model = match req.model.as_deref() {
+        Some(requested) => match resolve_requested_model(&s, requested).await {
+            Ok(model) => Some(model),
+            Err(resp) => return resp,
+        },
+        None => None,
+    };
+    s.hub.publish(
+        &session_id,
+        CollabEvent::Chat {
+            from: client_name(&headers),
+            text: req.message.clone(),
+        },
+    );
+    let result = match model {
+        Some(model) => {
+            let mut brain = s.core.brain.lock().await.with_model(&model);
+            brain.process(&req.message).await
+        }
+        None => s.core.brain.lock().await.process(&req.message).await,
+    };
+    match result {
+        Ok(response) => {
+            s.hub.publish(
+                &session_id,
+                CollabEvent::Chat {
+                    from: "synoid".to_string(),
+                    text: response.clone(),
+                },
+            );
+            Json(json!({
+            "response": response,
+            "actions": suggest_actions_from_response(&response),
+            }))
+            .into_response()
+        }
+        Err(e) => {
+            error!("[EDITOR-API] Brain error: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": e.to_string()})),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// The installed model `requested` names, checked against the LLM server's
+/// model list so a bad name is a 400 here instead of a failed job later.
+async fn resolve_requested_model(s: &EditorState, requested: &str) -> Result<String, Response> {
+    let mut hive = HiveMind::new(&s.core.api_url);
+    let listed = hive.refresh_models().await;
+    if let Some(model) = hive.resolve_model(requested) {
+        return Ok(model);
+    }
+    let mut available: Vec<String> = hive.models.keys().cloned().collect();
+    available.sort();
+    let error = match listed {
+        Err(e) => format!(
+            "model '{}' is not available: could not list models at {} ({})",
+            requested, s.core.api_url, e
+        ),
+        Ok(()) => format!("model '{}' is not installed", requested),
+    };
+    warn!("[EDITOR-API] Rejected model override: {}", error);
+    Err((
+        StatusCode::BAD_REQUEST,
+        Json(json!({"error": error, "availableModels": available})),
+    )
+        .into_response())
+}
+
+fn suggest_actions_from_response(response: &str) -> Vec<Value> {
+    let lower = response.to_lowercase();
+    let mut actions = Vec::new();
+    if lower.contains("remov") || lower.contains("cut") || lower.contains("trim") {
+        actions.push(json!({ "type": "auto-edit", "label": "Apply AI Edit", "params": { "intent": response } }));
+    }
+    if lower.contains("subtitle") || lower.contains("caption") || lower.contains("transcrib") {
+        actions.push(json!({ "type": "transcribe", "label": "Transcribe Video", "params": {} }));
+    }
+    actions
+}
+
+// ─── AI Actions ───────────────────────────────────────────────────────────────
+// `POST ai/action` runs one of the actions `ai/chat` suggests, so the front end
+// can offer each as a single click. Trim answers with the new asset; the other
+// actions answer exactly as their dedicated endpoints do.
+
+/// `{ "type": "trim", "params": { ... } }`
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", content = "params", rename_all = "kebab-case")]
+pub enum AiAction {
+    Trim(TrimParams),
+    Transcribe(TranscribeParams),
+    AutoEdit(AutoEditRequest),
+    Compress(CompressParams),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrimParams {
+    /// Defaults to the session's first asset.
+    #[serde(rename = "assetId")]
+    pub asset_id: Option<String>,
+    pub start: f64,
+    pub end: f64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TranscribeParams {
+    #[serde(rename = "assetId")]
+    pub asset_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CompressParams {
+    #[serde(rename = "assetId")]
+    pub asset_id: Option<String>,
+    #[serde(rename = "targetMb")]
+    pub target_mb: f64,
+    /// Compare the result against the source and report it on the job.
+    #[serde(rename = "measureQuality", default)]
+    pub measure_quality: bool,
+}
+
+impl AiAction {
+    /// Checks that don't need the asset itself.
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            AiAction::Trim(p) => {
+                if !p.start.is_finite() || !p.end.is_finite() || p.start < 0.0 {
+                    return Err("trim: start and end must be finite and start >= 0".into());
+                }
+                if p.end <= p.start {
+                    return Err("trim: end must be after start".into());
+                }
+            }
+            AiAction::Compress(p) => {
+                if !p.target_mb.is_finite() || p.target_mb <= 0.0 {
+                    return Err("compress: targetMb must be a positive number".into());
+                }
+            }
+            AiAction::AutoEdit(p) => {
+                if p.intent.trim().is_empty() {
+                    return Err("auto-edit: intent must not be empty".into());
+                }
+            }
+            AiAction::Transcribe(_) => {}
+        }
+        Ok(())
+    }
+}
+
+fn action_error(status: StatusCode, msg: impl Into<String>) -> Response {
+    (status, Json(json!({ "error": msg.into() }))).into_response()
+}
+
+/// The asset `asset_id` names, or the session's first media asset.
+async fn session_asset(s: &EditorState, session_id: &str, asset_id: Option<&str>) -> Option<AssetMeta> {
+    let store = s.store.read().await;
+    let assets = store.assets.get(session_id)?;
+    match asset_id {
+        Some(id) => assets.iter().find(|a| a.id == id).cloned(),
+        None => assets.iter().find(|a| a.kind != "subtitle").cloned(),
+    }
+}
+
+async fn ai_action(
+    Path(session_id): Path<String>,
+    State(s): State<EditorState>,
+    Json(body): Json<Value>,
+) -> Response {
+    let action: AiAction = match serde_json::from_value(body) {
+        Ok(a) => a,
+        Err(e) => return action_error(StatusCode::BAD_REQUEST, format!("Invalid action: {}", e)),
+    };
+    if let Err(e) = action.validate() {
+        return action_error(StatusCode::BAD_REQUEST, e);
+    }
+    info!("[EDITOR-API] AI action in session {}: {:?}", session_id, action);
+
+    match action {
+        AiAction::Trim(p) => run_trim_action(&s, &session_id, p).await,
+        AiAction::Compress(p) => run_compress_action(&s, &session_id, p).await,
+        AiAction::AutoEdit(req) => ai_auto_edit(Path(session_id), State(s), Json(req))
+            .await
+            .into_response(),
+        AiAction::Transcribe(p) => {
+            let Some(asset) = session_asset(&s, &session_id, p.asset_id.as_deref()).await else {
+                return action_error(StatusCode::NOT_FOUND, "Asset not found");
+            };
+            let req = TranscribeRequest { asset_id: asset.id };
+            transcribe_asset(Path(session_id), State(s), Json(req))
+                .await
+                .into_response()
+        }
+    }
+}
+
+/// Cut `[start, end)` out of an asset into a new asset of the same kind.
+async fn run_trim_action(s: &EditorState, session_id: &str, p: TrimParams) -> Response {
+    let Some(asset) = session_asset(s, session_id, p.asset_id.as_deref()).await else {
+        return action_error(StatusCode::NOT_FOUND, "Asset not found");
+    };
+    let ext = match asset.kind.as_str() {
+        "video" => "mp4",
+        "audio" => "m4a",
+        other => {
+            return action_error(
+                StatusCode::BAD_REQUEST,
+                format!("trim: {} assets have no timeline to trim", other),
+            )
+        }
+    };
+    if asset.duration > 0.0 && p.start >= asset.duration {
+        return action_error(
+            StatusCode::BAD_REQUEST,
+            format!("trim: start is past the end of the asset ({:.2}s)", asset.duration),
+        );
+    }
+    let end = if asset.duration > 0.0 { p.end.min(asset.duration) } else { p.end };
+    let Some(input) = find_asset_path(s, session_id, &asset.id).await else {
+        return action_error(StatusCode::NOT_FOUND, "Asset file missing");
+    };
+
+    let new_id = Uuid::new_v4().to_string();
+    let stem = std::path::Path::new(&asset.filename)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "asset".to_string());
+    let filename = format!("{}_trim.{}", stem, ext);
+    let Some(asset_dir) = input.parent().map(|p| p.to_path_buf()) else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    let output = asset_dir.join(format!("{}_{}", new_id, sanitize_filename(&filename)));
+
+    let trimmed =
+        crate::agent::production_tools::trim_video(&input, p.start, end - p.start, &output).await;
+    let size = match trimmed {
+        Ok(_) => tfs::metadata(&output).await.map(|m| m.len()).unwrap_or(0),
+        Err(e) => {
+            let _ = tfs::remove_file(&output).await;
+            error!("[EDITOR-API] Trim action failed: {}", e);
+            return action_error(StatusCode::INTERNAL_SERVER_ERROR, format!("Trim failed: {}", e));
+        }
+    };
+    let content_hash = match hash_asset_file(&output).await {
+        Ok(hash) => hash,
+        Err(e) => {
+            error!("[EDITOR-API] Failed to hash trimmed asset: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let finalized = finalize_asset(s, session_id, &new_id, &filename, &output, size, content_hash).await;
+    finalized_response(s, finalized).await
+}
+
+/// Compress an asset in the background; the result becomes a new asset and
+/// progress is reported through the session's render job.
+async fn run_compress_action(s: &EditorState, session_id: &str, p: CompressParams) -> Response {
+    let Some(asset) = session_asset(s, session_id, p.asset_id.as_deref()).await else {
+        return action_error(StatusCode::NOT_FOUND, "Asset not found");
+    };
+    if asset.kind != "video" {
+        return action_error(
+            StatusCode::BAD_REQUEST,
+            "compress: only video assets can be compressed",
+        );
+    }
+    let Some(input) = find_asset_path(s, session_id, &asset.id).await else {
+        return action_error(StatusCode::NOT_FOUND, "Asset file missing");
+    };
 
-    let segments = match engine.transcribe(&wav_path).await {
-        Ok(s) => s,
-        Err(e) => {
-            let _ = tfs::remove_file(&wav_path).await;
-            error!("[EDITOR-API] Transcription failed: {}", e);
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({"error": e.to_string()})),
-            )
-                .into_response();
-        }
+    let new_id = Uuid::new_v4().to_string();
+    let stem = std::path::Path::new(&asset.filename)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "asset".to_string());
+    let filename = format!("{}_{:.0}mb.mp4", stem, p.target_mb);
+    let Some(asset_dir) = input.parent().map(|p| p.to_path_buf()) else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
     };
-    let _ = tfs::remove_file(&wav_path).await;
+    let output = asset_dir.join(format!("{}_{}", new_id, sanitize_filename(&filename)));
 
-    // Build word-level approximation (distribute words evenly within each segment)
-    let mut words = Vec::new();
-    for seg in &segments {
-        let seg_words: Vec<&str> = seg.text.trim().split_whitespace().collect();
-        let n = seg_words.len().max(1);
-        let dur = (seg.end - seg.start) / n as f64;
-        for (i, word) in seg_words.iter().enumerate() {
-            words.push(json!({
-                "text": word,
-                "start": seg.start + i as f64 * dur,
-                "end": seg.start + (i + 1) as f64 * dur,
-            }));
+    // The session has one job slot; don't overwrite a render still using it
+    {
+        let mut store = s.store.write().await;
+        if store.jobs.get(session_id).is_some_and(|job| job.status == "running") {
+            return action_error(
+                StatusCode::CONFLICT,
+                "compress: another job is still running in this session",
+            );
         }
+        store.jobs.insert(
+            session_id.to_string(),
+            RenderJob {
+                progress: 0.0,
+                status: "running".to_string(),
+                output_path: None,
+                error: None,
+                explanation: None,
+                artifacts: Vec::new(),
+                quality: None,
+                asset_id: None,
+            },
+        );
     }
+    s.hub.publish(
+        session_id,
+        CollabEvent::RenderProgress {
+            progress: 0.0,
+            status: "running".to_string(),
+        },
+    );
 
-    let response = json!({
-        "segments": segments.iter().map(|s| json!({
-            "start": s.start,
-            "end": s.end,
-            "text": s.text,
-        })).collect::<Vec<_>>(),
-        "words": words,
+    let s = s.clone();
+    let session = session_id.to_string();
+    tokio::spawn(async move {
+        let result =
+            crate::agent::production_tools::compress_video(&input, p.target_mb, &output).await;
+        let quality = match &result {
+            Ok(_) if p.measure_quality => {
+                match crate::agent::production_tools::compare_quality(&input, &output).await {
+                    Ok(report) => Some(report),
+                    Err(e) => {
+                        warn!("[EDITOR-API] Quality measurement failed: {}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+        let registered = match result {
+            Ok(res) => match hash_asset_file(&output).await {
+                Ok(hash) => {
+                    let size = tfs::metadata(&output).await.map(|m| m.len()).unwrap_or(0);
+                    let registered = finalize_asset(&s, &session, &new_id, &filename, &output, size, hash)
+                        .await
+                        .map(|meta| meta.id)
+                        .map_err(|rejected| format!("Compressed output was rejected ({})", rejected.status()));
+                    if registered.is_ok() {
+                        info!("[EDITOR-API] Compressed asset ready: {:.2} MB", res.size_mb);
+                    }
+                    registered
+                }
+                Err(e) => {
+                    let _ = tfs::remove_file(&output).await;
+                    Err(e.to_string())
+                }
+            },
+            Err(e) => {
+                let _ = tfs::remove_file(&output).await;
+                Err(e.to_string())
+            }
+        };
+        // A duplicate of an existing asset is registered under that asset's file
+        let asset_path = match &registered {
+            Ok(id) => find_asset_path(&s, &session, id).await,
+            Err(_) => None,
+        };
+
+        let mut store = s.store.write().await;
+        if let Some(job) = store.jobs.get_mut(&session) {
+            job.progress = 1.0;
+            match registered {
+                Ok(id) => {
+                    job.status = "done".to_string();
+                    job.output_path = asset_path;
+                    job.asset_id = Some(id);
+                    job.quality = quality;
+                }
+                Err(e) => {
+                    error!("[EDITOR-API] Compress action failed: {}", e);
+                    job.status = "error".to_string();
+                    job.error = Some(e);
+                }
+            }
+            s.hub.publish(
+                &session,
+                CollabEvent::RenderProgress {
+                    progress: job.progress,
+                    status: job.status.clone(),
+                },
+            );
+        }
     });
 
-    Json(response).into_response()
+    Json(json!({
+        "jobId": session_id,
+        "status": "started",
+    }))
+    .into_response()
 }
 
-// ─── AI Chat ──────────────────────────────────────────────────────────────────
-async fn ai_chat(
+// ─── AI Auto-Edit ─────────────────────────────────────────────────────────────
+async fn ai_auto_edit(
     Path(session_id): Path<String>,
     State(s): State<EditorState>,
-    Json(req): Json<AiChatRequest>,
+    Json(req): Json<AutoEditRequest>,
 ) -> impl IntoResponse {
     info!(
-        "[EDITOR-API] AI chat in session {}: {}",
-        session_id, req.message
+        "[EDITOR-API] Auto-edit in session {}: {}",
+        session_id, req.intent
     );
-    let mut brain = s.core.brain.lock().await;
-    match brain.process(&req.message).await {
-        Ok(response) => Json(json!({
-            "response": response,
-            "actions": suggest_actions_from_response(&response),
-        }))
-        .into_response(),
+    let model = match req.model.as_deref() {
+        Some(requested) => match resolve_requested_model(&s, requested).await {
+            Ok(model) => Some(model),
+            Err(resp) => return resp,
+        },
+        None => None,
+    };
+    start_smart_edit_job(
+        &s,
+        &session_id,
+        req.asset_id.as_deref(),
+        req.output_path,
+        EditJobIntent::Text {
+            text: req.intent,
+            model,
+        },
+    )
+    .await
+}
+
+/// `POST /sessions/:id/ai/smart-edit`: an auto-edit driven by a structured
+/// `EditIntent` body instead of free text, so no LLM parsing is involved.
+async fn ai_smart_edit(
+    Path(session_id): Path<String>,
+    State(s): State<EditorState>,
+    Json(body): Json<Value>,
+) -> impl IntoResponse {
+    let req: SmartEditRequest = match serde_json::from_value(body) {
+        Ok(req) => req,
         Err(e) => {
-            error!("[EDITOR-API] Brain error: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({"error": e.to_string()})),
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": format!("invalid edit intent: {}", e)})),
             )
                 .into_response()
         }
+    };
+    if let Err(e) = req.intent.validate() {
+        return (StatusCode::BAD_REQUEST, Json(json!({"error": e}))).into_response();
     }
+    info!(
+        "[EDITOR-API] Structured smart edit in session {}: {:?} density",
+        session_id, req.intent.density
+    );
+    start_smart_edit_job(
+        &s,
+        &session_id,
+        req.asset_id.as_deref(),
+        req.output_path,
+        EditJobIntent::Structured(Box::new(req.intent)),
+    )
+    .await
 }
 
-fn suggest_actions_from_response(response: &str) -> Vec<Value> {
-    let lower = response.to_lowercase();
-    let mut actions = Vec::new();
-    if lower.contains("remov") || lower.contains("cut") || lower.contains("trim") {
-        actions.push(json!({ "type": "auto-edit", "label": "Apply AI Edit", "params": { "intent": response } }));
-    }
-    if lower.contains("subtitle") || lower.contains("caption") || lower.contains("transcrib") {
-        actions.push(json!({ "type": "transcribe", "label": "Transcribe Video", "params": {} }));
+/// One free-text smart edit: parsed by `model` when given, else by the
+/// default model inside `smart_edit`.
+async fn run_text_edit(
+    core: &crate::agent::core::AgentCore,
+    input: &std::path::Path,
+    text: &str,
+    output: &std::path::Path,
+    model: Option<&str>,
+    progress: crate::agent::smart_editor::ProgressCallback,
+    mode: RenderMode,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    match model {
+        Some(model) => {
+            let mut intent = EditIntent::from_llm_with_model(text, model).await;
+            intent.enable_subtitles = true;
+            intent.censor_profanity = true;
+            crate::agent::smart_editor::smart_edit_with_intent(
+                input,
+                intent,
+                text,
+                output,
+                Some(progress),
+                None,
+                None,
+                None,
+                mode,
+            )
+            .await
+        }
+        None => {
+            crate::agent::smart_editor::smart_edit_with_mode(
+                input,
+                text,
+                output,
+                false,
+                Some(progress),
+                None,
+                None,
+                None,
+                Some(core.animator.clone()),
+                true,
+                true,
+                mode,
+            )
+            .await
+        }
     }
-    actions
 }
 
-// ─── AI Auto-Edit ─────────────────────────────────────────────────────────────
-async fn ai_auto_edit(
-    Path(session_id): Path<String>,
-    State(s): State<EditorState>,
-    Json(req): Json<AutoEditRequest>,
-) -> impl IntoResponse {
-    info!(
-        "[EDITOR-API] Auto-edit in session {}: {}",
-        session_id, req.intent
-    );
+/// What a smart edit job edits towards.
+enum EditJobIntent {
+    /// Free text, parsed by the LLM: the default model inside `smart_edit`,
+    /// or `model` when the request named one.
+    Text { text: String, model: Option<String> },
+    /// Used exactly as given.
+    Structured(Box<EditIntent>),
+}
 
-    let asset_id = req.asset_id.as_deref().unwrap_or("");
+/// Resolve the asset (the session's first when `asset_id` is empty), then
+/// run the smart edit as a background job reported through `jobs` and
+/// `RenderProgress` events.
+async fn start_smart_edit_job(
+    s: &EditorState,
+    session_id: &str,
+    asset_id: Option<&str>,
+    output_path: Option<String>,
+    job_intent: EditJobIntent,
+) -> Response {
+    let session_id = session_id.to_string();
+    let asset_id = asset_id.unwrap_or("");
     let file_path = if asset_id.is_empty() {
         // Use the first asset in the session
-        let store = s.store.lock().unwrap();
+        let store = s.store.read().await;
         store
             .assets
             .get(&session_id)
@@ -522,11 +1945,11 @@ async fn ai_auto_edit(
                 dir.join(format!("{}_{}", a.id, a.filename))
             })
     } else {
-        find_asset_path(&s, &session_id, asset_id).await
+        find_asset_path(s, &session_id, asset_id).await
     };
 
     let input = match file_path {
-        Some(p) if p.exists() => p,
+        Some(p) if tfs::try_exists(&p).await.unwrap_or(false) => p,
         _ => {
             return (
                 StatusCode::NOT_FOUND,
@@ -536,11 +1959,14 @@ async fn ai_auto_edit(
         }
     };
 
-    let output_name = req.output_path.unwrap_or_else(|| {
-        format!(
-            "cortex_cache/editor_sessions/{}/ai_edit_output.mp4",
-            session_id
-        )
+    let intent_text = match &job_intent {
+        EditJobIntent::Text { text, .. } => Some(text.as_str()),
+        EditJobIntent::Structured(_) => None,
+    };
+    let output_name = output_path.unwrap_or_else(|| {
+        render_output_path(&s.session_dir(&session_id), &input, "edited", intent_text)
+            .to_string_lossy()
+            .into_owned()
     });
     let output = PathBuf::from(&output_name);
     if let Some(parent) = output.parent() {
@@ -549,7 +1975,7 @@ async fn ai_auto_edit(
 
     // Initialize job
     {
-        let mut store = s.store.lock().unwrap();
+        let mut store = s.store.write().await;
         store.jobs.insert(
             session_id.clone(),
             RenderJob {
@@ -557,89 +1983,274 @@ async fn ai_auto_edit(
                 status: "running".to_string(),
                 output_path: None,
                 error: None,
+                explanation: None,
+                artifacts: Vec::new(),
+                quality: None,
+                asset_id: None,
             },
         );
     }
 
-    let _core = s.core.clone();
-    let intent = req.intent.clone();
+    let structured = match &job_intent {
+        EditJobIntent::Structured(intent) => serde_json::to_value(intent).ok(),
+        EditJobIntent::Text { .. } => None,
+    };
+    let mode = RenderMode::from_config();
     let session_id_clone = session_id.clone();
     let store_clone = s.store.clone();
     let output_clone = output.clone();
+    s.hub.publish(
+        &session_id,
+        CollabEvent::RenderProgress {
+            progress: 0.0,
+            status: "running".to_string(),
+        },
+    );
 
+    let done_hub = s.hub.clone();
+    let progress_hub = s.hub.clone();
+    let progress_store = s.store.clone();
+    let progress_session = session_id.clone();
+    let core = s.core.clone();
+    // The callbacks can't await the store, so they queue their updates for
+    // a task that applies them in order.
+    let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel::<(Option<f32>, &'static str)>();
+    let progress_updates = tokio::spawn(async move {
+        while let Some((progress, status)) = progress_rx.recv().await {
+            let progress = match progress_store.write().await.jobs.get_mut(&progress_session) {
+                Some(job) => {
+                    job.progress = progress.unwrap_or(job.progress);
+                    job.status = status.to_string();
+                    job.progress
+                }
+                None => continue,
+            };
+            progress_hub.publish(
+                &progress_session,
+                CollabEvent::RenderProgress {
+                    progress,
+                    status: status.to_string(),
+                },
+            );
+        }
+    });
     tokio::spawn(async move {
-        let result = crate::agent::smart_editor::smart_edit(
-            &input,
-            &intent,
-            &output_clone,
-            false,
-            Some(Box::new(move |msg: &str| {
+        // Step `step` of `steps` reports its share of the job's progress
+        let progress_for = move |step: usize, steps: usize| -> crate::agent::smart_editor::ProgressCallback {
+            let progress_tx = progress_tx.clone();
+            Box::new(move |msg: &str| {
                 info!("[EDITOR-API] Edit progress: {}", msg);
-            })),
-            None,
-            None,
-            None,
-            Some(s.core.animator.clone()),
-            true,
-            true,
-        )
-        .await;
+                // The encode waits for a GPU slot while other sessions hold them all
+                let (progress, status) = if msg == gpu_backend::WAITING_FOR_SLOT {
+                    (None, "waiting-for-gpu")
+                } else {
+                    match crate::agent::editor_queue::parse_progress_from_msg(msg) {
+                        Some(progress) => (Some((step as f32 + progress) / steps as f32), "running"),
+                        None => return,
+                    }
+                };
+                let _ = progress_tx.send((progress, status));
+            })
+        };
 
-        let mut store = store_clone.lock().unwrap();
+        let mut artifacts = Vec::new();
+        let mut last_output = None;
+        let result: Result<PathBuf, Box<dyn std::error::Error + Send + Sync>> = match job_intent {
+            EditJobIntent::Text { text, model } => {
+                // "Remove silence, then make highlights" runs as separate
+                // edits, each reported as an artifact of this job
+                let agent = match &model {
+                    Some(model) => crate::agent::ai_systems::gpt_oss_bridge::SynoidAgent::new(
+                        &crate::config::current().api_url,
+                        model,
+                    ),
+                    None => core.brain.lock().await.agent(),
+                };
+                let steps = crate::agent::smart_editor::steps::decompose(&agent, &text).await;
+                if steps.len() == 1 {
+                    run_text_edit(&core, &input, &text, &output_clone, model.as_deref(), progress_for(0, 1), mode)
+                        .await
+                        .map(|_| output_clone.clone())
+                } else {
+                    info!("[EDITOR-API] Auto-edit split into {} steps", steps.len());
+                    let total = steps.len();
+                    let outcomes = crate::agent::smart_editor::steps::run_steps(
+                        &input,
+                        &steps,
+                        &output_clone,
+                        |index, step_input, step, step_output| {
+                            let core = core.clone();
+                            let model = model.clone();
+                            let progress = progress_for(index, total);
+                            async move {
+                                run_text_edit(&core, &step_input, &step.intent, &step_output, model.as_deref(), progress, mode)
+                                    .await
+                                    .map(|_| ())
+                                    .map_err(|e| e.to_string())
+                            }
+                        },
+                    )
+                    .await;
+                    last_output = outcomes.iter().rev().find(|o| o.succeeded()).map(|o| o.output.clone());
+                    let errors: Vec<String> = outcomes
+                        .iter()
+                        .filter_map(|o| o.error.as_ref().map(|e| format!("step {}: {}", o.index + 1, e)))
+                        .collect();
+                    artifacts = outcomes;
+                    match &last_output {
+                        Some(path) if errors.is_empty() => Ok(path.clone()),
+                        _ => Err(errors.join("; ").into()),
+                    }
+                }
+            }
+            EditJobIntent::Structured(intent) => {
+                let key = serde_json::to_string(&intent).unwrap_or_default();
+                crate::agent::smart_editor::smart_edit_with_intent(
+                    &input,
+                    *intent,
+                    &key,
+                    &output_clone,
+                    Some(progress_for(0, 1)),
+                    None,
+                    None,
+                    None,
+                    mode,
+                )
+                .await
+                .map(|_| output_clone.clone())
+            }
+        };
+        // Let queued progress land before the final status, so it can't
+        // overwrite "done"
+        drop(progress_for);
+        let _ = progress_updates.await;
+
+        let explanation = match &result {
+            Ok(path) => tokio::fs::read_to_string(explanation_path(path)).await.ok(),
+            Err(_) => None,
+        };
+        let mut store = store_clone.write().await;
         if let Some(job) = store.jobs.get_mut(&session_id_clone) {
+            job.artifacts = artifacts;
             match result {
-                Ok(_) => {
+                Ok(path) => {
                     job.progress = 1.0;
                     job.status = "done".to_string();
-                    job.output_path = Some(output_clone);
+                    job.output_path = Some(path);
+                    job.explanation = explanation;
                 }
                 Err(e) => {
                     job.status = "error".to_string();
                     job.error = Some(e.to_string());
+                    // Steps that finished before the failure stay previewable
+                    job.output_path = last_output;
                 }
             }
+            done_hub.publish(
+                &session_id_clone,
+                CollabEvent::RenderProgress {
+                    progress: job.progress,
+                    status: job.status.clone(),
+                },
+            );
         }
     });
 
-    Json(json!({
+    let mut body = json!({
         "jobId": session_id,
         "status": "started",
         "outputPath": output_name,
-    }))
-    .into_response()
+    });
+    if let Some(intent) = structured {
+        body["intent"] = intent;
+    }
+    Json(body).into_response()
 }
 
 // ─── Render ───────────────────────────────────────────────────────────────────
+/// `GET /gpu/slots`: who holds each GPU encode slot and who is waiting.
+async fn gpu_slot_status() -> impl IntoResponse {
+    Json(gpu_backend::gpu_slots().await.status())
+}
+
 async fn start_render(
     Path(session_id): Path<String>,
     State(s): State<EditorState>,
     Json(req): Json<RenderRequest>,
 ) -> impl IntoResponse {
+    let draft = req.draft.unwrap_or(false);
+
+    let subtitle_track = match &req.subtitles {
+        Some(_) if req.clips.is_some() || req.intent.as_deref().is_some_and(|i| !i.is_empty()) => {
+            return action_error(
+                StatusCode::BAD_REQUEST,
+                "subtitles can only be added to a plain render, without intent or clips",
+            )
+        }
+        Some(option) => match write_subtitle_track(&s, &session_id, option).await {
+            Ok(track) => Some(track),
+            Err(resp) => return resp,
+        },
+        None => None,
+    };
+
+    // Bookends go around a smart edit; probe them now so a bad file is a
+    // bad request rather than a failed job.
+    let has_intent = req.intent.as_deref().is_some_and(|i| !i.is_empty());
+    let with_bookends = req.intro.is_some() || req.outro.is_some() || req.intro_crossfade.is_some();
+    if with_bookends && (!has_intent || req.clips.is_some()) {
+        return action_error(
+            StatusCode::BAD_REQUEST,
+            "intro and outro are attached to intent renders, without clips",
+        );
+    }
+    for (role, path) in [("Intro", &req.intro), ("Outro", &req.outro)] {
+        if let Some(path) = path {
+            if let Err(e) = Bookend::probe(role, std::path::Path::new(path)).await {
+                return action_error(StatusCode::BAD_REQUEST, e);
+            }
+        }
+    }
+    let (intro, outro, intro_crossfade) = (
+        req.intro.map(PathBuf::from),
+        req.outro.map(PathBuf::from),
+        req.intro_crossfade,
+    );
+
+    // An arranged timeline wins over the single-asset intent path
+    if let Some(clips) = &req.clips {
+        match timeline_render::parse_clips(clips) {
+            Ok(clips) if !clips.is_empty() => {
+                return start_timeline_render(&s, &session_id, clips, draft).await
+            }
+            Ok(_) => {}
+            Err(e) => return action_error(StatusCode::BAD_REQUEST, e),
+        }
+    }
+
     let intent = req.intent.unwrap_or_default();
     let asset_id = req.asset_id.as_deref().unwrap_or("").to_string();
 
-    // Find the input asset
-    let file_path = if asset_id.is_empty() {
-        let store = s.store.lock().unwrap();
+    // Find the input asset: the requested one, else the first non-subtitle one
+    let asset_id = if asset_id.is_empty() {
+        let store = s.store.read().await;
         store
             .assets
             .get(&session_id)
-            .and_then(|a| a.first())
-            .and_then(|a| {
-                let dir = store.sessions.get(&session_id)?.asset_dir.clone();
-                // try to find file
-                std::fs::read_dir(&dir)
-                    .ok()?
-                    .filter_map(|e| e.ok())
-                    .find(|e| e.file_name().to_string_lossy().starts_with(&a.id))
-                    .map(|e| e.path())
-            })
+            .and_then(|a| a.iter().find(|a| a.kind != "subtitle"))
+            .map(|a| a.id.clone())
+            .unwrap_or_default()
+    } else {
+        asset_id
+    };
+    let file_path = if asset_id.is_empty() {
+        None
     } else {
         find_asset_path(&s, &session_id, &asset_id).await
     };
 
     let input = match file_path {
-        Some(p) if p.exists() => p,
+        Some(p) if tfs::try_exists(&p).await.unwrap_or(false) => p,
         _ => {
             return (
                 StatusCode::BAD_REQUEST,
@@ -649,16 +2260,18 @@ async fn start_render(
         }
     };
 
-    let output_path = PathBuf::from(format!(
-        "cortex_cache/editor_sessions/{}/render_output.mp4",
-        session_id
-    ));
+    let output_path = render_output_path(
+        &s.session_dir(&session_id),
+        &input,
+        render_op(draft),
+        Some(intent.as_str()).filter(|i| !i.is_empty()),
+    );
     if let Some(p) = output_path.parent() {
         let _ = tfs::create_dir_all(p).await;
     }
 
     {
-        let mut store = s.store.lock().unwrap();
+        let mut store = s.store.write().await;
         store.jobs.insert(
             session_id.clone(),
             RenderJob {
@@ -666,45 +2279,102 @@ async fn start_render(
                 status: "rendering".to_string(),
                 output_path: None,
                 error: None,
+                explanation: None,
+                artifacts: Vec::new(),
+                quality: None,
+                asset_id: None,
             },
         );
     }
 
+    s.hub.publish(
+        &session_id,
+        CollabEvent::RenderProgress {
+            progress: 0.0,
+            status: "rendering".to_string(),
+        },
+    );
+
     let _core = s.core.clone();
     let store_clone = s.store.clone();
     let session_id_clone = session_id.clone();
     let output_clone = output_path.clone();
+    let done_hub = s.hub.clone();
 
     tokio::spawn(async move {
         // If there's an intent, run smart_edit which handles both subtitle generation and editing
         if !intent.is_empty() {
-            let _ = crate::agent::smart_editor::smart_edit(
-                &input,
-                &intent,
-                &output_clone,
-                false,
-                None,
-                None,
-                None,
-                None,
-                Some(s.core.animator.clone()),
-                true,
-                true,
-            )
-            .await;
+            let mode = if draft {
+                RenderMode::Draft { fast_stretches: false }
+            } else {
+                RenderMode::Final
+            };
+            if with_bookends {
+                let mut edit_intent = EditIntent::from_llm(&intent).await;
+                edit_intent.enable_subtitles = true;
+                edit_intent.censor_profanity = true;
+                edit_intent.intro = intro;
+                edit_intent.outro = outro;
+                edit_intent.intro_crossfade = intro_crossfade;
+                let _ = crate::agent::smart_editor::smart_edit_with_intent(
+                    &input,
+                    edit_intent,
+                    &intent,
+                    &output_clone,
+                    None,
+                    None,
+                    None,
+                    None,
+                    mode,
+                )
+                .await;
+            } else {
+                let _ = crate::agent::smart_editor::smart_edit_with_mode(
+                    &input,
+                    &intent,
+                    &output_clone,
+                    false,
+                    None,
+                    None,
+                    None,
+                    None,
+                    Some(s.core.animator.clone()),
+                    true,
+                    true,
+                    mode,
+                )
+                .await;
+            }
         } else {
-            // Just copy-encode with subtitle burn-in if SRT exists
-            let srt_path = input.with_extension("srt");
+            // Just copy-encode, with the requested imported cues or else an
+            // SRT beside the input burned in
+            let sidecar = input.with_extension("srt");
+            let track = subtitle_track.or_else(|| {
+                sidecar.exists().then_some(SubtitleTrack {
+                    path: sidecar,
+                    burn: true,
+                })
+            });
             let mut args = vec![
                 "-y".to_string(),
                 "-i".to_string(),
                 input.to_string_lossy().to_string(),
             ];
-            if srt_path.exists() {
-                let srt_str = srt_path.to_string_lossy().to_string();
-                // Escape colons on Windows paths for ffmpeg vf filter
-                let safe_srt = srt_str.replace('\\', "/").replace(":/", "\\:/");
-                args.extend(["-vf".to_string(), format!("subtitles='{}'", safe_srt)]);
+            match &track {
+                Some(SubtitleTrack { path, burn: true }) => {
+                    let srt_str = path.to_string_lossy().to_string();
+                    // Escape colons on Windows paths for ffmpeg vf filter
+                    let safe_srt = srt_str.replace('\\', "/").replace(":/", "\\:/");
+                    args.extend(["-vf".to_string(), format!("subtitles='{}'", safe_srt)]);
+                }
+                Some(SubtitleTrack { path, burn: false }) => {
+                    args.extend(["-i".to_string(), path.to_string_lossy().to_string()]);
+                    args.extend(
+                        ["-map", "0:v?", "-map", "0:a?", "-map", "1:s", "-c:s", "mov_text"]
+                            .map(String::from),
+                    );
+                }
+                None => {}
             }
             args.extend([
                 "-c:v".to_string(),
@@ -717,43 +2387,257 @@ async fn start_render(
                 "aac".to_string(),
                 output_clone.to_string_lossy().to_string(),
             ]);
-            let _ = Command::new("ffmpeg").stealth().args(&args).status().await;
+            let _ = proc::ffmpeg(ProcClass::Render).stealth().args(&args).status().await;
+        }
+
+        let mut store = store_clone.write().await;
+        if let Some(job) = store.jobs.get_mut(&session_id_clone) {
+            job.progress = 1.0;
+            job.status = if output_clone.exists() {
+                "done".to_string()
+            } else {
+                "error".to_string()
+            };
+            job.output_path = if output_clone.exists() {
+                Some(output_clone)
+            } else {
+                None
+            };
+            done_hub.publish(
+                &session_id_clone,
+                CollabEvent::RenderProgress {
+                    progress: job.progress,
+                    status: job.status.clone(),
+                },
+            );
+        }
+    });
+
+    Json(json!({
+        "jobId": session_id,
+        "status": "started",
+        "draft": draft,
+    }))
+    .into_response()
+}
+
+/// Imported cues written out as an SRT for ffmpeg to burn or mux.
+struct SubtitleTrack {
+    path: PathBuf,
+    burn: bool,
+}
+
+async fn write_subtitle_track(
+    s: &EditorState,
+    session_id: &str,
+    option: &SubtitleRenderOption,
+) -> Result<SubtitleTrack, Response> {
+    let burn = match option.mode.as_deref().unwrap_or("burn") {
+        "burn" => true,
+        "mux" => false,
+        other => {
+            return Err(action_error(
+                StatusCode::BAD_REQUEST,
+                format!("unknown subtitle mode '{}' (use burn or mux)", other),
+            ))
+        }
+    };
+    if session_asset(s, session_id, Some(&option.asset_id)).await.is_none() {
+        return Err(action_error(StatusCode::BAD_REQUEST, "Subtitle asset not found"));
+    }
+    let Some(cues) = s.store.read().await.subtitles.get(&option.asset_id).map(|i| i.cues.clone()) else {
+        return Err(action_error(
+            StatusCode::BAD_REQUEST,
+            format!("asset {} is not an imported subtitle file", option.asset_id),
+        ));
+    };
+
+    let session_dir = s.session_dir(session_id);
+    let path = session_dir.join(format!("{}_edited.srt", option.asset_id));
+    let written = async {
+        tfs::create_dir_all(&session_dir).await?;
+        tfs::write(&path, transcription::generate_srt(&cues)).await
+    }
+    .await;
+    if let Err(e) = written {
+        error!("[EDITOR-API] Failed to write subtitle track: {}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR.into_response());
+    }
+    Ok(SubtitleTrack { path, burn })
+}
+
+/// Render the editor's clip arrangement (see `timeline_render`) into the
+/// session's render output, tracked as the session render job.
+async fn start_timeline_render(
+    s: &EditorState,
+    session_id: &str,
+    clips: Vec<timeline_render::TimelineClip>,
+    draft: bool,
+) -> Response {
+    let mut sources = HashMap::new();
+    let mut canvas = None;
+    for clip in &clips {
+        if sources.contains_key(&clip.asset_id) {
+            continue;
+        }
+        let Some(meta) = session_asset(s, session_id, Some(&clip.asset_id)).await else {
+            return action_error(
+                StatusCode::BAD_REQUEST,
+                format!("clip {} references unknown asset {}", clip.id, clip.asset_id),
+            );
+        };
+        if meta.kind == "subtitle" {
+            return action_error(
+                StatusCode::BAD_REQUEST,
+                format!("clip {} uses subtitle asset {}; pass it as `subtitles` instead", clip.id, clip.asset_id),
+            );
+        }
+        let Some(path) = find_asset_path(s, session_id, &meta.id).await else {
+            return action_error(StatusCode::NOT_FOUND, "Asset file missing");
+        };
+        // The frame follows the first clip that has picture
+        if canvas.is_none() && meta.kind != "audio" && meta.width > 0 && meta.height > 0 {
+            canvas = Some(timeline_render::Canvas {
+                width: meta.width & !1,
+                height: meta.height & !1,
+                fps: if meta.fps > 0.0 { meta.fps } else { 30.0 },
+            });
         }
+        let has_audio = match meta.kind.as_str() {
+            "audio" => true,
+            "video" => timeline_render::probe_has_audio(&path).await,
+            _ => false,
+        };
+        sources.insert(
+            clip.asset_id.clone(),
+            timeline_render::ClipSource {
+                path,
+                kind: meta.kind,
+                has_audio,
+            },
+        );
+    }
+
+    let mut canvas = canvas.unwrap_or_default();
+    if draft {
+        canvas = canvas.draft();
+    }
+    let plan = match timeline_render::build_plan(&clips, &sources, canvas) {
+        Ok(plan) => plan,
+        Err(e) => return action_error(StatusCode::BAD_REQUEST, e),
+    };
 
-        let mut store = store_clone.lock().unwrap();
-        if let Some(job) = store.jobs.get_mut(&session_id_clone) {
+    let session_dir = s.session_dir(session_id);
+    let output_path = render_output_path(&session_dir, std::path::Path::new("timeline"), render_op(draft), None);
+    let _ = tfs::create_dir_all(&session_dir).await;
+
+    s.store.write().await.jobs.insert(
+        session_id.to_string(),
+        RenderJob {
+            progress: 0.0,
+            status: "rendering".to_string(),
+            output_path: None,
+            error: None,
+            explanation: None,
+            artifacts: Vec::new(),
+            quality: None,
+            asset_id: None,
+        },
+    );
+    s.hub.publish(
+        session_id,
+        CollabEvent::RenderProgress {
+            progress: 0.0,
+            status: "rendering".to_string(),
+        },
+    );
+
+    let store = s.store.clone();
+    let hub = s.hub.clone();
+    let session = session_id.to_string();
+    tokio::spawn(async move {
+        let result = timeline_render::render(&plan, &output_path, &session_dir, draft).await;
+
+        let mut store = store.write().await;
+        if let Some(job) = store.jobs.get_mut(&session) {
             job.progress = 1.0;
-            job.status = if output_clone.exists() {
-                "done".to_string()
-            } else {
-                "error".to_string()
-            };
-            job.output_path = if output_clone.exists() {
-                Some(output_clone)
-            } else {
-                None
-            };
+            match result {
+                Ok(()) => {
+                    job.status = "done".to_string();
+                    job.output_path = Some(output_path);
+                }
+                Err(e) => {
+                    error!("[EDITOR-API] Timeline render failed: {}", e);
+                    job.status = "error".to_string();
+                    job.error = Some(e.to_string());
+                }
+            }
+            hub.publish(
+                &session,
+                CollabEvent::RenderProgress {
+                    progress: job.progress,
+                    status: job.status.clone(),
+                },
+            );
         }
     });
 
     Json(json!({
         "jobId": session_id,
         "status": "started",
+        "mode": "timeline",
+        "clips": clips.len(),
+        "draft": draft,
     }))
     .into_response()
 }
 
+/// Draft renders get their own name so a preview never overwrites an export.
+fn render_op(draft: bool) -> &'static str {
+    if draft {
+        "preview"
+    } else {
+        "render"
+    }
+}
+
+/// Where an editor output made from `source` goes: filed under the
+/// configured `outputs_dir` when there is one, else named by the output
+/// template inside the session folder.
+fn render_output_path(
+    session_dir: &std::path::Path,
+    source: &std::path::Path,
+    op: &str,
+    intent: Option<&str>,
+) -> PathBuf {
+    let name = source
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    // Asset files are stored as `<asset id>_<original name>`
+    let original = name.split_once('_').map_or(name.as_str(), |(_, rest)| rest);
+    NamingTemplate {
+        dir: Some(session_dir.to_path_buf()),
+        ..NamingTemplate::configured()
+    }
+    .output_for(std::path::Path::new(original), op, intent, "mp4")
+}
+
 async fn render_status(
     Path(session_id): Path<String>,
     State(s): State<EditorState>,
 ) -> impl IntoResponse {
-    let store = s.store.lock().unwrap();
+    let store = s.store.read().await;
     match store.jobs.get(&session_id) {
         Some(job) => Json(json!({
             "progress": job.progress,
             "status": job.status,
             "outputPath": job.output_path.as_ref().map(|p| p.to_string_lossy()),
             "error": job.error,
+            "explanation": job.explanation,
+            "artifacts": job.artifacts,
+            "quality": job.quality,
+            "assetId": job.asset_id,
         }))
         .into_response(),
         None => Json(json!({
@@ -764,43 +2648,774 @@ async fn render_status(
     }
 }
 
+/// The finished output of the session's last render, for the preview player.
+async fn render_output(
+    Path(session_id): Path<String>,
+    State(s): State<EditorState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let output = {
+        let store = s.store.read().await;
+        store
+            .jobs
+            .get(&session_id)
+            .and_then(|job| job.output_path.clone())
+    };
+    // serve_file_with_range answers 404 itself when the file is gone
+    match output {
+        Some(path) => serve_file_with_range(&path, &headers, "video/mp4")
+            .await
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Per-scene score breakdowns of the session's last smart edit, read from
+/// the edit plan beside the output. 404 until a smart edit has finished.
+async fn render_scenes(
+    Path(session_id): Path<String>,
+    State(s): State<EditorState>,
+) -> impl IntoResponse {
+    let output = {
+        let store = s.store.read().await;
+        store
+            .jobs
+            .get(&session_id)
+            .and_then(|job| job.output_path.clone())
+    };
+    match output.and_then(|path| EditPlan::load_for(&path)).and_then(|plan| plan.scoring) {
+        Some(scoring) => Json(scoring).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
 // ─── Project Save/Load ────────────────────────────────────────────────────────
+// Every save also lands in `history/` as a snapshot named
+// `<unix millis>_<revision>`, so a bad save or a crashed tab can be rolled
+// back with `load?snapshot=`. Only the newest `project_history` are kept.
+
+fn project_history_cap() -> usize {
+    crate::config::current().project_history
+}
+
+fn project_history_dir(s: &EditorState, session_id: &str) -> PathBuf {
+    s.session_dir(session_id).join("history")
+}
+
+/// Snapshot ids come from clients; only names this module writes are valid.
+fn valid_snapshot_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_digit() || c == '_')
+}
+
+/// Snapshot ids in `dir`, oldest first.
+async fn list_snapshots(dir: &std::path::Path) -> Vec<String> {
+    let mut ids = Vec::new();
+    if let Ok(mut entries) = tfs::read_dir(dir).await {
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if let Some(id) = name.strip_suffix(".json").filter(|id| valid_snapshot_id(id)) {
+                ids.push(id.to_string());
+            }
+        }
+    }
+    ids.sort();
+    ids
+}
+
+/// Write `body` as a new snapshot and prune the oldest beyond the cap.
+async fn write_snapshot(
+    s: &EditorState,
+    session_id: &str,
+    revision: u64,
+    body: &[u8],
+) -> std::io::Result<String> {
+    let dir = project_history_dir(s, session_id);
+    tfs::create_dir_all(&dir).await?;
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let id = format!("{:013}_{}", millis, revision);
+    tfs::write(dir.join(format!("{}.json", id)), body).await?;
+
+    let snapshots = list_snapshots(&dir).await;
+    let excess = snapshots.len().saturating_sub(project_history_cap());
+    for old in &snapshots[..excess] {
+        let _ = tfs::remove_file(dir.join(format!("{}.json", old))).await;
+    }
+    Ok(id)
+}
+
+#[derive(Deserialize)]
+pub struct LoadProjectQuery {
+    /// Load this history snapshot instead of the latest save.
+    pub snapshot: Option<String>,
+}
+
+/// Saves are last-writer-wins: every save overwrites the project file, bumps
+/// the session's revision, and tells connected peers who wrote it.
 async fn save_project(
     Path(session_id): Path<String>,
-    State(_s): State<EditorState>,
+    State(s): State<EditorState>,
+    headers: HeaderMap,
     body: axum::body::Bytes,
 ) -> impl IntoResponse {
-    let project_path = PathBuf::from(format!(
-        "cortex_cache/editor_sessions/{}/project.json",
-        session_id
-    ));
+    let project_path = s.session_dir(&session_id).join("project.json");
     if let Some(p) = project_path.parent() {
         let _ = tfs::create_dir_all(p).await;
     }
     match tfs::write(&project_path, &body).await {
-        Ok(_) => StatusCode::OK.into_response(),
+        Ok(_) => {
+            let revision = {
+                let mut store = s.store.write().await;
+                let rev = store.project_revisions.entry(session_id.clone()).or_insert(0);
+                *rev += 1;
+                *rev
+            };
+            let snapshot = match write_snapshot(&s, &session_id, revision, &body).await {
+                Ok(id) => Some(id),
+                Err(e) => {
+                    warn!("[EDITOR-API] Project snapshot for {} failed: {}", session_id, e);
+                    None
+                }
+            };
+            let by = client_name(&headers);
+            s.hub.publish(
+                &session_id,
+                CollabEvent::ProjectSaved {
+                    revision,
+                    by: by.clone(),
+                },
+            );
+            Json(json!({ "revision": revision, "savedBy": by, "snapshot": snapshot })).into_response()
+        }
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
 
 async fn load_project(
     Path(session_id): Path<String>,
-    State(_s): State<EditorState>,
+    State(s): State<EditorState>,
+    Query(query): Query<LoadProjectQuery>,
 ) -> impl IntoResponse {
-    let project_path = PathBuf::from(format!(
-        "cortex_cache/editor_sessions/{}/project.json",
-        session_id
-    ));
+    let project_path = match query.snapshot.as_deref() {
+        Some(id) if !valid_snapshot_id(id) => {
+            return action_error(StatusCode::BAD_REQUEST, "Invalid snapshot id")
+        }
+        Some(id) => project_history_dir(&s, &session_id).join(format!("{}.json", id)),
+        None => s.session_dir(&session_id).join("project.json"),
+    };
     match tfs::read_to_string(&project_path).await {
         Ok(content) => ([(header::CONTENT_TYPE, "application/json")], content).into_response(),
         Err(_) => StatusCode::NOT_FOUND.into_response(),
     }
 }
 
+/// Saved snapshots, newest first.
+async fn project_history(Path(session_id): Path<String>, State(s): State<EditorState>) -> impl IntoResponse {
+    let dir = project_history_dir(&s, &session_id);
+    let mut snapshots = Vec::new();
+    for id in list_snapshots(&dir).await.into_iter().rev() {
+        let (millis, revision) = id.split_once('_').unwrap_or((&id, "0"));
+        let size = tfs::metadata(dir.join(format!("{}.json", id)))
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+        snapshots.push(json!({
+            "id": id,
+            "savedAt": millis.parse::<u64>().unwrap_or(0),
+            "revision": revision.parse::<u64>().unwrap_or(0),
+            "size": size,
+        }));
+    }
+    Json(json!({ "snapshots": snapshots, "keep": project_history_cap() }))
+}
+
+// ─── Session Archives ─────────────────────────────────────────────────────────
+// `archive` streams a zip holding everything needed to resume a session on
+// another machine; `sessions/import` unpacks one into a new session:
+//
+//   session.json             assets, output and revision (format below)
+//   project.json             the last saved project
+//   output/                  the last render with its .srt, .plan.json and
+//                            .explain.md sidecars
+//   reports/qa.json          decode check of the output
+//   reports/provenance.json  where the output came from
+//   subtitles/<id>.json      imported subtitle cues, including edits
+//   assets/                  uploaded files, unless includeSources=false
+
+const SESSION_ARCHIVE_FORMAT: &str = "synoid-session";
+const SESSION_ARCHIVE_VERSION: u32 = 1;
+/// An archive may unpack to at most this many times the upload cap.
+const MAX_UNPACK_RATIO: u64 = 4;
+
+#[derive(Deserialize)]
+pub struct ArchiveQuery {
+    /// Pack the uploaded assets; on by default. Without them the archive
+    /// only carries the project, the output and the reports.
+    #[serde(rename = "includeSources")]
+    pub include_sources: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchivedAsset {
+    id: String,
+    filename: String,
+    #[serde(rename = "type")]
+    kind: String,
+    duration: f64,
+    width: u32,
+    height: u32,
+    size: u64,
+    fps: f64,
+    content_hash: String,
+    /// Entry holding the file; `None` when sources were left out.
+    file: Option<String>,
+    thumbnail: Option<String>,
+}
+
+/// `session.json` in a session archive.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionArchive {
+    format: String,
+    version: u32,
+    session_id: String,
+    archived_at: String,
+    project_revision: u64,
+    includes_sources: bool,
+    assets: Vec<ArchivedAsset>,
+    /// Entry of the last render's output.
+    output: Option<String>,
+}
+
+/// Hands the zip to the response body in `STREAM_CHUNK_BYTES` pieces. The
+/// channel is bounded, so a slow client slows the writer down instead of the
+/// archive piling up in memory.
+struct BodyWriter {
+    tx: tokio::sync::mpsc::Sender<std::io::Result<axum::body::Bytes>>,
+    buf: Vec<u8>,
+}
+
+impl std::io::Write for BodyWriter {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        self.buf.extend_from_slice(data);
+        if self.buf.len() >= STREAM_CHUNK_BYTES {
+            self.flush()?;
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        let chunk = axum::body::Bytes::from(std::mem::take(&mut self.buf));
+        self.tx.blocking_send(Ok(chunk)).map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::BrokenPipe, "client went away")
+        })
+    }
+}
+
+/// The sidecars written beside an editor output.
+fn output_sidecars(output: &std::path::Path) -> [PathBuf; 3] {
+    [
+        output.with_extension("srt"),
+        EditPlan::path_for(output),
+        explanation_path(output),
+    ]
+}
+
+/// Everything `write_session_archive` packs, gathered while the store is
+/// locked so the blocking writer never needs it.
+struct ArchiveSource {
+    manifest: SessionArchive,
+    /// Entry name → file on disk.
+    files: Vec<(String, PathBuf)>,
+    subtitles: Vec<(String, ImportedSubtitles)>,
+    project: PathBuf,
+    output: Option<PathBuf>,
+}
+
+/// Stream a zip of the session. With `?includeSources=false` the uploaded
+/// assets are left out (the archive then restores the project, output and
+/// reports only).
+async fn archive_session(
+    Path(session_id): Path<String>,
+    State(s): State<EditorState>,
+    Query(query): Query<ArchiveQuery>,
+) -> Response {
+    let include_sources = query.include_sources.unwrap_or(true);
+    let (asset_dir, assets, subtitles, output, revision) = {
+        let store = s.store.read().await;
+        let Some(session) = store.sessions.get(&session_id) else {
+            return action_error(StatusCode::NOT_FOUND, "Session not found");
+        };
+        let assets = store.assets.get(&session_id).cloned().unwrap_or_default();
+        let subtitles: Vec<(String, ImportedSubtitles)> = assets
+            .iter()
+            .filter_map(|a| Some((a.id.clone(), store.subtitles.get(&a.id)?.clone())))
+            .collect();
+        let output = store
+            .jobs
+            .get(&session_id)
+            .and_then(|job| job.output_path.clone());
+        let revision = store.project_revisions.get(&session_id).copied().unwrap_or(0);
+        (session.asset_dir.clone(), assets, subtitles, output, revision)
+    };
+
+    let mut stored = Vec::new();
+    if let Ok(mut dir) = tfs::read_dir(&asset_dir).await {
+        while let Ok(Some(entry)) = dir.next_entry().await {
+            stored.push(entry.path());
+        }
+    }
+    let stored_as = |name: &str| stored.iter().find(|p| p.file_name().is_some_and(|n| n == name));
+
+    let mut files = Vec::new();
+    let mut archived = Vec::new();
+    for asset in &assets {
+        let mut file = None;
+        let mut thumbnail = None;
+        if include_sources {
+            let prefix = format!("{}_", asset.id);
+            let thumb_name = format!("{}_thumb.jpg", asset.id);
+            let path = stored.iter().find(|p| {
+                p.file_name()
+                    .map(|n| n.to_string_lossy())
+                    .is_some_and(|n| n.starts_with(&prefix) && n != thumb_name)
+            });
+            if let Some(path) = path {
+                let entry = format!("assets/{}_{}", asset.id, archive::entry_name(&asset.filename));
+                files.push((entry.clone(), path.clone()));
+                file = Some(entry);
+            }
+            if let Some(path) = stored_as(&thumb_name) {
+                let entry = format!("assets/{}", thumb_name);
+                files.push((entry.clone(), path.clone()));
+                thumbnail = Some(entry);
+            }
+        }
+        archived.push(ArchivedAsset {
+            id: asset.id.clone(),
+            filename: asset.filename.clone(),
+            kind: asset.kind.clone(),
+            duration: asset.duration,
+            width: asset.width,
+            height: asset.height,
+            size: asset.size,
+            fps: asset.fps,
+            content_hash: asset.content_hash.clone(),
+            file,
+            thumbnail,
+        });
+    }
+
+    let output = match output {
+        Some(path) if tfs::try_exists(&path).await.unwrap_or(false) => Some(path),
+        _ => None,
+    };
+    let mut output_entry = None;
+    if let Some(path) = &output {
+        for (i, file) in std::iter::once(path.clone()).chain(output_sidecars(path)).enumerate() {
+            if !tfs::try_exists(&file).await.unwrap_or(false) {
+                continue;
+            }
+            let name = file.file_name().unwrap_or_default().to_string_lossy();
+            let entry = format!("output/{}", archive::entry_name(&name));
+            if i == 0 {
+                output_entry = Some(entry.clone());
+            }
+            files.push((entry, file));
+        }
+    }
+
+    let source = ArchiveSource {
+        manifest: SessionArchive {
+            format: SESSION_ARCHIVE_FORMAT.to_string(),
+            version: SESSION_ARCHIVE_VERSION,
+            session_id: session_id.clone(),
+            archived_at: chrono::Utc::now().to_rfc3339(),
+            project_revision: revision,
+            includes_sources: include_sources,
+            assets: archived,
+            output: output_entry,
+        },
+        files,
+        subtitles,
+        project: s.session_dir(&session_id).join("project.json"),
+        output,
+    };
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel(8);
+    let failed = tx.clone();
+    let log_id = session_id.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut zip = archive::ZipWriter::new_stream(BodyWriter {
+            tx,
+            buf: Vec::with_capacity(STREAM_CHUNK_BYTES),
+        });
+        let written = write_session_archive(&mut zip, source)
+            .and_then(|_| Ok(zip.finish()?.into_inner()))
+            .and_then(|mut body| std::io::Write::flush(&mut body));
+        if let Err(e) = written {
+            // The headers are gone already; cutting the body short is the
+            // only way left to tell the client
+            warn!("[EDITOR-API] Archive of session {} failed: {}", log_id, e);
+            let _ = failed.blocking_send(Err(e));
+        }
+    });
+    let body = futures_util::stream::poll_fn(move |cx| rx.poll_recv(cx));
+
+    info!(
+        "[EDITOR-API] Archiving session {} ({} sources)",
+        session_id,
+        if include_sources { "with" } else { "without" }
+    );
+    (
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"synoid-session-{}.zip\"", session_id),
+            ),
+        ],
+        Body::from_stream(body),
+    )
+        .into_response()
+}
+
+/// Write the session's archive entries. Blocking: runs the output's decode
+/// check and hashes it for the provenance report.
+fn write_session_archive(
+    zip: &mut archive::ZipWriter<archive::StreamWriter<BodyWriter>>,
+    source: ArchiveSource,
+) -> std::io::Result<()> {
+    fn to_json(value: &impl Serialize) -> std::io::Result<Vec<u8>> {
+        serde_json::to_vec_pretty(value).map_err(std::io::Error::other)
+    }
+
+    archive::add_bytes(zip, "session.json", &to_json(&source.manifest)?)?;
+    if source.project.is_file() {
+        archive::add_file(zip, "project.json", &source.project)?;
+    }
+    for (asset_id, imported) in &source.subtitles {
+        archive::add_bytes(zip, &format!("subtitles/{}.json", archive::entry_name(asset_id)), &to_json(imported)?)?;
+    }
+
+    if let Some(output) = &source.output {
+        let qa = crate::agent::project::RenderQa::check(output);
+        archive::add_bytes(zip, "reports/qa.json", &to_json(&qa)?)?;
+        let provenance = json!({
+            "synoidVersion": env!("CARGO_PKG_VERSION"),
+            "sessionId": source.manifest.session_id,
+            "archivedAt": source.manifest.archived_at,
+            "os": std::env::consts::OS,
+            "output": source.manifest.output,
+            "outputSha256": sha256_file(output).ok(),
+            "sources": source.manifest.assets.iter().map(|a| json!({
+                "assetId": a.id,
+                "filename": a.filename,
+                "sha256": a.content_hash,
+                "size": a.size,
+            })).collect::<Vec<_>>(),
+        });
+        archive::add_bytes(zip, "reports/provenance.json", &to_json(&provenance)?)?;
+    }
+
+    for (entry, path) in &source.files {
+        archive::add_file(zip, entry, path)?;
+    }
+    Ok(())
+}
+
+/// What `unpack_session_archive` put on disk.
+struct Unpacked {
+    manifest: SessionArchive,
+    /// Asset files on disk, not yet probed or registered.
+    assets: Vec<UnpackedAsset>,
+    /// Assets whose files were not in the archive.
+    missing: Vec<ArchivedAsset>,
+    output: Option<PathBuf>,
+    /// project.json as archived, still naming the old ids.
+    project: Option<String>,
+}
+
+/// An asset file unpacked under a fresh id.
+struct UnpackedAsset {
+    archived: ArchivedAsset,
+    id: String,
+    file: PathBuf,
+    /// Edited cues, for subtitle assets.
+    cues: Option<ImportedSubtitles>,
+}
+
+/// Unpack the archive at `zip_path` into `session`'s folder. Every restored
+/// asset gets a fresh id, so importing the same archive twice cannot
+/// collide. Refused before anything is written when its entries add up to
+/// more than `MAX_UNPACK_RATIO` times `max_bytes`. Blocking.
+fn unpack_session_archive(
+    zip_path: &std::path::Path,
+    session: &SessionState,
+    max_bytes: u64,
+) -> Result<Unpacked, String> {
+    let mut zip = archive::open(zip_path).map_err(|e| format!("not a zip archive ({})", e))?;
+    let limit = max_bytes.saturating_mul(MAX_UNPACK_RATIO);
+    if archive::unpacked_size(&mut zip).map_err(|e| e.to_string())? > limit {
+        return Err(format!("archive unpacks to more than {} MB", limit / (1024 * 1024)));
+    }
+    let manifest: SessionArchive = match archive::read_entry(&mut zip, "session.json").map_err(|e| e.to_string())? {
+        Some(data) => serde_json::from_slice(&data).map_err(|e| format!("bad session.json: {}", e))?,
+        None => return Err("not a SYNOID session archive (no session.json)".to_string()),
+    };
+    if manifest.format != SESSION_ARCHIVE_FORMAT || manifest.version > SESSION_ARCHIVE_VERSION {
+        return Err(format!("unsupported archive ({} v{})", manifest.format, manifest.version));
+    }
+    let session_dir = session.asset_dir.parent().unwrap_or(&session.asset_dir).to_path_buf();
+
+    let mut assets = Vec::new();
+    let mut missing = Vec::new();
+    let names = archive::entry_names(&zip);
+    let in_archive = |name: &Option<String>| name.clone().filter(|name| names.contains(name));
+    let mut referenced = std::collections::HashSet::new();
+    let duplicate = manifest
+        .assets
+        .iter()
+        .filter_map(|a| a.file.as_ref())
+        .find(|name| !referenced.insert(*name));
+    if let Some(name) = duplicate {
+        return Err(format!("entry '{}' belongs to more than one asset", name));
+    }
+    for asset in &manifest.assets {
+        let Some(entry) = in_archive(&asset.file) else {
+            missing.push(asset.clone());
+            continue;
+        };
+        let new_id = Uuid::new_v4().to_string();
+        let file = session
+            .asset_dir
+            .join(format!("{}_{}", new_id, sanitize_filename(&asset.filename)));
+        archive::extract(&mut zip, &entry, &file).map_err(|e| e.to_string())?;
+
+        // Edited cues travel as JSON; without them the file is parsed afresh
+        let cues_entry = format!("subtitles/{}.json", archive::entry_name(&asset.id));
+        let cues = match archive::read_entry(&mut zip, &cues_entry).map_err(|e| e.to_string())? {
+            Some(data) => Some(
+                serde_json::from_slice::<ImportedSubtitles>(&data)
+                    .map_err(|e| format!("bad {}: {}", cues_entry, e))?,
+            ),
+            None => None,
+        };
+        assets.push(UnpackedAsset {
+            archived: asset.clone(),
+            id: new_id,
+            file,
+            cues,
+        });
+    }
+
+    let mut output = None;
+    for entry in &names {
+        let Some(name) = entry.strip_prefix("output/") else {
+            continue;
+        };
+        if name.is_empty() || name.contains('/') {
+            continue;
+        }
+        let dest = session_dir.join(archive::entry_name(name));
+        archive::extract(&mut zip, entry, &dest).map_err(|e| e.to_string())?;
+        if manifest.output.as_deref() == Some(entry.as_str()) {
+            output = Some(dest);
+        }
+    }
+
+    let project = archive::read_entry(&mut zip, "project.json")
+        .map_err(|e| e.to_string())?
+        .map(|data| String::from_utf8_lossy(&data).into_owned());
+
+    Ok(Unpacked {
+        manifest,
+        assets,
+        missing,
+        output,
+        project,
+    })
+}
+
+/// Unpack a session archive, sent as the raw request body, into a new
+/// session. Assets, subtitle edits, the saved project and the last render
+/// come back; assets left out of the archive are listed in `missingAssets`
+/// for re-upload. Capped at `SYNOID_MAX_UPLOAD_MB` like uploads.
+async fn import_session(State(s): State<EditorState>, body: Body) -> Response {
+    let session = open_session(&s).await;
+    let zip_path = session.asset_dir.with_file_name("import.zip");
+    let result = match receive_archive(body, &zip_path, s.settings.max_upload_bytes).await {
+        Ok(()) => restore_session(&s, &session, &zip_path).await,
+        Err(response) => Err(response),
+    };
+    let _ = tfs::remove_file(&zip_path).await;
+    match result {
+        Ok(body) => Json(body).into_response(),
+        Err(response) => {
+            {
+                let mut store = s.store.write().await;
+                store.sessions.remove(&session.id);
+                store.assets.remove(&session.id);
+            }
+            if let Some(dir) = session.asset_dir.parent() {
+                let _ = tfs::remove_dir_all(dir).await;
+            }
+            response
+        }
+    }
+}
+
+/// Stream `body` to `path`, cut off at the upload cap.
+async fn receive_archive(body: Body, path: &std::path::Path, max_bytes: u64) -> Result<(), Response> {
+    use futures_util::StreamExt;
+
+    let write_failed = |e: std::io::Error| {
+        error!("[EDITOR-API] Failed to write archive: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR.into_response()
+    };
+    let mut file = tfs::File::create(path).await.map_err(write_failed)?;
+    let mut stream = body.into_data_stream();
+    let mut size = 0u64;
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| {
+            error!("[EDITOR-API] Archive read error: {}", e);
+            action_error(StatusCode::BAD_REQUEST, "Failed to read archive")
+        })?;
+        size += chunk.len() as u64;
+        if size > max_bytes {
+            return Err(action_error(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("Archive exceeds the {} MB limit", max_bytes / (1024 * 1024)),
+            ));
+        }
+        file.write_all(&chunk).await.map_err(write_failed)?;
+    }
+    file.flush().await.map_err(write_failed)?;
+    if size == 0 {
+        return Err(action_error(StatusCode::BAD_REQUEST, "No archive provided"));
+    }
+    Ok(())
+}
+
+/// Unpack the archive at `zip_path` into `session` and register what came
+/// back in the store.
+async fn restore_session(
+    s: &EditorState,
+    session: &SessionState,
+    zip_path: &std::path::Path,
+) -> Result<Value, Response> {
+    let unpacked = {
+        let zip_path = zip_path.to_path_buf();
+        let session = session.clone();
+        let max_bytes = s.settings.max_upload_bytes;
+        tokio::task::spawn_blocking(move || unpack_session_archive(&zip_path, &session, max_bytes))
+            .await
+            .map_err(|e| action_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .map_err(|e| {
+                warn!("[EDITOR-API] Rejected session archive: {}", e);
+                action_error(StatusCode::BAD_REQUEST, format!("Archive could not be imported: {}", e))
+            })?
+    };
+
+    // The archive's metadata is only a claim: hash, probe and register each
+    // file the way an upload is, so one that is not media is refused and
+    // identical files collapse into one asset
+    let session_id = session.id.as_str();
+    let mut ids = vec![(unpacked.manifest.session_id.clone(), session.id.clone())];
+    let mut metas: Vec<AssetMeta> = Vec::new();
+    let mut missing = unpacked.missing;
+    for asset in unpacked.assets {
+        let content_hash = hash_asset_file(&asset.file)
+            .await
+            .map_err(|e| action_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        let size = tfs::metadata(&asset.file).await.map(|m| m.len()).unwrap_or(0);
+        let filename = &asset.archived.filename;
+        match finalize_asset(s, session_id, &asset.id, filename, &asset.file, size, content_hash).await {
+            Ok(meta) => {
+                if let Some(cues) = asset.cues {
+                    s.store.write().await.subtitles.insert(meta.id.clone(), cues);
+                }
+                ids.push((asset.archived.id, meta.id.clone()));
+                if !metas.iter().any(|m| m.id == meta.id) {
+                    metas.push(meta);
+                }
+            }
+            Err(_) => missing.push(asset.archived),
+        }
+    }
+
+    // The project names assets, and their stream URLs the session, by id
+    let project = unpacked.project.map(|text| {
+        ids.iter()
+            .fold(text, |text, (old, new)| text.replace(old.as_str(), new))
+    });
+
+    // Saving bumps the revision, so a saved project is at least revision 1
+    let revision = unpacked.manifest.project_revision.max(1);
+    if let Some(project) = &project {
+        let path = session.asset_dir.with_file_name("project.json");
+        tfs::write(&path, project)
+            .await
+            .map_err(|e| action_error(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        if let Err(e) = write_snapshot(s, session_id, revision, project.as_bytes()).await {
+            warn!("[EDITOR-API] Project snapshot for {} failed: {}", session_id, e);
+        }
+    }
+    let explanation = match &unpacked.output {
+        Some(output) => tfs::read_to_string(explanation_path(output)).await.ok(),
+        None => None,
+    };
+
+    {
+        let mut store = s.store.write().await;
+        if project.is_some() {
+            store.project_revisions.insert(session_id.to_string(), revision);
+        }
+        if let Some(output) = &unpacked.output {
+            store.jobs.insert(
+                session_id.to_string(),
+                RenderJob {
+                    progress: 1.0,
+                    status: "done".to_string(),
+                    output_path: Some(output.clone()),
+                    explanation,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+    info!(
+        "[EDITOR-API] Imported session {} as {} ({} asset(s), {} missing)",
+        unpacked.manifest.session_id,
+        session_id,
+        metas.len(),
+        missing.len()
+    );
+
+    Ok(json!({
+        "id": session_id,
+        "status": "active",
+        "importedFrom": unpacked.manifest.session_id,
+        "assets": metas.iter().map(asset_json).collect::<Vec<_>>(),
+        "missingAssets": missing
+            .iter()
+            .map(|a| json!({ "id": a.id, "filename": a.filename, "type": a.kind }))
+            .collect::<Vec<_>>(),
+        "projectRevision": project.is_some().then_some(revision),
+        "outputPath": unpacked.output.as_ref().map(|p| p.to_string_lossy()),
+    }))
+}
+
 // ─── Helpers ──────────────────────────────────────────────────────────────────
 async fn find_asset_path(s: &EditorState, session_id: &str, asset_id: &str) -> Option<PathBuf> {
     let asset_dir = {
-        let store = s.store.lock().unwrap();
+        let store = s.store.read().await;
         store.sessions.get(session_id)?.asset_dir.clone()
     };
     let mut dir = tfs::read_dir(&asset_dir).await.ok()?;
@@ -814,7 +3429,7 @@ async fn find_asset_path(s: &EditorState, session_id: &str, asset_id: &str) -> O
 }
 
 async fn probe_video_meta(path: &PathBuf) -> (f64, u32, u32, f64) {
-    let output = Command::new("ffprobe")
+    let output = proc::ffprobe()
         .stealth()
         .args([
             "-v",
@@ -857,7 +3472,7 @@ fn parse_fps_ratio(s: &str) -> f64 {
 }
 
 async fn extract_thumbnail(input: &PathBuf, output: &PathBuf, time: f64) {
-    let _ = Command::new("ffmpeg")
+    let _ = proc::ffmpeg(ProcClass::Thumbnail)
         .stealth()
         .args(["-y", "-ss", &time.to_string(), "-i"])
         .arg(input)
@@ -867,13 +3482,50 @@ async fn extract_thumbnail(input: &PathBuf, output: &PathBuf, time: f64) {
         .await;
 }
 
-fn infer_asset_type(filename: &str) -> String {
-    let ext = filename.rsplit('.').next().unwrap_or("").to_lowercase();
-    match ext.as_str() {
-        "mp4" | "mkv" | "mov" | "avi" | "webm" => "video".to_string(),
-        "mp3" | "wav" | "aac" | "ogg" | "m4a" | "flac" => "audio".to_string(),
-        "jpg" | "jpeg" | "png" | "gif" | "webp" => "image".to_string(),
-        _ => "video".to_string(),
+/// Asset kind from the file's content, or `None` when ffprobe cannot parse
+/// it as media.
+async fn probe_media_kind(path: &PathBuf) -> Option<String> {
+    let output = proc::ffprobe()
+        .stealth()
+        .args([
+            "-v",
+            "error",
+            "-show_entries",
+            "stream=codec_type:stream_disposition=attached_pic:format=format_name",
+            "-of",
+            "json",
+        ])
+        .arg(path)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let v = serde_json::from_slice::<Value>(&output.stdout).ok()?;
+    media_kind_from_probe(&v).map(str::to_string)
+}
+
+/// Classify ffprobe JSON as "video", "audio" or "image". Cover art embedded
+/// in audio files shows up as a video stream and is ignored.
+fn media_kind_from_probe(v: &Value) -> Option<&'static str> {
+    let streams = v["streams"].as_array()?;
+    let has = |kind: &str| {
+        streams.iter().any(|s| {
+            s["codec_type"] == kind && s["disposition"]["attached_pic"].as_i64() != Some(1)
+        })
+    };
+    let format = v["format"]["format_name"].as_str().unwrap_or("");
+    if has("video") {
+        if format == "image2" || format == "gif" || format.ends_with("_pipe") {
+            Some("image")
+        } else {
+            Some("video")
+        }
+    } else if has("audio") {
+        Some("audio")
+    } else {
+        None
     }
 }
 
@@ -889,18 +3541,84 @@ fn sanitize_filename(name: &str) -> String {
         .collect()
 }
 
+/// Read size for streamed file bodies; memory per response stays at about
+/// this much no matter how large the asset is.
+const STREAM_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Cache validators for a file on disk. The ETag is size plus mtime, so a
+/// re-render in place gets a new tag without hashing the whole video.
+struct FileValidators {
+    etag: String,
+    last_modified: chrono::DateTime<chrono::Utc>,
+}
+
+impl FileValidators {
+    fn of(meta: &std::fs::Metadata) -> Self {
+        let modified = meta.modified().unwrap_or(std::time::UNIX_EPOCH);
+        let nanos = modified
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        Self {
+            etag: format!("\"{:x}-{:x}\"", meta.len(), nanos),
+            last_modified: modified.into(),
+        }
+    }
+
+    fn http_date(&self) -> String {
+        self.last_modified.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+    }
+
+    /// True when the request's `If-None-Match` or, failing that,
+    /// `If-Modified-Since` says the client's copy is current.
+    fn not_modified(&self, headers: &HeaderMap) -> bool {
+        if let Some(tags) = headers.get(header::IF_NONE_MATCH).and_then(|v| v.to_str().ok()) {
+            return tags.split(',').map(str::trim).any(|tag| {
+                tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == self.etag
+            });
+        }
+        headers
+            .get(header::IF_MODIFIED_SINCE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| chrono::DateTime::parse_from_rfc2822(v).ok())
+            .is_some_and(|since| self.last_modified.timestamp() <= since.timestamp())
+    }
+
+    fn apply(&self, builder: axum::http::response::Builder) -> axum::http::response::Builder {
+        builder
+            .header(header::ETAG, &self.etag)
+            .header(header::LAST_MODIFIED, self.http_date())
+            .header(header::CACHE_CONTROL, "no-cache")
+    }
+
+    fn not_modified_response(&self) -> Response {
+        self.apply(Response::builder().status(StatusCode::NOT_MODIFIED))
+            .body(Body::empty())
+            .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
+    }
+}
+
+/// Serve `path` as a streamed body, honouring a single `Range: bytes=` header.
 async fn serve_file_with_range(
     path: &PathBuf,
     headers: &HeaderMap,
     content_type: &str,
 ) -> Response {
     use axum::http::StatusCode;
+    use tokio::io::AsyncReadExt;
+    use tokio_util::io::ReaderStream;
 
-    let metadata = match tfs::metadata(path).await {
-        Ok(m) => m,
+    let mut file = match tfs::File::open(path).await {
+        Ok(f) => f,
+        Err(_) => return StatusCode::NOT_FOUND.into_response(),
+    };
+    let (total, validators) = match file.metadata().await {
+        Ok(m) => (m.len(), FileValidators::of(&m)),
         Err(_) => return StatusCode::NOT_FOUND.into_response(),
     };
-    let total = metadata.len();
+    if validators.not_modified(headers) {
+        return validators.not_modified_response();
+    }
     let content_type = content_type.to_string();
 
     // Parse Range header
@@ -912,11 +3630,22 @@ async fn serve_file_with_range(
                 .get(1)
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(total.saturating_sub(1))
-                .min(total - 1);
+                .min(total.saturating_sub(1));
+            if start >= total || start > end {
+                return Response::builder()
+                    .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                    .header(header::CONTENT_RANGE, format!("bytes */{}", total))
+                    .body(Body::empty())
+                    .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response());
+            }
             let length = end - start + 1;
 
-            let data = read_file_range(path, start, length).await;
-            return Response::builder()
+            if file.seek(std::io::SeekFrom::Start(start)).await.is_err() {
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+            let stream = ReaderStream::with_capacity(file.take(length), STREAM_CHUNK_BYTES);
+            return validators
+                .apply(Response::builder())
                 .status(StatusCode::PARTIAL_CONTENT)
                 .header(header::CONTENT_TYPE, content_type)
                 .header(
@@ -925,32 +3654,19 @@ async fn serve_file_with_range(
                 )
                 .header(header::CONTENT_LENGTH, length)
                 .header("Accept-Ranges", "bytes")
-                .body(Body::from(data))
+                .body(Body::from_stream(stream))
                 .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response());
         }
     }
 
     // Full file response
-    let data = tfs::read(path).await.unwrap_or_default();
-    Response::builder()
+    let stream = ReaderStream::with_capacity(file, STREAM_CHUNK_BYTES);
+    validators
+        .apply(Response::builder())
         .status(StatusCode::OK)
         .header(header::CONTENT_TYPE, content_type)
         .header(header::CONTENT_LENGTH, total)
         .header("Accept-Ranges", "bytes")
-        .body(Body::from(data))
+        .body(Body::from_stream(stream))
         .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
 }
-
-async fn read_file_range(path: &PathBuf, start: u64, length: u64) -> Vec<u8> {
-    use std::io::Read;
-    use std::io::Seek;
-    let mut file = match std::fs::File::open(path) {
-        Ok(f) => f,
-        Err(_) => return vec![],
-    };
-    let _ = file.seek(std::io::SeekFrom::Start(start));
-    let mut buf = vec![0u8; length as usize];
-    let n = file.read(&mut buf).unwrap_or(0);
-    buf.truncate(n);
-    buf
-}
@@ -0,0 +1,240 @@
+// SYNOID Event Bus — process-wide notifications between subsystems
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// `AgentCore` owns one bus. The edit queue and editor API publish job
+// lifecycle events, the health monitor its heartbeats, the autonomous
+// learner its cycles and the sentinel any integrity violation. The GUI, the
+// editor API's SSE stream and the metrics endpoint subscribe instead of
+// polling. Where `CollabHub` fans out per editor session, this bus carries
+// everything in the process.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+/// Events buffered before slow subscribers start lagging.
+const BUS_CAPACITY: usize = 256;
+
+/// Which subsystem ran a job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobOrigin {
+    /// `VideoEditorQueue` (CLI `embody`, the GUI's edit button); the job id
+    /// is the queue's job UUID.
+    Queue,
+    /// An editor API render or AI edit; the job id is the editor session.
+    Editor,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SynoidEvent {
+    JobStarted {
+        origin: JobOrigin,
+        job: String,
+        label: String,
+    },
+    /// `progress` is 0.0–1.0.
+    JobProgress {
+        origin: JobOrigin,
+        job: String,
+        progress: f32,
+        status: String,
+    },
+    JobDone {
+        origin: JobOrigin,
+        job: String,
+        ok: bool,
+        detail: String,
+    },
+    /// One health monitor heartbeat.
+    HealthTick {
+        heartbeat: u64,
+        uptime_secs: u64,
+        memory_ok: bool,
+        disk_ok: bool,
+    },
+    /// The autonomous learner finished a cycle on `topic`.
+    LearnerCycle { cycle: usize, topic: String },
+    /// A watched file changed under the sentinel.
+    IntegrityViolation { detail: String },
+}
+
+/// Cheap to clone; every clone publishes into the same channel.
+#[derive(Clone)]
+pub struct EventBus {
+    tx: broadcast::Sender<SynoidEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(BUS_CAPACITY);
+        Self { tx }
+    }
+
+    /// Send `event` to every current subscriber; with none it is dropped.
+    pub fn publish(&self, event: SynoidEvent) {
+        let _ = self.tx.send(event);
+    }
+
+    /// Events published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<SynoidEvent> {
+        self.tx.subscribe()
+    }
+}
+
+/// Running totals behind `/api/metrics`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsSnapshot {
+    pub jobs_started: u64,
+    pub jobs_succeeded: u64,
+    pub jobs_failed: u64,
+    pub jobs_active: u64,
+    pub health_ticks: u64,
+    pub uptime_secs: u64,
+    pub memory_ok: bool,
+    pub disk_ok: bool,
+    pub learner_cycles: u64,
+    pub integrity_violations: u64,
+    /// Events the collector fell too far behind to see.
+    pub events_missed: u64,
+}
+
+/// Folds bus events into a `MetricsSnapshot`.
+#[derive(Default)]
+pub struct EventMetrics {
+    totals: Mutex<MetricsSnapshot>,
+}
+
+impl EventMetrics {
+    /// Subscribe to `bus` and keep counting in a background task.
+    pub fn track(bus: &EventBus) -> Arc<Self> {
+        let metrics = Arc::new(Self {
+            totals: Mutex::new(MetricsSnapshot {
+                memory_ok: true,
+                disk_ok: true,
+                ..Default::default()
+            }),
+        });
+        let mut rx = bus.subscribe();
+        let collector = metrics.clone();
+        tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => collector.record(&event),
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        collector
+                            .totals
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .events_missed += n
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+        metrics
+    }
+
+    pub fn record(&self, event: &SynoidEvent) {
+        let mut m = self.totals.lock().unwrap_or_else(|e| e.into_inner());
+        match event {
+            SynoidEvent::JobStarted { .. } => {
+                m.jobs_started += 1;
+                m.jobs_active += 1;
+            }
+            SynoidEvent::JobProgress { .. } => {}
+            SynoidEvent::JobDone { ok, .. } => {
+                if *ok {
+                    m.jobs_succeeded += 1;
+                } else {
+                    m.jobs_failed += 1;
+                }
+                m.jobs_active = m.jobs_active.saturating_sub(1);
+            }
+            SynoidEvent::HealthTick {
+                uptime_secs,
+                memory_ok,
+                disk_ok,
+                ..
+            } => {
+                m.health_ticks += 1;
+                m.uptime_secs = *uptime_secs;
+                m.memory_ok = *memory_ok;
+                m.disk_ok = *disk_ok;
+            }
+            SynoidEvent::LearnerCycle { .. } => m.learner_cycles += 1,
+            SynoidEvent::IntegrityViolation { .. } => m.integrity_violations += 1,
+        }
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.totals
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn an_event_reaches_every_subscriber() {
+        let bus = EventBus::new();
+        let mut gui = bus.subscribe();
+        let mut sse = bus.clone().subscribe();
+
+        let event = SynoidEvent::JobStarted {
+            origin: JobOrigin::Queue,
+            job: "42".into(),
+            label: "remove silence".into(),
+        };
+        bus.publish(event.clone());
+
+        assert_eq!(gui.recv().await.unwrap(), event);
+        assert_eq!(sse.recv().await.unwrap(), event);
+    }
+
+    #[test]
+    fn metrics_count_jobs_until_they_finish() {
+        let metrics = EventMetrics::default();
+        let job = |n: &str| SynoidEvent::JobStarted {
+            origin: JobOrigin::Editor,
+            job: n.into(),
+            label: String::new(),
+        };
+        let done = |n: &str, ok| SynoidEvent::JobDone {
+            origin: JobOrigin::Editor,
+            job: n.into(),
+            ok,
+            detail: String::new(),
+        };
+        metrics.record(&job("a"));
+        metrics.record(&job("b"));
+        metrics.record(&done("a", true));
+        metrics.record(&SynoidEvent::IntegrityViolation {
+            detail: "config.json modified".into(),
+        });
+
+        let m = metrics.snapshot();
+        assert_eq!((m.jobs_started, m.jobs_succeeded, m.jobs_active), (2, 1, 1));
+        assert_eq!(m.integrity_violations, 1);
+
+        metrics.record(&done("b", false));
+        assert_eq!(metrics.snapshot().jobs_failed, 1);
+        assert_eq!(metrics.snapshot().jobs_active, 0);
+
+        let json = serde_json::to_value(job("c")).unwrap();
+        assert_eq!(json["type"], "job_started");
+        assert_eq!(json["origin"], "editor");
+    }
+}
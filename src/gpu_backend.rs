@@ -7,9 +7,19 @@
 // Neuroplasticity Integration: The Brain's adaptive speed multiplier
 // tunes CUDA batch sizes, thread counts, and FFmpeg presets so the
 // system gets faster as it learns.
+//
+// GPU Slots: consumer cards run a limited number of NVENC sessions well, so
+// GPU encodes hold a slot for their whole run and wait when none is free.
+// CPU-only jobs never take a slot, and probes/thumbnails stay in the process
+// governor's own classes, so a long render cannot starve them.
 
+use crate::agent::engines::proc;
 use crate::agent::engines::process_utils::CommandExt;
-use std::process::Command;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::time::Instant;
+use tokio::sync::Notify;
 use tracing::{info, warn};
 
 /// GPU Backend Selection
@@ -74,6 +84,8 @@ impl Default for CudaAccelConfig {
 /// GPU Context for unified processing
 pub struct GpuContext {
     pub backend: GpuBackend,
+    /// NVIDIA GPUs found; 0 on the CPU backend.
+    pub devices: usize,
 }
 
 impl GpuContext {
@@ -92,12 +104,14 @@ impl GpuContext {
         );
         Self {
             backend: GpuBackend::Cpu { threads },
+            devices: 0,
         }
     }
 
     /// Try to detect NVIDIA GPU via nvidia-smi
     fn try_nvenc() -> Option<Self> {
-        let output = Command::new("nvidia-smi")
+        // Unmanaged: a hardware query, not media work
+        let output = proc::blocking_unmanaged("nvidia-smi")
             .stealth()
             .args(["--query-gpu=name,driver_version", "--format=csv,noheader"])
             .output()
@@ -107,16 +121,18 @@ impl GpuContext {
             return None;
         }
 
+        // One line per GPU; the first one names the backend
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let parts: Vec<&str> = stdout.trim().split(',').collect();
+        let devices = stdout.trim().lines().count();
+        let parts: Vec<&str> = stdout.trim().lines().next()?.split(',').collect();
 
         if parts.len() >= 2 {
             let name = parts[0].trim().to_string();
             let driver_version = parts[1].trim().to_string();
 
             info!(
-                "[GPU] ✓ NVIDIA GPU detected: {} (Driver {})",
-                name, driver_version
+                "[GPU] ✓ NVIDIA GPU detected: {} (Driver {}), {} device(s)",
+                name, driver_version, devices
             );
             info!("[GPU] FFmpeg NVENC hardware encoding enabled");
 
@@ -125,12 +141,24 @@ impl GpuContext {
                     name,
                     driver_version,
                 },
+                devices,
             });
         }
 
         None
     }
 
+    /// Concurrent GPU encodes allowed: one NVENC session per GPU unless the
+    /// `gpu_slots` setting says otherwise. Always 0 on the CPU backend.
+    pub fn encode_slots(&self) -> usize {
+        if !self.has_gpu() {
+            return 0;
+        }
+        crate::config::current()
+            .gpu_slots
+            .unwrap_or(self.devices.max(1))
+    }
+
     /// Check if we have GPU acceleration available
     pub fn has_gpu(&self) -> bool {
         matches!(self.backend, GpuBackend::NvencGpu { .. })
@@ -246,6 +274,186 @@ pub async fn get_gpu_context() -> &'static GpuContext {
     GPU_CONTEXT.get_or_init(|| ctx)
 }
 
+// ---------------------------------------------------------------------------
+// GPU Slots — one encode per NVENC session, everyone else waits in line
+// ---------------------------------------------------------------------------
+
+/// One slot as shown in the queue status.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct GpuSlotStatus {
+    pub slot: usize,
+    /// Who is encoding in it (e.g. `session 1f2e…`), if anyone.
+    pub holder: Option<String>,
+    pub held_secs: f64,
+}
+
+/// Every slot plus the jobs waiting for one, oldest first.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct GpuSlotsStatus {
+    pub slots: Vec<GpuSlotStatus>,
+    pub waiting: Vec<String>,
+}
+
+struct SlotState {
+    holders: Vec<Option<(String, Instant)>>,
+    /// Waiters by ticket, so slots are handed out first come, first served.
+    waiting: BTreeMap<u64, String>,
+    next_ticket: u64,
+}
+
+pub struct GpuSlots {
+    state: Mutex<SlotState>,
+    notify: Notify,
+}
+
+impl GpuSlots {
+    pub fn new(count: usize) -> Self {
+        Self {
+            state: Mutex::new(SlotState {
+                holders: vec![None; count],
+                waiting: BTreeMap::new(),
+                next_ticket: 0,
+            }),
+            notify: Notify::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SlotState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn count(&self) -> usize {
+        self.lock().holders.len()
+    }
+
+    /// Every slot is held, so the next `acquire` waits its turn.
+    pub fn is_full(&self) -> bool {
+        self.lock().holders.iter().all(Option::is_some)
+    }
+
+    /// Wait for a free slot, in arrival order. With no slots at all (CPU-only)
+    /// this never returns; use `acquire_encode_slot` for the checked path.
+    pub async fn acquire(self: &Arc<Self>, holder: &str) -> GpuSlot {
+        let ticket = {
+            let mut state = self.lock();
+            let ticket = state.next_ticket;
+            state.next_ticket += 1;
+            state.waiting.insert(ticket, holder.to_string());
+            ticket
+        };
+        let mut waiter = SlotWaiter {
+            slots: self,
+            ticket,
+            granted: false,
+        };
+        loop {
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            {
+                let mut state = self.lock();
+                let first = state.waiting.keys().next() == Some(&ticket);
+                if let Some(index) = state.holders.iter().position(Option::is_none).filter(|_| first) {
+                    state.waiting.remove(&ticket);
+                    state.holders[index] = Some((holder.to_string(), Instant::now()));
+                    waiter.granted = true;
+                    drop(state);
+                    // The next in line may fit in another free slot
+                    self.notify.notify_waiters();
+                    info!("[GPU] 🎟️ Slot {} → {}", index, holder);
+                    return GpuSlot {
+                        slots: self.clone(),
+                        index,
+                    };
+                }
+            }
+            notified.await;
+        }
+    }
+
+    pub fn status(&self) -> GpuSlotsStatus {
+        let state = self.lock();
+        GpuSlotsStatus {
+            slots: state
+                .holders
+                .iter()
+                .enumerate()
+                .map(|(slot, h)| GpuSlotStatus {
+                    slot,
+                    holder: h.as_ref().map(|(name, _)| name.clone()),
+                    held_secs: h.as_ref().map_or(0.0, |(_, since)| since.elapsed().as_secs_f64()),
+                })
+                .collect(),
+            waiting: state.waiting.values().cloned().collect(),
+        }
+    }
+
+    fn release(&self, index: usize) {
+        self.lock().holders[index] = None;
+        self.notify.notify_waiters();
+    }
+}
+
+/// Drops an abandoned place in line when an `acquire` future is cancelled.
+struct SlotWaiter<'a> {
+    slots: &'a GpuSlots,
+    ticket: u64,
+    granted: bool,
+}
+
+impl Drop for SlotWaiter<'_> {
+    fn drop(&mut self) {
+        if !self.granted {
+            self.slots.lock().waiting.remove(&self.ticket);
+            self.slots.notify.notify_waiters();
+        }
+    }
+}
+
+/// A held GPU encode slot, freed on drop.
+pub struct GpuSlot {
+    slots: Arc<GpuSlots>,
+    index: usize,
+}
+
+impl GpuSlot {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl Drop for GpuSlot {
+    fn drop(&mut self) {
+        self.slots.release(self.index);
+    }
+}
+
+/// The process-wide slots, sized from the detected GPU(s).
+pub async fn gpu_slots() -> &'static Arc<GpuSlots> {
+    static SLOTS: OnceLock<Arc<GpuSlots>> = OnceLock::new();
+    if let Some(slots) = SLOTS.get() {
+        return slots;
+    }
+    let count = get_gpu_context().await.encode_slots();
+    SLOTS.get_or_init(|| {
+        info!("[GPU] {} encode slot(s)", count);
+        Arc::new(GpuSlots::new(count))
+    })
+}
+
+/// Progress message logged by an encode that has to wait for a slot.
+pub const WAITING_FOR_SLOT: &str = "[GPU] ⏳ Waiting for a GPU encode slot...";
+
+/// A slot for a GPU encode by `holder`, waiting for one if all are taken.
+/// `None` straight away on CPU-only machines, which have nothing to share.
+pub async fn acquire_encode_slot(holder: &str) -> Option<GpuSlot> {
+    let slots = gpu_slots().await;
+    if slots.count() == 0 {
+        return None;
+    }
+    Some(slots.acquire(holder).await)
+}
+
 /// Print GPU + Neuroplasticity combined status (for CLI `gpu` command)
 pub async fn print_gpu_status() {
     let ctx = get_gpu_context().await;
@@ -312,6 +520,7 @@ mod tests {
     fn test_cuda_accel_config_baseline() {
         let ctx = GpuContext {
             backend: GpuBackend::Cpu { threads: 8 },
+            devices: 0,
         };
         let cfg = ctx.cuda_accel_config(1.0);
         assert_eq!(cfg.batch_size, 4);
@@ -326,6 +535,7 @@ mod tests {
                 name: "RTX 5080".to_string(),
                 driver_version: "570.0".to_string(),
             },
+            devices: 1,
         };
 
         // 4× speed brain
@@ -339,4 +549,49 @@ mod tests {
         assert_eq!(cfg.batch_size, 128); // 8 * 16
         assert_eq!(cfg.ffmpeg_preset, "p1");
     }
+
+    #[tokio::test]
+    async fn gpu_slots_queue_in_order_without_blocking_probes() {
+        use crate::agent::engines::proc::{Governor, Priority, ProcClass};
+        use std::time::Duration;
+
+        let slots = Arc::new(GpuSlots::new(1));
+        let render = slots.acquire("session a").await;
+
+        let waiting = {
+            let slots = slots.clone();
+            tokio::spawn(async move { slots.acquire("session b").await.index() })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let status = slots.status();
+        assert_eq!(status.slots[0].holder.as_deref(), Some("session a"));
+        assert_eq!(status.waiting, vec!["session b".to_string()]);
+
+        // A probe goes through the governor and never waits on a GPU slot
+        let governor = Arc::new(Governor::new(|_| 1));
+        let probe = tokio::time::timeout(
+            Duration::from_millis(50),
+            governor.acquire(ProcClass::Probe, Priority::Normal),
+        )
+        .await;
+        assert!(probe.is_ok());
+
+        // A cancelled waiter gives up its place in line
+        let cancelled = tokio::time::timeout(Duration::from_millis(20), slots.acquire("session c")).await;
+        assert!(cancelled.is_err());
+        assert_eq!(slots.status().waiting.len(), 1);
+
+        drop(render);
+        assert_eq!(waiting.await.unwrap(), 0);
+        assert_eq!(slots.status().slots[0].holder, None);
+    }
+
+    #[test]
+    fn cpu_backend_has_no_encode_slots() {
+        let ctx = GpuContext {
+            backend: GpuBackend::Cpu { threads: 8 },
+            devices: 0,
+        };
+        assert_eq!(ctx.encode_slots(), 0);
+    }
 }
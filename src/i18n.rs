@@ -0,0 +1,121 @@
+// SYNOID GUI strings by locale
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Each locale is a flat JSON map of key → text under `locales/`, built into
+// the binary. The locale comes from `locale` / SYNOID_LOCALE, else the
+// system's LANG, and is fixed for the session. A key a locale lacks falls
+// back to English, and one English lacks shows as the key itself, so a
+// missing translation is visible but never breaks a panel.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+const LOCALES: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.json")),
+    ("es", include_str!("../locales/es.json")),
+    ("de", include_str!("../locales/de.json")),
+];
+
+type Table = HashMap<String, String>;
+
+fn tables() -> &'static HashMap<&'static str, Table> {
+    static TABLES: OnceLock<HashMap<&'static str, Table>> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        LOCALES
+            .iter()
+            .map(|(code, json)| {
+                let table = serde_json::from_str(json)
+                    .unwrap_or_else(|e| panic!("locales/{}.json is invalid: {}", code, e));
+                (*code, table)
+            })
+            .collect()
+    })
+}
+
+/// The supported locale named by `configured`, else by the LANG-style
+/// variables read through `var`; English when neither names one.
+pub fn pick_locale(configured: Option<&str>, var: impl Fn(&str) -> Option<String>) -> &'static str {
+    let wanted = configured
+        .map(str::to_string)
+        .or_else(|| {
+            ["LC_ALL", "LC_MESSAGES", "LANG"]
+                .iter()
+                .find_map(|name| var(name))
+        })
+        .unwrap_or_default();
+    // "es_ES.UTF-8" and "de-AT" name the language by their first two letters
+    let language = wanted.get(..2).unwrap_or("").to_ascii_lowercase();
+    LOCALES
+        .iter()
+        .map(|(code, _)| *code)
+        .find(|code| *code == language)
+        .unwrap_or("en")
+}
+
+/// The session's locale.
+pub fn locale() -> &'static str {
+    static LOCALE: OnceLock<&'static str> = OnceLock::new();
+    LOCALE.get_or_init(|| {
+        pick_locale(crate::config::current().locale.as_deref(), |name| {
+            std::env::var(name).ok().filter(|v| !v.is_empty())
+        })
+    })
+}
+
+/// `key`'s text in the session's locale.
+pub fn tr(key: &str) -> &str {
+    tr_in(locale(), key)
+}
+
+/// `key`'s text in `locale`.
+pub fn tr_in<'a>(locale: &str, key: &'a str) -> &'a str {
+    let tables = tables();
+    let lookup = |code: &str| tables.get(code).and_then(|table| table.get(key));
+    lookup(locale)
+        .or_else(|| lookup("en"))
+        .map(String::as_str)
+        .unwrap_or(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_locale_translates_every_english_key() {
+        let tables = tables();
+        let english = &tables["en"];
+        for (code, table) in tables {
+            let mut missing: Vec<_> = english.keys().filter(|k| !table.contains_key(*k)).collect();
+            let mut extra: Vec<_> = table.keys().filter(|k| !english.contains_key(*k)).collect();
+            missing.sort();
+            extra.sort();
+            assert!(
+                missing.is_empty(),
+                "locales/{}.json lacks {:?}",
+                code,
+                missing
+            );
+            assert!(
+                extra.is_empty(),
+                "locales/{}.json has unknown keys {:?}",
+                code,
+                extra
+            );
+        }
+
+        assert_eq!(tr_in("es", "nav.mixer"), "Mezclador");
+        assert_eq!(tr_in("fr", "nav.mixer"), "Mixer");
+        assert_eq!(tr_in("de", "no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn locale_comes_from_config_then_lang() {
+        let lang =
+            |value: &'static str| move |name: &str| (name == "LANG").then(|| value.to_string());
+        assert_eq!(pick_locale(None, lang("es_ES.UTF-8")), "es");
+        assert_eq!(pick_locale(Some("de-AT"), lang("es_ES.UTF-8")), "de");
+        assert_eq!(pick_locale(None, lang("fr_FR.UTF-8")), "en");
+        assert_eq!(pick_locale(None, |_| None), "en");
+    }
+}
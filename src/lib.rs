@@ -1,8 +1,14 @@
 pub mod agent;
+pub mod collab;
+pub mod config;
 pub mod editor_api;
 pub mod engine;
+pub mod events;
 pub mod gpu_backend;
+pub mod i18n;
+pub mod logging;
 pub mod net;
+pub mod open_with;
 pub mod server;
 pub mod state;
 pub mod window;
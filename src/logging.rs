@@ -0,0 +1,222 @@
+// SYNOID Logging — console output plus daily-rotated log files
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// Every event goes to the console as before and, unless `log_dir` is empty,
+// to `<log_dir>/synoid.<date>.log`, rotated at midnight. Long-running modes
+// (`serve`, `autonomous`, the GUI) leave a trail that outlives the terminal.
+// `log_format = json` writes one JSON object per event to both, for log
+// aggregators. The console is stderr, so stdout carries only command
+// results; `--quiet` and `-v`/`-vv` set its level, while the file always
+// keeps at least INFO.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::fmt::{self, MakeWriter};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{Layer, Registry};
+
+pub const LOG_FILE_PREFIX: &str = "synoid";
+
+/// How events are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines.
+    #[default]
+    Text,
+    /// One JSON object per line.
+    Json,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(format!("Unknown log format '{}' (expected text or json)", other)),
+        }
+    }
+}
+
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// Console level for `--quiet` and the number of `-v` flags: errors only,
+/// INFO by default, then DEBUG and TRACE.
+pub fn console_level(quiet: bool, verbose: u8) -> LevelFilter {
+    if quiet {
+        return LevelFilter::ERROR;
+    }
+    match verbose {
+        0 => LevelFilter::INFO,
+        1 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
+
+/// `level` for SYNOID, but graphics and decoder crates stay at errors even
+/// when verbose; their debug output drowns everything else.
+fn targets(level: LevelFilter) -> Targets {
+    let quiet = ["wgpu_core", "wgpu_hal", "naga", "winit", "symphonia", "egui_wgpu"];
+    quiet
+        .iter()
+        .fold(Targets::new().with_default(level), |targets, target| {
+            targets.with_target(*target, level.min(LevelFilter::ERROR))
+        })
+        .with_target("sctk_adwaita", LevelFilter::OFF)
+}
+
+/// A fmt layer writing `format` to `writer`. Colour codes only make sense on
+/// a terminal, so `ansi` is off for files.
+pub fn fmt_layer<W>(format: LogFormat, writer: W, ansi: bool) -> BoxedLayer
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => fmt::layer().with_writer(writer).with_ansi(ansi).boxed(),
+        LogFormat::Json => fmt::layer().json().with_writer(writer).boxed(),
+    }
+}
+
+/// The console layer at `level`, plus a file layer when `log_dir` is set.
+/// Keep the guard alive for as long as events should reach the file;
+/// dropping it flushes what is buffered.
+pub fn subscriber(
+    log_dir: Option<&Path>,
+    format: LogFormat,
+    level: LevelFilter,
+) -> Result<
+    (impl tracing::Subscriber + Send + Sync, Option<WorkerGuard>),
+    Box<dyn std::error::Error + Send + Sync>,
+> {
+    let mut layers = vec![fmt_layer(format, std::io::stderr, true)
+        .with_filter(targets(level))
+        .boxed()];
+    let guard = match log_dir {
+        Some(dir) => {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Cannot create {:?}: {}", dir, e))?;
+            let appender = RollingFileAppender::builder()
+                .rotation(Rotation::DAILY)
+                .filename_prefix(LOG_FILE_PREFIX)
+                .filename_suffix("log")
+                .build(dir)
+                .map_err(|e| format!("Cannot log to {:?}: {}", dir, e))?;
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            layers.push(
+                fmt_layer(format, writer, false)
+                    .with_filter(targets(level.max(LevelFilter::INFO)))
+                    .boxed(),
+            );
+            Some(guard)
+        }
+        None => None,
+    };
+    Ok((tracing_subscriber::registry().with(layers), guard))
+}
+
+/// Install the global subscriber. An unusable log directory falls back to
+/// console-only logging rather than stopping the kernel.
+pub fn init(log_dir: &str, format: LogFormat, level: LevelFilter) -> Option<WorkerGuard> {
+    let dir = (!log_dir.is_empty()).then(|| Path::new(log_dir));
+    match subscriber(dir, format, level) {
+        Ok((subscriber, guard)) => {
+            let _ = subscriber.try_init();
+            guard
+        }
+        Err(e) => {
+            let _ = tracing_subscriber::registry()
+                .with(fmt_layer(format, std::io::stderr, true).with_filter(targets(level)))
+                .try_init();
+            tracing::warn!("⚠️ File logging disabled: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn writes_a_log_file_into_the_configured_directory() {
+        let dir = std::env::temp_dir().join(format!("synoid_logs_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let (subscriber, guard) = subscriber(Some(&dir), LogFormat::Text, LevelFilter::ERROR).unwrap();
+        // A quiet console still leaves INFO in the file
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("log file check");
+            tracing::debug!("debug stays out");
+        });
+        drop(guard);
+
+        let files: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(files.len(), 1, "{:?}", files);
+        assert!(files[0].starts_with(LOG_FILE_PREFIX) && files[0].ends_with(".log"));
+        let contents = std::fs::read_to_string(dir.join(&files[0])).unwrap();
+        assert!(contents.contains("log file check"));
+        assert!(!contents.contains("debug stays out"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn json_format_writes_one_object_per_event() {
+        let buf = Arc::new(Mutex::new(Vec::new()));
+        let sink = buf.clone();
+        let writer = move || Capture(sink.clone());
+        let subscriber = tracing_subscriber::registry()
+            .with(fmt_layer(LogFormat::Json, writer, false))
+            .with(LevelFilter::INFO);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(job = "render", progress = 0.5, "json \"quoted\" check");
+        });
+
+        let out = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 1, "{}", out);
+        let event: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(event["level"], "INFO");
+        assert_eq!(event["fields"]["message"], "json \"quoted\" check");
+        assert_eq!(event["fields"]["job"], "render");
+        assert_eq!(event["fields"]["progress"], 0.5);
+
+        assert_eq!("JSON".parse::<LogFormat>(), Ok(LogFormat::Json));
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn quiet_and_verbose_flags_pick_the_console_level() {
+        assert_eq!(console_level(false, 0), LevelFilter::INFO);
+        assert_eq!(console_level(false, 1), LevelFilter::DEBUG);
+        assert_eq!(console_level(false, 3), LevelFilter::TRACE);
+        assert_eq!(console_level(true, 2), LevelFilter::ERROR);
+
+        let verbose = targets(LevelFilter::TRACE);
+        assert!(verbose.would_enable("synoid_core::agent", &tracing::Level::TRACE));
+        assert!(!verbose.would_enable("wgpu_core::device", &tracing::Level::WARN));
+        assert!(verbose.would_enable("wgpu_core::device", &tracing::Level::ERROR));
+    }
+
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Capture {
+        fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(data);
+            Ok(data.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+}
@@ -3,20 +3,80 @@
 
 use synoid_core::agent;
 use synoid_core::agent::core::AgentCore;
+use synoid_core::agent::doctor::{self, CheckArea, CheckStatus};
+use synoid_core::agent::output_naming;
+use synoid_core::agent::post_render::{self, PostRenderAction};
+use synoid_core::agent::source_tools::DownloadOptions;
+use synoid_core::config::ConfigLayer;
+use synoid_core::logging::LogFormat;
+use synoid_core::open_with::{self, Claim};
 use synoid_core::window;
 
-use clap::{Parser, Subcommand};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{CommandFactory, Parser, Subcommand};
 use dotenv::dotenv;
-use std::path::PathBuf;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 #[derive(Parser)]
 #[command(name = "synoid-core")]
 #[command(about = "SYNOID Agentic Kernel", long_about = None)]
 struct Cli {
+    /// JSON config file (default: $SYNOID_CONFIG); flags and SYNOID_* variables override it
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Ollama endpoint (overrides SYNOID_API_URL)
+    #[arg(long, global = true)]
+    api_url: Option<String>,
+
+    /// Reasoning model (overrides SYNOID_MODEL)
+    #[arg(long, global = true)]
+    model: Option<String>,
+
+    /// Print SYNOID's version and those of ffmpeg, yt-dlp, the Whisper model,
+    /// Ollama and the GPU driver, flagging anything missing or outdated
+    #[arg(long)]
+    version_detail: bool,
+
+    /// Log output: human-readable text or one JSON object per event
+    /// (overrides SYNOID_LOG_FORMAT)
+    #[arg(
+        long,
+        global = true,
+        value_name = "FORMAT",
+        value_parser = PossibleValuesParser::new(["text", "json"])
+            .map(|s| s.parse::<LogFormat>().expect("checked by PossibleValuesParser"))
+    )]
+    log_format: Option<LogFormat>,
+
+    /// Write outputs without --output beside their input, ignoring
+    /// outputs_dir / SYNOID_OUTPUTS_DIR
+    #[arg(long, global = true)]
+    here: bool,
+
+    /// After youtube, embody or process write their output: any of reveal,
+    /// copy-path, copy-summary (comma-separated), or none. Overrides
+    /// after_render and SYNOID_AFTER_RENDER
+    #[arg(long = "then", global = true, value_name = "ACTIONS", value_parser = post_render::parse_list)]
+    then: Option<PostRenderList>,
+
+    /// Keep stdout to the command's result and stderr to errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// More log detail on stderr: -v for debug, -vv for trace
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
     #[command(subcommand)]
-    command: Commands,
+    command: Option<Commands>,
+
+    /// Video to open in the GUI Editor ("Open with SYNOID")
+    #[arg(value_name = "FILE", allow_hyphen_values = true)]
+    open: Vec<OsString>,
 }
 
 #[derive(Subcommand)]
@@ -49,6 +109,18 @@ enum Commands {
         /// Browser to borrow cookies from for authentication
         #[arg(long)]
         login: Option<String>,
+
+        /// Cap download bandwidth (yt-dlp syntax, e.g. 2M or 500K)
+        #[arg(long)]
+        limit_rate: Option<String>,
+
+        /// Route downloads through a proxy (defaults to SYNOID_PROXY)
+        #[arg(long)]
+        proxy: Option<String>,
+
+        /// What to print on success: the output path, or a JSON object
+        #[arg(long, value_enum, default_value_t = ResultFormat::Text)]
+        format: ResultFormat,
     },
 
     /// Autonomous Research: Find tutorials and resources
@@ -60,6 +132,24 @@ enum Commands {
         /// Number of results to find
         #[arg(short, long, default_value = "5")]
         limit: usize,
+
+        /// Search backend: youtube (yt-dlp, then Invidious, then cached
+        /// results) | yt-dlp | invidious | cache | local
+        #[arg(long, default_value = "youtube")]
+        provider: String,
+
+        /// Read the subtitles of the top results and write a research report
+        /// to cortex_cache/research/
+        #[arg(long)]
+        deep: bool,
+
+        /// With --deep: results to read (at most 8)
+        #[arg(long, default_value_t = agent::deep_research::DEFAULT_DEEP_ITEMS)]
+        deep_limit: usize,
+
+        /// With --deep: also read each video's top comments
+        #[arg(long)]
+        comments: bool,
     },
 
     /// Trim/Clip a video
@@ -68,13 +158,17 @@ enum Commands {
         #[arg(short, long)]
         input: PathBuf,
 
-        /// Start time in seconds
-        #[arg(short, long)]
+        /// Start time: hh:mm:ss.ms, mm:ss or seconds
+        #[arg(short, long, value_parser = agent::timecode::parse)]
         start: f64,
 
-        /// Duration in seconds
-        #[arg(short, long)]
-        duration: f64,
+        /// Clip length: hh:mm:ss.ms, mm:ss or seconds
+        #[arg(short, long, value_parser = agent::timecode::parse, conflicts_with = "end", required_unless_present = "end")]
+        duration: Option<f64>,
+
+        /// End time instead of a duration: hh:mm:ss.ms, mm:ss or seconds
+        #[arg(short, long, value_parser = agent::timecode::parse)]
+        end: Option<f64>,
 
         /// Output path (optional)
         #[arg(short, long)]
@@ -94,6 +188,58 @@ enum Commands {
         /// Output path (optional)
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Compare the result against the source (PSNR/SSIM/VMAF)
+        #[arg(long)]
+        measure_quality: bool,
+
+        /// Smallest frame height to step down to when the bitrate is thin (e.g. 720 or 720p)
+        #[arg(long, value_parser = parse_min_resolution, default_value = "480")]
+        min_resolution: u32,
+
+        /// Keep the source resolution however low the bitrate gets
+        #[arg(long)]
+        no_downscale: bool,
+    },
+
+    /// Upscale a video (seedvr2, realesrgan, lanczos, vector)
+    Upscale {
+        /// Input video path
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Output path (optional)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Backend: seedvr2, realesrgan, lanczos, vector
+        #[arg(short, long, default_value = "seedvr2")]
+        mode: String,
+
+        /// Target width in pixels
+        #[arg(long, default_value_t = 3840)]
+        width: u32,
+
+        /// Target height in pixels (0 = keep aspect ratio)
+        #[arg(long, default_value_t = 0)]
+        height: u32,
+
+        /// Compare the result against the source (PSNR/SSIM/VMAF)
+        #[arg(long)]
+        measure_quality: bool,
+    },
+
+    /// Compare a candidate video against a reference (PSNR/SSIM/VMAF)
+    Compare {
+        /// Reference video
+        reference: PathBuf,
+
+        /// Candidate video (scaled to the reference's resolution)
+        candidate: PathBuf,
+
+        /// Write the full report as JSON
+        #[arg(long)]
+        json: Option<PathBuf>,
     },
 
     /// Combine video with external audio
@@ -109,6 +255,121 @@ enum Commands {
         /// Output video path
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Use the audio as recorded instead of lining it up with the
+        /// video's own audio
+        #[arg(long)]
+        no_sync: bool,
+    },
+
+    /// Extract the audio track as mp3, aac, flac, wav or opus
+    ExtractAudio {
+        /// Input video path
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Output audio path
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Audio format: mp3, aac, flac, wav or opus
+        #[arg(short, long, default_value = "mp3")]
+        format: agent::production_tools::AudioFormat,
+
+        /// Bitrate for lossy formats (e.g. 320k)
+        #[arg(short, long)]
+        bitrate: Option<String>,
+    },
+
+    /// Export each audio stream as its own WAV, for remixing
+    Stems {
+        /// Input video or audio path
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Directory for the stems (default: <input>_stems next to the input)
+        #[arg(short, long)]
+        output_dir: Option<PathBuf>,
+    },
+
+    /// Report loudness, peaks and clipping of a file's audio
+    AudioStats {
+        /// Input video or audio path
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Remove the background behind people (alpha matte or replacement)
+    RemoveBg {
+        /// Input video path
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Output path (.mov keeps transparency)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// "transparent", a colour name/hex, or a background image path
+        #[arg(short, long, default_value = "transparent")]
+        background: String,
+
+        /// Temporal mask smoothing (0.0 = off, 0.9 = heavy)
+        #[arg(long, default_value_t = 0.6)]
+        smoothing: f32,
+    },
+
+    /// Convert video frames to SVG (resolution-independent artistic look)
+    Vectorize {
+        /// Input video path
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Output directory for SVG frames
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Start of the range in seconds
+        #[arg(long)]
+        from: Option<f64>,
+
+        /// End of the range in seconds
+        #[arg(long)]
+        to: Option<f64>,
+
+        /// Keep every Nth frame
+        #[arg(long, default_value_t = 1)]
+        every: u32,
+
+        /// Parallel tracing workers (0 = all cores)
+        #[arg(long, default_value_t = 0)]
+        jobs: usize,
+    },
+
+    /// Export an upload-ready file for a social platform
+    Publish {
+        /// Input video path
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Target platform: shorts, tiktok, reels, youtube, discord, twitter
+        #[arg(short, long)]
+        platform: String,
+
+        /// Output path
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Smallest frame height to step down to when the bitrate is thin (e.g. 720 or 720p)
+        #[arg(long, value_parser = parse_min_resolution, default_value = "480")]
+        min_resolution: u32,
+
+        /// Keep the source resolution however low the bitrate gets
+        #[arg(long)]
+        no_downscale: bool,
     },
 
     /// Run the Brain directly
@@ -123,17 +384,61 @@ enum Commands {
         #[arg(short, long)]
         input: PathBuf,
 
-        /// User intent/instruction
-        #[arg(short, long)]
+        /// User intent/instruction; "A, then B" runs several edits in order
+        #[arg(long)]
         intent: String,
 
         /// Output path
         #[arg(short, long)]
         output: PathBuf,
 
-        /// Print command without executing
+        /// Print the editing plan (intent, strategy, predicted scene keeps)
+        /// without scanning or rendering
         #[arg(long)]
         dry_run: bool,
+
+        /// Directory of sponsor logo templates (png/jpg); spans showing one are cut
+        #[arg(long)]
+        sponsor_logos: Option<PathBuf>,
+
+        /// Fast 480p preview render; the next full render reuses its cuts
+        #[arg(long)]
+        draft: bool,
+
+        /// With --draft, play long uncut stretches at 2x
+        #[arg(long, requires = "draft")]
+        draft_fast: bool,
+
+        /// Reproducible edit: heuristic intent parse, seeded choices, and
+        /// decisions replayed from the edit plan on later runs
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Video to put before the edit (overrides the configured intro)
+        #[arg(long)]
+        intro: Option<PathBuf>,
+
+        /// Video or image card to put after the edit (overrides the configured outro)
+        #[arg(long)]
+        outro: Option<PathBuf>,
+
+        /// Seconds over which the intro's sound fades into the first scene's
+        #[arg(long)]
+        intro_crossfade: Option<f64>,
+
+        /// Command or http(s) URL that rescores scenes (JSON in, JSON out;
+        /// see examples/external_scorer.py)
+        #[arg(long)]
+        external_scorer: Option<String>,
+
+        /// Edit an input SYNOID already edited like any other, burning
+        /// subtitles and normalizing audio again
+        #[arg(long)]
+        force_reprocess: bool,
+
+        /// What to print on success: the output path, or a JSON object
+        #[arg(long, value_enum, default_value_t = ResultFormat::Text)]
+        format: ResultFormat,
     },
 
     /// Learn a new editing style
@@ -154,9 +459,27 @@ enum Commands {
         input: PathBuf,
     },
 
+    /// Extract on-screen text (slides, code, UI) with timestamps
+    Ocr {
+        /// Input video
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Seconds between sampled frames
+        #[arg(long, default_value_t = 2.0)]
+        interval: f64,
+
+        /// Write the results as a searchable semantic index (JSON)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
     /// Check GPU status
     Gpu,
 
+    /// List the processing tools usable as `process` stages and Brain commands
+    Tools,
+
     /// Activate Cyberdefense Sentinel
     Guard {
         /// Monitor Mode (all/sys/file)
@@ -189,7 +512,8 @@ enum Commands {
         #[arg(short, long)]
         input: PathBuf,
 
-        /// Processing stages (comma-separated): transcribe,smart_edit,enhance,encode (or "all")
+        /// Processing stages (comma-separated): transcribe,smart_edit,enhance,encode (or "all"),
+        /// plus registered tools with params, e.g. trim:start=5;duration=10,grade:look=warm
         #[arg(long, default_value = "all")]
         stages: String,
 
@@ -208,6 +532,45 @@ enum Commands {
         /// Scale factor for upscaling (2.0 = 2x resolution)
         #[arg(long, default_value_t = 2.0)]
         scale: f64,
+
+        /// Fast 480p preview render; the next full render reuses its cuts
+        #[arg(long)]
+        draft: bool,
+
+        /// With --draft, play long uncut stretches at 2x
+        #[arg(long, requires = "draft")]
+        draft_fast: bool,
+
+        /// Reproducible edit: heuristic intent parse, seeded choices, and
+        /// decisions replayed from the edit plan on later runs
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Video to put before the edit (overrides the configured intro)
+        #[arg(long)]
+        intro: Option<PathBuf>,
+
+        /// Video or image card to put after the edit (overrides the configured outro)
+        #[arg(long)]
+        outro: Option<PathBuf>,
+
+        /// Seconds over which the intro's sound fades into the first scene's
+        #[arg(long)]
+        intro_crossfade: Option<f64>,
+
+        /// Command or http(s) URL that rescores scenes (JSON in, JSON out;
+        /// see examples/external_scorer.py)
+        #[arg(long)]
+        external_scorer: Option<String>,
+
+        /// Edit an input SYNOID already edited like any other, burning
+        /// subtitles and normalizing audio again
+        #[arg(long)]
+        force_reprocess: bool,
+
+        /// What to print on success: the output path, or a JSON object
+        #[arg(long, value_enum, default_value_t = ResultFormat::Text)]
+        format: ResultFormat,
     },
 
     /// Learn editing style from videos already in D:\SYNOID\Download (up to 10)
@@ -233,6 +596,16 @@ enum Commands {
         /// Optional port for instance isolation (e.g., 3001)
         #[arg(short, long)]
         port: Option<u16>,
+        /// Publish each cycle's plan (downloads, clones, fetches) and wait for approval
+        /// (POST /api/learner/approve, or write the plan's hash to learner_approved)
+        #[arg(long)]
+        supervised: bool,
+        /// Stop downloading once this many videos were fetched today
+        #[arg(long)]
+        max_downloads_per_day: Option<u32>,
+        /// Stop learning once this many minutes were spent processing today
+        #[arg(long)]
+        max_process_minutes_per_day: Option<f64>,
     },
 
     /// Start the Dashboard Web Server
@@ -319,6 +692,328 @@ enum Commands {
         #[arg(short, long)]
         input: PathBuf,
     },
+
+    /// Translate an existing SRT file without re-transcribing
+    TranslateSubs {
+        /// Input SRT path
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// Target language (e.g. "Spanish", "ja")
+        #[arg(short, long)]
+        lang: String,
+
+        /// Output SRT path (default: <input>.<lang>.srt)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// List completed clip/compress/edit operations
+    History {
+        /// Only show operations matching this text
+        #[arg(short, long)]
+        filter: Option<String>,
+
+        #[command(subcommand)]
+        action: Option<HistoryAction>,
+    },
+
+    /// Manage .synoid project files (sources, intents, plans and renders)
+    Project {
+        #[command(subcommand)]
+        action: ProjectAction,
+    },
+
+    /// Step through scored scenes in the terminal, toggle keep/cut, then render
+    Review {
+        /// Input video path
+        #[arg(short, long)]
+        input: PathBuf,
+
+        /// User intent/instruction
+        #[arg(long)]
+        intent: String,
+
+        /// Output path (default: from the output naming template)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Print how often each scoring factor decided a keep/cut, from an edit plan
+    Tune {
+        /// Edit plan (`<output>.plan.json`) or scoring report JSON
+        #[arg(short, long)]
+        input: PathBuf,
+    },
+
+    /// Walk through first-run setup: tools, Ollama model, directories, Whisper model
+    Setup {
+        /// Re-run a single step
+        #[arg(
+            long,
+            value_name = "STEP",
+            value_parser = PossibleValuesParser::new(["tools", "ollama", "dirs", "whisper"])
+                .map(|s| s.parse::<CheckArea>().expect("checked by PossibleValuesParser"))
+        )]
+        only: Option<CheckArea>,
+
+        /// Accept every default without prompting
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Check that tools, Ollama, directories and the Whisper model are ready
+    Doctor,
+
+    /// Run the benchmark suite and compare against a stored baseline
+    Bench {
+        /// Only the 1-minute fixture
+        #[arg(long)]
+        quick: bool,
+
+        /// Where to write the JSON report (default: <cache_dir>/bench/latest.json)
+        #[arg(long)]
+        json: Option<PathBuf>,
+
+        /// Report to compare against (default: <cache_dir>/bench/baseline.json)
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+
+        /// Store this run as the baseline
+        #[arg(long)]
+        save_baseline: bool,
+
+        /// Exit non-zero when a stage is more than this many percent slower than the baseline
+        #[arg(long)]
+        max_regression: Option<f64>,
+    },
+
+    /// Print a shell completion script, e.g. `synoid-core completions bash > synoid-core.bash`
+    Completions {
+        /// bash, zsh, fish, powershell or elvish
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProjectAction {
+    /// Create a project and make it the open one
+    New {
+        /// Project directory (".synoid" is appended when missing)
+        path: PathBuf,
+
+        /// Display name (default: directory name)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Source videos to reference and hash up front
+        #[arg(short, long)]
+        source: Vec<PathBuf>,
+    },
+
+    /// Open an existing project; Embody and Process record into it
+    Open {
+        path: PathBuf,
+    },
+
+    /// Show a project's sources, intents and renders (default: the open one)
+    Status {
+        path: Option<PathBuf>,
+    },
+
+    /// Stop recording into the open project
+    Close,
+
+    /// Pack a project, its outputs and (optionally) its sources into a zip
+    Archive {
+        /// Project to pack (default: the open one)
+        path: Option<PathBuf>,
+
+        /// Zip to write (default: "<project name>.zip")
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Leave the source videos out; the archive then only works where
+        /// they exist at the same paths
+        #[arg(long)]
+        no_sources: bool,
+    },
+
+    /// Unpack a project archive and open it
+    Import {
+        /// Zip made by `project archive`
+        archive: PathBuf,
+
+        /// Project directory to create (default: named after the archive)
+        #[arg(long)]
+        into: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum HistoryAction {
+    /// Run a recorded operation again, optionally changing parameters
+    Rerun {
+        /// Operation id from `history`
+        id: u64,
+
+        /// Override a parameter, e.g. --set duration=8 --set output=out2.mp4
+        #[arg(long = "set")]
+        set: Vec<String>,
+    },
+}
+
+/// How youtube, embody and process report their output on stdout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum ResultFormat {
+    /// The output path alone
+    Text,
+    /// One JSON object
+    Json,
+}
+
+/// The single stdout line a finished youtube, embody or process run
+/// leaves for scripts. `steps` lists every step's output when an embody
+/// request ran several.
+fn result_line(format: ResultFormat, command: &str, output: &Path, steps: &[PathBuf]) -> String {
+    match format {
+        ResultFormat::Text => output.display().to_string(),
+        ResultFormat::Json => {
+            let mut result = serde_json::json!({
+                "command": command,
+                "output": output,
+                "bytes": std::fs::metadata(output).map(|m| m.len()).ok(),
+            });
+            if steps.len() > 1 {
+                result["steps"] = serde_json::json!(steps);
+            }
+            result.to_string()
+        }
+    }
+}
+
+/// `--then`'s actions. An alias, so clap takes the list as one value rather
+/// than one action per occurrence.
+type PostRenderList = Vec<PostRenderAction>;
+
+/// Run the configured post-render actions on a finished output, after
+/// saying so if the edit had to run without a transcript.
+async fn after_render(output: &Path) {
+    if let Some(reason) = synoid_core::agent::transcription::take_unavailable_notice() {
+        synoid_core::agent::setup::offer_fallback_model(&reason).await;
+    }
+    let actions = &synoid_core::config::current().after_render;
+    for outcome in post_render::run(actions, output, true).await {
+        match outcome {
+            post_render::ActionOutcome::Failed(_) => warn!("{}", outcome.describe()),
+            _ => info!("{}", outcome.describe()),
+        }
+    }
+}
+
+/// The smart-edit flags of `embody` and `process`, as the command-line
+/// config layer's share of `config::current()`.
+fn command_layer(command: Option<&Commands>) -> ConfigLayer {
+    // An absent switch leaves the setting to the lower layers
+    let switch = |on: bool| on.then_some(true);
+    match command {
+        Some(Commands::Embody {
+            sponsor_logos,
+            draft,
+            draft_fast,
+            seed,
+            intro,
+            outro,
+            intro_crossfade,
+            external_scorer,
+            force_reprocess,
+            ..
+        }) => ConfigLayer {
+            sponsor_logos: sponsor_logos.as_ref().map(|d| d.to_string_lossy().into_owned()),
+            draft: switch(*draft),
+            draft_fast: switch(*draft_fast),
+            force_reprocess: switch(*force_reprocess),
+            seed: *seed,
+            intro: intro.as_ref().map(|p| p.to_string_lossy().into_owned()),
+            outro: outro.as_ref().map(|p| p.to_string_lossy().into_owned()),
+            intro_crossfade: *intro_crossfade,
+            external_scorer: external_scorer.clone(),
+            ..Default::default()
+        },
+        Some(Commands::Process {
+            draft,
+            draft_fast,
+            seed,
+            intro,
+            outro,
+            intro_crossfade,
+            external_scorer,
+            force_reprocess,
+            ..
+        }) => ConfigLayer {
+            draft: switch(*draft),
+            draft_fast: switch(*draft_fast),
+            force_reprocess: switch(*force_reprocess),
+            seed: *seed,
+            intro: intro.as_ref().map(|p| p.to_string_lossy().into_owned()),
+            outro: outro.as_ref().map(|p| p.to_string_lossy().into_owned()),
+            intro_crossfade: *intro_crossfade,
+            external_scorer: external_scorer.clone(),
+            ..Default::default()
+        },
+        Some(Commands::Gui { port }) => ConfigLayer {
+            instance_id: port_instance_id(Some(*port)),
+            ..Default::default()
+        },
+        Some(Commands::Autonomous {
+            port,
+            supervised,
+            max_downloads_per_day,
+            max_process_minutes_per_day,
+        }) => ConfigLayer {
+            instance_id: port_instance_id(*port),
+            learner_supervised: switch(*supervised),
+            learner_max_downloads: *max_downloads_per_day,
+            learner_max_process_minutes: *max_process_minutes_per_day,
+            ..Default::default()
+        },
+        Some(Commands::Gepa { port, .. }) => ConfigLayer {
+            instance_id: port_instance_id(*port),
+            ..Default::default()
+        },
+        _ => ConfigLayer::default(),
+    }
+}
+
+/// A port other than the default one isolates the instance's state files.
+fn port_instance_id(port: Option<u16>) -> Option<String> {
+    port.filter(|&p| p != 3000).map(|p| format!("_{}", p))
+}
+
+/// `--min-resolution` takes a frame height, with or without the "p".
+fn parse_min_resolution(s: &str) -> Result<u32, String> {
+    let height: u32 = s
+        .trim()
+        .trim_end_matches(['p', 'P'])
+        .parse()
+        .map_err(|_| format!("'{}' is not a frame height like 720 or 720p", s))?;
+    if height < 144 {
+        return Err(format!("{}p is too small to be useful", height));
+    }
+    Ok(height)
+}
+
+fn resolution_ladder(min_resolution: u32, no_downscale: bool) -> agent::production_tools::ResolutionLadder {
+    agent::production_tools::ResolutionLadder {
+        min_height: min_resolution,
+        enabled: !no_downscale,
+    }
+}
+
+/// Completion scripts cover every subcommand and flag of `Cli`.
+fn write_completions(shell: clap_complete::Shell, out: &mut dyn std::io::Write) {
+    clap_complete::generate(shell, &mut Cli::command(), "synoid-core", out);
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -344,7 +1039,53 @@ fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
 
 #[tokio::main]
 async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    tracing_subscriber::fmt::init();
+    let args = Cli::parse();
+
+    // Completion scripts go to stdout, so emit them before logging starts
+    if let Some(Commands::Completions { shell }) = args.command {
+        write_completions(shell, &mut std::io::stdout());
+        return Ok(());
+    }
+
+    synoid_core::config::init(
+        args.config.as_deref(),
+        ConfigLayer {
+            api_url: args.api_url.clone(),
+            model: args.model.clone(),
+            log_format: args.log_format,
+            // An empty value overrides the file and SYNOID_OUTPUTS_DIR
+            outputs_dir: args.here.then(String::new),
+            after_render: args.then.clone(),
+            ..command_layer(args.command.as_ref())
+        },
+    )?;
+
+    // Setup and doctor talk to the terminal; they need no kernel or log files
+    if args.version_detail {
+        let checks = doctor::run_area(CheckArea::Versions, &synoid_core::config::current()).await;
+        println!("{}", doctor::report(&checks));
+        return Ok(());
+    }
+    match args.command {
+        Some(Commands::Setup { only, yes }) => return agent::setup::run(only, yes).await,
+        Some(Commands::Doctor) => {
+            let checks = doctor::run_all(&synoid_core::config::current()).await;
+            println!("{}", doctor::report(&checks));
+            if checks.iter().any(|c| c.status == CheckStatus::Fail) {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    // Console plus daily log files; the guard flushes the file on drop
+    let log_config = synoid_core::config::current();
+    let log_guard = synoid_core::logging::init(
+        &log_config.log_dir,
+        log_config.log_format,
+        synoid_core::logging::console_level(args.quiet, args.verbose),
+    );
 
     // Global panic handler: log panics instead of crashing silently
     std::panic::set_hook(Box::new(|panic_info| {
@@ -374,20 +1115,56 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         );
     }
 
-    let args = Cli::parse();
+    // A bare file argument comes from a file association: open it in the GUI
+    let (command, open) = match args.command {
+        Some(command) => (command, None),
+        None => match open_with::path_from_args(&args.open) {
+            Some(path) => (Commands::Gui { port: 3000 }, Some(path)),
+            None => {
+                Cli::command().print_help()?;
+                return Ok(());
+            }
+        },
+    };
+    if let Some(path) = &open {
+        if !open_with::has_display() {
+            eprintln!("No display to open {} in the GUI. Edit it headless with:", path.display());
+            eprintln!(
+                "  synoid-core embody --input \"{}\" --intent \"<what to make>\" --output <output.mp4>",
+                path.display()
+            );
+            return Ok(());
+        }
+    }
 
-    // Auto-set Instance ID based on port if in GUI mode and not already set
-    if let Commands::Gui { port } = args.command {
-        if port != 3000 && std::env::var("SYNOID_INSTANCE_ID").is_err() {
-            let instance_id = format!("_{}", port);
-            std::env::set_var("SYNOID_INSTANCE_ID", &instance_id);
+    // A non-default `--port` isolated the instance through the config layer
+    if let Commands::Gui { port } = command {
+        if let Some(instance_id) = port_instance_id(Some(port)) {
             info!("🔷 Auto-Isolated Instance: '{}' (port {})", instance_id, port);
         }
     }
 
-    let api_url =
-        std::env::var("SYNOID_API_URL").unwrap_or("http://localhost:11434/v1".to_string());
-    let instance_id = std::env::var("SYNOID_INSTANCE_ID").unwrap_or_else(|_| "default".to_string());
+    let api_url = synoid_core::config::current().api_url;
+    let instance_id = synoid_core::config::current()
+        .instance_id
+        .unwrap_or_else(|| "default".to_string());
+
+    // One GUI per instance: later launches hand their file to the running window
+    let gui_lock = if let Commands::Gui { .. } = command {
+        match open_with::claim_or_forward(&open_with::lock_path(&instance_id), open.as_deref()) {
+            Ok(Claim::Acquired(lock)) => Some(lock),
+            Ok(Claim::Forwarded) => {
+                info!("🖥️ SYNOID is already running for instance '{}'; handed over to it.", instance_id);
+                return Ok(());
+            }
+            Err(e) => {
+                tracing::warn!("⚠️ Could not take the GUI instance lock: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
 
     // Initialize the Ghost (Agent Core)
     let core = Arc::new(AgentCore::new(&api_url, &instance_id));
@@ -399,25 +1176,18 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         core.acceleration_status().await
     );
 
-    // Initialize Hive Mind (Ollama discovery) - Non-blocking background task
-    let core_for_hive = core.clone();
-    tokio::spawn(async move {
-        match core_for_hive.initialize_hive_mind().await {
-            Ok(_) => info!("🐝 Hive Mind Active: Connected to Ollama Neural Network"),
-            Err(e) => {
-                tracing::debug!("⚠️ Hive Mind Offline: {}", e);
-                tracing::debug!("⚠️ Continuing in degraded mode (Brain defaults only)");
-            }
-        }
-    });
-    match args.command {
+    // Initialize Hive Mind (Ollama discovery) - Non-blocking background task.
+    // With Ollama down the GUI and dashboard still start; only the LLM
+    // features wait for it.
+    core.connect_brain();
+    match command {
         Commands::Gui { port } => {
             use crate::agent::core_systems::health::HealthMonitor;
             use synoid_core::server;
             use synoid_core::state::KernelState;
 
             // Start health monitor (heartbeat every 30 seconds)
-            let health = HealthMonitor::new(30);
+            let health = HealthMonitor::new(30).with_events(core.events.clone());
             let _health_shutdown = health.start();
             info!("🩺 Health Monitor started");
 
@@ -441,7 +1211,9 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             // Launch GUI (Blocking) — pass AgentCore
             info!("🖥️ Launching GUI Command Center...");
             let core_in_gui = core.clone();
-            let res = tokio::task::block_in_place(|| window::run_gui(core_in_gui));
+            let res = tokio::task::block_in_place(|| {
+                window::run_gui(core_in_gui, open, gui_lock.as_ref())
+            });
             if let Err(e) = res {
                 error!("GUI Error: {}", e);
             }
@@ -456,6 +1228,10 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
 
             // Force-exit to kill all spawned tokio tasks (server, hive mind poller, health monitor).
             // Without this, background tasks keep the process alive as a ghost.
+            // exit() skips destructors, so release the instance lock and
+            // flush the log file first.
+            drop(gui_lock);
+            drop(log_guard);
             std::process::exit(0);
         }
         Commands::Youtube {
@@ -464,48 +1240,279 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             output,
             chunk_minutes,
             login,
+            limit_rate,
+            proxy,
+            format,
         } => {
-            core.process_youtube_intent(
-                &url,
-                &intent,
-                output,
-                login.as_deref(),
-                false,
-                chunk_minutes,
-                true,
-                true,
-            )
-            .await?;
+            let download_options = DownloadOptions::with_overrides(limit_rate, proxy);
+            let out_path = core
+                .process_youtube_intent(
+                    &url,
+                    &intent,
+                    output,
+                    login.as_deref(),
+                    &download_options,
+                    false,
+                    chunk_minutes,
+                    true,
+                    true,
+                )
+                .await?;
+            // The edit runs on the queue; report it only once it is written
+            core.editor_queue.wait_for_completion().await;
+            let failed = core.editor_queue.list_jobs_detailed().await.into_iter().find_map(|job| {
+                match job.status {
+                    agent::editor_queue::JobStatus::Failed(e) if job.output == out_path => Some(e),
+                    _ => None,
+                }
+            });
+            if let Some(e) = failed {
+                return Err(format!("Edit failed: {}", e).into());
+            }
+            println!("{}", result_line(format, "youtube", &out_path, &[]));
+            after_render(&out_path).await;
         }
-        Commands::Research { topic, limit } => {
-            core.process_research(&topic, limit).await?;
+        Commands::Research {
+            topic,
+            limit,
+            provider,
+            deep,
+            deep_limit,
+            comments,
+        } => {
+            let deep = deep.then_some(agent::deep_research::DeepOptions {
+                max_items: deep_limit,
+                comments,
+            });
+            core.process_research(&topic, limit, &provider, deep).await?;
         }
         Commands::Clip {
             input,
             start,
             duration,
+            end,
             output,
         } => {
+            let duration = agent::timecode::clip_length(start, duration, end)?;
             core.clip_video(&input, start, duration, output).await?;
         }
         Commands::Compress {
             input,
             size,
             output,
+            measure_quality,
+            min_resolution,
+            no_downscale,
+        } => {
+            let ladder = resolution_ladder(min_resolution, no_downscale);
+            core.compress_video(&input, size, output, measure_quality, ladder)
+                .await?;
+        }
+        Commands::Upscale {
+            input,
+            output,
+            mode,
+            width,
+            height,
+            measure_quality,
+        } => {
+            use synoid_core::agent::upscale_checkpoint::{format_eta, FrameProgress, RollingRate};
+            use synoid_core::agent::upscale_engine::{UpscaleConfig, UpscaleEngine, UpscaleMode};
+
+            let mode = match mode.to_lowercase().as_str() {
+                "vector" => UpscaleMode::Vector,
+                "realesrgan" | "esrgan" => UpscaleMode::RealEsrgan,
+                "lanczos" => UpscaleMode::Lanczos,
+                _ => UpscaleMode::SeedVR2,
+            };
+            let out_path =
+                output.unwrap_or_else(|| output_naming::default_output(&input, "upscaled", None, "mp4"));
+            let config = UpscaleConfig {
+                target_width: width,
+                target_height: height,
+                mode,
+                ..Default::default()
+            };
+
+            // Frame count, rolling fps and ETA every few seconds
+            let meter = std::sync::Mutex::new((
+                RollingRate::new(std::time::Duration::from_secs(60)),
+                None::<std::time::Instant>,
+            ));
+            let progress: FrameProgress = Arc::new(move |done, total| {
+                let now = std::time::Instant::now();
+                let mut meter = meter.lock().unwrap_or_else(|e| e.into_inner());
+                meter.0.record(now, done);
+                let due = meter.1.map_or(true, |last| now.duration_since(last).as_secs() >= 5);
+                if !due && done < total {
+                    return;
+                }
+                meter.1 = Some(now);
+                let pct = done as f64 * 100.0 / total.max(1) as f64;
+                match (meter.0.fps(), meter.0.eta(done, total)) {
+                    (Some(fps), Some(eta)) => info!(
+                        "[UPSCALE] {}/{} frames ({:.1}%) · {:.2} fps · ETA {}",
+                        done,
+                        total,
+                        pct,
+                        fps,
+                        format_eta(eta)
+                    ),
+                    _ => info!("[UPSCALE] {}/{} frames ({:.1}%)", done, total, pct),
+                }
+            });
+
+            match UpscaleEngine::upscale_with_progress(&input, &out_path, &config, Some(progress)).await {
+                Ok(()) => {
+                    println!("🔭 Upscaled ({}): {:?}", config.mode.label(), out_path);
+                    if measure_quality {
+                        // Compare at the source resolution: the upscale is scaled back down.
+                        match agent::production_tools::compare_quality(&input, &out_path).await {
+                            Ok(report) => println!("📏 Quality vs source: {}", report.summary()),
+                            Err(e) => error!("Quality measurement failed: {}", e),
+                        }
+                    }
+                }
+                Err(e) => error!("Upscale failed: {}", e),
+            }
+        }
+        Commands::Compare {
+            reference,
+            candidate,
+            json,
+        } => match agent::production_tools::compare_quality(&reference, &candidate).await {
+            Ok(report) => {
+                println!(
+                    "📏 {} (at {}x{})",
+                    report.summary(),
+                    report.compared_at.0,
+                    report.compared_at.1
+                );
+                for w in &report.windows {
+                    println!(
+                        "   {:>7.1}s +{:.0}s: PSNR {} | SSIM {} | VMAF {}",
+                        w.start,
+                        w.duration,
+                        w.scores.psnr.map(|v| format!("{:.2}", v)).unwrap_or("-".into()),
+                        w.scores.ssim.map(|v| format!("{:.4}", v)).unwrap_or("-".into()),
+                        w.scores.vmaf.map(|v| format!("{:.1}", v)).unwrap_or("-".into()),
+                    );
+                }
+                if let Some(path) = json {
+                    std::fs::write(&path, serde_json::to_string_pretty(&report)?)?;
+                    println!("💾 Report saved: {:?}", path);
+                }
+            }
+            Err(e) => error!("Compare failed: {}", e),
+        },
+        Commands::History { filter, action } => {
+            use agent::operation_history::{parse_override, OperationHistory};
+            let history = OperationHistory::for_instance(&core.instance_id);
+            match action {
+                None => {
+                    let records = history.load();
+                    let filter = filter.unwrap_or_default();
+                    let shown: Vec<_> = records.iter().filter(|r| r.matches(&filter)).collect();
+                    if shown.is_empty() {
+                        println!("📭 No operations recorded yet ({:?}).", history.path());
+                    }
+                    for record in shown {
+                        println!("{}", record.summary());
+                    }
+                }
+                Some(HistoryAction::Rerun { id, set }) => {
+                    let record = history
+                        .get(id)
+                        .ok_or_else(|| format!("No operation #{} in history", id))?;
+                    let overrides = set
+                        .iter()
+                        .map(|s| parse_override(s))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let record = record.with_overrides(&overrides)?;
+                    core.rerun_operation(&record).await?;
+                }
+            }
+        }
+        Commands::Project { action } => {
+            use agent::project::Project;
+            match action {
+                ProjectAction::New { path, name, source } => {
+                    let mut project = Project::create(&path, name.as_deref())?;
+                    for src in &source {
+                        let added = project.add_source(src)?;
+                        println!("🔗 {:?} ({}…)", added.path, &added.sha256[..12]);
+                    }
+                    project.save()?;
+                    Project::set_active(&core.instance_id, Some(project.root()))?;
+                    println!("📁 Created and opened project {:?}", project.root());
+                }
+                ProjectAction::Open { path } => {
+                    let project = Project::open(&path)?;
+                    Project::set_active(&core.instance_id, Some(project.root()))?;
+                    println!("📂 Opened project '{}' ({:?})", project.manifest.name, project.root());
+                }
+                ProjectAction::Status { path } => {
+                    let project = match path {
+                        Some(p) => Some(Project::open(&p)?),
+                        None => Project::active(&core.instance_id),
+                    };
+                    match project {
+                        Some(p) => print!("{}", p.status()),
+                        None => println!("📭 No project open. Use `project new` or `project open`."),
+                    }
+                }
+                ProjectAction::Close => {
+                    Project::set_active(&core.instance_id, None)?;
+                    println!("📁 Project closed.");
+                }
+                ProjectAction::Archive { path, output, no_sources } => {
+                    let project = match path {
+                        Some(p) => Project::open(&p)?,
+                        None => Project::active(&core.instance_id)
+                            .ok_or("No project open. Pass a project path or use `project open`.")?,
+                    };
+                    let output = output
+                        .unwrap_or_else(|| PathBuf::from(format!("{}.zip", project.manifest.name)));
+                    let summary = project.archive(&output, !no_sources)?;
+                    println!(
+                        "📦 Archived {} file(s), {:.1} MB → {:?}",
+                        summary.files,
+                        summary.bytes as f64 / 1_048_576.0,
+                        output
+                    );
+                }
+                ProjectAction::Import { archive, into } => {
+                    let into = into.unwrap_or_else(|| archive.with_extension(""));
+                    let project = Project::import_archive(&archive, &into)?;
+                    Project::set_active(&core.instance_id, Some(project.root()))?;
+                    println!("📂 Imported and opened project '{}' ({:?})", project.manifest.name, project.root());
+                }
+            }
+        }
+        Commands::Review {
+            input,
+            intent,
+            output,
         } => {
-            core.compress_video(&input, size, output).await?;
+            let out_path = output
+                .unwrap_or_else(|| output_naming::default_output(&input, "edited", Some(&intent), "mp4"));
+            let summary = agent::smart_editor::review::review(&input, &intent, &out_path).await?;
+            println!("{}", summary);
+        }
+        Commands::Tune { input } => {
+            let stats = agent::smart_editor::TuningStats::load(&input)?;
+            print!("{}", stats.to_table());
         }
         Commands::Combine {
             input,
             audio,
             output,
+            no_sync,
         } => {
-            let out_path = output.unwrap_or_else(|| {
-                let stem = input.file_stem().unwrap().to_string_lossy();
-                input.with_file_name(format!("{}_combined.mp4", stem))
-            });
+            let out_path =
+                output.unwrap_or_else(|| output_naming::default_output(&input, "combined", None, "mp4"));
 
-            match agent::production_tools::combine_av(&input, &audio, &out_path).await {
+            match agent::production_tools::combine_av(&input, &audio, &out_path, !no_sync).await {
                 Ok(res) => println!(
                     "🎹 Combine saved: {:?} ({:.2} MB)",
                     res.output_path, res.size_mb
@@ -513,6 +1520,163 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                 Err(e) => error!("Combine failed: {}", e),
             }
         }
+        Commands::ExtractAudio {
+            input,
+            output,
+            format,
+            bitrate,
+        } => {
+            let out_path = output.unwrap_or_else(|| {
+                output_naming::default_output(&input, "audio", None, format.extension())
+            });
+
+            match agent::production_tools::extract_audio(
+                &input,
+                &out_path,
+                format,
+                bitrate.as_deref(),
+            )
+            .await
+            {
+                Ok(path) => println!("🎧 Audio saved: {:?}", path),
+                Err(e) => error!("Audio extraction failed: {}", e),
+            }
+        }
+        Commands::Stems { input, output_dir } => {
+            let out_dir = output_dir.unwrap_or_else(|| {
+                let stem = input
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "audio".to_string());
+                input.with_file_name(format!("{}_stems", stem))
+            });
+
+            match agent::production_tools::export_stems(&input, &out_dir).await {
+                Ok(stems) => {
+                    println!("🎚️ Exported {} stem(s) to {:?}", stems.len(), out_dir);
+                    for stem in stems {
+                        println!("   {:?}", stem);
+                    }
+                }
+                Err(e) => error!("Stem export failed: {}", e),
+            }
+        }
+        Commands::AudioStats { input, json } => {
+            match agent::audio_tools::analyze_loudness(&input).await {
+                Ok(report) if json => {
+                    println!("{}", serde_json::to_string_pretty(&report)?);
+                }
+                Ok(report) => {
+                    println!("🔊 Audio stats for {:?}", input);
+                    println!("   Integrated: {:.1} LUFS", report.integrated_lufs);
+                    println!("   True peak:  {:.1} dBTP", report.true_peak_dbtp);
+                    println!("   Range:      {:.1} LU", report.loudness_range_lu);
+                    println!("   Peak:       {:.1} dBFS", report.peak_dbfs);
+                    println!("   RMS:        {:.1} dBFS", report.rms_dbfs);
+                    println!("   Clipped:    {} sample(s)", report.clipped_samples);
+                    for warning in report.warnings() {
+                        tracing::warn!("⚠️ {}", warning);
+                    }
+                    println!(
+                        "   Gain to -14 LUFS: {:+.1} dB",
+                        report.suggested_gain_db(-14.0)
+                    );
+                }
+                Err(e) => error!("Audio analysis failed: {}", e),
+            }
+        }
+        Commands::RemoveBg {
+            input,
+            output,
+            background,
+            smoothing,
+        } => {
+            use synoid_core::agent::matting::{MatteBackground, MattingConfig, MattingEngine};
+
+            let background = MatteBackground::parse(&background);
+            let out_path = output.unwrap_or_else(|| {
+                let ext = if background == MatteBackground::Transparent { "mov" } else { "mp4" };
+                output_naming::default_output(&input, "nobg", None, ext)
+            });
+            let config = MattingConfig {
+                background,
+                smoothing,
+                ..Default::default()
+            };
+
+            match MattingEngine::remove_background(&input, &out_path, &config).await {
+                Ok(()) => println!("✂️ Background removed: {:?}", out_path),
+                Err(e) => error!("Background removal failed: {}", e),
+            }
+        }
+        Commands::Vectorize {
+            input,
+            output,
+            from,
+            to,
+            every,
+            jobs,
+        } => {
+            use synoid_core::agent::vector_engine::{self, VectorConfig};
+
+            let out_dir = output.unwrap_or_else(|| vector_engine::default_output_dir(&input));
+            let config = VectorConfig {
+                start_secs: from,
+                end_secs: to,
+                frame_step: every.max(1),
+                jobs,
+                ..Default::default()
+            };
+            let progress: Arc<dyn Fn(usize, usize) + Send + Sync> =
+                Arc::new(|done, total| {
+                    if done % 25 == 0 || done == total {
+                        info!("[VECTOR] {}/{} frames", done, total);
+                    }
+                });
+
+            match vector_engine::vectorize_video(&input, &out_dir, &config, Some(progress)).await {
+                Ok(n) => println!("🖋️ Vectorized {} frame(s) into {:?}", n, out_dir),
+                Err(e) => error!("Vectorize failed: {}", e),
+            }
+        }
+        Commands::Publish {
+            input,
+            platform,
+            output,
+            min_resolution,
+            no_downscale,
+        } => {
+            use synoid_core::agent::platform_presets::{self, Platform};
+
+            let Some(target) = Platform::parse(&platform) else {
+                error!(
+                    "Unknown platform '{}'. Use one of: shorts, tiktok, reels, youtube, discord, twitter",
+                    platform
+                );
+                return Ok(());
+            };
+            let out_path = output.unwrap_or_else(|| {
+                let tag = target.label().to_lowercase().replace(' ', "_");
+                output_naming::default_output(&input, &tag, None, "mp4")
+            });
+
+            match platform_presets::publish(
+                &input,
+                target,
+                &out_path,
+                resolution_ladder(min_resolution, no_downscale),
+            )
+            .await {
+                Ok(res) => println!(
+                    "📣 {} export saved: {:?} ({:.2} MB, {:.1}s)",
+                    target.label(),
+                    res.output_path,
+                    res.size_mb,
+                    res.duration
+                ),
+                Err(e) => error!("Publish failed: {}", e),
+            }
+        }
         Commands::Run { request } => {
             core.process_brain_request(&request).await?;
         }
@@ -521,21 +1685,82 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             intent,
             output,
             dry_run,
+            sponsor_logos: _,
+            draft: _,
+            draft_fast: _,
+            seed: _,
+            intro: _,
+            outro: _,
+            intro_crossfade: _,
+            external_scorer: _,
+            force_reprocess: _,
+            format,
         } => {
-            core.embody_intent(&input, &intent, &output, dry_run, true, true)
-                .await?;
+            if dry_run {
+                for plan in core.plan_embody(&input, &intent, &output).await {
+                    match format {
+                        ResultFormat::Text => println!("{}", plan.describe()),
+                        ResultFormat::Json => println!("{}", serde_json::to_string(&plan)?),
+                    }
+                }
+                return Ok(());
+            }
+            // Runs each step of a compound request in turn and waits for it
+            let outcomes = core
+                .embody_steps(&input, &intent, &output, true, true)
+                .await;
+            for outcome in &outcomes {
+                if outcome.succeeded() {
+                    info!("{}", outcome.describe());
+                } else {
+                    error!("{}", outcome.describe());
+                }
+            }
+            let failed = outcomes.iter().filter(|o| !o.succeeded()).count();
+            if failed > 0 {
+                return Err(format!("{} of {} edit steps failed", failed, outcomes.len()).into());
+            }
+            let steps: Vec<PathBuf> = outcomes.iter().map(|o| o.output.clone()).collect();
+            let last = steps.last().unwrap_or(&output);
+            println!("{}", result_line(format, "embody", last, &steps));
+            after_render(last).await;
         }
         Commands::Learn { input, name } => {
             core.learn_style(&input, &name).await?;
         }
+        Commands::Ocr {
+            input,
+            interval,
+            output,
+        } => {
+            use synoid_core::agent::vision_tools;
+            match vision_tools::ocr_timeline(&input, interval).await {
+                Ok(entries) => {
+                    for (t, text) in &entries {
+                        println!("[{:02}:{:05.2}] {}", (*t as u64) / 60, t % 60.0, text);
+                    }
+                    println!("🔤 {} on-screen text blocks.", entries.len());
+
+                    if let Some(out_path) = output {
+                        let mut index = vision_tools::SemanticIndex {
+                            source_path: input.to_string_lossy().to_string(),
+                            frames: Vec::new(),
+                        };
+                        index.add_on_screen_text(&entries);
+                        std::fs::write(&out_path, serde_json::to_string_pretty(&index)?)?;
+                        println!("💾 Index saved: {:?}", out_path);
+                    }
+                }
+                Err(e) => error!("OCR failed: {}", e),
+            }
+        }
         Commands::Suggest { input } => {
             info!("💡 Analyzing {:?} for suggestions...", input);
-            use synoid_core::agent::vision_tools;
-            match vision_tools::scan_visual(&input).await {
+            match agent::scan_cache::visual(&input).await {
                 Ok(scenes) => {
                     let count = scenes.len();
                     if count == 0 {
-                        println!("❌ No scenes detected. Video might be empty or corrupt.");
+                        error!("❌ No scenes detected. Video might be empty or corrupt.");
                     } else {
                         let duration = scenes.last().unwrap().timestamp;
                         let avg = duration / count as f64;
@@ -562,6 +1787,11 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         Commands::Gpu => {
             synoid_core::gpu_backend::print_gpu_status().await;
         }
+        Commands::Tools => {
+            for (name, description) in agent::tool_registry::descriptions() {
+                println!("{:<10} {}", name, description);
+            }
+        }
         Commands::Serve { port } => {
             use crate::agent::core_systems::health::HealthMonitor;
             use synoid_core::server;
@@ -570,7 +1800,7 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             info!("🌐 Starting SYNOID Dashboard on port {}...", port);
 
             // Start health monitor for long-running server
-            let health = HealthMonitor::new(30);
+            let health = HealthMonitor::new(30).with_events(core.events.clone());
             let _health_shutdown = health.start();
 
             let state = Arc::new(KernelState::new(core.clone()));
@@ -580,7 +1810,7 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         }
 
         Commands::Guard { mode, watch } => {
-            // Guard runs indefinitely
+            // Guard runs until interrupted, or returns at once if the Sentinel is disabled
             core.activate_sentinel(&mode, watch).await;
         }
         Commands::Agent {
@@ -603,7 +1833,7 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                         Err(e) => error!("Director failed: {}", e),
                     }
                 }
-                _ => println!("Unknown role: {}. Available: director", role),
+                _ => error!("Unknown role: {}. Available: director", role),
             }
         }
         Commands::Process {
@@ -613,9 +1843,27 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             output,
             intent,
             scale,
+            draft,
+            draft_fast: _,
+            seed: _,
+            intro: _,
+            outro: _,
+            intro_crossfade: _,
+            external_scorer: _,
+            force_reprocess: _,
+            format,
         } => {
+            // A draft previews the cut only; enhance/upscale would defeat the point
+            let stages = if draft {
+                info!("👀 Draft render: running the smart_edit stage only");
+                "smart_edit".to_string()
+            } else {
+                stages
+            };
             core.run_unified_pipeline(&input, &output, &stages, &gpu, intent, scale)
                 .await?;
+            println!("{}", result_line(format, "process", &output, &[]));
+            after_render(&output).await;
         }
         Commands::LearnDownloads => {
             info!("🎓 Learning editing style from downloaded reference videos...");
@@ -660,23 +1908,20 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             }
         }
 
-        Commands::Autonomous { port } => {
+        Commands::Autonomous { port, .. } => {
             use agent::autonomous_learner::AutonomousLearner;
             use agent::brain::Brain;
             use tokio::signal;
             use tokio::sync::Mutex;
 
-            if let Some(p) = port {
-                if p != 3000 {
-                    let instance_id = format!("_{}", p);
-                    std::env::set_var("SYNOID_INSTANCE_ID", &instance_id);
-                    info!("🔷 Auto-Isolated Learning Instance: '{}' (port {})", instance_id, p);
-                }
+            if let (Some(instance_id), Some(p)) = (port_instance_id(port), port) {
+                info!("🔷 Auto-Isolated Learning Instance: '{}' (port {})", instance_id, p);
             }
 
+            // The budget and supervision flags reach the learner through the
+            // config layer
             info!("🚀 Starting Autonomous Learning Loop...");
             let brain = Arc::new(Mutex::new(Brain::new(&api_url, "llama3:latest", None)));
-            let instance_id = std::env::var("SYNOID_INSTANCE_ID").unwrap_or_else(|_| "default".to_string());
             let learner = AutonomousLearner::new(brain, &instance_id);
 
             learner.start();
@@ -719,16 +1964,10 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             use tokio::signal;
             use tokio::sync::Mutex;
 
-            if let Some(p) = port {
-                if p != 3000 {
-                    let instance_id = format!("_{}", p);
-                    std::env::set_var("SYNOID_INSTANCE_ID", &instance_id);
-                    info!("🔷 GEPA Instance: '{}' (port {})", instance_id, p);
-                }
+            if let (Some(instance_id), Some(p)) = (port_instance_id(port), port) {
+                info!("🔷 GEPA Instance: '{}' (port {})", instance_id, p);
             }
 
-            let instance_id = std::env::var("SYNOID_INSTANCE_ID")
-                .unwrap_or_else(|_| "default".to_string());
             let brain = Arc::new(Mutex::new(Brain::new(&api_url, "llama3:latest", None)));
             let gepa = Arc::new(GepaLoop::new(brain, &instance_id));
 
@@ -755,6 +1994,35 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             info!("🛑 GEPA Loop Stopped.");
         }
 
+        Commands::TranslateSubs {
+            input,
+            lang,
+            output,
+        } => {
+            use synoid_core::agent::tools::transcription::translate_srt;
+
+            let out_path = output.unwrap_or_else(|| {
+                let stem = input.file_stem().unwrap().to_string_lossy();
+                let tag: String = lang
+                    .chars()
+                    .filter(|c| c.is_ascii_alphanumeric())
+                    .collect::<String>()
+                    .to_lowercase();
+                input.with_file_name(format!("{}.{}.srt", stem, tag))
+            });
+
+            match translate_srt(&input, &lang, &out_path).await {
+                Ok(cues) if cues.untranslated > 0 => error!(
+                    "Translated {} of {} cue(s) → {:?}; {} kept their original text",
+                    cues.segments.len() - cues.untranslated,
+                    cues.segments.len(),
+                    out_path,
+                    cues.untranslated
+                ),
+                Ok(cues) => println!("🌐 Translated {} cue(s) → {:?}", cues.segments.len(), out_path),
+                Err(e) => error!("Subtitle translation failed: {}", e),
+            }
+        }
         Commands::Transcribe { input } => {
             use synoid_core::agent::tools::production_tools;
             use synoid_core::agent::tools::transcription::{
@@ -791,7 +2059,143 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             info!("[TRANSCRIBE] ✅ {} segments → {:?}", segments.len(), srt_path);
             println!("Saved: {:?}", srt_path);
         }
+        Commands::Bench {
+            quick,
+            json,
+            baseline,
+            save_baseline,
+            max_regression,
+        } => {
+            use synoid_core::agent::bench::{self, BenchOptions, BenchReport, Fixture};
+
+            let mut options = BenchOptions::configured();
+            if quick {
+                options.fixtures = vec![Fixture::OneMinute];
+            }
+            let report_path = json.unwrap_or_else(|| options.dir.join("latest.json"));
+            let baseline_path = baseline.unwrap_or_else(|| options.dir.join("baseline.json"));
+
+            let report = match bench::run(&options).await {
+                Ok(report) => report,
+                Err(e) => {
+                    error!("Benchmark failed: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            println!("{}", report.summary());
+            if let Err(e) = report.save(&report_path) {
+                error!("{}", e);
+            } else {
+                println!("📊 Report saved: {:?}", report_path);
+            }
+
+            let mut regressed = false;
+            if baseline_path.exists() {
+                match BenchReport::load(&baseline_path) {
+                    Ok(base) => {
+                        let deltas = bench::compare(&report, &base);
+                        let flag = max_regression.unwrap_or(bench::DEFAULT_REGRESSION_PCT);
+                        println!("{}", bench::render_comparison(&report, &base, &deltas, flag));
+                        regressed = max_regression
+                            .is_some_and(|max| !bench::regressions(&deltas, max).is_empty());
+                    }
+                    Err(e) => error!("{}", e),
+                }
+            } else if !save_baseline {
+                tracing::warn!("No baseline at {:?}; pass --save-baseline to record one", baseline_path);
+            }
+
+            if save_baseline {
+                match report.save(&baseline_path) {
+                    Ok(()) => println!("📌 Baseline saved: {:?}", baseline_path),
+                    Err(e) => error!("{}", e),
+                }
+            }
+            if regressed {
+                error!("Benchmark regression above {:.1}%", max_regression.unwrap_or_default());
+                std::process::exit(1);
+            }
+        }
+        Commands::Completions { .. } | Commands::Setup { .. } | Commands::Doctor => {
+            unreachable!("handled before start-up")
+        }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn results_are_one_line_of_text_or_json() {
+        let out = Path::new("/renders/clip_edited.mp4");
+        assert_eq!(
+            result_line(ResultFormat::Text, "process", out, &[]),
+            "/renders/clip_edited.mp4"
+        );
+
+        let steps = [PathBuf::from("/renders/cut.mp4"), out.to_path_buf()];
+        let line = result_line(ResultFormat::Json, "embody", out, &steps);
+        assert!(!line.contains('\n'), "{}", line);
+        let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(json["command"], "embody");
+        assert_eq!(json["output"], "/renders/clip_edited.mp4");
+        assert_eq!(json["bytes"], serde_json::Value::Null);
+        assert_eq!(json["steps"][0], "/renders/cut.mp4");
+
+        let cli = Cli::try_parse_from(["synoid-core", "-q", "youtube", "-u", "x", "-i", "y"]).unwrap();
+        assert!(cli.quiet);
+        assert!(Cli::try_parse_from(["synoid-core", "-q", "-v", "gpu"]).is_err());
+        assert_eq!(Cli::try_parse_from(["synoid-core", "gpu", "-vv"]).unwrap().verbose, 2);
+    }
+
+    #[test]
+    fn clip_takes_timecodes_and_a_duration_or_an_end() {
+        let clip = |args: &[&str]| {
+            let argv = ["synoid-core", "clip", "-i", "in.mp4"].iter().chain(args).copied();
+            match Cli::try_parse_from(argv).map(|cli| cli.command) {
+                Ok(Some(Commands::Clip { start, duration, end, .. })) => Ok((start, duration, end)),
+                Ok(_) => unreachable!("clip parsed as another command"),
+                Err(e) => Err(e.kind()),
+            }
+        };
+
+        assert_eq!(clip(&["-s", "1:23:45.5", "-d", "5:10"]), Ok((5025.5, Some(310.0), None)));
+        assert_eq!(clip(&["--start", "90", "--end", "2:00"]), Ok((90.0, None, Some(120.0))));
+        assert_eq!(
+            clip(&["-s", "0", "-d", "10", "-e", "20"]),
+            Err(clap::error::ErrorKind::ArgumentConflict)
+        );
+        assert_eq!(clip(&["-s", "0"]), Err(clap::error::ErrorKind::MissingRequiredArgument));
+        assert_eq!(clip(&["-s", "1:90", "-d", "5"]), Err(clap::error::ErrorKind::ValueValidation));
+    }
+
+    #[test]
+    fn then_takes_a_list_of_post_render_actions() {
+        let then = |value: &str| {
+            Cli::try_parse_from(["synoid-core", "process", "-i", "a.mp4", "-o", "b.mp4", "--then", value])
+                .map(|cli| cli.then)
+                .map_err(|e| e.kind())
+        };
+        assert_eq!(
+            then("reveal,copy-path"),
+            Ok(Some(vec![PostRenderAction::Reveal, PostRenderAction::CopyPath]))
+        );
+        assert_eq!(then("none"), Ok(Some(vec![])));
+        assert_eq!(then("upload"), Err(clap::error::ErrorKind::ValueValidation));
+    }
+
+    #[test]
+    fn bash_completions_cover_subcommands() {
+        let mut out = Vec::new();
+        write_completions(clap_complete::Shell::Bash, &mut out);
+        let script = String::from_utf8(out).unwrap();
+
+        assert!(!script.is_empty());
+        for name in ["gui", "embody", "review", "tune", "completions"] {
+            assert!(script.contains(name), "bash completions miss `{}`", name);
+        }
+    }
+}
@@ -0,0 +1,262 @@
+// SYNOID Open With — file associations and the single-instance GUI lock
+// Copyright (c) 2026 xingxerx_The_Creator | SYNOID
+//
+// "Open with SYNOID" runs the binary with the video as its only argument.
+// The first GUI to start takes a lock file holding a loopback port and a
+// random token; later launches connect to that port and hand over their path
+// instead of opening a second window. A lock whose holder no longer answers
+// is stale and is taken over.
+//
+// Windows passes the path as `"%1"` (see packaging/windows); a verb
+// registered without the quotes splits paths containing spaces, which are
+// joined back here. macOS delivers files to a bundle as an `open documents`
+// Apple event rather than argv, so the bundle's applet (packaging/macos)
+// turns the event into a plain launch; old launchers also add a `-psn_*`
+// process serial number argument, which is ignored.
+
+use std::ffi::OsString;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// How long a launch waits on a running instance before taking over.
+const FORWARD_TIMEOUT: Duration = Duration::from_millis(800);
+
+/// The video named by the binary's positional arguments, made absolute so a
+/// running instance with another working directory can open it.
+pub fn path_from_args(args: &[OsString]) -> Option<PathBuf> {
+    let parts: Vec<String> = args
+        .iter()
+        .map(|a| a.to_string_lossy().into_owned())
+        .filter(|a| !a.starts_with("-psn_"))
+        .collect();
+    let first = parts.first()?;
+    let raw = if parts.len() > 1 && !Path::new(unquote(first)).exists() {
+        parts.join(" ")
+    } else {
+        first.clone()
+    };
+    let path = PathBuf::from(unquote(&raw));
+    if path.as_os_str().is_empty() {
+        return None;
+    }
+    Some(std::path::absolute(&path).unwrap_or(path))
+}
+
+/// Strip the stray quotes some shells leave around a path.
+fn unquote(s: &str) -> &str {
+    s.trim().trim_matches('"')
+}
+
+/// False on Linux/BSD sessions without X11 or Wayland (SSH, servers).
+pub fn has_display() -> bool {
+    if cfg!(any(target_os = "windows", target_os = "macos")) {
+        return true;
+    }
+    std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+/// `cortex_cache<instance>/gui.lock`, beside the instance's other state.
+pub fn lock_path(instance_id: &str) -> PathBuf {
+    let suffix = if instance_id == "default" {
+        ""
+    } else {
+        instance_id
+    };
+    PathBuf::from(format!("cortex_cache{}", suffix)).join("gui.lock")
+}
+
+/// Held by the running GUI; removes its lock file when dropped.
+pub struct InstanceLock {
+    path: PathBuf,
+    token: String,
+    listener: TcpListener,
+}
+
+/// Result of trying to become the GUI for an instance.
+pub enum Claim {
+    /// No live instance: this process is now it.
+    Acquired(InstanceLock),
+    /// A running instance took the path (or was just brought forward).
+    Forwarded,
+}
+
+/// Hand `open` to the running GUI, or take the lock if there is none.
+pub fn claim_or_forward(lock: &Path, open: Option<&Path>) -> std::io::Result<Claim> {
+    if let Ok(contents) = std::fs::read_to_string(lock) {
+        match forward(&contents, open) {
+            Ok(()) => return Ok(Claim::Forwarded),
+            Err(e) => info!("[OPEN] Taking over stale GUI lock {:?} ({})", lock, e),
+        }
+    }
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    if let Some(dir) = lock.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(
+        lock,
+        format!("{} {}\n", listener.local_addr()?.port(), token),
+    )?;
+    Ok(Claim::Acquired(InstanceLock {
+        path: lock.to_path_buf(),
+        token,
+        listener,
+    }))
+}
+
+/// Send one `<token>\t<path>` line to the instance described by `contents`
+/// and wait for its `ok`. An empty path just brings the window forward.
+fn forward(contents: &str, open: Option<&Path>) -> std::io::Result<()> {
+    let invalid = || std::io::Error::new(std::io::ErrorKind::InvalidData, "malformed lock file");
+    let (port, token) = contents.trim().split_once(' ').ok_or_else(invalid)?;
+    let port: u16 = port.parse().map_err(|_| invalid())?;
+
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let mut stream = TcpStream::connect_timeout(&addr, FORWARD_TIMEOUT)?;
+    stream.set_read_timeout(Some(FORWARD_TIMEOUT))?;
+    let path = open
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    writeln!(stream, "{}\t{}", token, path)?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    if reply.trim() == "ok" {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "lock holder refused the path",
+        ))
+    }
+}
+
+impl InstanceLock {
+    /// Accept forwarded launches on a background thread. `on_open` gets each
+    /// path, or `None` when a launch without one only wants the window.
+    pub fn serve(&self, on_open: impl Fn(Option<PathBuf>) + Send + 'static) -> std::io::Result<()> {
+        let listener = self.listener.try_clone()?;
+        let token = self.token.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                match read_forwarded(stream, &token) {
+                    Ok(path) => on_open(path),
+                    Err(e) => warn!("[OPEN] Ignored a forwarded launch: {}", e),
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+fn read_forwarded(mut stream: TcpStream, token: &str) -> std::io::Result<Option<PathBuf>> {
+    stream.set_read_timeout(Some(FORWARD_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let (sent_token, path) = line
+        .trim_end_matches(['\r', '\n'])
+        .split_once('\t')
+        .unwrap_or(("", ""));
+    if sent_token != token {
+        writeln!(stream, "denied")?;
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "wrong token",
+        ));
+    }
+    writeln!(stream, "ok")?;
+    Ok((!path.is_empty()).then(|| PathBuf::from(path)))
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        // A newer instance may have taken the lock over; leave its file alone.
+        let ours = std::fs::read_to_string(&self.path)
+            .map(|c| c.split_whitespace().nth(1) == Some(self.token.as_str()))
+            .unwrap_or(false);
+        if ours {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn args(list: &[&str]) -> Vec<OsString> {
+        list.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn path_from_args_cleans_launcher_quirks() {
+        let dir = std::env::temp_dir().join(format!("synoid open with {}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let video = dir.join("my clip.mp4");
+        std::fs::write(&video, b"").unwrap();
+        let video_str = video.to_string_lossy().into_owned();
+
+        assert_eq!(path_from_args(&args(&[])), None);
+        assert_eq!(path_from_args(&args(&["-psn_0_12345"])), None);
+        assert_eq!(
+            path_from_args(&args(&["-psn_0_12345", &video_str])),
+            Some(video.clone())
+        );
+        // Stray quotes around the whole path
+        assert_eq!(
+            path_from_args(&args(&[&format!("\"{}\"", video_str)])),
+            Some(video.clone())
+        );
+        // "%1" registered without quotes: the path arrives split at its spaces
+        let split: Vec<&str> = video_str.splitn(2, "my ").collect();
+        let head = format!("{}my", split[0]);
+        assert_eq!(
+            path_from_args(&args(&[&head, split[1]])),
+            Some(video.clone())
+        );
+
+        let relative = path_from_args(&args(&["clip.mp4"])).unwrap();
+        assert!(relative.is_absolute());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn second_launch_forwards_to_the_first() {
+        let lock = std::env::temp_dir()
+            .join(format!("synoid_gui_lock_{}", std::process::id()))
+            .join("gui.lock");
+
+        let Claim::Acquired(first) = claim_or_forward(&lock, None).unwrap() else {
+            panic!("nothing was running, the first launch should take the lock");
+        };
+        let (tx, rx) = mpsc::channel();
+        first.serve(move |path| tx.send(path).unwrap()).unwrap();
+
+        let video = Path::new("/videos/clip.mp4");
+        assert!(matches!(
+            claim_or_forward(&lock, Some(video)).unwrap(),
+            Claim::Forwarded
+        ));
+        assert_eq!(
+            rx.recv_timeout(Duration::from_secs(2)).unwrap(),
+            Some(video.to_path_buf())
+        );
+
+        // Once the holder is gone its lock is stale and the next launch takes over.
+        drop(first);
+        assert!(!lock.exists());
+        std::fs::write(&lock, "1 not-a-live-instance\n").unwrap();
+        let Claim::Acquired(second) = claim_or_forward(&lock, Some(video)).unwrap() else {
+            panic!("a stale lock should be taken over");
+        };
+        drop(second);
+        let _ = std::fs::remove_dir_all(lock.parent().unwrap());
+    }
+}
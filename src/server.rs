@@ -19,7 +19,10 @@ use tower_http::timeout::TimeoutLayer;
 use tower_http::trace::TraceLayer;
 use tracing::{error, info};
 
+use crate::agent::autonomous_learner::{AutonomousLearner, CyclePlan, LearnerReport};
+use crate::agent::llm_json;
 use crate::editor_api;
+use crate::events::MetricsSnapshot;
 use crate::state::{DashboardStatus, DashboardTask, KernelState, TasksStatus};
 
 pub type AppState = Arc<KernelState>;
@@ -88,9 +91,22 @@ fn validate_stream_path(raw_path: &str) -> Result<PathBuf, String> {
     }
 }
 
+/// Body cap for every route but editor uploads and imports, which stream to
+/// disk under the `max_upload_mb` setting instead.
+pub const REQUEST_BODY_LIMIT: usize = 10 * 1024 * 1024;
+
+/// Wall-clock limit for every route but editor uploads and imports.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
 pub fn create_router(state: Arc<KernelState>) -> Router {
+    create_router_with(state, editor_api::EditorSettings::from_config())
+}
+
+/// `create_router` with the editor's sessions folder and upload cap given
+/// rather than configured, so tests can keep sessions out of the tree.
+pub fn create_router_with(state: Arc<KernelState>, editor: editor_api::EditorSettings) -> Router {
     // Editor API (no state — handled internally by EditorState)
-    let editor_router = editor_api::router(state.core.clone());
+    let editor_router = editor_api::router(state.core.clone(), editor);
 
     // Stateful dashboard routes
     let dashboard_router = Router::new()
@@ -98,6 +114,11 @@ pub fn create_router(state: Arc<KernelState>) -> Router {
         .route("/api/tasks", get(get_tasks))
         .route("/api/chat", post(handle_chat))
         .route("/api/stream", get(stream_video))
+        .route("/api/learner/plan", get(get_learner_plan))
+        .route("/api/learner/approve", post(approve_learner_plan))
+        .route("/api/learner/report", get(get_learner_report))
+        .route("/api/llm/json-stats", get(get_llm_json_stats))
+        .route("/api/metrics", get(get_metrics))
         .layer(middleware::from_fn(auth_middleware))
         .with_state(state);
 
@@ -107,22 +128,24 @@ pub fn create_router(state: Arc<KernelState>) -> Router {
         .nest_service("/editor", ServeDir::new("editor/dist"))
         // Dashboard legacy
         .nest_service("/", ServeDir::new("dashboard"))
-        // Editor REST API (no auth guard — runs on localhost only)
-        .nest("/api/editor", editor_router)
         // Merge the stateful dashboard router
         .merge(dashboard_router)
+        // 10 MiB request body cap — prevents runaway uploads exhausting RAM
+        .layer(RequestBodyLimitLayer::new(REQUEST_BODY_LIMIT))
+        // 30 s hard wall-clock timeout per request (returns 408 on breach)
+        .layer(TimeoutLayer::new(REQUEST_TIMEOUT))
+        // Editor REST API (no auth guard — runs on localhost only). Added after
+        // the layers above because it applies them itself, leaving out the
+        // upload and import routes.
+        .nest("/api/editor", editor_router)
         // Structured HTTP access log
         .layer(TraceLayer::new_for_http())
-        // 30 s hard wall-clock timeout per request (returns 408 on breach)
-        .layer(TimeoutLayer::new(Duration::from_secs(30)))
-        // 10 MiB request body cap — prevents runaway uploads exhausting RAM
-        .layer(RequestBodyLimitLayer::new(10 * 1024 * 1024))
         // Permissive CORS is safe here: server binds to 127.0.0.1 only
         .layer(CorsLayer::permissive())
 }
 
 pub async fn start_server(port: u16, state: Arc<KernelState>) {
-    if std::env::var("SYNOID_API_KEY").is_err() {
+    if crate::config::current().api_key.is_none() {
         tracing::debug!(
             "💡 TIP: SYNOID_API_KEY is not set. Using default developer key for local access."
         );
@@ -186,6 +209,7 @@ async fn get_status(State(state): State<AppState>) -> Json<DashboardStatus> {
             total: 20,
         },
         productivity: 85,
+        brain_offline: state.core.brain_offline_notice(),
     })
 }
 
@@ -214,14 +238,58 @@ async fn get_tasks(State(state): State<AppState>) -> Json<Vec<DashboardTask>> {
     Json(tasks)
 }
 
+/// Plan the supervised autonomous learner is waiting on (null if none).
+async fn get_learner_plan(State(state): State<AppState>) -> Json<Option<CyclePlan>> {
+    Json(AutonomousLearner::pending_plan(&state.core.instance_id))
+}
+
+/// Learner activity: pending plan, today's budget use and code-scan notes.
+async fn get_learner_report(State(state): State<AppState>) -> Json<LearnerReport> {
+    Json(AutonomousLearner::report(&state.core.instance_id))
+}
+
+/// Per-prompt parse/repair counts for LLM JSON replies, with the repair rate
+/// so flaky prompts stand out.
+async fn get_metrics(State(state): State<AppState>) -> Json<MetricsSnapshot> {
+    Json(state.metrics.snapshot())
+}
+
+async fn get_llm_json_stats() -> Json<serde_json::Value> {
+    let stats: serde_json::Map<String, serde_json::Value> = llm_json::stats()
+        .into_iter()
+        .map(|(label, s)| {
+            let rate = s.repair_rate();
+            let mut v = serde_json::to_value(s).unwrap_or_default();
+            v["repair_rate"] = serde_json::json!(rate);
+            (label, v)
+        })
+        .collect();
+    Json(serde_json::Value::Object(stats))
+}
+
+/// Approve the pending learner plan so the next cycle may download. 404
+/// when no plan is waiting.
+async fn approve_learner_plan(State(state): State<AppState>) -> StatusCode {
+    match AutonomousLearner::approve_pending_plan(&state.core.instance_id) {
+        Ok(()) => {
+            info!("[LEARNER] Plan approved via API");
+            StatusCode::NO_CONTENT
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => StatusCode::NOT_FOUND,
+        Err(e) => {
+            error!("❌ Failed to approve learner plan: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
 async fn auth_middleware(
     headers: HeaderMap,
     request: Request,
     next: middleware::Next,
 ) -> Result<Response, StatusCode> {
-    let api_key =
-        std::env::var("SYNOID_API_KEY").unwrap_or_else(|_| "synoid_secret_v1".to_string());
-    let expected = api_key.as_bytes();
+    let config = crate::config::current();
+    let expected = config.api_key_or_default().as_bytes();
 
     // 1. Check x-api-key header — constant-time compare to resist timing attacks
     if let Some(key) = headers.get("x-api-key") {
@@ -249,19 +317,26 @@ async fn auth_middleware(
 async fn handle_chat(
     State(state): State<AppState>,
     Json(payload): Json<ChatRequest>,
-) -> Json<ChatResponse> {
+) -> (axum::http::StatusCode, Json<ChatResponse>) {
     info!("Brain receiving: {}", payload.message);
 
+    // Say so plainly rather than let the request time out against a dead backend
+    if let Some(notice) = state.core.brain_offline_notice() {
+        state.core.connect_brain();
+        return (
+            axum::http::StatusCode::SERVICE_UNAVAILABLE,
+            Json(ChatResponse { response: notice }),
+        );
+    }
     let mut brain = state.core.brain.lock().await;
-    match brain.process(&payload.message).await {
-        Ok(res) => Json(ChatResponse { response: res }),
+    let response = match brain.process(&payload.message).await {
+        Ok(res) => res,
         Err(e) => {
             error!("Brain Error: {}", e);
-            Json(ChatResponse {
-                response: format!("Error: {}", e),
-            })
+            format!("Error: {}", e)
         }
-    }
+    };
+    (axum::http::StatusCode::OK, Json(ChatResponse { response }))
 }
 
 async fn stream_video(
@@ -4,6 +4,7 @@ use serde::Serialize;
 
 use crate::agent::core_systems::core::AgentCore;
 use crate::agent::security::defense::pressure::{PressureLevel, PressureWatcher};
+use crate::events::EventMetrics;
 
 pub struct KernelState {
     pub task: Mutex<TaskState>,
@@ -11,6 +12,9 @@ pub struct KernelState {
 
     /// Shared pressure level for the GUI health bar.
     pub pressure_level: Arc<RwLock<PressureLevel>>,
+
+    /// Totals from the core event bus, served at `/api/metrics`.
+    pub metrics: Arc<EventMetrics>,
 }
 
 impl KernelState {
@@ -20,6 +24,7 @@ impl KernelState {
 
         Self {
             task: Mutex::new(TaskState::default()),
+            metrics: EventMetrics::track(&core.events),
             core,
 
             pressure_level: pressure_handle,
@@ -68,6 +73,9 @@ impl Default for TaskState {
 pub struct DashboardStatus {
     pub tasks: TasksStatus,
     pub productivity: i32,
+    /// Set while the LLM backend is unreachable; AI endpoints answer 503.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub brain_offline: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -6,9 +6,12 @@
 
 use eframe::egui;
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
 
 use crate::agent::core_systems::core::AgentCore;
+use crate::agent::post_render::{self, PostRenderAction};
+use crate::agent::source_tools::DownloadOptions;
+use crate::i18n::tr;
 
 // --- Color Palette (Premium Dark) ---
 const COLOR_BG_DARK: egui::Color32 = egui::Color32::from_rgb(22, 22, 26);
@@ -23,15 +26,96 @@ const COLOR_TEXT_PRIMARY: egui::Color32 = egui::Color32::from_rgb(220, 220, 220)
 const COLOR_TEXT_SECONDARY: egui::Color32 = egui::Color32::from_rgb(140, 140, 150);
 const COLOR_TREE_ITEM: egui::Color32 = egui::Color32::from_rgb(100, 180, 255);
 
+// --- Repaint Scheduling ---
+// The window repaints on input, when the core logs or changes status (see
+// `GuiWaker`), at the frame rate while a video plays, and on a slow tick while
+// a background job runs. Otherwise it sleeps.
+
+/// Repaint tick while a job without its own wake-ups is running.
+const BUSY_REPAINT_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+const FRAME_COUNTER_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// How long to wait for the next video frame at `fps`.
+fn frame_interval(fps: f64) -> std::time::Duration {
+    let fps = if fps.is_finite() && fps > 0.0 { fps } else { 30.0 };
+    std::time::Duration::from_secs_f64(1.0 / fps)
+}
+
+/// Draw `image` into `texture`, reusing its allocation when there is one.
+/// Loading a fresh texture per decoded frame churns GPU memory.
+fn show_frame(
+    texture: &mut Option<egui::TextureHandle>,
+    ctx: &egui::Context,
+    name: &str,
+    image: egui::ColorImage,
+) {
+    match texture {
+        Some(handle) => handle.set(image, egui::TextureOptions::default()),
+        None => *texture = Some(ctx.load_texture(name, image, Default::default())),
+    }
+}
+
+/// Counts drawn frames and logs the rate from a side thread, so an idle
+/// window reports ~0 fps without being woken to say so.
+/// On with the `gui_frame_counter` setting (SYNOID_GUI_FRAME_COUNTER=1).
+struct FrameCounter {
+    frames: Arc<std::sync::atomic::AtomicU64>,
+}
+
+impl FrameCounter {
+    fn from_config() -> Option<Self> {
+        if !crate::config::current().gui_frame_counter {
+            return None;
+        }
+        let frames = Arc::new(std::sync::atomic::AtomicU64::new(0));
+        let counted = frames.clone();
+        std::thread::spawn(move || loop {
+            std::thread::sleep(FRAME_COUNTER_INTERVAL);
+            let n = counted.swap(0, std::sync::atomic::Ordering::Relaxed);
+            let secs = FRAME_COUNTER_INTERVAL.as_secs_f64();
+            // tracing only: a core.log() here would wake the window it measures
+            tracing::info!("[GUI] 🖼️ {} frames in {:.0}s ({:.1} fps)", n, secs, n as f64 / secs);
+        });
+        Some(Self { frames })
+    }
+
+    fn tick(&self) {
+        self.frames.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
 // --- WSL Helpers ---
 fn is_wsl() -> bool {
-    std::env::var("WSL_DISTRO_NAME").is_ok()
-        || std::fs::read_to_string("/proc/version")
-            .map(|s| s.contains("Microsoft") || s.contains("WSL"))
-            .unwrap_or(false)
+    crate::config::current().wsl
+}
+
+/// Where the output picker starts: the input's folder under the configured
+/// `outputs_dir` (created so the dialog can open it), else the videos folder.
+fn default_output_dir(input: &str) -> PathBuf {
+    let Some(root) = crate::config::current().outputs_dir else {
+        return get_default_videos_path();
+    };
+    let root = PathBuf::from(root);
+    let dir = if input.is_empty() {
+        root
+    } else {
+        crate::agent::output_naming::organized_dir(&root, std::path::Path::new(input), chrono::Local::now())
+    };
+    match std::fs::create_dir_all(&dir) {
+        Ok(()) => dir,
+        Err(_) => get_default_videos_path(),
+    }
 }
 
 fn get_default_videos_path() -> PathBuf {
+    // The directory chosen in setup wins
+    if let Some(dir) = crate::config::current().video_dir {
+        let dir = PathBuf::from(dir);
+        if dir.is_dir() {
+            return dir;
+        }
+    }
+
     // Prefer the project-local Video directory
     let project_video = PathBuf::from("Video");
     if project_video.exists() {
@@ -91,10 +175,13 @@ pub enum ActiveCommand {
     Discovery,
     // System
     GpuStatus,
+    Setup,
     // Self-improvement
     AutoImprove,
     // Gemma 4 builder/improver
     Gemma4,
+    // Operation history
+    History,
 }
 
 impl Default for ActiveCommand {
@@ -103,6 +190,16 @@ impl Default for ActiveCommand {
     }
 }
 
+impl ActiveCommand {
+    /// Panels that do nothing useful without the LLM backend.
+    fn needs_brain(self) -> bool {
+        matches!(
+            self,
+            Self::Brain | Self::Embody | Self::Suggest | Self::AutoImprove | Self::Gemma4
+        )
+    }
+}
+
 #[derive(Default, Clone, serde::Serialize, serde::Deserialize)]
 pub struct TreeState {
     pub media_expanded: bool,
@@ -114,6 +211,64 @@ pub struct TreeState {
 }
 
 /// Holds the temporary UI state (form inputs)
+/// Start and length of the clip panel's inputs, or what is wrong with them.
+/// Duration and End are alternatives: fill in one and leave the other blank.
+fn clip_span(state: &UiState) -> Result<(f64, f64), String> {
+    use crate::agent::timecode;
+
+    let optional = |label: &str, text: &str| -> Result<Option<f64>, String> {
+        if text.trim().is_empty() {
+            return Ok(None);
+        }
+        timecode::parse(text).map(Some).map_err(|e| format!("{}: {}", label, e))
+    };
+    let start = timecode::parse(&state.clip_start).map_err(|e| format!("Start: {}", e))?;
+    let duration = optional("Duration", &state.clip_duration)?;
+    let end = optional("End", &state.clip_end)?;
+    timecode::clip_length(start, duration, end).map(|length| (start, length))
+}
+
+/// A render that just finished, offered in the corner toast.
+pub struct RenderToast {
+    pub output: PathBuf,
+    /// Outcomes of the actions run so far, newest last.
+    pub notes: Vec<String>,
+}
+
+/// The edit plan beside the current output, re-read only when the output
+/// path or the plan file's mtime changes rather than on every frame.
+#[derive(Default)]
+pub struct CachedEditPlan {
+    output: String,
+    modified: Option<std::time::SystemTime>,
+    plan: Option<crate::agent::smart_editor::EditPlan>,
+}
+
+impl CachedEditPlan {
+    fn get(&mut self, output: &str) -> Option<&crate::agent::smart_editor::EditPlan> {
+        use crate::agent::smart_editor::EditPlan;
+
+        let path = std::path::Path::new(output);
+        let modified = std::fs::metadata(EditPlan::path_for(path))
+            .and_then(|m| m.modified())
+            .ok();
+        if self.output != output || self.modified != modified {
+            self.output = output.to_string();
+            self.modified = modified;
+            self.plan = modified.and_then(|_| EditPlan::load_for(path));
+        }
+        self.plan.as_ref()
+    }
+}
+
+/// An edit ran without a transcript; offered in a dialog until dismissed.
+pub struct TranscriptionNotice {
+    pub reason: String,
+    /// Progress or outcome of the fallback model download.
+    pub status: String,
+    pub downloading: bool,
+}
+
 #[derive(Default)]
 pub struct UiState {
     pub input_path: String,
@@ -125,6 +280,7 @@ pub struct UiState {
     // Production params
     pub clip_start: String,
     pub clip_duration: String,
+    pub clip_end: String,
     pub compress_size: String,
     pub scale_factor: String,
     pub research_topic: String,
@@ -140,9 +296,18 @@ pub struct UiState {
     pub last_previewed_path: String,
     pub suggestions: Vec<String>,
     pub video_player: Option<crate::agent::video_player::VideoPlayer>,
+    // Before/after split preview of the last edit
+    pub split_player: Option<crate::agent::video_player::SplitPlayer>,
+    pub split_wipe: Option<f32>,
+    pub split_position: f64,
+    pub split_plan: CachedEditPlan,
     pub active_editor_tab: String,
     pub video_duration: f64,
     pub video_position: f64,
+    /// Range the preview repeats, as marked on the timeline (either order).
+    pub loop_range: Option<(f64, f64)>,
+    /// Where the loop selection being dragged began.
+    loop_anchor: Option<f64>,
     pub is_transcribing: bool,
     // Timeline and editing
     pub timeline_zoom: f32,
@@ -158,11 +323,19 @@ pub struct UiState {
     pub is_scanning: bool,
     pub discovery_query: String,
     pub recent_jobs: Vec<crate::agent::editor_queue::EditJob>,
+    pub render_toast: Option<RenderToast>,
+    pub transcription_notice: Option<TranscriptionNotice>,
     // Editor feature toggles
     pub enable_subtitles: bool,
     pub enable_censoring: bool,
     pub enable_audio_enhancement: bool,
     pub enable_silence_removal: bool,
+    // Audio mixer
+    pub duck_music: bool,
+    pub duck_depth_db: f32,
+    /// Loudness of the file at `loudness_path`, once analyzed
+    pub loudness_report: Option<crate::agent::audio_tools::LoudnessReport>,
+    pub loudness_path: String,
     // AutoImprove
     pub improve_benchmark: String,
     pub improve_candidates: String,
@@ -173,6 +346,25 @@ pub struct UiState {
     pub gemma4_max_steps: String,
     pub gemma4_dry_run: bool,
     pub gemma4_log: String,
+    // Operation history
+    pub history_filter: String,
+    pub history_records: Vec<crate::agent::operation_history::OperationRecord>,
+    pub history_loaded_at: Option<std::time::Instant>,
+    // Open .synoid project (re-read periodically; the CLI can switch it)
+    pub project_name: Option<String>,
+    pub project_checked_at: Option<std::time::Instant>,
+    /// Panel to switch to on the next frame (set by panels that only get `&self`)
+    pub pending_command: Option<ActiveCommand>,
+    // Setup (the same checks and steps as `synoid-core setup`)
+    pub setup_loaded: bool,
+    pub setup_checks: Vec<crate::agent::doctor::Check>,
+    pub setup_status: String,
+    pub setup_busy: bool,
+    pub setup_model: String,
+    pub setup_video_dir: String,
+    pub setup_cache_dir: String,
+    pub setup_whisper_model: String,
+    pub setup_after_render: Vec<PostRenderAction>,
     // System
     pub is_restarting: bool,
     pub port: u16,
@@ -187,6 +379,7 @@ struct PersistedSettings {
     youtube_url: String,
     clip_start: String,
     clip_duration: String,
+    clip_end: String,
     compress_size: String,
     scale_factor: String,
     research_topic: String,
@@ -205,6 +398,8 @@ struct PersistedSettings {
     enable_censoring: bool,
     enable_audio_enhancement: bool,
     enable_silence_removal: bool,
+    duck_music: bool,
+    duck_depth_db: f32,
     improve_benchmark: String,
     improve_candidates: String,
     improve_iterations: String,
@@ -219,6 +414,7 @@ impl Default for PersistedSettings {
             youtube_url: String::new(),
             clip_start: "0.0".to_string(),
             clip_duration: "10.0".to_string(),
+            clip_end: String::new(),
             compress_size: "25.0".to_string(),
             scale_factor: "2.0".to_string(),
             research_topic: String::new(),
@@ -244,6 +440,8 @@ impl Default for PersistedSettings {
             enable_censoring: true,
             enable_audio_enhancement: true,
             enable_silence_removal: false,
+            duck_music: false,
+            duck_depth_db: 12.0,
             improve_benchmark: String::new(),
             improve_candidates: "4".to_string(),
             improve_iterations: String::new(),
@@ -279,6 +477,7 @@ fn save_settings(
         youtube_url: state.youtube_url.clone(),
         clip_start: state.clip_start.clone(),
         clip_duration: state.clip_duration.clone(),
+        clip_end: state.clip_end.clone(),
         compress_size: state.compress_size.clone(),
         scale_factor: state.scale_factor.clone(),
         research_topic: state.research_topic.clone(),
@@ -297,6 +496,8 @@ fn save_settings(
         enable_censoring: state.enable_censoring,
         enable_audio_enhancement: state.enable_audio_enhancement,
         enable_silence_removal: state.enable_silence_removal,
+        duck_music: state.duck_music,
+        duck_depth_db: state.duck_depth_db,
         improve_benchmark: state.improve_benchmark.clone(),
         improve_candidates: state.improve_candidates.clone(),
         improve_iterations: state.improve_iterations.clone(),
@@ -314,7 +515,13 @@ pub struct SynoidApp {
     ui_state: Arc<Mutex<UiState>>,
     tree_state: TreeState,
     active_command: ActiveCommand,
+    /// Still frame of the input, reloaded when the input changes.
     preview_texture: Option<egui::TextureHandle>,
+    /// Frames from the video or split player; dropped when playback stops.
+    video_texture: Option<egui::TextureHandle>,
+    /// Copy of the core's log lines, refreshed when the core marks them dirty.
+    logs: Vec<String>,
+    frame_counter: Option<FrameCounter>,
 }
 
 impl SynoidApp {
@@ -336,6 +543,7 @@ impl SynoidApp {
         ui_state.youtube_url = settings.youtube_url.clone();
         ui_state.clip_start = settings.clip_start.clone();
         ui_state.clip_duration = settings.clip_duration.clone();
+        ui_state.clip_end = settings.clip_end.clone();
         ui_state.compress_size = settings.compress_size.clone();
         ui_state.scale_factor = settings.scale_factor.clone();
         ui_state.research_topic = settings.research_topic.clone();
@@ -357,6 +565,8 @@ impl SynoidApp {
         ui_state.enable_censoring = settings.enable_censoring;
         ui_state.enable_audio_enhancement = settings.enable_audio_enhancement;
         ui_state.enable_silence_removal = settings.enable_silence_removal;
+        ui_state.duck_music = settings.duck_music;
+        ui_state.duck_depth_db = settings.duck_depth_db;
         ui_state.improve_benchmark = settings.improve_benchmark.clone();
         ui_state.improve_candidates = settings.improve_candidates.clone();
         ui_state.improve_iterations = settings.improve_iterations.clone();
@@ -372,7 +582,12 @@ impl SynoidApp {
         ui_state.port = port;
 
         let tree_state = settings.tree_state.clone();
-        let active_command = settings.active_command;
+        // Until setup has written a config file, open on the Setup panel
+        let active_command = if crate::config::file_path().exists() {
+            settings.active_command
+        } else {
+            ActiveCommand::Setup
+        };
 
         // Auto-start autonomous learning if enabled in settings
         if ui_state.is_autonomous_running {
@@ -393,23 +608,117 @@ impl SynoidApp {
         let return_state = ui_state_clone.clone();
 
         tokio::spawn(async move {
+            use crate::collab::CollabEvent;
+            use crate::events::SynoidEvent;
+            use tokio::sync::broadcast::error::RecvError;
+
+            // Job changes arrive on the core event bus and, once an editor
+            // session exists, render progress on its live channel; the slow
+            // fallback tick only refreshes hive status.
+            let mut bus = core_clone.events.subscribe();
+            let mut live: Option<(String, tokio::sync::broadcast::Receiver<CollabEvent>)> = None;
+            // Jobs already finished when the GUI opened get no toast
+            let mut finished: Option<std::collections::HashSet<uuid::Uuid>> = None;
             loop {
                 let status = core_clone.get_hive_status().await;
                 let jobs = core_clone.list_jobs().await;
-                if let Ok(mut state) = ui_state_clone.lock() {
+                let completed = jobs.iter().filter(|job| {
+                    matches!(job.status, crate::agent::editor_queue::JobStatus::Completed { .. })
+                });
+                let just_finished: Vec<PathBuf> = match finished.as_mut() {
+                    Some(known) => completed
+                        .filter(|job| known.insert(job.id))
+                        .map(|job| job.output.clone())
+                        .collect(),
+                    None => {
+                        finished = Some(completed.map(|job| job.id).collect());
+                        Vec::new()
+                    }
+                };
+                if let Some(reason) = crate::agent::transcription::take_unavailable_notice() {
+                    lock_ui(&ui_state_clone).transcription_notice = Some(TranscriptionNotice {
+                        reason,
+                        status: String::new(),
+                        downloading: false,
+                    });
+                    core_clone.gui.wake();
+                }
+                if let Some(output) = just_finished.last() {
+                    lock_ui(&ui_state_clone).render_toast = Some(RenderToast {
+                        output: output.clone(),
+                        notes: Vec::new(),
+                    });
+                    let actions = crate::config::current().after_render;
+                    if !actions.is_empty() {
+                        let (ui_state, core, output) =
+                            (ui_state_clone.clone(), core_clone.clone(), output.clone());
+                        tokio::spawn(async move {
+                            let outcomes = post_render::run(&actions, &output, false).await;
+                            show_render_notes(&ui_state, &output, &outcomes);
+                            core.gui.wake();
+                        });
+                    }
+                }
+                let (changed, session_id) = {
+                    let mut state = lock_ui(&ui_state_clone);
+                    let changed = state.hive_mind_status != status
+                        || state.recent_jobs.len() != jobs.len()
+                        || state
+                            .recent_jobs
+                            .iter()
+                            .zip(&jobs)
+                            .any(|(a, b)| a.id != b.id || a.status != b.status);
                     state.hive_mind_status = status;
                     state.recent_jobs = jobs;
+                    (changed, state.editor_session_id.clone())
+                };
+                if changed || !just_finished.is_empty() {
+                    core_clone.gui.wake();
+                }
+
+                if let Some(id) = session_id {
+                    if live.as_ref().map(|(live_id, _)| live_id != &id).unwrap_or(true) {
+                        live = Some((id.clone(), core_clone.collab.subscribe(&id)));
+                    }
+                }
+
+                let live_event = async {
+                    match live.as_mut() {
+                        Some((_, rx)) => rx.recv().await,
+                        None => std::future::pending().await,
+                    }
+                };
+                tokio::select! {
+                    event = bus.recv() => match event {
+                        // Queue progress lives in the job's shared cell; the
+                        // job list itself is refreshed on the next pass.
+                        Ok(SynoidEvent::JobProgress { .. }) => core_clone.gui.wake(),
+                        Ok(_) | Err(RecvError::Lagged(_)) => {}
+                        Err(RecvError::Closed) => {
+                            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await
+                        }
+                    },
+                    event = live_event => {
+                        if let Ok(CollabEvent::RenderProgress { progress, status }) = event {
+                            lock_ui(&ui_state_clone).editor_api_status =
+                                format!("Render: {} ({:.0}%)", status, progress * 100.0);
+                            core_clone.gui.wake();
+                        }
+                    }
+                    _ = tokio::time::sleep(tokio::time::Duration::from_secs(15)) => {}
                 }
-                tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
             }
         });
 
         Self {
+            logs: core.get_logs(),
             core,
             ui_state: return_state,
             tree_state,
             active_command,
             preview_texture: None,
+            video_texture: None,
+            frame_counter: FrameCounter::from_config(),
         }
     }
 
@@ -597,6 +906,9 @@ impl SynoidApp {
     }
 
     fn render_command_panel(&self, ui: &mut egui::Ui, state: &mut UiState) {
+        if self.active_command.needs_brain() {
+            self.render_brain_offline_banner(ui);
+        }
         match self.active_command {
             ActiveCommand::Dashboard => self.render_dashboard(ui, state),
             ActiveCommand::Clip => self.render_clip_panel(ui, state),
@@ -613,11 +925,13 @@ impl SynoidApp {
             ActiveCommand::AudioMixer => self.render_audio_mixer_panel(ui, state),
             ActiveCommand::Discovery => self.render_discovery_panel(ui, state),
             ActiveCommand::GpuStatus => self.render_gpu_status_panel(ui, state),
+            ActiveCommand::Setup => self.render_setup_panel(ui, state),
             ActiveCommand::AutoImprove => self.render_auto_improve_panel(ui, state),
             ActiveCommand::Gemma4 => self.render_gemma4_panel(ui, state),
+            ActiveCommand::History => self.render_history_panel(ui, state),
             ActiveCommand::Editor => {
                 // Create/reuse session then open React editor in browser
-                let _core = self.core.clone();
+                let core = self.core.clone();
                 let ui_ptr = self.ui_state.clone();
                 let session_id = state.editor_session_id.clone();
                 if session_id.is_none() {
@@ -630,19 +944,18 @@ impl SynoidApp {
                             Ok(r) => {
                                 if let Ok(json) = r.json::<serde_json::Value>().await {
                                     let id = json["id"].as_str().unwrap_or("").to_string();
-                                    if let Ok(mut s) = ui_ptr.lock() {
-                                        s.editor_session_id = Some(id.clone());
-                                        s.editor_api_status =
-                                            format!("Session: {}", &id[..8.min(id.len())]);
-                                    }
+                                    let mut s = lock_ui(&ui_ptr);
+                                    s.editor_api_status =
+                                        format!("Session: {}", &id[..8.min(id.len())]);
+                                    s.editor_session_id = Some(id);
                                 }
                             }
                             Err(_) => {
-                                if let Ok(mut s) = ui_ptr.lock() {
-                                    s.editor_api_status = "⚠ Server not running".to_string();
-                                }
+                                lock_ui(&ui_ptr).editor_api_status =
+                                    "⚠ Server not running".to_string();
                             }
                         }
+                        core.gui.wake();
                     });
                 }
             }
@@ -872,10 +1185,7 @@ impl SynoidApp {
                         });
                     }
                     if ui.add(btn_style("REFRESH", COLOR_ACCENT_BLUE)).clicked() {
-                        let core = self.core.clone();
-                        tokio::spawn(async move {
-                            let _ = core.initialize_hive_mind().await;
-                        });
+                        self.core.connect_brain();
                     }
                 });
             });
@@ -883,7 +1193,7 @@ impl SynoidApp {
             // RIGHT: Log Feed
             let col = &mut cols[2];
             Self::crt_panel(col, "Log Feed", COLOR_ACCENT_ORANGE, |ui| {
-                let logs = self.core.get_logs();
+                let logs = &self.logs;
                 egui::ScrollArea::vertical()
                     .max_height(240.0)
                     .id_salt("dash_log_scroll")
@@ -1127,17 +1437,38 @@ impl SynoidApp {
         });
     }
 
+    /// The playing video's frame, else the input's still preview.
+    fn shown_texture(&self) -> Option<&egui::TextureHandle> {
+        self.video_texture.as_ref().or(self.preview_texture.as_ref())
+    }
+
     fn render_preview_panel(&self, ui: &mut egui::Ui, state: &mut UiState) {
         ui.vertical_centered(|ui| {
             ui.add_space(10.0);
             ui.heading(egui::RichText::new("📺 Preview").color(COLOR_ACCENT_BLUE));
             ui.add_space(8.0);
 
-            if let Some(texture) = &self.preview_texture {
+            if let Some(texture) = self.shown_texture() {
                 let size = texture.size_vec2();
                 let max_width = ui.available_width() - 20.0;
                 let scale = max_width / size.x;
-                ui.image((texture.id(), size * scale));
+                let response = ui.add(
+                    egui::Image::new((texture.id(), size * scale)).sense(egui::Sense::drag()),
+                );
+                // Drag across the frame to move the wipe divider
+                if state.split_player.is_some() && state.split_wipe.is_some() {
+                    if let Some(pos) = response.interact_pointer_pos() {
+                        let rect = response.rect;
+                        state.split_wipe = Some(((pos.x - rect.left()) / rect.width()).clamp(0.0, 1.0));
+                    }
+                    if let Some(at) = state.split_wipe {
+                        let x = response.rect.left() + response.rect.width() * at;
+                        ui.painter().line_segment(
+                            [egui::pos2(x, response.rect.top()), egui::pos2(x, response.rect.bottom())],
+                            egui::Stroke::new(2.0, COLOR_ACCENT_BLUE),
+                        );
+                    }
+                }
             } else {
                 ui.add_space(50.0);
                 ui.label("No Preview Available");
@@ -1185,9 +1516,77 @@ impl SynoidApp {
                     }
                 });
             }
+
+            self.render_split_preview_controls(ui, state);
         });
     }
 
+    /// Before/after controls, shown once the output has an edit plan beside it.
+    fn render_split_preview_controls(&self, ui: &mut egui::Ui, state: &mut UiState) {
+        use crate::agent::video_player::SplitPlayer;
+
+        if state.output_path.is_empty() {
+            return;
+        }
+        let output = state.output_path.clone();
+        let Some(duration) = state.split_plan.get(&output).map(|p| p.output_duration()) else {
+            return;
+        };
+
+        ui.add_space(10.0);
+        ui.separator();
+        ui.label(egui::RichText::new("🔀 Before / After").color(COLOR_ACCENT_BLUE));
+
+        let mut wipe = state.split_wipe.is_some();
+        if ui.checkbox(&mut wipe, "Wipe divider (drag on frame)").changed() {
+            state.split_wipe = if wipe { Some(0.5) } else { None };
+        }
+
+        let scrub = ui.add(
+            egui::Slider::new(&mut state.split_position, 0.0..=duration.max(0.1))
+                .text("Output time")
+                .custom_formatter(|v, _| format_time(v)),
+        );
+        let position = state.split_position;
+        if let Some(src) = state.split_plan.get(&output).and_then(|p| p.source_time(position)) {
+            ui.label(
+                egui::RichText::new(format!("Source: {}", format_time(src)))
+                    .small()
+                    .color(COLOR_TEXT_SECONDARY),
+            );
+        }
+
+        let start_split = |state: &mut UiState| {
+            state.video_player = None;
+            match SplitPlayer::new(&state.output_path, state.split_position) {
+                Ok(p) => state.split_player = Some(p),
+                Err(e) => self
+                    .core
+                    .log(&format!("[GUI] ❌ Failed to start split preview: {}", e)),
+            }
+        };
+
+        // Re-seek both decoders once the user lets go of the scrubber
+        if scrub.drag_stopped() || (scrub.changed() && !scrub.dragged()) {
+            state.split_player = None;
+            start_split(state);
+        }
+
+        if state.split_player.is_some() {
+            if ui
+                .button(egui::RichText::new("⏹ Stop Compare").color(COLOR_ACCENT_RED))
+                .clicked()
+            {
+                state.split_player = None;
+            }
+        } else if ui
+            .button(egui::RichText::new("▶ Compare with Source").color(COLOR_ACCENT_GREEN))
+            .clicked()
+        {
+            start_split(state);
+        }
+    }
+
     // --- Command Panels ---
 
     fn render_clip_panel(&self, ui: &mut egui::Ui, state: &mut UiState) {
@@ -1199,27 +1598,42 @@ impl SynoidApp {
         ui.add_space(10.0);
 
         ui.horizontal(|ui| {
-            ui.label("Start (sec):");
-            ui.add(egui::TextEdit::singleline(&mut state.clip_start).desired_width(80.0));
-            ui.label("Duration (sec):");
-            ui.add(egui::TextEdit::singleline(&mut state.clip_duration).desired_width(80.0));
+            ui.label("Start:");
+            ui.add(
+                egui::TextEdit::singleline(&mut state.clip_start)
+                    .hint_text("1:23:45")
+                    .desired_width(80.0),
+            );
+            ui.label("Duration:");
+            ui.add(
+                egui::TextEdit::singleline(&mut state.clip_duration)
+                    .hint_text("5:10")
+                    .desired_width(80.0),
+            );
+            ui.label("or End:");
+            ui.add(
+                egui::TextEdit::singleline(&mut state.clip_end)
+                    .hint_text("1:28:55")
+                    .desired_width(80.0),
+            );
         });
+        let span = clip_span(state);
+        if let Err(e) = &span {
+            ui.colored_label(COLOR_ACCENT_RED, e);
+        }
         ui.add_space(10.0);
 
         self.render_output_file_picker(ui, state);
         ui.add_space(20.0);
 
-        if ui
-            .add(
-                egui::Button::new(egui::RichText::new("✂️ Trim Video").size(16.0))
-                    .fill(COLOR_ACCENT_BLUE),
-            )
-            .clicked()
-        {
+        let trim = ui.add_enabled(
+            span.is_ok(),
+            egui::Button::new(egui::RichText::new("✂️ Trim Video").size(16.0))
+                .fill(COLOR_ACCENT_BLUE),
+        );
+        if let (true, Ok((start, duration))) = (trim.clicked(), span) {
             let core = self.core.clone();
             let input = PathBuf::from(&state.input_path);
-            let start: f64 = state.clip_start.parse().unwrap_or(0.0);
-            let duration: f64 = state.clip_duration.parse().unwrap_or(10.0);
             let output = if !state.output_path.is_empty() {
                 Some(PathBuf::from(&state.output_path))
             } else {
@@ -1266,11 +1680,46 @@ impl SynoidApp {
             };
 
             tokio::spawn(async move {
-                let _ = core.compress_video(&input, size, output).await;
+                let _ = core
+                    .compress_video(&input, size, output, false, Default::default())
+                    .await;
             });
         }
     }
 
+    /// Shown above the LLM panels while Ollama is unreachable, with a retry
+    /// that reconnects in the background.
+    fn render_brain_offline_banner(&self, ui: &mut egui::Ui) {
+        let link = self.core.brain_link();
+        let Some(notice) = self.core.brain_offline_notice() else {
+            if link == crate::agent::core::BrainLink::Connecting {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(egui::RichText::new(tr("brain.connecting")).color(COLOR_TEXT_SECONDARY));
+                });
+            }
+            return;
+        };
+        egui::Frame::none()
+            .fill(COLOR_PANEL_BG)
+            .stroke(egui::Stroke::new(1.0, COLOR_ACCENT_RED))
+            .inner_margin(egui::Margin::same(8.0))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(format!("🧠 {}", notice)).strong().color(COLOR_ACCENT_RED));
+                    if ui.button(format!("🔄 {}", tr("brain.retry"))).clicked() {
+                        self.core.connect_brain();
+                    }
+                });
+                ui.label(
+                    egui::RichText::new(tr("brain.offline_hint"))
+                        .small()
+                        .color(COLOR_TEXT_SECONDARY),
+                );
+            });
+        ui.add_space(8.0);
+    }
+
     fn render_brain_panel(&self, ui: &mut egui::Ui, state: &mut UiState) {
         ui.heading(egui::RichText::new("🧠 Brain Command").color(COLOR_ACCENT_BLUE));
         ui.separator();
@@ -1417,7 +1866,7 @@ impl SynoidApp {
                 let enable_censoring = state.enable_censoring;
                 tokio::spawn(async move {
                     let _ = core
-                        .process_youtube_intent(&input, &intent, output, None, false, 0, enable_subtitles, enable_censoring)
+                        .process_youtube_intent(&input, &intent, output, None, &DownloadOptions::from_config(), false, 0, enable_subtitles, enable_censoring)
                         .await;
                 });
             }
@@ -1594,11 +2043,15 @@ impl SynoidApp {
                     crate::agent::source_tools::download_youtube(&url, academy_dir, None).await
                 {
                     let local_path = info.local_path;
-                    if let Err(e) =
+                    let check_path = local_path.clone();
+                    let verdict = tokio::task::spawn_blocking(move || {
                         crate::agent::download_guard::DownloadGuard::validate_downloaded_file(
-                            &local_path,
+                            &check_path,
                         )
-                    {
+                    })
+                    .await
+                    .unwrap_or_else(|e| Err(e.to_string()));
+                    if let Err(e) = verdict {
                         tracing::warn!("[GUI] Downloaded file blocked by Sentinel: {}", e);
                         let _ = tokio::fs::remove_file(local_path).await;
                         return;
@@ -1685,9 +2138,7 @@ impl SynoidApp {
             tokio::spawn(async move {
                 let path = std::path::PathBuf::from(input_path_str);
                 if let Ok(suggs) = core.get_suggestions(&path).await {
-                    if let Ok(mut s) = ui_ptr.lock() {
-                        s.suggestions = suggs;
-                    }
+                    lock_ui(&ui_ptr).suggestions = suggs;
                 }
             });
         }
@@ -1771,6 +2222,8 @@ impl SynoidApp {
             .sentinel_active
             .load(std::sync::atomic::Ordering::Relaxed);
 
+        let sentinel_enabled = crate::agent::defense::Sentinel::is_enabled();
+
         if sentinel_active {
             if ui
                 .add(
@@ -1783,7 +2236,8 @@ impl SynoidApp {
             }
         } else {
             if ui
-                .add(
+                .add_enabled(
+                    sentinel_enabled,
                     egui::Button::new(egui::RichText::new("🛡️ Activate Sentinel").size(16.0))
                         .fill(COLOR_ACCENT_RED),
                 )
@@ -1804,9 +2258,9 @@ impl SynoidApp {
         }
         ui.add_space(5.0);
         ui.label(
-            egui::RichText::new("Note: Requires SYNOID_ENABLE_SENTINEL=true environment variable.")
+            egui::RichText::new("Note: Requires enable_sentinel in the config file or SYNOID_ENABLE_SENTINEL=true.")
                 .small()
-                .color(COLOR_TEXT_SECONDARY),
+                .color(if sentinel_enabled { COLOR_TEXT_SECONDARY } else { COLOR_ACCENT_RED }),
         );
     }
 
@@ -1830,7 +2284,7 @@ impl SynoidApp {
             let topic = state.research_topic.clone();
 
             tokio::spawn(async move {
-                let _ = core.process_research(&topic, 5).await;
+                let _ = core.process_research(&topic, 5, "youtube", None).await;
             });
         }
     }
@@ -1862,8 +2316,7 @@ impl SynoidApp {
                     let path_clone = path.clone();
                     tokio::spawn(async move {
                         if let Ok(tracks) = core.get_audio_tracks(&path_clone).await {
-                            let mut s = ui_state_ptr.lock().unwrap();
-                            s.detected_tracks = tracks;
+                            lock_ui(&ui_state_ptr).detected_tracks = tracks;
                         }
                     });
                 }
@@ -1875,8 +2328,7 @@ impl SynoidApp {
                 let path = std::path::PathBuf::from(&state.input_path);
                 tokio::spawn(async move {
                     if let Ok(tracks) = core.get_audio_tracks(&path).await {
-                        let mut s = ui_state_ptr.lock().unwrap();
-                        s.detected_tracks = tracks;
+                        lock_ui(&ui_state_ptr).detected_tracks = tracks;
                     }
                 });
             }
@@ -1950,13 +2402,117 @@ impl SynoidApp {
                 });
         }
 
+        ui.add_space(15.0);
+        ui.horizontal(|ui| {
+            if ui
+                .button("📊 Analyze Loudness")
+                .on_hover_text("Integrated loudness, peaks and clipping of the first audio track")
+                .clicked()
+            {
+                let core = self.core.clone();
+                let ui_state_ptr = self.ui_state.clone();
+                let path_str = state.input_path.clone();
+                tokio::spawn(async move {
+                    let path = PathBuf::from(&path_str);
+                    match crate::agent::audio_tools::analyze_loudness(&path).await {
+                        Ok(report) => {
+                            let mut ui_state = lock_ui(&ui_state_ptr);
+                            ui_state.loudness_report = Some(report);
+                            ui_state.loudness_path = path_str;
+                        }
+                        Err(e) => core.log(&format!("❌ Loudness analysis failed: {}", e)),
+                    }
+                });
+            }
+            ui.label(
+                egui::RichText::new("Check for clipping and quiet tracks before setting gain")
+                    .small()
+                    .color(COLOR_TEXT_SECONDARY),
+            );
+        });
+        if let Some(report) = state
+            .loudness_report
+            .as_ref()
+            .filter(|_| state.loudness_path == state.input_path)
+        {
+            ui.group(|ui| {
+                egui::Grid::new("loudness_report").num_columns(2).show(ui, |ui| {
+                    ui.label("Integrated");
+                    ui.label(format!("{:.1} LUFS", report.integrated_lufs));
+                    ui.end_row();
+                    ui.label("True peak");
+                    ui.label(format!("{:.1} dBTP", report.true_peak_dbtp));
+                    ui.end_row();
+                    ui.label("Sample peak");
+                    ui.label(format!("{:.1} dBFS", report.peak_dbfs));
+                    ui.end_row();
+                    ui.label("Clipped samples");
+                    ui.label(report.clipped_samples.to_string());
+                    ui.end_row();
+                    ui.label("Gain to -14 LUFS");
+                    ui.label(format!("{:+.1} dB", report.suggested_gain_db(-14.0)));
+                    ui.end_row();
+                });
+                let warnings = report.warnings();
+                if warnings.is_empty() {
+                    ui.label(egui::RichText::new("✅ No clipping, level is usable").color(COLOR_ACCENT_GREEN));
+                }
+                for warning in warnings {
+                    ui.label(egui::RichText::new(format!("⚠️ {}", warning)).color(COLOR_ACCENT_ORANGE));
+                }
+            });
+        }
+
+        ui.add_space(15.0);
+        ui.group(|ui| {
+            ui.checkbox(&mut state.duck_music, "🦆 Duck music under speech")
+                .on_hover_text("Lower the music track while someone talks (needs separate music and voice tracks)");
+            ui.add_enabled_ui(state.duck_music, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Dip depth");
+                    ui.add(egui::Slider::new(&mut state.duck_depth_db, 3.0..=24.0).suffix(" dB"));
+                });
+            });
+        });
+
         ui.add_space(20.0);
         if ui
             .button(egui::RichText::new("🎚️ Apply Mix to File").size(16.0))
             .clicked()
         {
-            self.core
-                .log("Mixer application pending full audio-stitching implementation.");
+            if state.duck_music {
+                let input = PathBuf::from(&state.input_path);
+                let stem = input.file_stem().unwrap_or_default().to_string_lossy().to_string();
+                let ext = input.extension().unwrap_or_default().to_string_lossy().to_string();
+                let output = input.with_file_name(format!("{}_ducked.{}", stem, ext));
+                let duck = crate::agent::production_tools::SpeechDucking {
+                    depth_db: state.duck_depth_db as f64,
+                    ..Default::default()
+                };
+                let core = self.core.clone();
+                tokio::spawn(async move {
+                    use crate::agent::production_tools;
+                    // A transcript next to the file gives exact speech regions;
+                    // without one the voice track drives a sidechain compressor.
+                    let speech = std::fs::read_to_string(input.with_extension("srt"))
+                        .ok()
+                        .and_then(|srt| crate::agent::transcription::parse_srt(&srt).ok())
+                        .map(|t| production_tools::speech_regions(&t))
+                        .unwrap_or_default();
+                    core.log(&format!("🎚️ Ducking music under speech in {}...", input.display()));
+                    match production_tools::duck_music_under_speech(&input, &output, &speech, &duck).await {
+                        Ok(method) => core.log(&format!(
+                            "✅ Ducked mix ({:?}) saved to {}",
+                            method,
+                            output.display()
+                        )),
+                        Err(e) => core.log(&format!("❌ Ducking failed: {}", e)),
+                    }
+                });
+            } else {
+                self.core
+                    .log("Mixer application pending full audio-stitching implementation.");
+            }
         }
     }
 
@@ -2200,6 +2756,91 @@ impl SynoidApp {
         );
     }
 
+    fn render_history_panel(&self, ui: &mut egui::Ui, state: &mut UiState) {
+        use crate::agent::operation_history::{OperationHistory, OperationKind};
+
+        ui.heading(egui::RichText::new("🕘 Operation History").color(COLOR_ACCENT_BLUE));
+        ui.separator();
+        ui.add_space(10.0);
+
+        // Re-read the history file at most every couple of seconds
+        let stale = state
+            .history_loaded_at
+            .is_none_or(|t| t.elapsed() > std::time::Duration::from_secs(2));
+        if stale {
+            state.history_records = OperationHistory::for_instance(&self.core.instance_id).load();
+            state.history_loaded_at = Some(std::time::Instant::now());
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Filter:");
+            ui.add(egui::TextEdit::singleline(&mut state.history_filter).desired_width(240.0));
+            ui.label(
+                egui::RichText::new(format!("{} recorded", state.history_records.len()))
+                    .small()
+                    .color(COLOR_TEXT_SECONDARY),
+            );
+        });
+        ui.add_space(8.0);
+
+        let mut rerun = None;
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for record in state.history_records.iter().rev() {
+                    if !record.matches(&state.history_filter) {
+                        continue;
+                    }
+                    ui.horizontal(|ui| {
+                        let color = if record.success { COLOR_ACCENT_GREEN } else { COLOR_ACCENT_RED };
+                        ui.label(egui::RichText::new(format!("#{}", record.id)).color(color).strong());
+                        ui.label(record.kind.label());
+                        ui.label(
+                            egui::RichText::new(record.input.display().to_string())
+                                .small()
+                                .color(COLOR_TEXT_SECONDARY),
+                        )
+                        .on_hover_text(record.summary());
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.button("🔁 Re-run").clicked() {
+                                rerun = Some(record.clone());
+                            }
+                            ui.label(format!("{:.1}s", record.duration_secs));
+                        });
+                    });
+                }
+            });
+
+        // Pre-fill the matching command panel so the user can tweak and run it
+        if let Some(record) = rerun {
+            state.input_path = record.input.to_string_lossy().to_string();
+            if let Some(out) = &record.output {
+                state.output_path = out.to_string_lossy().to_string();
+            }
+            let param = |k: &str| record.param(k).unwrap_or_default().to_string();
+            state.pending_command = Some(match record.kind {
+                OperationKind::Clip => {
+                    state.clip_start = param("start");
+                    state.clip_duration = param("duration");
+                    state.clip_end.clear();
+                    ActiveCommand::Clip
+                }
+                OperationKind::Compress => {
+                    state.compress_size = param("size_mb");
+                    ActiveCommand::Compress
+                }
+                OperationKind::Edit => {
+                    state.intent = param("intent");
+                    state.enable_subtitles = record.param_bool("subtitles").unwrap_or(true);
+                    state.enable_censoring = record.param_bool("censoring").unwrap_or(true);
+                    ActiveCommand::Embody
+                }
+            });
+            self.core
+                .log(&format!("[GUI] 🔁 Loaded operation #{} for re-run", record.id));
+        }
+    }
+
     fn render_gemma4_panel(&self, ui: &mut egui::Ui, state: &mut UiState) {
         use std::sync::atomic::Ordering;
 
@@ -2407,13 +3048,355 @@ impl SynoidApp {
         );
     }
 
+    /// Corner notice for a finished render, with a button per post-render
+    /// action. The configured actions have already run by the time it shows.
+    fn render_toast(&self, ctx: &egui::Context) {
+        let mut state = lock_ui(&self.ui_state);
+        let Some(toast) = &state.render_toast else {
+            return;
+        };
+        let mut dismissed = false;
+        egui::Area::new(egui::Id::new("render_toast"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-16.0, -40.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).fill(COLOR_SIDEBAR_BG).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(format!("✅ {}", tr("toast.render_complete"))).strong().color(COLOR_ACCENT_GREEN));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            dismissed = ui.small_button("✖").clicked();
+                        });
+                    });
+                    ui.label(
+                        egui::RichText::new(toast.output.display().to_string())
+                            .monospace()
+                            .color(COLOR_TEXT_SECONDARY),
+                    );
+                    ui.horizontal(|ui| {
+                        for action in PostRenderAction::ALL {
+                            if ui.button(action.label()).clicked() {
+                                let (ui_state, core, output) =
+                                    (self.ui_state.clone(), self.core.clone(), toast.output.clone());
+                                tokio::spawn(async move {
+                                    let outcomes = post_render::run(&[action], &output, false).await;
+                                    show_render_notes(&ui_state, &output, &outcomes);
+                                    core.gui.wake();
+                                });
+                            }
+                        }
+                    });
+                    for note in &toast.notes {
+                        ui.label(egui::RichText::new(note).small().color(COLOR_TEXT_SECONDARY));
+                    }
+                });
+            });
+        if dismissed {
+            state.render_toast = None;
+        }
+    }
+
+    /// Dialog for an edit that ran without a transcript, offering the small
+    /// fallback Whisper model so the next one can transcribe.
+    fn transcription_notice(&self, ctx: &egui::Context) {
+        let mut state = lock_ui(&self.ui_state);
+        let Some(notice) = state.transcription_notice.as_mut() else {
+            return;
+        };
+        let model = crate::agent::transcription::FALLBACK_MODEL;
+        let models_dir = crate::config::current().models_dir();
+        let have_model = crate::agent::transcription::model_path_in(&models_dir, model).is_file();
+        let mut dismissed = false;
+        egui::Window::new(format!("⚠️ {}", tr("notice.title")))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.label(&notice.reason);
+                ui.label(
+                    egui::RichText::new(tr("notice.body")).color(COLOR_TEXT_SECONDARY),
+                );
+                if !notice.status.is_empty() {
+                    ui.label(egui::RichText::new(&notice.status).small());
+                }
+                ui.horizontal(|ui| {
+                    let label = format!("⬇️ {}", tr("notice.download").replace("{}", model));
+                    if !have_model
+                        && ui.add_enabled(!notice.downloading, egui::Button::new(label)).clicked()
+                    {
+                        notice.downloading = true;
+                        let (ui_state, core) = (self.ui_state.clone(), self.core.clone());
+                        let models_dir = models_dir.clone();
+                        tokio::spawn(async move {
+                            let progress_state = ui_state.clone();
+                            let progress_core = core.clone();
+                            let mut last_percent = None;
+                            let result = crate::agent::transcription::download_model(
+                                &models_dir,
+                                model,
+                                |done, total| {
+                                    let percent = total.filter(|t| *t > 0).map(|t| done * 100 / t);
+                                    if percent == last_percent {
+                                        return;
+                                    }
+                                    last_percent = percent;
+                                    if let Some(notice) = lock_ui(&progress_state).transcription_notice.as_mut() {
+                                        notice.status = match percent {
+                                            Some(p) => format!("⬇️ Downloading ggml-{}.bin — {}%", model, p),
+                                            None => format!("⬇️ Downloading ggml-{}.bin — {} MB", model, done / (1024 * 1024)),
+                                        };
+                                    }
+                                    progress_core.gui.wake();
+                                },
+                            )
+                            .await;
+                            if let Some(notice) = lock_ui(&ui_state).transcription_notice.as_mut() {
+                                notice.status = match result {
+                                    Ok(path) => format!("✅ Saved {}", path.display()),
+                                    Err(e) => format!("❌ Download failed: {}", e),
+                                };
+                                notice.downloading = false;
+                            }
+                            core.gui.wake();
+                        });
+                    }
+                    dismissed = ui.add_enabled(!notice.downloading, egui::Button::new(tr("notice.dismiss"))).clicked();
+                });
+            });
+        if dismissed {
+            state.transcription_notice = None;
+        }
+    }
+
+    /// `config::current()` with the Setup panel's unsaved answers applied.
+    fn setup_answers(state: &UiState) -> (crate::config::ConfigLayer, crate::config::SynoidConfig) {
+        let mut config = crate::config::current();
+        let changed = |value: &str, current: &str| {
+            let value = value.trim();
+            (!value.is_empty() && value != current).then(|| value.to_string())
+        };
+        let answers = crate::config::ConfigLayer {
+            model: changed(&state.setup_model, &config.model),
+            video_dir: changed(&state.setup_video_dir, config.video_dir.as_deref().unwrap_or_default()),
+            cache_dir: changed(&state.setup_cache_dir, &config.cache_dir),
+            whisper_model: changed(&state.setup_whisper_model, &config.whisper_model),
+            after_render: (state.setup_after_render != config.after_render)
+                .then(|| state.setup_after_render.clone()),
+            ..Default::default()
+        };
+        crate::agent::setup::apply(&mut config, &answers);
+        (answers, config)
+    }
+
+    /// Re-run every doctor check against the panel's answers.
+    fn refresh_setup_checks(&self, state: &mut UiState) {
+        let (_, config) = Self::setup_answers(state);
+        let ui_state = self.ui_state.clone();
+        let core = self.core.clone();
+        tokio::spawn(async move {
+            let checks = crate::agent::doctor::run_all(&config).await;
+            lock_ui(&ui_state).setup_checks = checks;
+            core.gui.wake();
+        });
+    }
+
+    fn render_setup_panel(&self, ui: &mut egui::Ui, state: &mut UiState) {
+        use crate::agent::doctor::CheckArea;
+        use crate::agent::setup;
+
+        ui.heading(egui::RichText::new("🛠️ Setup").color(COLOR_ACCENT_GREEN));
+        ui.separator();
+        ui.add_space(6.0);
+
+        if !state.setup_loaded {
+            let config = crate::config::current();
+            state.setup_model = config.model;
+            state.setup_video_dir = config.video_dir.unwrap_or_else(setup::default_video_dir);
+            state.setup_cache_dir = config.cache_dir;
+            state.setup_whisper_model = config.whisper_model;
+            state.setup_after_render = config.after_render;
+            state.setup_loaded = true;
+            self.refresh_setup_checks(state);
+        }
+
+        ui.label(
+            egui::RichText::new(
+                "The same checks as `synoid-core doctor`. Fix what is flagged, then save to write the config file.",
+            )
+            .color(COLOR_TEXT_SECONDARY),
+        );
+        ui.add_space(8.0);
+
+        for area in CheckArea::ALL {
+            ui.label(egui::RichText::new(area.title()).strong());
+            for check in state.setup_checks.iter().filter(|c| c.area == area) {
+                ui.label(format!("  {}", check.line()));
+            }
+            ui.add_space(4.0);
+
+            match area {
+                CheckArea::Ollama => {
+                    ui.horizontal(|ui| {
+                        ui.label("Reasoning model:");
+                        ui.text_edit_singleline(&mut state.setup_model);
+                        if ui.add_enabled(!state.setup_busy, egui::Button::new("⬇️ Pull")).clicked() {
+                            state.setup_busy = true;
+                            let (_, config) = Self::setup_answers(state);
+                            let ui_state = self.ui_state.clone();
+                            let core = self.core.clone();
+                            tokio::spawn(async move {
+                                use crate::agent::ollama_pull;
+                                let base = crate::agent::doctor::ollama_base(&config.api_url).to_string();
+                                let model = config.model.clone();
+                                let pull = tokio::spawn(async move { ollama_pull::pull_model(&base, &model).await });
+                                while !pull.is_finished() {
+                                    if let Some(status) = ollama_pull::pull_status() {
+                                        lock_ui(&ui_state).setup_status = status;
+                                    }
+                                    core.gui.wake();
+                                    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+                                }
+                                let status = match pull.await {
+                                    Ok(Ok(())) => format!("✅ Pulled {}", config.model),
+                                    Ok(Err(e)) => format!("❌ Pull failed: {}", e),
+                                    Err(e) => format!("❌ Pull failed: {}", e),
+                                };
+                                let checks = crate::agent::doctor::run_all(&config).await;
+                                {
+                                    let mut state = lock_ui(&ui_state);
+                                    state.setup_status = status;
+                                    state.setup_checks = checks;
+                                    state.setup_busy = false;
+                                }
+                                core.gui.wake();
+                            });
+                        }
+                    });
+                }
+                CheckArea::Dirs => {
+                    for (label, value) in [
+                        ("Video directory:", &mut state.setup_video_dir),
+                        ("Cache directory:", &mut state.setup_cache_dir),
+                    ] {
+                        ui.horizontal(|ui| {
+                            ui.label(label);
+                            ui.text_edit_singleline(value);
+                            if ui.button("📂").clicked() {
+                                if let Some(path) = rfd::FileDialog::new().pick_folder() {
+                                    *value = path.to_string_lossy().to_string();
+                                }
+                            }
+                        });
+                    }
+                }
+                CheckArea::Whisper => {
+                    ui.horizontal(|ui| {
+                        ui.label("Whisper model:");
+                        for model in setup::WHISPER_MODELS {
+                            ui.selectable_value(&mut state.setup_whisper_model, model.name.to_string(), model.name);
+                        }
+                        if ui.add_enabled(!state.setup_busy, egui::Button::new("⬇️ Download")).clicked() {
+                            state.setup_busy = true;
+                            let (_, config) = Self::setup_answers(state);
+                            let ui_state = self.ui_state.clone();
+                            let core = self.core.clone();
+                            tokio::spawn(async move {
+                                let model = config.whisper_model.clone();
+                                let progress_state = ui_state.clone();
+                                let progress_core = core.clone();
+                                let mut last_percent = None;
+                                let result = crate::agent::transcription::download_model(
+                                    &config.models_dir(),
+                                    &model,
+                                    |done, total| {
+                                        let percent = total.filter(|t| *t > 0).map(|t| done * 100 / t);
+                                        if percent == last_percent {
+                                            return;
+                                        }
+                                        last_percent = percent;
+                                        lock_ui(&progress_state).setup_status = match percent {
+                                            Some(p) => format!("⬇️ Downloading ggml-{}.bin — {}%", model, p),
+                                            None => format!("⬇️ Downloading ggml-{}.bin — {} MB", model, done / (1024 * 1024)),
+                                        };
+                                        progress_core.gui.wake();
+                                    },
+                                )
+                                .await;
+                                let status = match result {
+                                    Ok(path) => format!("✅ Saved {}", path.display()),
+                                    Err(e) => format!("❌ Download failed: {}", e),
+                                };
+                                let checks = crate::agent::doctor::run_all(&config).await;
+                                {
+                                    let mut state = lock_ui(&ui_state);
+                                    state.setup_status = status;
+                                    state.setup_checks = checks;
+                                    state.setup_busy = false;
+                                }
+                                core.gui.wake();
+                            });
+                        }
+                    });
+                }
+                CheckArea::Tools | CheckArea::Versions => {}
+            }
+            ui.add_space(8.0);
+        }
+
+        ui.label(egui::RichText::new("After a render").strong());
+        ui.horizontal(|ui| {
+            for action in PostRenderAction::ALL {
+                let mut on = state.setup_after_render.contains(&action);
+                if ui.checkbox(&mut on, action.label()).changed() {
+                    if on {
+                        state.setup_after_render.push(action);
+                    } else {
+                        state.setup_after_render.retain(|a| *a != action);
+                    }
+                }
+            }
+        });
+        ui.add_space(8.0);
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button("🔄 Run checks").clicked() {
+                self.refresh_setup_checks(state);
+            }
+            if ui
+                .add(egui::Button::new("💾 Save").fill(COLOR_ACCENT_GREEN))
+                .clicked()
+            {
+                let (answers, config) = Self::setup_answers(state);
+                state.setup_status = match setup::create_dirs(&config).and_then(|_| {
+                    setup::save(&answers).map_err(|e| e.to_string())
+                }) {
+                    Ok(path) => format!("💾 Saved {} — restart SYNOID to apply it", path.display()),
+                    Err(e) => format!("❌ {}", e),
+                };
+                self.refresh_setup_checks(state);
+            }
+            if !crate::config::file_path().exists() && ui.button("Skip for now").clicked() {
+                // An empty config file marks setup as done; defaults apply
+                if let Err(e) = setup::save(&crate::config::ConfigLayer::default()) {
+                    state.setup_status = format!("❌ {}", e);
+                } else {
+                    state.pending_command = Some(ActiveCommand::Dashboard);
+                }
+            }
+        });
+        if !state.setup_status.is_empty() {
+            ui.add_space(6.0);
+            ui.label(egui::RichText::new(&state.setup_status).color(COLOR_TEXT_SECONDARY));
+        }
+    }
+
     fn render_output_file_picker(&self, ui: &mut egui::Ui, state: &mut UiState) {
         ui.label("Output File:");
         ui.horizontal(|ui| {
             ui.text_edit_singleline(&mut state.output_path);
             if ui.button("📂").clicked() {
                 if let Some(path) = rfd::FileDialog::new()
-                    .set_directory(get_default_videos_path())
+                    .set_directory(default_output_dir(&state.input_path))
                     .save_file()
                 {
                     state.output_path = path.to_string_lossy().to_string();
@@ -2478,7 +3461,20 @@ impl SynoidApp {
                             .with_cross_align(egui::Align::Center),
                         |ui| {
                             ui.add_space(ui.available_width() / 2.0 - 100.0); // Rough center
-                            ui.label(egui::RichText::new("● My Project / ").color(color_text_dim));
+                            let stale = _state
+                                .project_checked_at
+                                .is_none_or(|t| t.elapsed() > std::time::Duration::from_secs(2));
+                            if stale {
+                                _state.project_name =
+                                    crate::agent::project::Project::active(&self.core.instance_id)
+                                        .map(|p| p.manifest.name);
+                                _state.project_checked_at = Some(std::time::Instant::now());
+                            }
+                            let project_label = match &_state.project_name {
+                                Some(name) => format!("● {} / ", name),
+                                None => "○ No Project / ".to_string(),
+                            };
+                            ui.label(egui::RichText::new(project_label).color(color_text_dim));
                             let display_name = if _state.input_path.is_empty() {
                                 "New File".to_string()
                             } else {
@@ -2604,9 +3600,7 @@ impl SynoidApp {
                                                             tracing::info!("[GUI] Transcription complete! Saved to {:?}", out_srt);
                                                         }
                                                     }
-                                                    if let Ok(mut s) = ui_ptr.lock() {
-                                                        s.is_transcribing = false;
-                                                    }
+                                                    lock_ui(&ui_ptr).is_transcribing = false;
                                                 });
                                             }
                                         }
@@ -2724,8 +3718,8 @@ impl SynoidApp {
                             let enable_censoring = _state.enable_censoring;
                             let ui_ptr = self.ui_state.clone();
                             tokio::spawn(async move {
-                                let _ = core.process_youtube_intent(&input, &intent, None, None, false, 0, enable_subtitles, enable_censoring).await;
-                                if let Ok(mut s) = ui_ptr.lock() { s.ai_edit_running = false; }
+                                let _ = core.process_youtube_intent(&input, &intent, None, None, &DownloadOptions::from_config(), false, 0, enable_subtitles, enable_censoring).await;
+                                lock_ui(&ui_ptr).ai_edit_running = false;
                             });
                         }
 
@@ -2757,7 +3751,7 @@ impl SynoidApp {
                 // Draw a nice dark background for the viewer
                 ui.painter().rect_filled(rect, 0.0, color_bg_darkest);
                 
-                if let Some(texture) = &self.preview_texture {
+                if let Some(texture) = self.shown_texture() {
                     let tex_size = texture.size_vec2();
                     let ratio = tex_size.x / tex_size.y;
                     
@@ -2855,6 +3849,14 @@ impl SynoidApp {
                                   }
                               }
                           }
+                          let loop_color = if _state.loop_range.is_some() { color_gold } else { color_text_dim };
+                          if ui
+                              .add(egui::Button::new(egui::RichText::new("🔁").color(loop_color)).fill(egui::Color32::TRANSPARENT))
+                              .on_hover_text("Shift-drag on the ruler to loop a range; click to stop looping")
+                              .clicked()
+                          {
+                              _state.loop_range = None;
+                          }
 
                          ui.add_space(16.0);
                          let pos_text = format_time(_state.video_position);
@@ -2877,10 +3879,39 @@ impl SynoidApp {
                     let start_y = ui.cursor().min.y;
 
                     // Ruler
+                    let px_per_sec = 10.0 * _state.timeline_zoom; // 10px per second rescaled
+                    let timeline_x = ui.cursor().min.x;
                     {
+                        let total_width = (_state.video_duration.max(60.0) as f32) * px_per_sec;
+                        let ruler_rect = egui::Rect::from_min_size(egui::pos2(timeline_x, start_y), egui::vec2(total_width, 20.0));
+
+                        // Clicking or dragging on the ruler scrubs the playhead; the player
+                        // coalesces the stream of seeks while the drag lasts. Shift-drag
+                        // marks a range to loop instead.
+                        let scrub = ui.interact(ruler_rect, ui.id().with("timeline_scrub"), egui::Sense::click_and_drag());
+                        let shift = ui.input(|i| i.modifiers.shift);
+                        if let Some(pointer) = scrub.interact_pointer_pos() {
+                            let t = (((pointer.x - timeline_x) / px_per_sec) as f64).clamp(0.0, _state.video_duration.max(0.0));
+                            if shift || _state.loop_anchor.is_some() {
+                                let anchor = *_state.loop_anchor.get_or_insert(t);
+                                _state.loop_range = Some((anchor, t));
+                            } else if (t - _state.video_position).abs() > f64::EPSILON {
+                                _state.video_position = t;
+                                if let Some(player) = &mut _state.video_player {
+                                    player.seek(t);
+                                }
+                            }
+                        }
+                        if _state.loop_anchor.is_some() && (scrub.drag_stopped() || scrub.clicked()) {
+                            _state.loop_anchor = None;
+                            // A shift-click, or a sliver too short to loop, clears the loop
+                            _state.loop_range = _state
+                                .loop_range
+                                .and_then(|(a, b)| crate::agent::video_player::LoopRange::new(a, b))
+                                .map(|r| (r.start, r.end));
+                        }
+
                         let p = ui.painter();
-                        let total_width = (_state.video_duration.max(60.0) as f32) * 10.0 * _state.timeline_zoom; // 10px per second rescaled
-                        let ruler_rect = egui::Rect::from_min_size(egui::pos2(ui.cursor().min.x, start_y), egui::vec2(total_width, 20.0));
                         p.rect_filled(ruler_rect, 0.0, color_panel_bg);
 
                         let steps = (_state.video_duration / 10.0) as i32 + 1;
@@ -2920,8 +3951,18 @@ impl SynoidApp {
                             p.rect_filled(clip_rect, 6.0, *accent_color);
                         }
 
+                        // Loop region
+                        if let Some((a, b)) = _state.loop_range {
+                            let x0 = timeline_x + a.min(b) as f32 * px_per_sec;
+                            let x1 = timeline_x + a.max(b) as f32 * px_per_sec;
+                            let region = egui::Rect::from_min_max(egui::pos2(x0, start_y), egui::pos2(x1.max(x0 + 1.0), start_y + 150.0));
+                            p.rect_filled(region, 0.0, color_gold.gamma_multiply(0.15));
+                            p.line_segment([egui::pos2(x0, start_y), egui::pos2(x0, start_y + 150.0)], egui::Stroke::new(1.0, color_gold));
+                            p.line_segment([egui::pos2(x1, start_y), egui::pos2(x1, start_y + 150.0)], egui::Stroke::new(1.0, color_gold));
+                        }
+
                         // Playhead
-                        let playhead_x = ui.cursor().min.x + 180.0;
+                        let playhead_x = timeline_x + _state.video_position as f32 * px_per_sec;
                         p.line_segment([egui::pos2(playhead_x, start_y), egui::pos2(playhead_x, start_y + 150.0)], egui::Stroke::new(2.0, color_gold));
                         p.circle_filled(egui::pos2(playhead_x, start_y + 10.0), 6.0, color_gold);
                     }
@@ -2951,7 +3992,7 @@ impl SynoidApp {
                     // The Video Frame
                     let mut video_rect = ui.available_rect_before_wrap();
 
-                    if let Some(texture) = &self.preview_texture {
+                    if let Some(texture) = self.shown_texture() {
                         let tex_size = texture.size_vec2();
                         let aspect = tex_size.x / tex_size.y;
                         let mut new_size = video_rect.size();
@@ -2990,7 +4031,7 @@ impl SynoidApp {
                         .rect_filled(video_rect, 12.0, egui::Color32::from_rgb(0, 0, 0)); // Pure black
 
                     // Texture render if available
-                    if let Some(texture) = &self.preview_texture {
+                    if let Some(texture) = self.shown_texture() {
                         ui.painter().image(
                             texture.id(),
                             video_rect,
@@ -3024,18 +4065,17 @@ impl eframe::App for SynoidApp {
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
         tracing::info!("[GUI] 🛑 Graceful shutdown initiated...");
 
+        // Don't leave renders or downloads running after the window closes
+        crate::agent::proc::kill_all_children();
+
         // Save UI state and settings
-        if let Ok(state) = self.ui_state.lock() {
-            save_settings(
-                &self.core.instance_id,
-                &state,
-                self.active_command,
-                &self.tree_state,
-            );
-            tracing::info!("[GUI] ✅ Settings saved successfully.");
-        } else {
-            tracing::warn!("[GUI] ⚠️ Failed to lock UI state for saving settings.");
-        }
+        save_settings(
+            &self.core.instance_id,
+            &lock_ui(&self.ui_state),
+            self.active_command,
+            &self.tree_state,
+        );
+        tracing::info!("[GUI] ✅ Settings saved successfully.");
 
         // Note: Heavy cleanup (waiting for video jobs, stopping background tasks)
         // is handled in main.rs after GUI closes to avoid blocking the UI thread.
@@ -3045,15 +4085,30 @@ impl eframe::App for SynoidApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         self.configure_style(ctx);
         Self::render_crt_overlay(ctx);
+        if let Some(counter) = &self.frame_counter {
+            counter.tick();
+        }
+        if self.core.gui.take_dirty() {
+            self.logs = self.core.get_logs();
+        }
 
         // --- BACKGROUND LOGIC ---
         {
-            let mut state = self.ui_state.lock().unwrap();
+            let mut state = lock_ui(&self.ui_state);
+
+            // A video dropped onto the window opens like "Open with SYNOID"
+            if let Some(path) = ctx.input(|i| i.raw.dropped_files.iter().find_map(|f| f.path.clone())) {
+                state.input_path = path.to_string_lossy().to_string();
+                state.pending_command = Some(ActiveCommand::Editor);
+            }
+
+            if let Some(cmd) = state.pending_command.take() {
+                self.active_command = cmd;
+            }
 
             // 1. Texture conversion
             if let Some(color_image) = state.preview_image.take() {
-                self.preview_texture =
-                    Some(ctx.load_texture("preview_frame", color_image, Default::default()));
+                show_frame(&mut self.preview_texture, ctx, "preview_frame", color_image);
             }
 
             // 2. Auto-preview and auto-suggest when path changes
@@ -3070,11 +4125,12 @@ impl eframe::App for SynoidApp {
                     if let Ok(duration) =
                         crate::agent::source_tools::get_video_duration(&path).await
                     {
-                        if let Ok(mut s) = ui_ptr.lock() {
+                        {
+                            let mut s = lock_ui(&ui_ptr);
                             s.video_duration = duration;
                             s.video_position = 0.0;
-                            ctx_clone.request_repaint();
                         }
+                        ctx_clone.request_repaint();
                     }
 
                     // 2. Preview Frame
@@ -3092,10 +4148,8 @@ impl eframe::App for SynoidApp {
                                             buffer.as_raw(),
                                         );
 
-                                        if let Ok(mut s) = ui_ptr.lock() {
-                                            s.preview_image = Some(color_img);
-                                            ctx_clone.request_repaint();
-                                        }
+                                        lock_ui(&ui_ptr).preview_image = Some(color_img);
+                                        ctx_clone.request_repaint();
                                     }
                                     Err(e) => tracing::error!(
                                         "[GUI] Failed to decode preview frame: {}",
@@ -3112,36 +4166,80 @@ impl eframe::App for SynoidApp {
             // 3. Video player frame update
             let mut new_texture_pixels: Option<(Vec<u8>, [usize; 2])> = None;
             let mut new_position: Option<f64> = None;
-            let mut player_is_playing = false;
+            let mut playing_fps: Option<f64> = None;
 
             // Snapshot immutable fields before mutably borrowing video_player
-            let cur_pos = state.video_position;
             let max_dur = state.video_duration;
+            // The timeline's loop, handed over once the selection is made
+            let wanted_loop = state
+                .loop_range
+                .and_then(|(a, b)| crate::agent::video_player::LoopRange::new(a, b));
+            let selecting_loop = state.loop_anchor.is_some();
 
             if let Some(player) = &mut state.video_player {
+                if !selecting_loop && player.loop_range() != wanted_loop {
+                    match wanted_loop {
+                        Some(range) => player.set_loop_range(range.start, range.end),
+                        None => player.clear_loop_range(),
+                    }
+                }
                 let size = [player.width, player.height];
                 let fps = player.fps;
-                player_is_playing = player.playing;
                 if let Some((is_new, frame)) = player.get_next_frame() {
                     if is_new {
                         new_texture_pixels = Some((frame.clone(), [size[0], size[1]]));
-                        let new_pos = cur_pos + 1.0 / fps;
-                        new_position = Some(new_pos.min(max_dur));
                     }
-                    player_is_playing = player.playing;
+                }
+                // The player's clock, not the repaint count, says where we are
+                if new_texture_pixels.is_some() {
+                    new_position = Some(player.position().min(max_dur));
+                }
+                // A pending seek needs redraws to land even while paused
+                if player.playing || player.is_seeking() {
+                    playing_fps = Some(fps);
                 }
             }
 
             if let Some((pixels, size)) = new_texture_pixels {
                 let color_image = egui::ColorImage::from_rgb([size[0], size[1]], &pixels);
-                // Safety check: Wrap in a block to ensure we don't hold lock if something fails
-                self.preview_texture = Some(ctx.load_texture("video_frame", color_image, Default::default()));
+                show_frame(&mut self.video_texture, ctx, "video_frame", color_image);
             }
             if let Some(pos) = new_position {
                 state.video_position = pos;
             }
-            if player_is_playing {
-                ctx.request_repaint();
+
+            // 4. Before/after split preview frame update
+            let layout = match state.split_wipe {
+                Some(at) => crate::agent::video_player::SplitLayout::Wipe(at),
+                None => crate::agent::video_player::SplitLayout::SideBySide,
+            };
+            if let Some(split) = &mut state.split_player {
+                let width = match layout {
+                    crate::agent::video_player::SplitLayout::SideBySide => split.width() * 2,
+                    crate::agent::video_player::SplitLayout::Wipe(_) => split.width(),
+                };
+                let height = split.height();
+                if let Some((is_new, pixels)) = split.get_next_frame(layout) {
+                    if is_new {
+                        let color_image = egui::ColorImage::from_rgb([width, height], &pixels);
+                        show_frame(&mut self.video_texture, ctx, "video_frame", color_image);
+                    }
+                }
+                if split.is_playing() {
+                    playing_fps = Some(playing_fps.unwrap_or(0.0).max(split.fps()));
+                }
+                let position = split.position;
+                state.split_position = position;
+            }
+
+            // Back to the still preview once playback stops
+            if state.video_player.is_none() && state.split_player.is_none() {
+                self.video_texture = None;
+            }
+
+            // Wake for the next decoded frame rather than spinning
+            if let Some(fps) = playing_fps {
+                ctx.request_repaint_after(frame_interval(fps));
             }
         }
 
@@ -3177,15 +4275,12 @@ impl eframe::App for SynoidApp {
 
                         ui.add_space(8.0);
                         // Hive Mind Status Display
-                        let hive_status = {
-                            let state = self.ui_state.lock().unwrap();
-                            state.hive_mind_status.clone()
-                        };
+                        let hive_status = lock_ui(&self.ui_state).hive_mind_status.clone();
 
                         if !hive_status.is_empty() {
                             ui.group(|ui| {
                                 ui.label(
-                                    egui::RichText::new("🐝 Hive Mind")
+                                    egui::RichText::new(format!("🐝 {}", tr("hive.title")))
                                         .color(COLOR_ACCENT_ORANGE)
                                         .strong(),
                                 );
@@ -3210,17 +4305,18 @@ impl eframe::App for SynoidApp {
                         // Media
                         if let Some(cmd) = self.render_tree_category(
                             ui,
-                            "Media",
+                            tr("nav.media"),
                             "📹",
                             COLOR_ACCENT_ORANGE,
                             &mut media_exp,
                             vec![
-                                ("✂️", "Clip", ActiveCommand::Clip),
-                                ("📦", "Compress", ActiveCommand::Compress),
-                                ("🎵", "Combine", ActiveCommand::Combine),
-                                ("📺", "YouTube", ActiveCommand::Youtube),
-                                ("🎬", "Editor", ActiveCommand::Editor),
-                                ("🔍", "Global Discovery", ActiveCommand::Discovery),
+                                ("✂️", tr("nav.clip"), ActiveCommand::Clip),
+                                ("📦", tr("nav.compress"), ActiveCommand::Compress),
+                                ("🎵", tr("nav.combine"), ActiveCommand::Combine),
+                                ("📺", tr("nav.youtube"), ActiveCommand::Youtube),
+                                ("🎬", tr("nav.editor"), ActiveCommand::Editor),
+                                ("🔍", tr("nav.discovery"), ActiveCommand::Discovery),
+                                ("🕘", tr("nav.history"), ActiveCommand::History),
                             ],
                         ) {
                             new_cmd = Some(cmd);
@@ -3229,18 +4325,18 @@ impl eframe::App for SynoidApp {
                         // AI Core
                         if let Some(cmd) = self.render_tree_category(
                             ui,
-                            "AI Core",
+                            tr("nav.ai_core"),
                             "🧠",
                             COLOR_ACCENT_BLUE,
                             &mut ai_exp,
                             vec![
-                                ("💬", "Brain", ActiveCommand::Brain),
-                                ("🤖", "Embody", ActiveCommand::Embody),
-                                ("🎓", "Learn", ActiveCommand::Learn),
-                                ("💡", "Suggest", ActiveCommand::Suggest),
-                                ("⚡", "Process Pipeline", ActiveCommand::Process),
-                                ("🧬", "AutoImprove", ActiveCommand::AutoImprove),
-                                ("🤖", "Gemma 4", ActiveCommand::Gemma4),
+                                ("💬", tr("nav.brain"), ActiveCommand::Brain),
+                                ("🤖", tr("nav.embody"), ActiveCommand::Embody),
+                                ("🎓", tr("nav.learn"), ActiveCommand::Learn),
+                                ("💡", tr("nav.suggest"), ActiveCommand::Suggest),
+                                ("⚡", tr("nav.process"), ActiveCommand::Process),
+                                ("🧬", tr("nav.auto_improve"), ActiveCommand::AutoImprove),
+                                ("🤖", tr("nav.gemma4"), ActiveCommand::Gemma4),
                             ],
                         ) {
                             new_cmd = Some(cmd);
@@ -3249,13 +4345,14 @@ impl eframe::App for SynoidApp {
                         // Security
                         if let Some(cmd) = self.render_tree_category(
                             ui,
-                            "Security",
+                            tr("nav.security"),
                             "🛡️",
                             COLOR_ACCENT_RED,
                             &mut security_exp,
                             vec![
-                                ("👁️", "Defense", ActiveCommand::Guard),
-                                ("🖥️", "GPU Status", ActiveCommand::GpuStatus),
+                                ("👁️", tr("nav.defense"), ActiveCommand::Guard),
+                                ("🖥️", tr("nav.gpu_status"), ActiveCommand::GpuStatus),
+                                ("🛠️", tr("nav.setup"), ActiveCommand::Setup),
                             ],
                         ) {
                             new_cmd = Some(cmd);
@@ -3263,11 +4360,11 @@ impl eframe::App for SynoidApp {
 
                         if let Some(cmd) = self.render_tree_category(
                             ui,
-                            "Research",
+                            tr("nav.research"),
                             "🔍",
                             COLOR_TEXT_PRIMARY,
                             &mut research_exp,
-                            vec![("📚", "Research", ActiveCommand::Research)],
+                            vec![("📚", tr("nav.research"), ActiveCommand::Research)],
                         ) {
                             new_cmd = Some(cmd);
                         }
@@ -3275,11 +4372,11 @@ impl eframe::App for SynoidApp {
                         // Audio
                         if let Some(cmd) = self.render_tree_category(
                             ui,
-                            "Audio",
+                            tr("nav.audio"),
                             "🔊",
                             COLOR_ACCENT_ORANGE,
                             &mut audio_exp,
-                            vec![("🎚️", "Mixer", ActiveCommand::AudioMixer)],
+                            vec![("🎚️", tr("nav.mixer"), ActiveCommand::AudioMixer)],
                         ) {
                             new_cmd = Some(cmd);
                         }
@@ -3328,9 +4425,8 @@ impl eframe::App for SynoidApp {
             });
 
         if self.active_command == ActiveCommand::Editor {
-            let ui_state_arc = self.ui_state.clone();
-            let mut state = ui_state_arc.lock().unwrap();
-            self.render_editor_layout(ctx, &mut state);
+            let ui_state = self.ui_state.clone();
+            self.render_editor_layout(ctx, &mut lock_ui(&ui_state));
         } else {
             // Main Content Area
             egui::CentralPanel::default()
@@ -3354,8 +4450,7 @@ impl eframe::App for SynoidApp {
                         ui.allocate_new_ui(
                             egui::UiBuilder::new().max_rect(panel_rect.shrink(20.0)),
                             |ui| {
-                                let mut state = self.ui_state.lock().unwrap();
-                                self.render_command_panel(ui, &mut state);
+                                self.render_command_panel(ui, &mut lock_ui(&self.ui_state));
                             },
                         );
 
@@ -3369,7 +4464,7 @@ impl eframe::App for SynoidApp {
                         );
                         ui.add_space(8.0);
 
-                        let logs = self.core.get_logs();
+                        let logs = &self.logs;
                         let logs_rect = egui::Rect::from_min_size(
                             ui.cursor().min,
                             egui::vec2(ui.available_width(), 200.0),
@@ -3384,7 +4479,7 @@ impl eframe::App for SynoidApp {
                                     .id_salt("log_scroll")
                                     .stick_to_bottom(true)
                                     .show(ui, |ui| {
-                                        for log in &logs {
+                                        for log in logs {
                                             ui.add(egui::Label::new(
                                                 egui::RichText::new(log)
                                                     .monospace()
@@ -3411,26 +4506,59 @@ impl eframe::App for SynoidApp {
                 egui::ScrollArea::vertical()
                     .id_salt("preview_panel_scroll")
                     .show(ui, |ui| {
-                        let mut state = self.ui_state.lock().unwrap();
-                        self.render_preview_panel(ui, &mut state);
+                        self.render_preview_panel(ui, &mut lock_ui(&self.ui_state));
                     });
                 });
         }
 
-        // Only request repaint if a video is playing or an AI job is running
-        let repainting = {
-            let state = self.ui_state.lock().unwrap();
-            let video_playing = state.video_player.as_ref().map_or(false, |p| p.playing);
-            video_playing || state.ai_edit_running || state.is_scanning || state.is_transcribing
-        };
+        self.render_toast(ctx);
+        self.transcription_notice(ctx);
 
-        if repainting {
-            ctx.request_repaint();
+        // Jobs that only flip a UI flag when done get a slow tick; playback
+        // and core log/status changes schedule their own repaints.
+        let busy = {
+            let state = lock_ui(&self.ui_state);
+            state.ai_edit_running || state.is_scanning || state.is_transcribing
+        } || crate::agent::ollama_pull::pull_status().is_some();
+
+        if busy {
+            ctx.request_repaint_after(BUSY_REPAINT_INTERVAL);
         }
     }
 }
 
-pub fn run_gui(core: Arc<AgentCore>) -> Result<(), eframe::Error> {
+/// Lock the shared UI state. A panic while it was held only poisons the
+/// lock; the state is plain data, so carry on with it, as `AgentCore` does
+/// with its own locks, rather than losing every later update. Background
+/// tasks copy what they need out, never hold the guard across an `.await`,
+/// and wake or repaint the GUI only after dropping it.
+pub(crate) fn lock_ui(state: &Mutex<UiState>) -> MutexGuard<'_, UiState> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Add post-render outcomes to the toast, if it still shows `output`.
+fn show_render_notes(ui_state: &Mutex<UiState>, output: &std::path::Path, outcomes: &[post_render::ActionOutcome]) {
+    let mut state = lock_ui(ui_state);
+    if let Some(toast) = state.render_toast.as_mut().filter(|t| t.output == output) {
+        toast.notes.extend(outcomes.iter().map(|o| o.describe()));
+    }
+}
+
+/// Load `path` as the input and switch to the Editor, as "Open with SYNOID" does.
+fn open_in_editor(ui_state: &Mutex<UiState>, path: &std::path::Path) {
+    tracing::info!("[GUI] Opening {:?} in the Editor", path);
+    let mut state = lock_ui(ui_state);
+    state.input_path = path.to_string_lossy().to_string();
+    state.pending_command = Some(ActiveCommand::Editor);
+}
+
+/// `open` is the video the binary was launched with; `lock` receives the
+/// paths later launches forward to this window.
+pub fn run_gui(
+    core: Arc<AgentCore>,
+    open: Option<PathBuf>,
+    lock: Option<&crate::open_with::InstanceLock>,
+) -> Result<(), eframe::Error> {
     // WSLg's Wayland compositor silently fails to forward eframe/winit windows
     // to the Windows desktop. Force X11 (via XWayland) which reliably works.
     if is_wsl() {
@@ -3464,6 +4592,108 @@ pub fn run_gui(core: Arc<AgentCore>) -> Result<(), eframe::Error> {
     eframe::run_native(
         "SYNOID Command Center",
         options,
-        Box::new(|_cc| Ok(Box::new(SynoidApp::new(core)))),
+        Box::new(move |cc| {
+            core.attach_gui(cc.egui_ctx.clone());
+            let app = SynoidApp::new(core);
+            if let Some(path) = open {
+                open_in_editor(&app.ui_state, &path);
+            }
+            if let Some(lock) = lock {
+                let ui_state = app.ui_state.clone();
+                let ctx = cc.egui_ctx.clone();
+                let served = lock.serve(move |path| {
+                    if let Some(path) = path {
+                        open_in_editor(&ui_state, &path);
+                    }
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                    ctx.request_repaint();
+                });
+                if let Err(e) = served {
+                    tracing::warn!("[GUI] Other launches can't forward files here: {}", e);
+                }
+            }
+            Ok(Box::new(app))
+        }),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn frame_updates_reuse_one_texture() {
+        let ctx = egui::Context::default();
+        let allocated = || ctx.tex_manager().read().num_allocated();
+        let baseline = allocated();
+
+        let mut texture = None;
+        for i in 0..100u8 {
+            // Varying sizes, as when the input switches between videos
+            let size = [16 + i as usize % 3, 9];
+            let image = egui::ColorImage::new(size, egui::Color32::from_gray(i));
+            show_frame(&mut texture, &ctx, "video_frame", image);
+            assert_eq!(allocated(), baseline + 1);
+        }
+        assert_eq!(texture.as_ref().unwrap().size(), [16, 9]);
+
+        // Stopping playback gives the texture back
+        drop(texture);
+        assert_eq!(allocated(), baseline);
+    }
+
+    #[test]
+    fn concurrent_state_access_survives_a_poisoned_lock() {
+        let state = Arc::new(Mutex::new(UiState::default()));
+
+        // A task that panics mid-update poisons the lock
+        let poisoner = state.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = lock_ui(&poisoner);
+            panic!("background task failed while holding the UI state");
+        })
+        .join();
+        assert!(state.is_poisoned());
+
+        // Pollers and the render loop keep taking turns without deadlocking
+        let (done, finished) = std::sync::mpsc::channel();
+        for worker in 0..8 {
+            let state = state.clone();
+            let done = done.clone();
+            std::thread::spawn(move || {
+                for i in 0..500 {
+                    let mut s = lock_ui(&state);
+                    s.editor_api_status = format!("worker {} update {}", worker, i);
+                    s.recent_jobs.clear();
+                }
+                done.send(()).unwrap();
+            });
+        }
+        for _ in 0..8 {
+            finished
+                .recv_timeout(Duration::from_secs(10))
+                .expect("UI state access deadlocked");
+        }
+        assert!(lock_ui(&state).editor_api_status.ends_with("update 499"));
+    }
+
+    #[test]
+    fn the_clip_panel_explains_bad_times_inline() {
+        let mut state = UiState {
+            clip_start: "1:23:45".to_string(),
+            clip_duration: "5:10".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(clip_span(&state), Ok((5025.0, 310.0)));
+
+        state.clip_end = "1:30:00".to_string();
+        assert!(clip_span(&state).unwrap_err().contains("not both"));
+
+        state.clip_duration.clear();
+        assert_eq!(clip_span(&state), Ok((5025.0, 375.0)));
+
+        state.clip_start = "1:90".to_string();
+        assert!(clip_span(&state).unwrap_err().starts_with("Start:"));
+    }
+}
@@ -0,0 +1,959 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use serde_json::json;
+use std::sync::Arc;
+use synoid_core::agent::core::AgentCore;
+use synoid_core::editor_api::EditorSettings;
+use synoid_core::server;
+use synoid_core::state::KernelState;
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+const BOUNDARY: &str = "synoid-test-boundary";
+
+/// The app with its editor sessions in a fresh temp dir, which goes when
+/// the returned guard drops, failed asserts included.
+fn app() -> (Router, TempDir) {
+    app_with_upload_cap(EditorSettings::from_config().max_upload_bytes)
+}
+
+fn app_with_upload_cap(max_upload_bytes: u64) -> (Router, TempDir) {
+    let root = tempfile::tempdir().unwrap();
+    let core = Arc::new(AgentCore::new("http://localhost:11434/v1", "test_instance"));
+    let settings = EditorSettings {
+        sessions_root: root.path().to_path_buf(),
+        max_upload_bytes,
+    };
+    (server::create_router_with(Arc::new(KernelState::new(core)), settings), root)
+}
+
+/// Half a second of 8 kHz mono silence as a WAV file.
+fn tiny_wav() -> Vec<u8> {
+    silent_wav(4000)
+}
+
+/// `samples` of 8 kHz mono 16-bit silence as a WAV file.
+fn silent_wav(samples: u32) -> Vec<u8> {
+    let data_len = samples * 2;
+    let mut wav = Vec::new();
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&8000u32.to_le_bytes());
+    wav.extend_from_slice(&16000u32.to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.resize(wav.len() + data_len as usize, 0);
+    wav
+}
+
+async fn create_session(app: &Router) -> String {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/editor/sessions")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    v["id"].as_str().unwrap().to_string()
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: String,
+    body: Vec<u8>,
+) -> (StatusCode, serde_json::Value) {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null),
+    )
+}
+
+async fn upload(
+    app: &Router,
+    session: &str,
+    filename: &str,
+    data: &[u8],
+) -> (StatusCode, serde_json::Value) {
+    let mut body = format!(
+        "--{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{f}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+        b = BOUNDARY,
+        f = filename
+    )
+    .into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/api/editor/sessions/{}/assets", session))
+                .header(
+                    "content-type",
+                    format!("multipart/form-data; boundary={}", BOUNDARY),
+                )
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null),
+    )
+}
+
+#[tokio::test]
+async fn test_upload_rejects_non_media_and_accepts_media() {
+    let (app, _root) = app_with_upload_cap(1024 * 1024);
+    let session = create_session(&app).await;
+
+    // A text file renamed to .mp4 must not get through on its extension
+    let (spoofed, _) = upload(&app, &session, "clip.mp4", b"definitely not a video\n").await;
+    assert_eq!(spoofed, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let (oversized, _) = upload(&app, &session, "big.wav", &vec![0u8; 2 * 1024 * 1024]).await;
+    assert_eq!(oversized, StatusCode::PAYLOAD_TOO_LARGE);
+
+    let (valid, _) = upload(&app, &session, "tone.wav", &tiny_wav()).await;
+    assert_eq!(valid, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_only_upload_routes_lift_the_request_body_cap() {
+    let (app, _root) = app();
+    let session = create_session(&app).await;
+    let base = format!("/api/editor/sessions/{}", session);
+    let big = vec![b' '; 11 * 1024 * 1024];
+
+    let (status, _) = send(&app, "POST", format!("{}/project/save", base), big.clone()).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+
+    let (status, init) = send(
+        &app,
+        "POST",
+        format!("{}/assets/init", base),
+        br#"{"filename": "big.wav"}"#.to_vec(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let upload_id = init["uploadId"].as_str().unwrap();
+    let (status, progress) = send(&app, "PUT", format!("{}/assets/{}/chunk/0", base, upload_id), big).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(progress["receivedBytes"], 11 * 1024 * 1024);
+}
+
+#[tokio::test]
+async fn test_chunked_upload_assembles_three_chunks() {
+    let (app, root) = app();
+    let session = create_session(&app).await;
+    let wav = tiny_wav();
+    let base = format!("/api/editor/sessions/{}/assets", session);
+
+    let (status, init) = send(
+        &app,
+        "POST",
+        format!("{}/init", base),
+        br#"{"filename": "tone.wav"}"#.to_vec(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let upload_id = init["uploadId"].as_str().unwrap().to_string();
+
+    let third = wav.len() / 3;
+    let chunks = [&wav[..third], &wav[third..2 * third], &wav[2 * third..]];
+
+    // Skipping ahead is refused and reports where to resume
+    let (status, progress) = send(
+        &app,
+        "PUT",
+        format!("{}/{}/chunk/1", base, upload_id),
+        chunks[1].to_vec(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(progress["nextChunk"], 0);
+
+    for (n, chunk) in chunks.iter().enumerate() {
+        let (status, progress) = send(
+            &app,
+            "PUT",
+            format!("{}/{}/chunk/{}", base, upload_id, n),
+            chunk.to_vec(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(progress["nextChunk"], n as u64 + 1);
+    }
+
+    // A retried chunk is a no-op
+    let (status, progress) = send(
+        &app,
+        "PUT",
+        format!("{}/{}/chunk/1", base, upload_id),
+        chunks[1].to_vec(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(progress["receivedBytes"], wav.len() as u64);
+
+    let (status, asset) = send(
+        &app,
+        "POST",
+        format!("{}/{}/complete", base, upload_id),
+        Vec::new(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(asset["size"], wav.len() as u64);
+    assert_eq!(asset["type"], "audio");
+
+    let session_dir = root.path().join(&session);
+    let on_disk = session_dir
+        .join("assets")
+        .join(format!("{}_tone.wav", upload_id));
+    assert_eq!(std::fs::metadata(&on_disk).unwrap().len(), wav.len() as u64);
+}
+
+#[tokio::test]
+async fn test_abandoned_chunked_uploads_are_swept() {
+    use std::time::{Duration, Instant};
+    use synoid_core::editor_api::{sweep_stale_uploads, EditorStore, PendingUpload};
+
+    let dir = std::env::temp_dir().join(format!("synoid_upload_sweep_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let ttl = Duration::from_secs(60);
+    let Some(long_ago) = Instant::now().checked_sub(ttl * 2) else {
+        return;
+    };
+    let pending = |name: &str, writing: bool| {
+        let temp_path = dir.join(format!("{}.part", name));
+        std::fs::write(&temp_path, b"partial").unwrap();
+        PendingUpload {
+            session_id: "s".to_string(),
+            filename: format!("{}.wav", name),
+            temp_path,
+            next_chunk: 1,
+            received_bytes: 7,
+            writing,
+            last_activity: long_ago,
+        }
+    };
+
+    let mut store = EditorStore::default();
+    store.uploads.insert("idle".to_string(), pending("idle", false));
+    store.uploads.insert("writing".to_string(), pending("writing", true));
+    let mut fresh = pending("fresh", false);
+    fresh.last_activity = Instant::now();
+    store.uploads.insert("fresh".to_string(), fresh);
+    let store = Arc::new(tokio::sync::RwLock::new(store));
+
+    assert!(sweep_stale_uploads(&store, &dir, ttl).await >= 1);
+    let store = store.read().await;
+    assert!(!store.uploads.contains_key("idle"));
+    assert!(!dir.join("idle.part").exists());
+    // A chunk mid-append and a recently active upload are left alone
+    assert!(store.uploads.contains_key("writing") && dir.join("writing.part").exists());
+    assert!(store.uploads.contains_key("fresh") && dir.join("fresh.part").exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn test_identical_uploads_share_one_asset() {
+    let (app, root) = app();
+    let session = create_session(&app).await;
+    let wav = tiny_wav();
+
+    let (status, first) = upload(&app, &session, "take1.wav", &wav).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, second) = upload(&app, &session, "take1_copy.wav", &wav).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(first["id"], second["id"]);
+    assert_eq!(first["contentHash"], second["contentHash"]);
+
+    let session_dir = root.path().join(&session);
+    let stored = std::fs::read_dir(session_dir.join("assets"))
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| !e.file_name().to_string_lossy().ends_with("_thumb.jpg"))
+        .count();
+    assert_eq!(stored, 1);
+}
+
+#[tokio::test]
+async fn test_trim_action_creates_new_asset() {
+    let (app, _root) = app();
+    let session = create_session(&app).await;
+    let (status, source) = upload(&app, &session, "tone.wav", &tiny_wav()).await;
+    assert_eq!(status, StatusCode::OK);
+    let action_uri = format!("/api/editor/sessions/{}/ai/action", session);
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        action_uri.clone(),
+        br#"{"type": "trim", "params": {"start": 0.3, "end": 0.1}}"#.to_vec(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let body = json!({
+        "type": "trim",
+        "params": { "assetId": source["id"], "start": 0.1, "end": 0.3 },
+    });
+    let (status, trimmed) = send(&app, "POST", action_uri, body.to_string().into_bytes()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(trimmed["type"], "audio");
+    assert_eq!(trimmed["filename"], "tone_trim.m4a");
+    assert_ne!(trimmed["id"], source["id"]);
+
+    let (_, assets) = send(
+        &app,
+        "GET",
+        format!("/api/editor/sessions/{}/assets", session),
+        Vec::new(),
+    )
+    .await;
+    assert_eq!(assets.as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_structured_smart_edit_skips_intent_parsing() {
+    let (app, _root) = app();
+    let session = create_session(&app).await;
+    let (status, source) = upload(&app, &session, "tone.wav", &tiny_wav()).await;
+    assert_eq!(status, StatusCode::OK);
+    let uri = format!("/api/editor/sessions/{}/ai/smart-edit", session);
+
+    let mut body = json!({
+        "assetId": source["id"],
+        "remove_boring": true,
+        "keep_action": false,
+        "remove_silence": true,
+        "keep_speech": true,
+        "ruthless": true,
+        "density": "Highlights",
+        "keywords": [{"term": "goal", "weight": 2.0, "mode": "Require"}],
+        "target_duration": [30.0, 60.0],
+        "edge_padding_secs": 0.05,
+    });
+    let (status, started) = send(&app, "POST", uri.clone(), body.to_string().into_bytes()).await;
+    assert_eq!(status, StatusCode::OK);
+    // The intent handed to the editor is the request, not a re-parse of it
+    let intent = &started["intent"];
+    assert_eq!(intent["density"], "Highlights");
+    assert_eq!(intent["target_duration"], json!([30.0, 60.0]));
+    assert_eq!(intent["keywords"][0]["mode"], "Require");
+    assert_eq!(intent["edge_padding_secs"], 0.05);
+    assert_eq!(intent["keep_action"], false);
+
+    body["density"] = json!("Full");
+    let (status, error) = send(&app, "POST", uri.clone(), body.to_string().into_bytes()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["error"].as_str().unwrap().contains("ruthless"));
+
+    body["density"] = json!("Balanced");
+    body["target_duration"] = json!([90.0, 60.0]);
+    let (status, _) = send(&app, "POST", uri.clone(), body.to_string().into_bytes()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = send(&app, "POST", uri, br#"{"density": "Balanced"}"#.to_vec()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_unavailable_model_override_is_rejected_up_front() {
+    let (app, _root) = app();
+    let session = create_session(&app).await;
+    let (status, source) = upload(&app, &session, "tone.wav", &tiny_wav()).await;
+    assert_eq!(status, StatusCode::OK);
+    let model = "no-such-model:404b";
+
+    let body = json!({"message": "what is in this clip?", "model": model});
+    let uri = format!("/api/editor/sessions/{}/ai/chat", session);
+    let (status, error) = send(&app, "POST", uri, body.to_string().into_bytes()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["error"].as_str().unwrap().contains(model), "{}", error);
+
+    let body = json!({"intent": "remove boring", "assetId": source["id"], "model": model});
+    let uri = format!("/api/editor/sessions/{}/ai/auto-edit", session);
+    let (status, error) = send(&app, "POST", uri, body.to_string().into_bytes()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["error"].as_str().unwrap().contains(model), "{}", error);
+    // Rejected before a job was started
+    let (_, job) = send(
+        &app,
+        "GET",
+        format!("/api/editor/sessions/{}/render/status", session),
+        Vec::new(),
+    )
+    .await;
+    assert_ne!(job["status"], "running");
+}
+
+#[tokio::test]
+async fn test_subtitle_export_checks_format_and_asset_before_transcribing() {
+    let (app, _root) = app();
+    let session = create_session(&app).await;
+    let (status, source) = upload(&app, &session, "tone.wav", &tiny_wav()).await;
+    assert_eq!(status, StatusCode::OK);
+    let id = source["id"].as_str().unwrap();
+
+    let uri = format!("/api/editor/sessions/{}/assets/{}/subtitles?format=ass", session, id);
+    let (status, error) = send(&app, "POST", uri, Vec::new()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["error"].as_str().unwrap().contains("srt or vtt"));
+
+    let uri = format!("/api/editor/sessions/{}/assets/missing/subtitles?format=vtt", session);
+    let (status, _) = send(&app, "POST", uri, Vec::new()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_imported_subtitles_are_repaired_and_editable() {
+    let (app, _root) = app();
+    let session = create_session(&app).await;
+    let srt = "\u{feff}1\r\n00:00:00,500 --> 00:00:02,500\r\n<i>First</i> line\r\n\r\n\
+               2\r\n00:00:02,000 --> 00:00:03,000\r\nSecond line\r\n";
+    let (status, asset) = upload(&app, &session, "captions.srt", srt.as_bytes()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(asset["type"], "subtitle");
+    assert_eq!(asset["cues"], 2);
+    let fixed: Vec<u64> = asset["fixes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| f["cue"].as_u64().unwrap())
+        .collect();
+    assert_eq!(fixed, vec![1, 1]);
+    let id = asset["id"].as_str().unwrap();
+
+    let cue_uri = |n: u32| format!("/api/editor/sessions/{}/assets/{}/cues/{}", session, id, n);
+    let (status, cue) = send(&app, "PATCH", cue_uri(2), br#"{"text": "Edited", "end": 4.0}"#.to_vec()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!((cue["text"].as_str(), cue["end"].as_f64()), (Some("Edited"), Some(4.0)));
+    let (status, _) = send(&app, "PATCH", cue_uri(2), br#"{"start": 1.0}"#.to_vec()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = send(&app, "PATCH", cue_uri(9), br#"{"text": "x"}"#.to_vec()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, listed) = send(
+        &app,
+        "GET",
+        format!("/api/editor/sessions/{}/assets/{}/cues", session, id),
+        Vec::new(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(listed["cues"][0]["text"], "First line");
+    assert_eq!(listed["cues"][0]["end"], 2.0);
+    assert_eq!(listed["cues"][1]["text"], "Edited");
+
+    let render = json!({"assetId": id, "subtitles": {"assetId": id, "mode": "karaoke"}});
+    let (status, error) = send(
+        &app,
+        "POST",
+        format!("/api/editor/sessions/{}/render", session),
+        render.to_string().into_bytes(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["error"].as_str().unwrap().contains("burn or mux"));
+}
+
+#[tokio::test]
+async fn test_project_saves_are_kept_as_restorable_snapshots() {
+    let (app, _root) = app();
+    let session = create_session(&app).await;
+    let project = |path: &str| format!("/api/editor/sessions/{}/project/{}", session, path);
+
+    let mut saved = Vec::new();
+    for version in 1..=3 {
+        let body = json!({ "version": version }).to_string().into_bytes();
+        let (status, reply) = send(&app, "POST", project("save"), body).await;
+        assert_eq!(status, StatusCode::OK);
+        saved.push(reply["snapshot"].as_str().unwrap().to_string());
+    }
+
+    let (status, history) = send(&app, "GET", project("history"), Vec::new()).await;
+    assert_eq!(status, StatusCode::OK);
+    let listed: Vec<&str> = history["snapshots"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(listed, vec![&saved[2], &saved[1], &saved[0]]);
+
+    let (status, restored) =
+        send(&app, "GET", project(&format!("load?snapshot={}", saved[1])), Vec::new()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(restored, json!({ "version": 2 }));
+    let (_, latest) = send(&app, "GET", project("load"), Vec::new()).await;
+    assert_eq!(latest, json!({ "version": 3 }));
+    let (status, _) = send(&app, "GET", project("load?snapshot=..%2Fproject"), Vec::new()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+async fn post_raw(app: &Router, uri: String, body: Vec<u8>) -> (StatusCode, Vec<u8>) {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", "application/zip")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, bytes.to_vec())
+}
+
+#[tokio::test]
+async fn test_session_archives_import_into_a_new_session() {
+    use synoid_core::agent::archive;
+
+    let (app, _root) = app();
+    let session = create_session(&app).await;
+    let srt = "1\n00:00:00,000 --> 00:00:02,000\nHello\n\n2\n00:00:02,000 --> 00:00:03,000\nWorld\n";
+    let (status, asset) = upload(&app, &session, "captions.srt", srt.as_bytes()).await;
+    assert_eq!(status, StatusCode::OK);
+    let asset_id = asset["id"].as_str().unwrap().to_string();
+    let (status, _) = send(
+        &app,
+        "PATCH",
+        format!("/api/editor/sessions/{}/assets/{}/cues/2", session, asset_id),
+        br#"{"text": "Edited"}"#.to_vec(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let project = json!({ "clips": [{ "assetId": asset_id, "src": asset["streamUrl"] }] });
+    let (status, _) = send(
+        &app,
+        "POST",
+        format!("/api/editor/sessions/{}/project/save", session),
+        project.to_string().into_bytes(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, zip) = post_raw(&app, format!("/api/editor/sessions/{}/archive", session), Vec::new()).await;
+    assert_eq!(status, StatusCode::OK);
+    let reader = archive::ZipArchive::new(std::io::Cursor::new(zip.clone())).unwrap();
+    let names = archive::entry_names(&reader);
+    assert!(names.iter().any(|n| n == "session.json"), "{:?}", names);
+    assert!(names.iter().any(|n| n == "project.json"), "{:?}", names);
+    assert!(names.contains(&format!("assets/{}_captions.srt", asset_id)), "{:?}", names);
+
+    let (status, imported) = post_raw(&app, "/api/editor/sessions/import".to_string(), zip).await;
+    assert_eq!(status, StatusCode::OK, "{}", String::from_utf8_lossy(&imported));
+    let imported: serde_json::Value = serde_json::from_slice(&imported).unwrap();
+    let copy = imported["id"].as_str().unwrap().to_string();
+    assert_ne!(copy, session);
+    assert_eq!(imported["importedFrom"], session.as_str());
+    assert_eq!(imported["missingAssets"], json!([]));
+    let new_id = imported["assets"][0]["id"].as_str().unwrap().to_string();
+    assert_ne!(new_id, asset_id);
+
+    // Edited cues, the project (pointing at the new ids) and the file are back
+    let (status, cues) = send(
+        &app,
+        "GET",
+        format!("/api/editor/sessions/{}/assets/{}/cues", copy, new_id),
+        Vec::new(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cues["cues"][1]["text"], "Edited");
+    let (_, restored) = send(&app, "GET", format!("/api/editor/sessions/{}/project/load", copy), Vec::new()).await;
+    assert_eq!(restored["clips"][0]["assetId"], new_id.as_str());
+    assert_eq!(restored["clips"][0]["src"], imported["assets"][0]["streamUrl"]);
+    let stream_url = imported["assets"][0]["streamUrl"].as_str().unwrap().to_string();
+    let (status, _) = send(&app, "GET", stream_url, Vec::new()).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Without sources the asset is reported missing instead
+    let (status, slim) = post_raw(
+        &app,
+        format!("/api/editor/sessions/{}/archive?includeSources=false", session),
+        Vec::new(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, imported_slim) = post_raw(&app, "/api/editor/sessions/import".to_string(), slim).await;
+    assert_eq!(status, StatusCode::OK);
+    let imported_slim: serde_json::Value = serde_json::from_slice(&imported_slim).unwrap();
+    assert_eq!(imported_slim["assets"], json!([]));
+    assert_eq!(imported_slim["missingAssets"][0]["id"], asset_id.as_str());
+
+    let (status, _) = post_raw(&app, "/api/editor/sessions/import".to_string(), b"not a zip".to_vec()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_imported_assets_are_probed_and_hashed_again() {
+    use sha2::{Digest, Sha256};
+    use synoid_core::agent::archive;
+
+    let wav = silent_wav(8000);
+    let claimed = |id: &str, file: &str| {
+        json!({
+            "id": id, "filename": file, "type": "video", "duration": 99.0,
+            "width": 1920, "height": 1080, "size": 1, "fps": 30.0,
+            "contentHash": "not-a-hash", "file": format!("assets/{}", file), "thumbnail": null,
+        })
+    };
+    let manifest = json!({
+        "format": "synoid-session", "version": 1, "sessionId": "old-session",
+        "archivedAt": "2026-01-01T00:00:00Z", "projectRevision": 0, "includesSources": true,
+        "assets": [claimed("a", "tone.wav"), claimed("b", "copy.wav"), claimed("c", "clip.mp4")],
+        "output": null,
+    });
+    let mut zip = archive::ZipWriter::new_stream(Vec::new());
+    archive::add_bytes(&mut zip, "session.json", manifest.to_string().as_bytes()).unwrap();
+    archive::add_bytes(&mut zip, "assets/tone.wav", &wav).unwrap();
+    archive::add_bytes(&mut zip, "assets/copy.wav", &wav).unwrap();
+    archive::add_bytes(&mut zip, "assets/clip.mp4", b"not a video").unwrap();
+    let zip = zip.finish().unwrap().into_inner();
+
+    let (app, _root) = app();
+    let (status, imported) = post_raw(&app, "/api/editor/sessions/import".to_string(), zip).await;
+    assert_eq!(status, StatusCode::OK, "{}", String::from_utf8_lossy(&imported));
+    let imported: serde_json::Value = serde_json::from_slice(&imported).unwrap();
+
+    // The two identical files become one asset described by its contents
+    let assets = imported["assets"].as_array().unwrap();
+    assert_eq!(assets.len(), 1, "{:?}", assets);
+    assert_eq!(assets[0]["type"], "audio");
+    assert_eq!(assets[0]["size"], wav.len() as u64);
+    assert_eq!(assets[0]["contentHash"], format!("{:x}", Sha256::digest(&wav)));
+    // What ffprobe cannot read is refused like an upload would be
+    assert_eq!(imported["missingAssets"][0]["id"], "c");
+}
+
+#[tokio::test]
+async fn test_archives_are_checked_before_anything_is_unpacked() {
+    use synoid_core::agent::archive;
+
+    let asset = |id: &str, file: &str| {
+        json!({
+            "id": id, "filename": "tone.wav", "type": "audio", "duration": 1.0,
+            "width": 0, "height": 0, "size": 16044, "fps": 0.0,
+            "contentHash": "", "file": file, "thumbnail": null,
+        })
+    };
+    let archive_of = |assets: Vec<serde_json::Value>, padding: usize| {
+        let manifest = json!({
+            "format": "synoid-session", "version": 1, "sessionId": "old-session",
+            "archivedAt": "2026-01-01T00:00:00Z", "projectRevision": 0, "includesSources": true,
+            "assets": assets, "output": null,
+        });
+        let mut zip = archive::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        archive::add_bytes(&mut zip, "session.json", manifest.to_string().as_bytes()).unwrap();
+        archive::add_bytes(&mut zip, "assets/tone.wav", &silent_wav(8000)).unwrap();
+        let deflated = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        zip.start_file("output/padding.bin", deflated).unwrap();
+        std::io::Write::write_all(&mut zip, &vec![0u8; padding]).unwrap();
+        zip.finish().unwrap().into_inner()
+    };
+
+    // Two assets claiming one file would share it on disk
+    let (app, _root) = app();
+    let shared = archive_of(vec![asset("a", "assets/tone.wav"), asset("b", "assets/tone.wav")], 0);
+    let (status, body) = post_raw(&app, "/api/editor/sessions/import".to_string(), shared).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(String::from_utf8_lossy(&body).contains("more than one asset"));
+
+    // A small archive inflating past four times the upload cap is refused
+    let (app, root) = app_with_upload_cap(1024 * 1024);
+    let bomb = archive_of(vec![asset("a", "assets/tone.wav")], 4 * 1024 * 1024);
+    assert!(bomb.len() < 1024 * 1024);
+    let (status, body) = post_raw(&app, "/api/editor/sessions/import".to_string(), bomb).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(String::from_utf8_lossy(&body).contains("unpacks to more than 4 MB"));
+    let left = std::fs::read_dir(root.path()).map(|d| d.count()).unwrap_or(0);
+    assert_eq!(left, 0);
+}
+
+#[tokio::test]
+async fn test_large_assets_stream_in_small_frames() {
+    use axum::body::HttpBody;
+
+    let (app, _root) = app();
+    let session = create_session(&app).await;
+    // Five minutes of audio: a bit over 4.5 MB
+    let wav = silent_wav(8000 * 300);
+    let (status, asset) = upload(&app, &session, "long.wav", &wav).await;
+    assert_eq!(status, StatusCode::OK);
+    let stream_uri = format!(
+        "/api/editor/sessions/{}/assets/{}/stream",
+        session,
+        asset["id"].as_str().unwrap()
+    );
+
+    let fetch = |range: Option<&str>| {
+        let mut request = Request::builder().uri(stream_uri.clone());
+        if let Some(range) = range {
+            request = request.header("range", range);
+        }
+        app.clone().oneshot(request.body(Body::empty()).unwrap())
+    };
+
+    // Frames arrive one read at a time rather than as one whole-file buffer
+    let response = fetch(None).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-length"], wav.len().to_string().as_str());
+    let mut body = response.into_body();
+    assert_eq!(body.size_hint().exact(), None);
+    let (mut frames, mut received, mut largest) = (0, 0, 0);
+    while let Some(frame) = std::future::poll_fn(|cx| std::pin::Pin::new(&mut body).poll_frame(cx)).await {
+        let data = frame.unwrap().into_data().unwrap();
+        frames += 1;
+        received += data.len();
+        largest = largest.max(data.len());
+    }
+    assert_eq!(received, wav.len());
+    assert!(frames > 1 && largest <= 256 * 1024, "{} frames, largest {}", frames, largest);
+
+    let response = fetch(Some("bytes=1000000-1000099")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&bytes[..], &wav[1_000_000..1_000_100]);
+
+    let response = fetch(Some("bytes=99999999-")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+}
+
+#[tokio::test]
+async fn test_asset_stream_answers_conditional_requests_with_304() {
+    let (app, _root) = app();
+    let session = create_session(&app).await;
+    let (status, asset) = upload(&app, &session, "tone.wav", &tiny_wav()).await;
+    assert_eq!(status, StatusCode::OK);
+    let stream_uri = format!(
+        "/api/editor/sessions/{}/assets/{}/stream",
+        session,
+        asset["id"].as_str().unwrap()
+    );
+    let fetch = |header: Option<(&str, String)>| {
+        let mut request = Request::builder().uri(stream_uri.clone());
+        if let Some((name, value)) = header {
+            request = request.header(name, value);
+        }
+        app.clone().oneshot(request.body(Body::empty()).unwrap())
+    };
+
+    let response = fetch(None).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = response.headers()["etag"].to_str().unwrap().to_string();
+    let last_modified = response.headers()["last-modified"].to_str().unwrap().to_string();
+
+    let response = fetch(Some(("if-none-match", etag.clone()))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()["etag"], etag.as_str());
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert!(body.is_empty());
+
+    let response = fetch(Some(("if-none-match", "\"stale\"".to_string()))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = fetch(Some(("if-modified-since", last_modified))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+}
+
+#[tokio::test]
+async fn test_multi_gigabyte_asset_streams_without_buffering() {
+    use axum::body::HttpBody;
+
+    let (app, root) = app();
+    let session = create_session(&app).await;
+    let (status, asset) = upload(&app, &session, "huge.wav", &tiny_wav()).await;
+    assert_eq!(status, StatusCode::OK);
+    let asset_id = asset["id"].as_str().unwrap().to_string();
+
+    // Grow the stored file to 3 GiB without writing it: a sparse file reads
+    // back as zeros, so only a buffering server would need the memory
+    const HUGE: u64 = 3 << 30;
+    let asset_dir = root.path().join(&session).join("assets");
+    let stored = std::fs::read_dir(&asset_dir)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .find(|p| {
+            let name = p.file_name().unwrap().to_string_lossy();
+            name.starts_with(&asset_id) && !name.ends_with("_thumb.jpg")
+        })
+        .unwrap();
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(&stored)
+        .unwrap()
+        .set_len(HUGE)
+        .unwrap();
+
+    let stream_uri = format!("/api/editor/sessions/{}/assets/{}/stream", session, asset_id);
+    let fetch = |range: Option<&str>| {
+        let mut request = Request::builder().uri(stream_uri.clone());
+        if let Some(range) = range {
+            request = request.header("range", range);
+        }
+        app.clone().oneshot(request.body(Body::empty()).unwrap())
+    };
+
+    // The full response starts flowing at once in small frames; read 32 MiB of it
+    let response = fetch(None).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-length"], HUGE.to_string().as_str());
+    let mut body = response.into_body();
+    assert_eq!(body.size_hint().exact(), None);
+    let (mut received, mut largest) = (0usize, 0usize);
+    while received < 32 << 20 {
+        let frame = std::future::poll_fn(|cx| std::pin::Pin::new(&mut body).poll_frame(cx))
+            .await
+            .expect("stream ended early")
+            .unwrap();
+        let data = frame.into_data().unwrap();
+        received += data.len();
+        largest = largest.max(data.len());
+    }
+    assert!(largest <= 256 * 1024, "largest frame {} bytes", largest);
+    drop(body);
+
+    // A range at the very end seeks instead of reading the 3 GiB before it
+    let tail = format!("bytes={}-", HUGE - 100);
+    let response = fetch(Some(&tail)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(
+        response.headers()["content-range"],
+        format!("bytes {}-{}/{}", HUGE - 100, HUGE - 1, HUGE).as_str()
+    );
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(bytes.len(), 100);
+    assert!(bytes.iter().all(|&b| b == 0));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_uploads_renders_and_polls_do_not_deadlock() {
+    let (app, _root) = app();
+
+    let mut tasks = Vec::new();
+    for i in 0..8 {
+        let app = app.clone();
+        tasks.push(tokio::spawn(async move {
+            // A session each, so every render's status can be waited on
+            let session = create_session(&app).await;
+            let base = format!("/api/editor/sessions/{}", session);
+
+            // Whole-file upload, then a render of it
+            let (status, asset) = upload(&app, &session, &format!("tone{}.wav", i), &silent_wav(4000 + i)).await;
+            assert_eq!(status, StatusCode::OK);
+            let render = json!({ "assetId": asset["id"] }).to_string().into_bytes();
+            let (status, _) = send(&app, "POST", format!("{}/render", base), render).await;
+            assert_eq!(status, StatusCode::OK);
+
+            // A chunked upload interleaved with status polls
+            let (status, init) = send(
+                &app,
+                "POST",
+                format!("{}/assets/init", base),
+                json!({ "filename": format!("chunked{}.wav", i) }).to_string().into_bytes(),
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            let upload_id = init["uploadId"].as_str().unwrap().to_string();
+            let wav = silent_wav(8000 + i);
+            for (n, chunk) in wav.chunks(wav.len() / 4 + 1).enumerate() {
+                let (status, _) = send(
+                    &app,
+                    "PUT",
+                    format!("{}/assets/{}/chunk/{}", base, upload_id, n),
+                    chunk.to_vec(),
+                )
+                .await;
+                assert_eq!(status, StatusCode::OK);
+                send(&app, "GET", format!("{}/render/status", base), Vec::new()).await;
+            }
+            let (status, _) = send(
+                &app,
+                "POST",
+                format!("{}/assets/{}/complete", base, upload_id),
+                Vec::new(),
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+
+            for _ in 0..10 {
+                send(&app, "GET", format!("{}/render/status", base), Vec::new()).await;
+                send(&app, "GET", base.clone(), Vec::new()).await;
+            }
+            base
+        }));
+    }
+
+    let all = async {
+        let mut bases = Vec::new();
+        for task in tasks {
+            bases.push(task.await.unwrap());
+        }
+        // Renders outlive the requests that start them; let them finish
+        // before the sessions' folder goes
+        for base in bases {
+            loop {
+                let (_, job) = send(&app, "GET", format!("{}/render/status", base), Vec::new()).await;
+                if job["status"] == "done" || job["status"] == "error" {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            }
+        }
+    };
+    tokio::time::timeout(std::time::Duration::from_secs(120), all)
+        .await
+        .expect("editor handlers deadlocked under concurrent load");
+}
@@ -0,0 +1,124 @@
+#![cfg(unix)]
+
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+use synoid_core::agent::smart_editor::{EditIntent, ExternalScorer, Scene, ScoreBreakdown};
+use synoid_core::agent::transcription::TranscriptSegment;
+
+fn scenes() -> Vec<Scene> {
+    [(0.0, 4.0), (4.0, 8.0)]
+        .iter()
+        .map(|&(start, end)| Scene {
+            start_time: start,
+            end_time: end,
+            duration: end - start,
+            score: 0.5,
+            vision_tags: vec![],
+            breakdown: Some(ScoreBreakdown::new(0.5)),
+            audio_mix: None,
+        })
+        .collect()
+}
+
+fn transcript() -> Vec<TranscriptSegment> {
+    vec![TranscriptSegment {
+        start: 0.0,
+        end: 4.0,
+        text: "this is the part where we actually explain how it all works".to_string(),
+        words: Vec::new(),
+    }]
+}
+
+#[tokio::test]
+async fn example_python_scorer_rescores_scenes() {
+    if Command::new("python3").arg("--version").output().is_err() {
+        eprintln!("python3 not found; skipping");
+        return;
+    }
+    let script = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/external_scorer.py");
+    let scorer = ExternalScorer::Command(format!("python3 '{}'", script.display()));
+    let intent = EditIntent::from_text("keep the talking parts");
+    let transcript = transcript();
+    let mut scenes = scenes();
+
+    scorer
+        .rescore(
+            &mut scenes,
+            &intent,
+            Some(&transcript),
+            Duration::from_secs(30),
+        )
+        .await
+        .unwrap();
+
+    // The spoken scene goes up, the silent one down
+    assert!(scenes[0].score > 0.5, "{}", scenes[0].score);
+    assert!(scenes[1].score < 0.5, "{}", scenes[1].score);
+    let breakdown = scenes[0].breakdown.as_ref().unwrap();
+    assert_eq!(breakdown.score, scenes[0].score);
+    assert!(breakdown.external > 0.0);
+}
+
+#[tokio::test]
+async fn failing_scorers_keep_the_builtin_scores() {
+    let intent = EditIntent::from_text("keep the talking parts");
+    let failures = [
+        ("exit 3", Duration::from_secs(30)),
+        (r#"echo '{"scores": [0.9]}'"#, Duration::from_secs(30)),
+        (r#"echo '{"scores": [0.9, 7]}'"#, Duration::from_secs(30)),
+        ("echo not json", Duration::from_secs(30)),
+        ("sleep 10", Duration::from_millis(200)),
+    ];
+    for (command, timeout) in failures {
+        let mut scenes = scenes();
+        let scorer = ExternalScorer::Command(command.to_string());
+        let started = std::time::Instant::now();
+        let result = scorer.rescore(&mut scenes, &intent, None, timeout).await;
+        assert!(result.is_err(), "{} should fail", command);
+        assert!(
+            started.elapsed() < Duration::from_secs(5),
+            "{} overran",
+            command
+        );
+        assert!(
+            scenes.iter().all(|s| s.score == 0.5),
+            "{} changed scores",
+            command
+        );
+    }
+}
+
+/// Whether `pid` still runs; a zombie awaiting its reaper does not count.
+fn is_running(pid: &str) -> bool {
+    match std::fs::read_to_string(format!("/proc/{}/stat", pid)) {
+        Ok(stat) => !stat.rsplit(')').next().unwrap_or("").trim_start().starts_with('Z'),
+        Err(_) if Path::new("/proc/self").exists() => false,
+        Err(_) => Command::new("kill").args(["-0", pid]).status().is_ok_and(|s| s.success()),
+    }
+}
+
+#[tokio::test]
+async fn timed_out_scorers_are_killed_with_their_children() {
+    let pid_file = std::env::temp_dir().join(format!("synoid_scorer_{}.pid", std::process::id()));
+    let _ = std::fs::remove_file(&pid_file);
+    let scorer = ExternalScorer::Command(format!(
+        "sleep 30 & echo $! > '{}'; wait",
+        pid_file.display()
+    ));
+    let mut scenes = scenes();
+    let intent = EditIntent::from_text("keep the talking parts");
+    let result = scorer
+        .rescore(&mut scenes, &intent, None, Duration::from_millis(500))
+        .await;
+    assert!(result.is_err());
+
+    let pid = std::fs::read_to_string(&pid_file).expect("scorer wrote its child's pid");
+    let pid = pid.trim();
+    let deadline = std::time::Instant::now() + Duration::from_secs(2);
+    while is_running(pid) && std::time::Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    assert!(!is_running(pid), "the scorer's child {} outlived the timeout", pid);
+    let _ = std::fs::remove_file(&pid_file);
+}
@@ -166,3 +166,90 @@ async fn test_burn_subtitles_integration() {
 
     assert!(result.is_ok(), "burn_subtitles failed: {:?}", result.err());
 }
+
+#[tokio::test]
+async fn test_export_stems_integration() {
+    let dir = std::env::temp_dir().join(format!("synoid_stems_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("interview.mkv");
+
+    // Two audio streams: English dialog and an untagged music bed
+    let made = Command::new("ffmpeg")
+        .args(["-y", "-v", "error"])
+        .args(["-f", "lavfi", "-i", "testsrc=duration=2:size=320x240:rate=25"])
+        .args(["-f", "lavfi", "-i", "sine=frequency=440:duration=2"])
+        .args(["-f", "lavfi", "-i", "sine=frequency=220:duration=2"])
+        .args(["-map", "0:v", "-map", "1:a", "-map", "2:a"])
+        .args(["-c:v", "libx264", "-c:a", "aac", "-metadata:s:a:0", "language=eng"])
+        .arg(&input)
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false);
+    if !made {
+        eprintln!("ffmpeg not found; skipping");
+        let _ = std::fs::remove_dir_all(&dir);
+        return;
+    }
+
+    let out_dir = dir.join("stems");
+    let stems = production_tools::export_stems(&input, &out_dir).await.unwrap();
+    assert_eq!(
+        stems,
+        vec![
+            out_dir.join("interview_track1_eng.wav"),
+            out_dir.join("interview_track2.wav"),
+        ]
+    );
+    for stem in &stems {
+        assert!(std::fs::metadata(stem).unwrap().len() > 0, "{:?} is empty", stem);
+    }
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn test_media_analysis_matches_standalone_scene_detection() {
+    use synoid_core::agent::media_analysis::MediaAnalysis;
+    use synoid_core::agent::smart_editor;
+
+    let dir = std::env::temp_dir().join(format!("synoid_analysis_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("three_shots.mp4");
+
+    // Three 2-second solid shots (hard cuts at 2s and 4s) over a tone
+    let made = Command::new("ffmpeg")
+        .args(["-y", "-v", "error"])
+        .args(["-f", "lavfi", "-i", "color=c=red:size=320x240:rate=25:duration=2"])
+        .args(["-f", "lavfi", "-i", "color=c=blue:size=320x240:rate=25:duration=2"])
+        .args(["-f", "lavfi", "-i", "color=c=green:size=320x240:rate=25:duration=2"])
+        .args(["-f", "lavfi", "-i", "sine=frequency=440:duration=6"])
+        .args(["-filter_complex", "[0:v][1:v][2:v]concat=n=3:v=1:a=0[v]"])
+        .args(["-map", "[v]", "-map", "3:a", "-c:v", "libx264", "-c:a", "aac"])
+        .arg(&input)
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false);
+    if !made {
+        eprintln!("ffmpeg not found; skipping");
+        let _ = std::fs::remove_dir_all(&dir);
+        return;
+    }
+
+    let analysis = MediaAnalysis::analyze(&input).await.unwrap();
+    assert!(analysis.has_video() && analysis.has_audio());
+    assert!((analysis.duration - 6.0).abs() < 0.2, "{}", analysis.duration);
+    assert!(analysis.audio.is_some());
+
+    for threshold in [0.25, 0.4] {
+        let standalone = smart_editor::detect_scenes(&input, threshold).await.unwrap();
+        assert_eq!(
+            analysis.scenes(threshold).len(),
+            standalone.len(),
+            "scene count differs at threshold {}",
+            threshold
+        );
+    }
+    assert_eq!(analysis.scenes(0.25).len(), 3);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
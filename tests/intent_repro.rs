@@ -22,12 +22,12 @@ fn test_highlights() {
 }
 
 #[test]
-fn test_negation_fail() {
+fn test_negation() {
+    use synoid_core::agent::audio_classify::AudioClass;
+
     let intent = EditIntent::from_text("remove all talking");
-    assert!(
-        intent.keep_speech,
-        "This is expected to be True currently due to weak parsing"
-    );
+    assert!(!intent.keep_speech, "talking asked to be cut is not kept");
+    assert!(intent.cut_audio.contains(&AudioClass::Speech));
 }
 
 #[test]
@@ -42,6 +42,8 @@ fn test_continuity_synchronization() {
             duration: 2.0,
             score: 0.1,
             vision_tags: vec![],
+            breakdown: None,
+            audio_mix: None,
         }, // Part of sentence
         Scene {
             start_time: 2.0,
@@ -49,6 +51,8 @@ fn test_continuity_synchronization() {
             duration: 2.0,
             score: 0.6,
             vision_tags: vec![],
+            breakdown: None,
+            audio_mix: None,
         }, // Good part of sentence
     ];
 
@@ -0,0 +1,81 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use synoid_core::agent::smart_editor::reedit::{self, OutputStamp};
+
+/// A two-second clip with a tone, standing in for a plain source.
+fn make_source(path: &Path) -> bool {
+    Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-v",
+            "error",
+            "-f",
+            "lavfi",
+            "-i",
+            "testsrc=duration=2:size=320x240:rate=25",
+        ])
+        .args(["-f", "lavfi", "-i", "sine=frequency=440:duration=2"])
+        .args(["-c:v", "libx264", "-c:a", "aac", "-shortest"])
+        .arg(path)
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+#[tokio::test]
+async fn outputs_of_outputs_are_recognised() {
+    let dir = std::env::temp_dir().join(format!("synoid_reedit_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let source = dir.join("raw.mp4");
+    if !make_source(&source) {
+        eprintln!("ffmpeg not found; skipping");
+        let _ = std::fs::remove_dir_all(&dir);
+        return;
+    }
+    let source = std::fs::canonicalize(&source).unwrap();
+
+    // A plain source carries no stamp
+    assert_eq!(reedit::read_stamp(&source).await, None);
+    assert_eq!(reedit::detect(&source, -16.0).await, None);
+
+    // First edit: burned subtitles, normalized audio
+    let first = dir.join("raw_edited.mp4");
+    std::fs::copy(&source, &first).unwrap();
+    let mut stamp = OutputStamp::new(&source, "cut the boring parts", None);
+    stamp.subtitles_burned = true;
+    stamp.loudness_target = Some(-16.0);
+    reedit::write_stamp(&first, &stamp, &dir).await.unwrap();
+    assert_eq!(reedit::read_stamp(&first).await.as_ref(), Some(&stamp));
+
+    // An edit of that edit still points at the first source
+    let second = dir.join("raw_edited_edited.mp4");
+    std::fs::copy(&first, &second).unwrap();
+    let next = OutputStamp::new(&first, "even shorter", Some(&stamp));
+    reedit::write_stamp(&second, &next, &dir).await.unwrap();
+    let read = reedit::read_stamp(&second).await.unwrap();
+    assert_eq!(read.generation, 2);
+    assert_eq!(read.source, source);
+
+    let found = reedit::detect(&second, -16.0).await.unwrap();
+    assert!(found.skip_subtitle_burn);
+    assert_eq!(found.original_source, Some(source.clone()));
+    // A 440 Hz tone is nowhere near -16 LUFS
+    assert!(!found.skip_normalization);
+
+    // Once the source is gone it is no longer suggested
+    let moved: PathBuf = dir.join("moved.mp4");
+    std::fs::rename(&source, &moved).unwrap();
+    assert_eq!(
+        reedit::detect(&second, -16.0)
+            .await
+            .unwrap()
+            .original_source,
+        None
+    );
+
+    std::env::set_var("SYNOID_FORCE_REPROCESS", "1");
+    assert_eq!(reedit::detect(&second, -16.0).await, None);
+    std::env::remove_var("SYNOID_FORCE_REPROCESS");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
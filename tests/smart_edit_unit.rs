@@ -11,6 +11,8 @@ async fn test_smart_edit_fallback() {
             duration: 10.0,
             score: 0.5,
             vision_tags: vec![],
+            breakdown: None,
+            audio_mix: None,
         }, // Boring/Silence
     ];
     let intent = EditIntent {
@@ -20,7 +22,7 @@ async fn test_smart_edit_fallback() {
         keep_speech: false,
         ruthless: true,
         density: synoid_core::agent::smart_editor::EditDensity::Highlights,
-        custom_keywords: vec![],
+        keywords: vec![],
         target_duration: None,
         censor_profanity: false,
         profanity_replacement: None,
@@ -28,6 +30,16 @@ async fn test_smart_edit_fallback() {
         use_remotion: false,
         remotion_template: None,
         enable_subtitles: true,
+        edge_padding_secs: 0.3,
+        audio_reactive: None,
+        duck_music: None,
+        exclude_ranges: Vec::new(),
+        exclude_phrases: Vec::new(),
+        intro: None,
+        outro: None,
+        intro_crossfade: None,
+        keep_audio: Vec::new(),
+        cut_audio: Vec::new(),
     };
 
     let config = EditingStrategy::default();
@@ -44,6 +56,8 @@ async fn test_smart_edit_fallback() {
         duration: 40.0,
         score: 0.5,
         vision_tags: vec![],
+        breakdown: None,
+        audio_mix: None,
     }];
     score_scenes(&mut scenes, &intent, None, &config, 40.0);
 
@@ -63,6 +77,8 @@ async fn test_speech_protection_ruthless() {
         duration: 5.0,
         score: 0.5,
         vision_tags: vec![],
+        breakdown: None,
+        audio_mix: None,
     }];
 
     let transcript = vec![TranscriptSegment {
@@ -78,7 +94,7 @@ async fn test_speech_protection_ruthless() {
         keep_speech: true,
         ruthless: true,
         density: synoid_core::agent::smart_editor::EditDensity::Highlights,
-        custom_keywords: vec![],
+        keywords: vec![],
         target_duration: None,
         censor_profanity: false,
         profanity_replacement: None,
@@ -86,6 +102,16 @@ async fn test_speech_protection_ruthless() {
         use_remotion: false,
         remotion_template: None,
         enable_subtitles: true,
+        edge_padding_secs: 0.3,
+        audio_reactive: None,
+        duck_music: None,
+        exclude_ranges: Vec::new(),
+        exclude_phrases: Vec::new(),
+        intro: None,
+        outro: None,
+        intro_crossfade: None,
+        keep_audio: Vec::new(),
+        cut_audio: Vec::new(),
     };
 
     let config = EditingStrategy::default();
@@ -110,6 +136,8 @@ async fn test_full_density_preserves_long_scenes() {
         duration: 60.0,
         score: 0.5,
         vision_tags: vec![],
+        breakdown: None,
+        audio_mix: None,
     }];
 
     let intent = EditIntent::from_text("Edit the whole video making it 60 minutes long");